@@ -0,0 +1,34 @@
+#!/usr/bin/env node
+
+import * as fs from "fs/promises";
+import type { EvalReport } from "./model.js";
+import { compareReports, renderMarkdown } from "./compare.js";
+import { parseCompareArgs } from "./parse.js";
+
+/**
+ * Compares two eval reports and prints the comparison, exiting with 1 when
+ * the current run regressed
+ */
+async function main() {
+  const args = await parseCompareArgs();
+
+  const readReport = async (file: string): Promise<EvalReport> =>
+    JSON.parse(await fs.readFile(file, "utf-8"));
+  const comparison = compareReports(
+    await readReport(args.baseline),
+    await readReport(args.current),
+    args.thresholds,
+  );
+
+  process.stdout.write(
+    args.format === "json"
+      ? JSON.stringify(comparison, null, 2) + "\n"
+      : renderMarkdown(comparison),
+  );
+  process.exit(comparison.regressed ? 1 : 0);
+}
+
+main().catch((error) => {
+  console.error(error instanceof Error ? error.message : error);
+  process.exit(2);
+});
@@ -0,0 +1,203 @@
+import { TaskStatus, type CaseReport, type EvalReport } from "./model.js";
+
+export type Thresholds = {
+  /** Largest allowed drop of the pass rate, as a fraction (default: 0) */
+  passRate?: number;
+  /**
+   * Largest allowed drop of a metric's mean, per metric name; `default`
+   * applies to the others (default: 0.05). Metrics are higher-is-better.
+   */
+  metrics?: Record<string, number>;
+};
+
+export type CaseChange = {
+  /** Case parameters identifying it across runs */
+  case: Record<string, string>;
+  baseline?: TaskStatus;
+  current?: TaskStatus;
+};
+
+export type MetricChange = {
+  name: string;
+  baseline?: number;
+  current?: number;
+  delta?: number;
+  regressed: boolean;
+};
+
+export type Comparison = {
+  name: string;
+  baseline: { startedAt: string; passRate: number };
+  current: { startedAt: string; passRate: number };
+  passRateRegressed: boolean;
+  /** Cases that passed in the baseline and no longer do */
+  regressions: CaseChange[];
+  /** Cases that pass now and did not in the baseline */
+  fixes: CaseChange[];
+  /** Cases only in one of the runs */
+  added: CaseChange[];
+  removed: CaseChange[];
+  metrics: MetricChange[];
+  regressed: boolean;
+};
+
+/**
+ * Parameters of a case without its temp directory, which differs per run
+ */
+function caseParameters(report: CaseReport): Record<string, string> {
+  const { dir: _dir, ...parameters } = report.context;
+  return parameters;
+}
+
+function caseKey(parameters: Record<string, string>): string {
+  return JSON.stringify(Object.entries(parameters).sort());
+}
+
+function passRate(report: EvalReport): number {
+  return report.summary.total === 0
+    ? 0
+    : report.summary.passed / report.summary.total;
+}
+
+/**
+ * Compares `current` against `baseline`, matching cases by their parameters,
+ * and flags drops beyond `thresholds` as regressions
+ */
+export function compareReports(
+  baseline: EvalReport,
+  current: EvalReport,
+  thresholds: Thresholds = {},
+): Comparison {
+  const baselineCases = new Map(
+    baseline.cases.map((c) => [caseKey(caseParameters(c)), c]),
+  );
+  const currentCases = new Map(
+    current.cases.map((c) => [caseKey(caseParameters(c)), c]),
+  );
+
+  const regressions: CaseChange[] = [];
+  const fixes: CaseChange[] = [];
+  const added: CaseChange[] = [];
+  const removed: CaseChange[] = [];
+
+  for (const [key, now] of currentCases) {
+    const before = baselineCases.get(key);
+    const change = {
+      case: caseParameters(now),
+      current: now.status,
+      ...(before ? { baseline: before.status } : {}),
+    };
+    if (!before) {
+      added.push(change);
+    } else if (before.status === TaskStatus.Passed && now.status !== TaskStatus.Passed) {
+      regressions.push(change);
+    } else if (before.status !== TaskStatus.Passed && now.status === TaskStatus.Passed) {
+      fixes.push(change);
+    }
+  }
+  for (const [key, before] of baselineCases) {
+    if (!currentCases.has(key)) {
+      removed.push({ case: caseParameters(before), baseline: before.status });
+    }
+  }
+
+  const metricNames = [
+    ...new Set([
+      ...Object.keys(baseline.summary.metrics),
+      ...Object.keys(current.summary.metrics),
+    ]),
+  ].sort();
+  const metrics = metricNames.map((name): MetricChange => {
+    const before = baseline.summary.metrics[name]?.mean;
+    const now = current.summary.metrics[name]?.mean;
+    const threshold =
+      thresholds.metrics?.[name] ?? thresholds.metrics?.default ?? 0.05;
+    const delta =
+      before !== undefined && now !== undefined ? now - before : undefined;
+    return {
+      name,
+      ...(before !== undefined ? { baseline: before } : {}),
+      ...(now !== undefined ? { current: now } : {}),
+      ...(delta !== undefined ? { delta } : {}),
+      regressed: delta !== undefined && delta < -threshold,
+    };
+  });
+
+  const baselineRate = passRate(baseline);
+  const currentRate = passRate(current);
+  const passRateRegressed =
+    baselineRate - currentRate > (thresholds.passRate ?? 0);
+
+  return {
+    name: current.name,
+    baseline: { startedAt: baseline.startedAt, passRate: baselineRate },
+    current: { startedAt: current.startedAt, passRate: currentRate },
+    passRateRegressed,
+    regressions,
+    fixes,
+    added,
+    removed,
+    metrics,
+    regressed:
+      passRateRegressed ||
+      regressions.length > 0 ||
+      metrics.some((m) => m.regressed),
+  };
+}
+
+function percent(value: number): string {
+  return `${(value * 100).toFixed(1)}%`;
+}
+
+function number(value: number | undefined): string {
+  return value === undefined ? "–" : value.toFixed(3);
+}
+
+function signed(value: number | undefined): string {
+  if (value === undefined) return "–";
+  return `${value >= 0 ? "+" : ""}${value.toFixed(3)}`;
+}
+
+function describeCase(change: CaseChange): string {
+  const parameters = Object.entries(change.case)
+    .map(([key, value]) => `${key}=${value}`)
+    .join(", ");
+  return `\`${parameters}\`: ${change.baseline ?? "–"} → ${change.current ?? "–"}`;
+}
+
+/**
+ * Renders a comparison as markdown, e.g. for a pull request comment
+ */
+export function renderMarkdown(comparison: Comparison): string {
+  const lines: string[] = [];
+  const verdict = comparison.regressed ? "❌ Regressed" : "✅ No regressions";
+  lines.push(`## Eval \`${comparison.name}\`: ${verdict}`, "");
+
+  lines.push("| | Baseline | Current | Change |", "|---|---|---|---|");
+  const rateDelta = comparison.current.passRate - comparison.baseline.passRate;
+  lines.push(
+    `| Pass rate${comparison.passRateRegressed ? " ❌" : ""} | ${percent(comparison.baseline.passRate)} | ${percent(comparison.current.passRate)} | ${rateDelta >= 0 ? "+" : ""}${percent(rateDelta)} |`,
+  );
+  for (const metric of comparison.metrics) {
+    lines.push(
+      `| ${metric.name}${metric.regressed ? " ❌" : ""} | ${number(metric.baseline)} | ${number(metric.current)} | ${signed(metric.delta)} |`,
+    );
+  }
+
+  const section = (title: string, changes: CaseChange[]) => {
+    if (changes.length > 0) {
+      lines.push("", `### ${title} (${changes.length})`, "");
+      lines.push(...changes.map((change) => `- ${describeCase(change)}`));
+    }
+  };
+  section("Regressions", comparison.regressions);
+  section("Fixes", comparison.fixes);
+  section("New cases", comparison.added);
+  section("Removed cases", comparison.removed);
+
+  lines.push(
+    "",
+    `<sub>Baseline from ${comparison.baseline.startedAt}, current run from ${comparison.current.startedAt}</sub>`,
+  );
+  return lines.join("\n") + "\n";
+}
@@ -0,0 +1,302 @@
+import { spawn } from "child_process";
+import * as fs from "fs/promises";
+import * as path from "path";
+import Handlebars from "handlebars";
+import { vertex } from "@ai-sdk/google-vertex";
+import { generateObject, type LanguageModel } from "ai";
+import { z } from "zod";
+import type { CaseRun, Grader, GradeResult, Validation } from "./model.js";
+import { escapeRegex } from "./utils.js";
+import { requestsOf, toolCalls } from "./metrics.js";
+
+// Register Handlebars helper for escaping regex
+Handlebars.registerHelper("escapeRegex", escapeRegex);
+
+/**
+ * Renders a template with the parameters of a case
+ */
+function render(template: string, context: Record<string, string>): string {
+  return Handlebars.compile(template, { strict: true })(context);
+}
+
+/**
+ * Passes when the output matches `regex`, rendered with the case parameters
+ */
+export function regexGrader(name: string, regex: string): Grader {
+  return {
+    name,
+    kind: "deterministic",
+    grade: async (run) => {
+      const pattern = render(regex, run.context);
+      const passed = new RegExp(pattern).test(run.output);
+      return {
+        name,
+        passed,
+        message: passed ? `Matched: ${pattern}` : `Did not match: ${pattern}`,
+      };
+    },
+  };
+}
+
+/**
+ * Passes when `command`, rendered with the case parameters and given the
+ * output on stdin, exits with `exitCode`
+ */
+export function shellGrader(
+  name: string,
+  command: string,
+  exitCode: number = 0,
+): Grader {
+  return {
+    name,
+    kind: "deterministic",
+    grade: async (run) => {
+      try {
+        const code = await runWithStdin(render(command, run.context), run.output);
+        const passed = code === exitCode;
+        return {
+          name,
+          passed,
+          message: passed
+            ? `Command succeeded with exit code ${code}`
+            : `Expected exit code ${exitCode}, got ${code}`,
+        };
+      } catch (error: any) {
+        return {
+          name,
+          passed: false,
+          message: `Command failed: ${error.message}`,
+        };
+      }
+    },
+  };
+}
+
+/**
+ * Runs a shell command with `input` piped to stdin and resolves its exit code
+ */
+function runWithStdin(command: string, input: string): Promise<number> {
+  return new Promise<number>((resolve, reject) => {
+    const child = spawn(command, {
+      shell: true,
+      stdio: ["pipe", "pipe", "pipe"],
+    });
+
+    let processExited = false;
+
+    // Handle stdin errors (EPIPE when process exits early)
+    child.stdin.on("error", (err: NodeJS.ErrnoException) => {
+      // Ignore EPIPE errors - they happen when the child process
+      // exits before we finish writing, which is expected behavior
+      if (err.code !== "EPIPE") {
+        reject(err);
+      }
+    });
+
+    child.on("close", (code) => {
+      processExited = true;
+      resolve(code ?? 0);
+    });
+
+    child.on("error", (err) => {
+      reject(err);
+    });
+
+    // Write input to stdin
+    // Use setImmediate to ensure event handlers are attached first
+    setImmediate(() => {
+      if (!processExited && child.stdin.writable) {
+        child.stdin.write(input, (err?: Error | null) => {
+          if (err && (err as NodeJS.ErrnoException).code !== "EPIPE") {
+            // Only reject on non-EPIPE errors
+            reject(err);
+          } else {
+            child.stdin.end();
+          }
+        });
+      } else {
+        // Process already exited or stdin not writable
+        child.stdin.end();
+      }
+    });
+  });
+}
+
+/**
+ * Passes when the agent called exactly the `expected` tools, in order, as
+ * recorded in `{{dir}}/context.json` unless `file` is given
+ */
+export function toolCallSequence(
+  name: string,
+  expected: Array<string>,
+  file?: string,
+): Grader {
+  return {
+    name,
+    kind: "deterministic",
+    grade: async (run) => {
+      const actual = toolCalls(await requestsOf(run, file));
+      const passed =
+        actual.length === expected.length &&
+        actual.every((tool, i) => tool === expected[i]);
+      return {
+        name,
+        passed,
+        message: passed
+          ? `Called ${expected.join(" → ")}`
+          : `Expected ${expected.join(" → ") || "no tool calls"}, got ${actual.join(" → ") || "no tool calls"}`,
+      };
+    },
+  };
+}
+
+/**
+ * Passes when every file, relative to the work directory, has exactly the
+ * expected content; both paths and contents are rendered with the case
+ * parameters
+ */
+export function fileContents(
+  name: string,
+  files: Record<string, string>,
+): Grader {
+  return {
+    name,
+    kind: "deterministic",
+    grade: async (run) => {
+      const mismatched: string[] = [];
+      for (const [file, expected] of Object.entries(files)) {
+        const filePath = path.resolve(run.workDir, render(file, run.context));
+        const actual = await fs.readFile(filePath, "utf-8").catch(() => undefined);
+        if (actual !== render(expected, run.context)) {
+          mismatched.push(actual === undefined ? `${file} (missing)` : file);
+        }
+      }
+      return {
+        name,
+        passed: mismatched.length === 0,
+        message:
+          mismatched.length === 0
+            ? `Files match: ${Object.keys(files).join(", ")}`
+            : `Files differ: ${mismatched.join(", ")}`,
+      };
+    },
+  };
+}
+
+const JudgementSchema = z.object({
+  score: z
+    .number()
+    .min(0)
+    .max(10)
+    .describe("How well the output meets the criteria (0-10)"),
+  feedback: z
+    .string()
+    .describe("Brief explanation of the score (1-2 sentences)"),
+});
+
+export type LlmJudgeOptions = {
+  name: string;
+  model: LanguageModel;
+  /** Criteria template, rendered with the case parameters */
+  criteria: string;
+  /** Minimum score, out of 10, for the case to pass */
+  threshold?: number;
+};
+
+/**
+ * Asks `model` to score the output against `criteria`
+ */
+export function llmJudge({
+  name,
+  model,
+  criteria,
+  threshold = 7,
+}: LlmJudgeOptions): Grader {
+  return {
+    name,
+    kind: "llm",
+    grade: async (run) => {
+      try {
+        const { object } = await generateObject({
+          model,
+          schema: JudgementSchema,
+          prompt: `You are an expert evaluator of an AI coding assistant. Score how well the output below meets the criteria.
+
+## Criteria
+${render(criteria, run.context)}
+
+## Output
+${run.output}`,
+        });
+        return {
+          name,
+          passed: object.score >= threshold,
+          score: object.score,
+          message: `Scored ${object.score}/10: ${object.feedback}`,
+        };
+      } catch (error: any) {
+        return {
+          name,
+          passed: false,
+          message: `Judge failed: ${error.message}`,
+        };
+      }
+    },
+  };
+}
+
+/**
+ * Converts the validations of a task file into graders
+ */
+export function gradersFromValidations(
+  validations: Array<Validation>,
+): Array<Grader> {
+  return validations.map((validation) => {
+    switch (validation.type) {
+      case "regex":
+        return regexGrader(validation.name, validation.regex);
+      case "shell":
+        return shellGrader(
+          validation.name,
+          validation.command,
+          validation.exit_code ?? 0,
+        );
+      case "tool_calls":
+        return toolCallSequence(validation.name, validation.expected);
+      case "files":
+        return fileContents(validation.name, validation.files);
+      case "llm":
+        return llmJudge({
+          name: validation.name,
+          model: vertex(validation.evaluator),
+          criteria: Object.entries(validation.criteria)
+            .map(([key, value]) => `- ${key}: ${value}`)
+            .join("\n"),
+        });
+    }
+  });
+}
+
+/**
+ * Runs `graders` on a case, skipping them all when it produced no output
+ */
+export async function grade(
+  run: CaseRun,
+  graders: Array<Grader>,
+): Promise<GradeResult[]> {
+  if (!run.output) {
+    return [];
+  }
+  const results: GradeResult[] = [];
+  for (const grader of graders) {
+    results.push(await grader.grade(run));
+  }
+  return results;
+}
+
+/**
+ * Checks if all grades passed
+ */
+export function allPassed(results: GradeResult[]): boolean {
+  return results.every((result) => result.passed);
+}
@@ -0,0 +1,383 @@
+import * as fs from "fs/promises";
+import * as path from "path";
+import { exec } from "child_process";
+import { promisify } from "util";
+import pLimit from "p-limit";
+import Handlebars from "handlebars";
+import {
+  TaskStatus,
+  type CaseReport,
+  type CaseRun,
+  type EvalDefinition,
+  type EvalReport,
+  type GradeResult,
+  type Logger,
+  type MetricSummary,
+  type Source,
+  type TestCase,
+} from "./model.js";
+import {
+  getContextsFromSources,
+  generateCommand,
+} from "./command-generator.js";
+import { executeTask } from "./task-executor.js";
+import { allPassed, grade } from "./graders.js";
+import { createTempDir, parseCsvAsync } from "./utils.js";
+
+const execAsync = promisify(exec);
+
+export type RunOptions = {
+  logger: Logger;
+};
+
+/**
+ * Loads the test cases of `sources` as the cross product of their rows. CSV
+ * paths are resolved against `baseDir`.
+ */
+export async function loadCases(
+  sources: Array<Source>,
+  baseDir: string,
+): Promise<Array<TestCase>> {
+  const sourcesData: Record<string, string>[][] = [];
+
+  for (const source of sources) {
+    if ("csv" in source) {
+      const csvPath = path.join(baseDir, source.csv);
+      const csvContent = await fs.readFile(csvPath, "utf-8").catch(() => {
+        throw new Error(`CSV file not found: ${csvPath}`);
+      });
+      const csvData = await parseCsvAsync(csvContent, {
+        columns: true,
+        skip_empty_lines: true,
+      });
+      sourcesData.push(csvData);
+    } else if ("cmd" in source) {
+      throw new Error("cmd source type not yet implemented");
+    } else if ("value" in source) {
+      sourcesData.push(source.value);
+    }
+  }
+
+  if (sourcesData.length === 0) {
+    throw new Error("No sources configured");
+  }
+
+  return getContextsFromSources(sourcesData);
+}
+
+/**
+ * Runs every case of `definition`, grades and measures it, and returns the
+ * report
+ */
+export async function runEval(
+  definition: EvalDefinition,
+  { logger }: RunOptions,
+): Promise<EvalReport> {
+  const startedAt = new Date();
+  const graders = definition.graders ?? [];
+  const metrics = definition.metrics ?? [];
+  const deterministic = graders.filter((g) => g.kind === "deterministic");
+
+  // Create a temp directory for setup commands
+  const setupTmpDir = await createTempDir("forge-setup-");
+
+  // Execute before_run commands
+  for (const cmd of definition.before_run ?? []) {
+    logger.info(
+      { dir: setupTmpDir.name, command: cmd },
+      "Running setup command",
+    );
+    // Small delay to allow logger to flush before command output
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    try {
+      await execAsync(cmd, { cwd: setupTmpDir.name });
+    } catch {
+      throw new Error(`Setup command failed: ${cmd}`);
+    }
+  }
+
+  // Support both single command and multiple commands
+  const commands = Array.isArray(definition.run)
+    ? definition.run
+    : [definition.run];
+
+  // Filter out empty or non-string commands
+  const validCommands = commands.filter(
+    (cmd) => typeof cmd === "string" && cmd.trim().length > 0,
+  );
+  const commandLabel =
+    validCommands.length === 1
+      ? validCommands[0]!
+      : `${validCommands.length} commands`;
+
+  // Recorded responses are rendered per case so they can refer to its paths
+  const replay = definition.replay
+    ? Handlebars.compile(await fs.readFile(definition.replay, "utf-8"), {
+        strict: true,
+        noEscape: true,
+      })
+    : undefined;
+
+  // Default to 1 for sequential execution
+  const limit = pLimit(definition.parallelism ?? 1);
+
+  const runCase = async (row: TestCase, i: number): Promise<CaseReport> => {
+    // Create a unique temp directory for this case
+    const taskTmpDir = await createTempDir(`forge-task-${i + 1}-`);
+
+    // Create a 'task' subdirectory for running commands
+    const taskWorkDir = path.join(taskTmpDir.name, "task");
+    await fs.mkdir(taskWorkDir, { recursive: true });
+
+    const logFile = path.join(taskTmpDir.name, `task.log`);
+
+    // Context for command interpolation, graders and metrics
+    const context = { ...row, dir: taskTmpDir.name };
+
+    if (validCommands.length === 0) {
+      logger.warn(
+        { task_id: i + 1 },
+        "No valid commands found, skipping task",
+      );
+      return {
+        index: i + 1,
+        status: TaskStatus.Failed,
+        command: "No valid commands",
+        duration: 0,
+        context,
+        log: logFile,
+        grades: [],
+        metrics: {},
+      };
+    }
+
+    const run: CaseRun = {
+      index: i + 1,
+      context,
+      output: "",
+      workDir: taskWorkDir,
+      logFile,
+      duration: 0,
+    };
+
+    const env = { ...definition.env };
+    if (replay) {
+      const replayFile = path.join(taskTmpDir.name, "replay.jsonl");
+      await fs.writeFile(replayFile, replay(context));
+      env.FORGE_REPLAY_RESPONSES = replayFile;
+      // Tool-call graders read the requests recorded here
+      env.FORGE_DEBUG_REQUESTS ??= path.join(taskTmpDir.name, "context.json");
+    }
+
+    // Only deterministic graders are cheap enough to run on every write
+    const earlyExit =
+      definition.early_exit && deterministic.length > 0
+        ? async (output: string) =>
+            allPassed(await grade({ ...run, output }, deterministic))
+        : undefined;
+
+    let lastError: string | undefined;
+    let hasTimeout = false;
+
+    logger.info(
+      {
+        task_id: i + 1,
+        total_commands: validCommands.length,
+        log: logFile,
+        dir: taskTmpDir.name,
+        work_dir: taskWorkDir,
+        parameters: context,
+      },
+      "Launching task",
+    );
+
+    // Execute commands sequentially
+    for (let cmdIdx = 0; cmdIdx < validCommands.length; cmdIdx++) {
+      const command = generateCommand(validCommands[cmdIdx]!, context);
+
+      logger.info(
+        {
+          command,
+          task_id: i + 1,
+          command_id: cmdIdx + 1,
+          total_commands: validCommands.length,
+        },
+        "Executing command",
+      );
+
+      const executionResult = await executeTask(
+        command,
+        i + 1,
+        logFile,
+        taskWorkDir,
+        {
+          env,
+          ...(definition.timeout ? { timeout: definition.timeout } : {}),
+          ...(earlyExit ? { earlyExit } : {}),
+        },
+        cmdIdx > 0, // append if this is not the first command
+      );
+
+      run.duration += executionResult.duration;
+      run.output += executionResult.output ?? "";
+
+      // If execution failed or timed out, stop executing remaining commands
+      if (executionResult.error) {
+        lastError = executionResult.error;
+        hasTimeout = executionResult.isTimeout;
+
+        logger.warn(
+          {
+            task_id: executionResult.index,
+            command: executionResult.command,
+            command_id: cmdIdx + 1,
+            duration: executionResult.duration,
+            error: executionResult.error,
+            is_timeout: executionResult.isTimeout,
+          },
+          executionResult.isTimeout ? "Task timed out" : "Task failed",
+        );
+        break;
+      }
+    }
+
+    const grades = await grade(run, graders);
+    logGrades(logger, run, grades);
+    const measured = await measure(logger, run, definition);
+
+    const status = lastError
+      ? hasTimeout
+        ? TaskStatus.Timeout
+        : TaskStatus.Failed
+      : allPassed(grades)
+        ? TaskStatus.Passed
+        : TaskStatus.ValidationFailed;
+
+    return {
+      index: i + 1,
+      status,
+      command: commandLabel,
+      duration: run.duration,
+      context,
+      log: logFile,
+      grades,
+      metrics: measured,
+    };
+  };
+
+  const cases = await Promise.all(
+    definition.cases.map((row, i) => limit(() => runCase(row, i))),
+  );
+
+  return {
+    name: definition.name,
+    startedAt: startedAt.toISOString(),
+    duration: Date.now() - startedAt.getTime(),
+    cases,
+    summary: summarize(cases, metrics.map((m) => m.name)),
+  };
+}
+
+/**
+ * Logs the grades of a case
+ */
+function logGrades(logger: Logger, run: CaseRun, grades: GradeResult[]) {
+  if (grades.length === 0) {
+    return;
+  }
+  if (allPassed(grades)) {
+    logger.info(
+      {
+        task_id: run.index,
+        duration: run.duration,
+        log: run.logFile,
+        parameters: run.context,
+        passed: grades.map((r) => r.name),
+      },
+      "Validation passed",
+    );
+  } else {
+    logger.error(
+      {
+        task_id: run.index,
+        duration: run.duration,
+        log_file: run.logFile,
+        parameters: run.context,
+        failed: grades
+          .filter((r) => !r.passed)
+          .map((r) => ({ name: r.name, message: r.message })),
+        summary: `${grades.filter((r) => r.passed).length}/${grades.length} passed`,
+      },
+      "Validation Failed",
+    );
+  }
+}
+
+/**
+ * Measures a case with every metric of `definition`; a metric that throws is
+ * logged and left out
+ */
+async function measure(
+  logger: Logger,
+  run: CaseRun,
+  definition: EvalDefinition,
+): Promise<Record<string, number>> {
+  const measured: Record<string, number> = {};
+  for (const metric of definition.metrics ?? []) {
+    try {
+      const value = await metric.measure(run);
+      if (value !== undefined) {
+        measured[metric.name] = value;
+      }
+    } catch (error: any) {
+      logger.warn(
+        { task_id: run.index, metric: metric.name, error: error.message },
+        "Metric failed",
+      );
+    }
+  }
+  return measured;
+}
+
+/**
+ * Aggregates the statuses, grades and metrics of all cases
+ */
+export function summarize(
+  cases: CaseReport[],
+  metricNames: string[],
+): EvalReport["summary"] {
+  const count = (status: TaskStatus) =>
+    cases.filter((c) => c.status === status).length;
+  const grades = cases.flatMap((c) => c.grades);
+  const passedGrades = grades.filter((g) => g.passed).length;
+
+  const metrics: Record<string, MetricSummary> = {};
+  for (const name of metricNames) {
+    const values = cases.flatMap((c) => {
+      const value = c.metrics[name];
+      return value === undefined ? [] : [value];
+    });
+    if (values.length > 0) {
+      metrics[name] = {
+        mean: values.reduce((sum, v) => sum + v, 0) / values.length,
+        min: Math.min(...values),
+        max: Math.max(...values),
+        count: values.length,
+      };
+    }
+  }
+
+  return {
+    total: cases.length,
+    passed: count(TaskStatus.Passed),
+    validation_failed: count(TaskStatus.ValidationFailed),
+    timeout: count(TaskStatus.Timeout),
+    failed: count(TaskStatus.Failed),
+    grades: {
+      total: grades.length,
+      passed: passedGrades,
+      failed: grades.length - passedGrades,
+    },
+    metrics,
+  };
+}
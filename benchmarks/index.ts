@@ -0,0 +1,40 @@
+/**
+ * Library API of the evaluation harness: define test cases, graders and
+ * metrics in code, run them with `runEval` and get an `EvalReport`.
+ */
+export * from "./model.js";
+export { runEval, loadCases, summarize, type RunOptions } from "./harness.js";
+export {
+  regexGrader,
+  shellGrader,
+  toolCallSequence,
+  fileContents,
+  llmJudge,
+  gradersFromValidations,
+  type LlmJudgeOptions,
+} from "./graders.js";
+export {
+  toolCallCorrectness,
+  informationRetrieval,
+  tokenReduction,
+  metricsFromSpecs,
+  readRequests,
+  type RecordedRequest,
+} from "./metrics.js";
+export {
+  compareReports,
+  renderMarkdown,
+  type Comparison,
+  type CaseChange,
+  type MetricChange,
+  type Thresholds,
+} from "./compare.js";
+export {
+  loadSweTasks,
+  sweBenchEval,
+  testsPass,
+  passRates,
+  type SweTask,
+  type SweBenchOptions,
+  type PassRate,
+} from "./swe-bench.js";
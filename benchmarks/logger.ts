@@ -0,0 +1,33 @@
+import pino from "pino";
+
+/**
+ * Create logger instance
+ * - Human-readable CLI output by default
+ * - Set LOG_JSON=1 for machine-readable JSON output (for piping to jq, log aggregators, etc.)
+ */
+export function createLogger() {
+  return process.env.LOG_JSON === "1"
+    ? pino({
+        level: process.env.LOG_LEVEL || "info",
+        formatters: {
+          level: (label) => ({ level: label }),
+        },
+        timestamp: pino.stdTimeFunctions.isoTime,
+      })
+    : pino({
+        level: process.env.LOG_LEVEL || "info",
+        transport: {
+          target: "pino-pretty",
+          options: {
+            colorize: true,
+            translateTime: "HH:MM:ss",
+            ignore: "pid,hostname",
+            messageFormat: "{msg}",
+          },
+        },
+        formatters: {
+          level: (label) => ({ level: label }),
+        },
+        timestamp: pino.stdTimeFunctions.isoTime,
+      });
+}
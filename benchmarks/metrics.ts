@@ -0,0 +1,182 @@
+import * as fs from "fs/promises";
+import * as path from "path";
+import Handlebars from "handlebars";
+import type { CaseRun, Metric, MetricSpec } from "./model.js";
+
+/**
+ * A provider request recorded with `FORGE_DEBUG_REQUESTS`
+ */
+export type RecordedRequest = {
+  messages?: Array<{
+    role?: string;
+    content?: unknown;
+    tool_calls?: Array<{ function?: { name?: string; arguments?: string } }>;
+  }>;
+};
+
+/**
+ * Reads the requests recorded at `file`, either a single JSON request or one
+ * per line. Returns an empty list when the file does not exist.
+ */
+export async function readRequests(file: string): Promise<RecordedRequest[]> {
+  let content: string;
+  try {
+    content = await fs.readFile(file, "utf-8");
+  } catch {
+    return [];
+  }
+  try {
+    return [JSON.parse(content)];
+  } catch {
+    return content
+      .split("\n")
+      .filter((line) => line.trim().length > 0)
+      .map((line) => JSON.parse(line));
+  }
+}
+
+/**
+ * The requests recorded for a case, from `context.json` in its directory
+ * unless `file` is given
+ */
+export function requestsOf(run: CaseRun, file?: string): Promise<RecordedRequest[]> {
+  return readRequests(
+    file
+      ? Handlebars.compile(file, { strict: true })(run.context)
+      : path.join(run.context.dir ?? run.workDir, "context.json"),
+  );
+}
+
+/**
+ * The last request holds the whole conversation
+ */
+function lastMessages(requests: RecordedRequest[]) {
+  return requests[requests.length - 1]?.messages ?? [];
+}
+
+/**
+ * Names of the tools called in the recorded conversation, in call order
+ */
+export function toolCalls(requests: RecordedRequest[]): string[] {
+  return lastMessages(requests).flatMap(
+    (message) =>
+      message.tool_calls?.map((call) => call.function?.name ?? "") ?? [],
+  );
+}
+
+/**
+ * Splits a comma-separated list rendered with the case parameters
+ */
+function expectedList(expected: string | string[], run: CaseRun): string[] {
+  const values = Array.isArray(expected)
+    ? expected
+    : Handlebars.compile(expected, { strict: true })(run.context).split(",");
+  return values.map((value) => value.trim()).filter((value) => value.length > 0);
+}
+
+/**
+ * Fraction of `expected` found by `found`
+ */
+function recall(expected: string[], found: (value: string) => boolean) {
+  if (expected.length === 0) {
+    return undefined;
+  }
+  return expected.filter(found).length / expected.length;
+}
+
+/**
+ * Fraction of the expected tools the agent called
+ */
+export function toolCallCorrectness({
+  name = "tool_call_correctness",
+  expected,
+  file,
+}: {
+  name?: string;
+  /** Tool names, or a comma-separated template */
+  expected: string | string[];
+  /** Recorded requests, defaults to `{{dir}}/context.json` */
+  file?: string;
+}): Metric {
+  return {
+    name,
+    measure: async (run) => {
+      const called = new Set(toolCalls(await requestsOf(run, file)));
+      return recall(expectedList(expected, run), (tool) => called.has(tool));
+    },
+  };
+}
+
+/**
+ * Fraction of the expected strings, e.g. file paths or symbols, that the
+ * agent retrieved through its tools or mentioned in its output
+ */
+export function informationRetrieval({
+  name = "information_retrieval",
+  expected,
+  file,
+}: {
+  name?: string;
+  /** Expected strings, or a comma-separated template */
+  expected: string | string[];
+  /** Recorded requests, defaults to `{{dir}}/context.json` */
+  file?: string;
+}): Metric {
+  return {
+    name,
+    measure: async (run) => {
+      const retrieved = lastMessages(await requestsOf(run, file))
+        .filter((message) => message.role === "tool")
+        .map((message) => JSON.stringify(message.content ?? ""))
+        .concat(run.output)
+        .join("\n");
+      return recall(expectedList(expected, run), (value) =>
+        retrieved.includes(value),
+      );
+    },
+  };
+}
+
+/**
+ * Reduction of the context sent in the last request relative to `baseline`
+ * tokens, estimated at ~4 characters per token
+ */
+export function tokenReduction({
+  name = "token_reduction",
+  baseline,
+  file,
+}: {
+  name?: string;
+  baseline: number;
+  /** Recorded requests, defaults to `{{dir}}/context.json` */
+  file?: string;
+}): Metric {
+  return {
+    name,
+    measure: async (run) => {
+      const messages = lastMessages(await requestsOf(run, file));
+      if (messages.length === 0 || baseline <= 0) {
+        return undefined;
+      }
+      const tokens = Math.ceil(JSON.stringify(messages).length / 4);
+      return 1 - tokens / baseline;
+    },
+  };
+}
+
+/**
+ * Converts the metrics of a task file into metrics
+ */
+export function metricsFromSpecs(specs: Array<MetricSpec>): Array<Metric> {
+  return specs.map((spec) => {
+    const name = spec.name ?? spec.type;
+    switch (spec.type) {
+      case "tool_call_correctness":
+        return toolCallCorrectness({ name, expected: spec.expected });
+      case "information_retrieval":
+        return informationRetrieval({ name, expected: spec.expected });
+      case "token_reduction":
+        return tokenReduction({ name, baseline: spec.baseline });
+    }
+  });
+}
@@ -4,11 +4,16 @@ export type Task = {
   parallelism?: number;
   timeout?: number;
   early_exit?: boolean;
+  /** Recorded provider responses to replay, relative to the task file */
+  replay?: string;
+  /** Environment variables of the commands */
+  env?: Record<string, string>;
   validations?: Array<Validation>;
+  metrics?: Array<MetricSpec>;
   sources: Array<Source>;
 };
 
-export type Validation = 
+export type Validation =
   | {
       name: string;
       type: "regex";
@@ -25,6 +30,37 @@ export type Validation =
       type: "llm";
       evaluator: string;
       criteria: Record<string, any>;
+    }
+  | {
+      name: string;
+      type: "tool_calls";
+      expected: Array<string>;
+    }
+  | {
+      name: string;
+      type: "files";
+      files: Record<string, string>;
+    };
+
+/**
+ * Built-in metrics that can be declared in a task file. `expected` values are
+ * comma-separated and support template placeholders.
+ */
+export type MetricSpec =
+  | {
+      name?: string;
+      type: "tool_call_correctness";
+      expected: string;
+    }
+  | {
+      name?: string;
+      type: "information_retrieval";
+      expected: string;
+    }
+  | {
+      name?: string;
+      type: "token_reduction";
+      baseline: number;
     };
 
 export type Source = { csv: string } | { cmd: string } | { value: Record<string, string>[] };
@@ -36,3 +72,123 @@ export enum TaskStatus {
   Timeout = "timeout",
   Failed = "failed",
 }
+
+/**
+ * Minimal logger interface, satisfied by pino
+ */
+export type Logger = {
+  info: (data: any, message: string) => void;
+  warn: (data: any, message: string) => void;
+  error: (data: any, message: string) => void;
+};
+
+/**
+ * A single case of an evaluation: the parameters its commands, graders and
+ * metrics are rendered with
+ */
+export type TestCase = Record<string, string>;
+
+/**
+ * Everything produced by running the commands of one test case
+ */
+export type CaseRun = {
+  /** 1-based position of the case */
+  index: number;
+  /** Parameters of the case, including `dir`, the case's temp directory */
+  context: Record<string, string>;
+  /** Combined stdout and stderr of all commands */
+  output: string;
+  /** Directory the commands ran in */
+  workDir: string;
+  /** Path of the case's log file */
+  logFile: string;
+  duration: number;
+};
+
+export type GradeResult = {
+  name: string;
+  passed: boolean;
+  message: string;
+  /** Score given by graders that score, e.g. an LLM judge */
+  score?: number;
+};
+
+/**
+ * Decides whether a case passed. Deterministic graders are cheap enough to
+ * also be checked while the commands run, to support `early_exit`.
+ */
+export type Grader = {
+  name: string;
+  kind: "deterministic" | "llm";
+  grade: (run: CaseRun) => Promise<GradeResult>;
+};
+
+/**
+ * Measures a case with a number, aggregated across cases in the report.
+ * Returning `undefined` leaves the case out of the aggregate.
+ */
+export type Metric = {
+  name: string;
+  measure: (run: CaseRun) => Promise<number | undefined>;
+};
+
+/**
+ * An evaluation defined in code
+ */
+export type EvalDefinition = {
+  name: string;
+  cases: Array<TestCase>;
+  /** Command template(s) run for each case */
+  run: string | Array<string>;
+  /** Commands run once before the cases */
+  before_run?: Array<string>;
+  parallelism?: number;
+  /** Timeout in seconds per command */
+  timeout?: number;
+  /** Stop a case's commands as soon as all deterministic graders pass */
+  early_exit?: boolean;
+  /**
+   * Recorded provider responses that forge replays instead of calling the
+   * provider, see `FORGE_REPLAY_RESPONSES`. The file is rendered with the
+   * case parameters first, so recorded paths can use `{{dir}}`.
+   */
+  replay?: string;
+  /** Environment variables of the commands */
+  env?: Record<string, string>;
+  graders?: Array<Grader>;
+  metrics?: Array<Metric>;
+};
+
+export type CaseReport = {
+  index: number;
+  status: TaskStatus;
+  command: string;
+  duration: number;
+  context: Record<string, string>;
+  log: string;
+  grades: Array<GradeResult>;
+  metrics: Record<string, number>;
+};
+
+export type MetricSummary = {
+  mean: number;
+  min: number;
+  max: number;
+  count: number;
+};
+
+export type EvalReport = {
+  name: string;
+  startedAt: string;
+  duration: number;
+  cases: Array<CaseReport>;
+  summary: {
+    total: number;
+    passed: number;
+    validation_failed: number;
+    timeout: number;
+    failed: number;
+    grades: { total: number; passed: number; failed: number };
+    metrics: Record<string, MetricSummary>;
+  };
+};
@@ -1,13 +1,70 @@
 import yargs from "yargs";
 import { hideBin } from "yargs/helpers";
 import path from "path";
+import type { Thresholds } from "./compare.js";
 
 export type CliArgs = {
   evalName: string;
   evalDir: string;
   taskFile: string;
+  /** Report to compare the run against, `baseline.json` of the eval by default */
+  baseline: string;
+  /** Store the report of the run as the eval's baseline */
+  saveBaseline: boolean;
+  thresholds: Thresholds;
+};
+
+export type CompareArgs = {
+  baseline: string;
+  current: string;
+  format: "markdown" | "json";
+  thresholds: Thresholds;
 };
 
+/**
+ * Parses `--metric-threshold` values, either a number applying to all metrics
+ * or `name=number` for one metric
+ */
+function parseMetricThresholds(values: string[]): Record<string, number> {
+  const thresholds: Record<string, number> = {};
+  for (const value of values) {
+    const [name, threshold] = value.includes("=")
+      ? value.split("=", 2)
+      : ["default", value];
+    const parsed = Number(threshold);
+    if (!name || Number.isNaN(parsed)) {
+      throw new Error(`Invalid metric threshold: ${value}`);
+    }
+    thresholds[name] = parsed;
+  }
+  return thresholds;
+}
+
+const thresholdOptions = {
+  "pass-rate-threshold": {
+    describe: "Largest allowed drop of the pass rate (0-1)",
+    type: "number",
+    default: 0,
+  },
+  "metric-threshold": {
+    describe:
+      "Largest allowed drop of a metric's mean, as a number for all metrics or name=number",
+    type: "string",
+    array: true,
+    default: [] as string[],
+  },
+} as const;
+
+function thresholdsFrom(argv: {
+  "pass-rate-threshold": number;
+  "metric-threshold": (string | number)[];
+}): Thresholds {
+  return {
+    passRate: argv["pass-rate-threshold"],
+    metrics: parseMetricThresholds(argv["metric-threshold"].map(String)),
+  };
+}
+
 /**
  * Parses command line arguments and resolves paths
  */
@@ -19,6 +76,16 @@ export async function parseCliArgs(dirname: string): Promise<CliArgs> {
       describe: "Name of the evaluation to run",
       type: "string",
     })
+    .option("baseline", {
+      describe: "Baseline report to compare against (default: <eval>/baseline.json)",
+      type: "string",
+    })
+    .option("save-baseline", {
+      describe: "Store the report of this run as the baseline",
+      type: "boolean",
+      default: false,
+    })
+    .options(thresholdOptions)
     .help()
     .alias("h", "help")
     .parseAsync();
@@ -48,5 +115,122 @@ export async function parseCliArgs(dirname: string): Promise<CliArgs> {
     evalName,
     evalDir,
     taskFile,
+    baseline: argv.baseline
+      ? path.resolve(argv.baseline)
+      : path.join(evalDir, "baseline.json"),
+    saveBaseline: argv["save-baseline"],
+    thresholds: thresholdsFrom(argv),
+  };
+}
+
+/**
+ * Parses the arguments of the report comparison command
+ */
+export async function parseCompareArgs(): Promise<CompareArgs> {
+  const argv = await yargs(hideBin(process.argv))
+    .usage("Usage: $0 <baseline> <current> [options]")
+    .command("$0 <baseline> <current>", "Compare an eval report with a baseline")
+    .positional("baseline", {
+      describe: "Baseline report.json",
+      type: "string",
+      demandOption: true,
+    })
+    .positional("current", {
+      describe: "Current report.json",
+      type: "string",
+      demandOption: true,
+    })
+    .option("format", {
+      describe: "Output format",
+      choices: ["markdown", "json"] as const,
+      default: "markdown" as const,
+    })
+    .options(thresholdOptions)
+    .help()
+    .alias("h", "help")
+    .parseAsync();
+
+  return {
+    baseline: path.resolve(argv.baseline),
+    current: path.resolve(argv.current),
+    format: argv.format,
+    thresholds: thresholdsFrom(argv),
+  };
+}
+
+export type SweBenchArgs = {
+  dataset: string;
+  models: string[];
+  provider: string;
+  agentCommand?: string;
+  parallelism: number;
+  timeout: number;
+  testTimeout: number;
+  output: string;
+};
+
+/**
+ * Parses the arguments of the SWE-bench-style task runner
+ */
+export async function parseSweBenchArgs(dirname: string): Promise<SweBenchArgs> {
+  const argv = await yargs(hideBin(process.argv))
+    .usage("Usage: $0 <dataset> --model <model> [options]")
+    .command("$0 <dataset>", "Run the agent on a dataset of repository tasks")
+    .positional("dataset", {
+      describe: "JSON or JSON lines file of tasks",
+      type: "string",
+      demandOption: true,
+    })
+    .option("model", {
+      describe: "Model to benchmark, repeat to compare models",
+      type: "string",
+      array: true,
+      demandOption: true,
+    })
+    .option("provider", {
+      describe: "Provider of the models",
+      type: "string",
+      default: "open_router",
+    })
+    .option("agent-command", {
+      describe:
+        "Command running the agent, with {{issue_file}}, {{model}} and {{provider}} placeholders",
+      type: "string",
+    })
+    .option("parallelism", {
+      describe: "Number of tasks to run concurrently",
+      type: "number",
+      default: 1,
+    })
+    .option("timeout", {
+      describe: "Timeout in seconds of the agent per task",
+      type: "number",
+      default: 1800,
+    })
+    .option("test-timeout", {
+      describe: "Timeout in seconds of the tests per task",
+      type: "number",
+      default: 600,
+    })
+    .option("output", {
+      describe: "Directory the report is written to",
+      type: "string",
+    })
+    .help()
+    .alias("h", "help")
+    .parseAsync();
+
+  const timestamp = new Date().toISOString().replace(/[:.]/g, "-");
+  return {
+    dataset: path.resolve(argv.dataset),
+    models: argv.model.map(String),
+    provider: argv.provider,
+    ...(argv["agent-command"] ? { agentCommand: argv["agent-command"] } : {}),
+    parallelism: argv.parallelism,
+    timeout: argv.timeout,
+    testTimeout: argv["test-timeout"],
+    output: argv.output
+      ? path.resolve(argv.output)
+      : path.join(dirname, "swe-bench", "debug", timestamp),
   };
 }
@@ -0,0 +1,58 @@
+#!/usr/bin/env node
+
+import * as fs from "fs/promises";
+import * as path from "path";
+import { fileURLToPath } from "url";
+import { createLogger } from "./logger.js";
+import { runEval } from "./harness.js";
+import { parseSweBenchArgs } from "./parse.js";
+import { loadSweTasks, passRates, sweBenchEval } from "./swe-bench.js";
+
+// ESM compatibility for __dirname
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+const logger = createLogger();
+
+async function main() {
+  const args = await parseSweBenchArgs(__dirname);
+  const tasks = await loadSweTasks(args.dataset);
+  await fs.mkdir(args.output, { recursive: true });
+
+  logger.info(
+    { dataset: args.dataset, tasks: tasks.length, models: args.models },
+    "Running SWE-bench tasks",
+  );
+
+  const report = await runEval(
+    sweBenchEval(path.basename(args.dataset), tasks, {
+      models: args.models,
+      provider: args.provider,
+      ...(args.agentCommand ? { agent_command: args.agentCommand } : {}),
+      parallelism: args.parallelism,
+      timeout: args.timeout,
+      test_timeout: args.testTimeout,
+    }),
+    { logger },
+  );
+
+  const byModel = passRates(report, "model");
+  const byRepo = passRates(report, "repo");
+  const reportFile = path.join(args.output, "report.json");
+  await fs.writeFile(reportFile, JSON.stringify(report, null, 2));
+  await fs.writeFile(
+    path.join(args.output, "pass-rates.json"),
+    JSON.stringify({ by_model: byModel, by_repo: byRepo }, null, 2),
+  );
+
+  logger.info(
+    { by_model: byModel, by_repo: byRepo, report: reportFile },
+    "SWE-bench completed",
+  );
+  process.exit(0);
+}
+
+main().catch((error) => {
+  logger.error({ error: error.message }, "Fatal error");
+  process.exit(1);
+});
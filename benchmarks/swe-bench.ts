@@ -0,0 +1,213 @@
+import * as fs from "fs/promises";
+import { exec } from "child_process";
+import { promisify } from "util";
+import {
+  TaskStatus,
+  type EvalDefinition,
+  type EvalReport,
+  type Grader,
+  type TestCase,
+} from "./model.js";
+
+const execAsync = promisify(exec);
+
+/**
+ * A repository issue to resolve, validated by running its tests
+ */
+export type SweTask = {
+  id: string;
+  /** Git URL of the repository */
+  repo: string;
+  /** Commit the workspace is checked out at */
+  base_commit: string;
+  /** Problem statement given to the agent */
+  issue: string;
+  /** Command that passes once the issue is resolved */
+  test_command: string;
+  /** Patch adding the tests, applied after the agent finished */
+  test_patch?: string;
+  /** Commands preparing the workspace, e.g. installing dependencies */
+  setup?: Array<string>;
+};
+
+export type SweBenchOptions = {
+  /** Models to benchmark, each run on every task */
+  models: Array<string>;
+  /** Provider of the models */
+  provider: string;
+  /**
+   * Command running the agent in the workspace; `{{issue_file}}` holds the
+   * problem statement and `{{model}}`/`{{provider}}` the model under test
+   */
+  agent_command?: string;
+  parallelism?: number;
+  /** Timeout in seconds of the agent */
+  timeout?: number;
+  /** Timeout in seconds of the tests */
+  test_timeout?: number;
+};
+
+const DEFAULT_AGENT_COMMAND =
+  "FORGE_SESSION__PROVIDER_ID={{provider}} FORGE_SESSION__MODEL_ID={{model}} FORGE_DEBUG_REQUESTS='{{dir}}/context.json' forgee -p \"$(cat '{{issue_file}}')\"";
+
+/**
+ * Reads tasks from a JSON array or a JSON lines file
+ */
+export async function loadSweTasks(file: string): Promise<Array<SweTask>> {
+  const content = await fs.readFile(file, "utf-8");
+  const tasks: Array<SweTask> = content.trimStart().startsWith("[")
+    ? JSON.parse(content)
+    : content
+        .split("\n")
+        .filter((line) => line.trim().length > 0)
+        .map((line) => JSON.parse(line));
+
+  for (const task of tasks) {
+    const required = [
+      "id",
+      "repo",
+      "base_commit",
+      "issue",
+      "test_command",
+    ] as const;
+    for (const field of required) {
+      if (!task[field]) {
+        throw new Error(`Task ${task.id ?? "without id"} is missing ${field}`);
+      }
+    }
+  }
+  return tasks;
+}
+
+function base64(value: string): string {
+  return Buffer.from(value, "utf-8").toString("base64");
+}
+
+function decode(value: string | undefined): string {
+  return Buffer.from(value ?? "", "base64").toString("utf-8");
+}
+
+/**
+ * Case parameters of a task: text fields are base64 encoded so commands can
+ * carry them without quoting
+ */
+function toCase(task: SweTask, model: string, provider: string): TestCase {
+  return {
+    id: task.id,
+    repo: task.repo,
+    base_commit: task.base_commit,
+    model,
+    provider,
+    issue_b64: base64(task.issue),
+    test_command_b64: base64(task.test_command),
+    test_patch_b64: base64(task.test_patch ?? ""),
+    setup_b64: base64((task.setup ?? []).join("\n")),
+  };
+}
+
+/**
+ * Passes when the task's tests pass in the workspace the agent left, after
+ * applying the task's test patch
+ */
+export function testsPass(timeout: number = 600): Grader {
+  const name = "Tests pass";
+  return {
+    name,
+    kind: "deterministic",
+    grade: async (run) => {
+      const cwd = run.workDir;
+      const patch = decode(run.context.test_patch_b64);
+      try {
+        if (patch) {
+          const patchFile = `${run.context.dir}/test.patch`;
+          await fs.writeFile(patchFile, patch);
+          await execAsync(`git apply --whitespace=nowarn '${patchFile}'`, {
+            cwd,
+          });
+        }
+      } catch (error: any) {
+        return {
+          name,
+          passed: false,
+          message: `Failed to apply test patch: ${error.message}`,
+        };
+      }
+
+      const command = decode(run.context.test_command_b64);
+      try {
+        await execAsync(command, {
+          cwd,
+          timeout: timeout * 1000,
+          maxBuffer: 64 * 1024 * 1024,
+        });
+        return { name, passed: true, message: `Passed: ${command}` };
+      } catch (error: any) {
+        return {
+          name,
+          passed: false,
+          message: error.killed
+            ? `Timed out after ${timeout}s: ${command}`
+            : `Failed with exit code ${error.code}: ${command}`,
+        };
+      }
+    },
+  };
+}
+
+/**
+ * An evaluation running every model on every task: each case clones the
+ * repository into its own workspace, runs the setup and the agent, then the
+ * tests
+ */
+export function sweBenchEval(
+  name: string,
+  tasks: Array<SweTask>,
+  options: SweBenchOptions,
+): EvalDefinition {
+  return {
+    name,
+    cases: options.models.flatMap((model) =>
+      tasks.map((task) => toCase(task, model, options.provider)),
+    ),
+    // Triple braces keep base64 padding from being HTML escaped
+    run: [
+      "git clone --quiet '{{{repo}}}' . && git checkout --quiet '{{base_commit}}' && echo 'Checked out {{{repo}}}@{{base_commit}}'",
+      "echo '{{{issue_b64}}}' | base64 -d > '{{dir}}/issue.md'",
+      "echo '{{{setup_b64}}}' | base64 -d > '{{dir}}/setup.sh' && bash -e '{{dir}}/setup.sh'",
+      (options.agent_command ?? DEFAULT_AGENT_COMMAND).replaceAll(
+        "{{issue_file}}",
+        "{{dir}}/issue.md",
+      ),
+    ],
+    parallelism: options.parallelism ?? 1,
+    ...(options.timeout ? { timeout: options.timeout } : {}),
+    graders: [testsPass(options.test_timeout)],
+  };
+}
+
+export type PassRate = {
+  total: number;
+  resolved: number;
+  /** Fraction of the tasks whose tests pass */
+  pass_rate: number;
+};
+
+/**
+ * Pass rates of a report grouped by a case parameter, e.g. `model` or `repo`
+ */
+export function passRates(
+  report: EvalReport,
+  parameter: string,
+): Record<string, PassRate> {
+  const rates: Record<string, PassRate> = {};
+  for (const c of report.cases) {
+    const key = c.context[parameter] ?? "";
+    const rate = (rates[key] ??= { total: 0, resolved: 0, pass_rate: 0 });
+    rate.total += 1;
+    if (c.status === TaskStatus.Passed) {
+      rate.resolved += 1;
+    }
+    rate.pass_rate = rate.resolved / rate.total;
+  }
+  return rates;
+}
@@ -2,8 +2,6 @@ import * as fs from "fs";
 import * as path from "path";
 import { spawn } from "child_process";
 import stripAnsi from "strip-ansi";
-import type { Validation, Task } from "./model.js";
-import { runValidations, allValidationsPassed } from "./verification.js";
 import { formatTimestamp } from "./utils.js";
 
 export type TaskExecutionResult = {
@@ -16,6 +14,15 @@ export type TaskExecutionResult = {
   earlyExit?: boolean;
 };
 
+export type ExecutionOptions = {
+  /** Timeout in seconds */
+  timeout?: number;
+  /** Environment variables added to the command's */
+  env?: Record<string, string>;
+  /** Checked as output arrives; the command is stopped once it resolves true */
+  earlyExit?: (output: string) => Promise<boolean>;
+};
+
 /**
  * Executes a single task command and returns the result
  */
@@ -24,8 +31,7 @@ export async function executeTask(
   index: number,
   logFile: string,
   cwd: string,
-  task: Task,
-  context?: Record<string, string>,
+  options: ExecutionOptions,
   append: boolean = false,
 ): Promise<TaskExecutionResult> {
   const startTime = Date.now();
@@ -50,6 +56,7 @@ export async function executeTask(
       const child = spawn(command, {
         shell: true,
         cwd: cwd,
+        env: { ...process.env, ...options.env },
         stdio: ["ignore", "pipe", "pipe"],
       });
 
@@ -57,23 +64,14 @@ export async function executeTask(
       let stderr = "";
       let timeoutId: NodeJS.Timeout | null = null;
 
-      // Helper function to check validations after each write
-      const checkValidations = async () => {
+      // Helper function to check for an early exit after each write
+      const checkEarlyExit = async () => {
         if (exitedEarly || timedOut) return;
 
-        if (
-          task.early_exit &&
-          task.validations &&
-          task.validations.length > 0
-        ) {
+        if (options.earlyExit) {
           const currentOutput = stdout + stderr;
           if (currentOutput) {
-            const results = await runValidations(
-              currentOutput,
-              task.validations,
-              context,
-            );
-            if (allValidationsPassed(results)) {
+            if (await options.earlyExit(currentOutput)) {
               exitedEarly = true;
               if (timeoutId) clearTimeout(timeoutId);
               if (logStream.writable) {
@@ -92,19 +90,20 @@ export async function executeTask(
       };
 
       // Set up timeout if configured
-      if (task.timeout) {
+      const timeout = options.timeout;
+      if (timeout) {
         timeoutId = setTimeout(() => {
           timedOut = true;
           if (logStream.writable) {
             logStream.write(`\n${"=".repeat(80)}\n`);
-            logStream.write(`Timeout: ${task.timeout}s exceeded\n`);
+            logStream.write(`Timeout: ${timeout}s exceeded\n`);
             logStream.write(`Killing process...\n`);
             logStream.end();
           }
           child.kill("SIGKILL");
           // Resolve with captured output so far
           resolve(stdout + stderr);
-        }, task.timeout * 1000);
+        }, timeout * 1000);
       }
 
       // Stream stdout to both log file and capture for validation
@@ -114,7 +113,7 @@ export async function executeTask(
         if (logStream.writable) {
           logStream.write(stripAnsi(text));
         }
-        checkValidations();
+        checkEarlyExit();
       });
 
       // Stream stderr to both log file and capture for validation
@@ -124,7 +123,7 @@ export async function executeTask(
         if (logStream.writable) {
           logStream.write(stripAnsi(text));
         }
-        checkValidations();
+        checkEarlyExit();
       });
 
       child.on("close", (code) => {
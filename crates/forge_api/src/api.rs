@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use forge_app::dto::ToolsOverview;
@@ -16,6 +16,49 @@ pub trait API: Sync + Send {
     /// completion
     async fn discover(&self) -> Result<Vec<crate::File>>;
 
+    /// Detects the subprojects of a monorepo in the current working directory
+    async fn get_projects(&self) -> Result<Vec<Project>>;
+
+    /// Diffs two snapshots taken of a file before agent edits, numbered from
+    /// 0 for the oldest, without restoring either of them
+    async fn diff_snapshots(
+        &self,
+        file_path: PathBuf,
+        from_index: usize,
+        to_index: usize,
+    ) -> Result<UnifiedDiff>;
+
+    /// Restores every file the agent changed since `timestamp` to its content
+    /// at that time, undoing whole agent runs, and returns the restored files
+    async fn restore_snapshots_at(
+        &self,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<PathBuf>>;
+
+    /// Re-renders a stored conversation turn by turn, each turn holding the
+    /// prompt, the replies of the agent, its tool calls and their diffs
+    async fn replay_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Vec<Vec<ChatResponseContent>>>;
+
+    /// Lists the snapshots taken before the edits of a conversation, each
+    /// tagged with the tool call that made the edit, from the oldest to the
+    /// most recent
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Vec<Snapshot>>;
+
+    /// Writes a bundle of the snapshots and current content of the given
+    /// files to `output`, so that their edits can be reviewed and applied on
+    /// another machine. Returns the number of bundled snapshots.
+    async fn export_snapshot_bundle(&self, paths: Vec<PathBuf>, output: PathBuf) -> Result<usize>;
+
+    /// Applies a bundle written by [`API::export_snapshot_bundle`] to the
+    /// current working directory and returns the changed files
+    async fn import_snapshot_bundle(&self, bundle: PathBuf) -> Result<Vec<PathBuf>>;
+
     /// Provides information about the tools available in the current
     /// environment
     async fn get_tools(&self) -> anyhow::Result<ToolsOverview>;
@@ -33,6 +76,10 @@ pub trait API: Sync + Send {
     /// Provides a list of agents available in the current environment
     async fn get_agents(&self) -> Result<Vec<Agent>>;
 
+    /// Reloads agent definitions, picking up files created or edited since
+    /// they were loaded
+    async fn reload_agents(&self) -> Result<()>;
+
     /// Provides lightweight metadata for all agents without requiring a
     /// configured provider or model
     async fn get_agent_infos(&self) -> Result<Vec<AgentInfo>>;
@@ -55,6 +102,47 @@ pub trait API: Sync + Send {
         additional_context: Option<String>,
     ) -> Result<forge_app::CommitResult>;
 
+    /// Splits the pending changes into logical commits with AI-generated
+    /// commit messages, without staging or committing anything
+    async fn plan_commits(
+        &self,
+        max_diff_size: Option<usize>,
+        additional_context: Option<String>,
+    ) -> Result<forge_app::CommitPlan>;
+
+    /// Replaces the index with exactly the changes of the given groups of a
+    /// commit plan
+    async fn stage_commit_groups(
+        &self,
+        plan: &forge_app::CommitPlan,
+        groups: &[usize],
+    ) -> Result<()>;
+
+    /// Commits the staged changes with the provided commit message
+    async fn commit_staged(&self, message: String) -> Result<forge_app::CommitResult>;
+
+    /// Resolves the default branch of a git remote
+    async fn default_branch(&self, remote: &str) -> Result<String>;
+
+    /// Generates the title and description of a pull request from the commits
+    /// and diff of the current branch against `base`
+    async fn pull_request_draft(
+        &self,
+        remote: &str,
+        base: &str,
+        max_diff_size: Option<usize>,
+        additional_context: Option<String>,
+    ) -> Result<PullRequestDraft>;
+
+    /// Pushes the current branch to `remote` and opens a pull request from it
+    /// into `base`, returning its URL
+    async fn create_pull_request(
+        &self,
+        remote: &str,
+        base: &str,
+        draft: &PullRequestDraft,
+    ) -> Result<Url>;
+
     /// Returns the current environment
     fn environment(&self) -> Environment;
 
@@ -79,6 +167,17 @@ pub trait API: Sync + Send {
     /// Returns an error if the operation fails
     async fn delete_conversation(&self, conversation_id: &ConversationId) -> Result<()>;
 
+    /// Locks a conversation for this process so that other Forge processes
+    /// can only read it. Returns the process holding the conversation instead
+    /// when another one already does.
+    async fn lock_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Option<ConversationLockHolder>>;
+
+    /// Releases a lock taken by [`API::lock_conversation`]
+    async fn unlock_conversation(&self, conversation_id: &ConversationId) -> Result<()>;
+
     /// Renames a conversation by setting its title
     ///
     /// # Arguments
@@ -93,6 +192,33 @@ pub trait API: Sync + Send {
         title: String,
     ) -> Result<()>;
 
+    /// Pins or unpins a conversation. Pinned conversations are exempt from
+    /// retention pruning.
+    ///
+    /// # Arguments
+    /// * `conversation_id` - The ID of the conversation to update
+    /// * `pinned` - Whether the conversation should be pinned
+    ///
+    /// # Errors
+    /// Returns an error if the conversation is not found or the operation fails
+    async fn pin_conversation(&self, conversation_id: &ConversationId, pinned: bool) -> Result<()>;
+
+    /// Prunes conversations that violate the configured retention policy.
+    /// Returns an empty report when no retention policy is configured.
+    ///
+    /// # Arguments
+    /// * `keep` - Conversation that is never pruned, such as the active one
+    /// * `dry_run` - When true, reports what would be pruned without deleting
+    ///
+    /// # Errors
+    /// Returns an error if the configuration cannot be read or the operation
+    /// fails
+    async fn prune_conversations(
+        &self,
+        keep: Option<&ConversationId>,
+        dry_run: bool,
+    ) -> Result<PruneReport>;
+
     /// Compacts the context of the main agent for the given conversation and
     /// persists it. Returns metrics about the compaction (original vs.
     /// compacted tokens and messages).
@@ -156,6 +282,9 @@ pub trait API: Sync + Send {
     /// Gets the default model
     async fn get_default_model(&self) -> Option<ModelId>;
 
+    /// Returns a token counter with the tokenizer closest to `model`
+    fn token_counter(&self, model: &ModelId) -> Tokenizer;
+
     /// Gets the commit configuration (provider and model for commit message
     /// generation).
     async fn get_commit_config(&self) -> anyhow::Result<Option<forge_domain::ModelConfig>>;
@@ -167,6 +296,9 @@ pub trait API: Sync + Send {
     /// Gets the current reasoning effort setting.
     async fn get_reasoning_effort(&self) -> anyhow::Result<Option<Effort>>;
 
+    /// Gets the reasoning effort chosen for the current session, if any.
+    async fn get_session_reasoning_effort(&self) -> anyhow::Result<Option<Effort>>;
+
     /// Refresh MCP caches by fetching fresh data
     async fn reload_mcp(&self) -> Result<()>;
 
@@ -176,6 +308,47 @@ pub trait API: Sync + Send {
     /// List of available skills
     async fn get_skills(&self) -> Result<Vec<Skill>>;
 
+    /// Project instruction files (AGENTS.md, FORGE.md, CLAUDE.md) loaded
+    /// into the system prompt, in the order they are applied
+    async fn get_instruction_files(&self) -> Result<Vec<InstructionFile>>;
+
+    /// Installs a skill or agent pack from a git URL or registry name,
+    /// replacing any installed pack with the same name
+    async fn install_pack(&self, kind: PackKind, source: &PackSource) -> Result<InstalledPack>;
+
+    /// Removes an installed skill or agent pack
+    async fn remove_pack(&self, kind: PackKind, name: &str) -> Result<()>;
+
+    /// Lists the installed packs of the given kind
+    async fn list_packs(&self, kind: PackKind) -> Result<Vec<InstalledPack>>;
+
+    /// Verifies installed packs and checks their sources for updates
+    async fn check_packs(&self, kind: PackKind) -> Result<Vec<PackStatus>>;
+
+    /// Records the current content of a custom agent definition file as a new
+    /// version, returning `None` when it is unchanged since the latest one
+    async fn record_agent_version(
+        &self,
+        agent_id: &AgentId,
+        path: &Path,
+    ) -> Result<Option<AgentVersion>>;
+
+    /// Lists the recorded versions of a custom agent definition, oldest first
+    async fn agent_versions(&self, agent_id: &AgentId) -> Result<Vec<AgentVersion>>;
+
+    /// Reads the content of a recorded agent version
+    async fn agent_version_content(&self, agent_id: &AgentId, version: u32) -> Result<String>;
+
+    /// Restores a custom agent definition to a recorded version
+    async fn rollback_agent(&self, agent_id: &AgentId, version: u32) -> Result<AgentVersion>;
+
+    /// Gets where conversations and snapshots are currently stored
+    async fn get_storage_scope(&self) -> Result<StorageScope>;
+
+    /// Moves the workspace's conversations and snapshots to the given storage
+    /// scope and makes it the configured scope
+    async fn migrate_storage(&self, to: StorageScope) -> Result<StorageMigration>;
+
     /// Generate a shell command from natural language prompt
     async fn generate_command(&self, prompt: UserPrompt) -> Result<String>;
 
@@ -242,6 +415,12 @@ pub trait API: Sync + Send {
     /// credentials file doesn't exist.
     async fn migrate_env_credentials(&self) -> Result<Option<forge_domain::MigrationResult>>;
 
+    /// Describes the cache of provider responses to deterministic requests
+    async fn response_cache_info(&self) -> Result<forge_domain::ResponseCacheInfo>;
+
+    /// Removes every cached provider response and returns how many there were
+    async fn clear_response_cache(&self) -> Result<usize>;
+
     async fn generate_data(
         &self,
         data_parameters: DataGenerationParameters,
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -6,9 +6,10 @@ use anyhow::Result;
 use forge_app::dto::ToolsOverview;
 use forge_app::{
     AgentProviderResolver, AgentRegistry, AppConfigService, AuthService, CommandInfra,
-    CommandLoaderService, ConversationService, DataGenerationApp, EnvironmentInfra,
-    FileDiscoveryService, ForgeApp, GitApp, GrpcInfra, McpConfigManager, McpService,
-    ProviderAuthService, ProviderService, Services, User, UserUsage, Walker, WorkspaceService,
+    CommandLoaderService, ConversationService, CustomInstructionsService, DataGenerationApp,
+    EnvironmentInfra, FileDiscoveryService, FileSnapshotService, ForgeApp, GitApp, GrpcInfra,
+    McpConfigManager, McpService, ProviderAuthService, ProviderService, Services,
+    TokenCounterService, User, UserUsage, Walker, WorkspaceService, replay_turns,
 };
 use forge_config::ForgeConfig;
 use forge_domain::{Agent, ConsoleWriter, *};
@@ -67,6 +68,9 @@ impl<
     F: CommandInfra
         + EnvironmentInfra<Config = forge_config::ForgeConfig>
         + SkillRepository
+        + PackRepository
+        + AgentVersionRepository
+        + StorageRepository
         + GrpcInfra,
 > API for ForgeAPI<A, F>
 {
@@ -76,6 +80,68 @@ impl<
         self.services.collect_files(config).await
     }
 
+    async fn get_projects(&self) -> Result<Vec<Project>> {
+        self.services.list_projects().await
+    }
+
+    async fn diff_snapshots(
+        &self,
+        file_path: PathBuf,
+        from_index: usize,
+        to_index: usize,
+    ) -> Result<UnifiedDiff> {
+        self.services
+            .diff_snapshots(&file_path, from_index, to_index)
+            .await
+    }
+
+    async fn restore_snapshots_at(
+        &self,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<PathBuf>> {
+        let timestamp = timestamp
+            .timestamp_nanos_opt()
+            .and_then(|nanos| u64::try_from(nanos).ok())
+            .map(Duration::from_nanos)
+            .ok_or_else(|| anyhow::anyhow!("Timestamp {timestamp} is out of range"))?;
+        self.services.restore_all_at(timestamp).await
+    }
+
+    async fn replay_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Vec<Vec<ChatResponseContent>>> {
+        let conversation = self
+            .services
+            .find_conversation(conversation_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Conversation {conversation_id} not found"))?;
+        let context = conversation.context.unwrap_or_default();
+        Ok(replay_turns(&context, &self.services.get_environment()))
+    }
+
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Vec<Snapshot>> {
+        self.services
+            .list_snapshots_for_conversation(conversation_id)
+            .await
+    }
+
+    async fn export_snapshot_bundle(&self, paths: Vec<PathBuf>, output: PathBuf) -> Result<usize> {
+        let cwd = self.services.get_environment().cwd;
+        let paths = paths.iter().map(|path| cwd.join(path)).collect::<Vec<_>>();
+        self.services
+            .export_bundle(&paths, &cwd, &cwd.join(output))
+            .await
+    }
+
+    async fn import_snapshot_bundle(&self, bundle: PathBuf) -> Result<Vec<PathBuf>> {
+        let cwd = self.services.get_environment().cwd;
+        self.services.import_bundle(&cwd.join(bundle), &cwd).await
+    }
+
     async fn get_tools(&self) -> anyhow::Result<ToolsOverview> {
         self.app().list_tools().await
     }
@@ -96,6 +162,10 @@ impl<
         self.services.get_agent_infos().await
     }
 
+    async fn reload_agents(&self) -> Result<()> {
+        self.services.reload_agents().await
+    }
+
     async fn get_providers(&self) -> Result<Vec<AnyProvider>> {
         Ok(self.services.get_all_providers().await?)
     }
@@ -121,6 +191,61 @@ impl<
         }
     }
 
+    async fn plan_commits(
+        &self,
+        max_diff_size: Option<usize>,
+        additional_context: Option<String>,
+    ) -> Result<forge_app::CommitPlan> {
+        GitApp::new(self.services.clone())
+            .plan_commits(max_diff_size, additional_context)
+            .await
+    }
+
+    async fn stage_commit_groups(
+        &self,
+        plan: &forge_app::CommitPlan,
+        groups: &[usize],
+    ) -> Result<()> {
+        GitApp::new(self.services.clone())
+            .stage_commit_groups(plan, groups)
+            .await
+    }
+
+    async fn commit_staged(&self, message: String) -> Result<forge_app::CommitResult> {
+        GitApp::new(self.services.clone())
+            .commit(message, true)
+            .await
+    }
+
+    async fn default_branch(&self, remote: &str) -> Result<String> {
+        GitApp::new(self.services.clone())
+            .default_branch(remote)
+            .await
+    }
+
+    async fn pull_request_draft(
+        &self,
+        remote: &str,
+        base: &str,
+        max_diff_size: Option<usize>,
+        additional_context: Option<String>,
+    ) -> Result<PullRequestDraft> {
+        GitApp::new(self.services.clone())
+            .pull_request_draft(remote, base, max_diff_size, additional_context)
+            .await
+    }
+
+    async fn create_pull_request(
+        &self,
+        remote: &str,
+        base: &str,
+        draft: &PullRequestDraft,
+    ) -> Result<Url> {
+        GitApp::new(self.services.clone())
+            .create_pull_request(remote, base, draft)
+            .await
+    }
+
     async fn get_provider(&self, id: &ProviderId) -> Result<AnyProvider> {
         let providers = self.services.get_all_providers().await?;
         Ok(providers
@@ -186,6 +311,17 @@ impl<
         self.services.delete_conversation(conversation_id).await
     }
 
+    async fn lock_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> anyhow::Result<Option<ConversationLockHolder>> {
+        self.services.lock_conversation(conversation_id).await
+    }
+
+    async fn unlock_conversation(&self, conversation_id: &ConversationId) -> anyhow::Result<()> {
+        self.services.unlock_conversation(conversation_id).await
+    }
+
     async fn rename_conversation(
         &self,
         conversation_id: &ConversationId,
@@ -198,6 +334,31 @@ impl<
             .await
     }
 
+    async fn pin_conversation(
+        &self,
+        conversation_id: &ConversationId,
+        pinned: bool,
+    ) -> anyhow::Result<()> {
+        self.services
+            .modify_conversation(conversation_id, |conv| {
+                conv.metadata.pinned = pinned;
+            })
+            .await
+    }
+
+    async fn prune_conversations(
+        &self,
+        keep: Option<&ConversationId>,
+        dry_run: bool,
+    ) -> anyhow::Result<PruneReport> {
+        let Some(retention) = self.services.get_config()?.retention else {
+            return Ok(PruneReport { pruned: Vec::new(), dry_run });
+        };
+        self.services
+            .prune_conversations(&retention_policy(&retention), keep, dry_run)
+            .await
+    }
+
     async fn execute_shell_command(
         &self,
         command: &str,
@@ -259,6 +420,10 @@ impl<
         self.services.get_reasoning_effort().await
     }
 
+    async fn get_session_reasoning_effort(&self) -> anyhow::Result<Option<Effort>> {
+        self.services.get_session_reasoning_effort().await
+    }
+
     async fn user_info(&self) -> Result<Option<User>> {
         let provider = self.get_default_provider().await?;
         if let Some(api_key) = provider.api_key() {
@@ -301,6 +466,10 @@ impl<
         self.services.get_provider_model(None).await.ok()
     }
 
+    fn token_counter(&self, model: &ModelId) -> Tokenizer {
+        self.services.token_counter(model)
+    }
+
     async fn reload_mcp(&self) -> Result<()> {
         self.services.mcp_service().reload_mcp().await
     }
@@ -312,6 +481,71 @@ impl<
         self.infra.load_skills().await
     }
 
+    async fn get_instruction_files(&self) -> Result<Vec<InstructionFile>> {
+        Ok(self.services.get_instruction_files().await)
+    }
+
+    async fn install_pack(&self, kind: PackKind, source: &PackSource) -> Result<InstalledPack> {
+        let pack = self.infra.install_pack(kind, source).await?;
+        if kind == PackKind::Agent {
+            self.services.reload_agents().await?;
+        }
+        Ok(pack)
+    }
+
+    async fn remove_pack(&self, kind: PackKind, name: &str) -> Result<()> {
+        self.infra.remove_pack(kind, name).await?;
+        if kind == PackKind::Agent {
+            self.services.reload_agents().await?;
+        }
+        Ok(())
+    }
+
+    async fn list_packs(&self, kind: PackKind) -> Result<Vec<InstalledPack>> {
+        self.infra.list_packs(kind).await
+    }
+
+    async fn check_packs(&self, kind: PackKind) -> Result<Vec<PackStatus>> {
+        self.infra.check_packs(kind).await
+    }
+
+    async fn record_agent_version(
+        &self,
+        agent_id: &AgentId,
+        path: &Path,
+    ) -> Result<Option<AgentVersion>> {
+        self.infra.record_agent_version(agent_id, path).await
+    }
+
+    async fn agent_versions(&self, agent_id: &AgentId) -> Result<Vec<AgentVersion>> {
+        self.infra.list_agent_versions(agent_id).await
+    }
+
+    async fn agent_version_content(&self, agent_id: &AgentId, version: u32) -> Result<String> {
+        self.infra.read_agent_version(agent_id, version).await
+    }
+
+    async fn rollback_agent(&self, agent_id: &AgentId, version: u32) -> Result<AgentVersion> {
+        let restored = self.infra.rollback_agent(agent_id, version).await?;
+        self.services.reload_agents().await?;
+        Ok(restored)
+    }
+
+    async fn get_storage_scope(&self) -> Result<StorageScope> {
+        Ok(forge_app::utils::storage_scope(
+            self.services.get_config()?.storage,
+        ))
+    }
+
+    async fn migrate_storage(&self, to: StorageScope) -> Result<StorageMigration> {
+        let from = self.get_storage_scope().await?;
+        let migration = self.infra.migrate_storage(from, to).await?;
+        self.services
+            .update_config(vec![ConfigOperation::SetStorageScope(to)])
+            .await?;
+        Ok(migration)
+    }
+
     async fn generate_command(&self, prompt: UserPrompt) -> Result<String> {
         use forge_app::CommandGenerator;
         let generator = CommandGenerator::new(self.services.clone());
@@ -395,6 +629,14 @@ impl<
         Ok(self.services.migrate_env_credentials().await?)
     }
 
+    async fn response_cache_info(&self) -> Result<forge_domain::ResponseCacheInfo> {
+        self.services.response_cache_info().await
+    }
+
+    async fn clear_response_cache(&self) -> Result<usize> {
+        self.services.clear_response_cache().await
+    }
+
     async fn generate_data(
         &self,
         data_parameters: DataGenerationParameters,
@@ -432,6 +674,20 @@ impl<
     }
 }
 
+/// Converts the configured retention limits into a domain
+/// [`RetentionPolicy`].
+fn retention_policy(retention: &forge_config::Retention) -> RetentionPolicy {
+    RetentionPolicy {
+        max_age: retention
+            .max_age_days
+            .map(|days| chrono::Duration::days(days.into())),
+        max_count: retention.max_count,
+        max_size_bytes: retention
+            .max_size_mb
+            .map(|mb| mb.saturating_mul(1024 * 1024)),
+    }
+}
+
 impl<A: Send + Sync, F: ConsoleWriter> ConsoleWriter for ForgeAPI<A, F> {
     fn write(&self, buf: &[u8]) -> std::io::Result<usize> {
         self.infra.write(buf)
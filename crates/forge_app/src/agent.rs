@@ -134,11 +134,28 @@ impl AgentExt for Agent {
                 eviction_window: workflow_compact.eviction_window.value(),
                 max_tokens: workflow_compact.max_tokens,
                 token_threshold: workflow_compact.token_threshold,
+                max_tokens_ratio: workflow_compact.max_tokens_ratio.map(|ratio| ratio.value()),
+                token_threshold_ratio: workflow_compact
+                    .token_threshold_ratio
+                    .map(|ratio| ratio.value()),
                 turn_threshold: workflow_compact.turn_threshold,
                 message_threshold: workflow_compact.message_threshold,
                 model: workflow_compact.model.as_deref().map(ModelId::new),
                 on_turn_end: workflow_compact.on_turn_end,
             };
+
+            // Thresholds configured for the agent's model win over the workflow
+            // ratios, but not over the agent's own settings
+            if let Some(thresholds) = workflow_compact.models.get(agent.model.as_str()) {
+                if let Some(max_tokens) = thresholds.max_tokens {
+                    merged_compact.max_tokens = Some(max_tokens);
+                    merged_compact.max_tokens_ratio = None;
+                }
+                if let Some(token_threshold) = thresholds.token_threshold {
+                    merged_compact.token_threshold = Some(token_threshold);
+                    merged_compact.token_threshold_ratio = None;
+                }
+            }
             merged_compact.merge(agent.compact.clone());
             agent.compact = merged_compact;
         }
@@ -146,17 +163,8 @@ impl AgentExt for Agent {
         // Apply workflow reasoning configuration to agents.
         // Agent-level fields take priority; config fills in any unset fields.
         if let Some(ref config_reasoning) = config.reasoning {
-            use forge_config::Effort as ConfigEffort;
             let config_as_domain = ReasoningConfig {
-                effort: config_reasoning.effort.as_ref().map(|e| match e {
-                    ConfigEffort::None => Effort::None,
-                    ConfigEffort::Minimal => Effort::Minimal,
-                    ConfigEffort::Low => Effort::Low,
-                    ConfigEffort::Medium => Effort::Medium,
-                    ConfigEffort::High => Effort::High,
-                    ConfigEffort::XHigh => Effort::XHigh,
-                    ConfigEffort::Max => Effort::Max,
-                }),
+                effort: config_reasoning.effort.as_ref().map(to_domain_effort),
                 max_tokens: config_reasoning.max_tokens,
                 exclude: config_reasoning.exclude,
                 enabled: config_reasoning.enabled,
@@ -167,10 +175,33 @@ impl AgentExt for Agent {
             agent.reasoning = Some(merged);
         }
 
+        // The effort chosen at runtime with `/reasoning` wins over both the
+        // agent's and the config's
+        if let Some(ref effort) = config.session_reasoning_effort {
+            let mut reasoning = agent.reasoning.take().unwrap_or_default();
+            reasoning.enabled = Some(*effort != forge_config::Effort::None);
+            reasoning.effort = Some(to_domain_effort(effort));
+            agent.reasoning = Some(reasoning);
+        }
+
         agent
     }
 }
 
+/// Converts a configured effort level to its domain counterpart
+fn to_domain_effort(effort: &forge_config::Effort) -> Effort {
+    use forge_config::Effort as ConfigEffort;
+    match effort {
+        ConfigEffort::None => Effort::None,
+        ConfigEffort::Minimal => Effort::Minimal,
+        ConfigEffort::Low => Effort::Low,
+        ConfigEffort::Medium => Effort::Medium,
+        ConfigEffort::High => Effort::High,
+        ConfigEffort::XHigh => Effort::XHigh,
+        ConfigEffort::Max => Effort::Max,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use forge_config::{Effort as ConfigEffort, ReasoningConfig as ConfigReasoningConfig};
@@ -233,4 +264,59 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    /// The effort chosen for the session wins over the agent's own effort.
+    #[test]
+    fn test_session_reasoning_effort_takes_priority_over_agent() {
+        let config = ForgeConfig::default().session_reasoning_effort(ConfigEffort::Low);
+        let agent = fixture_agent().reasoning(ReasoningConfig::default().effort(Effort::High));
+
+        let actual = agent.apply_config(&config).reasoning;
+
+        let expected = Some(ReasoningConfig::default().effort(Effort::Low).enabled(true));
+
+        assert_eq!(actual, expected);
+    }
+
+    /// Thresholds configured for the agent's model replace the ratios so that
+    /// they survive resolution against the context window.
+    #[test]
+    fn test_compact_model_thresholds_replace_ratios() {
+        let mut compact = forge_config::Compact::new()
+            .token_threshold(100_000_usize)
+            .token_threshold_ratio(forge_config::Percentage::new(0.5).unwrap());
+        compact.models.insert(
+            "claude-3-5-sonnet-20241022".to_string(),
+            forge_config::CompactThresholds::default().token_threshold(150_000_usize),
+        );
+        let config = ForgeConfig::default().compact(compact);
+
+        let actual = fixture_agent()
+            .apply_config(&config)
+            .compact
+            .with_context_length(Some(200_000));
+
+        let expected = (Some(150_000), None);
+        assert_eq!(
+            (actual.token_threshold, actual.token_threshold_ratio),
+            expected
+        );
+    }
+
+    /// The agent's own thresholds win over those configured for its model.
+    #[test]
+    fn test_compact_agent_thresholds_take_priority_over_model_thresholds() {
+        let mut compact = forge_config::Compact::new();
+        compact.models.insert(
+            "claude-3-5-sonnet-20241022".to_string(),
+            forge_config::CompactThresholds::default().max_tokens(150_000_usize),
+        );
+        let config = ForgeConfig::default().compact(compact);
+        let agent = fixture_agent().compact(Compact::new().max_tokens(50_000_usize));
+
+        let actual = agent.apply_config(&config).compact.max_tokens;
+
+        let expected = Some(50_000);
+        assert_eq!(actual, expected);
+    }
 }
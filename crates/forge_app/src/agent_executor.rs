@@ -97,7 +97,7 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> AgentEx
             match message {
                 ChatResponse::TaskMessage { ref content } => match content {
                     ChatResponseContent::ToolInput(_) => ctx.send(message).await?,
-                    ChatResponseContent::ToolOutput(_) => {}
+                    ChatResponseContent::ToolOutput(_) | ChatResponseContent::FileDiff(_) => {}
                     ChatResponseContent::Markdown { text, partial } => {
                         if *partial {
                             output.push_str(text);
@@ -111,6 +111,7 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> AgentEx
                 ChatResponse::ToolCallStart { .. } => ctx.send(message).await?,
                 ChatResponse::ToolCallEnd(_) => ctx.send(message).await?,
                 ChatResponse::RetryAttempt { .. } => ctx.send(message).await?,
+                ChatResponse::PermissionRequest { .. } => ctx.send(message).await?,
                 ChatResponse::Interrupt { reason } => {
                     return Err(Error::AgentToolInterrupted(reason))
                         .context(format!(
@@ -23,7 +23,7 @@ use crate::tool_resolver::ToolResolver;
 use crate::user_prompt::UserPromptGenerator;
 use crate::{
     AgentExt, AgentProviderResolver, ConversationService, EnvironmentInfra, FileDiscoveryService,
-    ProviderService, Services,
+    GitStateService, IncludeRoot, ProviderService, Services, TokenCounterService,
 };
 
 /// Builds a [`TemplateConfig`] from a [`ForgeConfig`].
@@ -99,8 +99,28 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
             .refresh_provider_credential(agent_provider)
             .await?;
 
+        if let Some(response) = &agent_provider.response {
+            let unsupported = agent.unsupported_sampling_parameters(response);
+            if !unsupported.is_empty() {
+                anyhow::bail!(
+                    "Agent '{}' sets {}, which provider '{}' does not support",
+                    agent.id,
+                    unsupported.join(", "),
+                    agent_provider.id
+                );
+            }
+        }
+
         let models = services.models(agent_provider).await?;
 
+        // Size the compaction thresholds to the model's context window
+        let context_length = models
+            .iter()
+            .find(|model| model.id == agent.model)
+            .and_then(|model| model.context_length);
+        let compact = agent.compact.clone().with_context_length(context_length);
+        let agent = agent.compact(compact);
+
         // Get system and mcp tool definitions and resolve them for the agent
         let all_tool_definitions = self.tool_registry.list().await?;
         let tool_resolver = ToolResolver::new(all_tool_definitions);
@@ -110,6 +130,48 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
 
         let current_time = Local::now();
 
+        // Git state is only gathered for templates using it. Outside a repository
+        // its fields are empty rather than missing, which strict rendering
+        // would reject.
+        let uses_git = agent
+            .system_prompt
+            .as_ref()
+            .is_some_and(|template| template.references("git"))
+            || chat
+                .event
+                .value
+                .as_ref()
+                .and_then(|value| value.as_command())
+                .is_some_and(|command| command.template.references("git"));
+        let git = if uses_git {
+            let git = services.git_state().await.unwrap_or_else(|error| {
+                tracing::warn!(error = %error, "Failed to read the git state");
+                None
+            });
+            Some(git.unwrap_or_default())
+        } else {
+            None
+        };
+
+        // A repository map that fails to build only leaves the prompt without it
+        let repo_map = match forge_config.repo_map_tokens {
+            0 => None,
+            max_tokens => match services
+                .repo_map(max_tokens, &services.token_counter(&agent.model))
+                .await
+            {
+                Ok(repo_map) if !repo_map.entries.is_empty() => Some(repo_map.to_string()),
+                Ok(_) => None,
+                Err(error) => {
+                    tracing::warn!(error = %error, "Failed to build the repository map");
+                    None
+                }
+            },
+        };
+
+        // Templates may include files of the working directory other than secrets
+        let secret_files = SecretFiles::new(&forge_config.secret_files)?;
+
         // Insert system prompt
         let conversation =
             SystemPrompt::new(self.services.clone(), environment.clone(), agent.clone())
@@ -118,7 +180,10 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
                 .models(models.clone())
                 .files(files.clone())
                 .max_extensions(forge_config.max_extensions)
+                .repo_map(repo_map)
                 .template_config(build_template_config(&forge_config))
+                .git(git.clone())
+                .secret_files(secret_files.clone())
                 .add_system_message(conversation)
                 .await?;
 
@@ -129,6 +194,8 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
             chat.event.clone(),
             current_time,
         )
+        .git(git)
+        .include_root(IncludeRoot::new(environment.cwd.clone(), secret_files))
         .add_user_prompt(conversation)
         .await?;
 
@@ -144,7 +211,10 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
 
         // Create the orchestrator with all necessary dependencies
         let tracing_handler = TracingHandler::new();
-        let title_handler = TitleGenerationHandler::new(services.clone());
+        let title_model = forge_config.title.clone().map(|mc| {
+            ModelConfig::new(ProviderId::from(mc.provider_id), ModelId::new(mc.model_id))
+        });
+        let title_handler = TitleGenerationHandler::new(services.clone()).model(title_model);
 
         // Build the on_end hook, conditionally adding PendingTodosHandler based on
         // config
@@ -157,14 +227,15 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
             tracing_handler.clone().and(title_handler.clone())
         };
 
+        let token_counter = services.token_counter(&agent.model);
         let hook = Hook::default()
             .on_start(tracing_handler.clone().and(title_handler))
             .on_request(tracing_handler.clone().and(DoomLoopDetector::default()))
-            .on_response(
-                tracing_handler
-                    .clone()
-                    .and(CompactionHandler::new(agent.clone(), environment.clone())),
-            )
+            .on_response(tracing_handler.clone().and(CompactionHandler::new(
+                agent.clone(),
+                environment.clone(),
+                token_counter,
+            )))
             .on_toolcall_start(tracing_handler.clone())
             .on_toolcall_end(tracing_handler)
             .on_end(on_end_hook);
@@ -178,7 +249,9 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
         .error_tracker(ToolErrorTracker::new(max_tool_failure_per_turn))
         .tool_definitions(tool_definitions)
         .models(models)
-        .hook(Arc::new(hook));
+        .hook(Arc::new(hook))
+        .pause(chat.pause)
+        .token_counter(token_counter);
 
         // Create and return the stream
         let stream = MpscStream::spawn(
@@ -234,7 +307,6 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
 
         // Calculate original metrics
         let original_messages = context.messages.len();
-        let original_token_count = *context.token_count();
 
         let forge_config = self.services.get_config()?;
 
@@ -243,13 +315,35 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
 
         let Some(agent) = agent else {
             return Ok(CompactionResult::new(
-                original_token_count,
+                *context.token_count(),
                 0,
                 original_messages,
                 0,
             ));
         };
 
+        let model = agent.model.clone();
+
+        // Count both contexts the same way, asking the agent's provider for
+        // exact counts when it can give them
+        let provider = match AgentProviderResolver::new(self.services.clone())
+            .get_provider(Some(agent.id.clone()))
+            .await
+        {
+            Ok(provider) => self
+                .services
+                .provider_auth_service()
+                .refresh_provider_credential(provider)
+                .await
+                .ok(),
+            Err(_) => None,
+        };
+        let original_token_count = *self
+            .services
+            .count_tokens(&context, &model, provider.clone())
+            .await;
+        let token_counter = self.services.token_counter(&model);
+
         // Get compact config from the agent
         let compact = agent
             .apply_config(&forge_config)
@@ -258,10 +352,15 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ForgeAp
 
         // Apply compaction using the Compactor
         let environment = self.services.get_environment();
-        let compacted_context = Compactor::new(compact, environment).compact(context, true)?;
+        let compacted_context = Compactor::new(compact, environment)
+            .token_counter(token_counter)
+            .compact(context, true)?;
 
         let compacted_messages = compacted_context.messages.len();
-        let compacted_tokens = *compacted_context.token_count();
+        let compacted_tokens = *self
+            .services
+            .count_tokens(&compacted_context, &model, provider)
+            .await;
 
         // Update the conversation with the compacted context
         conversation.context = Some(compacted_context);
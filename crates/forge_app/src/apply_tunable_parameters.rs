@@ -24,12 +24,24 @@ impl ApplyTunableParameters {
         if let Some(top_k) = self.agent.top_k {
             ctx = ctx.top_k(top_k);
         }
+        if let Some(frequency_penalty) = self.agent.frequency_penalty {
+            ctx = ctx.frequency_penalty(frequency_penalty);
+        }
+        if let Some(presence_penalty) = self.agent.presence_penalty {
+            ctx = ctx.presence_penalty(presence_penalty);
+        }
+        if let Some(ref stop) = self.agent.stop {
+            ctx = ctx.stop(stop.clone());
+        }
         if let Some(max_tokens) = self.agent.max_tokens {
             ctx = ctx.max_tokens(max_tokens.value() as usize);
         }
         if let Some(ref reasoning) = self.agent.reasoning {
             ctx = ctx.reasoning(reasoning.clone());
         }
+        if let Some(ref verbosity) = self.agent.verbosity {
+            ctx = ctx.verbosity(verbosity.clone());
+        }
 
         conversation.context(ctx.tools(self.tool_definitions))
     }
@@ -120,6 +120,10 @@ mod tests {
                 })
                 .ok_or_else(|| anyhow::anyhow!(std::io::Error::from(std::io::ErrorKind::NotFound)))
         }
+
+        async fn outline(&self, _: String) -> anyhow::Result<ReadOutput> {
+            unimplemented!()
+        }
     }
 
     impl EnvironmentInfra for TestServices {
@@ -190,6 +190,18 @@ mod tests {
 
             Ok(files)
         }
+
+        async fn list_projects(&self) -> Result<Vec<forge_domain::Project>> {
+            Ok(vec![])
+        }
+
+        async fn repo_map(
+            &self,
+            _max_tokens: usize,
+            _counter: &dyn forge_domain::TokenCounter,
+        ) -> Result<forge_domain::RepoMap> {
+            Ok(forge_domain::RepoMap::default())
+        }
     }
 
     #[async_trait::async_trait]
@@ -247,6 +259,14 @@ mod tests {
         async fn migrate_env_credentials(&self) -> anyhow::Result<Option<MigrationResult>> {
             Ok(None)
         }
+
+        async fn response_cache_info(&self) -> Result<forge_domain::ResponseCacheInfo> {
+            Ok(Default::default())
+        }
+
+        async fn clear_response_cache(&self) -> Result<usize> {
+            Ok(0)
+        }
     }
 
     #[async_trait::async_trait]
@@ -274,6 +294,10 @@ mod tests {
             Ok(None)
         }
 
+        async fn get_session_reasoning_effort(&self) -> Result<Option<forge_domain::Effort>> {
+            Ok(None)
+        }
+
         async fn update_config(&self, _ops: Vec<forge_domain::ConfigOperation>) -> Result<()> {
             Ok(())
         }
@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A file section of a unified diff
+#[derive(Debug, Clone, PartialEq)]
+struct DiffFile {
+    /// Path of the file after the change
+    path: String,
+    /// Lines preceding the first hunk (`diff --git`, index, mode, `---`/`+++`)
+    header: String,
+    /// Hunks of the file, each starting with its `@@` line
+    hunks: Vec<String>,
+}
+
+/// Smallest unit of a diff that can be assigned to a commit: a hunk, or the
+/// whole file for changes without hunks such as renames, mode changes and
+/// binary files
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Change {
+    file: usize,
+    hunk: Option<usize>,
+}
+
+/// Splits a unified diff (as produced by `git diff`) into files and hunks
+fn parse_diff(diff: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            let path = paths
+                .rsplit_once(" b/")
+                .map(|(_, path)| path)
+                .unwrap_or(paths)
+                .trim_end()
+                .to_string();
+            files.push(DiffFile { path, header: line.to_string(), hunks: Vec::new() });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if line.starts_with("@@") {
+            file.hunks.push(line.to_string());
+        } else if let Some(hunk) = file.hunks.last_mut() {
+            hunk.push_str(line);
+        } else {
+            file.header.push_str(line);
+        }
+    }
+    files
+}
+
+/// Identifier of a change as presented to the model
+fn change_id(index: usize) -> String {
+    format!("h{}", index + 1)
+}
+
+/// A change as presented to the model when planning commits
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ChangeView {
+    id: String,
+    file: String,
+    diff: String,
+}
+
+/// Structured response for splitting changes into commits
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(title = "commit_plan")]
+pub struct CommitPlanResponse {
+    /// Commits in the order they should be created
+    pub commits: Vec<PlannedCommit>,
+}
+
+/// A single commit of a [`CommitPlanResponse`]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PlannedCommit {
+    /// The commit message in conventional commit format
+    pub message: String,
+    /// Ids of the changes that belong to this commit
+    pub hunks: Vec<String>,
+}
+
+/// A logical group of changes that is committed together
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitGroup {
+    /// The generated commit message, editable before committing
+    pub message: String,
+    /// Paths of the files touched by the group
+    pub files: Vec<String>,
+    /// Indexes into the changes of the plan
+    changes: Vec<usize>,
+}
+
+impl CommitGroup {
+    /// Number of hunks in the group
+    pub fn hunk_count(&self) -> usize {
+        self.changes.len()
+    }
+}
+
+/// Pending changes split into logical commits
+#[derive(Debug, Clone)]
+pub struct CommitPlan {
+    /// Groups in the order they should be committed
+    pub groups: Vec<CommitGroup>,
+    /// Whether the plan was made from staged changes rather than unstaged
+    /// ones
+    pub has_staged_files: bool,
+    /// Tree the diff was taken against, `None` before the first commit
+    pub(crate) base_tree: Option<String>,
+    files: Vec<DiffFile>,
+    changes: Vec<Change>,
+}
+
+impl CommitPlan {
+    /// Parses `diff` into the changes that can be assigned to commits
+    pub(crate) fn new(diff: &str, has_staged_files: bool, base_tree: Option<String>) -> Self {
+        let files = parse_diff(diff);
+        let changes = files
+            .iter()
+            .enumerate()
+            .flat_map(|(file, diff_file)| {
+                let hunks = diff_file.hunks.len();
+                let hunks: Vec<Option<usize>> = if hunks == 0 {
+                    vec![None]
+                } else {
+                    (0..hunks).map(Some).collect()
+                };
+                hunks.into_iter().map(move |hunk| Change { file, hunk })
+            })
+            .collect();
+        Self {
+            groups: Vec::new(),
+            has_staged_files,
+            base_tree,
+            files,
+            changes,
+        }
+    }
+
+    /// Whether the diff has no changes
+    pub(crate) fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The changes as presented to the model, each truncated to an equal
+    /// share of `max_diff_size`
+    pub(crate) fn change_views(&self, max_diff_size: Option<usize>) -> Vec<ChangeView> {
+        let limit = max_diff_size.map(|size| size / self.changes.len().max(1));
+        self.changes
+            .iter()
+            .enumerate()
+            .map(|(index, change)| {
+                let file = &self.files[change.file];
+                let diff = match change.hunk {
+                    Some(hunk) => file.hunks[hunk].as_str(),
+                    // Binary patches are noise to the model, the header says enough
+                    None => file
+                        .header
+                        .split("GIT binary patch")
+                        .next()
+                        .unwrap_or_default(),
+                };
+                let diff = match limit {
+                    Some(limit) => diff
+                        .char_indices()
+                        .take_while(|(idx, _)| *idx < limit)
+                        .map(|(_, c)| c)
+                        .collect(),
+                    None => diff.to_string(),
+                };
+                ChangeView { id: change_id(index), file: file.path.clone(), diff }
+            })
+            .collect()
+    }
+
+    /// Groups the changes as proposed by the model
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response references unknown changes, assigns a
+    /// change more than once or leaves a change unassigned.
+    pub(crate) fn with_response(mut self, response: CommitPlanResponse) -> Result<Self> {
+        let ids: HashMap<String, usize> = (0..self.changes.len())
+            .map(|index| (change_id(index), index))
+            .collect();
+        let mut assigned = vec![false; self.changes.len()];
+        let mut groups = Vec::new();
+
+        for commit in response.commits {
+            let message = commit.message.trim().to_string();
+            let mut changes = Vec::new();
+            for id in commit.hunks {
+                let Some(&index) = ids.get(id.trim()) else {
+                    bail!("Commit plan references unknown change '{id}'");
+                };
+                if std::mem::replace(&mut assigned[index], true) {
+                    bail!("Commit plan assigns change '{id}' more than once");
+                }
+                changes.push(index);
+            }
+            if changes.is_empty() {
+                continue;
+            }
+            if message.is_empty() {
+                bail!("Commit plan has a commit without a message");
+            }
+            changes.sort_unstable();
+            let mut files: Vec<String> = Vec::new();
+            for &index in &changes {
+                let path = &self.files[self.changes[index].file].path;
+                if !files.contains(path) {
+                    files.push(path.clone());
+                }
+            }
+            groups.push(CommitGroup { message, files, changes });
+        }
+
+        let unassigned: Vec<String> = assigned
+            .iter()
+            .enumerate()
+            .filter(|(_, assigned)| !**assigned)
+            .map(|(index, _)| change_id(index))
+            .collect();
+        if !unassigned.is_empty() {
+            bail!(
+                "Commit plan leaves changes unassigned: {}",
+                unassigned.join(", ")
+            );
+        }
+
+        self.groups = groups;
+        Ok(self)
+    }
+
+    /// Builds a patch, relative to the base tree, containing the changes of
+    /// the given groups
+    ///
+    /// Hunks of a single diff never overlap, so any subset of them applies
+    /// cleanly to the base with `git apply --recount`.
+    pub(crate) fn patch(&self, groups: &[usize]) -> String {
+        let mut selected = vec![false; self.changes.len()];
+        for group in groups.iter().filter_map(|&index| self.groups.get(index)) {
+            for &change in &group.changes {
+                selected[change] = true;
+            }
+        }
+
+        let mut patch = String::new();
+        let mut current_file = None;
+        for (change, _) in self
+            .changes
+            .iter()
+            .zip(selected)
+            .filter(|(_, selected)| *selected)
+        {
+            let file = &self.files[change.file];
+            if current_file != Some(change.file) {
+                patch.push_str(&file.header);
+                current_file = Some(change.file);
+            }
+            if let Some(hunk) = change.hunk {
+                patch.push_str(&file.hunks[hunk]);
+            }
+        }
+        patch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+-fn a() {}
++fn a() -> u8 { 0 }
+ fn b() {}
+@@ -10,2 +10,3 @@
+ fn c() {}
++fn d() {}
+diff --git a/README.md b/README.md
+index 3333333..4444444 100644
+--- a/README.md
++++ b/README.md
+@@ -1 +1 @@
+-# Old
++# New
+diff --git a/logo.png b/logo.png
+index 5555555..6666666 100644
+Binary files a/logo.png and b/logo.png differ
+";
+
+    fn response(commits: &[(&str, &[&str])]) -> CommitPlanResponse {
+        CommitPlanResponse {
+            commits: commits
+                .iter()
+                .map(|(message, hunks)| PlannedCommit {
+                    message: message.to_string(),
+                    hunks: hunks.iter().map(|id| id.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_diff_splits_files_and_hunks() {
+        let actual = parse_diff(DIFF)
+            .into_iter()
+            .map(|file| (file.path, file.hunks.len()))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            ("src/lib.rs".to_string(), 2),
+            ("README.md".to_string(), 1),
+            ("logo.png".to_string(), 0),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_change_views_number_hunks_and_whole_files() {
+        let fixture = CommitPlan::new(DIFF, true, None);
+
+        let actual = fixture
+            .change_views(None)
+            .into_iter()
+            .map(|view| (view.id, view.file))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            ("h1".to_string(), "src/lib.rs".to_string()),
+            ("h2".to_string(), "src/lib.rs".to_string()),
+            ("h3".to_string(), "README.md".to_string()),
+            ("h4".to_string(), "logo.png".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_with_response_groups_changes() {
+        let fixture = CommitPlan::new(DIFF, true, None);
+
+        let actual = fixture
+            .with_response(response(&[
+                ("feat: add d", &["h2"]),
+                ("docs: rename project", &["h3", "h4", "h1"]),
+            ]))
+            .unwrap()
+            .groups
+            .into_iter()
+            .map(|group| (group.message, group.files, group.changes))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (
+                "feat: add d".to_string(),
+                vec!["src/lib.rs".to_string()],
+                vec![1],
+            ),
+            (
+                "docs: rename project".to_string(),
+                vec![
+                    "src/lib.rs".to_string(),
+                    "README.md".to_string(),
+                    "logo.png".to_string(),
+                ],
+                vec![0, 2, 3],
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_with_response_rejects_unassigned_changes() {
+        let fixture = CommitPlan::new(DIFF, true, None);
+
+        let actual = fixture
+            .with_response(response(&[("feat: add d", &["h1", "h2"])]))
+            .unwrap_err()
+            .to_string();
+        let expected = "Commit plan leaves changes unassigned: h3, h4";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_with_response_rejects_duplicate_changes() {
+        let fixture = CommitPlan::new(DIFF, true, None);
+
+        let actual = fixture
+            .with_response(response(&[
+                ("feat: add d", &["h1", "h2"]),
+                ("docs: rename project", &["h2", "h3", "h4"]),
+            ]))
+            .unwrap_err()
+            .to_string();
+        let expected = "Commit plan assigns change 'h2' more than once";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_patch_includes_headers_of_selected_hunks_only() {
+        let fixture = CommitPlan::new(DIFF, true, None)
+            .with_response(response(&[
+                ("feat: add d", &["h2"]),
+                ("docs: rename project", &["h1", "h3", "h4"]),
+            ]))
+            .unwrap();
+
+        let actual = fixture.patch(&[0]);
+        let expected = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,2 +10,3 @@
+ fn c() {}
++fn d() {}
+";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_patch_of_all_groups_is_the_original_diff() {
+        let fixture = CommitPlan::new(DIFF, true, None)
+            .with_response(response(&[
+                ("feat: add d", &["h2"]),
+                ("docs: rename project", &["h1", "h3", "h4"]),
+            ]))
+            .unwrap();
+
+        let actual = fixture.patch(&[1, 0]);
+        let expected = DIFF;
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,6 +1,6 @@
 use forge_domain::{
     Compact, CompactionStrategy, Context, ContextMessage, ContextSummary, Environment,
-    MessageEntry, Transformer,
+    MessageEntry, Tokenizer, Transformer,
 };
 use tracing::info;
 
@@ -11,11 +11,18 @@ use crate::transformers::SummaryTransformer;
 pub struct Compactor {
     compact: Compact,
     environment: Environment,
+    token_counter: Tokenizer,
 }
 
 impl Compactor {
     pub fn new(compact: Compact, environment: Environment) -> Self {
-        Self { compact, environment }
+        Self { compact, environment, token_counter: Tokenizer::default() }
+    }
+
+    /// Sets the counter used to measure messages when choosing what to evict
+    pub fn token_counter(mut self, token_counter: Tokenizer) -> Self {
+        self.token_counter = token_counter;
+        self
     }
 
     /// Applies the standard compaction transformer pipeline to a context
@@ -49,7 +56,7 @@ impl Compactor {
             eviction.min(retention)
         };
 
-        match strategy.eviction_range(&context) {
+        match strategy.eviction_range(&context, &self.token_counter) {
             Some(sequence) => self.compress_single_sequence(context, sequence),
             None => Ok(context),
         }
@@ -14,6 +14,8 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_sequence: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system: Option<Vec<SystemMessage>>,
@@ -37,6 +39,36 @@ pub struct Request {
     pub anthropic_version: Option<String>,
 }
 
+/// Body of the token counting endpoint, which only accepts the input fields
+/// of a [`Request`]
+#[derive(Serialize)]
+pub struct CountTokensRequest {
+    pub messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<Vec<SystemMessage>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolDefinition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Thinking>,
+}
+
+impl From<Request> for CountTokensRequest {
+    fn from(request: Request) -> Self {
+        Self {
+            messages: request.messages,
+            model: request.model,
+            system: request.system,
+            tool_choice: request.tool_choice,
+            tools: request.tools,
+            thinking: request.thinking,
+        }
+    }
+}
+
 #[derive(Serialize, Default)]
 pub struct SystemMessage {
     pub r#type: String,
@@ -188,6 +220,7 @@ impl TryFrom<forge_domain::Context> for Request {
             temperature: request.temperature.map(|t| t.value()),
             top_p: request.top_p.map(|t| t.value()),
             top_k: request.top_k.map(|t| t.value() as u64),
+            stop_sequences: request.stop,
             tool_choice: request.tool_choice.map(ToolChoice::from),
             stream: Some(request.stream.unwrap_or(true)),
             thinking,
@@ -20,6 +20,11 @@ pub struct ListModelResponse {
     pub data: Vec<Model>,
 }
 
+#[derive(Deserialize)]
+pub struct CountTokensResponse {
+    pub input_tokens: u64,
+}
+
 #[derive(Deserialize)]
 pub struct Model {
     pub id: String,
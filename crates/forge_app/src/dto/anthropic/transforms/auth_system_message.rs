@@ -90,6 +90,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
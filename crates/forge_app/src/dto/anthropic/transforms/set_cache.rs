@@ -101,6 +101,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -240,6 +244,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -381,6 +381,9 @@ impl From<Context> for Request {
             temperature: context.temperature.map(|t| t.value() as f64),
             top_p: context.top_p.map(|t| t.value() as f64),
             top_k: context.top_k.map(|t| t.value() as i32),
+            stop_sequences: context.stop,
+            frequency_penalty: context.frequency_penalty.map(f64::from),
+            presence_penalty: context.presence_penalty.map(f64::from),
             response_mime_type: context.response_format.as_ref().and_then(|rf| match rf {
                 forge_domain::ResponseFormat::JsonSchema(_) => Some("application/json".to_string()),
                 _ => None,
@@ -277,6 +277,8 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning_effort: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<forge_domain::Verbosity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_completion_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thinking: Option<ThinkingConfig>,
@@ -378,7 +380,7 @@ impl From<Context> for Request {
                     ResponseFormat::JsonSchema { name, schema }
                 }
             }),
-            stop: Default::default(),
+            stop: context.stop,
             stream: Some(context.stream.unwrap_or(true)),
             max_tokens: context.max_tokens.map(|t| t as u32),
             temperature: context.temperature.map(|t| t.value()),
@@ -386,8 +388,8 @@ impl From<Context> for Request {
             seed: Default::default(),
             top_p: context.top_p.map(|t| t.value()),
             top_k: context.top_k.map(|t| t.value()),
-            frequency_penalty: Default::default(),
-            presence_penalty: Default::default(),
+            frequency_penalty: context.frequency_penalty,
+            presence_penalty: context.presence_penalty,
             repetition_penalty: Default::default(),
             logit_bias: Default::default(),
             top_logprobs: Default::default(),
@@ -407,6 +409,7 @@ impl From<Context> for Request {
             initiator: context.initiator,
             reasoning: context.reasoning,
             reasoning_effort: Default::default(),
+            verbosity: context.verbosity,
             max_completion_tokens: Default::default(),
             thinking: Default::default(),
         }
@@ -934,4 +937,14 @@ mod tests {
         assert!(json.contains("\"type\":\"json_schema\""));
         assert!(json.contains("\"json_schema\""));
     }
+
+    #[test]
+    fn test_verbosity_is_sent_as_top_level_parameter() {
+        let fixture = forge_domain::Context::default().verbosity(forge_domain::Verbosity::Low);
+
+        let actual = serde_json::to_value(Request::from(fixture)).unwrap()["verbosity"].clone();
+
+        let expected = serde_json::json!("low");
+        assert_eq!(actual, expected);
+    }
 }
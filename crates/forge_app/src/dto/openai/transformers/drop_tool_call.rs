@@ -72,6 +72,10 @@ mod tests {
             stream: None,
             response_format: None,
             initiator: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
+            verbosity: None,
         };
 
         let request = Request::from(context);
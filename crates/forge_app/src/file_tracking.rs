@@ -188,6 +188,10 @@ mod tests {
                 )))
             }
         }
+
+        async fn outline(&self, _: String) -> anyhow::Result<crate::ReadOutput> {
+            unimplemented!()
+        }
     }
 
     #[tokio::test]
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use forge_domain::{ChatResponseContent, Environment, TitleFormat, ToolCatalog};
+use forge_domain::{ChatResponseContent, Environment, ReadMode, TitleFormat, ToolCatalog};
 
 use crate::fmt::content::FormatContent;
 use crate::utils::format_display_path;
@@ -14,7 +14,11 @@ impl FormatContent for ToolCatalog {
                 let display_path = display_path_for(&input.file_path);
                 let is_explicit_range = input.start_line.is_some() || input.end_line.is_some();
                 let mut subtitle = display_path;
-                if is_explicit_range {
+                if let Some(mode @ (ReadMode::Head | ReadMode::Tail | ReadMode::Outline)) =
+                    input.mode
+                {
+                    subtitle.push_str(&format!(" ({})", mode.as_ref().to_lowercase()));
+                } else if is_explicit_range {
                     match (&input.start_line, &input.end_line) {
                         (Some(start), Some(end)) => {
                             subtitle.push_str(&format!(":{start}-{end}"));
@@ -1,35 +1,34 @@
 use forge_display::DiffFormat;
-use forge_domain::{ChatResponseContent, Environment, TitleFormat};
+use forge_domain::{ChatResponseContent, Environment, FileDiff, TitleFormat};
 
 use crate::fmt::content::FormatContent;
 use crate::fmt::todo_fmt::{format_todos, format_todos_diff};
 use crate::operation::ToolOperation;
 use crate::utils::format_display_path;
 
+/// Renders the changes a tool made to the file at `path`
+pub(crate) fn file_diff(path: &str, before: &str, after: &str) -> ChatResponseContent {
+    let diff = DiffFormat::format_highlighted(before, after, path);
+    ChatResponseContent::FileDiff(FileDiff {
+        path: path.to_string(),
+        diff: diff.diff().to_string(),
+        lines_added: diff.lines_added(),
+        lines_removed: diff.lines_removed(),
+    })
+}
+
 impl FormatContent for ToolOperation {
     fn to_content(&self, env: &Environment) -> Option<ChatResponseContent> {
         match self {
-            ToolOperation::FsWrite { input, output } => {
-                if let Some(ref before) = output.before {
-                    let after = &input.content;
-                    Some(ChatResponseContent::ToolOutput(
-                        DiffFormat::format(before, after).diff().to_string(),
-                    ))
-                } else {
-                    None
-                }
+            ToolOperation::FsWrite { input, output } => output
+                .before
+                .as_ref()
+                .map(|before| file_diff(&input.file_path, before, &input.content)),
+            ToolOperation::FsPatch { input, output } => {
+                Some(file_diff(&input.file_path, &output.before, &output.after))
             }
-            ToolOperation::FsPatch { input: _, output } => Some(ChatResponseContent::ToolOutput(
-                DiffFormat::format(&output.before, &output.after)
-                    .diff()
-                    .to_string(),
-            )),
-            ToolOperation::FsMultiPatch { input: _, output } => {
-                Some(ChatResponseContent::ToolOutput(
-                    DiffFormat::format(&output.before, &output.after)
-                        .diff()
-                        .to_string(),
-                ))
+            ToolOperation::FsMultiPatch { input, output } => {
+                Some(file_diff(&input.file_path, &output.before, &output.after))
             }
             ToolOperation::PlanCreate { input: _, output } => Some({
                 let title = TitleFormat::debug(format!(
@@ -63,7 +62,7 @@ mod tests {
 
     use console::strip_ansi_codes;
     use forge_display::DiffFormat;
-    use forge_domain::{ChatResponseContent, Environment, FileInfo};
+    use forge_domain::{ChatResponseContent, Environment, FileDiff, FileInfo};
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
 
@@ -91,6 +90,8 @@ mod tests {
                 start_line: None,
                 end_line: None,
                 show_line_numbers: true,
+                mode: None,
+                lines: None,
             },
             output: ReadOutput {
                 content: Content::file(content),
@@ -114,6 +115,8 @@ mod tests {
                 start_line: Some(2),
                 end_line: Some(4),
                 show_line_numbers: true,
+                mode: None,
+                lines: None,
             },
             output: ReadOutput {
                 content: Content::file(content),
@@ -171,11 +174,14 @@ mod tests {
         let env = fixture_environment();
 
         let actual = fixture.to_content(&env);
-        let expected = Some(ChatResponseContent::ToolOutput(
-            DiffFormat::format("old content", "new content")
+        let expected = Some(ChatResponseContent::FileDiff(FileDiff {
+            path: "/home/user/project/existing_file.txt".to_string(),
+            diff: DiffFormat::format("old content", "new content")
                 .diff()
                 .to_string(),
-        ));
+            lines_added: 1,
+            lines_removed: 1,
+        }));
 
         assert_eq!(actual, expected);
     }
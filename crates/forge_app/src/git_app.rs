@@ -6,9 +6,10 @@ use forge_domain::*;
 use schemars::JsonSchema;
 use serde::Deserialize;
 
+use crate::commit_plan::{CommitPlan, CommitPlanResponse};
 use crate::services::{
-    AgentRegistry, AppConfigService, ProviderAuthService, ProviderService, ShellService,
-    TemplateService,
+    AgentRegistry, AppConfigService, ProviderAuthService, ProviderService, PullRequestService,
+    ShellService, TemplateService,
 };
 use crate::{AgentProviderResolver, EnvironmentInfra, Services};
 
@@ -17,6 +18,8 @@ use crate::{AgentProviderResolver, EnvironmentInfra, Services};
 pub enum GitAppError {
     #[error("nothing to commit, working tree clean")]
     NoChangesToCommit,
+    #[error("no commits on the current branch ahead of {0}")]
+    NoCommitsAhead(String),
 }
 
 /// GitApp handles git-related operations like commit message generation.
@@ -55,6 +58,17 @@ pub struct CommitMessageResponse {
     pub commit_message: String,
 }
 
+/// Structured response for pull request generation using JSON format
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(title = "pull_request")]
+pub struct PullRequestResponse {
+    /// Title of the pull request
+    pub title: String,
+    /// Description of the pull request in Markdown
+    pub description: String,
+}
+
 /// Context for generating a commit message from a diff
 #[derive(Debug, Clone)]
 struct DiffContext {
@@ -177,6 +191,281 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> GitApp<
         Ok(CommitResult { message, committed: true, has_staged_files, git_output })
     }
 
+    /// Splits the pending changes into logical commits with generated
+    /// messages
+    ///
+    /// Staged changes are planned when there are any, otherwise unstaged
+    /// ones. Nothing is staged or committed; see
+    /// [`GitApp::stage_commit_groups`].
+    ///
+    /// # Arguments
+    ///
+    /// * `max_diff_size` - Maximum size of git diff in bytes sent to the model.
+    ///   None for unlimited.
+    /// * `additional_context` - Optional additional text to help group the
+    ///   changes and structure the commit messages
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is nothing to commit, git operations fail or
+    /// the model fails to produce a valid plan
+    pub async fn plan_commits(
+        &self,
+        max_diff_size: Option<usize>,
+        additional_context: Option<String>,
+    ) -> Result<CommitPlan> {
+        let cwd = self.services.get_environment().cwd;
+
+        let ((recent_commits, branch_name), (diff, has_staged_files), base_tree) = tokio::try_join!(
+            self.fetch_git_context(&cwd),
+            self.fetch_git_patch(&cwd),
+            self.fetch_base_tree(&cwd)
+        )?;
+
+        let plan = CommitPlan::new(&diff, has_staged_files, base_tree);
+        if plan.is_empty() {
+            return Err(GitAppError::NoChangesToCommit.into());
+        }
+
+        let user_data = serde_json::json!({
+            "branch_name": branch_name,
+            "recent_commit_messages": recent_commits,
+            "changes": plan.change_views(max_diff_size),
+            "additional_context": additional_context
+        });
+
+        let retry_config = self.services.get_config()?.retry.unwrap_or_default();
+        crate::retry::retry_with_config(
+            &retry_config,
+            || self.generate_plan(plan.clone(), &user_data),
+            None::<fn(&anyhow::Error, std::time::Duration)>,
+        )
+        .await
+    }
+
+    /// Replaces the index with the base tree of `plan` plus the changes of
+    /// the given groups, leaving the working tree untouched
+    ///
+    /// Committing after staging each approved group together with the
+    /// previously committed ones creates one commit per group. Staging the
+    /// committed groups (and the skipped ones, for plans made from staged
+    /// changes) afterwards restores the user's index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be reset or the patch does not
+    /// apply
+    pub async fn stage_commit_groups(&self, plan: &CommitPlan, groups: &[usize]) -> Result<()> {
+        let cwd = self.services.get_environment().cwd;
+
+        let read_tree = match &plan.base_tree {
+            Some(tree) => format!("git read-tree {tree}"),
+            None => "git read-tree --empty".to_string(),
+        };
+        let output = self
+            .services
+            .execute(read_tree, cwd.clone(), false, true, None, None)
+            .await
+            .context("Failed to reset the index")?;
+        if !output.output.success() {
+            anyhow::bail!("Failed to reset the index: {}", output.output.stderr);
+        }
+
+        let patch = plan.patch(groups);
+        if patch.is_empty() {
+            return Ok(());
+        }
+
+        let file = tempfile::Builder::new()
+            .prefix("forge-commit-")
+            .suffix(".patch")
+            .tempfile()
+            .context("Failed to create patch file")?;
+        std::fs::write(file.path(), patch).context("Failed to write patch file")?;
+
+        let path = file.path().display().to_string().replace('\'', r"'\''");
+        let output = self
+            .services
+            .execute(
+                format!("git apply --cached --recount --whitespace=nowarn '{path}'"),
+                cwd,
+                false,
+                true,
+                None,
+                None,
+            )
+            .await
+            .context("Failed to stage changes")?;
+        if !output.output.success() {
+            anyhow::bail!("Failed to stage changes: {}", output.output.stderr);
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the default branch of `remote`, falling back to `main` when
+    /// the remote does not record one
+    pub async fn default_branch(&self, remote: &str) -> Result<String> {
+        let cwd = self.services.get_environment().cwd;
+        let head = self
+            .git(
+                &cwd,
+                format!(
+                    "git symbolic-ref --short {}",
+                    shell_quote(&format!("refs/remotes/{remote}/HEAD"))
+                ),
+            )
+            .await
+            .unwrap_or_default();
+        Ok(head
+            .trim()
+            .strip_prefix(&format!("{remote}/"))
+            .filter(|branch| !branch.is_empty())
+            .unwrap_or("main")
+            .to_string())
+    }
+
+    /// Generates the title and description of a pull request from the commits
+    /// and diff of the current branch since it left `base`
+    ///
+    /// # Arguments
+    ///
+    /// * `remote` - Remote whose copy of `base` is compared against when it
+    ///   exists, as the local branch may be behind
+    /// * `base` - Branch the pull request merges into
+    /// * `max_diff_size` - Maximum size of git diff in bytes. None for
+    ///   unlimited.
+    /// * `additional_context` - Optional additional text to guide the title and
+    ///   description
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the branch has no commits ahead of `base`, git
+    /// operations fail or AI generation fails
+    pub async fn pull_request_draft(
+        &self,
+        remote: &str,
+        base: &str,
+        max_diff_size: Option<usize>,
+        additional_context: Option<String>,
+    ) -> Result<PullRequestDraft> {
+        let cwd = self.services.get_environment().cwd;
+
+        let remote_base = format!("{remote}/{base}");
+        let base_ref = match self
+            .git(
+                &cwd,
+                format!(
+                    "git rev-parse --verify --quiet {}",
+                    shell_quote(&remote_base)
+                ),
+            )
+            .await
+        {
+            Ok(_) => remote_base,
+            Err(_) => base.to_string(),
+        };
+
+        let (commits, diff, branch_name) = tokio::try_join!(
+            self.git(
+                &cwd,
+                format!(
+                    "git log --reverse --pretty=format:%B {}",
+                    shell_quote(&format!("{base_ref}..HEAD"))
+                )
+            ),
+            self.git(
+                &cwd,
+                format!("git diff {}", shell_quote(&format!("{base_ref}...HEAD")))
+            ),
+            self.git(&cwd, "git rev-parse --abbrev-ref HEAD".to_string()),
+        )?;
+        if commits.trim().is_empty() {
+            return Err(GitAppError::NoCommitsAhead(base_ref).into());
+        }
+
+        let size = diff.len();
+        let (diff, _) = self.truncate_diff(diff, max_diff_size, size);
+        let user_data = serde_json::json!({
+            "branch_name": branch_name.trim(),
+            "base_branch": base,
+            "commits": commits,
+            "git_diff": diff,
+            "additional_context": additional_context
+        });
+
+        let retry_config = self.services.get_config()?.retry.unwrap_or_default();
+        crate::retry::retry_with_config(
+            &retry_config,
+            || self.generate_pull_request(&user_data),
+            None::<fn(&anyhow::Error, std::time::Duration)>,
+        )
+        .await
+    }
+
+    /// Pushes the current branch to `remote` and opens a pull request from it
+    /// into `base`, returning its URL
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remote is not hosted on GitHub or GitLab, the
+    /// push fails or the host refuses the pull request
+    pub async fn create_pull_request(
+        &self,
+        remote: &str,
+        base: &str,
+        draft: &PullRequestDraft,
+    ) -> Result<url::Url> {
+        let cwd = self.services.get_environment().cwd;
+
+        let (remote_url, head) = tokio::try_join!(
+            self.git(&cwd, format!("git remote get-url {}", shell_quote(remote))),
+            self.git(&cwd, "git rev-parse --abbrev-ref HEAD".to_string()),
+        )?;
+        let head = head.trim();
+        anyhow::ensure!(
+            head != "HEAD",
+            "Cannot open a pull request from a detached HEAD"
+        );
+        anyhow::ensure!(
+            head != base,
+            "The current branch is the base branch {base}, switch to the branch to merge"
+        );
+        let repository = RemoteRepository::parse(&remote_url).with_context(|| {
+            format!(
+                "Remote {remote} ({}) is not hosted on GitHub or GitLab",
+                remote_url.trim()
+            )
+        })?;
+
+        self.git(
+            &cwd,
+            format!(
+                "git push --set-upstream {} {}",
+                shell_quote(remote),
+                shell_quote(head)
+            ),
+        )
+        .await?;
+
+        self.services
+            .create_pull_request(&repository, head, base, draft)
+            .await
+    }
+
+    /// Runs a git command and returns its output, failing when git does
+    async fn git(&self, cwd: &Path, command: String) -> Result<String> {
+        let output = self
+            .services
+            .execute(command.clone(), cwd.to_path_buf(), false, true, None, None)
+            .await
+            .with_context(|| format!("Failed to run {command}"))?;
+        if !output.output.success() {
+            anyhow::bail!("{command} failed: {}", output.output.stderr.trim());
+        }
+        Ok(output.output.stdout)
+    }
+
     /// Generates a commit message based on staged git changes and returns
     /// details about the commit context
     async fn generate_commit_message(
@@ -283,6 +572,61 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> GitApp<
         Ok((diff_output.output.stdout, size, has_staged_files))
     }
 
+    /// Fetches the patch to plan commits from: staged changes if there are
+    /// any, otherwise unstaged ones, including binary files so that they can
+    /// be staged again
+    async fn fetch_git_patch(&self, cwd: &Path) -> Result<(String, bool)> {
+        let (staged_diff, unstaged_diff) = tokio::join!(
+            self.services.execute(
+                "git diff --staged --binary".into(),
+                cwd.to_path_buf(),
+                false,
+                true,
+                None,
+                None,
+            ),
+            self.services.execute(
+                "git diff --binary".into(),
+                cwd.to_path_buf(),
+                false,
+                true,
+                None,
+                None,
+            )
+        );
+
+        let staged_diff = staged_diff.context("Failed to get staged changes")?;
+        let unstaged_diff = unstaged_diff.context("Failed to get unstaged changes")?;
+
+        let has_staged_files = !staged_diff.output.stdout.trim().is_empty();
+        let diff_output = if has_staged_files {
+            staged_diff
+        } else {
+            unstaged_diff
+        };
+
+        Ok((diff_output.output.stdout, has_staged_files))
+    }
+
+    /// Fetches the tree of `HEAD`, `None` in a repository without commits
+    async fn fetch_base_tree(&self, cwd: &Path) -> Result<Option<String>> {
+        let output = self
+            .services
+            .execute(
+                "git rev-parse --verify --quiet 'HEAD^{tree}'".into(),
+                cwd.to_path_buf(),
+                false,
+                true,
+                None,
+                None,
+            )
+            .await
+            .context("Failed to resolve HEAD")?;
+
+        let tree = output.output.stdout.trim();
+        Ok(Some(tree.to_string()).filter(|tree| output.output.success() && !tree.is_empty()))
+    }
+
     /// Resolves the provider and model from the active agent's configuration.
     async fn resolve_agent_provider_and_model(
         &self,
@@ -300,8 +644,9 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> GitApp<
         Ok((provider, model))
     }
 
-    /// Generates a commit message from the provided diff and git context
-    async fn generate_message_from_diff(&self, ctx: DiffContext) -> Result<CommitMessageDetails> {
+    /// Resolves the provider and model used for commit messages: the commit
+    /// config takes priority over the active agent's defaults.
+    async fn resolve_commit_provider_and_model(&self) -> Result<(Provider<url::Url>, ModelId)> {
         let (agent_id, commit_config) = tokio::try_join!(
             self.services.get_active_agent_id(),
             self.services.get_commit_config()
@@ -338,6 +683,12 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> GitApp<
                     .await?
             }
         };
+        Ok((provider, model))
+    }
+
+    /// Generates a commit message from the provided diff and git context
+    async fn generate_message_from_diff(&self, ctx: DiffContext) -> Result<CommitMessageDetails> {
+        let (provider, model) = self.resolve_commit_provider_and_model().await?;
 
         let rendered_prompt = self
             .services
@@ -386,4 +737,83 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> GitApp<
             has_staged_files: ctx.has_staged_files,
         })
     }
+
+    /// Asks the model for the title and description of a pull request
+    async fn generate_pull_request(
+        &self,
+        user_data: &serde_json::Value,
+    ) -> Result<PullRequestDraft> {
+        let (provider, model) = self.resolve_commit_provider_and_model().await?;
+
+        let rendered_prompt = self
+            .services
+            .render_template(Template::new("{{> forge-pull-request-prompt.md }}"), &())
+            .await?;
+
+        let schema = schemars::schema_for!(PullRequestResponse);
+
+        let context = forge_domain::Context::default()
+            .add_message(ContextMessage::system(rendered_prompt))
+            .add_message(ContextMessage::user(
+                serde_json::to_string(user_data)?,
+                Some(model.clone()),
+            ))
+            .response_format(ResponseFormat::JsonSchema(Box::new(schema)));
+
+        let stream = self.services.chat(&model, context, provider).await?;
+        let message = stream.into_full(false).await?;
+
+        // Providers without structured output answer with the draft as text
+        let draft = match serde_json::from_str::<PullRequestResponse>(&message.content) {
+            Ok(response) => Some(PullRequestDraft::new(
+                response.title.trim(),
+                response.description.trim(),
+            ))
+            .filter(|draft| !draft.title.is_empty()),
+            Err(_) => PullRequestDraft::parse(&message.content),
+        };
+        draft.ok_or_else(|| {
+            Error::Retryable(anyhow::anyhow!("Empty pull request title generated")).into()
+        })
+    }
+
+    /// Asks the model to group the changes of `plan` into commits
+    async fn generate_plan(
+        &self,
+        plan: CommitPlan,
+        user_data: &serde_json::Value,
+    ) -> Result<CommitPlan> {
+        let (provider, model) = self.resolve_commit_provider_and_model().await?;
+
+        let rendered_prompt = self
+            .services
+            .render_template(Template::new("{{> forge-commit-plan-prompt.md }}"), &())
+            .await?;
+
+        let schema = schemars::schema_for!(CommitPlanResponse);
+
+        let context = forge_domain::Context::default()
+            .add_message(ContextMessage::system(rendered_prompt))
+            .add_message(ContextMessage::user(
+                serde_json::to_string(user_data)?,
+                Some(model.clone()),
+            ))
+            .response_format(ResponseFormat::JsonSchema(Box::new(schema)));
+
+        let stream = self.services.chat(&model, context, provider).await?;
+        let message = stream.into_full(false).await?;
+
+        // Unlike a commit message, a plan has no plain text fallback, so any
+        // malformed response is worth another attempt
+        let response = serde_json::from_str::<CommitPlanResponse>(&message.content)
+            .map_err(|err| Error::Retryable(anyhow::anyhow!("Invalid commit plan: {err}")))?;
+
+        plan.with_response(response)
+            .map_err(|err| Error::Retryable(err).into())
+    }
+}
+
+/// Quotes `value` as a single argument of a POSIX shell command
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
 }
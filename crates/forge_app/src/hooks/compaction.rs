@@ -1,5 +1,7 @@
 use async_trait::async_trait;
-use forge_domain::{Agent, Conversation, Environment, EventData, EventHandle, ResponsePayload};
+use forge_domain::{
+    Agent, Conversation, Environment, EventData, EventHandle, ResponsePayload, Tokenizer,
+};
 use tracing::{debug, info};
 
 use crate::compact::Compactor;
@@ -14,6 +16,7 @@ use crate::compact::Compactor;
 pub struct CompactionHandler {
     agent: Agent,
     environment: Environment,
+    token_counter: Tokenizer,
 }
 
 impl CompactionHandler {
@@ -22,8 +25,9 @@ impl CompactionHandler {
     /// # Arguments
     /// * `agent` - The agent configuration containing compaction settings
     /// * `environment` - The environment configuration
-    pub fn new(agent: Agent, environment: Environment) -> Self {
-        Self { agent, environment }
+    /// * `token_counter` - Counts tokens for the agent's model
+    pub fn new(agent: Agent, environment: Environment, token_counter: Tokenizer) -> Self {
+        Self { agent, environment, token_counter }
     }
 }
 
@@ -35,11 +39,12 @@ impl EventHandle<EventData<ResponsePayload>> for CompactionHandler {
         conversation: &mut Conversation,
     ) -> anyhow::Result<()> {
         if let Some(context) = &conversation.context {
-            let token_count = context.token_count();
+            let token_count = context.token_count_with(&self.token_counter);
             if self.agent.compact.should_compact(context, *token_count) {
                 info!(agent_id = %self.agent.id, "Compaction triggered by hook");
                 let compacted =
                     Compactor::new(self.agent.compact.clone(), self.environment.clone())
+                        .token_counter(self.token_counter)
                         .compact(context.clone(), false)?;
                 conversation.context = Some(compacted);
             } else {
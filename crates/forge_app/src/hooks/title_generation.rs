@@ -3,7 +3,8 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use dashmap::DashMap;
 use forge_domain::{
-    Conversation, ConversationId, EndPayload, EventData, EventHandle, StartPayload,
+    Conversation, ConversationId, EndPayload, EventData, EventHandle, ModelConfig, ModelId,
+    ProviderId, ReasoningConfig, StartPayload,
 };
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
@@ -22,12 +23,38 @@ struct TitleGenerationState {
 pub struct TitleGenerationHandler<S> {
     services: Arc<S>,
     title_tasks: Arc<DashMap<ConversationId, TitleGenerationState>>,
+    /// Model used instead of the agent's to generate titles
+    model: Option<ModelConfig>,
 }
 
 impl<S> TitleGenerationHandler<S> {
     /// Creates a new title generation handler.
     pub fn new(services: Arc<S>) -> Self {
-        Self { services, title_tasks: Arc::new(DashMap::new()) }
+        Self { services, title_tasks: Arc::new(DashMap::new()), model: None }
+    }
+
+    /// Generates titles with `model` instead of the agent's model, typically
+    /// a cheaper one
+    pub fn model(mut self, model: Option<ModelConfig>) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Model, provider and reasoning used to generate the title of a
+    /// conversation run by the agent of `event`. The agent's reasoning only
+    /// applies to the agent's own model.
+    fn title_model<T: Send + Sync>(
+        &self,
+        event: &EventData<T>,
+    ) -> (ModelId, ProviderId, Option<ReasoningConfig>) {
+        match &self.model {
+            Some(config) => (config.model.clone(), config.provider.clone(), None),
+            None => (
+                event.model_id.clone(),
+                event.agent.provider.clone(),
+                event.agent.reasoning.clone(),
+            ),
+        }
     }
 }
 
@@ -57,13 +84,14 @@ impl<S: AgentService> EventHandle<EventData<StartPayload>> for TitleGenerationHa
             return Ok(());
         };
 
+        let (model_id, provider_id, reasoning) = self.title_model(event);
         let generator = TitleGenerator::new(
             self.services.clone(),
             user_prompt.clone(),
-            event.model_id.clone(),
-            Some(event.agent.provider.clone()),
+            model_id,
+            Some(provider_id),
         )
-        .reasoning(event.agent.reasoning.clone());
+        .reasoning(reasoning);
 
         // `or_insert_with` holds the shard lock for its entire call. Any occupied
         // entry — InProgress, Awaiting, or Done — is left untouched, so at most
@@ -302,4 +330,30 @@ mod tests {
         // Only one task should exist in the map
         assert_eq!(handler.title_tasks.len(), 1);
     }
+
+    #[test]
+    fn test_title_model_prefers_configured_model() {
+        let (handler, _) = setup("test message");
+        let handler = handler.model(Some(ModelConfig::new(
+            ProviderId::from("openai".to_string()),
+            ModelId::new("gpt-4.1-mini"),
+        )));
+
+        let actual = handler.title_model(&event(StartPayload));
+        let expected = (
+            ModelId::new("gpt-4.1-mini"),
+            ProviderId::from("openai".to_string()),
+            None,
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_title_model_defaults_to_agent_model() {
+        let (handler, _) = setup("test message");
+
+        let actual = handler.title_model(&event(StartPayload));
+        let expected = (ModelId::new("t"), ProviderId::from("t".to_string()), None);
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::Result;
 use bytes::Bytes;
@@ -308,9 +309,25 @@ pub trait KVStore: Send + Sync {
         K: Hash + Sync,
         V: serde::Serialize + Sync;
 
+    /// Stores a value in the cache that expires after `ttl`, regardless of
+    /// the cache's default expiry.
+    ///
+    /// # Arguments
+    /// * `key` - The key to store the value under
+    /// * `value` - The value to cache
+    /// * `ttl` - How long the value stays valid
+    ///
+    /// # Errors
+    /// Returns an error if the cache operation fails
+    async fn cache_set_with_ttl<K, V>(&self, key: &K, value: &V, ttl: Duration) -> Result<()>
+    where
+        K: Hash + Sync,
+        V: serde::Serialize + Sync;
+
     /// Clears all entries from the cache.
     ///
-    /// This operation removes all cached data. Use with caution.
+    /// This operation removes all cached data, or only that of its namespace
+    /// for caches sharing storage. Use with caution.
     ///
     /// # Errors
     /// Returns an error if the cache clear operation fails
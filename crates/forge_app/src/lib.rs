@@ -5,6 +5,7 @@ mod app;
 mod apply_tunable_parameters;
 mod changed_files;
 mod command_generator;
+mod commit_plan;
 mod compact;
 mod data_gen;
 pub mod dto;
@@ -20,12 +21,14 @@ mod operation;
 mod orch;
 #[cfg(test)]
 mod orch_spec;
+mod replay;
 mod retry;
 mod search_dedup;
 mod services;
 mod set_conversation_id;
 pub mod system_prompt;
 mod template_engine;
+mod template_helpers;
 mod title_generator;
 mod tool_executor;
 mod tool_registry;
@@ -42,12 +45,15 @@ pub use agent::*;
 pub use agent_provider_resolver::*;
 pub use app::*;
 pub use command_generator::*;
+pub use commit_plan::{CommitGroup, CommitPlan};
 pub use data_gen::*;
 pub use error::*;
 pub use git_app::*;
 pub use infra::*;
+pub use replay::replay_turns;
 pub use services::*;
 pub use template_engine::*;
+pub use template_helpers::IncludeRoot;
 pub use tool_resolver::*;
 pub use user::*;
 pub use utils::{compute_hash, is_binary_content_type};
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use forge_domain::{TitleFormat, ToolCallContext, ToolCallFull, ToolName, ToolOutput};
 
 use crate::McpService;
+use crate::truncation::truncate_mcp_output;
 
 pub struct McpExecutor<S> {
     services: Arc<S>,
@@ -22,7 +23,8 @@ impl<S: McpService> McpExecutor<S> {
             .send_tool_input(TitleFormat::info("MCP").sub_title(input.name.as_str()))
             .await?;
 
-        self.services.execute_mcp(input).await
+        let output = self.services.execute_mcp(input).await?;
+        Ok(truncate_mcp_output(output))
     }
 
     pub async fn contains_tool(&self, tool_name: &ToolName) -> anyhow::Result<bool> {
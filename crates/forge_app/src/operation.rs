@@ -8,7 +8,7 @@ use forge_config::ForgeConfig;
 use forge_display::DiffFormat;
 use forge_domain::{
     CodebaseSearchResults, Environment, FSMultiPatch, FSPatch, FSRead, FSRemove, FSSearch, FSUndo,
-    FSWrite, FileOperation, LineNumbers, Metrics, NetFetch, PlanCreate, ToolKind,
+    FSWrite, FileOperation, LineNumbers, Metrics, NetFetch, PlanCreate, ReadMode, ToolKind,
 };
 use forge_template::Element;
 
@@ -262,7 +262,9 @@ impl ToolOperation {
 
                 // Handle text content
                 let content = output.content.file_content();
-                let content = if input.show_line_numbers {
+                let is_outline = input.mode == Some(ReadMode::Outline);
+                // Outline lines are numbered by the service as they are not contiguous
+                let content = if input.show_line_numbers && !is_outline {
                     content
                         .to_numbered_from(output.info.start_line as usize)
                         .to_string()
@@ -271,6 +273,7 @@ impl ToolOperation {
                 };
                 let elm = Element::new("file")
                     .attr("path", &input.file_path)
+                    .attr_if_some("mode", is_outline.then_some("outline"))
                     .attr(
                         "display_lines",
                         format!("{}-{}", output.info.start_line, output.info.end_line),
@@ -569,7 +572,7 @@ impl ToolOperation {
                     .attr("url", &input.url)
                     .attr("status_code", output.code)
                     .attr("start_char", 0)
-                    .attr("end_char", config.max_fetch_chars.min(output.content.len()))
+                    .attr("end_char", truncated_content.end_char)
                     .attr("total_chars", output.content.len())
                     .attr("content_type", content_type);
 
@@ -864,6 +867,8 @@ mod tests {
                 start_line: None,
                 end_line: None,
                 show_line_numbers: true,
+                mode: None,
+                lines: None,
             },
             output: ReadOutput {
                 content: Content::file(content),
@@ -895,6 +900,8 @@ mod tests {
                 start_line: None,
                 end_line: None,
                 show_line_numbers: true,
+                mode: None,
+                lines: None,
             },
             output: ReadOutput {
                 content: Content::file(content),
@@ -925,6 +932,8 @@ mod tests {
                 start_line: Some(2),
                 end_line: Some(3),
                 show_line_numbers: true,
+                mode: None,
+                lines: None,
             },
             output: ReadOutput {
                 content: Content::file(content),
@@ -956,6 +965,8 @@ mod tests {
                 start_line: None,
                 end_line: None,
                 show_line_numbers: true,
+                mode: None,
+                lines: None,
             },
             output: ReadOutput {
                 content: Content::file(content),
@@ -2624,6 +2635,8 @@ mod tests {
                 start_line: None,
                 end_line: None,
                 show_line_numbers: true,
+                mode: None,
+                lines: None,
             },
             output: ReadOutput {
                 content: Content::image(Image::new_base64(
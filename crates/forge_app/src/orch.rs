@@ -8,11 +8,32 @@ use forge_domain::{Agent, *};
 use forge_template::Element;
 use futures::future::join_all;
 use tokio::sync::Notify;
-use tracing::warn;
+use tracing::{Instrument, warn};
 
 use crate::agent::AgentService;
+use crate::compact::Compactor;
 use crate::{EnvironmentInfra, TemplateEngine};
 
+/// Result recorded for a tool call skipped because the user paused the turn
+const SKIPPED_ON_PAUSE: &str = "Not run: the user paused before this tool call";
+
+/// Checks if the provider rejected a request for exceeding the context window
+fn is_context_overflow(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<Error>()
+        .is_some_and(|error| matches!(error, Error::ContextOverflow(_)))
+}
+
+/// Creates the tracing span covering the execution of a single tool call
+fn tool_call_span(tool_call: &ToolCallFull) -> tracing::Span {
+    tracing::info_span!(
+        "tool_call",
+        gen_ai.tool.name = %tool_call.name,
+        error = tracing::field::Empty,
+        otel.status_code = tracing::field::Empty,
+    )
+}
+
 #[derive(Clone, Setters)]
 #[setters(into)]
 pub struct Orchestrator<S> {
@@ -25,6 +46,8 @@ pub struct Orchestrator<S> {
     error_tracker: ToolErrorTracker,
     hook: Arc<Hook>,
     config: forge_config::ForgeConfig,
+    pause: PauseSignal,
+    token_counter: Tokenizer,
 }
 
 impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orchestrator<S> {
@@ -44,6 +67,8 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
             models: Default::default(),
             error_tracker: Default::default(),
             hook: Arc::new(Hook::default()),
+            pause: Default::default(),
+            token_counter: Tokenizer::default(),
         }
     }
 
@@ -77,11 +102,11 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
 
         // Execute task tool calls in parallel — mirrors how direct agent-as-tool calls
         // work.
-        let task_results: Vec<(ToolCallFull, ToolResult)> = join_all(
-            task_calls
-                .iter()
-                .map(|tc| self.services.call(&self.agent, tool_context, (*tc).clone())),
-        )
+        let task_results: Vec<(ToolCallFull, ToolResult)> = join_all(task_calls.iter().map(|tc| {
+            self.services
+                .call(&self.agent, tool_context, (*tc).clone())
+                .instrument(tool_call_span(tc))
+        }))
         .await
         .into_iter()
         .zip(task_calls.iter())
@@ -99,6 +124,16 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
         let mut other_results: Vec<(ToolCallFull, ToolResult)> =
             Vec::with_capacity(other_calls.len());
         for tool_call in &other_calls {
+            // Every call needs a result, so the ones left after a pause are recorded as
+            // skipped
+            if self.pause.is_requested() {
+                let skipped = ToolResult::new(tool_call.name.clone())
+                    .call_id(tool_call.call_id.clone())
+                    .success(SKIPPED_ON_PAUSE);
+                other_results.push(((*tool_call).clone(), skipped));
+                continue;
+            }
+
             // Send the start notification for system tools and not agent as a tool
             let is_system_tool = system_tools.contains(&tool_call.name);
             if is_system_tool {
@@ -125,10 +160,17 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
                 .await?;
 
             // Execute the tool
+            let span = tool_call_span(tool_call);
             let tool_result = self
                 .services
                 .call(&self.agent, tool_context, (*tool_call).clone())
+                .instrument(span.clone())
                 .await;
+            span.record("error", tool_result.is_error());
+            if tool_result.is_error() {
+                span.record("otel.status_code", "ERROR");
+            }
+            drop(span);
 
             // Fire the ToolcallEnd lifecycle event (fires on both success and failure)
             let toolcall_end_event = LifecycleEvent::ToolcallEnd(EventData::new(
@@ -209,19 +251,97 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
             // Strip all reasoning from messages when the model has changed (signatures are
             // model-specific and invalid across models). No-op when model is unchanged.
             .pipe(ReasoningNormalizer::new(model_id.clone()));
-        let response = self
-            .services
-            .chat_agent(
-                model_id,
-                transformers.transform(context),
-                Some(self.agent.provider.clone()),
-            )
-            .await?;
 
-        // Always stream content deltas
-        response
-            .into_full_streaming(!tool_supported, self.sender.clone())
-            .await
+        let span = tracing::info_span!(
+            "provider_request",
+            gen_ai.request.model = %model_id,
+            gen_ai.provider.name = %self.agent.provider,
+            gen_ai.usage.input_tokens = tracing::field::Empty,
+            gen_ai.usage.output_tokens = tracing::field::Empty,
+        );
+        let message = async {
+            let response = self
+                .services
+                .chat_agent(
+                    model_id,
+                    transformers.transform(context),
+                    Some(self.agent.provider.clone()),
+                )
+                .await?;
+
+            // Always stream content deltas
+            response
+                .into_full_streaming(!tool_supported, self.sender.clone())
+                .await
+        }
+        .instrument(span.clone())
+        .await?;
+
+        span.record(
+            "gen_ai.usage.input_tokens",
+            *message.usage.prompt_tokens as u64,
+        );
+        span.record(
+            "gen_ai.usage.output_tokens",
+            *message.usage.completion_tokens as u64,
+        );
+        // Exported as the `gen_ai.client.token.usage` metric by the OTLP exporter
+        for (token_type, count) in [
+            ("input", *message.usage.prompt_tokens),
+            ("output", *message.usage.completion_tokens),
+        ] {
+            tracing::info!(
+                parent: &span,
+                monotonic_counter.gen_ai.client.token.usage = count as u64,
+                gen_ai.token.type = token_type,
+                gen_ai.request.model = %model_id,
+            );
+        }
+        Ok(message)
+    }
+
+    /// Sends the context to the model, retrying errors the provider marked as
+    /// transient
+    async fn chat_with_retry(
+        &self,
+        model_id: &ModelId,
+        context: &Context,
+    ) -> anyhow::Result<ChatCompletionMessageFull> {
+        crate::retry::retry_with_config(
+            &self.config.clone().retry.unwrap_or_default(),
+            || self.execute_chat_turn(model_id, context.clone(), context.is_reasoning_supported()),
+            self.sender.as_ref().map(|sender| {
+                let sender = sender.clone();
+                let agent_id = self.agent.id.clone();
+                let model_id = model_id.clone();
+                move |error: &anyhow::Error, duration: Duration| {
+                    let root_cause = error.root_cause();
+                    // Log retry attempts - critical for debugging API failures
+                    tracing::error!(
+                        agent_id = %agent_id,
+                        error = ?root_cause,
+                        model = %model_id,
+                        "Retry attempt due to error"
+                    );
+                    let retry_event = ChatResponse::RetryAttempt {
+                        cause: error.into(),
+                        duration,
+                        stalled: Error::is_stall(error),
+                    };
+                    let _ = sender.try_send(Ok(retry_event));
+                }
+            }),
+        )
+        .await
+    }
+
+    /// Compacts as much of the context as the agent's compaction settings
+    /// allow. Returns `None` when nothing could be compacted.
+    fn compact_on_overflow(&self, context: &Context) -> anyhow::Result<Option<Context>> {
+        let compacted = Compactor::new(self.agent.compact.clone(), self.services.get_environment())
+            .token_counter(self.token_counter)
+            .compact(context.clone(), true)?;
+        Ok((compacted.messages.len() < context.messages.len()).then_some(compacted))
     }
 
     // Create a helper method with the core functionality
@@ -250,8 +370,9 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
 
         // Retrieve the number of requests allowed per tick.
         let max_requests_per_turn = self.agent.max_requests_per_turn;
-        let tool_context =
-            ToolCallContext::new(self.conversation.metrics.clone()).sender(self.sender.clone());
+        let tool_context = ToolCallContext::new(self.conversation.metrics.clone())
+            .sender(self.sender.clone())
+            .conversation_id(Some(self.conversation.id));
 
         while !should_yield {
             // Set context for the current loop iteration
@@ -267,35 +388,30 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
                 .handle(&request_event, &mut self.conversation)
                 .await?;
 
-            let message = crate::retry::retry_with_config(
-                &self.config.clone().retry.unwrap_or_default(),
-                || {
-                    self.execute_chat_turn(
-                        &model_id,
-                        context.clone(),
-                        context.is_reasoning_supported(),
+            let message = match self.chat_with_retry(&model_id, &context).await {
+                Err(error) if is_context_overflow(&error) => {
+                    // Compact once and resend, failing the turn if it still doesn't fit
+                    let Some(compacted) = self.compact_on_overflow(&context)? else {
+                        return Err(error);
+                    };
+                    warn!(
+                        agent_id = %self.agent.id,
+                        model_id = %model_id,
+                        "Context overflow reported by provider, retrying after compaction"
+                    );
+                    self.send(
+                        TitleFormat::info("Context overflow")
+                            .sub_title("Compacted the conversation, retrying")
+                            .into(),
                     )
-                },
-                self.sender.as_ref().map(|sender| {
-                    let sender = sender.clone();
-                    let agent_id = self.agent.id.clone();
-                    let model_id = model_id.clone();
-                    move |error: &anyhow::Error, duration: Duration| {
-                        let root_cause = error.root_cause();
-                        // Log retry attempts - critical for debugging API failures
-                        tracing::error!(
-                            agent_id = %agent_id,
-                            error = ?root_cause,
-                            model = %model_id,
-                            "Retry attempt due to error"
-                        );
-                        let retry_event =
-                            ChatResponse::RetryAttempt { cause: error.into(), duration };
-                        let _ = sender.try_send(Ok(retry_event));
-                    }
-                }),
-            )
-            .await?;
+                    .await?;
+                    context = compacted;
+                    self.conversation.context = Some(context.clone());
+                    self.services.update(self.conversation.clone()).await?;
+                    self.chat_with_retry(&model_id, &context).await?
+                }
+                result => result?,
+            };
 
             // Fire the Response lifecycle event
             let response_event = LifecycleEvent::Response(EventData::new(
@@ -324,6 +440,9 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
                 .execute_tool_calls(&message.tool_calls, &tool_context)
                 .await?;
 
+            // A pause stops the turn once the tool calls in progress are done
+            let paused = !is_complete && self.pause.is_requested();
+
             // Update context from conversation after response / tool-call hooks run
             if let Some(updated_context) = &self.conversation.context {
                 context = updated_context.clone();
@@ -357,7 +476,11 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
                 message.phase,
             );
 
-            if self.error_tracker.limit_reached() {
+            if paused {
+                self.send(ChatResponse::Interrupt { reason: InterruptionReason::Paused })
+                    .await?;
+                should_yield = true;
+            } else if self.error_tracker.limit_reached() {
                 self.send(ChatResponse::Interrupt {
                     reason: InterruptionReason::MaxToolFailurePerTurnLimitReached {
                         limit: *self.error_tracker.limit() as u64,
@@ -404,8 +527,8 @@ impl<S: AgentService + EnvironmentInfra<Config = forge_config::ForgeConfig>> Orc
             })?;
 
             // If completing (should_yield is due), fire End hook and check if
-            // it adds messages
-            if should_yield {
+            // it adds messages. A paused turn waits for the user instead.
+            if should_yield && !paused {
                 let end_count_before = self.conversation.len();
                 self.hook
                     .handle(
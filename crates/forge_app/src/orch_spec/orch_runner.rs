@@ -132,6 +132,7 @@ impl Runner {
         let orch = Orchestrator::new(services.clone(), conversation, agent, setup.config.clone())
             .error_tracker(ToolErrorTracker::new(3))
             .tool_definitions(system_tools)
+            .pause(setup.pause.clone())
             .hook(Arc::new(
                 Hook::default()
                     .on_request(DoomLoopDetector::default())
@@ -6,8 +6,8 @@ use derive_setters::Setters;
 use forge_config::ForgeConfig;
 use forge_domain::{
     Agent, AgentId, Attachment, ChatCompletionMessage, ChatResponse, Conversation, Environment,
-    Event, File, MessageEntry, Metrics, ModelId, ProviderId, Role, Template, ToolCallFull,
-    ToolDefinition, ToolResult,
+    Event, File, MessageEntry, Metrics, ModelId, PauseSignal, ProviderId, Role, Template,
+    ToolCallFull, ToolDefinition, ToolResult,
 };
 
 use crate::ShellOutput;
@@ -43,6 +43,8 @@ pub struct TestContext {
     /// ForgeConfig used to populate TemplateConfig for
     /// system prompt rendering in tests.
     pub config: ForgeConfig,
+    /// Pause signal shared with the orchestrator under test
+    pub pause: PauseSignal,
 }
 
 impl Default for TestContext {
@@ -81,6 +83,7 @@ impl Default for TestContext {
                 ToolDefinition::new("fs_read"),
                 ToolDefinition::new("fs_write"),
             ],
+            pause: Default::default(),
         }
     }
 }
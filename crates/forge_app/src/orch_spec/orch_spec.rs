@@ -1,6 +1,6 @@
 use forge_domain::{
-    ChatCompletionMessage, ChatResponse, Content, EventValue, FinishReason, ReasoningConfig, Role,
-    ToolCallArguments, ToolCallFull, ToolOutput, ToolResult,
+    ChatCompletionMessage, ChatResponse, Content, EventValue, FinishReason, InterruptionReason,
+    ReasoningConfig, Role, ToolCallArguments, ToolCallFull, ToolOutput, ToolResult,
 };
 use pretty_assertions::assert_eq;
 use serde_json::json;
@@ -714,3 +714,49 @@ async fn test_complete_when_empty_todos() {
         "Should have TaskComplete when no todos exist"
     );
 }
+
+#[tokio::test]
+async fn test_pause_skips_pending_tool_calls_and_stops_the_turn() {
+    let tool_call = ToolCallFull::new("fs_read")
+        .arguments(ToolCallArguments::from(json!({"path": "test.txt"})));
+    let tool_result = ToolResult::new("fs_read").output(Ok(ToolOutput::text("file content")));
+
+    let mut ctx = TestContext::default()
+        .mock_tool_call_responses(vec![(tool_call.clone(), tool_result)])
+        .mock_assistant_responses(vec![
+            ChatCompletionMessage::assistant("Reading file").tool_calls(vec![tool_call.into()]),
+            ChatCompletionMessage::assistant("File read successfully")
+                .finish_reason(FinishReason::Stop),
+        ]);
+    ctx.pause.request();
+
+    ctx.run("Read a file").await.unwrap();
+
+    let responses = ctx
+        .output
+        .chat_responses
+        .iter()
+        .filter_map(|r| r.as_ref().ok())
+        .collect::<Vec<_>>();
+    let actual = (
+        responses
+            .iter()
+            .any(|response| matches!(response, ChatResponse::ToolCallStart { .. })),
+        responses.iter().any(|response| {
+            matches!(
+                response,
+                ChatResponse::Interrupt { reason: InterruptionReason::Paused }
+            )
+        }),
+        responses
+            .iter()
+            .any(|response| matches!(response, ChatResponse::TaskComplete)),
+        ctx.output
+            .context_messages()
+            .iter()
+            .filter(|message| message.has_role(Role::Assistant))
+            .count(),
+    );
+    let expected = (false, true, false, 1);
+    assert_eq!(actual, expected);
+}
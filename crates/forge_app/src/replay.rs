@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use forge_domain::{
+    ChatResponseContent, Context, ContextMessage, Environment, EventValue, Role, TitleFormat,
+    ToolCallFull, ToolCallId, ToolCatalog,
+};
+
+use crate::fmt::content::FormatContent;
+use crate::fmt::fmt_output::file_diff;
+use crate::utils::format_display_path;
+
+/// Re-renders a stored conversation as the content shown while it ran, one
+/// list per turn. A turn starts with a prompt of the user and holds the
+/// replies of the agent along with its tool calls and the diffs of the edits
+/// they made. Diffs are rebuilt from the tool calls, so a patch shows the
+/// replaced text rather than the whole file, and are only shown for calls
+/// whose result, matched by call id, is a success.
+pub fn replay_turns(context: &Context, env: &Environment) -> Vec<Vec<ChatResponseContent>> {
+    let succeeded = context
+        .messages
+        .iter()
+        .filter_map(|message| match &**message {
+            ContextMessage::Tool(result) if !result.is_error() => result.call_id.as_ref(),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let mut turns: Vec<Vec<ChatResponseContent>> = Vec::new();
+    for message in &context.messages {
+        match &**message {
+            ContextMessage::Text(message) if message.role == Role::User => {
+                // Messages added by Forge rather than typed by the user have no raw content
+                let Some(prompt) = message.raw_content.as_ref().map(prompt_text) else {
+                    continue;
+                };
+                let quoted = prompt
+                    .lines()
+                    .map(|line| format!("> {line}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                turns.push(vec![ChatResponseContent::Markdown {
+                    text: format!("{quoted}\n\n"),
+                    partial: false,
+                }]);
+            }
+            ContextMessage::Text(message) if message.role == Role::Assistant => {
+                let Some(turn) = turns.last_mut() else {
+                    continue;
+                };
+                if !message.content.trim().is_empty() {
+                    turn.push(ChatResponseContent::Markdown {
+                        text: format!("{}\n\n", message.content),
+                        partial: false,
+                    });
+                }
+                for call in message.tool_calls.iter().flatten() {
+                    turn.extend(tool_call_contents(call, env, &succeeded));
+                }
+            }
+            ContextMessage::Tool(result) if result.is_error() => {
+                if let Some(turn) = turns.last_mut() {
+                    turn.push(ChatResponseContent::ToolInput(TitleFormat::error(format!(
+                        "{} failed",
+                        result.name.as_str()
+                    ))));
+                }
+            }
+            _ => {}
+        }
+    }
+    turns
+}
+
+/// Text of a prompt as the user typed it
+fn prompt_text(value: &EventValue) -> String {
+    match value {
+        EventValue::Text(prompt) => prompt.as_str().to_string(),
+        EventValue::Command(command) => std::iter::once(format!("/{}", command.name))
+            .chain(command.parameters.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Title of a tool call followed by the diffs of the edits it made, which are
+/// left out unless the call is among the `succeeded` ones
+fn tool_call_contents(
+    call: &ToolCallFull,
+    env: &Environment,
+    succeeded: &HashSet<&ToolCallId>,
+) -> Vec<ChatResponseContent> {
+    let Ok(tool) = ToolCatalog::try_from(call.clone()) else {
+        return vec![TitleFormat::debug(call.name.as_str()).into()];
+    };
+    let applied = call
+        .call_id
+        .as_ref()
+        .is_some_and(|call_id| succeeded.contains(call_id));
+
+    match &tool {
+        // The file exists by now, so whether the write created it can't be
+        // read from the disk
+        ToolCatalog::Write(input) => {
+            let title = if input.overwrite {
+                "Overwrite"
+            } else {
+                "Create"
+            };
+            let display_path = format_display_path(Path::new(&input.file_path), &env.cwd);
+            let mut contents = vec![TitleFormat::debug(title).sub_title(display_path).into()];
+            if applied && !input.overwrite {
+                contents.push(file_diff(&input.file_path, "", &input.content));
+            }
+            contents
+        }
+        ToolCatalog::Patch(input) => tool
+            .to_content(env)
+            .into_iter()
+            .chain(
+                applied.then(|| file_diff(&input.file_path, &input.old_string, &input.new_string)),
+            )
+            .collect(),
+        ToolCatalog::MultiPatch(input) => {
+            tool.to_content(env)
+                .into_iter()
+                .chain(
+                    input.edits.iter().filter(|_| applied).map(|edit| {
+                        file_diff(&input.file_path, &edit.old_string, &edit.new_string)
+                    }),
+                )
+                .collect()
+        }
+        _ => tool.to_content(env).into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use fake::{Fake, Faker};
+    use forge_domain::{TextMessage, ToolCallArguments, ToolCallId, ToolResult};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_replay_turns() {
+        let env: Environment = Faker.fake();
+        let env = env.cwd(PathBuf::from("/project"));
+        let failed = ToolCallFull::new("patch")
+            .call_id(ToolCallId::new("call_1"))
+            .arguments(ToolCallArguments::from_json(
+                r#"{"file_path": "/project/src/lib.rs", "old_string": "a", "new_string": "b"}"#,
+            ));
+        let applied = ToolCallFull::new("patch")
+            .call_id(ToolCallId::new("call_2"))
+            .arguments(ToolCallArguments::from_json(
+                r#"{"file_path": "/project/src/main.rs", "old_string": "a", "new_string": "b"}"#,
+            ));
+        let fixture = Context::default()
+            .add_message(ContextMessage::system("You are Forge"))
+            .add_message(ContextMessage::Text(
+                TextMessage::new(Role::User, "<task>Rename a</task>")
+                    .raw_content(EventValue::text("Rename a")),
+            ))
+            .add_message(ContextMessage::assistant(
+                "Renaming it",
+                None,
+                None,
+                Some(vec![failed, applied]),
+            ))
+            .add_message(ContextMessage::tool_result(
+                ToolResult::new("patch")
+                    .call_id(ToolCallId::new("call_1"))
+                    .failure(anyhow::anyhow!("No match")),
+            ))
+            .add_message(ContextMessage::tool_result(
+                ToolResult::new("patch")
+                    .call_id(ToolCallId::new("call_2"))
+                    .success("Patched"),
+            ))
+            .add_message(ContextMessage::user("Todo reminder", None))
+            .add_message(ContextMessage::assistant("Done", None, None, None));
+
+        // Titles carry the time they were created at, so only their text is compared
+        let actual = replay_turns(&fixture, &env)
+            .into_iter()
+            .map(|turn| {
+                turn.into_iter()
+                    .map(|content| match content {
+                        ChatResponseContent::ToolInput(title) => {
+                            format!("{} {}", title.title, title.sub_title.unwrap_or_default())
+                        }
+                        ChatResponseContent::ToolOutput(text)
+                        | ChatResponseContent::Markdown { text, .. } => text,
+                        ChatResponseContent::FileDiff(diff) => {
+                            format!(
+                                "{} +{} -{}",
+                                diff.path, diff.lines_added, diff.lines_removed
+                            )
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let expected = vec![vec![
+            "> Rename a\n\n".to_string(),
+            "Renaming it\n\n".to_string(),
+            "Replace src/lib.rs".to_string(),
+            "Replace src/main.rs".to_string(),
+            "/project/src/main.rs +1 -1".to_string(),
+            "patch failed ".to_string(),
+            "Done\n\n".to_string(),
+        ]];
+        assert_eq!(actual, expected);
+    }
+}
@@ -5,10 +5,12 @@ use bytes::Bytes;
 use derive_setters::Setters;
 use forge_domain::{
     AgentId, AnyProvider, Attachment, AuthContextRequest, AuthContextResponse, AuthMethod,
-    ChatCompletionMessage, CommandOutput, Context, Conversation, ConversationId, File, FileInfo,
-    FileStatus, Image, McpConfig, McpServers, Model, ModelId, Node, Provider, ProviderId,
-    ResultStream, Scope, SearchParams, SyncProgress, SyntaxError, Template, ToolCallFull,
-    ToolOutput, WorkspaceAuth, WorkspaceId, WorkspaceInfo,
+    ChatCompletionMessage, CommandOutput, Context, Conversation, ConversationId,
+    ConversationLockHolder, File, FileInfo, FileStatus, GitState, Image, McpConfig, McpServers,
+    Model, ModelId, Node, Project, Provider, ProviderId, PruneReport, PullRequestDraft,
+    RemoteRepository, RepoMap, ResultStream, RetentionPolicy, Scope, SearchParams, Snapshot,
+    SnapshotTag, SyncProgress, SyntaxError, Template, TokenCount, TokenCounter, Tokenizer,
+    ToolCallFull, ToolOutput, UnifiedDiff, WorkspaceAuth, WorkspaceId, WorkspaceInfo,
 };
 use reqwest::Response;
 use reqwest::header::HeaderMap;
@@ -98,6 +100,16 @@ pub enum MatchResult {
         before_context: Vec<String>,
         after_context: Vec<String>,
     },
+    /// A match located by line and column, for the json output mode
+    Located {
+        line_number: usize,
+        /// 1-based byte offset of the match in the line, like rg --column
+        column: usize,
+        matched: String,
+        line: String,
+        before_context: Vec<String>,
+        after_context: Vec<String>,
+    },
 }
 
 #[derive(Debug)]
@@ -177,7 +189,29 @@ pub trait ProviderService: Send + Sync {
     async fn migrate_env_credentials(
         &self,
     ) -> anyhow::Result<Option<forge_domain::MigrationResult>>;
+    /// Describes the cache of responses to deterministic requests
+    async fn response_cache_info(&self) -> anyhow::Result<forge_domain::ResponseCacheInfo>;
+    /// Removes every cached response and returns how many there were
+    async fn clear_response_cache(&self) -> anyhow::Result<usize>;
 }
+/// Counts tokens the way the model in use would, so that compaction and
+/// context display agree on sizes.
+#[async_trait::async_trait]
+pub trait TokenCounterService: Send + Sync {
+    /// Returns a local counter with the tokenizer closest to `model`
+    fn token_counter(&self, model: &ModelId) -> Tokenizer;
+
+    /// Counts the input tokens of `context` for `model`. Asks `provider` for
+    /// an exact count when it exposes a counting API and falls back to the
+    /// local tokenizer otherwise.
+    async fn count_tokens(
+        &self,
+        context: &Context,
+        model: &ModelId,
+        provider: Option<Provider<Url>>,
+    ) -> TokenCount;
+}
+
 /// Manages user preferences for default providers and models.
 #[async_trait::async_trait]
 pub trait AppConfigService: Send + Sync {
@@ -207,6 +241,10 @@ pub trait AppConfigService: Send + Sync {
     /// Gets the current reasoning effort setting.
     async fn get_reasoning_effort(&self) -> anyhow::Result<Option<forge_domain::Effort>>;
 
+    /// Gets the reasoning effort chosen for the current session, which takes
+    /// precedence over the effort of agents.
+    async fn get_session_reasoning_effort(&self) -> anyhow::Result<Option<forge_domain::Effort>>;
+
     /// Applies one or more configuration mutations atomically.
     ///
     /// Each operation in `ops` is applied in order, and the result is
@@ -258,6 +296,26 @@ pub trait ConversationService: Send + Sync {
 
     /// Permanently deletes a conversation
     async fn delete_conversation(&self, conversation_id: &ConversationId) -> anyhow::Result<()>;
+
+    /// Applies a retention policy to the stored conversations, deleting the
+    /// ones that violate it unless `dry_run` is set. `keep` is never pruned,
+    /// like a pinned conversation.
+    async fn prune_conversations(
+        &self,
+        policy: &RetentionPolicy,
+        keep: Option<&ConversationId>,
+        dry_run: bool,
+    ) -> anyhow::Result<PruneReport>;
+
+    /// Locks a conversation for this process, returning the process holding
+    /// it instead when another one already does
+    async fn lock_conversation(
+        &self,
+        id: &ConversationId,
+    ) -> anyhow::Result<Option<ConversationLockHolder>>;
+
+    /// Releases a lock taken by [`ConversationService::lock_conversation`]
+    async fn unlock_conversation(&self, id: &ConversationId) -> anyhow::Result<()>;
 }
 
 #[async_trait::async_trait]
@@ -278,6 +336,10 @@ pub trait AttachmentService {
 #[async_trait::async_trait]
 pub trait CustomInstructionsService: Send + Sync {
     async fn get_custom_instructions(&self) -> Vec<String>;
+
+    /// Instruction files loaded into the system prompt, in the order they
+    /// are applied
+    async fn get_instruction_files(&self) -> Vec<forge_domain::InstructionFile>;
 }
 
 /// Service for indexing workspaces for semantic search
@@ -331,6 +393,18 @@ pub trait FileDiscoveryService: Send + Sync {
     /// Lists all entries (files and directories) in the current directory
     /// Returns a sorted vector of File entries with directories first
     async fn list_current_directory(&self) -> anyhow::Result<Vec<File>>;
+
+    /// Detects the subprojects of the workspace in the current directory
+    async fn list_projects(&self) -> anyhow::Result<Vec<Project>>;
+
+    /// Builds a ranked map of the key files of the workspace, their symbols
+    /// and the imports between them, sized to `max_tokens` as counted by
+    /// `counter`
+    async fn repo_map(
+        &self,
+        max_tokens: usize,
+        counter: &dyn TokenCounter,
+    ) -> anyhow::Result<RepoMap>;
 }
 
 #[async_trait::async_trait]
@@ -383,6 +457,10 @@ pub trait FsReadService: Send + Sync {
         start_line: Option<u64>,
         end_line: Option<u64>,
     ) -> anyhow::Result<ReadOutput>;
+
+    /// Reads only the lines of a file declaring functions, types and other
+    /// symbols. The returned content is already prefixed with line numbers.
+    async fn outline(&self, path: String) -> anyhow::Result<ReadOutput>;
 }
 
 #[async_trait::async_trait]
@@ -432,6 +510,52 @@ pub trait FsUndoService: Send + Sync {
     async fn undo(&self, path: String) -> anyhow::Result<FsUndoOutput>;
 }
 
+#[async_trait::async_trait]
+pub trait FileSnapshotService: Send + Sync {
+    /// Diffs two snapshots of the file at `file_path`, numbered from 0 for the
+    /// oldest, without restoring either of them
+    async fn diff_snapshots(
+        &self,
+        file_path: &Path,
+        from_index: usize,
+        to_index: usize,
+    ) -> anyhow::Result<UnifiedDiff>;
+
+    /// Restores every file changed since `timestamp`, a duration since the
+    /// Unix epoch, to its content at that time and returns the restored files
+    async fn restore_all_at(&self, timestamp: Duration) -> anyhow::Result<Vec<PathBuf>>;
+
+    /// Tags the snapshots of `file_path` taken since `since` with the agent
+    /// turn that triggered them
+    async fn tag_snapshots(
+        &self,
+        file_path: &Path,
+        since: Duration,
+        tag: &SnapshotTag,
+    ) -> anyhow::Result<()>;
+
+    /// Lists the snapshots taken before the edits of a conversation, from the
+    /// oldest to the most recent
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> anyhow::Result<Vec<Snapshot>>;
+
+    /// Writes a bundle of the snapshots and current content of the files at
+    /// `paths`, recorded relative to `root`, to `output` and returns the
+    /// number of bundled snapshots
+    async fn export_bundle(
+        &self,
+        paths: &[PathBuf],
+        root: &Path,
+        output: &Path,
+    ) -> anyhow::Result<usize>;
+
+    /// Applies the bundle at `bundle` to the files under `root` and returns
+    /// the changed files
+    async fn import_bundle(&self, bundle: &Path, root: &Path) -> anyhow::Result<Vec<PathBuf>>;
+}
+
 #[async_trait::async_trait]
 pub trait NetFetchService: Send + Sync {
     /// Fetches content from a URL and returns it as a string.
@@ -458,6 +582,25 @@ pub trait AuthService: Send + Sync {
     async fn user_usage(&self, api_key: &str) -> anyhow::Result<UserUsage>;
 }
 
+#[async_trait::async_trait]
+pub trait PullRequestService: Send + Sync {
+    /// Opens a pull request, a merge request on GitLab, from the `head` branch
+    /// into `base` and returns its URL
+    async fn create_pull_request(
+        &self,
+        repository: &RemoteRepository,
+        head: &str,
+        base: &str,
+        draft: &PullRequestDraft,
+    ) -> anyhow::Result<Url>;
+}
+
+#[async_trait::async_trait]
+pub trait GitStateService: Send + Sync {
+    /// Reads the git state of the workspace, or `None` outside a repository
+    async fn git_state(&self) -> anyhow::Result<Option<GitState>>;
+}
+
 #[async_trait::async_trait]
 pub trait AgentRegistry: Send + Sync {
     /// Get the active agent ID
@@ -495,6 +638,13 @@ pub trait PolicyService: Send + Sync {
         &self,
         operation: &forge_domain::PermissionOperation,
     ) -> anyhow::Result<PolicyDecision>;
+
+    /// Returns true when the policies require the user to confirm the
+    /// operation before it is performed
+    async fn requires_confirmation(
+        &self,
+        operation: &forge_domain::PermissionOperation,
+    ) -> anyhow::Result<bool>;
 }
 
 /// Skill fetch service
@@ -559,16 +709,20 @@ pub trait Services: Send + Sync + 'static + Clone + EnvironmentInfra {
     type FsSearchService: FsSearchService;
     type FollowUpService: FollowUpService;
     type FsUndoService: FsUndoService;
+    type FileSnapshotService: FileSnapshotService;
     type NetFetchService: NetFetchService;
     type ShellService: ShellService;
     type McpService: McpService;
     type AuthService: AuthService;
+    type PullRequestService: PullRequestService;
+    type GitStateService: GitStateService;
     type AgentRegistry: AgentRegistry;
     type CommandLoaderService: CommandLoaderService;
     type PolicyService: PolicyService;
     type ProviderAuthService: ProviderAuthService;
     type WorkspaceService: WorkspaceService;
     type SkillFetchService: SkillFetchService;
+    type TokenCounterService: TokenCounterService;
 
     fn provider_service(&self) -> &Self::ProviderService;
     fn config_service(&self) -> &Self::AppConfigService;
@@ -586,17 +740,21 @@ pub trait Services: Send + Sync + 'static + Clone + EnvironmentInfra {
     fn fs_search_service(&self) -> &Self::FsSearchService;
     fn follow_up_service(&self) -> &Self::FollowUpService;
     fn fs_undo_service(&self) -> &Self::FsUndoService;
+    fn file_snapshot_service(&self) -> &Self::FileSnapshotService;
     fn net_fetch_service(&self) -> &Self::NetFetchService;
     fn shell_service(&self) -> &Self::ShellService;
     fn mcp_service(&self) -> &Self::McpService;
     fn custom_instructions_service(&self) -> &Self::CustomInstructionsService;
     fn auth_service(&self) -> &Self::AuthService;
+    fn pull_request_service(&self) -> &Self::PullRequestService;
+    fn git_state_service(&self) -> &Self::GitStateService;
     fn agent_registry(&self) -> &Self::AgentRegistry;
     fn command_loader_service(&self) -> &Self::CommandLoaderService;
     fn policy_service(&self) -> &Self::PolicyService;
     fn provider_auth_service(&self) -> &Self::ProviderAuthService;
     fn workspace_service(&self) -> &Self::WorkspaceService;
     fn skill_fetch_service(&self) -> &Self::SkillFetchService;
+    fn token_counter_service(&self) -> &Self::TokenCounterService;
 }
 
 #[async_trait::async_trait]
@@ -635,6 +793,28 @@ impl<I: Services> ConversationService for I {
             .delete_conversation(conversation_id)
             .await
     }
+
+    async fn prune_conversations(
+        &self,
+        policy: &RetentionPolicy,
+        keep: Option<&ConversationId>,
+        dry_run: bool,
+    ) -> anyhow::Result<PruneReport> {
+        self.conversation_service()
+            .prune_conversations(policy, keep, dry_run)
+            .await
+    }
+
+    async fn lock_conversation(
+        &self,
+        id: &ConversationId,
+    ) -> anyhow::Result<Option<ConversationLockHolder>> {
+        self.conversation_service().lock_conversation(id).await
+    }
+
+    async fn unlock_conversation(&self, id: &ConversationId) -> anyhow::Result<()> {
+        self.conversation_service().unlock_conversation(id).await
+    }
 }
 #[async_trait::async_trait]
 impl<I: Services> ProviderService for I {
@@ -677,6 +857,32 @@ impl<I: Services> ProviderService for I {
     ) -> anyhow::Result<Option<forge_domain::MigrationResult>> {
         self.provider_service().migrate_env_credentials().await
     }
+
+    async fn response_cache_info(&self) -> anyhow::Result<forge_domain::ResponseCacheInfo> {
+        self.provider_service().response_cache_info().await
+    }
+
+    async fn clear_response_cache(&self) -> anyhow::Result<usize> {
+        self.provider_service().clear_response_cache().await
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: Services> TokenCounterService for I {
+    fn token_counter(&self, model: &ModelId) -> Tokenizer {
+        self.token_counter_service().token_counter(model)
+    }
+
+    async fn count_tokens(
+        &self,
+        context: &Context,
+        model: &ModelId,
+        provider: Option<Provider<Url>>,
+    ) -> TokenCount {
+        self.token_counter_service()
+            .count_tokens(context, model, provider)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -740,6 +946,20 @@ impl<I: Services> FileDiscoveryService for I {
     async fn list_current_directory(&self) -> anyhow::Result<Vec<File>> {
         self.file_discovery_service().list_current_directory().await
     }
+
+    async fn list_projects(&self) -> anyhow::Result<Vec<Project>> {
+        self.file_discovery_service().list_projects().await
+    }
+
+    async fn repo_map(
+        &self,
+        max_tokens: usize,
+        counter: &dyn TokenCounter,
+    ) -> anyhow::Result<RepoMap> {
+        self.file_discovery_service()
+            .repo_map(max_tokens, counter)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -805,6 +1025,10 @@ impl<I: Services> FsReadService for I {
             .read(path, start_line, end_line)
             .await
     }
+
+    async fn outline(&self, path: String) -> anyhow::Result<ReadOutput> {
+        self.fs_read_service().outline(path).await
+    }
 }
 #[async_trait::async_trait]
 impl<I: Services> ImageReadService for I {
@@ -848,6 +1072,61 @@ impl<I: Services> FsUndoService for I {
     }
 }
 
+#[async_trait::async_trait]
+impl<I: Services> FileSnapshotService for I {
+    async fn diff_snapshots(
+        &self,
+        file_path: &Path,
+        from_index: usize,
+        to_index: usize,
+    ) -> anyhow::Result<UnifiedDiff> {
+        self.file_snapshot_service()
+            .diff_snapshots(file_path, from_index, to_index)
+            .await
+    }
+
+    async fn restore_all_at(&self, timestamp: Duration) -> anyhow::Result<Vec<PathBuf>> {
+        self.file_snapshot_service().restore_all_at(timestamp).await
+    }
+
+    async fn tag_snapshots(
+        &self,
+        file_path: &Path,
+        since: Duration,
+        tag: &SnapshotTag,
+    ) -> anyhow::Result<()> {
+        self.file_snapshot_service()
+            .tag_snapshots(file_path, since, tag)
+            .await
+    }
+
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> anyhow::Result<Vec<Snapshot>> {
+        self.file_snapshot_service()
+            .list_snapshots_for_conversation(conversation_id)
+            .await
+    }
+
+    async fn export_bundle(
+        &self,
+        paths: &[PathBuf],
+        root: &Path,
+        output: &Path,
+    ) -> anyhow::Result<usize> {
+        self.file_snapshot_service()
+            .export_bundle(paths, root, output)
+            .await
+    }
+
+    async fn import_bundle(&self, bundle: &Path, root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        self.file_snapshot_service()
+            .import_bundle(bundle, root)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl<I: Services> NetFetchService for I {
     async fn fetch(&self, url: String, raw: Option<bool>) -> anyhow::Result<HttpResponse> {
@@ -879,6 +1158,12 @@ impl<I: Services> CustomInstructionsService for I {
             .get_custom_instructions()
             .await
     }
+
+    async fn get_instruction_files(&self) -> Vec<forge_domain::InstructionFile> {
+        self.custom_instructions_service()
+            .get_instruction_files()
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -892,6 +1177,28 @@ impl<I: Services> AuthService for I {
     }
 }
 
+#[async_trait::async_trait]
+impl<I: Services> PullRequestService for I {
+    async fn create_pull_request(
+        &self,
+        repository: &RemoteRepository,
+        head: &str,
+        base: &str,
+        draft: &PullRequestDraft,
+    ) -> anyhow::Result<Url> {
+        self.pull_request_service()
+            .create_pull_request(repository, head, base, draft)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: Services> GitStateService for I {
+    async fn git_state(&self) -> anyhow::Result<Option<GitState>> {
+        self.git_state_service().git_state().await
+    }
+}
+
 /// HTTP service trait for making HTTP requests
 #[async_trait::async_trait]
 pub trait HttpClientService: Send + Sync + 'static {
@@ -952,6 +1259,13 @@ impl<I: Services> PolicyService for I {
             .check_operation_permission(operation)
             .await
     }
+
+    async fn requires_confirmation(
+        &self,
+        operation: &forge_domain::PermissionOperation,
+    ) -> anyhow::Result<bool> {
+        self.policy_service().requires_confirmation(operation).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -979,6 +1293,10 @@ impl<I: Services> AppConfigService for I {
         self.config_service().get_reasoning_effort().await
     }
 
+    async fn get_session_reasoning_effort(&self) -> anyhow::Result<Option<forge_domain::Effort>> {
+        self.config_service().get_session_reasoning_effort().await
+    }
+
     async fn update_config(&self, ops: Vec<forge_domain::ConfigOperation>) -> anyhow::Result<()> {
         self.config_service().update_config(ops).await
     }
@@ -3,14 +3,14 @@ use std::sync::Arc;
 
 use derive_setters::Setters;
 use forge_domain::{
-    Agent, Conversation, Environment, Extension, ExtensionStat, File, Model, SystemContext,
-    Template, TemplateConfig, ToolCatalog, ToolDefinition, ToolUsagePrompt,
+    Agent, Conversation, Environment, Extension, ExtensionStat, File, GitState, Model, SecretFiles,
+    SystemContext, Template, TemplateConfig, ToolCatalog, ToolDefinition, ToolUsagePrompt,
 };
 use serde_json::{Map, Value, json};
 use strum::IntoEnumIterator;
 use tracing::debug;
 
-use crate::{ShellService, SkillFetchService, TemplateEngine};
+use crate::{IncludeRoot, ShellService, SkillFetchService, TemplateEngine};
 
 #[derive(Setters)]
 pub struct SystemPrompt<S> {
@@ -23,8 +23,14 @@ pub struct SystemPrompt<S> {
     custom_instructions: Vec<String>,
     /// Maximum number of file extensions shown in the workspace summary.
     max_extensions: usize,
+    /// Rendered repository map, if enabled.
+    repo_map: Option<String>,
     /// Configuration values passed into tool description templates.
     template_config: TemplateConfig,
+    /// Git state of the workspace, exposed to the template as `git`.
+    git: Option<GitState>,
+    /// Files `{{include}}` refuses to read from the working directory.
+    secret_files: SecretFiles,
 }
 
 impl<S: SkillFetchService + ShellService> SystemPrompt<S> {
@@ -38,7 +44,10 @@ impl<S: SkillFetchService + ShellService> SystemPrompt<S> {
             files: Vec::default(),
             custom_instructions: Vec::default(),
             max_extensions: 0,
+            repo_map: None,
             template_config: TemplateConfig::default(),
+            git: None,
+            secret_files: SecretFiles::default(),
         }
     }
 
@@ -116,13 +125,19 @@ impl<S: SkillFetchService + ShellService> SystemPrompt<S> {
                 model: None,
                 tool_names,
                 extensions,
+                repo_map: self.repo_map.clone(),
                 agents: vec![],
                 config: None,
+                git: self.git.clone(),
             };
 
-            let static_block = TemplateEngine::default()
-                .render_template(Template::new(&system_prompt.template), &ctx)?;
-            let non_static_block = TemplateEngine::default()
+            let engine = TemplateEngine::default().include_root(IncludeRoot::new(
+                self.environment.cwd.clone(),
+                self.secret_files.clone(),
+            ));
+            let static_block =
+                engine.render_template(Template::new(&system_prompt.template), &ctx)?;
+            let non_static_block = engine
                 .render_template(Template::new("{{> forge-custom-agent-template.md }}"), &ctx)?;
 
             context.set_system_messages(vec![static_block, non_static_block])
@@ -1,20 +1,45 @@
-use std::sync::LazyLock;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, OnceLock};
 
-use forge_domain::Template;
+use forge_domain::{Template, ToolCatalog};
+use forge_embed::TemplateOverride;
 use handlebars::{Handlebars, no_escape};
 use include_dir::{Dir, include_dir};
+use serde_json::{Map, Value, json};
+use strum::IntoEnumIterator;
+
+use crate::template_helpers::IncludeRoot;
 
 static TEMPLATE_DIR: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/../../templates");
 
+/// Source of the embedded templates, read again on every render in dev mode.
+const TEMPLATE_SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../templates");
+
+/// Dev mode, enabled by setting `FORGE_TEMPLATE_DEV=1` in debug builds,
+/// renders templates from [`TEMPLATE_SOURCE_DIR`] instead of the copies
+/// embedded at compile time so changes to prompts apply without rebuilding the
+/// binary. Release builds always use the embedded templates.
+static DEV_MODE: LazyLock<bool> = LazyLock::new(|| {
+    cfg!(debug_assertions)
+        && std::env::var("FORGE_TEMPLATE_DEV").is_ok_and(|value| value == "1" || value == "true")
+});
+
+/// Directory whose files replace embedded templates of the same name. Unset
+/// (e.g. in tests) means only the embedded templates are used.
+static OVERRIDE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
 /// Creates a new Handlebars instance with all custom helpers registered.
 ///
 /// This function configures a Handlebars instance with:
 /// - The 'inc' helper for incrementing values (useful for 1-based indexing)
 /// - The 'json' helper for serializing values to JSON strings
 /// - The 'contains' helper for checking if an array contains a value
+/// - The helpers in [`crate::template_helpers`] (`today`, `env`, `include` and
+///   `truncate`)
 /// - Strict mode enabled
 /// - No HTML escaping
-/// - All embedded templates registered
+/// - All embedded templates registered, replaced by the user's overrides
+/// - The user's partials from the `partials` directory of the overrides
 ///
 /// This is useful for creating standalone Handlebars instances with consistent
 /// configuration across the application.
@@ -98,9 +123,50 @@ fn create_handlebar() -> Handlebars<'static> {
         ),
     );
 
+    crate::template_helpers::register_helpers(&mut hb);
+
     // Register all embedded templates from the templates directory
     forge_embed::register_templates(&mut hb, &TEMPLATE_DIR);
 
+    // In dev mode the source files replace the embedded copies, which are kept
+    // for any file that fails to parse mid-edit
+    if *DEV_MODE {
+        for (template, error) in
+            forge_embed::register_overrides(&mut hb, &TEMPLATE_DIR, Path::new(TEMPLATE_SOURCE_DIR))
+        {
+            tracing::warn!(
+                template = %template.name,
+                error = ?error,
+                "Using embedded template"
+            );
+        }
+    }
+
+    // Let the user's copies take precedence over the embedded templates
+    if let Some(override_dir) = OVERRIDE_DIR.get() {
+        for (template, error) in
+            forge_embed::register_overrides(&mut hb, &TEMPLATE_DIR, override_dir)
+        {
+            tracing::warn!(
+                template = %template.name,
+                error = ?error,
+                "Ignoring template override"
+            );
+        }
+    }
+
+    // User partials, usable as `{{> name.md}}` in overrides and custom commands
+    if let Some(override_dir) = OVERRIDE_DIR.get() {
+        for (path, error) in forge_embed::register_partials(&mut hb, &override_dir.join("partials"))
+        {
+            tracing::warn!(
+                partial = %path.display(),
+                error = ?error,
+                "Ignoring template partial"
+            );
+        }
+    }
+
     hb
 }
 
@@ -111,14 +177,79 @@ fn create_handlebar() -> Handlebars<'static> {
 /// - The 'inc' helper for incrementing values (useful for 1-based indexing)
 /// - The 'json' helper for serializing values to JSON strings
 /// - The 'contains' helper for checking if an array contains a value
+/// - The helpers in [`crate::template_helpers`] (`today`, `env`, `include` and
+///   `truncate`)
 /// - Strict mode enabled
 /// - No HTML escaping
-/// - All embedded templates registered
+/// - All embedded templates registered, replaced by the user's overrides
+/// - The user's partials from the `partials` directory of the overrides
 ///
 /// Use this instance for template rendering throughout the application to avoid
 /// creating multiple Handlebars instances.
 static HANDLEBARS: LazyLock<Handlebars<'static>> = LazyLock::new(create_handlebar);
 
+/// Sample of the data templates are rendered with at runtime: the system
+/// context of agents, the event of user prompts and the variables of reminders
+/// and retry messages, merged into one object since templates ignore what they
+/// don't use.
+fn sample_context() -> Value {
+    let tool_names: Map<String, Value> = ToolCatalog::iter()
+        .map(|tool| {
+            let name = tool.definition().name.to_string();
+            (name.clone(), json!(name))
+        })
+        .collect();
+
+    json!({
+        "env": {
+            "os": "linux",
+            "cwd": "/home/user/project",
+            "home": "/home/user",
+            "shell": "/bin/bash"
+        },
+        "tool_information": "",
+        "tool_supported": true,
+        "files": [{"path": "src/main.rs", "is_dir": false}],
+        "custom_rules": "Prefer small functions",
+        "supports_parallel_tool_calls": true,
+        "skills": [{"name": "review", "description": "Reviews the pending changes"}],
+        "tool_names": tool_names,
+        "extensions": {
+            "extension_stats": [{"extension": "rs", "count": 10, "percentage": "100.00"}],
+            "max_extensions": 15,
+            "git_tracked_files": 10,
+            "total_extensions": 1,
+            "remaining_percentage": "0.00"
+        },
+        "git": {
+            "branch": "main",
+            "dirty_files": ["src/main.rs"],
+            "last_commit_message": "Add the parser",
+            "ahead": 1,
+            "behind": 0
+        },
+        "event": {"name": "forge/user_task_init", "value": "Add a test"},
+        "suggestions": [],
+        "variables": {},
+        "current_date": "2025-01-01",
+        "consecutive_calls": 3,
+        "attempts_left": 2,
+        "todos": [{"content": "Write tests", "status": "pending"}],
+        "messages": [{"role": "user", "contents": [{"text": "Add a test"}]}],
+        "contents": []
+    })
+}
+
+/// Outcome of rendering one template with sample data in
+/// [`TemplateEngine::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateCheck {
+    /// Name of the template
+    pub name: String,
+    /// Why rendering failed, `None` when it succeeded
+    pub error: Option<String>,
+}
+
 /// A wrapper around the Handlebars template engine providing a simplified API.
 ///
 /// This struct provides a clean interface for template rendering using the
@@ -129,11 +260,24 @@ pub struct TemplateEngine<'a> {
 
 impl Default for TemplateEngine<'_> {
     fn default() -> Self {
-        Self { handlebar: HANDLEBARS.clone() }
+        // Dev mode reads the templates again for every engine, which is created
+        // per render
+        let handlebar = if *DEV_MODE {
+            create_handlebar()
+        } else {
+            HANDLEBARS.clone()
+        };
+        Self { handlebar }
     }
 }
 
 impl<'a> TemplateEngine<'a> {
+    /// Lets `{{include}}` read the files of `root`, which it refuses otherwise
+    pub fn include_root(mut self, root: IncludeRoot) -> Self {
+        crate::template_helpers::register_include(&mut self.handlebar, Some(root));
+        self
+    }
+
     /// Renders a template with the provided data.
     pub fn render<V: serde::Serialize>(
         &self,
@@ -154,9 +298,44 @@ impl<'a> TemplateEngine<'a> {
         Ok(self.handlebar.render_template(&template.template, data)?)
     }
 
+    /// Renders every registered template, then each of `templates` given as
+    /// (name, source), against sample data shaped like what templates receive
+    /// at runtime. Catches missing variables and partials before they fail in
+    /// a conversation.
+    pub fn check(&self, templates: Vec<(String, String)>) -> Vec<TemplateCheck> {
+        let sample = sample_context();
+        let mut names: Vec<_> = self.handlebar.get_templates().keys().cloned().collect();
+        names.sort();
+
+        let registered = names.into_iter().map(|name| {
+            let error = self.handlebar.render(&name, &sample).err();
+            TemplateCheck { name, error: error.map(|e| e.to_string()) }
+        });
+        let given = templates.into_iter().map(|(name, source)| {
+            let error = self.handlebar.render_template(&source, &sample).err();
+            TemplateCheck { name, error: error.map(|e| e.to_string()) }
+        });
+        registered.chain(given).collect()
+    }
+
     pub fn handlebar_instance() -> Handlebars<'static> {
         create_handlebar()
     }
+
+    /// Sets the directory whose files override embedded templates of the
+    /// same name. Must be called before the first template is rendered;
+    /// later calls are ignored.
+    pub fn set_override_dir(path: PathBuf) {
+        let _ = OVERRIDE_DIR.set(path);
+    }
+
+    /// Embedded templates replaced by a file in the override directory
+    pub fn overrides() -> Vec<TemplateOverride> {
+        OVERRIDE_DIR
+            .get()
+            .map(|dir| forge_embed::template_overrides(&TEMPLATE_DIR, dir))
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -282,6 +461,46 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_check_renders_embedded_templates() {
+        let fixture = TemplateEngine::default();
+
+        let actual: Vec<_> = fixture
+            .check(vec![])
+            .into_iter()
+            .filter(|check| check.error.is_some())
+            .collect();
+
+        let expected: Vec<TemplateCheck> = vec![];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_check_reports_missing_variables() {
+        let fixture = TemplateEngine::default();
+
+        let actual = fixture.check(vec![
+            (
+                "valid".to_string(),
+                "{{env.cwd}} {{tool_names.read}}".to_string(),
+            ),
+            ("missing".to_string(), "{{unknown_variable}}".to_string()),
+            (
+                "partial".to_string(),
+                "{{> unknown-partial.md}}".to_string(),
+            ),
+        ]);
+        let actual: Vec<_> = actual
+            .iter()
+            .rev()
+            .take(3)
+            .map(|check| (check.name.as_str(), check.error.is_some()))
+            .collect();
+
+        let expected = vec![("partial", true), ("missing", true), ("valid", false)];
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_contains_helper_with_non_array_value() {
         let hb = create_handlebar();
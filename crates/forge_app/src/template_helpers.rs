@@ -0,0 +1,289 @@
+//! Helpers available to prompt templates and custom commands:
+//!
+//! - `{{today}}` - today's date as `YYYY-MM-DD`, or in a chrono format given as
+//!   the first parameter, e.g. `{{today "%A, %B %d"}}`
+//! - `{{env "NAME"}}` - the value of an environment variable, empty when unset,
+//!   or the second parameter when given, e.g. `{{env "EDITOR" "vi"}}`
+//! - `{{include "path"}}` - the content of a file, relative to the working
+//!   directory, cut after `max_tokens` tokens (default 2000), e.g. `{{include
+//!   "docs/style.md" max_tokens=500}}`. Files outside the working directory and
+//!   secret files are refused, as is every file when the engine has no
+//!   [`IncludeRoot`]
+//! - `{{truncate text 80}}` - `text` cut after the given number of characters,
+//!   ending with `…` when cut
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use forge_domain::{SecretFiles, estimate_token_count};
+use handlebars::{
+    Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderErrorReason,
+};
+
+/// Token cap of `{{include}}` when `max_tokens` is not given
+const DEFAULT_INCLUDE_TOKENS: usize = 2000;
+
+/// Files `{{include}}` may read: those inside the working directory that
+/// don't match the secret file patterns
+#[derive(Clone, Debug)]
+pub struct IncludeRoot {
+    cwd: PathBuf,
+    secret_files: SecretFiles,
+}
+
+impl IncludeRoot {
+    pub fn new(cwd: PathBuf, secret_files: SecretFiles) -> Self {
+        Self { cwd, secret_files }
+    }
+
+    /// Resolves `path` against the working directory, refusing files outside
+    /// of it, including through symlinks, and secret files
+    fn resolve(&self, path: &str) -> anyhow::Result<PathBuf> {
+        let root = self.cwd.canonicalize()?;
+        let resolved = root.join(path).canonicalize()?;
+        anyhow::ensure!(
+            resolved.starts_with(&root),
+            "it is outside the working directory"
+        );
+        anyhow::ensure!(
+            !self.secret_files.is_secret(&resolved),
+            "it matches secret_files"
+        );
+        Ok(resolved)
+    }
+
+    fn read(&self, path: &str) -> anyhow::Result<String> {
+        let resolved = self.resolve(path)?;
+        Ok(std::fs::read_to_string(resolved)?)
+    }
+}
+
+/// Registers the helpers documented at the top of this module. `{{include}}`
+/// refuses every file until [`register_include`] gives it a root.
+pub(crate) fn register_helpers(hb: &mut Handlebars<'_>) {
+    hb.register_helper("today", Box::new(today));
+    hb.register_helper("env", Box::new(env));
+    register_include(hb, None);
+    hb.register_helper("truncate", Box::new(truncate));
+}
+
+/// Registers `{{include}}`, reading files from `root`
+pub(crate) fn register_include(hb: &mut Handlebars<'_>, root: Option<IncludeRoot>) {
+    hb.register_helper(
+        "include",
+        Box::new(
+            move |h: &Helper<'_>,
+                  _: &Handlebars<'_>,
+                  _: &Context,
+                  _: &mut RenderContext<'_, '_>,
+                  out: &mut dyn Output|
+                  -> HelperResult { include(root.as_ref(), h, out) },
+        ),
+    );
+}
+
+/// String parameter of a helper at `index`, if given
+fn str_param<'a>(h: &'a Helper<'_>, index: usize) -> Option<&'a str> {
+    h.param(index).and_then(|param| param.value().as_str())
+}
+
+/// Cuts `text` after `max_chars` characters, marking the cut with `suffix`
+fn cut(text: &str, max_chars: usize, suffix: &str) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}{suffix}", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn today(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let format = str_param(h, 0).unwrap_or("%Y-%m-%d");
+    out.write(&chrono::Local::now().format(format).to_string())?;
+    Ok(())
+}
+
+fn env(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let name = str_param(h, 0).ok_or(RenderErrorReason::ParamNotFoundForIndex("env", 0))?;
+    let value = std::env::var(name)
+        .ok()
+        .or_else(|| str_param(h, 1).map(str::to_string))
+        .unwrap_or_default();
+    out.write(&value)?;
+    Ok(())
+}
+
+fn include(root: Option<&IncludeRoot>, h: &Helper<'_>, out: &mut dyn Output) -> HelperResult {
+    let path = str_param(h, 0).ok_or(RenderErrorReason::ParamNotFoundForIndex("include", 0))?;
+    let max_tokens = h
+        .hash_get("max_tokens")
+        .and_then(|value| value.value().as_u64())
+        .map_or(DEFAULT_INCLUDE_TOKENS, |value| value as usize);
+    let content = root
+        .context("files can only be included in prompts and commands")
+        .and_then(|root| root.read(path))
+        .map_err(|e| RenderErrorReason::Other(format!("Failed to include {path}: {e}")))?;
+
+    // Inverse of the ~4 characters per token estimate
+    let max_chars = max_tokens * 4;
+    let included = if estimate_token_count(content.chars().count()) > max_tokens {
+        cut(
+            &content,
+            max_chars,
+            &format!("\n... [truncated to {max_tokens} tokens]"),
+        )
+    } else {
+        content
+    };
+    out.write(&included)?;
+    Ok(())
+}
+
+fn truncate(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let text = str_param(h, 0).ok_or(RenderErrorReason::ParamNotFoundForIndex("truncate", 0))?;
+    let max_chars = h
+        .param(1)
+        .and_then(|param| param.value().as_u64())
+        .ok_or(RenderErrorReason::ParamNotFoundForIndex("truncate", 1))?;
+    out.write(&cut(text, max_chars as usize, "…"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn handlebars_fixture() -> Handlebars<'static> {
+        let mut hb = Handlebars::new();
+        hb.set_strict_mode(true);
+        register_helpers(&mut hb);
+        hb
+    }
+
+    #[test]
+    fn test_today_with_format() {
+        let fixture = handlebars_fixture();
+
+        let actual = fixture
+            .render_template(r#"{{today "%Y"}}"#, &json!({}))
+            .unwrap();
+
+        let expected = chrono::Local::now().format("%Y").to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_env_falls_back_to_default() {
+        let fixture = handlebars_fixture();
+
+        let actual = fixture
+            .render_template(
+                r#"{{env "FORGE_TEST_UNSET_VARIABLE" "fallback"}}"#,
+                &json!({}),
+            )
+            .unwrap();
+
+        let expected = "fallback";
+        assert_eq!(actual, expected);
+    }
+
+    /// Handlebars including files from `cwd`, where `.env` files are secret
+    fn include_fixture(cwd: &std::path::Path) -> Handlebars<'static> {
+        let mut hb = handlebars_fixture();
+        let secret_files = SecretFiles::new(&[".env".to_string()]).unwrap();
+        register_include(
+            &mut hb,
+            Some(IncludeRoot::new(cwd.to_path_buf(), secret_files)),
+        );
+        hb
+    }
+
+    #[test]
+    fn test_include_caps_tokens() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("docs")).unwrap();
+        std::fs::write(dir.path().join("docs/style.md"), "a".repeat(100)).unwrap();
+        let fixture = include_fixture(dir.path());
+
+        let actual = fixture
+            .render_template(r#"{{include "docs/style.md" max_tokens=5}}"#, &json!({}))
+            .unwrap();
+
+        let expected = format!("{}\n... [truncated to 5 tokens]", "a".repeat(20));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_include_refuses_files_outside_the_root_and_secrets() {
+        let parent = tempfile::tempdir().unwrap();
+        let cwd = parent.path().join("project");
+        std::fs::create_dir(&cwd).unwrap();
+        std::fs::write(parent.path().join("outside.md"), "outside").unwrap();
+        std::fs::write(cwd.join(".env"), "TOKEN=secret").unwrap();
+        let fixture = include_fixture(&cwd);
+
+        let actual = [
+            format!("{}/outside.md", parent.path().display()),
+            "../outside.md".to_string(),
+            ".env".to_string(),
+        ]
+        .map(|path| {
+            fixture
+                .render_template("{{include path}}", &json!({"path": path}))
+                .is_err()
+        });
+
+        let expected = [true, true, true];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_include_without_root_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.md"), "notes").unwrap();
+        let fixture = handlebars_fixture();
+
+        let actual = fixture
+            .render_template(
+                "{{include path}}",
+                &json!({"path": dir.path().join("notes.md")}),
+            )
+            .is_err();
+
+        assert!(actual);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let fixture = handlebars_fixture();
+
+        let actual = fixture
+            .render_template(
+                "{{truncate text 5}}|{{truncate short 5}}",
+                &json!({"text": "héllo world", "short": "hi"}),
+            )
+            .unwrap();
+
+        let expected = "héllo…|hi";
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,15 +1,18 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::anyhow;
-use forge_domain::{CodebaseQueryResult, ToolCallContext, ToolCatalog, ToolOutput};
+use forge_domain::{
+    CodebaseQueryResult, ReadMode, SecretFiles, ToolCallContext, ToolCatalog, ToolOutput,
+};
 
 use crate::fmt::content::FormatContent;
 use crate::operation::{TempContentFiles, ToolOperation};
 use crate::services::{Services, ShellService};
 use crate::{
-    AgentRegistry, ConversationService, EnvironmentInfra, FollowUpService, FsPatchService,
-    FsReadService, FsRemoveService, FsSearchService, FsUndoService, FsWriteService,
+    AgentRegistry, ConversationService, EnvironmentInfra, FileSnapshotService, FollowUpService,
+    FsPatchService, FsReadService, FsRemoveService, FsSearchService, FsUndoService, FsWriteService,
     ImageReadService, NetFetchService, PlanCreateService, ProviderService, SkillFetchService,
     WorkspaceService,
 };
@@ -156,14 +159,40 @@ impl<
         Ok(match input {
             ToolCatalog::Read(input) => {
                 let normalized_path = self.normalize_path(input.file_path.clone());
-                let output = self
-                    .services
-                    .read(
-                        normalized_path,
-                        input.start_line.map(|i| i as u64),
-                        input.end_line.map(|i| i as u64),
-                    )
-                    .await?;
+                let lines = input.window_lines();
+                let output = match input.mode.unwrap_or(ReadMode::Range) {
+                    ReadMode::Range => {
+                        self.services
+                            .read(
+                                normalized_path,
+                                input.start_line.map(|i| i as u64),
+                                input.end_line.map(|i| i as u64),
+                            )
+                            .await?
+                    }
+                    ReadMode::Head => {
+                        self.services
+                            .read(normalized_path, Some(1), Some(lines))
+                            .await?
+                    }
+                    ReadMode::Tail => {
+                        // The window can only be placed once the length of the file is known
+                        let total_lines = self
+                            .services
+                            .read(normalized_path.clone(), Some(1), Some(1))
+                            .await?
+                            .info
+                            .total_lines;
+                        self.services
+                            .read(
+                                normalized_path,
+                                Some(total_lines.saturating_sub(lines) + 1),
+                                Some(total_lines),
+                            )
+                            .await?
+                    }
+                    ReadMode::Outline => self.services.outline(normalized_path).await?,
+                };
 
                 (input, output).into()
             }
@@ -181,7 +210,14 @@ impl<
                 if let Some(ref path) = params.path {
                     params.path = Some(self.normalize_path(path.clone()));
                 }
-                let output = self.services.search(params).await?;
+                let secret_files = SecretFiles::new(&self.services.get_config()?.secret_files)?;
+                let output = self.services.search(params).await?.and_then(|mut result| {
+                    // Secret files stay out of the results; reading one asks the user
+                    result
+                        .matches
+                        .retain(|matched| !secret_files.is_secret(Path::new(&matched.path)));
+                    (!result.matches.is_empty()).then_some(result)
+                });
                 (input, output).into()
             }
             ToolCatalog::SemSearch(input) => {
@@ -209,6 +245,16 @@ impl<
 
                 let mut results = futures::future::try_join_all(futures).await?;
 
+                // Secret files indexed before they were listed stay out of the results
+                let secret_files = SecretFiles::new(&config.secret_files)?;
+                for nodes in &mut results {
+                    nodes.retain(|node| {
+                        node.node
+                            .file_path()
+                            .is_none_or(|path| !secret_files.is_secret(Path::new(path)))
+                    });
+                }
+
                 // Deduplicate results across queries
                 crate::search_dedup::deduplicate_results(&mut results);
 
@@ -281,6 +327,11 @@ impl<
                 (input, output).into()
             }
             ToolCatalog::Followup(input) => {
+                // Without a user to answer, the agent proceeds as if the
+                // question was dismissed
+                if self.services.get_config()?.non_interactive {
+                    return Ok(ToolOperation::FollowUp { output: None });
+                }
                 let output = self
                     .services
                     .follow_up(
@@ -358,6 +409,18 @@ impl<
             self.require_prior_read(context, &input.file_path, "overwrite it")?;
         }
 
+        // Edits snapshot the file they change, the snapshot is tagged with the
+        // conversation and tool call once the edit is done
+        let edited_path = match &tool_input {
+            ToolCatalog::Write(input) => Some(&input.file_path),
+            ToolCatalog::Patch(input) => Some(&input.file_path),
+            ToolCatalog::MultiPatch(input) => Some(&input.file_path),
+            ToolCatalog::Remove(input) => Some(&input.path),
+            _ => None,
+        }
+        .map(|path| self.normalize_path(path.clone()));
+        let started = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
         let execution_result = self.call_internal(tool_input.clone(), context).await;
 
         if let Err(ref error) = execution_result {
@@ -366,6 +429,15 @@ impl<
 
         let operation = execution_result?;
 
+        if let (Some(path), Some(tag)) = (edited_path, context.snapshot_tag())
+            && let Err(error) = self
+                .services
+                .tag_snapshots(Path::new(&path), started, &tag)
+                .await
+        {
+            tracing::warn!(error = ?error, path = %path, "Failed to tag snapshot");
+        }
+
         // Send formatted output message
         if let Some(output) = operation.to_content(&env) {
             context.send(output).await?;
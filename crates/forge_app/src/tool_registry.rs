@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -5,8 +6,8 @@ use anyhow::Context;
 use console::style;
 use forge_domain::{
     Agent, AgentId, AgentInput, ChatResponse, ChatResponseContent, Environment, InputModality,
-    Model, SystemContext, TemplateConfig, ToolCallContext, ToolCallFull, ToolCatalog,
-    ToolDefinition, ToolKind, ToolName, ToolOutput, ToolResult,
+    Model, PermissionReply, SecretFiles, SystemContext, TemplateConfig, ToolCallContext,
+    ToolCallFull, ToolCatalog, ToolDefinition, ToolKind, ToolName, ToolOutput, ToolResult,
 };
 use forge_template::Element;
 use futures::future::join_all;
@@ -21,10 +22,13 @@ use crate::fmt::content::FormatContent;
 use crate::mcp_executor::McpExecutor;
 use crate::tool_executor::ToolExecutor;
 use crate::{
-    AgentRegistry, EnvironmentInfra, McpService, PolicyService, ProviderService, Services,
-    ToolResolver, WorkspaceService,
+    AgentRegistry, EnvironmentInfra, FollowUpService, McpService, PolicyService, ProviderService,
+    Services, ToolResolver, WorkspaceService,
 };
 
+/// Answer sharing a secret file with the agent
+const SHARE_SECRET: &str = "Share it";
+
 pub struct ToolRegistry<S> {
     tool_executor: ToolExecutor<S>,
     agent_executor: AgentExecutor<S>,
@@ -69,6 +73,27 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ToolReg
         let cwd = self.services.get_environment().cwd;
         let operation = tool_input.to_policy_operation(cwd.clone());
         if let Some(operation) = operation {
+            // Let consumers of the chat stream know that the tool is blocked on the
+            // user's confirmation
+            if self.services.requires_confirmation(&operation).await? {
+                // Nobody can answer in the terminal, so the consumer of the stream is asked
+                if self.services.get_config()?.non_interactive {
+                    return Self::ask_stream_consumer(
+                        tool_input.kind().name(),
+                        operation.message(),
+                        context,
+                    )
+                    .await;
+                }
+                context
+                    .send(ChatResponse::PermissionRequest {
+                        tool_name: tool_input.kind().name(),
+                        message: operation.message(),
+                        reply: None,
+                    })
+                    .await?;
+            }
+
             let decision = self.services.check_operation_permission(&operation).await?;
 
             // Send custom policy message to the user when a policy file was created
@@ -90,6 +115,60 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ToolReg
         Ok(false)
     }
 
+    /// Asks the consumer of the chat stream, e.g. a WebSocket client of the
+    /// server, to allow an operation. Returns true when the operation is
+    /// denied, which it is when the consumer drops the request unanswered.
+    async fn ask_stream_consumer(
+        tool_name: ToolName,
+        message: String,
+        context: &ToolCallContext,
+    ) -> anyhow::Result<bool> {
+        let (reply, answer) = PermissionReply::new();
+        context
+            .send(ChatResponse::PermissionRequest { tool_name, message, reply: Some(reply) })
+            .await?;
+        Ok(!answer.await.unwrap_or(false))
+    }
+
+    /// Asks the user whether a tool call accessing a file matching
+    /// `secret_files` may go ahead. Returns true when the call is denied.
+    async fn check_secret_access(
+        &self,
+        tool_input: &ToolCatalog,
+        context: &ToolCallContext,
+    ) -> anyhow::Result<bool> {
+        let config = self.services.get_config()?;
+        let secret_files = SecretFiles::new(&config.secret_files)?;
+        let Some(path) = Self::accessed_paths(tool_input)
+            .into_iter()
+            .find(|path| secret_files.is_secret(Path::new(path)))
+        else {
+            return Ok(false);
+        };
+        let message = format!("{path} may contain secrets");
+        // Nobody can answer in the terminal, so the consumer of the stream is asked
+        if config.non_interactive {
+            return Self::ask_stream_consumer(tool_input.kind().name(), message, context).await;
+        }
+
+        context
+            .send(ChatResponse::PermissionRequest {
+                tool_name: tool_input.kind().name(),
+                message: message.clone(),
+                reply: None,
+            })
+            .await?;
+        let answer = self
+            .services
+            .follow_up(
+                format!("{message}. Share it with the agent?"),
+                vec![SHARE_SECRET.to_string(), "Keep it private".to_string()],
+                None,
+            )
+            .await?;
+        Ok(answer.as_deref() != Some(SHARE_SECRET))
+    }
+
     async fn call_inner(
         &self,
         agent: &Agent,
@@ -152,6 +231,16 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ToolReg
                 ));
             }
 
+            if self.check_secret_access(&tool_input, context).await? {
+                context
+                    .send(forge_domain::TitleFormat::error("Secret File"))
+                    .await?;
+
+                return Ok(ToolOutput::text(Element::new("permission_denied").cdata(
+                    "The file may contain secrets and the user has not shared it. Do not try to access it another way",
+                )));
+            }
+
             // Validate tool modality support before execution
             // Only resolve the current model when modality validation is needed.
             if matches!(&tool_input, ToolCatalog::Read(input) if Self::has_image_extension(&input.file_path))
@@ -218,7 +307,8 @@ impl<S: Services + EnvironmentInfra<Config = forge_config::ForgeConfig>> ToolReg
     ) -> ToolResult {
         let call_id = call.call_id.clone();
         let tool_name = call.name.clone();
-        let output = self.call_inner(agent, call, context).await;
+        let context = context.clone().tool_call_id(call_id.clone());
+        let output = self.call_inner(agent, call, &context).await;
 
         ToolResult::new(tool_name).call_id(call_id).output(output)
     }
@@ -359,6 +449,25 @@ impl<S> ToolRegistry<S> {
         Ok(())
     }
 
+    /// Paths a tool call reads or rewrites. Shell commands are split into
+    /// words so that commands naming a file, e.g. `cat .env`, are caught. This
+    /// is best-effort: paths the shell expands from globs, variables or
+    /// command substitutions are not seen.
+    fn accessed_paths(tool_input: &ToolCatalog) -> Vec<&str> {
+        match tool_input {
+            ToolCatalog::Read(input) => vec![input.file_path.as_str()],
+            ToolCatalog::Write(input) => vec![input.file_path.as_str()],
+            ToolCatalog::Patch(input) => vec![input.file_path.as_str()],
+            ToolCatalog::MultiPatch(input) => vec![input.file_path.as_str()],
+            ToolCatalog::Shell(input) => input
+                .command
+                .split(|c: char| c.is_whitespace() || "'\"<>;|&()`=".contains(c))
+                .filter(|word| !word.is_empty())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
     /// Checks if a file path has an image extension.
     /// This is a lightweight check that doesn't require reading the file.
     fn has_image_extension(path: &str) -> bool {
@@ -944,6 +1053,20 @@ fn test_validate_tool_modality_with_non_read_tool() {
     );
 }
 
+#[test]
+fn test_accessed_paths_splits_shell_commands() {
+    use forge_domain::ToolCatalog;
+
+    let fixture = ToolCatalog::Shell(forge_domain::Shell {
+        command: "cat \"config/.env\" | grep KEY>out.txt".to_string(),
+        ..Default::default()
+    });
+
+    let actual = ToolRegistry::<()>::accessed_paths(&fixture);
+    let expected = vec!["cat", "config/.env", "grep", "KEY", "out.txt"];
+    assert_eq!(actual, expected);
+}
+
 #[test]
 fn test_has_image_extension() {
     // Test various image extensions (case-insensitive)
@@ -0,0 +1,483 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Maximum character limit for truncation
+const MAX_LIMIT: usize = 40_000;
+
+/// Start of a log record: a timestamp or a level, optionally bracketed.
+/// Lines that don't match, such as stack traces, continue the previous record.
+static LOG_RECORD_START: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\[?(\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}|\d{2}:\d{2}:\d{2}|(TRACE|DEBUG|INFO|WARN|WARNING|ERROR|FATAL)\b)",
+    )
+    .unwrap()
+});
+
+/// Result of a truncation operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipperResult<'a> {
+    /// The actual content passed for truncation.
+    pub actual: &'a str,
+    /// The prefix portion of the truncated content (if applicable)
+    pub prefix: Option<Range<usize>>,
+    /// The suffix portion of the truncated content (if applicable)
+    pub suffix: Option<Range<usize>>,
+}
+
+impl ClipperResult<'_> {
+    /// Number of characters left out between the prefix and the suffix
+    pub fn elided_chars(&self) -> usize {
+        let start = self.prefix.as_ref().map_or(0, |range| range.end);
+        let end = self
+            .suffix
+            .as_ref()
+            .map_or(self.actual.len(), |range| range.start);
+        self.actual[start..end.max(start)].chars().count()
+    }
+
+    /// Check if this result represents truncated content
+    pub fn is_truncated(&self) -> bool {
+        self.prefix.is_some() || self.suffix.is_some()
+    }
+
+    /// Get the prefix content if it exists
+    pub fn prefix_content(&self) -> Option<&str> {
+        self.prefix
+            .as_ref()
+            .map(|range| &self.actual[range.clone()])
+    }
+
+    /// Get the suffix content if it exists
+    pub fn suffix_content(&self) -> Option<&str> {
+        self.suffix
+            .as_ref()
+            .map(|range| &self.actual[range.clone()])
+    }
+}
+
+/// Renders the retained content with a marker in place of the elided part,
+/// or the whole content when nothing was clipped
+impl fmt::Display for ClipperResult<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_truncated() {
+            return f.write_str(self.actual);
+        }
+
+        let prefix = self.prefix_content().unwrap_or_default();
+        let suffix = self.suffix_content().unwrap_or_default();
+        f.write_str(prefix)?;
+        if !prefix.is_empty() && !prefix.ends_with('\n') {
+            f.write_str("\n")?;
+        }
+        write!(f, "[... {} characters elided ...]", self.elided_chars())?;
+        if !suffix.is_empty() && !suffix.starts_with('\n') {
+            f.write_str("\n")?;
+        }
+        f.write_str(suffix)
+    }
+}
+
+/// Shape of the content being clipped, which decides where it can be cut
+/// without leaving half a record behind
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Structure {
+    /// A JSON document, cut between the elements of its top-level array or
+    /// object
+    Json,
+    /// A unified diff, cut between files and hunks
+    Diff,
+    /// Log output, cut between records so that multi-line records such as
+    /// stack traces stay whole
+    Log,
+    /// Anything else, cut between lines
+    Text,
+}
+
+impl Structure {
+    /// Detects the structure of `content`
+    pub fn detect(content: &str) -> Self {
+        let trimmed = content.trim_start();
+        if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && serde_json::from_str::<serde::de::IgnoredAny>(content).is_ok()
+        {
+            return Self::Json;
+        }
+
+        let mut lines = 0;
+        let mut hunks = 0;
+        let mut records = 0;
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            lines += 1;
+            if line.starts_with("@@ ") {
+                hunks += 1;
+            }
+            if LOG_RECORD_START.is_match(line) {
+                records += 1;
+            }
+        }
+
+        if hunks > 0 && content.lines().any(|line| line.starts_with("--- ")) {
+            Self::Diff
+        } else if records >= 2 && records * 4 >= lines {
+            Self::Log
+        } else {
+            Self::Text
+        }
+    }
+
+    /// Byte offsets at which a structural unit of `content` starts, in
+    /// ascending order
+    fn boundaries(self, content: &str) -> Vec<usize> {
+        match self {
+            Self::Json => json_boundaries(content),
+            Self::Diff => line_starts(content)
+                .filter(|&start| {
+                    let line = &content[start..];
+                    line.starts_with("diff --git ") || line.starts_with("@@ ")
+                })
+                .collect(),
+            Self::Log => line_starts(content)
+                .filter(|&start| LOG_RECORD_START.is_match(&content[start..]))
+                .collect(),
+            Self::Text => line_starts(content).collect(),
+        }
+    }
+}
+
+/// Byte offsets of the start of every line but the first
+fn line_starts(content: &str) -> impl Iterator<Item = usize> + '_ {
+    content.match_indices('\n').map(|(idx, _)| idx + 1)
+}
+
+/// Byte offsets right after the separators of the top-level elements of a
+/// JSON document, skipping over strings
+fn json_boundaries(content: &str) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (idx, c) in content.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 1 => boundaries.push(idx + 1),
+            _ => {}
+        }
+    }
+    boundaries
+}
+
+/// A strategy for truncating text content.
+///
+/// This enum provides different ways to truncate text while preserving
+/// meaningful portions of the content based on the specific use case.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Clipper {
+    /// Retains data from the beginning up to the specified character count
+    Prefix(usize),
+
+    /// Retains data from both the beginning and end of the content
+    /// First parameter is the prefix character count
+    /// Second parameter is the suffix character count
+    PrefixSuffix(usize, usize),
+}
+
+impl Default for Clipper {
+    /// Creates a default Clipper that keeps the prefix up to MAX_LIMIT
+    /// characters
+    fn default() -> Self {
+        Self::Prefix(MAX_LIMIT)
+    }
+}
+
+impl Clipper {
+    /// Creates a Clipper that keeps the prefix (beginning) of the content
+    /// up to the specified number of characters
+    pub fn from_start(prefix_chars: usize) -> Clipper {
+        Self::Prefix(prefix_chars)
+    }
+
+    /// Creates a Clipper that keeps both the beginning and end of the content
+    /// with the specified character counts for each
+    pub fn from_start_end(start: usize, end: usize) -> Clipper {
+        Self::PrefixSuffix(start, end)
+    }
+
+    /// Apply this truncation strategy to the given content
+    ///
+    /// # Arguments
+    /// * `content` - The text content to truncate
+    ///
+    /// # Returns
+    /// A TruncationResult containing the truncated content
+    pub fn clip(self, content: &str) -> ClipperResult<'_> {
+        // If content is empty, return as is
+        if content.is_empty() {
+            return ClipperResult { prefix: None, suffix: None, actual: content };
+        }
+
+        // Get character count (not byte count)
+        let char_count = content.chars().count();
+
+        // Apply the truncation strategy
+        match self {
+            Clipper::Prefix(limit) => self.apply_prefix(content, char_count, limit),
+            Clipper::PrefixSuffix(prefix_limit, suffix_limit) => {
+                self.apply_prefix_suffix(content, char_count, prefix_limit, suffix_limit)
+            }
+        }
+    }
+
+    /// Applies this truncation strategy like [`Clipper::clip`], but moves
+    /// the cuts to the nearest boundary of the content's [`Structure`] so that
+    /// no JSON element, diff hunk, log record or line is cut in half.
+    ///
+    /// A cut only moves if that keeps at least half of the budget; otherwise
+    /// it falls back to the nearest line boundary, and then to the exact
+    /// character limit.
+    pub fn clip_structured(self, content: &str) -> ClipperResult<'_> {
+        let clipped = self.clip(content);
+        if !clipped.is_truncated() {
+            return clipped;
+        }
+
+        let structural = Structure::detect(content).boundaries(content);
+        let lines = line_starts(content).collect::<Vec<_>>();
+        let snap_end = |end: usize| {
+            [&structural, &lines]
+                .into_iter()
+                .find_map(|boundaries| {
+                    boundaries
+                        .iter()
+                        .rev()
+                        .find(|&&boundary| boundary <= end)
+                        .filter(|&&boundary| boundary > 0 && boundary * 2 >= end)
+                        .copied()
+                })
+                .unwrap_or(end)
+        };
+        let snap_start = |start: usize| {
+            let len = content.len();
+            [&structural, &lines]
+                .into_iter()
+                .find_map(|boundaries| {
+                    boundaries
+                        .iter()
+                        .find(|&&boundary| boundary >= start)
+                        .filter(|&&boundary| boundary < len && (len - boundary) * 2 >= len - start)
+                        .copied()
+                })
+                .unwrap_or(start)
+        };
+
+        ClipperResult {
+            prefix: clipped.prefix.map(|range| 0..snap_end(range.end)),
+            suffix: clipped
+                .suffix
+                .map(|range| snap_start(range.start)..range.end),
+            actual: content,
+        }
+    }
+
+    /// Helper method to truncate content from the beginning
+    fn apply_prefix<'a>(
+        &self,
+        content: &'a str,
+        char_count: usize,
+        limit: usize,
+    ) -> ClipperResult<'a> {
+        if char_count <= limit {
+            return ClipperResult { prefix: None, suffix: None, actual: content };
+        }
+
+        // Find the byte index corresponding to the character limit
+        let byte_idx = content
+            .char_indices()
+            .nth(limit)
+            .map_or(content.len(), |(idx, _)| idx);
+
+        ClipperResult { prefix: Some(0..byte_idx), suffix: None, actual: content }
+    }
+
+    /// Helper method to truncate content from both prefix and suffix
+    fn apply_prefix_suffix<'a>(
+        &self,
+        content: &'a str,
+        char_count: usize,
+        prefix_limit: usize,
+        suffix_limit: usize,
+    ) -> ClipperResult<'a> {
+        // If the combined limits exceed or equal content length, return the whole
+        // content
+        if prefix_limit + suffix_limit >= char_count {
+            return ClipperResult { prefix: None, suffix: None, actual: content };
+        }
+
+        // Find the byte index for prefix
+        let prefix_end_idx = content
+            .char_indices()
+            .nth(prefix_limit)
+            .map_or(content.len(), |(idx, _)| idx);
+
+        // Find the byte index for suffix
+        let suffix_start_idx = content
+            .char_indices()
+            .nth(char_count - suffix_limit)
+            .map_or(0, |(idx, _)| idx);
+
+        ClipperResult {
+            prefix: Some(0..prefix_end_idx),
+            suffix: Some(suffix_start_idx..content.len()),
+            actual: content,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_truncate_strategy_start() {
+        let content = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".repeat(10); // 260 chars
+        let strategy = Clipper::Prefix(10);
+
+        let result = strategy.clip(&content);
+
+        // Should contain only the first 10 characters
+        assert!(result.prefix.is_some());
+        let range = result.prefix.unwrap();
+        assert_eq!(&content[range], "ABCDEFGHIJ");
+        assert!(result.suffix.is_none());
+    }
+
+    #[test]
+    fn test_truncate_strategy_both() {
+        let content = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".repeat(10); // 260 chars
+        let strategy = Clipper::PrefixSuffix(10, 10);
+
+        let result = strategy.clip(&content);
+
+        // Should contain first 10 and last 10 characters
+        assert!(result.prefix.is_some());
+        assert!(result.suffix.is_some());
+        let prefix_range = result.prefix.unwrap();
+        let suffix_range = result.suffix.unwrap();
+        assert_eq!(&content[prefix_range], "ABCDEFGHIJ");
+        assert_eq!(&content[suffix_range], "QRSTUVWXYZ");
+    }
+
+    #[test]
+    fn test_truncate_within_limit() {
+        let content = "Short content";
+        let strategy = Clipper::Prefix(100);
+
+        let result = strategy.clip(content);
+
+        // Should return the original content as is
+        assert!(result.prefix.is_none());
+        assert!(result.suffix.is_none());
+        assert_eq!(result.actual, content);
+    }
+
+    #[test]
+    fn test_truncate_strategy_both_overlapping() {
+        let content = "ABCDEFGHIJKLMNOPQRSTUVWXYZ"; // 26 chars
+        let strategy = Clipper::PrefixSuffix(15, 15);
+
+        let result = strategy.clip(content);
+
+        // Should return the original content as the combined limits exceed content
+        // length
+        assert!(result.prefix.is_none());
+        assert!(result.suffix.is_none());
+        assert_eq!(result.actual, content);
+    }
+
+    #[test]
+    fn test_detect_structure() {
+        let fixture = [
+            r#"{"a": [1, 2], "b": "c"}"#,
+            "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n",
+            "2024-01-01 10:00:00 INFO start\n2024-01-01 10:00:01 ERROR boom\n  at main.rs:1\n",
+            "just\nsome\ntext\n",
+        ];
+
+        let actual = fixture.map(Structure::detect);
+
+        let expected = [
+            Structure::Json,
+            Structure::Diff,
+            Structure::Log,
+            Structure::Text,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_clip_structured_cuts_between_lines() {
+        let fixture = "first line\nsecond line\nthird line\nfourth line\n";
+
+        let actual = Clipper::PrefixSuffix(15, 15)
+            .clip_structured(fixture)
+            .to_string();
+
+        let expected = "first line\n[... 23 characters elided ...]\nfourth line\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_clip_structured_keeps_log_records_whole() {
+        let fixture = "2024-01-01 10:00:00 INFO start\n\
+                       2024-01-01 10:00:01 ERROR boom\n  at main.rs:1\n  at lib.rs:2\n\
+                       2024-01-01 10:00:02 INFO done\n";
+
+        let actual = Clipper::PrefixSuffix(0, 95)
+            .clip_structured(fixture)
+            .to_string();
+
+        let expected = "[... 31 characters elided ...]\n\
+                        2024-01-01 10:00:01 ERROR boom\n  at main.rs:1\n  at lib.rs:2\n\
+                        2024-01-01 10:00:02 INFO done\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_clip_structured_keeps_diff_hunks_whole() {
+        let fixture = "diff --git a/x b/x\n--- a/x\n+++ b/x\n\
+                       @@ -1,2 +1,2 @@\n-one\n+uno\n two\n\
+                       @@ -10,2 +10,2 @@\n-ten\n+diez\n eleven\n";
+
+        let actual = Clipper::Prefix(80).clip_structured(fixture).to_string();
+
+        let expected = "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n-one\n+uno\n two\n\
+                        [... 37 characters elided ...]";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_clip_structured_cuts_between_json_elements() {
+        let fixture =
+            r#"[{"id": 1, "name": "one"}, {"id": 2, "name": "two"}, {"id": 3, "name": "three"}]"#;
+
+        let actual = Clipper::Prefix(40).clip_structured(fixture).to_string();
+
+        let expected = "[{\"id\": 1, \"name\": \"one\"},\n[... 54 characters elided ...]";
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,7 +1,11 @@
+mod clipper;
 mod truncate_fetch;
+mod truncate_mcp;
 mod truncate_search;
 mod truncate_shell;
 
+pub use clipper::*;
 pub use truncate_fetch::*;
+pub use truncate_mcp::*;
 pub use truncate_search::*;
 pub use truncate_shell::*;
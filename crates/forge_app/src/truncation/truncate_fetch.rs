@@ -1,19 +1,47 @@
+use super::Clipper;
+
 /// Represents the result of fetch content truncation
 #[derive(Debug)]
 pub struct TruncatedFetchOutput {
     pub content: String,
+    /// Number of characters of the original content that were kept
+    pub end_char: usize,
 }
 
-/// Truncates fetch content based on character limit
+/// Truncates fetch content based on character limit, cutting at the nearest
+/// boundary of its structure (JSON element, diff hunk, log record or line) so
+/// the kept content does not end halfway through one
 pub fn truncate_fetch_content(content: &str, truncation_limit: usize) -> TruncatedFetchOutput {
-    let original_length = content.len();
-    let is_truncated = original_length > truncation_limit;
+    let clipped = Clipper::from_start(truncation_limit).clip_structured(content);
+    let truncated_content = clipped.prefix_content().unwrap_or(content).to_string();
+    let end_char = truncated_content.chars().count();
+
+    TruncatedFetchOutput { content: truncated_content, end_char }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_truncate_fetch_content_keeps_whole_lines() {
+        let fixture = "first line\nsecond line\nthird line\n";
+
+        let actual = truncate_fetch_content(fixture, 28);
+
+        assert_eq!(actual.content, "first line\nsecond line\n");
+        assert_eq!(actual.end_char, 23);
+    }
+
+    #[test]
+    fn test_truncate_fetch_content_within_limit() {
+        let fixture = "short";
 
-    let truncated_content = if is_truncated {
-        content.chars().take(truncation_limit).collect()
-    } else {
-        content.to_string()
-    };
+        let actual = truncate_fetch_content(fixture, 28);
 
-    TruncatedFetchOutput { content: truncated_content }
+        assert_eq!(actual.content, "short");
+        assert_eq!(actual.end_char, 5);
+    }
 }
@@ -0,0 +1,61 @@
+use forge_domain::{ToolOutput, ToolValue};
+
+use super::Clipper;
+
+/// Maximum number of characters kept from the start of an MCP tool's text
+/// output
+const MCP_PREFIX_CHARS: usize = 30_000;
+
+/// Maximum number of characters kept from the end of an MCP tool's text output
+const MCP_SUFFIX_CHARS: usize = 10_000;
+
+/// Clips every text value of an MCP tool's output to its head and tail, cut at
+/// the nearest boundary of its structure (JSON element, diff hunk, log record
+/// or line), with a marker in place of the elided part
+pub fn truncate_mcp_output(output: ToolOutput) -> ToolOutput {
+    let values = output
+        .values
+        .into_iter()
+        .map(|value| match value {
+            ToolValue::Text(text) => ToolValue::Text(
+                Clipper::from_start_end(MCP_PREFIX_CHARS, MCP_SUFFIX_CHARS)
+                    .clip_structured(&text)
+                    .to_string(),
+            ),
+            value => value,
+        })
+        .collect();
+
+    ToolOutput { values, ..output }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_truncate_mcp_output_keeps_head_and_tail() {
+        let line = format!("{}\n", "a".repeat(99));
+        let fixture = ToolOutput::text(line.repeat(500));
+
+        let actual = truncate_mcp_output(fixture);
+
+        let expected = ToolOutput::text(format!(
+            "{}[... 10000 characters elided ...]\n{}",
+            line.repeat(300),
+            line.repeat(100)
+        ));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_truncate_mcp_output_within_limit() {
+        let fixture = ToolOutput::text("short");
+
+        let actual = truncate_mcp_output(fixture.clone());
+
+        assert_eq!(actual, fixture);
+    }
+}
@@ -5,7 +5,7 @@ use forge_domain::{Agent, *};
 use serde_json::json;
 use tracing::debug;
 
-use crate::{AttachmentService, TemplateEngine};
+use crate::{AttachmentService, IncludeRoot, TemplateEngine};
 
 /// Service responsible for setting user prompts in the conversation context
 #[derive(Clone)]
@@ -14,6 +14,8 @@ pub struct UserPromptGenerator<S> {
     agent: Agent,
     event: Event,
     current_time: chrono::DateTime<chrono::Local>,
+    git: Option<GitState>,
+    include_root: Option<IncludeRoot>,
 }
 
 impl<S: AttachmentService> UserPromptGenerator<S> {
@@ -24,7 +26,26 @@ impl<S: AttachmentService> UserPromptGenerator<S> {
         event: Event,
         current_time: chrono::DateTime<chrono::Local>,
     ) -> Self {
-        Self { services: service, agent, event, current_time }
+        Self {
+            services: service,
+            agent,
+            event,
+            current_time,
+            git: None,
+            include_root: None,
+        }
+    }
+
+    /// Sets the git state exposed to custom command templates as `git`
+    pub fn git(mut self, git: Option<GitState>) -> Self {
+        self.git = git;
+        self
+    }
+
+    /// Sets the files `{{include}}` may read in custom command templates
+    pub fn include_root(mut self, root: IncludeRoot) -> Self {
+        self.include_root = Some(root);
+        self
     }
 
     /// Sets the user prompt in the context based on agent configuration and
@@ -138,7 +159,10 @@ impl<S: AttachmentService> UserPromptGenerator<S> {
     ) -> anyhow::Result<(Conversation, Option<String>)> {
         let mut context = conversation.context.take().unwrap_or_default();
         let event_value = self.event.value.clone();
-        let template_engine = TemplateEngine::default();
+        let template_engine = match &self.include_root {
+            Some(root) => TemplateEngine::default().include_root(root.clone()),
+            None => TemplateEngine::default(),
+        };
 
         let content =
             if let Some(user_prompt) = &self.agent.user_prompt
@@ -167,9 +191,18 @@ impl<S: AttachmentService> UserPromptGenerator<S> {
                 // Render the command first.
                 let event_context = match self.event.value.as_ref().and_then(|v| v.as_command()) {
                     Some(command) => {
+                        // Declared arguments are exposed by name next to the raw parameters
+                        let mut variables = serde_json::Map::from_iter(command.arguments.clone());
+                        variables.insert(
+                            "parameters".to_string(),
+                            json!(command.parameters.join(" ")),
+                        );
+                        if let Some(git) = &self.git {
+                            variables.entry("git").or_insert(serde_json::to_value(git)?);
+                        }
                         let rendered_prompt = template_engine.render_template(
                             command.template.clone(),
-                            &json!({"parameters": command.parameters.join(" ")}),
+                            &serde_json::Value::Object(variables),
                         )?;
                         event_context.event(EventContextValue::new(rendered_prompt))
                     }
@@ -244,6 +277,8 @@ impl<S: AttachmentService> UserPromptGenerator<S> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use forge_domain::{
         AgentId, AttachmentContent, Context, ContextMessage, ConversationId, FileInfo, ModelId,
         ProviderId, ToolKind,
@@ -358,6 +393,55 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_command_arguments_rendered_by_name() {
+        let agent = fixture_agent_without_user_prompt()
+            .user_prompt(Template::<EventContext>::new("{{event.value}}"));
+        let event = Event::from(
+            UserCommand::new(
+                "review",
+                Template::new("Review {{target}} at depth {{depth}} ({{parameters}})"),
+                vec!["src".to_string(), "depth=5".to_string()],
+            )
+            .arguments(BTreeMap::from([
+                ("target".to_string(), json!("src")),
+                ("depth".to_string(), json!(5)),
+            ])),
+        );
+        let conversation = fixture_conversation();
+        let generator = fixture_generator(agent, event);
+
+        let actual = generator.add_user_prompt(conversation).await.unwrap();
+
+        let messages = actual.context.unwrap().messages;
+        let expected = "Review src at depth 5 (src depth=5)";
+        assert_eq!(messages[0].content().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_command_renders_git_state() {
+        let agent = fixture_agent_without_user_prompt()
+            .user_prompt(Template::<EventContext>::new("{{event.value}}"));
+        let event = Event::from(UserCommand::new(
+            "sync",
+            Template::new("Rebase {{git.branch}}, {{git.behind}} behind"),
+            vec![],
+        ));
+        let git = GitState {
+            branch: Some("main".to_string()),
+            behind: 3,
+            ..Default::default()
+        };
+        let conversation = fixture_conversation();
+        let generator = fixture_generator(agent, event).git(Some(git));
+
+        let actual = generator.add_user_prompt(conversation).await.unwrap();
+
+        let messages = actual.context.unwrap().messages;
+        let expected = "Rebase main, 3 behind";
+        assert_eq!(messages[0].content().unwrap(), expected);
+    }
+
     #[tokio::test]
     async fn test_raw_content_preserved_in_message() {
         let agent = fixture_agent_without_user_prompt();
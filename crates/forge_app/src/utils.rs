@@ -85,6 +85,23 @@ pub fn format_match(matched: &Match, base_dir: &Path) -> String {
 
             output
         }
+        Some(MatchResult::Located {
+            line_number,
+            column,
+            matched: text,
+            line,
+            before_context,
+            after_context,
+        }) => serde_json::json!({
+            "path": format_display_path(Path::new(&matched.path), base_dir),
+            "line": line_number,
+            "column": column,
+            "match": text,
+            "text": line,
+            "before": before_context,
+            "after": after_context,
+        })
+        .to_string(),
         None => format_display_path(Path::new(&matched.path), base_dir),
     }
 }
@@ -107,6 +124,22 @@ pub fn compute_hash(content: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
+/// Converts the configured storage scope into its domain counterpart
+pub fn storage_scope(scope: forge_config::StorageScope) -> forge_domain::StorageScope {
+    match scope {
+        forge_config::StorageScope::Global => forge_domain::StorageScope::Global,
+        forge_config::StorageScope::Workspace => forge_domain::StorageScope::Workspace,
+    }
+}
+
+/// Converts a domain storage scope into its configured counterpart
+pub fn config_storage_scope(scope: forge_domain::StorageScope) -> forge_config::StorageScope {
+    match scope {
+        forge_domain::StorageScope::Global => forge_config::StorageScope::Global,
+        forge_domain::StorageScope::Workspace => forge_config::StorageScope::Workspace,
+    }
+}
+
 // Merges strict-mode incompatible `allOf` branches into a single schema object.
 fn flatten_all_of_schema(map: &mut serde_json::Map<String, serde_json::Value>) {
     let Some(serde_json::Value::Array(all_of)) = map.remove("allOf") else {
@@ -458,6 +491,19 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_storage_scope_round_trip() {
+        let fixture = [
+            forge_domain::StorageScope::Global,
+            forge_domain::StorageScope::Workspace,
+        ];
+
+        let actual = fixture.map(|scope| storage_scope(config_storage_scope(scope)));
+
+        let expected = fixture;
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_normalize_json_schema_anthropic_mode() {
         let mut schema = json!({
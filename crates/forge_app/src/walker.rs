@@ -1,7 +1,20 @@
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 use derive_setters::Setters;
 
+/// Order of the entries returned by a walk
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WalkOrder {
+    /// Order in which entries were visited, directories included
+    #[default]
+    Traversal,
+    /// Largest files first, directories excluded
+    LargestFirst,
+    /// Most recently modified files first, directories excluded
+    RecentlyModified,
+}
+
 /// Configuration for filesystem walking operations
 #[derive(Debug, Clone, Setters)]
 #[setters(strip_option, into)]
@@ -20,6 +33,12 @@ pub struct Walker {
     pub max_total_size: Option<u64>,
     /// Whether to skip binary files
     pub skip_binary: bool,
+    /// Whether to walk into symlinks instead of skipping them
+    pub follow_symlinks: bool,
+    /// Order of the returned entries
+    pub order: WalkOrder,
+    /// Maximum number of entries to return after ordering (None for unlimited)
+    pub limit: Option<usize>,
 }
 
 impl Walker {
@@ -33,6 +52,9 @@ impl Walker {
             max_files: Some(100),
             max_total_size: Some(10 * 1024 * 1024), // 10MB
             skip_binary: true,
+            follow_symlinks: false,
+            order: WalkOrder::Traversal,
+            limit: None,
         }
     }
 
@@ -46,6 +68,9 @@ impl Walker {
             max_files: None,
             max_total_size: None,
             skip_binary: false,
+            follow_symlinks: false,
+            order: WalkOrder::Traversal,
+            limit: None,
         }
     }
 }
@@ -65,6 +90,10 @@ pub struct WalkedFile {
     pub file_name: Option<String>,
     /// Size in bytes
     pub size: u64,
+    /// Last modification time, when the platform reports one
+    pub modified: Option<SystemTime>,
+    /// Whether the file's extension marks it as binary
+    pub is_binary: bool,
 }
 
 impl WalkedFile {
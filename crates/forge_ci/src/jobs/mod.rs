@@ -9,6 +9,7 @@ mod release_draft;
 mod release_draft_pr;
 mod release_homebrew;
 mod release_npm;
+mod release_sign;
 
 pub use bounty_job::*;
 pub use draft_release_update_job::*;
@@ -19,3 +20,4 @@ pub use release_draft::*;
 pub use release_draft_pr::*;
 pub use release_homebrew::*;
 pub use release_npm::*;
+pub use release_sign::*;
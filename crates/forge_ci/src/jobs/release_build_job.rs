@@ -78,7 +78,8 @@ impl From<ReleaseBuilderJob> for Job {
                     .add_with(("cross-version", "0.2.5"))
                     .add_env(("RUSTFLAGS", "${{ env.RUSTFLAGS }}"))
                     .add_env(("POSTHOG_API_SECRET", "${{secrets.POSTHOG_API_SECRET}}"))
-                    .add_env(("APP_VERSION", value.version.to_string())),
+                    .add_env(("APP_VERSION", value.version.to_string()))
+                    .add_env(("UPDATE_PUBLIC_KEY", "${{ vars.UPDATE_PUBLIC_KEY }}")),
             );
 
         if let Some(release_id) = value.release_id {
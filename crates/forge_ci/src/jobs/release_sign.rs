@@ -0,0 +1,28 @@
+use gh_workflow::*;
+
+/// Signs every binary of the release with the Ed25519 key in
+/// `UPDATE_SIGNING_KEY` and uploads each signature, base64 encoded, next to
+/// its binary as `<binary>.sig`. `forge update` verifies downloads against the
+/// matching public key, burned in at build time from `UPDATE_PUBLIC_KEY`.
+const SIGN_SCRIPT: &str = r#"mkdir -p release && cd release
+gh release download "$TAG" --repo "$GITHUB_REPOSITORY" --pattern 'forge-*'
+printf '%s\n' "$UPDATE_SIGNING_KEY" > signing-key.pem
+for binary in forge-*; do
+  case "$binary" in *.sig) continue ;; esac
+  openssl pkeyutl -sign -rawin -inkey signing-key.pem -in "$binary" | base64 -w0 > "$binary.sig"
+done
+rm signing-key.pem
+gh release upload "$TAG" --repo "$GITHUB_REPOSITORY" --clobber forge-*.sig"#;
+
+/// Create a job signing the release binaries
+pub fn release_sign_job() -> Job {
+    Job::new("sign_release")
+        .permissions(Permissions::default().contents(Level::Write))
+        .add_step(
+            Step::new("Sign Binaries")
+                .run(SIGN_SCRIPT)
+                .add_env(("TAG", "${{ github.event.release.tag_name }}"))
+                .add_env(("GH_TOKEN", "${{ secrets.GITHUB_TOKEN }}"))
+                .add_env(("UPDATE_SIGNING_KEY", "${{ secrets.UPDATE_SIGNING_KEY }}")),
+        )
+}
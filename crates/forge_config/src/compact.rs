@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use derive_setters::Setters;
@@ -27,6 +28,30 @@ impl From<UpdateFrequency> for Duration {
     }
 }
 
+/// Release channel forge updates from; each channel also receives the
+/// releases of the more stable ones
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+    Nightly,
+}
+
 /// Configuration for automatic forge updates
 #[derive(
     Debug, Clone, Serialize, Deserialize, Default, JsonSchema, Setters, PartialEq, fake::Dummy,
@@ -37,6 +62,23 @@ pub struct Update {
     pub frequency: Option<UpdateFrequency>,
     /// Whether to automatically install updates without prompting
     pub auto_update: Option<bool>,
+    /// Release channel to update from
+    pub channel: Option<UpdateChannel>,
+}
+
+/// Token thresholds that apply to one model, replacing the ratio based ones
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[setters(strip_option, into)]
+pub struct CompactThresholds {
+    /// Maximum number of tokens to keep after compaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+
+    /// Maximum number of tokens before triggering compaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_threshold: Option<usize>,
 }
 
 /// Configuration for automatic context compaction for all agents
@@ -66,6 +108,22 @@ pub struct Compact {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_threshold: Option<usize>,
 
+    /// Fraction of the model's context window to keep after compaction.
+    /// Replaces `max_tokens` for models whose context length is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens_ratio: Option<Percentage>,
+
+    /// Fraction of the model's context window at which compaction triggers.
+    /// Replaces `token_threshold` for models whose context length is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_threshold_ratio: Option<Percentage>,
+
+    /// Thresholds for specific models keyed by model ID, e.g.
+    /// `[compact.models."claude-sonnet-4"]`; these take precedence over the
+    /// ratios.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, CompactThresholds>,
+
     /// Maximum number of conversation turns before triggering compaction
     #[serde(skip_serializing_if = "Option::is_none")]
     pub turn_threshold: Option<usize>,
@@ -97,6 +155,9 @@ impl Compact {
         Self {
             max_tokens: None,
             token_threshold: None,
+            max_tokens_ratio: None,
+            token_threshold_ratio: None,
+            models: BTreeMap::new(),
             turn_threshold: None,
             message_threshold: None,
             model: None,
@@ -115,6 +176,9 @@ impl Dummy<fake::Faker> for Compact {
             eviction_window: Percentage::from((0.0f64..=1.0f64).fake_with_rng::<f64, R>(rng)),
             max_tokens: fake::Faker.fake_with_rng(rng),
             token_threshold: fake::Faker.fake_with_rng(rng),
+            max_tokens_ratio: fake::Faker.fake_with_rng(rng),
+            token_threshold_ratio: fake::Faker.fake_with_rng(rng),
+            models: fake::Faker.fake_with_rng(rng),
             turn_threshold: fake::Faker.fake_with_rng(rng),
             message_threshold: fake::Faker.fake_with_rng(rng),
             model: fake::Faker.fake_with_rng(rng),
@@ -180,4 +244,20 @@ mod tests {
             result.ok()
         );
     }
+
+    #[test]
+    fn test_model_thresholds_from_toml() {
+        let toml = "[compact.models.claude-sonnet-4]\ntoken_threshold = 500000\n";
+
+        let actual = ConfigReader::default()
+            .read_defaults()
+            .read_toml(toml)
+            .build()
+            .unwrap()
+            .compact
+            .unwrap_or_default();
+
+        let expected = CompactThresholds::default().token_threshold(500_000_usize);
+        assert_eq!(actual.models.get("claude-sonnet-4"), Some(&expected));
+    }
 }
@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use derive_setters::Setters;
 use fake::Dummy;
@@ -9,7 +9,9 @@ use serde::{Deserialize, Serialize};
 use crate::reader::ConfigReader;
 use crate::writer::ConfigWriter;
 use crate::{
-    AutoDumpFormat, Compact, Decimal, HttpConfig, ModelConfig, ReasoningConfig, RetryConfig, Update,
+    AutoDumpFormat, Compact, ContextPreset, Decimal, Effort, HttpConfig, ModelConfig, ModelRouting,
+    OtlpConfig, Profile, ReasoningConfig, ResponseCache, Retention, RetryConfig, Schedule,
+    ServerConfig, StorageScope, TelemetryMode, Update,
 };
 
 /// Wire protocol a provider uses for chat completions.
@@ -136,6 +138,11 @@ pub struct ForgeConfig {
     /// Maximum number of files read in a single batch operation.
     #[serde(default)]
     pub max_file_read_batch_size: usize,
+    /// Maximum number of changed lines of a file diff shown in full; larger
+    /// diffs are summarized and can be expanded with `/diff <file>`. Set to 0
+    /// to always show diffs in full.
+    #[serde(default)]
+    pub max_diff_lines: usize,
     /// HTTP client settings including proxy, TLS, and timeout configuration.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http: Option<HttpConfig>,
@@ -149,6 +156,10 @@ pub struct ForgeConfig {
     /// cancelled.
     #[serde(default)]
     pub tool_timeout_secs: u64,
+    /// Maximum time in seconds a provider's streaming response may go without
+    /// sending data before the request is retried; disabled when 0.
+    #[serde(default)]
+    pub stream_stall_timeout_secs: u64,
     /// Whether to automatically open HTML dump files in the browser after
     /// creation.
     #[serde(default)]
@@ -156,6 +167,15 @@ pub struct ForgeConfig {
     /// Directory where debug request files are written; disabled when absent.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub debug_requests: Option<PathBuf>,
+    /// JSON lines file of recorded provider responses that every HTTP request
+    /// is answered from, in order, instead of the network; disabled when
+    /// absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay_responses: Option<PathBuf>,
+    /// Cache of provider responses to requests sent with a temperature of 0;
+    /// disabled when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache: Option<ResponseCache>,
     /// Path to the conversation history file; defaults to the global history
     /// location when absent.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -163,6 +183,31 @@ pub struct ForgeConfig {
     /// Maximum number of conversations shown in the conversation list.
     #[serde(default)]
     pub max_conversations: usize,
+    /// Retention limits used to prune old conversations; pruning is disabled
+    /// when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<Retention>,
+    /// Whether conversations and snapshots are stored in the global data
+    /// directory or inside the workspace's `.forge/` directory.
+    #[serde(default)]
+    pub storage: StorageScope,
+    /// Whether new snapshots are compressed with zstd. Snapshots stored
+    /// uncompressed stay readable either way.
+    #[serde(default)]
+    pub compress_snapshots: bool,
+    /// Whether telemetry is disabled, written to a local file, or sent to the
+    /// Forge telemetry service. The `FORGE_TRACKER` environment variable
+    /// takes precedence.
+    #[serde(default)]
+    pub telemetry: TelemetryMode,
+    /// OpenTelemetry collector receiving spans and token usage metrics;
+    /// export is disabled when absent unless `OTEL_EXPORTER_OTLP_ENDPOINT` is
+    /// set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp: Option<OtlpConfig>,
+    /// Users and API keys of the HTTP server started with `forge serve`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<ServerConfig>,
     /// Maximum number of candidate results returned from the initial semantic
     /// search vector query.
     #[serde(default)]
@@ -178,6 +223,10 @@ pub struct ForgeConfig {
     /// Maximum number of file extensions included in the agent system prompt.
     #[serde(default)]
     pub max_extensions: usize,
+    /// Token budget of the repository map of key files, their symbols and
+    /// imports included in the agent system prompt; disabled when 0.
+    #[serde(default)]
+    pub repo_map_tokens: usize,
     /// Format used when automatically creating a session dump after task
     /// completion; disabled when absent.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -203,6 +252,15 @@ pub struct ForgeConfig {
     /// generation.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub suggest: Option<ModelConfig>,
+    /// Model and provider configuration used for conversation title
+    /// generation. A small, fast model is enough; the agent's model is used
+    /// when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<ModelConfig>,
+    /// Models picked by task, e.g. a cheaper model for planning than for
+    /// editing; commit messages and titles are routed by `commit` and `title`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<ModelRouting>,
 
     // --- Workflow fields ---
     /// Configuration for automatic Forge updates.
@@ -248,6 +306,12 @@ pub struct ForgeConfig {
     #[serde(default)]
     pub restricted: bool,
 
+    /// Whether Forge runs without a user to answer prompts, such as in CI;
+    /// when enabled, operations requiring confirmation are denied and
+    /// follow-up questions go unanswered.
+    #[serde(default)]
+    pub non_interactive: bool,
+
     /// Whether tool use is supported in the current environment; when false,
     /// all tool calls are disabled.
     #[serde(default)]
@@ -258,6 +322,12 @@ pub struct ForgeConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<ReasoningConfig>,
 
+    /// Reasoning effort chosen for the current session with `/reasoning`,
+    /// taking precedence over the effort of agents and of `reasoning`. Never
+    /// read from or written to a file.
+    #[serde(skip)]
+    pub session_reasoning_effort: Option<Effort>,
+
     /// Additional provider definitions merged with the built-in provider list.
     ///
     /// Entries with an `id` matching a built-in provider override its fields;
@@ -281,23 +351,67 @@ pub struct ForgeConfig {
     /// when a task ends and reminds the LLM about them.
     #[serde(default)]
     pub verify_todos: bool,
+
+    /// Name of the active profile, whose settings take precedence over the
+    /// rest of the configuration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    /// Named profiles, e.g. `work` or `local`, each bundling a provider and
+    /// model, an API key variable, and policy settings.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Glob patterns of files holding secrets, e.g. `.env` or `*.pem`, that
+    /// file reads, searches and attachments leave out unless the user shares
+    /// them. Patterns without a `/` match file names, and patterns starting
+    /// with `!` exempt files, e.g. `!.env.example`. Shell commands are only
+    /// checked for file names written out in them, not for ones expanded
+    /// from globs, variables or command substitutions.
+    #[serde(default)]
+    pub secret_files: Vec<String>,
+
+    /// Named context presets, e.g. `frontend` or `db-layer`, each a set of
+    /// files, globs and notes placed in the prompt with `/context use <name>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub context_presets: BTreeMap<String, ContextPreset>,
+
+    /// Named schedules, e.g. `nightly-deps`, each a prompt that
+    /// `forge schedule run` sends to an agent at the times of a cron
+    /// expression.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub schedules: BTreeMap<String, Schedule>,
 }
 
 impl ForgeConfig {
     /// Reads and merges configuration from all sources, returning the resolved
     /// [`ForgeConfig`].
     ///
+    /// Later sources take precedence over earlier ones:
+    /// 1. The legacy `~/.forge/.config.json`, whose values are only kept for
+    ///    keys the built-in defaults do not set
+    /// 2. The built-in defaults
+    /// 3. The global `~/.forge/.forge.toml`
+    /// 4. The workspace `.forge/.forge.toml` of `cwd`, limited to the keys a
+    ///    repository may safely set
+    /// 5. `FORGE_`-prefixed environment variables
+    ///
+    /// The settings of `profile`, or of the persisted active profile when
+    /// `None`, are then applied over the result.
+    ///
     /// # Errors
     ///
     /// Returns an error if the config path cannot be resolved, the file cannot
-    /// be read, or deserialization fails.
-    pub fn read() -> crate::Result<ForgeConfig> {
+    /// be read, deserialization fails, or the active profile is not defined.
+    pub fn read(cwd: &Path, profile: Option<&str>) -> crate::Result<ForgeConfig> {
         ConfigReader::default()
             .read_legacy()
             .read_defaults()
             .read_global()
+            .read_workspace(cwd)
             .read_env()
-            .build()
+            .build()?
+            .apply_profile(profile)
     }
 
     /// Writes the configuration to the user config file.
@@ -0,0 +1,25 @@
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A named set of files and notes, e.g. `frontend` or `db-layer`, placed in
+/// the prompt with `/context use <name>` to focus a session on one area of the
+/// workspace.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(into)]
+pub struct ContextPreset {
+    /// Files attached to the prompt, relative to the workspace root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Glob patterns, relative to the workspace root, whose matching files are
+    /// attached to the prompt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub globs: Vec<String>,
+    /// Notes added to the prompt, such as conventions or decisions the agent
+    /// should keep in mind while working in this area.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memory: Vec<String>,
+}
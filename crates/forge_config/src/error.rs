@@ -15,4 +15,8 @@ pub enum Error {
 
     #[error("JSON error: {0}")]
     Serde(#[from] serde_json::Error),
+
+    /// The active profile is not defined in `profiles`.
+    #[error("Unknown profile '{0}'; define it under [profiles.{0}] in .forge.toml")]
+    UnknownProfile(String),
 }
@@ -1,28 +1,50 @@
 mod auto_dump;
 mod compact;
 mod config;
+mod context_preset;
 mod decimal;
 mod error;
 mod http;
 mod legacy;
 mod model;
+mod origin;
+mod otlp;
 mod percentage;
+mod profile;
 mod reader;
 mod reasoning;
+mod response_cache;
+mod retention;
 mod retry;
+mod routing;
+mod schedule;
+mod server;
+mod storage;
+mod telemetry;
 mod writer;
 
 pub use auto_dump::*;
 pub use compact::*;
 pub use config::*;
+pub use context_preset::*;
 pub use decimal::*;
 pub use error::Error;
 pub use http::*;
 pub use model::*;
+pub use origin::*;
+pub use otlp::*;
 pub use percentage::*;
+pub use profile::*;
 pub use reader::*;
 pub use reasoning::*;
+pub use response_cache::*;
+pub use retention::*;
 pub use retry::*;
+pub use routing::*;
+pub use schedule::*;
+pub use server::*;
+pub use storage::*;
+pub use telemetry::*;
 pub use writer::*;
 
 /// A `Result` type alias for this crate's [`Error`] type.
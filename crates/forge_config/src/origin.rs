@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::Value;
+use strum_macros::Display;
+
+use crate::{ConfigReader, ForgeConfig};
+
+/// Source of a configuration value, ordered from lowest to highest precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ConfigOrigin {
+    /// The legacy `~/.forge/.config.json`
+    Legacy,
+    /// The built-in defaults, including values no source sets
+    Default,
+    /// The global `~/.forge/.forge.toml`
+    Global,
+    /// The workspace `.forge/.forge.toml`
+    Workspace,
+    /// A `FORGE_`-prefixed environment variable
+    Env,
+    /// The active profile
+    Profile,
+}
+
+/// Effective value of a configuration key and the source it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigEntry {
+    /// Dotted path of the key, e.g. `session.model_id`
+    pub key: String,
+    /// Effective value
+    pub value: Value,
+    /// Source that set the value
+    pub origin: ConfigOrigin,
+}
+
+impl ForgeConfig {
+    /// Reads the configuration like [`ForgeConfig::read`] and reports the
+    /// source of every effective value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any source cannot be read or deserialized.
+    pub fn read_with_origins(
+        cwd: &Path,
+        profile: Option<&str>,
+    ) -> crate::Result<(ForgeConfig, Vec<ConfigEntry>)> {
+        let config = ForgeConfig::read(cwd, profile)?;
+        let layers = vec![
+            (ConfigOrigin::Legacy, ConfigReader::default().read_legacy()),
+            (
+                ConfigOrigin::Default,
+                ConfigReader::default().read_defaults(),
+            ),
+            (ConfigOrigin::Global, ConfigReader::default().read_global()),
+            (
+                ConfigOrigin::Workspace,
+                ConfigReader::default().read_workspace(cwd),
+            ),
+            (ConfigOrigin::Env, ConfigReader::default().read_env()),
+        ];
+        let mut entries = origins(&config, layers)?;
+        if let Some(profile) = config.active_profile() {
+            let mut values = BTreeMap::new();
+            flatten("", serde_json::to_value(profile)?, &mut values);
+            for entry in entries.iter_mut() {
+                if values.contains_key(&entry.key) {
+                    entry.origin = ConfigOrigin::Profile;
+                }
+            }
+        }
+        Ok((config, entries))
+    }
+}
+
+/// Attributes every value of `config` to the last of `layers`, given from
+/// lowest to highest precedence, that sets its key. Values no layer sets come
+/// from the defaults of [`ForgeConfig`].
+fn origins(
+    config: &ForgeConfig,
+    layers: Vec<(ConfigOrigin, ConfigReader)>,
+) -> crate::Result<Vec<ConfigEntry>> {
+    let mut origins = BTreeMap::new();
+    for (origin, reader) in layers {
+        let mut values = BTreeMap::new();
+        flatten("", reader.build_value()?, &mut values);
+        for key in values.into_keys() {
+            origins.insert(key, origin);
+        }
+    }
+
+    let mut values = BTreeMap::new();
+    flatten("", serde_json::to_value(config)?, &mut values);
+    Ok(values
+        .into_iter()
+        .map(|(key, value)| {
+            let origin = origins.get(&key).copied().unwrap_or(ConfigOrigin::Default);
+            ConfigEntry { key, value, origin }
+        })
+        .collect())
+}
+
+/// Collects the leaves of `value` keyed by their dotted path. Arrays are kept
+/// whole as a single value.
+fn flatten(prefix: &str, value: Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, value, out);
+            }
+        }
+        value => {
+            out.insert(prefix.to_string(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_origins_follow_layer_precedence() {
+        let global = r#"
+max_diff_lines = 50
+currency_symbol = "€"
+"#;
+        let workspace = "max_diff_lines = 10";
+        let layers = vec![
+            (
+                ConfigOrigin::Default,
+                ConfigReader::default().read_defaults(),
+            ),
+            (
+                ConfigOrigin::Global,
+                ConfigReader::default().read_toml(global),
+            ),
+            (
+                ConfigOrigin::Workspace,
+                ConfigReader::default().read_toml(workspace),
+            ),
+        ];
+        let fixture = ConfigReader::default()
+            .read_defaults()
+            .read_toml(global)
+            .read_toml(workspace)
+            .build()
+            .unwrap();
+
+        let actual: Vec<_> = origins(&fixture, layers)
+            .unwrap()
+            .into_iter()
+            .filter(|entry| ["max_diff_lines", "currency_symbol"].contains(&entry.key.as_str()))
+            .map(|entry| (entry.key, entry.value, entry.origin))
+            .collect();
+
+        let expected = vec![
+            (
+                "currency_symbol".to_string(),
+                json!("€"),
+                ConfigOrigin::Global,
+            ),
+            (
+                "max_diff_lines".to_string(),
+                json!(10),
+                ConfigOrigin::Workspace,
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_flatten_nested_tables() {
+        let mut actual = BTreeMap::new();
+
+        flatten(
+            "",
+            json!({"session": {"model_id": "m"}, "codes": [1, 2]}),
+            &mut actual,
+        );
+
+        let expected = BTreeMap::from([
+            ("codes".to_string(), json!([1, 2])),
+            ("session.model_id".to_string(), json!("m")),
+        ]);
+        assert_eq!(actual, expected);
+    }
+}
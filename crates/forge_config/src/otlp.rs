@@ -0,0 +1,21 @@
+use std::collections::BTreeMap;
+
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// OpenTelemetry collector receiving tool call spans, provider request spans
+/// and token usage metrics over OTLP/HTTP.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(into)]
+pub struct OtlpConfig {
+    /// Base URL of the collector, e.g. `http://localhost:4318`. Spans are sent
+    /// to `/v1/traces` and metrics to `/v1/metrics`.
+    pub endpoint: String,
+    /// Headers sent with every export request, e.g. for authentication.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
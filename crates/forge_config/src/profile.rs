@@ -0,0 +1,162 @@
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, ForgeConfig, ModelConfig, ReasoningConfig};
+
+/// A named bundle of settings, e.g. `work` or `local`, applied over the rest
+/// of the configuration while it is the active profile.
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, fake::Dummy, Setters,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(strip_option, into)]
+pub struct Profile {
+    /// Provider and model used for sessions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<ModelConfig>,
+
+    /// Environment variable holding the API key of the session provider; used
+    /// instead of the credential stored by `forge provider login`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_var: Option<String>,
+
+    /// Whether restricted mode is active; when enabled, tool execution
+    /// requires explicit permission grants.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restricted: Option<bool>,
+
+    /// Reasoning configuration applied to all agents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningConfig>,
+}
+
+impl ForgeConfig {
+    /// The active profile, if one is selected and defined.
+    pub fn active_profile(&self) -> Option<&Profile> {
+        self.profile
+            .as_ref()
+            .and_then(|name| self.profiles.get(name))
+    }
+
+    /// Applies the settings of the profile `profile`, e.g. one given with
+    /// `--profile`, over the configuration, or those of the persisted
+    /// `profile` when `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile is not defined in `profiles`.
+    pub fn apply_profile(self, profile: Option<&str>) -> crate::Result<ForgeConfig> {
+        let name = profile.map(str::to_string).or_else(|| self.profile.clone());
+        self.with_profile(name)
+    }
+
+    /// Applies the settings of the profile `name` over the configuration and
+    /// records it as the active profile.
+    fn with_profile(mut self, name: Option<String>) -> crate::Result<ForgeConfig> {
+        let Some(name) = name else {
+            return Ok(self);
+        };
+        let profile = self
+            .profiles
+            .get(&name)
+            .cloned()
+            .ok_or_else(|| Error::UnknownProfile(name.clone()))?;
+
+        if let Some(session) = profile.session {
+            self.session = Some(session);
+        }
+        if let Some(restricted) = profile.restricted {
+            self.restricted = restricted;
+        }
+        if let Some(reasoning) = profile.reasoning {
+            self.reasoning = Some(reasoning);
+        }
+        self.profile = Some(name);
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{ConfigReader, Effort};
+
+    fn config_fixture() -> ForgeConfig {
+        ForgeConfig {
+            session: Some(ModelConfig::new("openai", "gpt-5")),
+            reasoning: Some(ReasoningConfig::default().effort(Effort::Low)),
+            profiles: BTreeMap::from([(
+                "local".to_string(),
+                Profile::default()
+                    .session(ModelConfig::new("ollama", "qwen3"))
+                    .restricted(true),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_with_profile_overlays_set_fields() {
+        let fixture = config_fixture();
+
+        let actual = fixture.with_profile(Some("local".to_string())).unwrap();
+
+        let expected = ForgeConfig {
+            session: Some(ModelConfig::new("ollama", "qwen3")),
+            restricted: true,
+            profile: Some("local".to_string()),
+            ..config_fixture()
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_with_profile_rejects_unknown_profile() {
+        let fixture = config_fixture();
+
+        let actual = fixture.with_profile(Some("work".to_string()));
+
+        assert!(matches!(actual, Err(Error::UnknownProfile(name)) if name == "work"));
+    }
+
+    #[test]
+    fn test_apply_profile_prefers_given_profile_over_persisted() {
+        let fixture = ForgeConfig { profile: Some("work".to_string()), ..config_fixture() };
+
+        let actual = fixture.apply_profile(Some("local")).unwrap();
+
+        let expected = ForgeConfig {
+            session: Some(ModelConfig::new("ollama", "qwen3")),
+            restricted: true,
+            profile: Some("local".to_string()),
+            ..config_fixture()
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_profiles_from_toml() {
+        let fixture = r#"
+profile = "work"
+
+[profiles.work]
+api_key_var = "WORK_OPENAI_API_KEY"
+
+[profiles.work.session]
+provider_id = "openai"
+model_id = "gpt-5"
+"#;
+
+        let actual = ConfigReader::default().read_toml(fixture).build().unwrap();
+
+        let expected = Profile::default()
+            .session(ModelConfig::new("openai", "gpt-5"))
+            .api_key_var("WORK_OPENAI_API_KEY");
+        assert_eq!(actual.active_profile(), Some(&expected));
+    }
+}
@@ -0,0 +1,24 @@
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Cache of provider responses to deterministic requests.
+///
+/// Only requests sent with a temperature of 0 are cached, keyed by provider,
+/// model and request, so that repeated evaluation runs and replayed workflows
+/// reuse identical completions instead of being billed for them again.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(strip_option, into)]
+pub struct ResponseCache {
+    /// Cached responses older than this many hours are requested again; they
+    /// are kept until cleared when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_hours: Option<u64>,
+    /// Maximum combined size in megabytes of the cached responses; the oldest
+    /// ones are evicted beyond it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+}
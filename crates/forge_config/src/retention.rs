@@ -0,0 +1,28 @@
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Limits for automatically pruning stored conversations.
+///
+/// Every limit is optional. Pinned conversations are never pruned and do not
+/// count towards `max_count` or `max_size_mb`.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(strip_option, into)]
+pub struct Retention {
+    /// Conversations not updated within this many days are pruned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u32>,
+    /// Maximum number of unpinned conversations kept per workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_count: Option<usize>,
+    /// Maximum combined size in megabytes of unpinned conversations kept per
+    /// workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+    /// Whether pruning runs automatically when an interactive session starts.
+    #[serde(default)]
+    pub auto_prune: bool,
+}
@@ -0,0 +1,22 @@
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ModelConfig;
+
+/// Models picked by the kind of work an agent does, so that cheaper models
+/// handle the lighter tasks. Agents that set their own model keep it, and
+/// tasks without a rule use the session model.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(strip_option, into)]
+pub struct ModelRouting {
+    /// Model for planning, used by the `muse` agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<ModelConfig>,
+    /// Model for making changes, used by the `forge` agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit: Option<ModelConfig>,
+}
@@ -0,0 +1,23 @@
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A prompt run by `forge schedule run` at the times of a cron expression,
+/// e.g. a nightly dependency triage or a weekly code-health report. Each run
+/// is stored as a new conversation.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(into)]
+pub struct Schedule {
+    /// Cron expression of five fields (minute, hour, day of month, month and
+    /// day of week) in local time, e.g. `0 3 * * 1-5`, or one of `@hourly`,
+    /// `@daily`, `@weekly` and `@monthly`.
+    pub cron: String,
+    /// Prompt sent to the agent on each run.
+    pub prompt: String,
+    /// Agent handling the prompt; the active agent when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Settings of the HTTP server started with `forge serve`.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(into)]
+pub struct ServerConfig {
+    /// Users allowed to access the server. Requests must authenticate with
+    /// the API key of one of them; the server is open to anyone who can reach
+    /// it when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<ServerUser>,
+    /// Quota applied to every API key without a quota of its own; usage is
+    /// unlimited when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<ServerQuota>,
+    /// Webhooks starting agent runs when external services deliver events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<ServerWebhook>,
+}
+
+/// User authenticating to the server with an API key.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(into)]
+pub struct ServerUser {
+    /// Name of the user, recorded as the owner of the conversations they
+    /// create. It must differ from the names of other users and webhooks and
+    /// must not start with `webhook:`.
+    pub name: String,
+    /// Secret sent as a bearer token to authenticate the user. It must not be
+    /// empty or shared with another user.
+    pub api_key: String,
+    /// What the user is allowed to do.
+    #[serde(default)]
+    pub role: ServerRole,
+    /// Quota of the user's API key, overriding the server-wide quota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<ServerQuota>,
+}
+
+/// Usage allowed per API key. Daily limits reset at midnight UTC and the
+/// monthly cost limit on the first day of the month.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(strip_option)]
+pub struct ServerQuota {
+    /// Maximum number of API requests per day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_day: Option<u64>,
+    /// Maximum number of tokens consumed by prompts per day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_per_day: Option<u64>,
+    /// Maximum provider cost of prompts per month.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_per_month: Option<f64>,
+}
+
+/// Permissions granted to a server user.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerRole {
+    /// Read conversations, usage and models
+    #[default]
+    Viewer,
+    /// Also create, update and delete conversations and send prompts
+    Operator,
+}
+
+/// Endpoint at `/v1/webhooks/<name>` turning deliveries of an external
+/// service, such as GitHub, into agent runs.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(into)]
+pub struct ServerWebhook {
+    /// Name of the webhook, used in its URL; the conversations it creates are
+    /// owned by `webhook:<name>`. It must differ from the names of other
+    /// webhooks and users.
+    pub name: String,
+    /// Secret deliveries are signed with. Deliveries must carry a valid
+    /// `X-Hub-Signature-256` header made with it. It must not be empty.
+    pub secret: String,
+    /// Triggers checked in order; the first one matching a delivery starts a
+    /// run and deliveries matching none are ignored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<ServerWebhookTrigger>,
+}
+
+/// Maps matching webhook deliveries to a prompt or workflow command.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Setters, PartialEq, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+#[setters(into)]
+pub struct ServerWebhookTrigger {
+    /// Value the `X-GitHub-Event` header must have, e.g. `issues`; matches
+    /// every event when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    /// JSON pointers into the payload and the value they must have, e.g.
+    /// `"/action" = "opened"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub when: BTreeMap<String, String>,
+    /// Handlebars template rendered with the payload, e.g.
+    /// `Fix issue #{{issue.number}}: {{issue.title}}`.
+    pub prompt: String,
+    /// Workflow command run with the rendered prompt as its parameters; the
+    /// rendered prompt is sent to the active agent when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Location where conversations and snapshots are persisted.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageScope {
+    /// Store data in the global data directory shared by every workspace
+    #[default]
+    Global,
+    /// Store data inside the workspace's `.forge/` directory
+    Workspace,
+}
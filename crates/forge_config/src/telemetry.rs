@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Destination of usage telemetry events.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, fake::Dummy,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryMode {
+    /// Do not record any telemetry
+    Off,
+    /// Append events to a local file that can be inspected by the user
+    Local,
+    /// Send events to the Forge telemetry service
+    #[default]
+    Remote,
+}
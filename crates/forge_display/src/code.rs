@@ -1,12 +1,17 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use syntect::easy::HighlightLines;
-use syntect::highlighting::ThemeSet;
+use syntect::highlighting::{Color, Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
-use syntect::util::as_24_bit_terminal_escaped;
+use syntect::util::{LinesWithEndings, as_24_bit_terminal_escaped};
 use terminal_colorsaurus::{QueryOptions, ThemeMode, theme_mode};
 use two_face::theme::EmbeddedThemeName;
 
+/// Highlighted lines of a file, each split into runs of text sharing a
+/// foreground color. Lines keep their line endings.
+pub(crate) type ColoredLines = Vec<Vec<(Color, String)>>;
+
 /// Loads and caches syntax highlighting resources.
 #[derive(Clone)]
 pub struct SyntaxHighlighter {
@@ -26,13 +31,55 @@ impl Default for SyntaxHighlighter {
 
 impl SyntaxHighlighter {
     /// Detects whether the terminal is using a dark or light background.
-    fn is_dark_theme() -> bool {
+    pub(crate) fn is_dark_theme() -> bool {
         match theme_mode(QueryOptions::default()) {
             Ok(ThemeMode::Light) => false,
             Ok(ThemeMode::Dark) | Err(_) => true,
         }
     }
 
+    /// Theme matching the terminal background.
+    fn theme(&self, is_dark: bool) -> &Theme {
+        let theme_name = if is_dark {
+            EmbeddedThemeName::Base16OceanDark
+        } else {
+            EmbeddedThemeName::InspiredGithub
+        };
+        &self.theme_set.themes[theme_name.as_name()]
+    }
+
+    /// Highlights every line of `code` with the syntax matching the extension
+    /// (or name, e.g. `Makefile`) of `path`.
+    ///
+    /// Returns `None` when no syntax other than plain text matches the path or
+    /// highlighting fails.
+    pub(crate) fn highlight_lines(
+        &self,
+        code: &str,
+        path: &Path,
+        is_dark: bool,
+    ) -> Option<ColoredLines> {
+        let syntax = [path.extension(), path.file_name()]
+            .into_iter()
+            .flatten()
+            .filter_map(|token| token.to_str())
+            .find_map(|token| self.syntax_set.find_syntax_by_extension(token))
+            .filter(|syntax| syntax.name != self.syntax_set.find_syntax_plain_text().name)?;
+        let mut hl = HighlightLines::new(syntax, self.theme(is_dark));
+
+        LinesWithEndings::from(code)
+            .map(|line| {
+                let ranges = hl.highlight_line(line, &self.syntax_set).ok()?;
+                Some(
+                    ranges
+                        .into_iter()
+                        .map(|(style, text)| (style.foreground, text.to_string()))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
     /// Syntax-highlights `code` for the given language token (e.g. `"toml"`,
     /// `"rust"`), returning an ANSI-escaped string ready for terminal output.
     ///
@@ -44,13 +91,7 @@ impl SyntaxHighlighter {
             .syntax_set
             .find_syntax_by_token(lang)
             .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
-        let theme_name = if Self::is_dark_theme() {
-            EmbeddedThemeName::Base16OceanDark
-        } else {
-            EmbeddedThemeName::InspiredGithub
-        };
-        let theme = &self.theme_set.themes[theme_name.as_name()];
-        let mut hl = HighlightLines::new(syntax, theme);
+        let mut hl = HighlightLines::new(syntax, self.theme(Self::is_dark_theme()));
 
         code.lines()
             .filter_map(|line| hl.highlight_line(line, &self.syntax_set).ok())
@@ -272,6 +313,27 @@ mod tests {
         assert!(actual.contains("Bye"));
     }
 
+    #[test]
+    fn test_highlight_lines_by_extension() {
+        let highlighter = SyntaxHighlighter::default();
+        let fixture = "fn main() {}\nlet x = 1;\n";
+
+        let actual = highlighter
+            .highlight_lines(fixture, Path::new("src/main.rs"), true)
+            .unwrap()
+            .into_iter()
+            .map(|line| line.into_iter().map(|(_, text)| text).collect::<String>())
+            .collect::<Vec<_>>();
+
+        let expected = vec!["fn main() {}\n", "let x = 1;\n"];
+        assert_eq!(actual, expected);
+        assert!(
+            highlighter
+                .highlight_lines(fixture, Path::new("notes.unknownext"), true)
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_highlighter_can_be_reused() {
         let highlighter = SyntaxHighlighter::default();
@@ -1,7 +1,15 @@
 use std::fmt;
+use std::path::Path;
+use std::sync::LazyLock;
 
 use console::{Style, style};
-use similar::{ChangeTag, TextDiff};
+use similar::{ChangeTag, InlineChangeMode, InlineChangeOptions, TextDiff};
+use syntect::highlighting::Color;
+
+use crate::code::{ColoredLines, SyntaxHighlighter};
+
+/// Shared so syntaxes and themes are loaded once, not for every diff
+static HIGHLIGHTER: LazyLock<SyntaxHighlighter> = LazyLock::new(SyntaxHighlighter::default);
 
 struct Line {
     index: Option<usize>,
@@ -44,11 +52,137 @@ impl DiffResult {
     }
 }
 
+/// Syntax colors of both sides of a diff
+struct SyntaxColors {
+    old: ColoredLines,
+    new: ColoredLines,
+    is_dark: bool,
+}
+
+impl SyntaxColors {
+    /// Syntax colors of the line a change refers to
+    fn line(
+        &self,
+        tag: ChangeTag,
+        old_index: Option<usize>,
+        new_index: Option<usize>,
+    ) -> Option<&[(Color, String)]> {
+        match tag {
+            ChangeTag::Insert => new_index.and_then(|index| self.new.get(index)),
+            ChangeTag::Delete | ChangeTag::Equal => old_index.and_then(|index| self.old.get(index)),
+        }
+        .map(Vec::as_slice)
+    }
+
+    /// Backgrounds of changed lines and of the words changed within them
+    fn backgrounds(&self, tag: ChangeTag) -> Option<(Color, Color)> {
+        let rgb = |r, g, b| Color { r, g, b, a: 0xff };
+        match (tag, self.is_dark) {
+            (ChangeTag::Delete, true) => Some((rgb(63, 0, 1), rgb(144, 16, 17))),
+            (ChangeTag::Insert, true) => Some((rgb(0, 40, 0), rgb(0, 96, 0))),
+            (ChangeTag::Delete, false) => Some((rgb(255, 235, 233), rgb(255, 192, 192))),
+            (ChangeTag::Insert, false) => Some((rgb(230, 255, 237), rgb(172, 238, 187))),
+            (ChangeTag::Equal, _) => None,
+        }
+    }
+}
+
+/// Paints the segments of a line with their syntax colors on top of the
+/// diff backgrounds, emphasized segments using the stronger background
+fn paint_line<'a>(
+    segments: impl Iterator<Item = (bool, std::borrow::Cow<'a, str>)>,
+    colors: &[(Color, String)],
+    backgrounds: Option<(Color, Color)>,
+) -> String {
+    let mut colors = colors
+        .iter()
+        .flat_map(|(color, text)| text.chars().map(move |_| *color));
+
+    // Consecutive characters sharing a color and emphasis form a run
+    let mut runs: Vec<(Option<Color>, bool, String)> = Vec::new();
+    for (emphasized, text) in segments {
+        for ch in text.chars() {
+            let color = colors.next();
+            match runs.last_mut() {
+                Some((last_color, last_emphasized, run))
+                    if *last_color == color && *last_emphasized == emphasized =>
+                {
+                    run.push(ch)
+                }
+                _ => runs.push((color, emphasized, ch.to_string())),
+            }
+        }
+    }
+
+    let mut output = String::new();
+    for (color, emphasized, run) in runs {
+        let (text, newline) = match run.strip_suffix('\n') {
+            Some(text) => (text, "\n"),
+            None => (run.as_str(), ""),
+        };
+        if let Some(color) = color {
+            output.push_str(&format!("\x1b[38;2;{};{};{}m", color.r, color.g, color.b));
+        }
+        if let Some((line, word)) = backgrounds {
+            let background = if emphasized { word } else { line };
+            output.push_str(&format!(
+                "\x1b[48;2;{};{};{}m",
+                background.r, background.g, background.b
+            ));
+        }
+        output.push_str(text);
+        output.push_str("\x1b[0m");
+        output.push_str(newline);
+    }
+    output
+}
+
 pub struct DiffFormat;
 
 impl DiffFormat {
+    /// Options highlighting the words that changed within modified lines
+    fn inline_options() -> InlineChangeOptions {
+        let mut options = InlineChangeOptions::new();
+        options
+            .mode(InlineChangeMode::UnicodeWords)
+            .semantic_cleanup(true);
+        options
+    }
+
     pub fn format(old: &str, new: &str) -> DiffResult {
+        Self::render(old, new, None)
+    }
+
+    /// Formats the diff of a file, keeping the syntax colors of the language
+    /// matching its extension on changed and unchanged lines.
+    ///
+    /// Falls back to [`DiffFormat::format`] when no syntax matches the path or
+    /// the terminal does not support colors.
+    pub fn format_highlighted(old: &str, new: &str, path: impl AsRef<Path>) -> DiffResult {
+        if !console::colors_enabled() {
+            return Self::format(old, new);
+        }
+        Self::format_themed(old, new, path.as_ref(), SyntaxHighlighter::is_dark_theme())
+    }
+
+    /// Formats the diff of a file with the syntax theme for a dark or light
+    /// terminal background
+    fn format_themed(old: &str, new: &str, path: &Path, is_dark: bool) -> DiffResult {
+        let syntax = HIGHLIGHTER
+            .highlight_lines(old, path, is_dark)
+            .and_then(|old| {
+                Some(SyntaxColors {
+                    old,
+                    new: HIGHLIGHTER.highlight_lines(new, path, is_dark)?,
+                    is_dark,
+                })
+            });
+        Self::render(old, new, syntax.as_ref())
+    }
+
+    fn render(old: &str, new: &str, syntax: Option<&SyntaxColors>) -> DiffResult {
         let diff = TextDiff::from_lines(old, new);
+        let inline_options = Self::inline_options();
         let ops = diff.grouped_ops(3);
         let mut output = String::new();
 
@@ -88,7 +222,7 @@ impl DiffFormat {
                 output.push_str(&format!("{}\n", style("...").dim()));
             }
             for op in group {
-                for change in diff.iter_inline_changes(op) {
+                for change in diff.iter_inline_changes_with_options(op, inline_options) {
                     let (sign, s) = match change.tag() {
                         ChangeTag::Delete => {
                             lines_removed += 1;
@@ -108,7 +242,30 @@ impl DiffFormat {
                         s.apply_to(sign),
                     ));
 
-                    for (_, value) in change.iter_strings_lossy() {
+                    let colors = syntax.and_then(|syntax| {
+                        let line =
+                            syntax.line(change.tag(), change.old_index(), change.new_index())?;
+                        Some((line, syntax.backgrounds(change.tag())))
+                    });
+                    if let Some((line, backgrounds)) = colors {
+                        output.push_str(&paint_line(
+                            change.iter_strings_lossy(),
+                            line,
+                            backgrounds,
+                        ));
+                        if change.missing_newline() {
+                            output.push('\n');
+                        }
+                        continue;
+                    }
+
+                    // Words that changed within the line stand out from the rest of it
+                    for (emphasized, value) in change.iter_strings_lossy() {
+                        let s = if emphasized {
+                            s.clone().reverse()
+                        } else {
+                            s.clone()
+                        };
                         output.push_str(&format!("{}", s.apply_to(value)));
                     }
                     if change.missing_newline() {
@@ -140,6 +297,68 @@ mod tests {
         eprintln!("\nColor Output Test:\n{diff_str}");
     }
 
+    #[test]
+    fn test_changed_words_are_highlighted() {
+        console::set_colors_enabled(true);
+        let old = "let timeout = Duration::from_secs(30);\n";
+        let new = "let timeout = Duration::from_secs(60);\n";
+
+        let actual = DiffFormat::format(old, new);
+
+        let removed = format!("{}", Style::new().red().reverse().apply_to("30"));
+        let added = format!("{}", Style::new().yellow().reverse().apply_to("60"));
+        let unchanged = format!("{}", Style::new().red().reverse().apply_to("let"));
+        assert!(actual.diff().contains(&removed));
+        assert!(actual.diff().contains(&added));
+        assert!(!actual.diff().contains(&unchanged));
+        assert_eq!(
+            strip_ansi_codes(actual.diff()),
+            "1   |-let timeout = Duration::from_secs(30);\n  1 |+let timeout = Duration::from_secs(60);\n"
+        );
+    }
+
+    #[test]
+    fn test_format_highlighted_keeps_syntax_colors() {
+        console::set_colors_enabled(true);
+        let old = "fn main() {\n    let timeout = 30;\n}\n";
+        let new = "fn main() {\n    let timeout = 60;\n}\n";
+
+        let actual = DiffFormat::format_themed(old, new, Path::new("src/main.rs"), true);
+        let plain = DiffFormat::format(old, new);
+
+        assert_eq!(actual.lines_added(), 1);
+        assert_eq!(actual.lines_removed(), 1);
+        assert_eq!(
+            strip_ansi_codes(actual.diff()),
+            strip_ansi_codes(plain.diff())
+        );
+        assert!(actual.diff().contains("\x1b[38;2;"));
+        assert!(actual.diff().contains("\x1b[48;2;144;16;17m"));
+    }
+
+    #[test]
+    fn test_format_highlighted_uses_light_backgrounds() {
+        console::set_colors_enabled(true);
+        let old = "fn main() {\n    let timeout = 30;\n}\n";
+        let new = "fn main() {\n    let timeout = 60;\n}\n";
+
+        let actual = DiffFormat::format_themed(old, new, Path::new("src/main.rs"), false);
+
+        assert!(actual.diff().contains("\x1b[48;2;255;192;192m"));
+        assert!(!actual.diff().contains("\x1b[48;2;144;16;17m"));
+    }
+
+    #[test]
+    fn test_format_highlighted_falls_back_without_syntax() {
+        let old = "first\nsecond\n";
+        let new = "first\nthird\n";
+
+        let actual = DiffFormat::format_themed(old, new, Path::new("notes.unknownext"), true);
+
+        let expected = DiffFormat::format(old, new);
+        assert_eq!(actual.diff(), expected.diff());
+    }
+
     #[test]
     fn test_diff_printer_no_differences() {
         let content = "line 1\nline 2\nline 3";
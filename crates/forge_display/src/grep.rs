@@ -1,9 +1,14 @@
 use std::collections::BTreeMap;
+use std::sync::LazyLock;
 
 use console::style;
 use derive_setters::Setters;
 use regex::Regex;
 
+/// Context line printed by `rg --context`, e.g. `src/main.rs-12-fn main() {`
+static CONTEXT_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([^:]+?)-(\d+)-(.*)$").unwrap());
+
 /// RipGrepFormatter formats search results in ripgrep-like style.
 #[derive(Clone, Setters)]
 #[setters(into, strip_option)]
@@ -13,7 +18,7 @@ pub struct GrepFormat {
 }
 
 /// Represents a parsed line from grep-like output format
-/// (path:line_num:content)
+/// (path:line_num:content for matches, path-line_num-content for context)
 #[derive(Debug)]
 struct ParsedLine<'a> {
     /// File path where the match was found
@@ -22,10 +27,13 @@ struct ParsedLine<'a> {
     line_num: &'a str,
     /// Content of the matching line
     content: &'a str,
+    /// Whether the line matched, as opposed to being context around a match
+    is_match: bool,
 }
 
 impl<'a> ParsedLine<'a> {
-    /// Parse a line in the format "path:line_num:content"
+    /// Parse a line in the format "path:line_num:content", or
+    /// "path-line_num-content" for context lines
     ///
     /// # Arguments
     /// * `line` - The line to parse in the format "path:line_num:content"
@@ -36,7 +44,7 @@ impl<'a> ParsedLine<'a> {
     fn parse(line: &'a str) -> Option<Self> {
         let parts: Vec<_> = line.split(':').collect();
         if parts.len() != 3 {
-            return None;
+            return Self::parse_context(line);
         }
 
         // Validate that path and line number parts are not empty
@@ -45,18 +53,31 @@ impl<'a> ParsedLine<'a> {
             || parts[1].is_empty()
             || !parts[1].chars().all(|c| c.is_ascii_digit())
         {
-            return None;
+            return Self::parse_context(line);
         }
 
         Some(Self {
             path: parts[0].trim(),
             line_num: parts[1].trim(),
             content: parts[2].trim(),
+            is_match: true,
+        })
+    }
+
+    /// Parse a context line in the format "path-line_num-content"
+    fn parse_context(line: &'a str) -> Option<Self> {
+        let captures = CONTEXT_LINE.captures(line)?;
+        let part = |index| captures.get(index).map_or("", |m| m.as_str());
+        Some(Self {
+            path: part(1).trim(),
+            line_num: part(2),
+            content: part(3).trim(),
+            is_match: false,
         })
     }
 }
 
-type Lines<'a> = Vec<(&'a str, &'a str)>;
+type Lines<'a> = Vec<ParsedLine<'a>>;
 impl GrepFormat {
     /// Create a new GrepFormat without a specific regex
     pub fn new(lines: Vec<String>) -> Self {
@@ -71,49 +92,69 @@ impl GrepFormat {
             .filter_map(ParsedLine::parse)
             .fold((BTreeMap::new(), 0), |(mut entries, max_width), parsed| {
                 let new_width = max_width.max(parsed.line_num.len());
-                entries
-                    .entry(parsed.path)
-                    .or_default()
-                    .push((parsed.line_num, parsed.content));
+                entries.entry(parsed.path).or_default().push(parsed);
                 (entries, new_width)
             })
     }
 
-    /// Format a single line with colorization and consistent padding
-    fn format_line(&self, num: &str, content: &str, padding: usize) -> String {
-        let num = style(format!("{num:>padding$}: ")).dim();
-
-        // Format the content with highlighting if regex is available
-        let line = match self.regex {
-            Some(ref regex) => regex.find(content).map_or_else(
-                || content.to_string(),
-                |mat| {
-                    format!(
-                        "{}{}{}",
-                        &content[..mat.start()],
-                        style(&content[mat.start()..mat.end()]).yellow().bold(),
-                        &content[mat.end()..]
-                    )
-                },
-            ),
-            None => content.to_string(),
+    /// Highlight every match of the regex within `content`
+    fn highlight(&self, content: &str) -> String {
+        let Some(ref regex) = self.regex else {
+            return content.to_string();
         };
 
-        format!("{num}{line}\n")
+        let mut line = String::new();
+        let mut last = 0;
+        for mat in regex.find_iter(content) {
+            line.push_str(&content[last..mat.start()]);
+            line.push_str(&style(mat.as_str()).yellow().bold().to_string());
+            last = mat.end();
+        }
+        line.push_str(&content[last..]);
+        line
+    }
+
+    /// Format a single line with colorization and consistent padding. Context
+    /// lines use a `-` separator and still show where the pattern matches.
+    fn format_line(&self, parsed: &ParsedLine<'_>, padding: usize) -> String {
+        let num = parsed.line_num;
+        if parsed.is_match {
+            let num = style(format!("{num:>padding$}: ")).dim();
+            format!("{num}{}\n", self.highlight(parsed.content))
+        } else {
+            let num = style(format!("{num:>padding$}- ")).dim();
+            let line = match self.regex {
+                Some(_) => self.highlight(parsed.content),
+                None => style(parsed.content).dim().to_string(),
+            };
+            format!("{num}{line}\n")
+        }
     }
 
-    /// Format a group of lines for a single file
-    fn format_file_group(
-        &self,
-        path: &str,
-        group: Vec<(&str, &str)>,
-        max_num_width: usize,
-    ) -> String {
-        let file_header = style(path).cyan();
-        let formatted_lines = group
-            .into_iter()
-            .map(|(num, content)| self.format_line(num, content, max_num_width))
-            .collect::<String>();
+    /// Format a group of lines for a single file under a header with the
+    /// number of matches. Gaps between non-adjacent lines are marked with
+    /// `--` like ripgrep does when showing context.
+    fn format_file_group(&self, path: &str, group: Lines<'_>, max_num_width: usize) -> String {
+        let matches = group.iter().filter(|parsed| parsed.is_match).count();
+        let count = if matches == 1 {
+            "(1 match)".to_string()
+        } else {
+            format!("({matches} matches)")
+        };
+        let file_header = format!("{} {}", style(path).cyan(), style(count).dim());
+
+        let has_context = group.iter().any(|parsed| !parsed.is_match);
+        let mut previous: Option<usize> = None;
+        let mut formatted_lines = String::new();
+        for parsed in &group {
+            let current = parsed.line_num.parse::<usize>().ok();
+            let is_gap = matches!((previous, current), (Some(p), Some(c)) if c > p + 1);
+            if has_context && is_gap {
+                formatted_lines.push_str(&format!("{}\n", style("--").dim()));
+            }
+            previous = current;
+            formatted_lines.push_str(&self.format_line(parsed, max_num_width));
+        }
         format!("{file_header}\n{formatted_lines}")
     }
 
@@ -166,6 +207,7 @@ mod tests {
     use std::fmt::{Display, Formatter};
 
     use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
 
     use super::*;
 
@@ -312,9 +354,34 @@ mod tests {
             None,
         );
 
+        suite.add(
+            "Context lines around matches",
+            vec![
+                "src/main.rs-1-use std::io;",
+                "src/main.rs:2:fn main() {",
+                "src/main.rs-3-    run();",
+                "--",
+                "src/main.rs-9-// main entry",
+                "src/main.rs:10:fn main_loop() {",
+            ],
+            Some("main"),
+        );
+
         assert_snapshot!(suite);
     }
 
+    #[test]
+    fn test_highlights_every_match_in_context_lines() {
+        console::set_colors_enabled(true);
+        let fixture = GrepFormat::new(vec!["src/main.rs-9-// main calls main".to_string()])
+            .regex(Regex::new("main").unwrap());
+
+        let actual = fixture.format();
+
+        let highlighted = style("main").yellow().bold().to_string();
+        assert_eq!(actual.matches(&highlighted).count(), 2);
+    }
+
     #[test]
     fn test_with_and_without_regex() {
         let lines = vec!["a/b/c.md".to_string(), "p/q/r.rs".to_string()];
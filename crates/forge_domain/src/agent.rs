@@ -8,8 +8,8 @@ use serde::{Deserialize, Serialize};
 use strum_macros::{Display as StrumDisplay, EnumString};
 
 use crate::{
-    Compact, Error, EventContext, MaxTokens, ModelId, ProviderId, Result, SystemContext,
-    Temperature, Template, ToolDefinition, ToolName, TopK, TopP,
+    Compact, Error, EventContext, MaxTokens, ModelId, ProviderId, ProviderResponse, Result,
+    SystemContext, Temperature, Template, ToolDefinition, ToolName, TopK, TopP,
 };
 
 // Unique identifier for an agent
@@ -71,7 +71,9 @@ pub struct ReasoningConfig {
     pub enabled: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, StrumDisplay, EnumString)]
+#[derive(
+    Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, StrumDisplay, EnumString,
+)]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase", ascii_case_insensitive)]
 pub enum Effort {
@@ -91,6 +93,20 @@ pub enum Effort {
     Max,
 }
 
+/// How much detail the model puts in its answers, independently of how long
+/// it reasons.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, StrumDisplay, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Verbosity {
+    /// Short answers.
+    Low,
+    /// Balanced answers; the default of the models supporting verbosity.
+    Medium,
+    /// Thorough answers.
+    High,
+}
+
 /// Estimates the token count from a string representation
 /// This is a simple estimation that should be replaced with a more accurate
 /// tokenizer
@@ -155,12 +171,24 @@ pub struct Agent {
     /// Top-k used for agent
     pub top_k: Option<TopK>,
 
+    /// Penalty on tokens by how often they already appeared
+    pub frequency_penalty: Option<f32>,
+
+    /// Penalty on tokens that already appeared at least once
+    pub presence_penalty: Option<f32>,
+
+    /// Sequences that end the model's response when generated
+    pub stop: Option<Vec<String>>,
+
     /// Maximum number of tokens the model can generate
     pub max_tokens: Option<MaxTokens>,
 
     /// Reasoning configuration for the agent.
     pub reasoning: Option<ReasoningConfig>,
 
+    /// Detail of the agent's answers, supported by OpenAI models
+    pub verbosity: Option<Verbosity>,
+
     /// Maximum number of times a tool can fail before sending the response back
     pub max_tool_failure_per_turn: Option<usize>,
 
@@ -202,14 +230,47 @@ impl Agent {
             temperature: Default::default(),
             top_p: Default::default(),
             top_k: Default::default(),
+            frequency_penalty: Default::default(),
+            presence_penalty: Default::default(),
+            stop: Default::default(),
             max_tokens: Default::default(),
             reasoning: Default::default(),
+            verbosity: Default::default(),
             max_tool_failure_per_turn: Default::default(),
             max_requests_per_turn: Default::default(),
             path: Default::default(),
         }
     }
 
+    /// Names of the sampling parameters set on this agent that requests in the
+    /// `response` format cannot carry
+    pub fn unsupported_sampling_parameters(
+        &self,
+        response: &ProviderResponse,
+    ) -> Vec<&'static str> {
+        let (penalties, stop) = match response {
+            ProviderResponse::OpenAI | ProviderResponse::Google | ProviderResponse::OpenCode => {
+                (true, true)
+            }
+            ProviderResponse::Anthropic | ProviderResponse::Bedrock => (false, true),
+            ProviderResponse::OpenAIResponses => (false, false),
+        };
+        [
+            (
+                "frequency_penalty",
+                self.frequency_penalty.is_some() && !penalties,
+            ),
+            (
+                "presence_penalty",
+                self.presence_penalty.is_some() && !penalties,
+            ),
+            ("stop", self.stop.is_some() && !stop),
+        ]
+        .into_iter()
+        .filter_map(|(name, unsupported)| unsupported.then_some(name))
+        .collect()
+    }
+
     /// Creates a ToolDefinition from this agent
     ///
     /// # Errors
@@ -251,3 +312,34 @@ impl From<Agent> for ToolDefinition {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_unsupported_sampling_parameters() {
+        let fixture = Agent::new(
+            AgentId::FORGE,
+            ProviderId::ANTHROPIC,
+            ModelId::new("claude"),
+        )
+        .frequency_penalty(0.5_f32)
+        .stop(vec!["END".to_string()]);
+
+        let actual = (
+            fixture.unsupported_sampling_parameters(&ProviderResponse::OpenAI),
+            fixture.unsupported_sampling_parameters(&ProviderResponse::Anthropic),
+            fixture.unsupported_sampling_parameters(&ProviderResponse::OpenAIResponses),
+        );
+
+        let expected = (
+            vec![],
+            vec!["frequency_penalty"],
+            vec!["frequency_penalty", "stop"],
+        );
+        assert_eq!(actual, expected);
+    }
+}
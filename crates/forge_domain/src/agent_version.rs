@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A recorded revision of a custom agent definition file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentVersion {
+    /// Sequential version number, starting at 1
+    pub version: u32,
+    /// Definition file the revision was read from
+    pub path: PathBuf,
+    /// SHA-256 hash of the file content
+    pub hash: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl AgentVersion {
+    /// Returns the version to record for `path` when its content hashes to
+    /// `hash`, or `None` when the latest revision of that file is unchanged
+    pub fn next(versions: &[AgentVersion], path: PathBuf, hash: String) -> Option<AgentVersion> {
+        let unchanged = versions
+            .iter()
+            .rev()
+            .find(|version| version.path == path)
+            .is_some_and(|version| version.hash == hash);
+        if unchanged {
+            return None;
+        }
+
+        let version = versions.iter().map(|v| v.version).max().unwrap_or(0) + 1;
+        Some(AgentVersion { version, path, hash, recorded_at: Utc::now() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn version(version: u32, path: &str, hash: &str) -> AgentVersion {
+        AgentVersion {
+            version,
+            path: PathBuf::from(path),
+            hash: hash.to_string(),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_next_skips_unchanged_content() {
+        let fixture = vec![version(1, "/a.md", "x")];
+        let actual = AgentVersion::next(&fixture, PathBuf::from("/a.md"), "x".to_string());
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn test_next_increments_on_change() {
+        let fixture = vec![version(1, "/a.md", "x"), version(2, "/b.md", "y")];
+        let actual = AgentVersion::next(&fixture, PathBuf::from("/a.md"), "z".to_string())
+            .map(|v| (v.version, v.hash));
+        let expected = Some((3, "z".to_string()));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_next_compares_against_same_file() {
+        let fixture = vec![version(1, "/a.md", "x"), version(2, "/b.md", "y")];
+        let actual = AgentVersion::next(&fixture, PathBuf::from("/b.md"), "x".to_string())
+            .map(|v| v.version);
+        assert_eq!(actual, Some(3));
+    }
+}
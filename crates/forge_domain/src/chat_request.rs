@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 
@@ -8,10 +11,33 @@ use crate::{ConversationId, Event};
 pub struct ChatRequest {
     pub event: Event,
     pub conversation_id: ConversationId,
+    /// Lets the caller pause the turn once the tool call in progress is done
+    #[serde(skip)]
+    pub pause: PauseSignal,
 }
 
 impl ChatRequest {
     pub fn new(content: Event, conversation_id: ConversationId) -> Self {
-        Self { event: content, conversation_id }
+        Self { event: content, conversation_id, pause: Default::default() }
+    }
+}
+
+/// Asks a running turn to stop after the tool call in progress instead of
+/// aborting it midway. Clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct PauseSignal(Arc<AtomicBool>);
+
+impl PauseSignal {
+    pub fn request(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Withdraws the request, e.g. once the paused turn is resumed
+    pub fn clear(&self) {
+        self.0.store(false, Ordering::SeqCst);
     }
 }
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::Local;
-use tokio::sync::Notify;
+use tokio::sync::{Notify, oneshot};
 
 use crate::{ToolCallFull, ToolName, ToolResult};
 
@@ -13,7 +13,22 @@ pub enum ChatResponseContent {
     ToolInput(TitleFormat),
     // Should be only used to send tool outputs.
     ToolOutput(String),
-    Markdown { text: String, partial: bool },
+    /// Rendered diff of a file changed by a tool
+    FileDiff(FileDiff),
+    Markdown {
+        text: String,
+        partial: bool,
+    },
+}
+
+/// Changes a tool made to a file, rendered for display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path of the changed file
+    pub path: String,
+    pub diff: String,
+    pub lines_added: u64,
+    pub lines_removed: u64,
 }
 
 impl From<ChatResponseContent> for ChatResponse {
@@ -44,6 +59,7 @@ impl ChatResponseContent {
             ChatResponseContent::ToolOutput(text) | ChatResponseContent::Markdown { text, .. } => {
                 text
             }
+            ChatResponseContent::FileDiff(diff) => &diff.diff,
             ChatResponseContent::ToolInput(_) => "",
         }
     }
@@ -68,10 +84,49 @@ pub enum ChatResponse {
     RetryAttempt {
         cause: Cause,
         duration: Duration,
+        /// Whether the provider stopped sending data mid-response
+        stalled: bool,
     },
     Interrupt {
         reason: InterruptionReason,
     },
+    /// A tool call is waiting for the user to confirm a restricted operation
+    PermissionRequest {
+        tool_name: ToolName,
+        message: String,
+        /// Set when the consumer of the stream answers the request; `None`
+        /// when the user is asked in the terminal
+        reply: Option<PermissionReply>,
+    },
+}
+
+/// Answer to a [`ChatResponse::PermissionRequest`] given by the consumer of
+/// the chat stream. The request is denied when every copy is dropped without
+/// answering.
+#[derive(Debug, Clone)]
+pub struct PermissionReply(Arc<std::sync::Mutex<Option<oneshot::Sender<bool>>>>);
+
+impl PermissionReply {
+    /// Creates a reply and the receiver the asking tool call waits on
+    pub fn new() -> (Self, oneshot::Receiver<bool>) {
+        let (sender, receiver) = oneshot::channel();
+        (
+            Self(Arc::new(std::sync::Mutex::new(Some(sender)))),
+            receiver,
+        )
+    }
+
+    /// Allows or denies the operation; only the first answer counts
+    pub fn send(&self, allowed: bool) {
+        let sender = self
+            .0
+            .lock()
+            .expect("permission reply lock poisoned")
+            .take();
+        if let Some(sender) = sender {
+            let _ = sender.send(allowed);
+        }
+    }
 }
 
 impl ChatResponse {
@@ -85,6 +140,7 @@ impl ChatResponse {
             ChatResponse::TaskMessage { content, .. } => match content {
                 ChatResponseContent::ToolInput(_) => false,
                 ChatResponseContent::ToolOutput(content) => content.is_empty(),
+                ChatResponseContent::FileDiff(diff) => diff.diff.is_empty(),
                 ChatResponseContent::Markdown { text, .. } => text.is_empty(),
             },
             ChatResponse::TaskReasoning { content } => content.is_empty(),
@@ -102,6 +158,8 @@ pub enum InterruptionReason {
     MaxRequestPerTurnLimitReached {
         limit: u64,
     },
+    /// The user asked to pause; tool calls not started yet were skipped
+    Paused,
 }
 
 #[derive(Clone)]
@@ -235,4 +293,26 @@ mod tests {
         assert_eq!(title.category, Category::Action);
         assert_eq!(title.timestamp, timestamp);
     }
+
+    #[tokio::test]
+    async fn test_permission_reply_keeps_first_answer() {
+        let (fixture, answer) = PermissionReply::new();
+
+        fixture.clone().send(true);
+        fixture.send(false);
+
+        let actual = answer.await.unwrap();
+        assert_eq!(actual, true);
+    }
+
+    #[tokio::test]
+    async fn test_permission_reply_dropped_unanswered() {
+        let (fixture, answer) = PermissionReply::new();
+
+        drop(fixture.clone());
+        drop(fixture);
+
+        let actual = answer.await.is_err();
+        assert_eq!(actual, true);
+    }
 }
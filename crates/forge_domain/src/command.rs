@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use derive_setters::Setters;
 use serde::Deserialize;
+use serde_json::Value;
+use strum_macros::Display;
 
 /// A user-defined command loaded from a Markdown file with YAML frontmatter.
 ///
@@ -18,4 +22,261 @@ pub struct Command {
     /// The prompt template body (Markdown content after the frontmatter).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+    /// Named arguments accepted by the command, available to the prompt
+    /// template as variables of the same name.
+    #[serde(default)]
+    pub arguments: Vec<CommandArgument>,
+}
+
+/// Type of the value accepted by a [`CommandArgument`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ArgumentType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+}
+
+/// A named argument declared in the frontmatter of a custom command.
+///
+/// Arguments are given either as `name=value` or positionally in the order
+/// they are declared.
+#[derive(Debug, Clone, Default, Deserialize, Setters, PartialEq, Eq)]
+#[setters(into, strip_option)]
+pub struct CommandArgument {
+    /// Name used to pass the argument and to reference it in the prompt.
+    pub name: String,
+    /// Type the given value is checked against.
+    #[serde(default, rename = "type")]
+    pub kind: ArgumentType,
+    /// Short description shown when completing the argument.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Value used when the argument is not given.
+    #[serde(default)]
+    #[setters(rename = "default_value")]
+    pub default: Option<Value>,
+    /// Whether the command fails when the argument is not given.
+    #[serde(default)]
+    pub required: bool,
+    /// Accepted values, offered for completion. Any value is accepted when
+    /// empty.
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+impl CommandArgument {
+    /// Binds the words following a command to `arguments`.
+    ///
+    /// `name=value` words bind by name and the remaining words bind in
+    /// declaration order, the last positional string argument taking all
+    /// the words left over. Arguments that are not given take their default
+    /// or `null`, so that templates can test for them with `{{#if name}}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a required argument is missing or a value does
+    /// not match the type or the accepted values of its argument.
+    pub fn bind(
+        arguments: &[CommandArgument],
+        words: &[&str],
+    ) -> anyhow::Result<BTreeMap<String, Value>> {
+        let mut given: BTreeMap<&str, String> = BTreeMap::new();
+        let mut positional = Vec::new();
+        for word in words {
+            match word.split_once('=') {
+                Some((name, value)) if arguments.iter().any(|arg| arg.name == name) => {
+                    given.insert(name, value.to_string());
+                }
+                _ => positional.push(*word),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let unnamed = arguments
+            .iter()
+            .filter(|arg| !given.contains_key(arg.name.as_str()))
+            .collect::<Vec<_>>();
+        for (index, arg) in unnamed.iter().enumerate() {
+            let value = if index + 1 == unnamed.len() && arg.kind == ArgumentType::String {
+                positional.by_ref().collect::<Vec<_>>().join(" ")
+            } else {
+                positional.next().unwrap_or_default().to_string()
+            };
+            if !value.is_empty() {
+                given.insert(arg.name.as_str(), value);
+            }
+        }
+
+        arguments
+            .iter()
+            .map(|arg| {
+                let value = match (given.get(arg.name.as_str()), &arg.default) {
+                    (Some(value), _) => arg.parse(value)?,
+                    (None, Some(default)) => default.clone(),
+                    (None, None) if arg.required => {
+                        anyhow::bail!(
+                            "Missing required argument '{}'. Usage: {}",
+                            arg.name,
+                            Self::usage(arguments)
+                        )
+                    }
+                    (None, None) => Value::Null,
+                };
+                Ok((arg.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Synopsis of `arguments`, e.g. `<target> [depth=3]`.
+    pub fn usage(arguments: &[CommandArgument]) -> String {
+        arguments
+            .iter()
+            .map(|arg| match (arg.default_display(), arg.required) {
+                (_, true) => format!("<{}>", arg.name),
+                (Some(default), false) => format!("[{}={default}]", arg.name),
+                (None, false) => format!("[{}]", arg.name),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The default value the way it would be typed on the command line.
+    pub fn default_display(&self) -> Option<String> {
+        self.default.as_ref().map(|value| match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        })
+    }
+
+    /// Converts a value given on the command line to the argument's type.
+    fn parse(&self, value: &str) -> anyhow::Result<Value> {
+        if !self.values.is_empty() && !self.values.iter().any(|accepted| accepted == value) {
+            anyhow::bail!(
+                "Argument '{}' must be one of: {}",
+                self.name,
+                self.values.join(", ")
+            );
+        }
+
+        let parsed = match self.kind {
+            ArgumentType::String => Some(Value::from(value)),
+            ArgumentType::Number => value.parse::<i64>().map(Value::from).ok().or_else(|| {
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|n| serde_json::Number::from_f64(n).map(Value::Number))
+            }),
+            ArgumentType::Boolean => match value.to_lowercase().as_str() {
+                "true" | "yes" | "1" => Some(Value::Bool(true)),
+                "false" | "no" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+        };
+        parsed.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Argument '{}' expects a {}, got '{value}'",
+                self.name,
+                self.kind
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn fixture() -> Vec<CommandArgument> {
+        vec![
+            CommandArgument::default().name("target").required(true),
+            CommandArgument::default()
+                .name("depth")
+                .kind(ArgumentType::Number)
+                .default_value(json!(3)),
+            CommandArgument::default()
+                .name("format")
+                .values(vec!["short".to_string(), "long".to_string()]),
+            CommandArgument::default().name("focus"),
+        ]
+    }
+
+    #[test]
+    fn test_bind_named_and_positional_arguments() {
+        let actual = CommandArgument::bind(
+            &fixture(),
+            &["depth=5", "src/lib.rs", "short", "error", "handling"],
+        )
+        .unwrap();
+        let expected = BTreeMap::from([
+            ("target".to_string(), json!("src/lib.rs")),
+            ("depth".to_string(), json!(5)),
+            ("format".to_string(), json!("short")),
+            ("focus".to_string(), json!("error handling")),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bind_applies_defaults() {
+        let actual = CommandArgument::bind(&fixture(), &["target=src"]).unwrap();
+        let expected = BTreeMap::from([
+            ("target".to_string(), json!("src")),
+            ("depth".to_string(), json!(3)),
+            ("format".to_string(), Value::Null),
+            ("focus".to_string(), Value::Null),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bind_rejects_invalid_arguments() {
+        let arguments = fixture();
+        let actual = [
+            vec![],
+            vec!["src", "depth=deep"],
+            vec!["src", "format=medium"],
+        ]
+        .iter()
+        .map(|words| {
+            CommandArgument::bind(&arguments, words)
+                .unwrap_err()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+        let expected = vec![
+            "Missing required argument 'target'. Usage: <target> [depth=3] [format] [focus]"
+                .to_string(),
+            "Argument 'depth' expects a number, got 'deep'".to_string(),
+            "Argument 'format' must be one of: short, long".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_deserialize_command_arguments() {
+        let fixture = json!({
+            "name": "review",
+            "arguments": [
+                {"name": "strict", "type": "boolean", "default": false, "description": "Fail on warnings"}
+            ]
+        });
+
+        let actual = serde_json::from_value::<Command>(fixture)
+            .unwrap()
+            .arguments;
+        let expected = vec![
+            CommandArgument::default()
+                .name("strict")
+                .kind(ArgumentType::Boolean)
+                .default_value(json!(false))
+                .description("Fail on warnings"),
+        ];
+        assert_eq!(actual, expected);
+    }
 }
@@ -36,6 +36,18 @@ pub struct Compact {
     #[merge(strategy = crate::merge::option)]
     pub token_threshold: Option<usize>,
 
+    /// Fraction of the model's context window to keep after compaction.
+    /// Replaces `max_tokens` once the model's context length is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = crate::merge::option)]
+    pub max_tokens_ratio: Option<f64>,
+
+    /// Fraction of the model's context window at which compaction triggers.
+    /// Replaces `token_threshold` once the model's context length is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = crate::merge::option)]
+    pub token_threshold_ratio: Option<f64>,
+
     /// Maximum number of conversation turns before triggering compaction
     #[serde(skip_serializing_if = "Option::is_none")]
     #[merge(strategy = crate::merge::option)]
@@ -86,6 +98,8 @@ impl Compact {
         Self {
             max_tokens: None,
             token_threshold: None,
+            max_tokens_ratio: None,
+            token_threshold_ratio: None,
             turn_threshold: None,
             message_threshold: None,
             model: None,
@@ -95,6 +109,23 @@ impl Compact {
         }
     }
 
+    /// Derives `token_threshold` and `max_tokens` from the ratios of the
+    /// model's context window, keeping the fixed values when the context
+    /// length is unknown or no ratio is set
+    pub fn with_context_length(mut self, context_length: Option<u64>) -> Self {
+        let Some(context_length) = context_length else {
+            return self;
+        };
+        let share = |ratio: f64| (context_length as f64 * ratio.clamp(0.0, 1.0)) as usize;
+        if let Some(ratio) = self.token_threshold_ratio {
+            self.token_threshold = Some(share(ratio));
+        }
+        if let Some(ratio) = self.max_tokens_ratio {
+            self.max_tokens = Some(share(ratio));
+        }
+        self
+    }
+
     /// Determines if compaction should be triggered based on the current
     /// context
     pub fn should_compact(&self, context: &Context, token_count: usize) -> bool {
@@ -328,6 +359,30 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_with_context_length_derives_thresholds_from_ratios() {
+        let fixture = Compact::new()
+            .token_threshold(100_000_usize)
+            .max_tokens(2_000_usize)
+            .token_threshold_ratio(0.5)
+            .max_tokens_ratio(0.01);
+
+        let actual = fixture.with_context_length(Some(1_000_000));
+        let expected = (Some(500_000), Some(10_000));
+        assert_eq!((actual.token_threshold, actual.max_tokens), expected);
+    }
+
+    #[test]
+    fn test_with_context_length_keeps_fixed_thresholds_when_unknown() {
+        let fixture = Compact::new()
+            .token_threshold(100_000_usize)
+            .token_threshold_ratio(0.5);
+
+        let actual = fixture.with_context_length(None);
+        let expected = Some(100_000);
+        assert_eq!(actual.token_threshold, expected);
+    }
+
     #[test]
     fn test_should_compact_turn_threshold_triggers() {
         let fixture = Compact::new()
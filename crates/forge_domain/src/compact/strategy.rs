@@ -1,4 +1,4 @@
-use crate::{Context, Role};
+use crate::{Context, Role, TokenCounter};
 
 /// Strategy for context compaction that unifies different compaction approaches
 #[derive(Debug, Clone)]
@@ -38,11 +38,11 @@ impl CompactionStrategy {
     /// This simulates the original percentage algorithm to determine how many
     /// messages would be preserved, then returns that as a preserve_last_n
     /// value
-    fn to_fixed(&self, context: &Context) -> usize {
+    fn to_fixed(&self, context: &Context, counter: &dyn TokenCounter) -> usize {
         match self {
             CompactionStrategy::Evict(percentage) => {
                 let percentage = percentage.min(1.0);
-                let total_tokens = context.token_count();
+                let total_tokens = context.token_count_with(counter);
                 let mut eviction_budget: usize =
                     (percentage * (*total_tokens) as f64).ceil() as usize;
 
@@ -53,7 +53,8 @@ impl CompactionStrategy {
                     // Skip system message
                     .filter(|m| !m.1.has_role(Role::System))
                     .find(|(_, m)| {
-                        eviction_budget = eviction_budget.saturating_sub(m.token_count_approx());
+                        eviction_budget =
+                            eviction_budget.saturating_sub(m.token_count_with(counter));
                         eviction_budget == 0
                     });
 
@@ -63,14 +64,23 @@ impl CompactionStrategy {
                 }
             }
             CompactionStrategy::Retain(fixed) => *fixed,
-            CompactionStrategy::Min(a, b) => a.to_fixed(context).min(b.to_fixed(context)),
-            CompactionStrategy::Max(a, b) => a.to_fixed(context).max(b.to_fixed(context)),
+            CompactionStrategy::Min(a, b) => a
+                .to_fixed(context, counter)
+                .min(b.to_fixed(context, counter)),
+            CompactionStrategy::Max(a, b) => a
+                .to_fixed(context, counter)
+                .max(b.to_fixed(context, counter)),
         }
     }
 
-    /// Find the sequence to compact using the unified algorithm
-    pub fn eviction_range(&self, context: &Context) -> Option<(usize, usize)> {
-        let retention = self.to_fixed(context);
+    /// Find the sequence to compact using the unified algorithm, measuring
+    /// messages with `counter`
+    pub fn eviction_range(
+        &self,
+        context: &Context,
+        counter: &dyn TokenCounter,
+    ) -> Option<(usize, usize)> {
+        let retention = self.to_fixed(context, counter);
         find_sequence_preserving_last_n(context, retention)
     }
 }
@@ -156,7 +166,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::MessagePattern;
+    use crate::{ApproxTokenCounter, MessagePattern};
 
     fn context_from_pattern(pattern: impl ToString) -> Context {
         MessagePattern::new(pattern.to_string()).build()
@@ -350,13 +360,13 @@ mod tests {
         // Result: Eviction budget exhausted at index 2 (Assistant), so to_fixed returns
         // 2
         let percentage_strategy = CompactionStrategy::evict(0.4);
-        let actual = percentage_strategy.to_fixed(&fixture);
+        let actual = percentage_strategy.to_fixed(&fixture, &ApproxTokenCounter);
         let expected = 2;
         assert_eq!(actual, expected);
 
         // Test PreserveLastN strategy
         let preserve_strategy = CompactionStrategy::retain(3);
-        let actual = preserve_strategy.to_fixed(&fixture);
+        let actual = preserve_strategy.to_fixed(&fixture, &ApproxTokenCounter);
         let expected = 3;
         assert_eq!(actual, expected);
 
@@ -365,7 +375,7 @@ mod tests {
         // With 9 tokens budget, all 3 messages (3+3+3) exhaust the budget at message
         // index 2
         let invalid_strategy = CompactionStrategy::evict(1.5);
-        let actual = invalid_strategy.to_fixed(&fixture);
+        let actual = invalid_strategy.to_fixed(&fixture, &ApproxTokenCounter);
         let expected = 2; // Returns index 2 (last message) when all messages fit in budget
         assert_eq!(actual, expected);
     }
@@ -376,12 +386,12 @@ mod tests {
         let fixture = context_from_pattern("uauau");
 
         let percentage_strategy = CompactionStrategy::evict(0.6);
-        let actual_sequence = percentage_strategy.eviction_range(&fixture);
+        let actual_sequence = percentage_strategy.eviction_range(&fixture, &ApproxTokenCounter);
 
         // Convert percentage to preserve_last_n and test equivalence
-        let preserve_last_n = percentage_strategy.to_fixed(&fixture);
+        let preserve_last_n = percentage_strategy.to_fixed(&fixture, &ApproxTokenCounter);
         let preserve_strategy = CompactionStrategy::retain(preserve_last_n);
-        let expected_sequence = preserve_strategy.eviction_range(&fixture);
+        let expected_sequence = preserve_strategy.eviction_range(&fixture, &ApproxTokenCounter);
         assert_eq!(actual_sequence, expected_sequence);
     }
 
@@ -392,12 +402,12 @@ mod tests {
 
         // Use percentage-based strategy
         let percentage_strategy = CompactionStrategy::evict(0.4);
-        percentage_strategy.to_fixed(&fixture);
+        percentage_strategy.to_fixed(&fixture, &ApproxTokenCounter);
 
         // Use fixed window strategy - preserve last 1 message, starting from first
         // assistant
         let preserve_strategy = CompactionStrategy::retain(1);
-        let actual_sequence = preserve_strategy.eviction_range(&fixture);
+        let actual_sequence = preserve_strategy.eviction_range(&fixture, &ApproxTokenCounter);
         let expected = Some((1, 2)); // Start from first assistant at index 1
         assert_eq!(actual_sequence, expected);
     }
@@ -408,11 +418,11 @@ mod tests {
         let empty_context = Context::default();
 
         let percentage_strategy = CompactionStrategy::evict(0.4);
-        let actual = percentage_strategy.to_fixed(&empty_context);
+        let actual = percentage_strategy.to_fixed(&empty_context, &ApproxTokenCounter);
         let expected = 0; // Should be 0 for empty context (saturating_sub(1) on 0 = 0)
         assert_eq!(actual, expected);
 
-        let actual_range = percentage_strategy.eviction_range(&empty_context);
+        let actual_range = percentage_strategy.eviction_range(&empty_context, &ApproxTokenCounter);
         assert_eq!(actual_range, None); // Should return None for empty context
     }
 
@@ -422,11 +432,11 @@ mod tests {
         let single_context = context_from_pattern("s");
 
         let percentage_strategy = CompactionStrategy::evict(0.4);
-        let actual = percentage_strategy.to_fixed(&single_context);
+        let actual = percentage_strategy.to_fixed(&single_context, &ApproxTokenCounter);
         let expected = 0; // Should be 0 (1 - 1 = 0 with saturating_sub)
         assert_eq!(actual, expected);
 
-        let actual_range = percentage_strategy.eviction_range(&single_context);
+        let actual_range = percentage_strategy.eviction_range(&single_context, &ApproxTokenCounter);
         assert_eq!(actual_range, None); // Should return None for single system message
     }
 }
@@ -18,8 +18,9 @@ use crate::temperature::Temperature;
 use crate::top_k::TopK;
 use crate::top_p::TopP;
 use crate::{
-    Attachment, AttachmentContent, ConversationId, EventValue, Image, MessagePhase, ModelId,
-    ReasoningFull, ToolChoice, ToolDefinition, ToolOutput, ToolValue, Usage,
+    ApproxTokenCounter, Attachment, AttachmentContent, ConversationId, EventValue, Image,
+    MessagePhase, ModelId, ReasoningFull, TokenCounter, ToolChoice, ToolDefinition, ToolOutput,
+    ToolValue, Usage,
 };
 
 /// Response format for structured output
@@ -89,25 +90,35 @@ impl ContextMessage {
     /// approximation.
     /// ref: https://github.com/openai/codex/blob/main/codex-cli/src/utils/approximate-tokens-used.ts
     pub fn token_count_approx(&self) -> usize {
-        let char_count = match self {
+        self.token_count_with(&ApproxTokenCounter)
+    }
+
+    /// Counts the tokens of the message content, tool calls, reasoning and
+    /// text tool outputs with `counter`
+    pub fn token_count_with(&self, counter: &dyn TokenCounter) -> usize {
+        let mut text = String::new();
+        match self {
             ContextMessage::Text(text_message) => {
-                text_message.content.chars().count()
-                    + tool_call_content_char_count(text_message)
-                    + reasoning_content_char_count(text_message)
+                text.push_str(&text_message.content);
+                for call in text_message.tool_calls.iter().flatten() {
+                    text.push_str(call.name.as_str());
+                    text.push_str(&call.arguments.to_owned().into_string());
+                }
+                for details in text_message.reasoning_details.iter().flatten() {
+                    text.push_str(details.text.as_deref().unwrap_or_default());
+                }
             }
-            ContextMessage::Tool(tool_result) => tool_result
-                .output
-                .values
-                .iter()
-                .map(|result| match result {
-                    ToolValue::Text(text) => text.chars().count(),
-                    _ => 0,
-                })
-                .sum(),
-            _ => 0,
-        };
+            ContextMessage::Tool(tool_result) => {
+                for value in &tool_result.output.values {
+                    if let ToolValue::Text(output) = value {
+                        text.push_str(output);
+                    }
+                }
+            }
+            ContextMessage::Image(_) => {}
+        }
 
-        char_count.div_ceil(4)
+        counter.count(&text)
     }
 
     pub fn to_text(&self) -> String {
@@ -264,34 +275,6 @@ impl ContextMessage {
     }
 }
 
-fn tool_call_content_char_count(text_message: &TextMessage) -> usize {
-    text_message
-        .tool_calls
-        .as_ref()
-        .map(|tool_calls| {
-            tool_calls
-                .iter()
-                .map(|tc| {
-                    tc.arguments.to_owned().into_string().chars().count()
-                        + tc.name.as_str().chars().count()
-                })
-                .sum()
-        })
-        .unwrap_or(0)
-}
-
-fn reasoning_content_char_count(text_message: &TextMessage) -> usize {
-    text_message
-        .reasoning_details
-        .as_ref()
-        .map_or(0, |details| {
-            details
-                .iter()
-                .map(|rd| rd.text.as_ref().map_or(0, |text| text.chars().count()))
-                .sum::<usize>()
-        })
-}
-
 //TODO: Rename to TextMessage
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Setters)]
 #[setters(strip_option, into)]
@@ -421,7 +404,16 @@ pub struct Context {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub top_k: Option<TopK>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<crate::ReasoningConfig>,
+    /// Detail of the model's answers, for providers that support it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<crate::Verbosity>,
     /// Controls whether responses should be streamed. When `true`, responses
     /// are delivered incrementally as they're generated. When `false`, the
     /// complete response is returned at once. Defaults to `true` if not
@@ -616,6 +608,19 @@ impl Context {
 
     /// Returns the token count for context
     pub fn token_count(&self) -> TokenCount {
+        self.token_count_with(&ApproxTokenCounter)
+    }
+
+    pub fn token_count_approx(&self) -> usize {
+        self.messages
+            .iter()
+            .map(|m| m.token_count_approx())
+            .sum::<usize>()
+    }
+
+    /// Returns the token count for context, counting the messages with
+    /// `counter` when no usage has been reported yet
+    pub fn token_count_with(&self, counter: &dyn TokenCounter) -> TokenCount {
         let actual = self
             .messages
             .last()
@@ -626,17 +631,15 @@ impl Context {
 
         match actual {
             TokenCount::Actual(actual) if actual > 0 => TokenCount::Actual(actual),
-            _ => TokenCount::Approx(self.token_count_approx()),
+            _ => TokenCount::Approx(
+                self.messages
+                    .iter()
+                    .map(|m| m.token_count_with(counter))
+                    .sum(),
+            ),
         }
     }
 
-    pub fn token_count_approx(&self) -> usize {
-        self.messages
-            .iter()
-            .map(|m| m.token_count_approx())
-            .sum::<usize>()
-    }
-
     /// Checks if reasoning is enabled by user or not.
     pub fn is_reasoning_supported(&self) -> bool {
         self.reasoning.as_ref().is_some_and(|reasoning| {
@@ -1075,6 +1078,29 @@ mod tests {
         assert_eq!(fixture.token_count(), TokenCount::Approx(18));
     }
 
+    #[test]
+    fn test_context_token_count_with_counter() {
+        struct WordCounter;
+        impl TokenCounter for WordCounter {
+            fn count(&self, text: &str) -> usize {
+                text.split_whitespace().count()
+            }
+        }
+
+        let fixture = Context::default()
+            .add_message(ContextMessage::user("Hello there", None))
+            .add_message(ContextMessage::assistant(
+                "How can I help?",
+                None,
+                None,
+                None,
+            ));
+
+        let actual = fixture.token_count_with(&WordCounter);
+        let expected = TokenCount::Approx(6);
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_context_token_count_uses_last_message_usage() {
         // Setup: Create multiple messages with different usage values
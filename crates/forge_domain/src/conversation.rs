@@ -6,7 +6,7 @@ use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{Context, Error, Metrics, Result, TokenCount};
+use crate::{Context, Error, Metrics, Result, TokenCount, TokenCounter};
 
 #[derive(Debug, Default, Display, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(transparent)]
@@ -53,11 +53,18 @@ pub struct Conversation {
 pub struct MetaData {
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// Pinned conversations are exempt from retention pruning
+    #[serde(default)]
+    pub pinned: bool,
+    /// Name of the server user who created the conversation; conversations
+    /// created locally have no owner
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 impl MetaData {
     pub fn new(created_at: DateTime<Utc>) -> Self {
-        Self { created_at, updated_at: None }
+        Self { created_at, updated_at: None, pinned: false, owner: None }
     }
 }
 
@@ -104,6 +111,14 @@ impl Conversation {
         crate::conversation_html::render_conversation_html_with_related(self, related)
     }
 
+    /// Returns a copy with API keys, tokens and passwords masked so the
+    /// conversation can be shared with others
+    pub fn redacted(&self) -> anyhow::Result<Self> {
+        let mut value = serde_json::to_value(self)?;
+        crate::redact_json(&mut value);
+        Ok(serde_json::from_value(value)?)
+    }
+
     /// Returns a vector of user messages, selecting the first message from
     /// each consecutive sequence of user messages.
     pub fn first_user_messages(&self) -> Vec<&crate::ContextMessage> {
@@ -133,6 +148,14 @@ impl Conversation {
         self.context.as_ref().map(|ctx| ctx.token_count())
     }
 
+    /// Returns the token count of the context, counting messages with
+    /// `counter` when no usage has been reported yet
+    pub fn token_count_with(&self, counter: &dyn TokenCounter) -> Option<TokenCount> {
+        self.context
+            .as_ref()
+            .map(|ctx| ctx.token_count_with(counter))
+    }
+
     pub fn accumulated_cost(&self) -> Option<f64> {
         self.accumulated_usage().and_then(|usage| usage.cost)
     }
@@ -281,6 +304,24 @@ mod tests {
         assert_eq!(actual, vec![agent_conv_id]);
     }
 
+    #[test]
+    fn test_redacted_masks_secrets_in_messages() {
+        let context = Context::default().add_message(ContextMessage::user(
+            "Deploy with api_key=hunter2 and Bearer abc.def",
+            None,
+        ));
+        let fixture = Conversation::generate().context(context);
+
+        let redacted = fixture.redacted().unwrap();
+        let actual = redacted.context.as_ref().unwrap().messages[0]
+            .content()
+            .map(str::to_string);
+
+        let expected = Some("Deploy with api_key=[REDACTED] and Bearer [REDACTED]".to_string());
+        assert_eq!(actual, expected);
+        assert_eq!(redacted.id, fixture.id);
+    }
+
     #[test]
     fn test_total_cost() {
         use crate::{MessageEntry, Usage};
@@ -0,0 +1,43 @@
+use std::fmt;
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The Forge process holding the lock of a conversation. Either field may be
+/// missing when the lock file couldn't be read, e.g. while it is being
+/// written.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationLockHolder {
+    pub pid: Option<u32>,
+    pub acquired_at: Option<DateTime<Utc>>,
+}
+
+impl fmt::Display for ConversationLockHolder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pid {
+            Some(pid) => write!(f, "process {pid}")?,
+            None => write!(f, "another process")?,
+        }
+        if let Some(acquired_at) = self.acquired_at {
+            let acquired_at = acquired_at.with_timezone(&Local);
+            write!(f, " since {}", acquired_at.format("%Y-%m-%d %H:%M"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_display_unknown_holder() {
+        let fixture = ConversationLockHolder::default();
+
+        let actual = fixture.to_string();
+        let expected = "another process";
+        assert_eq!(actual, expected);
+    }
+}
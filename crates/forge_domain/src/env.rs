@@ -4,8 +4,9 @@ use std::path::PathBuf;
 use derive_more::Display;
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display as StrumDisplay, EnumString};
 
-use crate::{Effort, ModelConfig};
+use crate::{Effort, ModelConfig, Schedule};
 
 /// All discrete mutations that can be applied to the application configuration.
 ///
@@ -29,6 +30,44 @@ pub enum ConfigOperation {
     SetSuggestConfig(ModelConfig),
     /// Set the reasoning effort level for all agents.
     SetReasoningEffort(Effort),
+    /// Set the reasoning effort of every agent for the rest of the session,
+    /// taking precedence over the effort agents set themselves.
+    ///
+    /// Unlike the other operations this one is not persisted. `None` clears
+    /// it so agents and the configuration decide again.
+    SetSessionReasoningEffort(Option<Effort>),
+    /// Set where conversations and snapshots are stored.
+    SetStorageScope(StorageScope),
+    /// Set the active profile.
+    ///
+    /// `None` deactivates profiles so only the rest of the configuration
+    /// applies.
+    SetProfile(Option<String>),
+    /// Add or replace the named schedule.
+    ///
+    /// `None` removes the schedule.
+    SetSchedule(String, Option<Schedule>),
+}
+
+/// Location where conversations and snapshots are persisted.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, StrumDisplay, EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum StorageScope {
+    /// Stored in the global data directory shared by every workspace.
+    #[default]
+    Global,
+    /// Stored inside the workspace's `.forge/` directory.
+    Workspace,
+}
+
+/// Number of items moved by a storage migration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StorageMigration {
+    pub conversations: usize,
+    pub snapshots: usize,
 }
 
 const VERSION: &str = match option_env!("APP_VERSION") {
@@ -64,6 +103,32 @@ impl Environment {
         self.base_path.join("logs")
     }
 
+    /// Returns the file local-only telemetry events are appended to
+    /// (base_path/telemetry.jsonl)
+    pub fn telemetry_path(&self) -> PathBuf {
+        self.base_path.join("telemetry.jsonl")
+    }
+
+    /// Returns the file submitted prompts are logged to along with their
+    /// workspace, backing `/history` (base_path/prompt_history.jsonl)
+    pub fn prompt_history_path(&self) -> PathBuf {
+        self.base_path.join("prompt_history.jsonl")
+    }
+
+    /// Returns the file the unsent input of this workspace is autosaved to
+    /// (base_path/drafts/<workspace hash>.md)
+    pub fn draft_path(&self) -> PathBuf {
+        self.base_path
+            .join("drafts")
+            .join(format!("{}.md", self.workspace_hash()))
+    }
+
+    /// Returns the directory holding the lock files of the conversations open
+    /// in a Forge process (base_path/locks)
+    pub fn conversation_locks_path(&self) -> PathBuf {
+        self.base_path.join("locks")
+    }
+
     /// Returns the history file path.
     ///
     /// # Arguments
@@ -75,8 +140,18 @@ impl Environment {
             .cloned()
             .unwrap_or(self.base_path.join(".forge_history"))
     }
-    pub fn snapshot_path(&self) -> PathBuf {
-        self.base_path.join("snapshots")
+
+    /// Returns the root directory for data stored in the given scope, either
+    /// base_path or the workspace's `.forge/` directory
+    pub fn storage_path(&self, scope: StorageScope) -> PathBuf {
+        match scope {
+            StorageScope::Global => self.base_path.clone(),
+            StorageScope::Workspace => self.cwd.join(".forge"),
+        }
+    }
+
+    pub fn snapshot_path(&self, scope: StorageScope) -> PathBuf {
+        self.storage_path(scope).join("snapshots")
     }
     pub fn mcp_user_config(&self) -> PathBuf {
         self.base_path.join(".mcp.json")
@@ -89,6 +164,12 @@ impl Environment {
         self.cwd.join(".forge/agents")
     }
 
+    /// Returns the directory where revisions of custom agent definitions are
+    /// recorded (base_path/agent_versions)
+    pub fn agent_versions_path(&self) -> PathBuf {
+        self.base_path.join("agent_versions")
+    }
+
     pub fn permissions_path(&self) -> PathBuf {
         self.base_path.join("permissions.yaml")
     }
@@ -105,8 +186,8 @@ impl Environment {
         self.base_path.join(".config.json")
     }
 
-    pub fn database_path(&self) -> PathBuf {
-        self.base_path.join(".forge.db")
+    pub fn database_path(&self, scope: StorageScope) -> PathBuf {
+        self.storage_path(scope).join(".forge.db")
     }
 
     /// Returns the path to the cache directory
@@ -114,6 +195,12 @@ impl Environment {
         self.base_path.join("cache")
     }
 
+    /// Returns the path to the key-value cache shared by subsystems, each
+    /// under its own namespace
+    pub fn kv_cache_dir(&self) -> PathBuf {
+        self.cache_dir().join("kv")
+    }
+
     /// Returns the global skills directory path (~/forge/skills)
     pub fn global_skills_path(&self) -> PathBuf {
         self.base_path.join("skills")
@@ -126,6 +213,12 @@ impl Environment {
         self.home.as_ref().map(|home| home.join(".agents/skills"))
     }
 
+    /// Returns the directory where packs installed from git are stored
+    /// (base_path/packs)
+    pub fn packs_path(&self) -> PathBuf {
+        self.base_path.join("packs")
+    }
+
     /// Returns the project-local skills directory path (.forge/skills)
     pub fn local_skills_path(&self) -> PathBuf {
         self.cwd.join(".forge/skills")
@@ -175,6 +268,16 @@ impl Environment {
 
         WorkspaceHash(hasher.finish())
     }
+
+    /// Returns the id conversations of this workspace are stored under in the
+    /// given scope. Workspace storage only ever holds a single workspace, so it
+    /// uses a fixed id that survives the project directory being moved.
+    pub fn storage_workspace_hash(&self, scope: StorageScope) -> WorkspaceHash {
+        match scope {
+            StorageScope::Global => self.workspace_hash(),
+            StorageScope::Workspace => WorkspaceHash::new(0),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Display)]
@@ -240,6 +343,38 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_storage_paths_follow_scope() {
+        let fixture: Environment = Faker.fake();
+        let fixture = fixture
+            .cwd(PathBuf::from("/projects/app"))
+            .base_path(PathBuf::from("/home/user/.forge"));
+
+        let actual = (
+            fixture.database_path(StorageScope::Global),
+            fixture.database_path(StorageScope::Workspace),
+            fixture.snapshot_path(StorageScope::Workspace),
+        );
+        let expected = (
+            PathBuf::from("/home/user/.forge/.forge.db"),
+            PathBuf::from("/projects/app/.forge/.forge.db"),
+            PathBuf::from("/projects/app/.forge/snapshots"),
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_packs_path() {
+        let fixture: Environment = Faker.fake();
+        let fixture = fixture.base_path(PathBuf::from("/home/user/.forge"));
+
+        let actual = fixture.packs_path();
+        let expected = PathBuf::from("/home/user/.forge/packs");
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_agents_skills_path_with_home() {
         let fixture: Environment = Faker.fake();
@@ -74,9 +74,19 @@ pub enum Error {
     #[error("Empty completion received - no content, tool calls, or valid finish reason")]
     EmptyCompletion,
 
+    /// The provider sent nothing for longer than the stall timeout, in seconds
+    #[error("Provider stalled, no data received for {0}s")]
+    #[from(skip)]
+    ProviderStalled(u64),
+
     #[error(transparent)]
     Retryable(anyhow::Error),
 
+    /// The request did not fit in the model's context window
+    #[error(transparent)]
+    #[from(skip)]
+    ContextOverflow(anyhow::Error),
+
     #[error("Environment variable {env_var} not found for provider {provider}")]
     EnvironmentVariableNotFound {
         provider: ProviderId,
@@ -128,6 +138,15 @@ impl std::fmt::Display for ToolCallArgumentError {
 }
 
 impl Error {
+    /// Whether `error` reports a provider response that stalled
+    pub fn is_stall(error: &anyhow::Error) -> bool {
+        match error.downcast_ref::<Error>() {
+            Some(Error::ProviderStalled(_)) => true,
+            Some(Error::Retryable(error)) => Self::is_stall(error),
+            _ => false,
+        }
+    }
+
     pub fn into_retryable(self) -> Self {
         use anyhow::anyhow;
         Self::Retryable(anyhow!(self))
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use derive_more::{Deref, From};
 use derive_setters::Setters;
@@ -11,11 +11,16 @@ use crate::{Attachment, NamedTool, Template, ToolName};
 ///
 /// This is an intermediate structure for parsing event JSON from the CLI
 /// before converting it to a full Event type.
-#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq, Setters)]
+#[setters(into)]
 pub struct UserCommand {
     pub name: String,
     pub template: Template<Value>,
     pub parameters: Vec<String>,
+    /// Values bound to the arguments declared by the command, rendered into
+    /// the template as variables of the same name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub arguments: BTreeMap<String, Value>,
 }
 
 impl UserCommand {
@@ -24,7 +29,12 @@ impl UserCommand {
         value: V,
         parameters: Vec<String>,
     ) -> Self {
-        Self { name: name.to_string(), template: value.into(), parameters }
+        Self {
+            name: name.to_string(),
+            template: value.into(),
+            parameters,
+            arguments: BTreeMap::new(),
+        }
     }
 }
 
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+/// Git state of the workspace, available to system prompts and custom
+/// commands as the `git` template variable, e.g. `{{git.branch}}`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitState {
+    /// Branch checked out, absent on a detached HEAD
+    pub branch: Option<String>,
+    /// Paths with uncommitted changes, untracked files included
+    pub dirty_files: Vec<String>,
+    /// Message of the last commit, absent before the first one
+    pub last_commit_message: Option<String>,
+    /// Commits of the branch its upstream doesn't have
+    pub ahead: usize,
+    /// Commits of the upstream the branch doesn't have
+    pub behind: usize,
+}
+
+impl GitState {
+    /// Parses the output of `git status --porcelain=v2 --branch`. The last
+    /// commit message isn't part of it and is left out.
+    pub fn from_status(status: &str) -> Self {
+        let mut state = Self::default();
+        for line in status.lines() {
+            if let Some(head) = line.strip_prefix("# branch.head ") {
+                state.branch = Some(head.to_string()).filter(|head| head != "(detached)");
+            } else if let Some(counts) = line.strip_prefix("# branch.ab ") {
+                for count in counts.split_whitespace() {
+                    if let Some(ahead) = count.strip_prefix('+') {
+                        state.ahead = ahead.parse().unwrap_or_default();
+                    } else if let Some(behind) = count.strip_prefix('-') {
+                        state.behind = behind.parse().unwrap_or_default();
+                    }
+                }
+            } else if let Some(path) = changed_path(line) {
+                state.dirty_files.push(path.to_string());
+            }
+        }
+        state
+    }
+}
+
+/// Path of a changed entry of `git status --porcelain=v2`; for a rename it's
+/// the new path
+fn changed_path(line: &str) -> Option<&str> {
+    // Number of space separated fields before the path of each kind of entry
+    let fields = match line.split_once(' ')?.0 {
+        "1" => 8,
+        "2" => 9,
+        "u" => 10,
+        "?" => 1,
+        _ => return None,
+    };
+    let path = line.splitn(fields + 1, ' ').nth(fields)?;
+    path.split('\t').next()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_from_status() {
+        let fixture = "# branch.oid 4f1c2d9\n\
+                       # branch.head main\n\
+                       # branch.upstream origin/main\n\
+                       # branch.ab +2 -1\n\
+                       1 .M N... 100644 100644 100644 3b18e51 3b18e51 src/lib.rs\n\
+                       2 R. N... 100644 100644 100644 9c1f0a2 9c1f0a2 R100 src/new name.rs\tsrc/old.rs\n\
+                       u UU N... 100644 100644 100644 100644 1a2b3c4 5d6e7f8 9a0b1c2 Cargo.toml\n\
+                       ? notes.md\n";
+
+        let actual = GitState::from_status(fixture);
+
+        let expected = GitState {
+            branch: Some("main".to_string()),
+            dirty_files: vec![
+                "src/lib.rs".to_string(),
+                "src/new name.rs".to_string(),
+                "Cargo.toml".to_string(),
+                "notes.md".to_string(),
+            ],
+            last_commit_message: None,
+            ahead: 2,
+            behind: 1,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_from_status_detached_without_upstream() {
+        let fixture = "# branch.oid 4f1c2d9\n# branch.head (detached)\n";
+
+        let actual = GitState::from_status(fixture);
+
+        let expected = GitState::default();
+        assert_eq!(actual, expected);
+    }
+}
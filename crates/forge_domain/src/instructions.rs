@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+/// A project instruction file, such as an `AGENTS.md`, loaded into the system
+/// prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+impl InstructionFile {
+    /// The file's content labelled with where it comes from, so instructions
+    /// scoped to a subdirectory can be told apart from project-wide ones
+    pub fn to_rule(&self) -> String {
+        format!(
+            "Instructions from {}:\n{}",
+            self.path.display(),
+            self.content.trim()
+        )
+    }
+}
@@ -1,4 +1,5 @@
 mod agent;
+mod agent_version;
 mod attachment;
 mod auth;
 mod chat_request;
@@ -9,16 +10,19 @@ mod console;
 mod context;
 mod conversation;
 mod conversation_html;
+mod conversation_lock;
 mod data_gen;
 mod env;
 mod error;
 mod event;
 mod file;
 mod file_operation;
+mod git_state;
 mod group_by_key;
 mod hook;
 mod http_config;
 mod image;
+mod instructions;
 mod max_tokens;
 mod mcp;
 mod mcp_servers;
@@ -29,12 +33,21 @@ mod migration;
 mod model;
 mod model_config;
 mod node;
+mod pack;
 mod point;
 mod policies;
+mod project;
 mod provider;
+mod pull_request;
 mod reasoning;
+mod redact;
 mod repo;
+mod repo_map;
+mod response_cache;
 mod result_stream_ext;
+mod retention;
+mod schedule;
+mod secret_file;
 mod session_metrics;
 mod shell;
 mod skill;
@@ -43,6 +56,7 @@ mod suggestion;
 mod system_context;
 mod temperature;
 mod template;
+mod token_counter;
 mod tools;
 
 mod tool_order;
@@ -55,6 +69,7 @@ mod workspace;
 mod xml;
 
 pub use agent::*;
+pub use agent_version::*;
 pub use attachment::*;
 pub use chat_request::*;
 pub use chat_response::*;
@@ -64,6 +79,7 @@ pub use console::*;
 pub use context::*;
 pub use conversation::*;
 pub use conversation_html::*;
+pub use conversation_lock::*;
 pub use data_gen::*;
 pub use env::*;
 pub use error::*;
@@ -71,10 +87,12 @@ pub use event::*;
 pub use file::*;
 pub use file_operation::*;
 pub use fuzzy_search::*;
+pub use git_state::*;
 pub use group_by_key::*;
 pub use hook::*;
 pub use http_config::*;
 pub use image::*;
+pub use instructions::*;
 pub use max_tokens::*;
 pub use mcp::*;
 pub use mcp_servers::*;
@@ -84,12 +102,21 @@ pub use migration::*;
 pub use model::*;
 pub use model_config::*;
 pub use node::*;
+pub use pack::*;
 pub use point::*;
 pub use policies::*;
+pub use project::*;
 pub use provider::*;
+pub use pull_request::*;
 pub use reasoning::*;
+pub use redact::*;
 pub use repo::*;
+pub use repo_map::*;
+pub use response_cache::*;
 pub use result_stream_ext::*;
+pub use retention::*;
+pub use schedule::*;
+pub use secret_file::*;
 pub use session_metrics::*;
 pub use shell::*;
 pub use skill::*;
@@ -98,6 +125,7 @@ pub use suggestion::*;
 pub use system_context::*;
 pub use temperature::*;
 pub use template::*;
+pub use token_counter::*;
 pub use tool_order::*;
 pub use tools::*;
 pub use top_k::*;
@@ -420,6 +420,18 @@ pub enum NodeData {
     Task(Task),
 }
 
+impl NodeData {
+    /// Path of the file the node was taken from, if any
+    pub fn file_path(&self) -> Option<&str> {
+        match self {
+            NodeData::FileChunk(chunk) => Some(&chunk.file_path),
+            NodeData::File(file) => Some(&file.file_path),
+            NodeData::FileRef(file) => Some(&file.file_path),
+            NodeData::Note(_) | NodeData::Task(_) => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -0,0 +1,193 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum_macros::Display as StrumDisplay;
+
+/// The kind of content a pack provides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, StrumDisplay)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PackKind {
+    /// A pack containing one or more skills (directories with a SKILL.md)
+    Skill,
+    /// A pack containing one or more agent definitions (markdown files)
+    Agent,
+}
+
+/// Location of a pack along with an optional version pin.
+///
+/// Accepted forms:
+/// - Full git URLs: `https://github.com/org/skills-rust.git`,
+///   `git@github.com:org/skills-rust.git`
+/// - Host-qualified paths: `github.com/org/skills-rust`
+/// - Registry names resolved against GitHub: `org/skills-rust`
+///
+/// A branch, tag or commit can be pinned by appending `@<ref>`, e.g.
+/// `github.com/org/skills-rust@v1.2.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackSource {
+    /// Git URL the pack is cloned from
+    pub url: String,
+    /// Branch, tag or commit the pack is pinned to
+    pub reference: Option<String>,
+}
+
+impl PackSource {
+    /// Name under which the pack is installed, derived from the last segment
+    /// of the URL
+    pub fn name(&self) -> String {
+        let url = self.url.trim_end_matches('/');
+        let segment = url.rsplit(['/', ':']).next().unwrap_or(url);
+        segment.trim_end_matches(".git").to_string()
+    }
+}
+
+impl FromStr for PackSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+
+        // Only treat `@` as a version separator when it follows the repository
+        // path, so the user part of `git@host:org/repo` is left untouched
+        let (location, reference) = match (s.rfind('@'), s.rfind('/')) {
+            (Some(at), Some(slash)) if at > slash => (&s[..at], Some(&s[at + 1..])),
+            _ => (s, None),
+        };
+
+        if reference.is_some_and(str::is_empty) {
+            anyhow::bail!("Missing version after '@' in pack source: {s}");
+        }
+
+        let url = if ["https://", "http://", "ssh://", "file://", "git@"]
+            .iter()
+            .any(|prefix| location.starts_with(prefix))
+        {
+            location.to_string()
+        } else {
+            let segments: Vec<_> = location.split('/').collect();
+            if segments.iter().any(|segment| segment.is_empty()) {
+                anyhow::bail!("Invalid pack source: {s}");
+            }
+            match segments.as_slice() {
+                [host, _, ..] if host.contains('.') => format!("https://{location}"),
+                [_, _] => format!("https://github.com/{location}"),
+                _ => anyhow::bail!(
+                    "Invalid pack source: {s}. Expected a git URL or a registry name like 'org/repo'"
+                ),
+            }
+        };
+
+        Ok(Self { url, reference: reference.map(str::to_string) })
+    }
+}
+
+impl fmt::Display for PackSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reference {
+            Some(reference) => write!(f, "{}@{reference}", self.url),
+            None => write!(f, "{}", self.url),
+        }
+    }
+}
+
+/// A pack installed from a git repository
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledPack {
+    pub name: String,
+    pub kind: PackKind,
+    pub url: String,
+    /// Branch, tag or commit the pack was pinned to when installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Commit that was checked out at install time
+    pub commit: String,
+    /// Git tree hash of the installed content, used to verify integrity
+    pub tree: String,
+    pub installed_at: DateTime<Utc>,
+}
+
+impl InstalledPack {
+    /// Source the pack was installed from
+    pub fn source(&self) -> PackSource {
+        PackSource { url: self.url.clone(), reference: self.reference.clone() }
+    }
+}
+
+/// Result of checking an installed pack against its source
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackStatus {
+    pub pack: InstalledPack,
+    /// Latest commit available upstream for the pinned reference, if it could
+    /// be determined
+    pub latest_commit: Option<String>,
+    /// Whether the installed content still matches the recorded tree hash
+    pub intact: bool,
+}
+
+impl PackStatus {
+    /// Returns true when upstream has a different commit than the one
+    /// installed
+    pub fn has_update(&self) -> bool {
+        self.latest_commit
+            .as_ref()
+            .is_some_and(|latest| latest != &self.pack.commit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn source(url: &str, reference: Option<&str>) -> PackSource {
+        PackSource {
+            url: url.to_string(),
+            reference: reference.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_host_qualified_path() {
+        let actual = PackSource::from_str("github.com/org/skills-rust").unwrap();
+        let expected = source("https://github.com/org/skills-rust", None);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_registry_name_with_version() {
+        let actual = PackSource::from_str("org/skills-rust@v1.2.0").unwrap();
+        let expected = source("https://github.com/org/skills-rust", Some("v1.2.0"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_ssh_url_keeps_user() {
+        let actual = PackSource::from_str("git@github.com:org/skills-rust.git").unwrap();
+        let expected = source("git@github.com:org/skills-rust.git", None);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_rejects_bare_name() {
+        let actual = PackSource::from_str("skills-rust");
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_version() {
+        let actual = PackSource::from_str("org/skills-rust@");
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_name_strips_git_suffix() {
+        let fixture = source("git@github.com:org/skills-rust.git", None);
+        let actual = fixture.name();
+        let expected = "skills-rust";
+        assert_eq!(actual, expected);
+    }
+}
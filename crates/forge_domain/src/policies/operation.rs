@@ -24,3 +24,16 @@ pub enum PermissionOperation {
         message: String,
     },
 }
+
+impl PermissionOperation {
+    /// Human readable description of the operation shown when asking for
+    /// permission
+    pub fn message(&self) -> String {
+        match self {
+            PermissionOperation::Write { message, .. }
+            | PermissionOperation::Read { message, .. }
+            | PermissionOperation::Fetch { message, .. } => message.clone(),
+            PermissionOperation::Execute { command, .. } => format!("Execute command: {command}"),
+        }
+    }
+}
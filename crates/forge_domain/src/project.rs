@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Build system declaring the subprojects of a workspace
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProjectKind {
+    Cargo,
+    Npm,
+    Pnpm,
+    Bazel,
+}
+
+/// A subproject of a monorepo, such as a cargo workspace member or an npm
+/// workspace package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Project {
+    /// Package name, falling back to the directory name
+    pub name: String,
+    /// Directory of the project, relative to the workspace root
+    pub path: PathBuf,
+    pub kind: ProjectKind,
+}
+
+impl Project {
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>, kind: ProjectKind) -> Self {
+        Self { name: name.into(), path: path.into(), kind }
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+
+/// Code hosting service serving a git remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+pub enum CodeHost {
+    #[strum(serialize = "GitHub")]
+    GitHub,
+    #[strum(serialize = "GitLab")]
+    GitLab,
+}
+
+/// Repository on a code host, parsed from the URL of a git remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepository {
+    pub host: CodeHost,
+    /// Domain of the code host, e.g. `github.com`
+    pub domain: String,
+    /// Path of the repository without the `.git` suffix, e.g. `owner/name`.
+    /// GitLab paths may hold nested groups.
+    pub path: String,
+}
+
+impl RemoteRepository {
+    /// Parses `https://`, `ssh://` and scp-like `git@host:path` remote URLs.
+    /// The code host is told from the domain, so self-hosted instances need
+    /// `github` or `gitlab` in their domain name.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (authority, path) = match url.split_once("://") {
+            Some((_, rest)) => rest.split_once('/')?,
+            None => url.split_once(':')?,
+        };
+        // Drop the user and the port
+        let domain = authority.rsplit('@').next()?.split(':').next()?;
+        let path = path.trim_matches('/').trim_end_matches(".git");
+        if !path.contains('/') {
+            return None;
+        }
+
+        let domain = domain.to_lowercase();
+        let host = if domain.contains("github") {
+            CodeHost::GitHub
+        } else if domain.contains("gitlab") {
+            CodeHost::GitLab
+        } else {
+            return None;
+        };
+        Some(Self { host, domain, path: path.to_string() })
+    }
+}
+
+/// Title and description of a pull request. Users edit it as text whose first
+/// line is the title and the rest the description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestDraft {
+    pub title: String,
+    pub description: String,
+}
+
+impl PullRequestDraft {
+    pub fn new(title: impl Into<String>, description: impl Into<String>) -> Self {
+        Self { title: title.into(), description: description.into() }
+    }
+
+    /// Parses an edited draft, `None` when the title was removed
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim_start();
+        let (title, description) = text.split_once('\n').unwrap_or((text, ""));
+        let title = title.trim();
+        (!title.is_empty()).then(|| Self::new(title, description.trim()))
+    }
+}
+
+impl fmt::Display for PullRequestDraft {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}\n\n{}", self.title, self.description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_repository() {
+        let actual = [
+            "git@github.com:antinomyhq/forge.git",
+            "https://github.com/antinomyhq/forge",
+            "ssh://git@gitlab.example.com:2222/group/sub/project.git",
+            "https://token@GitLab.com/group/project.git/",
+            "https://bitbucket.org/team/project.git",
+            "git@github.com:forge.git",
+        ]
+        .map(RemoteRepository::parse);
+
+        let repository = |host, domain: &str, path: &str| {
+            Some(RemoteRepository { host, domain: domain.to_string(), path: path.to_string() })
+        };
+        let expected = [
+            repository(CodeHost::GitHub, "github.com", "antinomyhq/forge"),
+            repository(CodeHost::GitHub, "github.com", "antinomyhq/forge"),
+            repository(CodeHost::GitLab, "gitlab.example.com", "group/sub/project"),
+            repository(CodeHost::GitLab, "gitlab.com", "group/project"),
+            None,
+            None,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_edited_draft() {
+        let fixture = PullRequestDraft::new("Add pull requests", "Opens a pull request.\n\n- Push");
+
+        let actual = [
+            PullRequestDraft::parse(&fixture.to_string()),
+            PullRequestDraft::parse("\n  Only a title  \n"),
+            PullRequestDraft::parse("\n\n"),
+        ];
+
+        let expected = [
+            Some(fixture),
+            Some(PullRequestDraft::new("Only a title", "")),
+            None,
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_json::Value;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Object keys whose string values are always redacted, also when they end a
+/// longer key such as `auth_token`
+const SENSITIVE_KEYS: [&str; 6] = [
+    "api_key",
+    "authorization",
+    "password",
+    "secret",
+    "token",
+    "headers",
+];
+
+static SECRET_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        // Assignments such as `api_key=...` or `"token": "..."`
+        (
+            r#"(?i)((?:api[_-]?key|access[_-]?token|auth[_-]?token|secret|password)["']?\s*[:=]\s*["']?)[^\s"',]+"#,
+            "${1}[REDACTED]",
+        ),
+        (r"(?i)(bearer\s+)[A-Za-z0-9._~+/=-]+", "${1}[REDACTED]"),
+        // Provider API keys (OpenAI, Anthropic, OpenRouter, ...)
+        (r"\b(?:sk|pk|rk)-[A-Za-z0-9_-]{16,}", REDACTED),
+        // GitHub tokens
+        (r"\bgh[pousr]_[A-Za-z0-9]{20,}", REDACTED),
+        // AWS access key ids
+        (r"\bAKIA[0-9A-Z]{16}\b", REDACTED),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+    .collect()
+});
+
+/// Masks API keys, tokens, and passwords in free-form text
+pub fn redact(text: &str) -> String {
+    SECRET_PATTERNS
+        .iter()
+        .fold(text.to_string(), |text, (pattern, replacement)| {
+            pattern.replace_all(&text, *replacement).into_owned()
+        })
+}
+
+/// Masks secrets in a JSON value. String values of sensitive keys are replaced
+/// entirely, every other string is redacted like free-form text.
+pub fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive_key(key) {
+                    mask_strings(value);
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_json),
+        Value::String(text) => *text = redact(text),
+        _ => {}
+    }
+}
+
+/// Whether `key` names a secret, i.e. is a sensitive key or ends with one
+/// after a `_` or `-`, so `access_token` matches but `max_tokens` does not
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_lowercase().replace('-', "_");
+    SENSITIVE_KEYS.iter().any(|sensitive| {
+        key == *sensitive
+            || key
+                .strip_suffix(sensitive)
+                .is_some_and(|prefix| prefix.ends_with('_'))
+    })
+}
+
+fn mask_strings(value: &mut Value) {
+    match value {
+        Value::Object(map) => map.values_mut().for_each(mask_strings),
+        Value::Array(values) => values.iter_mut().for_each(mask_strings),
+        Value::String(text) => *text = REDACTED.to_string(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_redact_masks_known_secret_formats() {
+        let fixture = "key sk-ant-REDACTED and Authorization: Bearer abc.def \
+                       with api_key=hunter2 from ghp_abcdefghijklmnopqrstuvwxyz";
+        let actual = redact(fixture);
+        let expected = "key [REDACTED] and Authorization: Bearer [REDACTED] \
+                        with api_key=[REDACTED] from [REDACTED]";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_redact_json_masks_sensitive_keys() {
+        let mut actual = json!({
+            "max_tokens": 20480,
+            "otlp": { "endpoint": "http://localhost:4318", "headers": { "x-api": "abc" } },
+            "auth_token": "plain",
+            "x-api-key": "plain",
+            "tokenizer": "cl100k",
+            "token_budget": "low",
+            "note": "uses sk-abcdefghijklmnopqrstuvwx",
+        });
+        redact_json(&mut actual);
+        let expected = json!({
+            "max_tokens": 20480,
+            "otlp": { "endpoint": "http://localhost:4318", "headers": { "x-api": REDACTED } },
+            "auth_token": REDACTED,
+            "x-api-key": REDACTED,
+            "tokenizer": "cl100k",
+            "token_budget": "low",
+            "note": "uses [REDACTED]",
+        });
+        assert_eq!(actual, expected);
+    }
+}
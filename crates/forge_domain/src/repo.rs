@@ -1,12 +1,15 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::Result;
 use url::Url;
 
 use crate::{
-    AnyProvider, AuthCredential, ChatCompletionMessage, Context, Conversation, ConversationId,
-    MigrationResult, Model, ModelId, Provider, ProviderId, ProviderTemplate, ResultStream,
-    SearchMatch, Skill, Snapshot, WorkspaceAuth, WorkspaceId,
+    AgentId, AgentVersion, AnyProvider, AuthCredential, ChatCompletionMessage, Context,
+    Conversation, ConversationId, ConversationLockHolder, ConversationSummary, InstalledPack,
+    MigrationResult, Model, ModelId, PackKind, PackSource, PackStatus, Provider, ProviderId,
+    ProviderTemplate, ResponseCacheInfo, ResultStream, SearchMatch, Skill, Snapshot, SnapshotTag,
+    StorageMigration, StorageScope, WorkspaceAuth, WorkspaceId,
 };
 
 /// Repository for managing file snapshots
@@ -32,6 +35,83 @@ pub trait SnapshotRepository: Send + Sync {
     /// # Errors
     /// Returns an error if no snapshot exists or restoration fails
     async fn undo_snapshot(&self, file_path: &Path) -> Result<()>;
+
+    /// Reads the content of a snapshot of the given file path
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the file the snapshot was taken of
+    /// * `index` - Position of the snapshot, 0 being the oldest
+    ///
+    /// # Errors
+    /// Returns an error if the file has no snapshot at `index`
+    async fn read_snapshot(&self, file_path: &Path, index: usize) -> Result<Vec<u8>>;
+
+    /// Restores every file changed since `timestamp` to its content at that
+    /// time and returns the restored files
+    ///
+    /// # Arguments
+    /// * `timestamp` - Point in time to go back to, as a duration since the
+    ///   Unix epoch
+    ///
+    /// # Errors
+    /// Returns an error if reading a snapshot or writing a file fails
+    async fn restore_all_at(&self, timestamp: Duration) -> Result<Vec<PathBuf>>;
+
+    /// Tags the snapshots of the given file path taken since a point in time
+    /// with the agent turn that triggered them
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the file the snapshots were taken of
+    /// * `since` - Time the agent turn started, as a duration since the Unix
+    ///   epoch
+    /// * `tag` - Conversation and tool call that triggered the snapshots
+    ///
+    /// # Errors
+    /// Returns an error if writing a tag fails
+    async fn tag_snapshots(
+        &self,
+        file_path: &Path,
+        since: Duration,
+        tag: &SnapshotTag,
+    ) -> Result<()>;
+
+    /// Lists the snapshots tagged with a conversation, from the oldest to the
+    /// most recent
+    ///
+    /// # Arguments
+    /// * `conversation_id` - Conversation whose snapshots to list
+    ///
+    /// # Errors
+    /// Returns an error if reading a tag fails
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Vec<Snapshot>>;
+
+    /// Writes a bundle of the snapshots and current content of the given
+    /// files, to be applied on another machine with
+    /// [`SnapshotRepository::import_bundle`], and returns the number of
+    /// bundled snapshots
+    ///
+    /// # Arguments
+    /// * `paths` - Files to bundle
+    /// * `root` - Directory the paths are recorded relative to
+    /// * `output` - Path of the bundle to write
+    ///
+    /// # Errors
+    /// Returns an error if a file is outside `root` or has no snapshots
+    async fn export_bundle(&self, paths: &[PathBuf], root: &Path, output: &Path) -> Result<usize>;
+
+    /// Applies a bundle to the files under a directory: adds its snapshots
+    /// and gives each file its content at export. Returns the changed files.
+    ///
+    /// # Arguments
+    /// * `bundle` - Path of the bundle to apply
+    /// * `root` - Directory the paths of the bundle are relative to
+    ///
+    /// # Errors
+    /// Returns an error if the bundle is invalid or writing a file fails
+    async fn import_bundle(&self, bundle: &Path, root: &Path) -> Result<Vec<PathBuf>>;
 }
 
 /// Repository for managing conversation persistence
@@ -87,6 +167,35 @@ pub trait ConversationRepository: Send + Sync {
     /// # Errors
     /// Returns an error if the operation fails
     async fn delete_conversation(&self, conversation_id: &ConversationId) -> Result<()>;
+
+    /// Retrieves lightweight summaries of every conversation in the workspace,
+    /// including empty ones, for use by retention policies
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails
+    async fn get_conversation_summaries(&self) -> Result<Vec<ConversationSummary>>;
+
+    /// Locks a conversation for this process so that other Forge processes
+    /// can only read it until it is unlocked. Locking a conversation this
+    /// process already holds succeeds and must be matched by another unlock.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails. A conversation locked by
+    /// another process is not an error: its holder is returned instead.
+    async fn lock_conversation(
+        &self,
+        _conversation_id: &ConversationId,
+    ) -> Result<Option<ConversationLockHolder>> {
+        Ok(None)
+    }
+
+    /// Releases a lock taken by [`ConversationRepository::lock_conversation`]
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails
+    async fn unlock_conversation(&self, _conversation_id: &ConversationId) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,6 +207,27 @@ pub trait ChatRepository: Send + Sync {
         provider: Provider<Url>,
     ) -> ResultStream<ChatCompletionMessage, anyhow::Error>;
     async fn models(&self, provider: Provider<Url>) -> anyhow::Result<Vec<Model>>;
+
+    /// Counts the input tokens of `context` with the provider's token counting
+    /// API. Returns `None` when the provider has no such API.
+    async fn count_tokens(
+        &self,
+        _model_id: &ModelId,
+        _context: &Context,
+        _provider: Provider<Url>,
+    ) -> anyhow::Result<Option<u64>> {
+        Ok(None)
+    }
+
+    /// Describes the cache of responses to deterministic requests
+    async fn response_cache_info(&self) -> anyhow::Result<ResponseCacheInfo> {
+        Ok(ResponseCacheInfo::default())
+    }
+
+    /// Removes every cached response and returns how many there were
+    async fn clear_response_cache(&self) -> anyhow::Result<usize> {
+        Ok(0)
+    }
 }
 
 #[async_trait::async_trait]
@@ -185,6 +315,89 @@ pub trait SkillRepository: Send + Sync {
     async fn load_skills(&self) -> Result<Vec<Skill>>;
 }
 
+/// Repository for managing skill and agent packs installed from git
+///
+/// Installed packs are picked up by the skill and agent repositories alongside
+/// the built-in and user-defined definitions.
+#[async_trait::async_trait]
+pub trait PackRepository: Send + Sync {
+    /// Clones the pack at `source` and records it in the pack manifest,
+    /// replacing any existing pack with the same name
+    ///
+    /// # Errors
+    /// Returns an error if the repository cannot be cloned, the pinned
+    /// reference does not exist, or the pack contains no definitions of the
+    /// given kind
+    async fn install_pack(&self, kind: PackKind, source: &PackSource) -> Result<InstalledPack>;
+
+    /// Removes an installed pack and its files
+    ///
+    /// # Errors
+    /// Returns an error if no pack with the given name is installed
+    async fn remove_pack(&self, kind: PackKind, name: &str) -> Result<()>;
+
+    /// Lists the installed packs of the given kind
+    async fn list_packs(&self, kind: PackKind) -> Result<Vec<InstalledPack>>;
+
+    /// Verifies the integrity of each installed pack and checks its source for
+    /// newer commits
+    async fn check_packs(&self, kind: PackKind) -> Result<Vec<PackStatus>>;
+}
+
+/// Repository for the recorded revisions of custom agent definitions
+///
+/// A new version is recorded when an agent file is created, edited or rolled
+/// back through Forge with content that differs from its latest recorded
+/// version.
+#[async_trait::async_trait]
+pub trait AgentVersionRepository: Send + Sync {
+    /// Records the current content of an agent's definition file as a new
+    /// version, returning `None` when it matches the latest recorded version
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or the version cannot be
+    /// written
+    async fn record_agent_version(
+        &self,
+        agent_id: &AgentId,
+        path: &Path,
+    ) -> Result<Option<AgentVersion>>;
+
+    /// Lists the recorded versions of an agent, oldest first
+    async fn list_agent_versions(&self, agent_id: &AgentId) -> Result<Vec<AgentVersion>>;
+
+    /// Reads the content of a recorded version
+    ///
+    /// # Errors
+    /// Returns an error if the version does not exist
+    async fn read_agent_version(&self, agent_id: &AgentId, version: u32) -> Result<String>;
+
+    /// Restores an agent's definition file to the content of a recorded
+    /// version, recording the restored content as a new version
+    ///
+    /// # Errors
+    /// Returns an error if the version does not exist or the file cannot be
+    /// written
+    async fn rollback_agent(&self, agent_id: &AgentId, version: u32) -> Result<AgentVersion>;
+}
+
+/// Repository for moving the current workspace's stored data between storage
+/// scopes
+#[async_trait::async_trait]
+pub trait StorageRepository: Send + Sync {
+    /// Moves the workspace's conversations and snapshots from the `from`
+    /// scope to the `to` scope, removing them from the source
+    ///
+    /// # Errors
+    /// Returns an error if both scopes are the same or the data cannot be
+    /// copied
+    async fn migrate_storage(
+        &self,
+        from: StorageScope,
+        to: StorageScope,
+    ) -> Result<StorageMigration>;
+}
+
 /// Repository for validating file syntax
 ///
 /// This repository provides operations for validating the syntax of source
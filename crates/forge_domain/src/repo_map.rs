@@ -0,0 +1,46 @@
+use std::fmt;
+
+/// A file of a [`RepoMap`] with the symbols it declares
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoMapEntry {
+    /// Path relative to the workspace root
+    pub path: String,
+    /// Declaration lines of the file, such as function and type signatures
+    pub symbols: Vec<String>,
+    /// Paths of the files of the map this file imports
+    pub dependencies: Vec<String>,
+    /// Number of files importing this file
+    pub dependents: usize,
+}
+
+/// Ranked summary of the key files of a repository, their symbols and the
+/// imports between them, giving agents a starting point in unfamiliar code
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoMap {
+    pub entries: Vec<RepoMapEntry>,
+}
+
+impl fmt::Display for RepoMapEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        if self.dependents > 0 {
+            write!(f, " (imported by {})", self.dependents)?;
+        }
+        writeln!(f)?;
+        if !self.dependencies.is_empty() {
+            writeln!(f, "  imports: {}", self.dependencies.join(", "))?;
+        }
+        for symbol in &self.symbols {
+            writeln!(f, "  {symbol}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RepoMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.entries
+            .iter()
+            .try_for_each(|entry| write!(f, "{entry}"))
+    }
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// What the cache of provider responses to deterministic requests holds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ResponseCacheInfo {
+    /// Whether new responses are cached, i.e. the cache is configured
+    pub enabled: bool,
+    pub entries: usize,
+    pub size_bytes: u64,
+}
@@ -0,0 +1,244 @@
+use chrono::{DateTime, Duration, Utc};
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display as StrumDisplay;
+
+use crate::ConversationId;
+
+/// Limits used to decide which stored conversations should be pruned.
+///
+/// Every limit is optional; a policy with no limits never prunes anything.
+/// Pinned conversations are always exempt and do not count towards the count
+/// or size limits.
+#[derive(Debug, Clone, Default, PartialEq, Setters)]
+#[setters(strip_option, into)]
+pub struct RetentionPolicy {
+    /// Conversations not updated within this duration are pruned
+    pub max_age: Option<Duration>,
+    /// Maximum number of unpinned conversations to keep
+    pub max_count: Option<usize>,
+    /// Maximum combined size in bytes of unpinned conversations to keep
+    pub max_size_bytes: Option<u64>,
+}
+
+/// Lightweight view of a stored conversation used for retention decisions
+#[derive(Debug, Clone, PartialEq, Setters)]
+#[setters(into)]
+pub struct ConversationSummary {
+    pub id: ConversationId,
+    pub title: Option<String>,
+    /// Last time the conversation was updated, or its creation time if it was
+    /// never updated
+    pub updated_at: DateTime<Utc>,
+    /// Size in bytes of the persisted conversation
+    pub size_bytes: u64,
+    pub pinned: bool,
+}
+
+impl ConversationSummary {
+    pub fn new(id: ConversationId, updated_at: DateTime<Utc>) -> Self {
+        Self { id, title: None, updated_at, size_bytes: 0, pinned: false }
+    }
+}
+
+/// The retention limit that caused a conversation to be pruned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, StrumDisplay)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PruneReason {
+    Age,
+    Count,
+    Size,
+}
+
+/// A conversation selected for pruning along with the reason it was selected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrunedConversation {
+    pub id: ConversationId,
+    pub title: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    pub reason: PruneReason,
+}
+
+impl PrunedConversation {
+    fn new(summary: ConversationSummary, reason: PruneReason) -> Self {
+        Self {
+            id: summary.id,
+            title: summary.title,
+            updated_at: summary.updated_at,
+            size_bytes: summary.size_bytes,
+            reason,
+        }
+    }
+}
+
+/// Outcome of applying a [`RetentionPolicy`] to the conversation store
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PruneReport {
+    /// Conversations that were (or, in a dry run, would be) deleted
+    pub pruned: Vec<PrunedConversation>,
+    /// Whether the report was produced without deleting anything
+    pub dry_run: bool,
+}
+
+impl PruneReport {
+    /// Total number of bytes reclaimed by the pruned conversations
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.pruned.iter().map(|c| c.size_bytes).sum()
+    }
+}
+
+impl RetentionPolicy {
+    /// Returns true when the policy defines at least one limit
+    pub fn is_enabled(&self) -> bool {
+        self.max_age.is_some() || self.max_count.is_some() || self.max_size_bytes.is_some()
+    }
+
+    /// Selects the conversations that violate this policy.
+    ///
+    /// Conversations are considered newest first so that the most recently
+    /// used conversations are the ones kept when count or size limits are hit.
+    /// Pinned conversations are never selected.
+    ///
+    /// # Arguments
+    /// * `summaries` - Summaries of all stored conversations
+    /// * `now` - Reference time used to evaluate the age limit
+    pub fn select(
+        &self,
+        mut summaries: Vec<ConversationSummary>,
+        now: DateTime<Utc>,
+    ) -> Vec<PrunedConversation> {
+        summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+        let mut kept_count = 0usize;
+        let mut kept_bytes = 0u64;
+        let mut pruned = Vec::new();
+
+        for summary in summaries.into_iter().filter(|s| !s.pinned) {
+            let reason = if self
+                .max_age
+                .is_some_and(|max_age| now - summary.updated_at > max_age)
+            {
+                Some(PruneReason::Age)
+            } else if self.max_count.is_some_and(|max| kept_count >= max) {
+                Some(PruneReason::Count)
+            } else if self
+                .max_size_bytes
+                .is_some_and(|max| kept_bytes + summary.size_bytes > max)
+            {
+                Some(PruneReason::Size)
+            } else {
+                None
+            };
+
+            match reason {
+                Some(reason) => pruned.push(PrunedConversation::new(summary, reason)),
+                None => {
+                    kept_count += 1;
+                    kept_bytes += summary.size_bytes;
+                }
+            }
+        }
+
+        pruned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap()
+    }
+
+    fn fixture(days_ago: i64, size_bytes: u64) -> ConversationSummary {
+        ConversationSummary::new(ConversationId::generate(), now() - Duration::days(days_ago))
+            .size_bytes(size_bytes)
+    }
+
+    fn reasons(pruned: &[PrunedConversation]) -> Vec<(ConversationId, PruneReason)> {
+        pruned.iter().map(|p| (p.id, p.reason)).collect()
+    }
+
+    #[test]
+    fn test_select_without_limits_prunes_nothing() {
+        let fixture = vec![fixture(400, 10), fixture(1, 10)];
+
+        let actual = RetentionPolicy::default().select(fixture, now());
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn test_select_by_age() {
+        let old = fixture(40, 10);
+        let recent = fixture(5, 10);
+        let policy = RetentionPolicy::default().max_age(Duration::days(30));
+
+        let actual = reasons(&policy.select(vec![old.clone(), recent], now()));
+
+        let expected = vec![(old.id, PruneReason::Age)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_select_by_count_keeps_newest() {
+        let oldest = fixture(3, 10);
+        let middle = fixture(2, 10);
+        let newest = fixture(1, 10);
+        let policy = RetentionPolicy::default().max_count(1usize);
+
+        let actual = reasons(&policy.select(vec![oldest.clone(), newest, middle.clone()], now()));
+
+        let expected = vec![
+            (middle.id, PruneReason::Count),
+            (oldest.id, PruneReason::Count),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_select_by_size() {
+        let older = fixture(2, 60);
+        let newer = fixture(1, 60);
+        let policy = RetentionPolicy::default().max_size_bytes(100u64);
+
+        let actual = reasons(&policy.select(vec![older.clone(), newer], now()));
+
+        let expected = vec![(older.id, PruneReason::Size)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_select_skips_pinned_conversations() {
+        let pinned = fixture(400, 1000).pinned(true);
+        let unpinned = fixture(1, 10);
+        let policy = RetentionPolicy::default()
+            .max_age(Duration::days(30))
+            .max_count(1usize)
+            .max_size_bytes(100u64);
+
+        let actual = policy.select(vec![pinned, unpinned], now());
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn test_prune_report_reclaimed_bytes() {
+        let policy = RetentionPolicy::default().max_count(0usize);
+        let fixture = PruneReport {
+            pruned: policy.select(vec![fixture(1, 10), fixture(2, 32)], now()),
+            dry_run: true,
+        };
+
+        let actual = fixture.reclaimed_bytes();
+
+        let expected = 42;
+        assert_eq!(actual, expected);
+    }
+}
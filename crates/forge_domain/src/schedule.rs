@@ -0,0 +1,212 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Context, bail};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use derive_setters::Setters;
+
+use crate::AgentId;
+
+/// How far ahead to look for the next run; long enough for schedules that
+/// only fire on February 29
+const SEARCH_DAYS: i64 = 5 * 366;
+
+/// A prompt run by the scheduler at the times of a cron expression, each run
+/// in a new conversation
+#[derive(Debug, Clone, PartialEq, Setters)]
+#[setters(strip_option, into)]
+pub struct Schedule {
+    pub cron: Cron,
+    pub prompt: String,
+    /// Agent handling the prompt; the active agent when absent
+    pub agent: Option<AgentId>,
+}
+
+impl Schedule {
+    pub fn new(cron: Cron, prompt: impl Into<String>) -> Self {
+        Self { cron, prompt: prompt.into(), agent: None }
+    }
+}
+
+/// A cron expression of five fields: minute, hour, day of month, month and day
+/// of week, e.g. `0 3 * * 1-5` for 03:00 on weekdays. Fields take `*`, values,
+/// ranges, lists and steps, and `@hourly`, `@daily`, `@weekly` and `@monthly`
+/// stand for the common schedules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day of month or day of week is restricted; when both are,
+    /// a day matching either runs the schedule, as in cron
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    /// The first time after `after` the schedule runs, at minute precision
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = time + Duration::days(SEARCH_DAYS);
+        while time < limit {
+            if !self.matches_day(time.date()) {
+                time = time.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if !has(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+            } else if !has(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if !has(self.months, date.month()) {
+            return false;
+        }
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses one field into the set of values it matches
+fn parse_field(field: &str, min: u32, max: u32) -> anyhow::Result<u64> {
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<u32>()?)),
+            None => (part, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (start.parse()?, end.parse()?),
+            // A step after a single value runs from it to the end of the field
+            None if step.is_some() => (range.parse()?, max),
+            None => (range.parse()?, range.parse()?),
+        };
+        if start < min || end > max || start > end {
+            bail!("{part} is outside {min}-{max}");
+        }
+        let step = step.unwrap_or(1);
+        if step == 0 {
+            bail!("{part} has a step of 0");
+        }
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+impl FromStr for Cron {
+    type Err = anyhow::Error;
+
+    fn from_str(expression: &str) -> anyhow::Result<Self> {
+        let fields = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            fields => fields,
+        }
+        .split_whitespace()
+        .collect::<Vec<_>>();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            bail!("Cron expression '{expression}' must have 5 fields");
+        };
+
+        let field = |field: &str, name: &str, min: u32, max: u32| {
+            parse_field(field, min, max)
+                .with_context(|| format!("Invalid {name} '{field}' in '{expression}'"))
+        };
+        let weekdays = field(weekday, "day of week", 0, 7)?;
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            minutes: field(minute, "minute", 0, 59)?,
+            hours: field(hour, "hour", 0, 23)?,
+            days: field(day, "day of month", 1, 31)?,
+            months: field(month, "month", 1, 12)?,
+            // 7 is Sunday too
+            weekdays: (weekdays | (weekdays >> 7)) & 0x7f,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_next_after() {
+        // 2026-10-16 is a Friday
+        let fixture = time("2026-10-16 10:37");
+
+        let actual = [
+            "*/15 * * * *",
+            "0 3 * * 1-5",
+            "30 9 1,15 * *",
+            "0 0 13 * 5",
+            "@weekly",
+            "0 12 29 2 *",
+        ]
+        .map(|expression| {
+            expression
+                .parse::<Cron>()
+                .unwrap()
+                .next_after(fixture)
+                .unwrap()
+        });
+
+        let expected = [
+            time("2026-10-16 10:45"),
+            time("2026-10-19 03:00"),
+            time("2026-11-01 09:30"),
+            time("2026-10-23 00:00"),
+            time("2026-10-18 00:00"),
+            time("2028-02-29 12:00"),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        let actual = [
+            "* * * *",
+            "60 * * * *",
+            "*/0 * * * *",
+            "0 5-3 * * *",
+            "a * * * *",
+        ]
+        .map(|expression| expression.parse::<Cron>().is_err());
+
+        let expected = [true; 5];
+        assert_eq!(actual, expected);
+    }
+}
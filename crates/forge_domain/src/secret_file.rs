@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use anyhow::Context;
+use glob::Pattern;
+
+/// Glob patterns of files holding secrets, such as `.env` files and private
+/// keys, kept out of file reads, searches and attachments unless the user
+/// shares them. Patterns without a `/` match the file name, others the whole
+/// path. Patterns starting with `!` exempt the files they match, e.g.
+/// `!.env.example`.
+#[derive(Debug, Clone, Default)]
+pub struct SecretFiles {
+    secrets: Vec<Pattern>,
+    exempt: Vec<Pattern>,
+}
+
+impl SecretFiles {
+    pub fn new(patterns: &[String]) -> anyhow::Result<Self> {
+        let mut secret_files = Self::default();
+        for pattern in patterns {
+            let (list, glob) = match pattern.strip_prefix('!') {
+                Some(glob) => (&mut secret_files.exempt, glob),
+                None => (&mut secret_files.secrets, pattern.as_str()),
+            };
+            list.push(
+                Pattern::new(glob)
+                    .with_context(|| format!("Invalid secret file pattern: {pattern}"))?,
+            );
+        }
+        Ok(secret_files)
+    }
+
+    /// Whether the file at `path` holds secrets
+    pub fn is_secret(&self, path: &Path) -> bool {
+        let matches = |patterns: &[Pattern]| {
+            patterns.iter().any(|pattern| {
+                if pattern.as_str().contains('/') {
+                    pattern.matches_path(path)
+                } else {
+                    path.file_name()
+                        .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
+                }
+            })
+        };
+        matches(&self.secrets) && !matches(&self.exempt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_is_secret() {
+        let fixture = SecretFiles::new(&[
+            ".env".to_string(),
+            ".env.*".to_string(),
+            "!.env.example".to_string(),
+            "*.pem".to_string(),
+            "**/.aws/credentials".to_string(),
+        ])
+        .unwrap();
+
+        let actual = [
+            "/project/.env",
+            "/project/web/.env.local",
+            "/project/.env.example",
+            "/project/certs/server.pem",
+            "/home/user/.aws/credentials",
+            "/project/src/credentials",
+            "/project/src/env.rs",
+        ]
+        .map(|path| fixture.is_secret(Path::new(path)));
+
+        let expected = [true, true, false, true, true, false, false];
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,11 +1,16 @@
 use std::fmt::{Display, Formatter};
 use std::hash::Hasher;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::{ConversationId, ToolCallId};
+
+/// Format of the timestamp naming a snapshot file, including nanoseconds
+const SNAPSHOT_TIME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S-%9f";
+
 /// A newtype for snapshot IDs, internally using UUID
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct SnapshotId(Uuid);
@@ -45,6 +50,21 @@ impl From<Uuid> for SnapshotId {
     }
 }
 
+/// Changes between two snapshots of the same file, rendered for the terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnifiedDiff {
+    /// Path of the file the snapshots were taken of
+    pub path: PathBuf,
+    /// Position of the older side of the diff, 0 being the oldest snapshot
+    pub from_index: usize,
+    /// Position of the newer side of the diff
+    pub to_index: usize,
+    /// Diff of the two contents
+    pub diff: String,
+    pub lines_added: u64,
+    pub lines_removed: u64,
+}
+
 /// Represents information about a file snapshot
 ///
 /// Contains details about when the snapshot was created,
@@ -59,6 +79,23 @@ pub struct Snapshot {
 
     /// Original file path that is being processed
     pub path: String,
+
+    /// Conversation whose agent made the edit the snapshot was taken before
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<ConversationId>,
+
+    /// Tool call that made the edit the snapshot was taken before
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<ToolCallId>,
+}
+
+/// Agent turn that triggered a snapshot, stored next to the snapshot so that
+/// the changes of a conversation can be reviewed and reverted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotTag {
+    pub conversation_id: ConversationId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<ToolCallId>,
 }
 
 impl Snapshot {
@@ -81,6 +118,8 @@ impl Snapshot {
             id: SnapshotId::new(),
             timestamp,
             path: path.display().to_string(),
+            conversation_id: None,
+            tool_call_id: None,
         })
     }
 
@@ -95,9 +134,8 @@ impl Snapshot {
     pub fn snapshot_path(&self, cwd: Option<PathBuf>) -> PathBuf {
         // Convert Duration to SystemTime then to a formatted string
         let datetime = UNIX_EPOCH + self.timestamp;
-        // Format: YYYY-MM-DD_HH-MM-SS-nnnnnnnnn (including nanoseconds)
         let formatted_time = chrono::DateTime::<chrono::Utc>::from(datetime)
-            .format("%Y-%m-%d_%H-%M-%S-%9f")
+            .format(SNAPSHOT_TIME_FORMAT)
             .to_string();
 
         let filename = format!("{formatted_time}.snap");
@@ -108,10 +146,26 @@ impl Snapshot {
             path
         }
     }
+
+    /// Reads the creation time of a snapshot from its file name, `None` for
+    /// files not named by [`Snapshot::snapshot_path`]
+    pub fn timestamp_of(snapshot_path: &Path) -> Option<Duration> {
+        let name = snapshot_path.file_name()?.to_str()?.strip_suffix(".snap")?;
+        let datetime = chrono::NaiveDateTime::parse_from_str(name, SNAPSHOT_TIME_FORMAT).ok()?;
+        let nanos = datetime.and_utc().timestamp_nanos_opt()?;
+        Some(Duration::from_nanos(u64::try_from(nanos).ok()?))
+    }
+
+    /// Path of the file holding the [`SnapshotTag`] of a snapshot
+    pub fn tag_path(snapshot_path: &Path) -> PathBuf {
+        snapshot_path.with_extension("tag")
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use pretty_assertions::assert_eq;
+
     use super::*;
 
     #[test]
@@ -126,6 +180,19 @@ mod tests {
         assert_eq!(snapshot.path, nonexistent_path.display().to_string());
     }
 
+    #[test]
+    fn test_timestamp_of_snapshot_path() {
+        let fixture = Snapshot::create(PathBuf::from("/project/src/main.rs")).unwrap();
+
+        let actual = (
+            Snapshot::timestamp_of(&fixture.snapshot_path(Some(PathBuf::from("/snapshots")))),
+            Snapshot::timestamp_of(Path::new("/snapshots/blobs/af13")),
+        );
+
+        let expected = (Some(fixture.timestamp), None);
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_create_with_nonexistent_relative_path() {
         // Test with a non-existent relative path
@@ -2,7 +2,7 @@ use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
-use crate::{Agent, Environment, File, Model, Skill};
+use crate::{Agent, Environment, File, GitState, Model, Skill};
 
 /// Statistics for a file extension
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -132,6 +132,11 @@ pub struct SystemContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensions: Option<Extension>,
 
+    /// Ranked map of the key files of the workspace, their symbols and the
+    /// imports between them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo_map: Option<String>,
+
     /// List of available agents for task delegation
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub agents: Vec<Agent>,
@@ -139,4 +144,9 @@ pub struct SystemContext {
     /// Template configuration for tool descriptions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<TemplateConfig>,
+
+    /// Git state of the workspace, only gathered when the system prompt uses
+    /// it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitState>,
 }
@@ -28,6 +28,20 @@ impl<V> Template<V> {
             _marker: std::marker::PhantomData,
         }
     }
+
+    /// Whether an expression of the template uses `variable`, so that values
+    /// costly to gather are only gathered for templates using them. Partials
+    /// aren't expanded.
+    pub fn references(&self, variable: &str) -> bool {
+        self.template.split("{{").skip(1).any(|expression| {
+            expression
+                .split("}}")
+                .next()
+                .unwrap_or_default()
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|word| word == variable)
+        })
+    }
 }
 
 impl<S: AsRef<str>> From<S> for Template<Value> {
@@ -35,3 +49,29 @@ impl<S: AsRef<str>> From<S> for Template<Value> {
         Template::new(value.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_references() {
+        let fixture = [
+            "On {{git.branch}}",
+            "{{#if git.dirty_files}}Uncommitted changes{{/if}}",
+            "{{#each git.dirty_files}}- {{this}}{{/each}}",
+            "Read the .git folder and {{gitignore}}",
+            "No variables",
+        ];
+
+        let actual = fixture
+            .iter()
+            .map(|template| Template::<Value>::new(template).references("git"))
+            .collect::<Vec<_>>();
+
+        let expected = vec![true, true, true, false, false];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::LazyLock;
+
+use tiktoken_rs::CoreBPE;
+
+use crate::ModelId;
+
+static O200K: LazyLock<CoreBPE> =
+    LazyLock::new(|| tiktoken_rs::o200k_base().expect("o200k_base ranks are bundled"));
+static CL100K: LazyLock<CoreBPE> =
+    LazyLock::new(|| tiktoken_rs::cl100k_base().expect("cl100k_base ranks are bundled"));
+
+/// Counts how many tokens a piece of text takes up for a model
+pub trait TokenCounter: Send + Sync {
+    fn count(&self, text: &str) -> usize;
+}
+
+/// Approximates the token count from the number of characters, assuming ~4
+/// characters per token. Used where no tokenizer for the model is available.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ApproxTokenCounter;
+
+impl TokenCounter for ApproxTokenCounter {
+    fn count(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+/// Tokenizer used to count tokens locally for a model
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Tokenizer {
+    /// tiktoken's `o200k_base`, used by GPT-4o, GPT-4.1, GPT-5 and the
+    /// o-series
+    O200k,
+    /// tiktoken's `cl100k_base`, used by GPT-4 and GPT-3.5, and the closest
+    /// available match for models whose tokenizer is not public, such as
+    /// Claude and Gemini
+    Cl100k,
+    /// The [`ApproxTokenCounter`] heuristic, for when the model is not known
+    #[default]
+    Approx,
+}
+
+impl Tokenizer {
+    /// Tokenizer closest to the one `model` uses
+    pub fn for_model(model: &ModelId) -> Self {
+        let id = model.as_str().to_lowercase();
+        // Drop routing prefixes such as `openai/gpt-4o`
+        let name = id.rsplit('/').next().unwrap_or_default();
+        let is_o200k = [
+            "gpt-4o",
+            "gpt-4.1",
+            "gpt-5",
+            "gpt-oss",
+            "chatgpt-4o",
+            "codex",
+        ]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+            || ["o1", "o3", "o4"]
+                .iter()
+                .any(|series| name == *series || name.starts_with(&format!("{series}-")));
+        if is_o200k { Self::O200k } else { Self::Cl100k }
+    }
+
+    /// Number of tokens `text` takes up
+    pub fn count(&self, text: &str) -> usize {
+        match self {
+            Self::O200k => O200K.encode_with_special_tokens(text).len(),
+            Self::Cl100k => CL100K.encode_with_special_tokens(text).len(),
+            Self::Approx => ApproxTokenCounter.count(text),
+        }
+    }
+}
+
+impl TokenCounter for Tokenizer {
+    fn count(&self, text: &str) -> usize {
+        Tokenizer::count(self, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_approx_token_counter_rounds_up() {
+        let fixture = "hello";
+
+        let actual = ApproxTokenCounter.count(fixture);
+        let expected = 2;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_tokenizer_for_model() {
+        let fixture = [
+            "gpt-4o-mini",
+            "openai/gpt-5",
+            "o3",
+            "o4-mini",
+            "gpt-4",
+            "claude-sonnet-4",
+            "omni",
+        ];
+
+        let actual = fixture
+            .iter()
+            .map(|model| Tokenizer::for_model(&ModelId::new(*model)))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            Tokenizer::O200k,
+            Tokenizer::O200k,
+            Tokenizer::O200k,
+            Tokenizer::O200k,
+            Tokenizer::Cl100k,
+            Tokenizer::Cl100k,
+            Tokenizer::Cl100k,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_tokenizer_counts_tokens() {
+        let actual = Tokenizer::Cl100k.count("hello world");
+        let expected = 2;
+        assert_eq!(actual, expected);
+    }
+}
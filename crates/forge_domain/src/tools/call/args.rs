@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use forge_json_repair::json_repair;
+use forge_json_repair::parse_tolerant;
 use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue;
 use serde_json::{Map, Value};
@@ -40,10 +40,10 @@ impl<'de> Deserialize<'de> for ToolCallArguments {
         // Handle case where API sends arguments as a string containing JSON
         // e.g., "{\"key\": \"value\"}" instead of {"key": "value"}
         if let Value::String(json_str) = &value {
-            if let Ok(repaired) = json_repair(json_str) {
+            if let Ok(repaired) = parse_tolerant(json_str) {
                 return Ok(ToolCallArguments::Parsed(repaired));
             }
-            // If json_repair fails, fall back to storing as Unparsed
+            // If parsing fails, fall back to storing as Unparsed
             return Ok(ToolCallArguments::Unparsed(json_str.clone()));
         }
 
@@ -77,7 +77,7 @@ impl ToolCallArguments {
         match self {
             ToolCallArguments::Unparsed(json_str) => {
                 // Try to parse the string as JSON
-                if let Ok(repaired) = json_repair(&json_str) {
+                if let Ok(repaired) = parse_tolerant(&json_str) {
                     ToolCallArguments::Parsed(repaired)
                 } else {
                     // If it's not valid JSON, create a fallback object with the raw content
@@ -95,7 +95,7 @@ impl ToolCallArguments {
         match self {
             ToolCallArguments::Unparsed(json) => {
                 Ok(
-                    json_repair(json).map_err(|error| crate::Error::ToolCallArgument {
+                    parse_tolerant(json).map_err(|error| crate::Error::ToolCallArgument {
                         error,
                         args: json.to_owned(),
                     })?,
@@ -378,6 +378,14 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_parse_unparsed_json5_arguments() {
+        let fixture = ToolCallArguments::from_json("{path: 'src/lib.rs', recursive: true,}");
+        let actual = fixture.parse().unwrap();
+        let expected = json!({"path": "src/lib.rs", "recursive": true});
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_parse_parsed_value() {
         let value = json!({"param": "value"});
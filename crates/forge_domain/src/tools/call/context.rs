@@ -2,19 +2,38 @@ use std::sync::{Arc, Mutex};
 
 use derive_setters::Setters;
 
-use crate::{ArcSender, ChatResponse, Metrics, TitleFormat, Todo, TodoItem};
+use crate::{
+    ArcSender, ChatResponse, ConversationId, Metrics, SnapshotTag, TitleFormat, Todo, TodoItem,
+    ToolCallId,
+};
 
 /// Provides additional context for tool calls.
 #[derive(Debug, Clone, Setters)]
 pub struct ToolCallContext {
     sender: Option<ArcSender>,
     metrics: Arc<Mutex<Metrics>>,
+    conversation_id: Option<ConversationId>,
+    tool_call_id: Option<ToolCallId>,
 }
 
 impl ToolCallContext {
     /// Creates a new ToolCallContext with default values
     pub fn new(metrics: Metrics) -> Self {
-        Self { sender: None, metrics: Arc::new(Mutex::new(metrics)) }
+        Self {
+            sender: None,
+            metrics: Arc::new(Mutex::new(metrics)),
+            conversation_id: None,
+            tool_call_id: None,
+        }
+    }
+
+    /// Tag for the snapshots taken by the tool call, `None` outside of a
+    /// conversation
+    pub fn snapshot_tag(&self) -> Option<SnapshotTag> {
+        self.conversation_id.map(|conversation_id| SnapshotTag {
+            conversation_id,
+            tool_call_id: self.tool_call_id.clone(),
+        })
     }
 
     /// Send a message through the sender if available
@@ -209,6 +209,42 @@ pub struct FSRead {
     /// is too large to read at once
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_line: Option<i32>,
+
+    /// What to read: "range" (default) reads from start_line to end_line,
+    /// "head" and "tail" the first or last `lines` lines, and "outline" only
+    /// the lines declaring functions, types and other symbols, to find the
+    /// range to read in a large file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<ReadMode>,
+
+    /// Number of lines to read in "head" and "tail" mode. Defaults to 50.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<u32>,
+}
+
+impl FSRead {
+    /// Lines read in head and tail mode when `lines` is not given
+    const DEFAULT_WINDOW_LINES: u64 = 50;
+
+    /// Number of lines read in head and tail mode
+    pub fn window_lines(&self) -> u64 {
+        self.lines
+            .map_or(Self::DEFAULT_WINDOW_LINES, |lines| u64::from(lines.max(1)))
+    }
+}
+
+/// Part of a file returned by the read tool
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, AsRefStr, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadMode {
+    /// Lines from start_line to end_line
+    Range,
+    /// First lines of the file
+    Head,
+    /// Last lines of the file
+    Tail,
+    /// Lines declaring functions, types and other symbols
+    Outline,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
@@ -248,23 +284,25 @@ pub struct FSSearch {
 
     /// Output mode: "content" shows matching lines (supports -A/-B/-C context,
     /// -n line numbers, head_limit), "files_with_matches" shows file paths
-    /// (supports head_limit), "count" shows match counts (supports head_limit).
+    /// (supports head_limit), "count" shows match counts (supports head_limit),
+    /// "json" shows one JSON object per match with its path, line, column,
+    /// matched text and -A/-B/-C context (supports head_limit).
     /// Defaults to "files_with_matches".
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_mode: Option<OutputMode>,
 
     /// Number of lines to show before each match (rg -B). Requires output_mode:
-    /// "content", ignored otherwise.
+    /// "content" or "json", ignored otherwise.
     #[serde(rename = "-B", skip_serializing_if = "Option::is_none")]
     pub before_context: Option<u32>,
 
     /// Number of lines to show after each match (rg -A). Requires output_mode:
-    /// "content", ignored otherwise.
+    /// "content" or "json", ignored otherwise.
     #[serde(rename = "-A", skip_serializing_if = "Option::is_none")]
     pub after_context: Option<u32>,
 
     /// Number of lines to show before and after each match (rg -C). Requires
-    /// output_mode: "content", ignored otherwise.
+    /// output_mode: "content" or "json", ignored otherwise.
     #[serde(rename = "-C", skip_serializing_if = "Option::is_none")]
     pub context: Option<u32>,
 
@@ -309,6 +347,8 @@ pub enum OutputMode {
     FilesWithMatches,
     /// Show match counts per file
     Count,
+    /// Show one JSON object per match with its position and context
+    Json,
 }
 
 /// A paired query and use_case for semantic search. Each query must have a
@@ -516,6 +556,16 @@ impl JsonSchema for PatchOperation {
     }
 }
 
+impl JsonSchema for ReadMode {
+    fn schema_name() -> Cow<'static, str> {
+        <Self as SimpleEnumSchema>::simple_enum_schema_name()
+    }
+
+    fn json_schema(r#gen: &mut schemars::generate::SchemaGenerator) -> Schema {
+        <Self as SimpleEnumSchema>::simple_enum_schema(r#gen)
+    }
+}
+
 impl JsonSchema for OutputMode {
     fn schema_name() -> Cow<'static, str> {
         <Self as SimpleEnumSchema>::simple_enum_schema_name()
@@ -1216,7 +1266,7 @@ mod tests {
     use pretty_assertions::assert_eq;
     use strum::IntoEnumIterator;
 
-    use super::Shell;
+    use super::{FSRead, ReadMode, Shell};
     use crate::{ToolCatalog, ToolKind, ToolName};
 
     #[test]
@@ -1721,6 +1771,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fs_read_window_mode() {
+        use pretty_assertions::assert_eq;
+
+        let fixture = r#"{"file_path": "/test/lib.rs", "mode": "tail", "lines": 20}"#;
+        let read: FSRead = serde_json::from_str(fixture).unwrap();
+
+        let actual = (
+            read.mode,
+            read.window_lines(),
+            FSRead::default().window_lines(),
+        );
+        let expected = (Some(ReadMode::Tail), 20, 50);
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_unit_enum_schema_generation() {
         use schemars::generate::SchemaSettings;
@@ -1750,10 +1816,11 @@ mod tests {
         assert_eq!(schema_value.get("type"), Some(&serde_json::json!("string")));
 
         let enum_values = schema_value.get("enum").and_then(|v| v.as_array()).unwrap();
-        assert_eq!(enum_values.len(), 3);
+        assert_eq!(enum_values.len(), 4);
         assert_eq!(enum_values[0], serde_json::json!("content"));
         assert_eq!(enum_values[1], serde_json::json!("files_with_matches"));
         assert_eq!(enum_values[2], serde_json::json!("count"));
+        assert_eq!(enum_values[3], serde_json::json!("json"));
     }
 
     #[test]
@@ -1,3 +1,6 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
 use handlebars::Handlebars;
 use include_dir::{Dir, DirEntry, File};
 
@@ -37,3 +40,193 @@ pub fn register_templates(hb: &mut Handlebars<'_>, dir: &'static Dir<'static>) {
             .unwrap_or_else(|e| panic!("failed to register template '{}': {}", name, e));
     }
 }
+
+/// A file in the user's template directory replacing the embedded template of
+/// the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateOverride {
+    /// Name of the embedded template, e.g. `forge-system-prompt.md`
+    pub name: String,
+    /// File replacing it
+    pub path: PathBuf,
+}
+
+/// Returns the templates embedded in `dir` that have a file of the same
+/// relative path in `override_dir`, sorted by name.
+pub fn template_overrides(
+    dir: &'static Dir<'static>,
+    override_dir: &Path,
+) -> Vec<TemplateOverride> {
+    let mut overrides: Vec<_> = files(dir)
+        .filter_map(|file| {
+            let name = file.path().to_str()?;
+            let path = override_dir.join(file.path());
+            path.is_file()
+                .then(|| TemplateOverride { name: name.to_string(), path })
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.name.cmp(&b.name));
+    overrides
+}
+
+/// Registers the files in `override_dir` that replace templates embedded in
+/// `dir`, on top of templates already registered with [`register_templates`].
+///
+/// Precedence: a user file always wins over the embedded template of the same
+/// name. Files that do not match an embedded template are ignored, so
+/// templates can be replaced but not added. An override that cannot be read
+/// or parsed is skipped, leaving the embedded template in place, and returned
+/// with the reason so callers can report it.
+pub fn register_overrides(
+    hb: &mut Handlebars<'_>,
+    dir: &'static Dir<'static>,
+    override_dir: &Path,
+) -> Vec<(TemplateOverride, anyhow::Error)> {
+    template_overrides(dir, override_dir)
+        .into_iter()
+        .filter_map(|template| {
+            let registered = std::fs::read_to_string(&template.path)
+                .with_context(|| format!("failed to read {}", template.path.display()))
+                .and_then(|content| {
+                    hb.register_template_string(&template.name, content)
+                        .with_context(|| format!("failed to parse {}", template.path.display()))
+                });
+            registered.err().map(|error| (template, error))
+        })
+        .collect()
+}
+
+/// Registers every file directly inside `partials_dir` as a partial named
+/// after its file name (e.g. `{{> rules.md}}`), the same way embedded
+/// templates are named.
+///
+/// A missing directory registers nothing. Partials never replace an already
+/// registered template; use an override for that. Files that collide with a
+/// template, cannot be read or fail to parse are skipped and returned with the
+/// reason.
+pub fn register_partials(
+    hb: &mut Handlebars<'_>,
+    partials_dir: &Path,
+) -> Vec<(PathBuf, anyhow::Error)> {
+    let Ok(entries) = std::fs::read_dir(partials_dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let registered = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .context("partial file name is not valid UTF-8")
+                .and_then(|name| {
+                    anyhow::ensure!(
+                        !hb.has_template(name),
+                        "partial '{name}' collides with a built-in template"
+                    );
+                    let content = std::fs::read_to_string(&path)
+                        .with_context(|| format!("failed to read {}", path.display()))?;
+                    hb.register_partial(name, content)
+                        .with_context(|| format!("failed to parse {}", path.display()))
+                });
+            registered.err().map(|error| (path, error))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use include_dir::{Dir, DirEntry, File};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    static FIXTURE_DIR: Dir<'static> = Dir::new(
+        "",
+        &[
+            DirEntry::File(File::new("greeting.md", b"Hello {{name}}")),
+            DirEntry::File(File::new("farewell.md", b"Bye {{name}}")),
+        ],
+    );
+
+    fn handlebars_fixture() -> Handlebars<'static> {
+        let mut hb = Handlebars::new();
+        register_templates(&mut hb, &FIXTURE_DIR);
+        hb
+    }
+
+    #[test]
+    fn test_override_replaces_embedded_template() {
+        let override_dir = tempfile::tempdir().unwrap();
+        std::fs::write(override_dir.path().join("greeting.md"), "Hi {{name}}!").unwrap();
+        std::fs::write(override_dir.path().join("unknown.md"), "ignored").unwrap();
+        let mut hb = handlebars_fixture();
+
+        let errors = register_overrides(&mut hb, &FIXTURE_DIR, override_dir.path());
+        let data = serde_json::json!({"name": "Ada"});
+        let actual = (
+            errors.len(),
+            hb.render("greeting.md", &data).unwrap(),
+            hb.render("farewell.md", &data).unwrap(),
+            hb.get_template("unknown.md").is_some(),
+        );
+
+        let expected = (0, "Hi Ada!".to_string(), "Bye Ada".to_string(), false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_invalid_override_keeps_embedded_template() {
+        let override_dir = tempfile::tempdir().unwrap();
+        std::fs::write(override_dir.path().join("farewell.md"), "Bye {{#if}}").unwrap();
+        let mut hb = handlebars_fixture();
+
+        let errors = register_overrides(&mut hb, &FIXTURE_DIR, override_dir.path());
+        let actual = hb
+            .render("farewell.md", &serde_json::json!({"name": "Ada"}))
+            .unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0.name, "farewell.md");
+        assert_eq!(actual, "Bye Ada");
+    }
+
+    #[test]
+    fn test_partials_are_registered_without_replacing_templates() {
+        let partials_dir = tempfile::tempdir().unwrap();
+        std::fs::write(partials_dir.path().join("sign.md"), "-- {{name}}").unwrap();
+        std::fs::write(partials_dir.path().join("greeting.md"), "Hijacked").unwrap();
+        let mut hb = handlebars_fixture();
+
+        let errors = register_partials(&mut hb, partials_dir.path());
+        let actual = hb
+            .render_template(
+                "{{> greeting.md}} {{> sign.md}}",
+                &serde_json::json!({"name": "Ada"}),
+            )
+            .unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, partials_dir.path().join("greeting.md"));
+        assert_eq!(actual, "Hello Ada -- Ada");
+    }
+
+    #[test]
+    fn test_template_overrides_lists_matching_files() {
+        let override_dir = tempfile::tempdir().unwrap();
+        std::fs::write(override_dir.path().join("greeting.md"), "Hi").unwrap();
+
+        let actual = template_overrides(&FIXTURE_DIR, override_dir.path());
+
+        let expected = vec![TemplateOverride {
+            name: "greeting.md".to_string(),
+            path: override_dir.path().join("greeting.md"),
+        }];
+        assert_eq!(actual, expected);
+    }
+}
@@ -29,13 +29,21 @@ pub fn to_environment(cwd: PathBuf) -> Environment {
 /// Applies a single [`ConfigOperation`] directly to a [`ForgeConfig`].
 ///
 /// Used by [`ForgeEnvironmentInfra::update_environment`] to mutate the
-/// persisted config without an intermediate `Environment` round-trip.
-fn apply_config_op(fc: &mut ForgeConfig, op: ConfigOperation) {
+/// persisted config without an intermediate `Environment` round-trip. The
+/// session and reasoning effort are written to `profile`, the profile the
+/// session uses, when it sets them, since the profile would otherwise shadow
+/// the change.
+fn apply_config_op(fc: &mut ForgeConfig, op: ConfigOperation, profile: Option<&str>) {
+    let profile = profile.and_then(|name| fc.profiles.get_mut(name));
     match op {
         ConfigOperation::SetSessionConfig(mc) => {
             let pid_str = mc.provider.as_ref().to_string();
             let mid_str = mc.model.to_string();
-            fc.session = Some(ModelConfig { provider_id: pid_str, model_id: mid_str });
+            let session = Some(ModelConfig { provider_id: pid_str, model_id: mid_str });
+            match profile {
+                Some(profile) if profile.session.is_some() => profile.session = session,
+                _ => fc.session = session,
+            }
         }
         ConfigOperation::SetCommitConfig(mc) => {
             fc.commit = mc.map(|m| ModelConfig {
@@ -50,20 +58,51 @@ fn apply_config_op(fc: &mut ForgeConfig, op: ConfigOperation) {
             });
         }
         ConfigOperation::SetReasoningEffort(effort) => {
-            let config_effort = match effort {
-                forge_domain::Effort::None => forge_config::Effort::None,
-                forge_domain::Effort::Minimal => forge_config::Effort::Minimal,
-                forge_domain::Effort::Low => forge_config::Effort::Low,
-                forge_domain::Effort::Medium => forge_config::Effort::Medium,
-                forge_domain::Effort::High => forge_config::Effort::High,
-                forge_domain::Effort::XHigh => forge_config::Effort::XHigh,
-                forge_domain::Effort::Max => forge_config::Effort::Max,
+            let config_effort = to_config_effort(effort);
+            let reasoning = match profile {
+                Some(profile) if profile.reasoning.is_some() => &mut profile.reasoning,
+                _ => &mut fc.reasoning,
             };
-            let reasoning = fc
-                .reasoning
-                .get_or_insert_with(forge_config::ReasoningConfig::default);
-            reasoning.effort = Some(config_effort);
+            reasoning
+                .get_or_insert_with(forge_config::ReasoningConfig::default)
+                .effort = Some(config_effort);
+        }
+        ConfigOperation::SetStorageScope(scope) => {
+            fc.storage = forge_app::utils::config_storage_scope(scope);
         }
+        ConfigOperation::SetProfile(name) => {
+            fc.profile = name;
+        }
+        // Session state is kept by the infra rather than written to the file
+        ConfigOperation::SetSessionReasoningEffort(_) => {}
+        ConfigOperation::SetSchedule(name, schedule) => match schedule {
+            Some(schedule) => {
+                fc.schedules.insert(
+                    name,
+                    forge_config::Schedule {
+                        cron: schedule.cron.to_string(),
+                        prompt: schedule.prompt,
+                        agent: schedule.agent.map(|agent| agent.to_string()),
+                    },
+                );
+            }
+            None => {
+                fc.schedules.remove(&name);
+            }
+        },
+    }
+}
+
+/// Converts a domain effort level to its configuration counterpart.
+fn to_config_effort(effort: forge_domain::Effort) -> forge_config::Effort {
+    match effort {
+        forge_domain::Effort::None => forge_config::Effort::None,
+        forge_domain::Effort::Minimal => forge_config::Effort::Minimal,
+        forge_domain::Effort::Low => forge_config::Effort::Low,
+        forge_domain::Effort::Medium => forge_config::Effort::Medium,
+        forge_domain::Effort::High => forge_config::Effort::High,
+        forge_domain::Effort::XHigh => forge_config::Effort::XHigh,
+        forge_domain::Effort::Max => forge_config::Effort::Max,
     }
 }
 
@@ -72,10 +111,14 @@ fn apply_config_op(fc: &mut ForgeConfig, op: ConfigOperation) {
 ///
 /// Uses [`ForgeConfig::read`] and [`ForgeConfig::write`] for all file I/O and
 /// maintains an in-memory cache to reduce disk access. Also handles
-/// environment variable discovery via `.env` files and OS APIs.
+/// environment variable discovery via `.env` files and OS APIs. Settings that
+/// last only for the session, such as the reasoning effort chosen with
+/// `/reasoning`, are kept here and applied over every config it returns.
 pub struct ForgeEnvironmentInfra {
     cwd: PathBuf,
     cache: Arc<std::sync::Mutex<Option<ForgeConfig>>>,
+    profile: Arc<std::sync::Mutex<Option<String>>>,
+    session_reasoning_effort: Arc<std::sync::Mutex<Option<forge_config::Effort>>>,
 }
 
 impl ForgeEnvironmentInfra {
@@ -86,27 +129,53 @@ impl ForgeEnvironmentInfra {
     ///
     /// # Arguments
     /// * `cwd` - The working directory path; used to resolve `.env` files
-    /// * `config` - The pre-read [`ForgeConfig`] to seed the in-memory cache
+    /// * `config` - The pre-read [`ForgeConfig`] to seed the in-memory cache;
+    ///   the profile applied to it, e.g. one given with `--profile`, is the
+    ///   one applied whenever the config is read again
     pub fn new(cwd: PathBuf, config: ForgeConfig) -> Self {
-        Self { cwd, cache: Arc::new(std::sync::Mutex::new(Some(config))) }
+        Self {
+            cwd,
+            profile: Arc::new(std::sync::Mutex::new(config.profile.clone())),
+            cache: Arc::new(std::sync::Mutex::new(Some(config))),
+            session_reasoning_effort: Default::default(),
+        }
     }
 
-    /// Returns the cached [`ForgeConfig`], re-reading from disk if the cache
-    /// has been invalidated by [`Self::update_environment`].
+    /// Returns the cached [`ForgeConfig`] with the session settings applied,
+    /// re-reading from disk if the cache has been invalidated by
+    /// [`Self::update_environment`].
     ///
     /// # Errors
     ///
     /// Returns an error if the cache is empty and the disk read fails.
     pub fn cached_config(&self) -> anyhow::Result<ForgeConfig> {
+        let mut config = self.read_config()?;
+        config.session_reasoning_effort = self
+            .session_reasoning_effort
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        Ok(config)
+    }
+
+    /// Returns the cached [`ForgeConfig`] as read from disk.
+    fn read_config(&self) -> anyhow::Result<ForgeConfig> {
         let mut cache = self.cache.lock().expect("cache mutex poisoned");
         if let Some(ref config) = *cache {
             Ok(config.clone())
         } else {
+            let profile = self
+                .profile
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
             let config = ConfigReader::default()
                 .read_defaults()
                 .read_global()
+                .read_workspace(&self.cwd)
                 .read_env()
-                .build()?;
+                .build()?
+                .apply_profile(profile.as_deref())?;
             *cache = Some(config.clone());
             Ok(config)
         }
@@ -133,6 +202,21 @@ impl EnvironmentInfra for ForgeEnvironmentInfra {
     }
 
     async fn update_environment(&self, ops: Vec<ConfigOperation>) -> anyhow::Result<()> {
+        let (session_ops, ops): (Vec<_>, Vec<_>) = ops
+            .into_iter()
+            .partition(|op| matches!(op, ConfigOperation::SetSessionReasoningEffort(_)));
+        for op in session_ops {
+            if let ConfigOperation::SetSessionReasoningEffort(effort) = op {
+                *self
+                    .session_reasoning_effort
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner()) = effort.map(to_config_effort);
+            }
+        }
+        if ops.is_empty() {
+            return Ok(());
+        }
+
         // Load the global config (with defaults applied) for the update round-trip
         let mut fc = ConfigReader::default()
             .read_defaults()
@@ -141,13 +225,24 @@ impl EnvironmentInfra for ForgeEnvironmentInfra {
 
         debug!(config = ?fc, ?ops, "applying app config operations");
 
+        let mut profile = self
+            .profile
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
         for op in ops {
-            apply_config_op(&mut fc, op);
+            // Activating a profile replaces the one the session started with
+            if let ConfigOperation::SetProfile(ref name) = op {
+                profile = name.clone();
+            }
+            apply_config_op(&mut fc, op, profile.as_deref());
         }
 
         fc.write()?;
         debug!(config = ?fc, "written .forge.toml");
 
+        *self.profile.lock().unwrap_or_else(|e| e.into_inner()) = profile;
+
         // Reset cache so next get_config() re-reads the updated values from disk
         *self.cache.lock().expect("cache mutex poisoned") = None;
 
@@ -234,6 +329,7 @@ mod tests {
                 ProviderId::ANTHROPIC,
                 ModelId::new("claude-3-5-sonnet"),
             )),
+            None,
         );
 
         let actual_provider = fixture.session.as_ref().map(|s| s.provider_id.as_str());
@@ -262,6 +358,7 @@ mod tests {
                 ProviderId::ANTHROPIC,
                 ModelId::new("claude-3-5-sonnet-20241022"),
             )),
+            None,
         );
 
         let actual_provider = fixture.session.as_ref().map(|s| s.provider_id.as_str());
@@ -283,6 +380,7 @@ mod tests {
                 ProviderId::ANTHROPIC,
                 ModelId::new("claude-3-5-sonnet-20241022"),
             )),
+            None,
         );
 
         let actual_provider = fixture.session.as_ref().map(|s| s.provider_id.as_str());
@@ -291,4 +389,96 @@ mod tests {
         assert_eq!(actual_provider, Some("anthropic"));
         assert_eq!(actual_model, Some("claude-3-5-sonnet-20241022"));
     }
+
+    #[test]
+    fn test_apply_config_op_set_storage_scope() {
+        let mut fixture = ForgeConfig::default();
+
+        apply_config_op(
+            &mut fixture,
+            ConfigOperation::SetStorageScope(forge_domain::StorageScope::Workspace),
+            None,
+        );
+
+        let actual = fixture.storage;
+        let expected = forge_config::StorageScope::Workspace;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_apply_config_op_set_schedule() {
+        use forge_domain::{AgentId, Schedule};
+
+        let mut fixture = ForgeConfig::default();
+        let schedule = Schedule::new("@daily".parse().unwrap(), "Triage dependency updates")
+            .agent(AgentId::new("sage"));
+
+        apply_config_op(
+            &mut fixture,
+            ConfigOperation::SetSchedule("deps".to_string(), Some(schedule)),
+            None,
+        );
+        apply_config_op(
+            &mut fixture,
+            ConfigOperation::SetSchedule("missing".to_string(), None),
+            None,
+        );
+
+        let actual = fixture.schedules;
+        let expected = BTreeMap::from([(
+            "deps".to_string(),
+            forge_config::Schedule::default()
+                .cron("@daily")
+                .prompt("Triage dependency updates")
+                .agent(Some("sage".to_string())),
+        )]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_apply_config_op_set_session_config_updates_active_profile() {
+        use forge_config::{ModelConfig as ForgeCfgModelConfig, Profile};
+        use forge_domain::{ModelConfig as DomainModelConfig, ModelId, ProviderId};
+
+        let mut fixture = ForgeConfig {
+            session: Some(ForgeCfgModelConfig::new("openai", "gpt-4")),
+            profiles: BTreeMap::from([(
+                "local".to_string(),
+                Profile::default().session(ForgeCfgModelConfig::new("ollama", "qwen3")),
+            )]),
+            ..Default::default()
+        };
+
+        apply_config_op(
+            &mut fixture,
+            ConfigOperation::SetSessionConfig(DomainModelConfig::new(
+                ProviderId::ANTHROPIC,
+                ModelId::new("claude-sonnet-4"),
+            )),
+            Some("local"),
+        );
+
+        let actual = (fixture.session, fixture.profiles["local"].session.clone());
+        let expected = (
+            Some(ForgeCfgModelConfig::new("openai", "gpt-4")),
+            Some(ForgeCfgModelConfig::new("anthropic", "claude-sonnet-4")),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_session_reasoning_effort_applies_without_writing() {
+        let fixture = ForgeEnvironmentInfra::new(PathBuf::from("/any/cwd"), ForgeConfig::default());
+
+        fixture
+            .update_environment(vec![ConfigOperation::SetSessionReasoningEffort(Some(
+                forge_domain::Effort::High,
+            ))])
+            .await
+            .unwrap();
+
+        let actual = fixture.get_config().unwrap().session_reasoning_effort;
+        let expected = Some(forge_config::Effort::High);
+        assert_eq!(actual, expected);
+    }
 }
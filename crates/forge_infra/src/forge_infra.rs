@@ -72,6 +72,7 @@ impl ForgeInfra {
 
         let http_service = Arc::new(ForgeHttpInfra::new(
             config.clone(),
+            env.kv_cache_dir(),
             file_write_service.clone(),
         ));
         let file_read_service = Arc::new(ForgeFileReadService::new());
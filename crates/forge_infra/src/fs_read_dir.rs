@@ -48,13 +48,13 @@ impl ForgeDirectoryReaderService {
                 continue;
             }
 
+            let is_dir = file.is_dir();
             let file_path = PathBuf::from(file.path);
             let absolute_path = if file_path.is_relative() {
                 directory.join(file_path)
             } else {
                 file_path
             };
-            let is_dir = absolute_path.is_dir();
             entries.push((absolute_path, is_dir));
         }
 
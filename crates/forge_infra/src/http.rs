@@ -13,14 +13,29 @@ use reqwest::{Certificate, Client, Response, StatusCode, Url};
 use reqwest_eventsource::{EventSource, RequestBuilderExt};
 use tracing::{debug, warn};
 
+use crate::http_cache::HttpCache;
+use crate::kv_storage::CacacheStorage;
+use crate::replay::ReplayServer;
+
 const VERSION: &str = match option_env!("APP_VERSION") {
     None => env!("CARGO_PKG_VERSION"),
     Some(v) => v,
 };
 
+/// Age in seconds after which a cached GET response is dropped instead of
+/// revalidated, so that stale entries do not accumulate on disk
+const HTTP_CACHE_TTL_SECS: u128 = 7 * 24 * 60 * 60;
+
+/// Total size in bytes of the cached GET responses
+const HTTP_CACHE_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
 pub struct ForgeHttpInfra<F> {
     client: Client,
     debug_requests: Option<PathBuf>,
+    cache: HttpCache<CacacheStorage>,
+    /// Server answering all requests with recorded responses, or the error
+    /// that prevented starting it
+    replay: Option<Result<ReplayServer, String>>,
     file: Arc<F>,
 }
 
@@ -35,8 +50,9 @@ fn to_reqwest_tls(tls: TlsVersion) -> reqwest::tls::Version {
 }
 
 impl<F: forge_app::FileWriterInfra + 'static> ForgeHttpInfra<F> {
-    /// Creates a new [`ForgeHttpInfra`] from a resolved [`ForgeConfig`].
-    pub fn new(config: ForgeConfig, file_writer: Arc<F>) -> Self {
+    /// Creates a new [`ForgeHttpInfra`] from a resolved [`ForgeConfig`],
+    /// caching GET responses under `cache_dir`.
+    pub fn new(config: ForgeConfig, cache_dir: PathBuf, file_writer: Arc<F>) -> Self {
         let http = config.http.unwrap_or(forge_config::HttpConfig {
             connect_timeout_secs: 30,
             read_timeout_secs: 900,
@@ -113,18 +129,73 @@ impl<F: forge_app::FileWriterInfra + 'static> ForgeHttpInfra<F> {
             TlsBackend::Default => {}
         }
 
+        let replay = config
+            .replay_responses
+            .as_deref()
+            .map(|path| ReplayServer::start(path).map_err(|e| format!("{e:#}")));
+
         Self {
             debug_requests: config.debug_requests,
             client: client.build().unwrap(),
+            cache: HttpCache::new(
+                CacacheStorage::new(cache_dir, Some(HTTP_CACHE_TTL_SECS))
+                    .with_namespace("http")
+                    .with_max_size(HTTP_CACHE_MAX_SIZE),
+            ),
+            replay,
             file: file_writer,
         }
     }
 
+    /// The URL a request to `url` is sent to: the replay server when replaying
+    /// recorded responses, otherwise `url` itself
+    fn target(&self, url: &Url) -> anyhow::Result<Url> {
+        match &self.replay {
+            None => Ok(url.clone()),
+            Some(Ok(server)) => Ok(server.redirect(url)),
+            Some(Err(error)) => Err(anyhow::anyhow!("{error}"))
+                .with_context(|| format!("Cannot replay recorded response for {url}")),
+        }
+    }
+
+    /// Sends a GET request, made conditional when a cached response with an
+    /// `ETag` or `Last-Modified` validator exists. The cached response is
+    /// returned when the server reports it unchanged or cannot be reached.
     async fn get(&self, url: &Url, headers: Option<HeaderMap>) -> anyhow::Result<Response> {
-        self.execute_request("GET", url, |client| {
-            client.get(url.clone()).headers(self.headers(headers))
-        })
-        .await
+        let url = &self.target(url)?;
+        let headers = self.headers(headers);
+        let cached = self.cache.get(url, &headers).await;
+
+        let mut request_headers = headers.clone();
+        if let Some(cached) = &cached {
+            request_headers.extend(cached.conditional_headers());
+        }
+        let response = match self
+            .client
+            .get(url.clone())
+            .headers(request_headers)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(error) => match cached {
+                Some(cached) => {
+                    warn!(%url, error = %error, "Request failed, using cached response");
+                    return cached.into_response(url);
+                }
+                None => return Err(error).with_context(|| format_http_context(None, "GET", url)),
+            },
+        };
+
+        if response.status() == StatusCode::NOT_MODIFIED
+            && let Some(cached) = cached
+        {
+            debug!(%url, "Not modified, using cached response");
+            return cached.into_response(url);
+        }
+
+        let response = ensure_success(response, "GET", url).await?;
+        self.cache.store(url, &headers, response).await
     }
 
     async fn post(
@@ -133,6 +204,7 @@ impl<F: forge_app::FileWriterInfra + 'static> ForgeHttpInfra<F> {
         headers: Option<HeaderMap>,
         body: Bytes,
     ) -> anyhow::Result<Response> {
+        let url = &self.target(url)?;
         let mut request_headers = self.headers(headers);
         request_headers.insert("Content-Type", HeaderValue::from_static("application/json"));
 
@@ -145,6 +217,7 @@ impl<F: forge_app::FileWriterInfra + 'static> ForgeHttpInfra<F> {
     }
 
     async fn delete(&self, url: &Url) -> anyhow::Result<Response> {
+        let url = &self.target(url)?;
         self.execute_request("DELETE", url, |client| {
             client.delete(url.clone()).headers(self.headers(None))
         })
@@ -167,17 +240,7 @@ impl<F: forge_app::FileWriterInfra + 'static> ForgeHttpInfra<F> {
             .await
             .with_context(|| format_http_context(None, method, url))?;
 
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unable to read response body".to_string());
-            return Err(anyhow::anyhow!(error_body))
-                .with_context(|| format_http_context(Some(status), method, url));
-        }
-
-        Ok(response)
+        ensure_success(response, method, url).await
     }
 
     // OpenRouter optional headers ref: https://openrouter.ai/docs/api-reference/overview#headers
@@ -251,6 +314,7 @@ impl<F: forge_app::FileWriterInfra + 'static> ForgeHttpInfra<F> {
         headers: Option<HeaderMap>,
         body: Bytes,
     ) -> anyhow::Result<EventSource> {
+        let url = &self.target(url)?;
         let mut request_headers = self.headers(headers);
         request_headers.insert("Content-Type", HeaderValue::from_static("application/json"));
 
@@ -265,6 +329,21 @@ impl<F: forge_app::FileWriterInfra + 'static> ForgeHttpInfra<F> {
     }
 }
 
+/// Turns a response with a non-success status into an error carrying its body
+async fn ensure_success(response: Response, method: &str, url: &Url) -> anyhow::Result<Response> {
+    let status = response.status();
+    if !status.is_success() {
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unable to read response body".to_string());
+        return Err(anyhow::anyhow!(error_body))
+            .with_context(|| format_http_context(Some(status), method, url));
+    }
+
+    Ok(response)
+}
+
 /// Helper function to format HTTP request/response context for logging and
 /// error reporting
 fn format_http_context<U: AsRef<str>>(status: Option<StatusCode>, method: &str, url: U) -> String {
@@ -367,7 +446,12 @@ mod tests {
     async fn test_debug_requests_none_does_not_write() {
         let file_writer = MockFileWriter::new();
         let config = create_test_config(None);
-        let http = ForgeHttpInfra::new(config, Arc::new(file_writer.clone()));
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(file_writer.clone()),
+        );
 
         let body = Bytes::from("test request body");
         let url = Url::parse("https://api.test.com/messages").unwrap();
@@ -391,7 +475,12 @@ mod tests {
         let file_writer = MockFileWriter::new();
         let debug_path = PathBuf::from("/tmp/forge-test/debug.json");
         let config = create_test_config(Some(debug_path.clone()));
-        let http = ForgeHttpInfra::new(config, Arc::new(file_writer.clone()));
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(file_writer.clone()),
+        );
 
         let body = Bytes::from("test request body");
         let url = Url::parse("https://api.test.com/messages").unwrap();
@@ -414,7 +503,12 @@ mod tests {
         let file_writer = MockFileWriter::new();
         let debug_path = PathBuf::from("./debug/requests.json");
         let config = create_test_config(Some(debug_path.clone()));
-        let http = ForgeHttpInfra::new(config, Arc::new(file_writer.clone()));
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(file_writer.clone()),
+        );
 
         let body = Bytes::from("test request body");
         let url = Url::parse("https://api.test.com/messages").unwrap();
@@ -436,7 +530,12 @@ mod tests {
     async fn test_debug_requests_post_none_does_not_write() {
         let file_writer = MockFileWriter::new();
         let config = create_test_config(None);
-        let http = ForgeHttpInfra::new(config, Arc::new(file_writer.clone()));
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(file_writer.clone()),
+        );
 
         let body = Bytes::from("test request body");
         let url = Url::parse("http://127.0.0.1:9/responses").unwrap();
@@ -459,7 +558,12 @@ mod tests {
         let file_writer = MockFileWriter::new();
         let debug_path = PathBuf::from("/tmp/forge-test/debug-post.json");
         let config = create_test_config(Some(debug_path.clone()));
-        let http = ForgeHttpInfra::new(config, Arc::new(file_writer.clone()));
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(file_writer.clone()),
+        );
 
         let body = Bytes::from("test request body");
         let url = Url::parse("http://127.0.0.1:9/responses").unwrap();
@@ -488,7 +592,12 @@ mod tests {
         // (in practice, this would be a permission issue)
         let debug_path = PathBuf::from("test_debug.json");
         let config = create_test_config(Some(debug_path.clone()));
-        let http = ForgeHttpInfra::new(config, Arc::new(file_writer.clone()));
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(file_writer.clone()),
+        );
 
         let body = Bytes::from("test request body");
         let url = Url::parse("https://api.test.com/messages").unwrap();
@@ -549,4 +658,55 @@ mod tests {
             Some(&HeaderValue::from_static("application/json"))
         );
     }
+
+    #[tokio::test]
+    async fn test_replay_responses_answer_post() {
+        let replay = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(replay.path(), r#"{"body": "recorded"}"#).unwrap();
+        let config = ForgeConfig {
+            replay_responses: Some(replay.path().to_path_buf()),
+            ..Default::default()
+        };
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(MockFileWriter::new()),
+        );
+        let url = Url::parse("https://api.test.com/messages").unwrap();
+
+        let actual = http
+            .post(&url, None, Bytes::from("{}"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        let expected = "recorded";
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_replay_file_fails_requests() {
+        let config = ForgeConfig {
+            replay_responses: Some(PathBuf::from("/tmp/forge-test/missing-replay.jsonl")),
+            ..Default::default()
+        };
+        let cache_dir = tempfile::tempdir().unwrap();
+        let http = ForgeHttpInfra::new(
+            config,
+            cache_dir.path().to_path_buf(),
+            Arc::new(MockFileWriter::new()),
+        );
+        let url = Url::parse("https://api.test.com/messages").unwrap();
+
+        let actual = http.post(&url, None, Bytes::from("{}")).await.unwrap_err();
+
+        assert!(
+            actual
+                .to_string()
+                .starts_with("Cannot replay recorded response")
+        );
+    }
 }
@@ -0,0 +1,312 @@
+use anyhow::Context;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use forge_app::KVStore;
+use reqwest::header::{
+    CACHE_CONTROL, CONTENT_TYPE, ETAG, HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::{Response, ResponseBuilderExt, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// A successful GET response stored with the validators needed to revalidate
+/// it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    headers: Vec<(String, String)>,
+    /// Base64 encoded body
+    body: String,
+}
+
+impl CachedResponse {
+    /// Headers that make a request return `304 Not Modified` while the cached
+    /// version is still current
+    pub fn conditional_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(etag) = self
+            .etag
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = self
+            .last_modified
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(IF_MODIFIED_SINCE, last_modified);
+        }
+        headers
+    }
+
+    /// Rebuilds the response as if it had just been received from `url`
+    pub fn into_response(self, url: &Url) -> anyhow::Result<Response> {
+        let body = STANDARD
+            .decode(&self.body)
+            .context("Failed to decode cached response body")?;
+        let mut builder = http::Response::builder()
+            .status(StatusCode::OK)
+            .url(url.clone());
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        Ok(Response::from(builder.body(body)?))
+    }
+}
+
+/// Cache of GET responses that carry an `ETag` or `Last-Modified` validator,
+/// persisted in a [`KVStore`].
+///
+/// Cached responses are revalidated with a conditional request, so an
+/// unchanged resource costs a `304 Not Modified` instead of a full download,
+/// and are served as they are when the server cannot be reached.
+pub struct HttpCache<S> {
+    storage: S,
+}
+
+impl<S: KVStore> HttpCache<S> {
+    /// Creates a cache storing its entries in `storage`
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Key of a request; the headers are part of it so responses fetched with
+    /// different credentials are kept apart.
+    fn key<'a>(url: &'a Url, headers: &'a HeaderMap) -> (&'a str, Vec<(&'a str, &'a [u8])>) {
+        let mut headers = headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_bytes()))
+            .collect::<Vec<_>>();
+        headers.sort();
+        (url.as_str(), headers)
+    }
+
+    /// The cached response of a GET request to `url` with `headers`, if any
+    pub async fn get(&self, url: &Url, headers: &HeaderMap) -> Option<CachedResponse> {
+        self.storage
+            .cache_get(&Self::key(url, headers))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Whether `response` may be persisted: a `200 OK` with a validator that
+    /// the server does not mark as `no-store` or `private`
+    fn is_storable(response: &Response) -> bool {
+        let headers = response.headers();
+        let has_validator = headers.contains_key(ETAG) || headers.contains_key(LAST_MODIFIED);
+        let forbidden = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|directive| directive.trim().to_ascii_lowercase())
+            .any(|directive| directive == "no-store" || directive.starts_with("private"));
+        response.status() == StatusCode::OK && has_validator && !forbidden
+    }
+
+    /// Stores `response` when it is storable and returns an equivalent
+    /// response, since storing consumes its body. Only the validators and the
+    /// content type are kept, so headers such as `Set-Cookie` never reach the
+    /// disk.
+    pub async fn store(
+        &self,
+        url: &Url,
+        headers: &HeaderMap,
+        response: Response,
+    ) -> anyhow::Result<Response> {
+        let header = |name: HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        if !Self::is_storable(&response) {
+            return Ok(response);
+        }
+
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let response_headers = [ETAG, LAST_MODIFIED, CONTENT_TYPE]
+            .into_iter()
+            .filter_map(|name| Some((name.as_str().to_string(), header(name)?)))
+            .collect();
+        let body = response
+            .bytes()
+            .await
+            .with_context(|| format!("Failed to read response body of GET {url}"))?;
+        let cached = CachedResponse {
+            etag,
+            last_modified,
+            headers: response_headers,
+            body: STANDARD.encode(&body),
+        };
+
+        if let Err(error) = self
+            .storage
+            .cache_set(&Self::key(url, headers), &cached)
+            .await
+        {
+            warn!(%url, error = ?error, "Failed to cache response");
+        }
+        cached.into_response(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::kv_storage::CacacheStorage;
+
+    fn cache_fixture(dir: &tempfile::TempDir) -> HttpCache<CacacheStorage> {
+        HttpCache::new(CacacheStorage::new(dir.path().to_path_buf(), None).with_namespace("http"))
+    }
+
+    fn response_fixture(headers: &[(&str, &str)]) -> Response {
+        let mut builder = http::Response::builder().status(StatusCode::OK);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        Response::from(builder.body("{\"models\":[]}").unwrap())
+    }
+
+    fn url_fixture() -> Url {
+        Url::parse("https://openrouter.ai/api/v1/models").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_store_and_revalidate_response_with_etag() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = cache_fixture(&dir);
+        let headers = HeaderMap::new();
+
+        let response = fixture
+            .store(
+                &url_fixture(),
+                &headers,
+                response_fixture(&[("etag", "\"v1\"")]),
+            )
+            .await
+            .unwrap();
+        let cached = fixture.get(&url_fixture(), &headers).await.unwrap();
+
+        let actual = (
+            response.text().await.unwrap(),
+            cached.conditional_headers().get(IF_NONE_MATCH).cloned(),
+            cached
+                .into_response(&url_fixture())
+                .unwrap()
+                .text()
+                .await
+                .unwrap(),
+        );
+        let expected = (
+            "{\"models\":[]}".to_string(),
+            Some(HeaderValue::from_static("\"v1\"")),
+            "{\"models\":[]}".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_store_skips_response_without_validators() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = cache_fixture(&dir);
+        let headers = HeaderMap::new();
+
+        fixture
+            .store(&url_fixture(), &headers, response_fixture(&[]))
+            .await
+            .unwrap();
+
+        let actual = fixture.get(&url_fixture(), &headers).await;
+        assert_eq!(actual, None);
+    }
+
+    #[tokio::test]
+    async fn test_store_skips_no_store_and_private_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = cache_fixture(&dir);
+        let headers = HeaderMap::new();
+        let no_store = Url::parse("https://example.com/no-store").unwrap();
+        let private = Url::parse("https://example.com/private").unwrap();
+
+        fixture
+            .store(
+                &no_store,
+                &headers,
+                response_fixture(&[("etag", "\"v1\""), ("cache-control", "no-store")]),
+            )
+            .await
+            .unwrap();
+        fixture
+            .store(
+                &private,
+                &headers,
+                response_fixture(&[("etag", "\"v1\""), ("cache-control", "max-age=60, private")]),
+            )
+            .await
+            .unwrap();
+
+        let actual = (
+            fixture.get(&no_store, &headers).await,
+            fixture.get(&private, &headers).await,
+        );
+        let expected = (None, None);
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_store_keeps_only_validators_and_content_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = cache_fixture(&dir);
+        let headers = HeaderMap::new();
+
+        fixture
+            .store(
+                &url_fixture(),
+                &headers,
+                response_fixture(&[
+                    ("etag", "\"v1\""),
+                    ("content-type", "application/json"),
+                    ("set-cookie", "session=secret"),
+                ]),
+            )
+            .await
+            .unwrap();
+
+        let actual = fixture.get(&url_fixture(), &headers).await.unwrap().headers;
+        let expected = vec![
+            ("etag".to_string(), "\"v1\"".to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_responses_are_kept_apart_by_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = cache_fixture(&dir);
+        let mut other = HeaderMap::new();
+        other.insert("authorization", HeaderValue::from_static("Bearer other"));
+
+        fixture
+            .store(
+                &url_fixture(),
+                &HeaderMap::new(),
+                response_fixture(&[("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")]),
+            )
+            .await
+            .unwrap();
+
+        let actual = fixture.get(&url_fixture(), &other).await;
+        assert_eq!(actual, None);
+    }
+}
@@ -1,16 +1,26 @@
-use std::hash::Hash;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+/// Percentage of the maximum size eviction shrinks a namespace to, so that
+/// the index is listed once every many writes rather than on each of them
+const EVICTION_TARGET_PERCENT: u64 = 75;
 
 /// Wrapper for cached values with timestamp for TTL validation
 #[derive(Serialize, Deserialize)]
 struct CachedEntry<V> {
     value: V,
     timestamp: u128,
+    /// Expiry of the entry in seconds since the Unix epoch, overriding the
+    /// storage's TTL when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u128>,
 }
 
 /// Generic content-addressable key-value storage using cacache.
@@ -19,9 +29,19 @@ struct CachedEntry<V> {
 /// key-value caching with content verification. Keys are serialized to
 /// deterministic strings using hash values, and values are stored as JSON
 /// using serde_json for maximum compatibility.
+///
+/// Several storages can share a cache directory by giving each its own
+/// namespace: keys, clearing and size-bounded eviction are then scoped to the
+/// namespace. Since identical values share their content, removing an entry
+/// only deletes its content once no entry of any namespace references it.
 pub struct CacacheStorage {
     cache_dir: PathBuf,
     ttl_seconds: Option<u128>,
+    namespace: Option<String>,
+    max_size: Option<u64>,
+    /// Size of the namespace in bytes as tracked across writes, listed from
+    /// the index on the first write
+    size: Mutex<Option<u64>>,
 }
 
 impl CacacheStorage {
@@ -36,20 +56,58 @@ impl CacacheStorage {
     /// * `ttl_seconds` - Optional TTL in seconds. If provided, entries older
     ///   than this will be considered expired.
     pub fn new(cache_dir: PathBuf, ttl_seconds: Option<u128>) -> Self {
-        Self { cache_dir, ttl_seconds }
+        Self {
+            cache_dir,
+            ttl_seconds,
+            namespace: None,
+            max_size: None,
+            size: Mutex::new(None),
+        }
+    }
+
+    /// Scopes the storage to `namespace` so that it can share its cache
+    /// directory with other storages without key collisions.
+    ///
+    /// Clearing a namespaced storage only removes the entries of its
+    /// namespace, while clearing a storage without a namespace removes the
+    /// whole directory.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Bounds the total size of the stored values in bytes. Writes that
+    /// exceed it evict the least recently written entries of the namespace
+    /// until it is back under three quarters of the bound.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
     }
 
-    /// Converts a key to a deterministic cache key string using its hash value.
+    /// Converts a key to a deterministic cache key string using its hash value,
+    /// prefixed with the namespace if any. FNV is used since the keys must
+    /// stay the same across Rust releases for the cache to be reused.
     fn key_to_string<K>(&self, key: &K) -> Result<String>
     where
         K: Hash,
     {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::Hasher;
-
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = fnv_rs::Fnv64::default();
         key.hash(&mut hasher);
-        Ok(hasher.finish().to_string())
+        let hash = hasher.finish().to_string();
+        Ok(match &self.namespace {
+            Some(namespace) => format!("{namespace}:{hash}"),
+            None => hash,
+        })
+    }
+
+    /// Whether a raw cacache key belongs to this storage's namespace
+    fn owns_key(&self, key: &str) -> bool {
+        match &self.namespace {
+            Some(namespace) => key
+                .strip_prefix(namespace.as_str())
+                .is_some_and(|rest| rest.starts_with(':')),
+            None => true,
+        }
     }
 
     /// Gets the current Unix timestamp in seconds
@@ -60,15 +118,146 @@ impl CacacheStorage {
             .as_secs() as u128
     }
 
-    /// Checks if a cached entry has expired based on TTL
-    fn is_expired(&self, timestamp: u128) -> bool {
-        if let Some(ttl) = self.ttl_seconds {
-            let current = Self::get_current_timestamp();
-            current.saturating_sub(timestamp) > ttl
+    /// Checks if a cached entry has expired, preferring the entry's own
+    /// expiry over the storage's TTL
+    fn is_expired<V>(&self, entry: &CachedEntry<V>) -> bool {
+        let current = Self::get_current_timestamp();
+        if let Some(expires_at) = entry.expires_at {
+            current >= expires_at
+        } else if let Some(ttl) = self.ttl_seconds {
+            current.saturating_sub(entry.timestamp) > ttl
         } else {
             false
         }
     }
+
+    /// Lists the index entries of every namespace of the cache directory
+    async fn all_entries(&self) -> Result<Vec<cacache::Metadata>> {
+        // Nothing has been written yet
+        if !self.cache_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let cache_dir = self.cache_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            cacache::index::ls(&cache_dir).collect::<Result<Vec<_>, _>>()
+        })
+        .await
+        .context("Failed to list cache entries")?
+        .context("Failed to list cache entries")
+    }
+
+    /// Lists the index entries of this storage's namespace
+    async fn entries(&self) -> Result<Vec<cacache::Metadata>> {
+        Ok(self
+            .all_entries()
+            .await?
+            .into_iter()
+            .filter(|entry| self.owns_key(&entry.key))
+            .collect())
+    }
+
+    /// Number of entries in this storage's namespace and their combined size
+    /// in bytes, including entries that have expired but not been read since
+    pub async fn usage(&self) -> Result<(usize, u64)> {
+        let entries = self.entries().await?;
+        let size = entries.iter().map(|entry| entry.size as u64).sum();
+        Ok((entries.len(), size))
+    }
+
+    /// Removes the index entries, then the content that no remaining entry
+    /// of the cache directory references
+    async fn remove(&self, entries: Vec<cacache::Metadata>) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        for entry in &entries {
+            cacache::remove(&self.cache_dir, &entry.key)
+                .await
+                .context("Failed to remove cache entry")?;
+        }
+
+        let referenced: HashSet<cacache::Integrity> = self
+            .all_entries()
+            .await?
+            .into_iter()
+            .map(|entry| entry.integrity)
+            .collect();
+        let unreferenced: HashSet<cacache::Integrity> = entries
+            .into_iter()
+            .map(|entry| entry.integrity)
+            .filter(|integrity| !referenced.contains(integrity))
+            .collect();
+        for integrity in unreferenced {
+            cacache::remove_hash(&self.cache_dir, &integrity)
+                .await
+                .context("Failed to remove cache content")?;
+        }
+        Ok(())
+    }
+
+    /// Removes the entry stored under `key` if there is one
+    async fn remove_key(&self, key: &str) -> Result<()> {
+        let entry = cacache::metadata(&self.cache_dir, key)
+            .await
+            .context("Failed to read cache entry")?;
+        self.remove(entry.into_iter().collect()).await
+    }
+
+    /// Accounts for `written` bytes and, once the namespace exceeds the
+    /// maximum size, evicts its least recently written entries
+    async fn evict(&self, written: u64) -> Result<()> {
+        let Some(max_size) = self.max_size else {
+            return Ok(());
+        };
+
+        let mut size = self.size.lock().await;
+        let total = match *size {
+            Some(total) => total + written,
+            None => self.usage().await?.1,
+        };
+        *size = Some(total);
+        if total <= max_size {
+            return Ok(());
+        }
+
+        // Overwritten keys are counted twice, so the total is listed again
+        let mut entries = self.entries().await?;
+        let mut total: u64 = entries.iter().map(|entry| entry.size as u64).sum();
+        let target = max_size * EVICTION_TARGET_PERCENT / 100;
+        entries.sort_by_key(|entry| entry.time);
+        let mut evicted = Vec::new();
+        for entry in entries {
+            if total <= target {
+                break;
+            }
+            total = total.saturating_sub(entry.size as u64);
+            evicted.push(entry);
+        }
+        self.remove(evicted).await?;
+        *size = Some(total);
+        Ok(())
+    }
+
+    /// Serializes and writes an entry, then evicts old entries if the
+    /// storage is size-bounded
+    async fn write<K, V>(&self, key: &K, value: &V, expires_at: Option<u128>) -> Result<()>
+    where
+        K: Hash + Sync,
+        V: serde::Serialize + Sync,
+    {
+        let key_str = self.key_to_string(key)?;
+
+        let entry = CachedEntry { value, timestamp: Self::get_current_timestamp(), expires_at };
+
+        let data = serde_json::to_vec(&entry).context("Failed to serialize entry for caching")?;
+        let written = data.len() as u64;
+
+        cacache::write(&self.cache_dir, &key_str, data)
+            .await
+            .context("Failed to write to cache")?;
+
+        self.evict(written).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -86,7 +275,8 @@ impl forge_app::KVStore for CacacheStorage {
                 match serde_json::from_slice::<CachedEntry<V>>(&data) {
                     Ok(entry) => {
                         // Check if entry has expired
-                        if self.is_expired(entry.timestamp) {
+                        if self.is_expired(&entry) {
+                            let _ = self.remove_key(&key_str).await;
                             Ok(None)
                         } else {
                             Ok(Some(entry.value))
@@ -95,7 +285,7 @@ impl forge_app::KVStore for CacacheStorage {
                     Err(_) => {
                         // Failed to deserialize (likely due to format change)
                         // Clear the invalid cache entry to maintain backward compatibility
-                        let _ = cacache::remove(&self.cache_dir, &key_str).await;
+                        let _ = self.remove_key(&key_str).await;
                         Ok(None)
                     }
                 }
@@ -118,23 +308,29 @@ impl forge_app::KVStore for CacacheStorage {
         K: Hash + Sync,
         V: serde::Serialize + Sync,
     {
-        let key_str = self.key_to_string(key)?;
-
-        let entry = CachedEntry { value, timestamp: Self::get_current_timestamp() };
-
-        let data = serde_json::to_vec(&entry).context("Failed to serialize entry for caching")?;
-
-        cacache::write(&self.cache_dir, &key_str, data)
-            .await
-            .context("Failed to write to cache")?;
+        self.write(key, value, None).await
+    }
 
-        Ok(())
+    async fn cache_set_with_ttl<K, V>(&self, key: &K, value: &V, ttl: Duration) -> Result<()>
+    where
+        K: Hash + Sync,
+        V: serde::Serialize + Sync,
+    {
+        let expires_at = Self::get_current_timestamp() + ttl.as_secs() as u128;
+        self.write(key, value, Some(expires_at)).await
     }
 
     async fn cache_clear(&self) -> Result<()> {
-        cacache::clear(&self.cache_dir)
-            .await
-            .context("Failed to clear cache")?;
+        if self.namespace.is_none() {
+            cacache::clear(&self.cache_dir)
+                .await
+                .context("Failed to clear cache")?;
+            *self.size.lock().await = None;
+            return Ok(());
+        }
+
+        self.remove(self.entries().await?).await?;
+        *self.size.lock().await = None;
         Ok(())
     }
 }
@@ -260,4 +456,125 @@ mod tests {
 
         assert_eq!(result, Some(value));
     }
+
+    #[tokio::test]
+    async fn test_entry_ttl_overrides_storage_ttl() {
+        let cache_dir = test_cache_dir();
+        let cache = CacacheStorage::new(cache_dir, Some(60));
+
+        let key = TestKey { id: "test".to_string() };
+        let value = TestValue { data: "hello".to_string(), count: 42 };
+
+        cache
+            .cache_set_with_ttl(&key, &value, std::time::Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+        let result: Option<TestValue> = cache.cache_get(&key).await.unwrap();
+
+        assert_eq!(result, None);
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_do_not_collide() {
+        let cache_dir = test_cache_dir();
+        let models = CacacheStorage::new(cache_dir.clone(), None).with_namespace("models");
+        let tools = CacacheStorage::new(cache_dir, None).with_namespace("tools");
+
+        let key = TestKey { id: "test".to_string() };
+        let value = TestValue { data: "hello".to_string(), count: 42 };
+
+        models.cache_set(&key, &value).await.unwrap();
+
+        let actual: (Option<TestValue>, Option<TestValue>) = (
+            models.cache_get(&key).await.unwrap(),
+            tools.cache_get(&key).await.unwrap(),
+        );
+        let expected = (Some(value), None);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_clear_only_removes_own_namespace() {
+        let cache_dir = test_cache_dir();
+        let models = CacacheStorage::new(cache_dir.clone(), None).with_namespace("models");
+        let tools = CacacheStorage::new(cache_dir, None).with_namespace("tools");
+
+        let key = TestKey { id: "test".to_string() };
+        let value = TestValue { data: "hello".to_string(), count: 42 };
+
+        models.cache_set(&key, &value).await.unwrap();
+        tools.cache_set(&key, &value).await.unwrap();
+
+        models.cache_clear().await.unwrap();
+
+        let actual: (Option<TestValue>, Option<TestValue>) = (
+            models.cache_get(&key).await.unwrap(),
+            tools.cache_get(&key).await.unwrap(),
+        );
+        let expected = (None, Some(value));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_max_size_evicts_oldest_entries() {
+        let cache_dir = test_cache_dir();
+        let value = TestValue { data: "hello".to_string(), count: 42 };
+        let entry_size = serde_json::to_vec(&CachedEntry {
+            value: &value,
+            timestamp: CacacheStorage::get_current_timestamp(),
+            expires_at: None,
+        })
+        .unwrap()
+        .len() as u64;
+        let cache = CacacheStorage::new(cache_dir, None)
+            .with_namespace("models")
+            .with_max_size(entry_size * 3);
+
+        let keys: Vec<TestKey> = (1..=4).map(|id| TestKey { id: id.to_string() }).collect();
+        for key in &keys {
+            cache.cache_set(key, &value).await.unwrap();
+            // Index timestamps have millisecond precision
+            tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
+        }
+
+        let mut actual = Vec::new();
+        for key in &keys {
+            let result: Option<TestValue> = cache.cache_get(key).await.unwrap();
+            actual.push(result.is_some());
+        }
+        // Eviction shrinks the namespace to three quarters of its bound
+        let expected = vec![false, false, true, true];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_usage_counts_own_namespace() {
+        let cache_dir = test_cache_dir().join("kv");
+        let models = CacacheStorage::new(cache_dir.clone(), None).with_namespace("models");
+        let tools = CacacheStorage::new(cache_dir, None).with_namespace("tools");
+        let empty = models.usage().await.unwrap();
+
+        let value = TestValue { data: "hello".to_string(), count: 42 };
+        for id in ["a", "b"] {
+            models
+                .cache_set(&TestKey { id: id.to_string() }, &value)
+                .await
+                .unwrap();
+        }
+        tools
+            .cache_set(&TestKey { id: "c".to_string() }, &value)
+            .await
+            .unwrap();
+        let (entries, size) = models.usage().await.unwrap();
+
+        let actual = (empty, entries, size > 0);
+        let expected = ((0, 0), 2, true);
+        assert_eq!(actual, expected);
+    }
 }
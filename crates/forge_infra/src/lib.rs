@@ -12,10 +12,12 @@ mod fs_remove;
 mod fs_write;
 mod grpc;
 mod http;
+mod http_cache;
 mod inquire;
 mod kv_storage;
 mod mcp_client;
 mod mcp_server;
+mod replay;
 mod walker;
 
 pub use console::StdConsoleWriter;
@@ -0,0 +1,267 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tiny_http::{Header, Response, Server};
+use tracing::warn;
+
+/// A provider response recorded for replay, one per line of a replay file.
+///
+/// `method`, `path` and `contains`, a string the request body must contain,
+/// restrict the requests the response answers; a response without them
+/// answers the next request of any kind.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    contains: Option<String>,
+    #[serde(default = "default_status")]
+    status: u16,
+    #[serde(default = "default_content_type")]
+    content_type: String,
+    /// Raw body, e.g. the events of a stream; any other JSON value is sent
+    /// serialized
+    body: Value,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+fn default_content_type() -> String {
+    "text/event-stream".to_string()
+}
+
+impl RecordedResponse {
+    fn matches(&self, method: &str, path: &str, body: &str) -> bool {
+        self.method
+            .as_deref()
+            .is_none_or(|m| m.eq_ignore_ascii_case(method))
+            && self.path.as_deref().is_none_or(|p| p == path)
+            && self.contains.as_deref().is_none_or(|c| body.contains(c))
+    }
+
+    fn body(&self) -> String {
+        match &self.body {
+            Value::String(body) => body.clone(),
+            body => body.to_string(),
+        }
+    }
+}
+
+/// Local server answering requests with recorded responses, in the order they
+/// were recorded, so a session replays deterministically without a network.
+pub struct ReplayServer {
+    url: Url,
+    server: Arc<Server>,
+}
+
+impl ReplayServer {
+    /// Starts a server replaying the responses recorded in the JSON lines file
+    /// at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or the server
+    /// cannot be bound.
+    pub fn start(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read replay file {}", path.display()))?;
+        let responses = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str::<RecordedResponse>(line).with_context(|| {
+                    format!(
+                        "Invalid recorded response at {}:{}",
+                        path.display(),
+                        index + 1
+                    )
+                })
+            })
+            .collect::<anyhow::Result<VecDeque<_>>>()?;
+
+        let server = Arc::new(
+            Server::http("127.0.0.1:0")
+                .map_err(|e| anyhow::anyhow!("Failed to start replay server: {e}"))?,
+        );
+        let addr = server
+            .server_addr()
+            .to_ip()
+            .context("Replay server is not listening on an IP address")?;
+        let url = Url::parse(&format!("http://{addr}"))?;
+
+        std::thread::spawn({
+            let server = Arc::clone(&server);
+            move || serve(&server, responses)
+        });
+
+        Ok(Self { url, server })
+    }
+
+    /// `url` with its origin replaced by the replay server's
+    pub fn redirect(&self, url: &Url) -> Url {
+        let mut redirected = self.url.clone();
+        redirected.set_path(url.path());
+        redirected.set_query(url.query());
+        redirected
+    }
+}
+
+impl Drop for ReplayServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+/// Answers every request with the first remaining response that matches it,
+/// or `404 Not Found` once none does.
+fn serve(server: &Server, mut responses: VecDeque<RecordedResponse>) {
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        if let Err(error) = request.as_reader().read_to_string(&mut body) {
+            warn!(error = ?error, "Failed to read replayed request body");
+        }
+        let method = request.method().as_str().to_string();
+        let path = request
+            .url()
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let response = match responses
+            .iter()
+            .position(|response| response.matches(&method, &path, &body))
+            .and_then(|index| responses.remove(index))
+        {
+            Some(recorded) => {
+                let mut response =
+                    Response::from_string(recorded.body()).with_status_code(recorded.status);
+                if let Ok(header) = Header::from_bytes("Content-Type", recorded.content_type) {
+                    response = response.with_header(header);
+                }
+                response
+            }
+            None => {
+                warn!(%method, %path, "No recorded response left for request");
+                Response::from_string(format!("No recorded response for {method} {path}"))
+                    .with_status_code(404)
+            }
+        };
+
+        if let Err(error) = request.respond(response) {
+            warn!(error = ?error, "Failed to send recorded response");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn replay_fixture(lines: &[&str]) -> tempfile::NamedTempFile {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), lines.join("\n")).unwrap();
+        file
+    }
+
+    async fn send(
+        server: &ReplayServer,
+        method: reqwest::Method,
+        url: &str,
+        body: &'static str,
+    ) -> (u16, String) {
+        let url = server.redirect(&Url::parse(url).unwrap());
+        let response = reqwest::Client::new()
+            .request(method, url)
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        (response.status().as_u16(), response.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_replays_responses_in_order() {
+        let file = replay_fixture(&[
+            r#"{"body": "data: first\n\n"}"#,
+            r#"{"body": "data: second\n\n"}"#,
+        ]);
+        let fixture = ReplayServer::start(file.path()).unwrap();
+
+        let mut actual = vec![];
+        for _ in 0..3 {
+            actual.push(
+                send(
+                    &fixture,
+                    reqwest::Method::POST,
+                    "https://api.openai.com/v1/chat/completions",
+                    "{}",
+                )
+                .await,
+            );
+        }
+
+        let expected = vec![
+            (200, "data: first\n\n".to_string()),
+            (200, "data: second\n\n".to_string()),
+            (
+                404,
+                "No recorded response for POST /v1/chat/completions".to_string(),
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_matches_method_path_and_body() {
+        let file = replay_fixture(&[
+            r#"{"contains": "response_format", "body": "data: title\n\n"}"#,
+            r#"{"method": "POST", "path": "/v1/chat/completions", "body": "data: chat\n\n"}"#,
+            r#"{"method": "GET", "path": "/v1/models", "content_type": "application/json", "body": {"data": []}}"#,
+        ]);
+        let fixture = ReplayServer::start(file.path()).unwrap();
+
+        let models_url = "https://api.openai.com/v1/models?limit=10";
+        let chat_url = "https://api.openai.com/v1/chat/completions";
+
+        let actual = vec![
+            send(&fixture, reqwest::Method::GET, models_url, "").await,
+            send(&fixture, reqwest::Method::POST, chat_url, "{}").await,
+            send(
+                &fixture,
+                reqwest::Method::POST,
+                chat_url,
+                r#"{"response_format": {}}"#,
+            )
+            .await,
+        ];
+
+        let expected = vec![
+            (200, r#"{"data":[]}"#.to_string()),
+            (200, "data: chat\n\n".to_string()),
+            (200, "data: title\n\n".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_start_rejects_invalid_recording() {
+        let file = replay_fixture(&[r#"{"body": "ok"}"#, "not json"]);
+
+        let actual = ReplayServer::start(file.path()).map(|_| ()).unwrap_err();
+
+        assert!(actual.to_string().ends_with(":2"));
+    }
+}
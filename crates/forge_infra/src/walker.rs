@@ -1,5 +1,5 @@
 use anyhow::Result;
-use forge_app::{WalkedFile, Walker};
+use forge_app::{WalkOrder, WalkedFile, Walker};
 
 pub struct ForgeWalkerService;
 
@@ -38,13 +38,37 @@ impl ForgeWalkerService {
         if let Some(total_size) = config.max_total_size {
             walker = walker.max_total_size(total_size);
         }
-        walker = walker.skip_binary(config.skip_binary);
+        walker = walker
+            .skip_binary(config.skip_binary)
+            .follow_symlinks(config.follow_symlinks)
+            .order(match config.order {
+                WalkOrder::Traversal => forge_walker::WalkOrder::Traversal,
+                WalkOrder::LargestFirst => forge_walker::WalkOrder::LargestFirst,
+                WalkOrder::RecentlyModified => forge_walker::WalkOrder::RecentlyModified,
+            });
+        if let Some(limit) = config.limit {
+            walker = walker.limit(limit);
+        }
 
         // Execute the walker and convert results
-        let files = walker.get().await?;
-        let walked_files = files
+        let report = walker.report().await?;
+        for entry in &report.skipped {
+            tracing::debug!(
+                path = %entry.path,
+                reason = ?entry.reason,
+                "Skipped entry while walking"
+            );
+        }
+        let walked_files = report
+            .files
             .into_iter()
-            .map(|f| WalkedFile { path: f.path, file_name: f.file_name, size: f.size })
+            .map(|f| WalkedFile {
+                path: f.path,
+                file_name: f.file_name,
+                size: f.size,
+                modified: f.modified,
+                is_binary: f.is_binary,
+            })
             .collect();
 
         Ok(walked_files)
@@ -20,6 +20,9 @@ pub enum JsonRepairError {
     #[error("Invalid unicode character {chars:?} at position {position}")]
     InvalidUnicodeCharacter { chars: String, position: usize },
 
+    #[error("Repair would turn plain text into a JSON {shape}")]
+    ShapeChanged { shape: &'static str },
+
     #[error("JSON parsing error: {0}")]
     JsonError(#[from] serde_json::Error),
 }
@@ -1,7 +1,9 @@
 mod error;
 mod parser;
 mod schema_coercion;
+mod tolerant;
 
 pub use error::{JsonRepairError, Result};
 pub use parser::json_repair;
 pub use schema_coercion::coerce_to_schema;
+pub use tolerant::parse_tolerant;
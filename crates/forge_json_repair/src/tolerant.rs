@@ -0,0 +1,60 @@
+use serde_json::Value;
+
+use crate::error::{JsonRepairError, Result};
+use crate::parser::json_repair;
+
+/// Parses model output written in JSON or one of the dialects models commonly
+/// confuse with it, normalizing it to a JSON value.
+///
+/// The text is read, in order, as strict JSON, as JSON5 (unquoted keys,
+/// single quotes, trailing commas, comments), and then repaired with
+/// [`json_repair`] when it is shaped like JSON. Other text is read as a YAML
+/// mapping or sequence before falling back to repair. YAML scalars are not
+/// accepted since any plain text is a valid YAML string.
+///
+/// # Errors
+///
+/// Returns the repair error when the text cannot be read in any dialect, and
+/// [`JsonRepairError::ShapeChanged`] when repairing text that isn't shaped
+/// like JSON would turn it into an object or an array.
+pub fn parse_tolerant(text: &str) -> Result<Value> {
+    if let Ok(value) = serde_json::from_str::<Value>(text) {
+        return Ok(value);
+    }
+
+    if let Ok(value) = serde_json5::from_str::<Value>(text) {
+        return Ok(value);
+    }
+
+    let repaired = json_repair(text);
+    if repaired.is_ok() && text.trim_start().starts_with(['{', '[']) {
+        return repaired;
+    }
+
+    if let Ok(value @ (Value::Object(_) | Value::Array(_))) = serde_yml::from_str::<Value>(text)
+        && keeps_strings(&value, text)
+    {
+        return Ok(value);
+    }
+
+    match repaired? {
+        Value::Object(_) => Err(JsonRepairError::ShapeChanged { shape: "object" }),
+        Value::Array(_) => Err(JsonRepairError::ShapeChanged { shape: "array" }),
+        value => Ok(value),
+    }
+}
+
+/// Whether every string of a YAML reading appears verbatim in its source.
+///
+/// YAML folds line breaks in multi-line scalars into spaces, so a reading
+/// whose strings cannot be found in the text has rewritten them.
+fn keeps_strings(value: &Value, text: &str) -> bool {
+    match value {
+        Value::String(string) => text.contains(string.as_str()),
+        Value::Array(items) => items.iter().all(|item| keeps_strings(item, text)),
+        Value::Object(map) => map
+            .iter()
+            .all(|(key, item)| text.contains(key.as_str()) && keeps_strings(item, text)),
+        _ => true,
+    }
+}
@@ -0,0 +1,68 @@
+use forge_json_repair::{JsonRepairError, parse_tolerant};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn test_parse_tolerant_json5() {
+    let fixture = r#"{
+        // File to update
+        path: 'src/main.rs',
+        'line': 12,
+        tags: ['a', 'b',],
+    }"#;
+    let actual = parse_tolerant(fixture).unwrap();
+    let expected = json!({"path": "src/main.rs", "line": 12, "tags": ["a", "b"]});
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_parse_tolerant_yaml_mapping() {
+    let fixture = "path: src/main.rs\nrecursive: true\nexclude:\n  - target\n  - node_modules\n";
+    let actual = parse_tolerant(fixture).unwrap();
+    let expected = json!({
+        "path": "src/main.rs",
+        "recursive": true,
+        "exclude": ["target", "node_modules"]
+    });
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_parse_tolerant_yaml_sequence() {
+    let fixture = "- one\n- 2\n";
+    let actual = parse_tolerant(fixture).unwrap();
+    let expected = json!(["one", 2]);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_parse_tolerant_falls_back_to_repair() {
+    let fixture = r#"{"command": "cargo test", "cwd": "crates""#;
+    let actual = parse_tolerant(fixture).unwrap();
+    let expected = json!({"command": "cargo test", "cwd": "crates"});
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_parse_tolerant_keeps_json_scalars() {
+    let fixture = r#""plain string""#;
+    let actual = parse_tolerant(fixture).unwrap();
+    let expected = json!("plain string");
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_parse_tolerant_repairs_json_before_yaml() {
+    let fixture = "{path: \"src/main.rs\", content: \"fn main() {}\n  // done\"}";
+    let actual = parse_tolerant(fixture).unwrap();
+    let expected = json!({"path": "src/main.rs", "content": "fn main() {}\n  // done"});
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_parse_tolerant_rejects_folded_yaml() {
+    let fixture = "description: first line\n  continues here\n";
+    let actual = parse_tolerant(fixture).unwrap_err();
+    let expected = JsonRepairError::ShapeChanged { shape: "array" };
+    assert_eq!(actual.to_string(), expected.to_string());
+}
@@ -0,0 +1,150 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Context;
+
+/// Prompt body written into a new agent definition, to be replaced by the
+/// user
+const PROMPT_PLACEHOLDER: &str = "Describe here who the agent is, how it should approach its tasks and any rules it must follow.";
+
+/// Answers collected by `/agents new`, rendered into an agent definition file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentScaffold {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub model: Option<String>,
+    pub tools: Vec<String>,
+}
+
+impl AgentScaffold {
+    /// Whether `id` can name an agent: lowercase letters, digits, dashes and
+    /// underscores, starting with a letter
+    pub fn is_valid_id(id: &str) -> bool {
+        id.starts_with(|c: char| c.is_ascii_lowercase())
+            && id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    }
+
+    /// Markdown agent definition with the answers as YAML frontmatter and a
+    /// placeholder prompt as body
+    pub fn render(&self) -> String {
+        // JSON strings are valid YAML scalars and take care of quoting
+        let quote = |value: &str| serde_json::Value::from(value).to_string();
+
+        let mut lines = vec![
+            "---".to_string(),
+            format!("id: {}", quote(&self.id)),
+            format!("title: {}", quote(&self.title)),
+            format!("description: {}", quote(&self.description)),
+        ];
+        if let Some(model) = &self.model {
+            lines.push(format!("model: {}", quote(model)));
+        }
+        if !self.tools.is_empty() {
+            lines.push("tools:".to_string());
+            lines.extend(self.tools.iter().map(|tool| format!("  - {}", quote(tool))));
+        }
+        lines.push("---".to_string());
+        lines.push(String::new());
+        lines.push(PROMPT_PLACEHOLDER.to_string());
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Opens `path` in the user's editor and waits for it to close. The editor
+/// is picked the same way as in the shell plugin: `FORGE_EDITOR`, then
+/// `EDITOR`, then nano.
+pub fn open_in_editor(path: &Path) -> anyhow::Result<()> {
+    let editor = std::env::var("FORGE_EDITOR")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "nano".to_string());
+    // Editors such as `code --wait` carry their own arguments
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("nano");
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| {
+            format!("Failed to start editor: {editor} (set FORGE_EDITOR or EDITOR)")
+        })?;
+    anyhow::ensure!(status.success(), "Editor exited with {status}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_render_agent_scaffold() {
+        let fixture = AgentScaffold {
+            id: "reviewer".to_string(),
+            title: "Review pull requests".to_string(),
+            description: "Reviews diffs: points out \"risky\" changes".to_string(),
+            model: Some("claude-sonnet-4".to_string()),
+            tools: vec!["read".to_string(), "search".to_string()],
+        };
+
+        let actual = fixture.render();
+        let expected = [
+            "---",
+            "id: \"reviewer\"",
+            "title: \"Review pull requests\"",
+            "description: \"Reviews diffs: points out \\\"risky\\\" changes\"",
+            "model: \"claude-sonnet-4\"",
+            "tools:",
+            "  - \"read\"",
+            "  - \"search\"",
+            "---",
+            "",
+            PROMPT_PLACEHOLDER,
+            "",
+        ]
+        .join("\n");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_agent_scaffold_without_optional_fields() {
+        let fixture = AgentScaffold {
+            id: "notes".to_string(),
+            title: "Take notes".to_string(),
+            description: "Writes notes".to_string(),
+            ..Default::default()
+        };
+
+        let actual = fixture.render();
+        let expected = format!(
+            "---\nid: \"notes\"\ntitle: \"Take notes\"\ndescription: \"Writes notes\"\n---\n\n{PROMPT_PLACEHOLDER}\n"
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_is_valid_agent_id() {
+        let fixture = [
+            "reviewer",
+            "code-review_2",
+            "Reviewer",
+            "2fast",
+            "has space",
+            "",
+        ];
+
+        let actual = fixture
+            .iter()
+            .map(|id| AgentScaffold::is_valid_id(id))
+            .collect::<Vec<_>>();
+        let expected = vec![true, true, false, false, false, false];
+        assert_eq!(actual, expected);
+    }
+}
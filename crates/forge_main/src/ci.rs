@@ -0,0 +1,252 @@
+use anyhow::Context;
+use forge_domain::ConversationId;
+use forge_server::{ConversationUsage, StreamEvent};
+use regex::Regex;
+use serde::Serialize;
+
+/// Exit code of a run that completed and met its success criteria
+const EXIT_SUCCESS: i32 = 0;
+/// Exit code of a run that could not start or failed midway
+const EXIT_ERROR: i32 = 1;
+/// Exit code of a run that completed without meeting its success criteria
+const EXIT_FAILED: i32 = 2;
+
+/// Outcome of a `forge ci` run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CiStatus {
+    Success,
+    Failed,
+    Error,
+}
+
+impl CiStatus {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CiStatus::Success => EXIT_SUCCESS,
+            CiStatus::Failed => EXIT_FAILED,
+            CiStatus::Error => EXIT_ERROR,
+        }
+    }
+}
+
+/// Conditions a completed run must meet to succeed
+#[derive(Debug, Clone, Default)]
+pub struct CiCriteria {
+    pub fail_on_tool_error: bool,
+    /// Pattern the agent's final message must match
+    pub expect: Option<Regex>,
+}
+
+impl CiCriteria {
+    pub fn new(fail_on_tool_error: bool, expect: Option<&str>) -> anyhow::Result<Self> {
+        let expect = expect
+            .map(Regex::new)
+            .transpose()
+            .context("Invalid --expect pattern")?;
+        Ok(Self { fail_on_tool_error, expect })
+    }
+}
+
+/// Summary of a `forge ci` run, printed as the last JSON line and written to
+/// `report.json` in the output directory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "report")]
+pub struct CiReport {
+    pub status: CiStatus,
+    pub exit_code: i32,
+    pub conversation_id: Option<String>,
+    /// Last message of the agent
+    pub message: Option<String>,
+    pub tool_calls: usize,
+    pub tool_errors: usize,
+    /// Why the agent stopped before completing, when it did
+    pub interrupted: Option<String>,
+    /// Reasons the run did not meet its success criteria or failed
+    pub failures: Vec<String>,
+    pub usage: ConversationUsage,
+}
+
+/// Accumulates the events of a run to evaluate it once it ends
+#[derive(Debug, Default)]
+pub struct CiRun {
+    message: String,
+    completed: bool,
+    tool_calls: usize,
+    tool_errors: usize,
+    interrupted: Option<String>,
+}
+
+impl CiRun {
+    pub fn observe(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::Message { text, .. } => self.message.push_str(text),
+            StreamEvent::ToolCallStart { .. } => {
+                // Text before a tool call is not the final message
+                self.message.clear();
+                self.tool_calls += 1;
+            }
+            StreamEvent::ToolCallEnd { is_error: true, .. } => self.tool_errors += 1,
+            StreamEvent::Interrupt { reason } => self.interrupted = Some(reason.clone()),
+            StreamEvent::Complete => self.completed = true,
+            _ => {}
+        }
+    }
+
+    /// Builds the report of the run, `error` being the reason it could not
+    /// finish
+    pub fn finish(
+        self,
+        criteria: &CiCriteria,
+        conversation_id: Option<ConversationId>,
+        usage: ConversationUsage,
+        error: Option<String>,
+    ) -> CiReport {
+        let message = Some(self.message.trim().to_string()).filter(|message| !message.is_empty());
+
+        let mut failures = Vec::new();
+        if let Some(reason) = &self.interrupted {
+            failures.push(format!("Agent was interrupted: {reason}"));
+        } else if !self.completed && error.is_none() {
+            failures.push("Agent did not complete the task".to_string());
+        }
+        if criteria.fail_on_tool_error && self.tool_errors > 0 {
+            failures.push(format!("{} tool call(s) failed", self.tool_errors));
+        }
+        if let Some(pattern) = &criteria.expect
+            && !message
+                .as_deref()
+                .is_some_and(|message| pattern.is_match(message))
+        {
+            failures.push(format!("Final message does not match `{pattern}`"));
+        }
+
+        let status = match (&error, failures.is_empty()) {
+            (Some(_), _) => CiStatus::Error,
+            (None, true) => CiStatus::Success,
+            (None, false) => CiStatus::Failed,
+        };
+        // The error comes first as it explains every other failure
+        if let Some(error) = error {
+            failures.insert(0, error);
+        }
+
+        CiReport {
+            status,
+            exit_code: status.exit_code(),
+            conversation_id: conversation_id.map(|id| id.into_string()),
+            message,
+            tool_calls: self.tool_calls,
+            tool_errors: self.tool_errors,
+            interrupted: self.interrupted,
+            failures,
+            usage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn run_fixture(events: Vec<StreamEvent>) -> CiRun {
+        let mut run = CiRun::default();
+        for event in &events {
+            run.observe(event);
+        }
+        run
+    }
+
+    fn message(text: &str) -> StreamEvent {
+        StreamEvent::Message { text: text.to_string(), partial: true }
+    }
+
+    #[test]
+    fn test_completed_run_succeeds_with_final_message() {
+        let fixture = run_fixture(vec![
+            message("Looking into it"),
+            StreamEvent::ToolCallStart { name: "shell".to_string() },
+            StreamEvent::ToolCallEnd { name: "shell".to_string(), is_error: true },
+            message("All tests "),
+            message("pass. DONE"),
+            StreamEvent::Complete,
+        ]);
+        let criteria = CiCriteria { fail_on_tool_error: false, expect: Regex::new("DONE$").ok() };
+
+        let actual = fixture.finish(&criteria, None, ConversationUsage::default(), None);
+
+        let expected = CiReport {
+            status: CiStatus::Success,
+            exit_code: 0,
+            conversation_id: None,
+            message: Some("All tests pass. DONE".to_string()),
+            tool_calls: 1,
+            tool_errors: 1,
+            interrupted: None,
+            failures: vec![],
+            usage: ConversationUsage::default(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_unmet_criteria_fail_the_run() {
+        let fixture = run_fixture(vec![
+            StreamEvent::ToolCallStart { name: "shell".to_string() },
+            StreamEvent::ToolCallEnd { name: "shell".to_string(), is_error: true },
+            StreamEvent::Interrupt {
+                reason: "Maximum request (100) per turn achieved".to_string(),
+            },
+        ]);
+        let criteria = CiCriteria { fail_on_tool_error: true, expect: Regex::new("DONE").ok() };
+
+        let actual = fixture.finish(&criteria, None, ConversationUsage::default(), None);
+
+        let expected = (
+            CiStatus::Failed,
+            2,
+            vec![
+                "Agent was interrupted: Maximum request (100) per turn achieved".to_string(),
+                "1 tool call(s) failed".to_string(),
+                "Final message does not match `DONE`".to_string(),
+            ],
+        );
+        assert_eq!((actual.status, actual.exit_code, actual.failures), expected);
+    }
+
+    #[test]
+    fn test_error_takes_precedence() {
+        let fixture = run_fixture(vec![message("Starting")]);
+
+        let actual = fixture.finish(
+            &CiCriteria::default(),
+            None,
+            ConversationUsage::default(),
+            Some("Provider request failed".to_string()),
+        );
+
+        let expected = (
+            CiStatus::Error,
+            1,
+            vec!["Provider request failed".to_string()],
+        );
+        assert_eq!((actual.status, actual.exit_code, actual.failures), expected);
+    }
+
+    #[test]
+    fn test_report_serializes_with_type_tag() {
+        let fixture = run_fixture(vec![StreamEvent::Complete]).finish(
+            &CiCriteria::default(),
+            None,
+            ConversationUsage::default(),
+            None,
+        );
+
+        let actual = serde_json::to_value(&fixture).unwrap();
+
+        assert_eq!(actual["type"], "report");
+        assert_eq!(actual["status"], "success");
+    }
+}
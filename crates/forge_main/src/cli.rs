@@ -5,10 +5,13 @@
 //! remains compatible. The plugin at `shell-plugin/forge.plugin.zsh` implements
 //! shell completion and command shortcuts that depend on the CLI structure.
 
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand, ValueEnum};
-use forge_domain::{AgentId, ConversationId, Effort, ModelId, ProviderId};
+use forge_domain::{
+    AgentId, ConversationId, Effort, ModelId, PackSource, ProviderId, StorageScope,
+};
 
 #[derive(Parser)]
 #[command(version = env!("CARGO_PKG_VERSION"))]
@@ -50,14 +53,32 @@ pub struct Cli {
     #[arg(long)]
     pub sandbox: Option<String>,
 
+    /// Subproject of a monorepo to scope the session to, by name or path.
+    ///
+    /// File discovery, search and relative policy paths are restricted to the
+    /// project's directory. See `forge list projects`.
+    #[arg(long)]
+    pub project: Option<String>,
+
     /// Enable verbose logging output.
     #[arg(long, default_value_t = false)]
     pub verbose: bool,
 
+    /// Output format of a prompt given with `--prompt` or piped input.
+    ///
+    /// `ndjson` prints one versioned JSON event per line on stdout for
+    /// wrapper tools, while progress meant for humans goes to stderr.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+
     /// Agent ID to use for this session.
     #[arg(long, alias = "aid")]
     pub agent: Option<AgentId>,
 
+    /// Profile to use for this session instead of the saved one.
+    #[arg(long)]
+    pub profile: Option<String>,
+
     /// Top-level subcommands.
     #[command(subcommand)]
     pub subcommands: Option<TopLevelCommand>,
@@ -82,6 +103,9 @@ pub enum TopLevelCommand {
     /// Manage agents.
     Agent(AgentCommandGroup),
 
+    /// Manage skills.
+    Skill(SkillCommandGroup),
+
     /// Generate shell extension scripts.
     #[command(subcommand, alias = "extension")]
     Zsh(ZshCommandGroup),
@@ -103,6 +127,13 @@ pub enum TopLevelCommand {
         porcelain: bool,
     },
 
+    /// Show usage statistics derived from locally recorded telemetry.
+    Stats {
+        /// Output in machine-readable format.
+        #[arg(long)]
+        porcelain: bool,
+    },
+
     /// Get, set, or list configuration values.
     Config(ConfigCommandGroup),
 
@@ -110,9 +141,28 @@ pub enum TopLevelCommand {
     #[command(alias = "session")]
     Conversation(ConversationCommandGroup),
 
+    /// Re-render a stored conversation turn by turn, including tool calls and
+    /// diffs.
+    Replay {
+        /// Conversation ID to replay.
+        conversation_id: ConversationId,
+
+        /// Stream the replies and pause between steps as in a live session
+        /// instead of printing everything at once.
+        #[arg(long)]
+        realtime: bool,
+    },
+
+    /// Share the snapshots taken before agent edits.
+    Snapshot(SnapshotCommandGroup),
+
     /// Generate and optionally commit changes with AI-generated message
     Commit(CommitCommandGroup),
 
+    /// Open a pull request for the current branch with an AI-generated title
+    /// and description.
+    Pr(PrCommandGroup),
+
     /// Manage Model Context Protocol servers.
     Mcp(McpCommandGroup),
 
@@ -139,6 +189,10 @@ pub enum TopLevelCommand {
     #[command(subcommand)]
     Vscode(VscodeCommand),
 
+    /// Validate prompt templates.
+    #[command(subcommand, alias = "template")]
+    Templates(TemplatesCommand),
+
     /// Update forge to the latest version.
     Update(UpdateArgs),
 
@@ -147,7 +201,110 @@ pub enum TopLevelCommand {
     Setup,
 
     /// Run diagnostics on shell environment (alias for `zsh doctor`).
-    Doctor,
+    Doctor {
+        /// Write a diagnostic archive with version info, redacted config,
+        /// recent logs, panic reports and the last conversation for bug
+        /// reports.
+        #[arg(long)]
+        bundle: bool,
+
+        /// Path of the diagnostic archive, defaults to a timestamped file in
+        /// the diagnostics directory of the forge data directory.
+        #[arg(long, requires = "bundle")]
+        output: Option<PathBuf>,
+    },
+
+    /// Serve conversations over a REST API documented at `/openapi.json`.
+    Serve {
+        /// Address the server listens on.
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        addr: SocketAddr,
+    },
+
+    /// Run a prompt or workflow event non-interactively inside CI jobs.
+    Ci(CiArgs),
+
+    /// Manage prompts run on a recurring schedule.
+    Schedule(ScheduleCommandGroup),
+
+    /// Review and merge the worktrees created with `--sandbox`.
+    Sandbox(SandboxCommandGroup),
+}
+
+/// Command group for sandbox worktrees.
+#[derive(Parser, Debug, Clone)]
+pub struct SandboxCommandGroup {
+    #[command(subcommand)]
+    pub command: SandboxCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum SandboxCommand {
+    /// List sandboxes with a summary of their changes.
+    #[command(alias = "ls")]
+    List,
+
+    /// Show the changes of a sandbox since it branched off the current branch.
+    Diff {
+        /// Name of the sandbox.
+        name: String,
+    },
+
+    /// Merge a sandbox into the current branch, committing any uncommitted
+    /// changes first, then remove it.
+    Merge {
+        /// Name of the sandbox.
+        name: String,
+    },
+
+    /// Remove a sandbox and its branch without merging them.
+    Discard {
+        /// Name of the sandbox.
+        name: String,
+    },
+}
+
+/// Command group for scheduled prompts.
+#[derive(Parser, Debug, Clone)]
+pub struct ScheduleCommandGroup {
+    #[command(subcommand)]
+    pub command: ScheduleCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ScheduleCommand {
+    /// Add a schedule, replacing any schedule of the same name.
+    Add {
+        /// Name of the schedule.
+        name: String,
+
+        /// Cron expression in local time, e.g. "0 3 * * 1-5" or "@daily".
+        #[arg(long)]
+        cron: String,
+
+        /// Prompt sent to the agent on each run.
+        #[arg(long, short = 'p', allow_hyphen_values = true)]
+        prompt: String,
+
+        /// Agent that handles the prompt.
+        #[arg(long, alias = "aid")]
+        agent: Option<AgentId>,
+    },
+
+    /// List schedules and when they run next.
+    #[command(alias = "ls")]
+    List,
+
+    /// Remove a schedule.
+    #[command(alias = "rm")]
+    Remove {
+        /// Name of the schedule.
+        name: String,
+    },
+
+    /// Run the schedules in the foreground until interrupted, each run in a
+    /// new conversation as with `forge ci`.
+    Run,
 }
 
 /// Command group for custom command management.
@@ -198,6 +355,84 @@ pub enum AgentCommand {
     /// List available agents.
     #[command(alias = "ls")]
     List,
+
+    /// Show the recorded versions of a custom agent.
+    History {
+        /// Agent ID.
+        id: AgentId,
+    },
+
+    /// Show the changes between two versions of a custom agent.
+    Diff {
+        /// Agent ID.
+        id: AgentId,
+        /// Version to compare from.
+        from: u32,
+        /// Version to compare to; defaults to the latest version.
+        to: Option<u32>,
+    },
+
+    /// Restore a custom agent to a recorded version.
+    Rollback {
+        /// Agent ID.
+        id: AgentId,
+        /// Version to restore.
+        version: u32,
+    },
+
+    #[command(flatten)]
+    Pack(PackCommand),
+}
+
+/// Command group for skill management.
+#[derive(Parser, Debug, Clone)]
+pub struct SkillCommandGroup {
+    #[command(subcommand)]
+    pub command: SkillCommand,
+
+    /// Output in machine-readable format.
+    #[arg(long, global = true)]
+    pub porcelain: bool,
+}
+
+/// Skill management commands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum SkillCommand {
+    /// List available skills.
+    #[command(alias = "ls")]
+    List {
+        /// Shows only custom skills
+        #[arg(long)]
+        custom: bool,
+    },
+
+    #[command(flatten)]
+    Pack(PackCommand),
+}
+
+/// Commands for managing packs installed from git, shared by agents and
+/// skills.
+#[derive(Subcommand, Debug, Clone)]
+pub enum PackCommand {
+    /// Install a pack from a git URL or registry name (append @<version> to
+    /// pin a branch, tag or commit).
+    Add {
+        /// Git URL or registry name, e.g. github.com/org/skills-rust@v1.0.0.
+        source: PackSource,
+    },
+
+    /// Remove an installed pack.
+    #[command(alias = "rm")]
+    Remove {
+        /// Name of the installed pack.
+        name: String,
+    },
+
+    /// List installed packs.
+    Installed,
+
+    /// Verify installed packs and check their sources for updates.
+    Check,
 }
 
 /// Command group for workspace management.
@@ -366,6 +601,18 @@ pub enum ListCommand {
         #[arg(long)]
         custom: bool,
     },
+
+    /// List prompt templates overridden by files in the config directory.
+    #[command(alias = "templates")]
+    Template,
+
+    /// List profiles defined in the configuration.
+    #[command(alias = "profiles")]
+    Profile,
+
+    /// List subprojects of the monorepo in the working directory.
+    #[command(alias = "projects")]
+    Project,
 }
 
 /// Shell extension commands.
@@ -493,6 +740,17 @@ impl From<Scope> for forge_domain::Scope {
     }
 }
 
+/// Format of the output of a prompt.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum OutputFormat {
+    /// Rendered for humans.
+    #[default]
+    Text,
+    /// Newline-delimited JSON events.
+    Ndjson,
+}
+
 /// Transport protocol for communication.
 #[derive(Copy, Clone, Debug, ValueEnum)]
 #[clap(rename_all = "lower")]
@@ -525,6 +783,14 @@ pub enum ConfigCommand {
     /// List configuration values.
     List,
 
+    /// Show the effective configuration.
+    Show {
+        /// Show which source set each value: default, legacy, global,
+        /// workspace, env or profile.
+        #[arg(long)]
+        origin: bool,
+    },
+
     /// Print the path to the global config file.
     Path,
 
@@ -575,6 +841,20 @@ pub enum ConfigSetField {
         /// Effort level: none, minimal, low, medium, high, xhigh, max.
         effort: Effort,
     },
+    /// Set where conversations and snapshots are stored.
+    Storage {
+        /// Storage scope: global or workspace.
+        scope: StorageScope,
+        /// Move the workspace's existing conversations and snapshots to the
+        /// new location.
+        #[arg(long)]
+        migrate: bool,
+    },
+    /// Set the active profile.
+    Profile {
+        /// Name of a profile defined under `profiles`.
+        name: String,
+    },
 }
 
 /// Type-safe subcommands for `forge config get`.
@@ -590,6 +870,38 @@ pub enum ConfigGetField {
     Suggest,
     /// Get the reasoning effort level.
     ReasoningEffort,
+    /// Get where conversations and snapshots are stored.
+    Storage,
+    /// Get the active profile.
+    Profile,
+}
+
+/// Command group for sharing snapshots.
+#[derive(Parser, Debug, Clone)]
+pub struct SnapshotCommandGroup {
+    #[command(subcommand)]
+    pub command: SnapshotCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum SnapshotCommand {
+    /// Bundle the snapshots and current content of files, so that their
+    /// edits can be reviewed and applied on another machine.
+    Export {
+        /// Files to bundle.
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path of the bundle to write.
+        #[arg(long, short, default_value = "snapshots.tar.zst")]
+        output: PathBuf,
+    },
+
+    /// Apply a bundle to the files of the current directory.
+    Import {
+        /// Path of the bundle to apply.
+        bundle: PathBuf,
+    },
 }
 
 /// Command group for conversation management.
@@ -689,6 +1001,29 @@ pub enum ConversationCommand {
         /// New name for the conversation.
         name: String,
     },
+
+    /// Pin a conversation so retention pruning never deletes it.
+    Pin {
+        /// Conversation ID to pin.
+        id: ConversationId,
+    },
+
+    /// Unpin a conversation so it is subject to retention pruning again.
+    Unpin {
+        /// Conversation ID to unpin.
+        id: ConversationId,
+    },
+
+    /// Delete conversations that exceed the configured retention limits.
+    Prune {
+        /// Show what would be deleted without deleting anything.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output in machine-readable format.
+        #[arg(long)]
+        porcelain: bool,
+    },
 }
 
 /// Command group for provider authentication management.
@@ -727,6 +1062,20 @@ pub enum ProviderCommand {
         #[arg(long = "type", short = 't')]
         types: Vec<forge_domain::ProviderType>,
     },
+
+    /// Manage the cache of responses to requests sent with a temperature
+    /// of 0.
+    #[command(subcommand)]
+    Cache(ProviderCacheCommand),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProviderCacheCommand {
+    /// Show whether the cache is enabled, its entries and its size.
+    Info,
+
+    /// Remove every cached response.
+    Clear,
 }
 
 /// Group of Commit-related commands
@@ -736,6 +1085,11 @@ pub struct CommitCommandGroup {
     #[arg(long)]
     pub preview: bool,
 
+    /// Split the changes into several logical commits, reviewing each one
+    /// before it is committed
+    #[arg(long, conflicts_with = "preview")]
+    pub split: bool,
+
     /// Maximum git diff size in bytes (default: 100k)
     ///
     /// Limits the size of the git diff sent to the AI model. Large diffs are
@@ -760,6 +1114,31 @@ pub struct CommitCommandGroup {
     pub text: Vec<String>,
 }
 
+/// Group of pull request commands
+#[derive(Parser, Debug, Clone)]
+pub struct PrCommandGroup {
+    /// Branch to merge into (default: the default branch of the remote)
+    #[arg(long)]
+    pub base: Option<String>,
+
+    /// Remote to push the branch to and open the pull request on
+    #[arg(long, default_value = "origin")]
+    pub remote: String,
+
+    /// Print the generated title and description without opening a pull
+    /// request
+    #[arg(long)]
+    pub preview: bool,
+
+    /// Maximum git diff size in bytes (default: 100k)
+    #[arg(long = "max-diff", default_value = "100000", value_parser = clap::builder::RangedI64ValueParser::<usize>::new().range(5000..))]
+    pub max_diff_size: Option<usize>,
+
+    /// Additional text to guide the title and description, e.g. `forge pr
+    /// closes the flaky test issue`
+    pub text: Vec<String>,
+}
+
 /// Group of Data-related commands
 #[derive(Parser, Debug, Clone)]
 pub struct DataCommandGroup {
@@ -803,12 +1182,89 @@ pub enum VscodeCommand {
     InstallExtension,
 }
 
+/// Prompt template commands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum TemplatesCommand {
+    /// Render every template and agent prompt against sample data to catch
+    /// missing variables and partials.
+    Check,
+}
+
+/// Arguments of `forge ci`.
+///
+/// Every event of the run is printed to stdout as a JSON line, followed by a
+/// report of the run. The process exits with 0 on success, 1 when the run
+/// fails and 2 when it completes without meeting the success criteria.
+#[derive(Parser, Debug, Clone)]
+pub struct CiArgs {
+    /// Prompt sent to the agent; read from stdin when neither a prompt nor an
+    /// event is given.
+    #[arg(
+        long,
+        short = 'p',
+        allow_hyphen_values = true,
+        conflicts_with = "event"
+    )]
+    pub prompt: Option<String>,
+
+    /// Workflow event to dispatch in JSON format, as with `forge --event`.
+    #[arg(long, short = 'e')]
+    pub event: Option<String>,
+
+    /// Agent that handles the prompt.
+    #[arg(long, alias = "aid")]
+    pub agent: Option<AgentId>,
+
+    /// Fail the run when any tool call returns an error.
+    #[arg(long)]
+    pub fail_on_tool_error: bool,
+
+    /// Fail the run unless the agent's final message matches this regular
+    /// expression.
+    #[arg(long)]
+    pub expect: Option<String>,
+
+    /// Directory the report, event log and conversation dump are written to.
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+}
+
 /// Update command arguments.
 #[derive(Parser, Debug, Clone)]
 pub struct UpdateArgs {
     /// Skip the confirmation prompt when applying updates.
     #[arg(long, default_value_t = false)]
     pub no_confirm: bool,
+
+    /// Release channel to update from, overriding the configured one.
+    #[arg(long, value_enum)]
+    pub channel: Option<UpdateChannel>,
+
+    /// Restore the version of forge the last update replaced.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["no_confirm", "channel"])]
+    pub rollback: bool,
+}
+
+/// Release channel forge updates from.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum UpdateChannel {
+    /// Stable releases only.
+    Stable,
+    /// Beta pre-releases and stable releases.
+    Beta,
+    /// Nightly builds and all other releases.
+    Nightly,
+}
+
+impl From<UpdateChannel> for forge_config::UpdateChannel {
+    fn from(value: UpdateChannel) -> Self {
+        match value {
+            UpdateChannel::Stable => forge_config::UpdateChannel::Stable,
+            UpdateChannel::Beta => forge_config::UpdateChannel::Beta,
+            UpdateChannel::Nightly => forge_config::UpdateChannel::Nightly,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -862,6 +1318,53 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_output_format_ndjson() {
+        let fixture = Cli::parse_from(["forge", "-p", "hi", "--output-format", "ndjson"]);
+        let actual = fixture.output_format;
+        let expected = OutputFormat::Ndjson;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_pr_command() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "pr",
+            "--base",
+            "develop",
+            "mention",
+            "the",
+            "migration",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Pr(pr)) => (pr.base, pr.remote, pr.text.join(" ")),
+            _ => panic!("Expected Pr command"),
+        };
+        let expected = (
+            Some("develop".to_string()),
+            "origin".to_string(),
+            "mention the migration".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_commit_split() {
+        let fixture = Cli::parse_from(["forge", "commit", "--split"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Commit(commit)) => commit.split,
+            _ => panic!("Expected Commit command"),
+        };
+        assert!(actual);
+    }
+
+    #[test]
+    fn test_commit_split_conflicts_with_preview() {
+        let actual = Cli::try_parse_from(["forge", "commit", "--split", "--preview"]);
+        assert!(actual.is_err());
+    }
+
     #[test]
     fn test_config_set_with_provider_and_model() {
         let fixture = Cli::parse_from([
@@ -915,6 +1418,30 @@ mod tests {
         assert!(actual);
     }
 
+    #[test]
+    fn test_config_set_storage_with_migrate() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "config",
+            "set",
+            "storage",
+            "workspace",
+            "--migrate",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Config(config)) => match config.command {
+                ConfigCommand::Set(args) => match args.field {
+                    ConfigSetField::Storage { scope, migrate } => Some((scope, migrate)),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        let expected = Some((StorageScope::Workspace, true));
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_config_set_commit_with_provider_and_model() {
         let fixture = Cli::parse_from([
@@ -1038,6 +1565,44 @@ mod tests {
         assert_eq!(html, true);
     }
 
+    #[test]
+    fn test_replay_realtime() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "replay",
+            "550e8400-e29b-41d4-a716-446655440000",
+            "--realtime",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Replay { conversation_id, realtime }) => {
+                Some((conversation_id, realtime))
+            }
+            _ => None,
+        };
+        let expected = Some((
+            ConversationId::parse("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+            true,
+        ));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_snapshot_export() {
+        let fixture = Cli::parse_from(["forge", "snapshot", "export", "src/lib.rs", "README.md"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Snapshot(group)) => match group.command {
+                SnapshotCommand::Export { paths, output } => Some((paths, output)),
+                SnapshotCommand::Import { .. } => None,
+            },
+            _ => None,
+        };
+        let expected = Some((
+            vec![PathBuf::from("src/lib.rs"), PathBuf::from("README.md")],
+            PathBuf::from("snapshots.tar.zst"),
+        ));
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_conversation_retry_with_id() {
         let fixture = Cli::parse_from([
@@ -1146,6 +1711,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_conversation_prune_dry_run() {
+        let fixture = Cli::parse_from(["forge", "conversation", "prune", "--dry-run"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Conversation(conversation)) => match conversation.command {
+                ConversationCommand::Prune { dry_run, porcelain } => (dry_run, porcelain),
+                _ => panic!("Expected Prune command"),
+            },
+            _ => panic!("Expected Conversation command"),
+        };
+        let expected = (true, false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_conversation_pin() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "conversation",
+            "pin",
+            "550e8400-e29b-41d4-a716-446655440005",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Conversation(conversation)) => match conversation.command {
+                ConversationCommand::Pin { id } => id,
+                _ => ConversationId::default(),
+            },
+            _ => ConversationId::default(),
+        };
+        let expected = ConversationId::parse("550e8400-e29b-41d4-a716-446655440005").unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_skill_add_with_pinned_version() {
+        let fixture =
+            Cli::parse_from(["forge", "skill", "add", "github.com/org/skills-rust@v1.0.0"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Skill(skill)) => match skill.command {
+                SkillCommand::Pack(PackCommand::Add { source }) => source,
+                _ => panic!("Expected Skill Add command"),
+            },
+            _ => panic!("Expected Skill command"),
+        };
+        let expected = PackSource {
+            url: "https://github.com/org/skills-rust".to_string(),
+            reference: Some("v1.0.0".to_string()),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_agent_check() {
+        let fixture = Cli::parse_from(["forge", "agent", "check"]);
+        let actual = matches!(
+            fixture.subcommands,
+            Some(TopLevelCommand::Agent(AgentCommandGroup {
+                command: AgentCommand::Pack(PackCommand::Check),
+                ..
+            }))
+        );
+        assert!(actual);
+    }
+
+    #[test]
+    fn test_agent_diff_defaults_to_latest() {
+        let fixture = Cli::parse_from(["forge", "agent", "diff", "reviewer", "2"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Agent(group)) => match group.command {
+                AgentCommand::Diff { id, from, to } => Some((id, from, to)),
+                _ => None,
+            },
+            _ => None,
+        };
+        let expected = Some((AgentId::new("reviewer"), 2, None));
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_list_tools_command_with_agent() {
         let fixture = Cli::parse_from(["forge", "list", "tool", "sage"]);
@@ -1258,6 +1901,17 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_stats_command_with_porcelain() {
+        let fixture = Cli::parse_from(["forge", "stats", "--porcelain"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Stats { porcelain }) => porcelain,
+            _ => false,
+        };
+        let expected = true;
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_info_command_with_conversation_id() {
         let fixture = Cli::parse_from([
@@ -1634,6 +2288,77 @@ mod tests {
         assert_eq!(is_skill_list, true);
     }
 
+    #[test]
+    fn test_list_templates_command() {
+        let fixture = Cli::parse_from(["forge", "list", "templates"]);
+        let is_template_list = match fixture.subcommands {
+            Some(TopLevelCommand::List(list)) => matches!(list.command, ListCommand::Template),
+            _ => false,
+        };
+        assert_eq!(is_template_list, true);
+    }
+
+    #[test]
+    fn test_project_flag_and_list_projects_command() {
+        let fixture = Cli::parse_from(["forge", "--project", "web", "list", "projects"]);
+        let actual = (
+            fixture.project,
+            matches!(
+                fixture.subcommands,
+                Some(TopLevelCommand::List(list)) if matches!(list.command, ListCommand::Project)
+            ),
+        );
+        let expected = (Some("web".to_string()), true);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_profile_flag() {
+        let fixture = Cli::parse_from(["forge", "--profile", "work", "-p", "hi"]);
+        let actual = fixture.profile;
+        let expected = Some("work".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_config_set_profile_command() {
+        let fixture = Cli::parse_from(["forge", "config", "set", "profile", "local"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Config(config)) => match config.command {
+                ConfigCommand::Set(args) => match args.field {
+                    ConfigSetField::Profile { name } => Some(name),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        let expected = Some("local".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_config_show_origin_command() {
+        let fixture = Cli::parse_from(["forge", "config", "show", "--origin"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Config(config)) => {
+                matches!(config.command, ConfigCommand::Show { origin: true })
+            }
+            _ => false,
+        };
+        assert_eq!(actual, true);
+    }
+
+    #[test]
+    fn test_templates_check_command() {
+        let fixture = Cli::parse_from(["forge", "templates", "check"]);
+        let actual = matches!(
+            fixture.subcommands,
+            Some(TopLevelCommand::Templates(TemplatesCommand::Check))
+        );
+        assert_eq!(actual, true);
+    }
+
     #[test]
     fn test_list_skills_alias_command() {
         let fixture = Cli::parse_from(["forge", "list", "skills"]);
@@ -1772,10 +2497,126 @@ mod tests {
     #[test]
     fn test_doctor_alias() {
         let fixture = Cli::parse_from(["forge", "doctor"]);
-        let actual = matches!(fixture.subcommands, Some(TopLevelCommand::Doctor));
+        let actual = matches!(
+            fixture.subcommands,
+            Some(TopLevelCommand::Doctor { bundle: false, output: None })
+        );
+        assert_eq!(actual, true);
+    }
+
+    #[test]
+    fn test_doctor_bundle() {
+        let fixture = Cli::parse_from(["forge", "doctor", "--bundle"]);
+        let actual = matches!(
+            fixture.subcommands,
+            Some(TopLevelCommand::Doctor { bundle: true, output: None })
+        );
+        assert_eq!(actual, true);
+    }
+
+    #[test]
+    fn test_doctor_bundle_output() {
+        let fixture = Cli::parse_from(["forge", "doctor", "--bundle", "--output", "bug.tar.gz"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Doctor { output, .. }) => output,
+            _ => None,
+        };
+        let expected = Some(PathBuf::from("bug.tar.gz"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_doctor_output_requires_bundle() {
+        let actual = Cli::try_parse_from(["forge", "doctor", "--output", "bug.tar.gz"]).is_err();
         assert_eq!(actual, true);
     }
 
+    #[test]
+    fn test_serve_with_addr() {
+        let fixture = Cli::parse_from(["forge", "serve", "--addr", "0.0.0.0:9000"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Serve { addr }) => Some(addr),
+            _ => None,
+        };
+        let expected = Some("0.0.0.0:9000".parse().unwrap());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_ci_with_success_criteria() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "ci",
+            "-p",
+            "fix the failing test",
+            "--fail-on-tool-error",
+            "--expect",
+            "DONE",
+            "--output-dir",
+            "artifacts",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Ci(args)) => Some((
+                args.prompt,
+                args.fail_on_tool_error,
+                args.expect,
+                args.output_dir,
+            )),
+            _ => None,
+        };
+        let expected = Some((
+            Some("fix the failing test".to_string()),
+            true,
+            Some("DONE".to_string()),
+            Some(PathBuf::from("artifacts")),
+        ));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_schedule_add() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "schedule",
+            "add",
+            "deps",
+            "--cron",
+            "0 3 * * 1-5",
+            "-p",
+            "Triage dependency updates",
+            "--agent",
+            "sage",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Schedule(group)) => Some(group.command),
+            _ => None,
+        };
+        let expected = Some(ScheduleCommand::Add {
+            name: "deps".to_string(),
+            cron: "0 3 * * 1-5".to_string(),
+            prompt: "Triage dependency updates".to_string(),
+            agent: Some(AgentId::new("sage")),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_sandbox_merge() {
+        let fixture = Cli::parse_from(["forge", "sandbox", "merge", "fix-parser"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Sandbox(group)) => Some(group.command),
+            _ => None,
+        };
+        let expected = Some(SandboxCommand::Merge { name: "fix-parser".to_string() });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_ci_rejects_prompt_with_event() {
+        let actual = Cli::try_parse_from(["forge", "ci", "-p", "hi", "-e", "{}"]).is_err();
+        assert!(actual);
+    }
+
     #[test]
     fn test_install_vscode_extension() {
         let fixture = Cli::parse_from(["forge", "vscode", "install-extension"]);
@@ -1852,6 +2693,25 @@ mod tests {
         assert!(actual);
     }
 
+    #[test]
+    fn test_update_with_channel() {
+        let fixture = Cli::parse_from(["forge", "update", "--channel", "beta"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Update(args)) => {
+                args.channel.map(forge_config::UpdateChannel::from)
+            }
+            _ => panic!("Expected Update command"),
+        };
+        let expected = Some(forge_config::UpdateChannel::Beta);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_update_rollback_conflicts_with_channel() {
+        let actual = Cli::try_parse_from(["forge", "update", "--rollback", "--channel", "beta"]);
+        assert!(actual.is_err());
+    }
+
     #[test]
     fn test_update_without_no_confirm() {
         let fixture = Cli::parse_from(["forge", "update"]);
@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+/// Saves the image on the system clipboard as a PNG in `dir` and returns its
+/// path, or `None` when the clipboard holds no image.
+///
+/// Terminals only forward text when pasting and OSC 52 clipboard queries
+/// answer with text too, so the image is read through the platform clipboard
+/// instead.
+#[cfg(not(target_os = "android"))]
+pub fn save_clipboard_image(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    let image = match arboard::Clipboard::new()?.get_image() {
+        Ok(image) => image,
+        Err(arboard::Error::ContentNotAvailable) => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let png = encode_png(image.width as u32, image.height as u32, &image.bytes)?;
+
+    std::fs::create_dir_all(dir)?;
+    let name = format!(
+        "clipboard-{}.png",
+        chrono::Local::now().format("%Y%m%d-%H%M%S-%3f")
+    );
+    let path = dir.join(name);
+    std::fs::write(&path, png)?;
+    Ok(Some(path))
+}
+
+#[cfg(target_os = "android")]
+pub fn save_clipboard_image(_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    Ok(None)
+}
+
+/// Encodes 8-bit RGBA pixels, as handed out by the clipboard, into a PNG
+#[cfg(not(target_os = "android"))]
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(rgba)?;
+    writer.finish()?;
+    Ok(png)
+}
+
+#[cfg(all(test, not(target_os = "android")))]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_png_keeps_pixels() {
+        let fixture = [255, 0, 0, 255, 0, 0, 255, 128];
+
+        let png = encode_png(2, 1, &fixture).unwrap();
+        let mut reader = png::Decoder::new(std::io::Cursor::new(png))
+            .read_info()
+            .unwrap();
+        let mut actual = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut actual).unwrap();
+
+        let expected = fixture.to_vec();
+        assert_eq!(actual, expected);
+    }
+}
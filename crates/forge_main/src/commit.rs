@@ -0,0 +1,74 @@
+use forge_app::{CommitGroup, CommitPlan};
+
+use crate::info::Info;
+
+/// What to do with a group of a commit plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumIter)]
+pub enum GroupAction {
+    Commit,
+    #[strum(to_string = "Edit message")]
+    Edit,
+    Skip,
+    Abort,
+}
+
+/// Outcome of reviewing each group of a commit plan
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SplitProgress {
+    /// Groups committed so far, in commit order
+    pub committed: Vec<usize>,
+    /// Groups the user chose not to commit
+    pub skipped: Vec<usize>,
+}
+
+impl SplitProgress {
+    /// Groups to stage together with `group` so that committing the index
+    /// records exactly the changes of `group` on top of the previous commits
+    pub fn staged_for(&self, group: usize) -> Vec<usize> {
+        let mut groups = self.committed.clone();
+        groups.push(group);
+        groups
+    }
+
+    /// Groups to stage once the user is done, leaving the index as it was
+    /// minus the committed changes
+    ///
+    /// Skipped groups of a plan made from staged changes stay staged, while
+    /// those of a plan made from unstaged changes stay unstaged.
+    pub fn staged_after(&self, plan: &CommitPlan) -> Vec<usize> {
+        if plan.has_staged_files {
+            (0..plan.groups.len()).collect()
+        } else {
+            self.committed.clone()
+        }
+    }
+}
+
+/// Formats a group of a commit plan for review
+pub fn format_commit_group(index: usize, total: usize, group: &CommitGroup) -> Info {
+    let mut info = Info::new()
+        .add_title(format!("Commit {} of {total}", index + 1))
+        .add_key_value("Message", &group.message)
+        .add_key_value("Hunks", group.hunk_count().to_string())
+        .add_title("Files");
+    for file in &group.files {
+        info = info.add_value(file);
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_staged_for_includes_committed_groups() {
+        let fixture = SplitProgress { committed: vec![0, 2], skipped: vec![1] };
+
+        let actual = fixture.staged_for(3);
+        let expected = vec![0, 2, 3];
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use forge_domain::CommandArgument;
 use reedline::{Completer, Span, Suggestion};
 
 use crate::model::ForgeCommandManager;
@@ -11,10 +13,98 @@ impl CommandCompleter {
     pub fn new(command_manager: Arc<ForgeCommandManager>) -> Self {
         Self(command_manager)
     }
+
+    /// Suggests `name=` for the arguments of a custom command that are not
+    /// given yet, or the accepted values of the argument being typed
+    fn complete_arguments(&self, line: &str) -> Vec<Suggestion> {
+        let Some((name, rest)) = line.split_once(char::is_whitespace) else {
+            return vec![];
+        };
+        let Some(command) = self
+            .0
+            .list()
+            .into_iter()
+            .find(|cmd| name.strip_prefix('/') == Some(cmd.name.as_str()))
+        else {
+            return vec![];
+        };
+
+        // The word under the cursor, empty right after a space
+        let current = if rest.ends_with(char::is_whitespace) {
+            ""
+        } else {
+            rest.split_ascii_whitespace().last().unwrap_or_default()
+        };
+        let span = Span::new(line.len() - current.len(), line.len());
+
+        if let Some((name, partial)) = current.split_once('=') {
+            return command
+                .arguments
+                .iter()
+                .find(|arg| arg.name == name)
+                .into_iter()
+                .flat_map(|arg| arg.values.iter())
+                .filter(|value| value.starts_with(partial))
+                .map(|value| Suggestion {
+                    value: format!("{name}={value}"),
+                    description: None,
+                    style: None,
+                    extra: None,
+                    span,
+                    append_whitespace: true,
+                    match_indices: None,
+                    display_override: None,
+                })
+                .collect();
+        }
+
+        let given = rest
+            .split_ascii_whitespace()
+            .filter_map(|word| word.split_once('=').map(|(name, _)| name))
+            .collect::<HashSet<_>>();
+        command
+            .arguments
+            .iter()
+            .filter(|arg| !given.contains(arg.name.as_str()) && arg.name.starts_with(current))
+            .map(|arg| Suggestion {
+                value: format!("{}=", arg.name),
+                description: Some(describe_argument(arg)),
+                style: None,
+                extra: None,
+                span,
+                append_whitespace: false,
+                match_indices: None,
+                display_override: None,
+            })
+            .collect()
+    }
+}
+
+/// Description of an argument shown next to its completion, e.g.
+/// `Review depth (number, default 2)`
+fn describe_argument(arg: &CommandArgument) -> String {
+    let mut details = vec![arg.kind.to_string()];
+    if arg.required {
+        details.push("required".to_string());
+    }
+    if let Some(default) = arg.default_display() {
+        details.push(format!("default {default}"));
+    }
+    let details = details.join(", ");
+
+    match &arg.description {
+        Some(description) => format!("{description} ({details})"),
+        None => details,
+    }
 }
 
 impl Completer for CommandCompleter {
-    fn complete(&mut self, line: &str, _: usize) -> Vec<reedline::Suggestion> {
+    fn complete(&mut self, line: &str, pos: usize) -> Vec<reedline::Suggestion> {
+        let line = line.get(..pos).unwrap_or(line);
+        if line.contains(char::is_whitespace) {
+            return self.complete_arguments(line);
+        }
+
         self.0
             .list()
             .into_iter()
@@ -47,3 +137,62 @@ impl Completer for CommandCompleter {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{ArgumentType, Command};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn fixture() -> CommandCompleter {
+        let manager = ForgeCommandManager::default();
+        manager.register_all(vec![
+            Command::default()
+                .name("review")
+                .description("Review code")
+                .arguments(vec![
+                    CommandArgument::default()
+                        .name("depth")
+                        .kind(ArgumentType::Number)
+                        .default_value(serde_json::json!(2))
+                        .description("Review depth"),
+                    CommandArgument::default()
+                        .name("tone")
+                        .values(vec!["friendly".to_string(), "strict".to_string()]),
+                ]),
+        ]);
+        CommandCompleter::new(Arc::new(manager))
+    }
+
+    fn complete(line: &str) -> Vec<(String, Option<String>)> {
+        fixture()
+            .complete(line, line.len())
+            .into_iter()
+            .map(|suggestion| (suggestion.value, suggestion.description))
+            .collect()
+    }
+
+    #[test]
+    fn test_complete_arguments_not_given_yet() {
+        let actual = (complete("/review "), complete("/review depth=3 t"));
+        let expected = (
+            vec![
+                (
+                    "depth=".to_string(),
+                    Some("Review depth (number, default 2)".to_string()),
+                ),
+                ("tone=".to_string(), Some("string".to_string())),
+            ],
+            vec![("tone=".to_string(), Some("string".to_string()))],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_complete_argument_values() {
+        let actual = complete("/review tone=st");
+        let expected = vec![("tone=strict".to_string(), None)];
+        assert_eq!(actual, expected);
+    }
+}
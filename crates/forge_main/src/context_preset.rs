@@ -0,0 +1,100 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::Context;
+use forge_config::ContextPreset;
+
+/// A context preset resolved against the workspace, ready to be placed in the
+/// prompt
+#[derive(Debug, Default, PartialEq)]
+pub struct PresetContext {
+    /// Files to attach, relative to the workspace root
+    pub files: Vec<String>,
+    /// Files named by the preset that don't exist in the workspace
+    pub missing: Vec<String>,
+    /// Notes to add ahead of the attachments
+    pub memory: Vec<String>,
+}
+
+impl PresetContext {
+    /// Resolves the files and globs of a preset relative to `cwd`, keeping
+    /// each file once
+    pub fn resolve(preset: &ContextPreset, cwd: &Path) -> anyhow::Result<Self> {
+        let mut files = BTreeSet::new();
+        let mut missing = Vec::new();
+
+        for file in &preset.files {
+            if cwd.join(file).is_file() {
+                files.insert(file.clone());
+            } else {
+                missing.push(file.clone());
+            }
+        }
+
+        for pattern in &preset.globs {
+            let full = cwd.join(pattern);
+            let matches = glob::glob(&full.to_string_lossy())
+                .with_context(|| format!("Invalid glob pattern '{pattern}'"))?;
+            for path in matches.filter_map(Result::ok).filter(|path| path.is_file()) {
+                let relative = path.strip_prefix(cwd).unwrap_or(&path);
+                files.insert(relative.to_string_lossy().into_owned());
+            }
+        }
+
+        Ok(Self {
+            files: files.into_iter().collect(),
+            missing,
+            memory: preset.memory.clone(),
+        })
+    }
+
+    /// Renders the notes followed by one `@[path]` attachment per file
+    pub fn to_prompt(&self) -> String {
+        self.memory
+            .iter()
+            .cloned()
+            .chain(self.files.iter().map(|file| format!("@[{file}]")))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_preset_files_globs_and_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("web/src")).unwrap();
+        std::fs::write(dir.path().join("web/src/app.ts"), "").unwrap();
+        std::fs::write(dir.path().join("web/src/view.ts"), "").unwrap();
+        std::fs::write(dir.path().join("web/package.json"), "").unwrap();
+        let fixture = ContextPreset::default()
+            .files(vec![
+                "web/package.json".to_string(),
+                "web/gone.ts".to_string(),
+            ])
+            .globs(vec!["web/src/*.ts".to_string(), "web/*.json".to_string()])
+            .memory(vec!["Components use hooks".to_string()]);
+
+        let actual = PresetContext::resolve(&fixture, dir.path()).unwrap();
+
+        let expected = PresetContext {
+            files: vec![
+                "web/package.json".to_string(),
+                "web/src/app.ts".to_string(),
+                "web/src/view.ts".to_string(),
+            ],
+            missing: vec!["web/gone.ts".to_string()],
+            memory: vec!["Components use hooks".to_string()],
+        };
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual.to_prompt(),
+            "Components use hooks\n@[web/package.json]\n@[web/src/app.ts]\n@[web/src/view.ts]"
+        );
+    }
+}
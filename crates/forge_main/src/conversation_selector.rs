@@ -10,10 +10,45 @@ use crate::display_constants::markers;
 use crate::info::Info;
 use crate::porcelain::Porcelain;
 
+/// Number of recent prompts shown in a conversation's preview
+const PREVIEW_PROMPTS: usize = 5;
+
+/// How to go on with a conversation open in another Forge process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumIter)]
+pub enum LockedConversationAction {
+    #[strum(to_string = "Attach read-only")]
+    ReadOnly,
+    #[strum(to_string = "Start a new conversation")]
+    New,
+}
+
 /// Logic for selecting conversations from a list
 pub struct ConversationSelector;
 
 impl ConversationSelector {
+    /// Formats the details of a conversation shown beside the list: its
+    /// message counts and most recent prompts.
+    fn conversation_details(conversation: &Conversation) -> String {
+        let mut info = Info::new()
+            .add_title(conversation.title.as_deref().unwrap_or(markers::EMPTY))
+            .add_key_value("ID", conversation.id.to_string());
+
+        if let Some(context) = &conversation.context {
+            info = info
+                .add_key_value("Messages", context.total_messages().to_string())
+                .add_key_value("Tool Calls", context.tool_call_count().to_string())
+                .add_title("Recent Prompts");
+
+            let prompts = context.first_user_messages();
+            for prompt in prompts.iter().rev().take(PREVIEW_PROMPTS) {
+                let line = prompt.content().unwrap_or_default().lines().next();
+                info = info.add_value(line.unwrap_or_default().trim());
+            }
+        }
+
+        info.to_string()
+    }
+
     /// Select a conversation from the provided list using porcelain-style
     /// tabular display matching the shell plugin's `:conversation` action.
     ///
@@ -83,6 +118,7 @@ impl ConversationSelector {
         struct ConversationRow {
             conversation: Option<Conversation>,
             display: String,
+            details: String,
         }
         impl Display for ConversationRow {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -92,11 +128,20 @@ impl ConversationSelector {
 
         let mut rows: Vec<ConversationRow> = Vec::with_capacity(all_lines.len());
         // Header row (non-selectable via header_lines=1)
-        rows.push(ConversationRow { conversation: None, display: all_lines[0].to_string() });
+        rows.push(ConversationRow {
+            conversation: None,
+            display: all_lines[0].to_string(),
+            details: String::new(),
+        });
         // Data rows
         for (i, line) in all_lines.iter().skip(1).enumerate() {
+            let conversation = valid_conversations.get(i).cloned().cloned();
             rows.push(ConversationRow {
-                conversation: valid_conversations.get(i).cloned().cloned(),
+                details: conversation
+                    .as_ref()
+                    .map(Self::conversation_details)
+                    .unwrap_or_default(),
+                conversation,
                 display: line.to_string(),
             });
         }
@@ -110,6 +155,7 @@ impl ConversationSelector {
             ForgeWidget::select("Conversation", rows)
                 .with_starting_cursor(starting_cursor)
                 .with_header_lines(1)
+                .with_preview(|row| row.details.clone())
                 .prompt()
         })
         .await??
@@ -137,7 +183,12 @@ mod tests {
             title: title.map(|t| t.to_string()),
             context: None,
             metrics: Metrics::default().started_at(now),
-            metadata: MetaData { created_at: now, updated_at: Some(now) },
+            metadata: MetaData {
+                created_at: now,
+                updated_at: Some(now),
+                pinned: false,
+                owner: None,
+            },
         }
     }
 
@@ -177,4 +228,37 @@ mod tests {
 
         assert_eq!(conversations.len(), 2);
     }
+
+    #[test]
+    fn test_conversation_details_lists_recent_prompts_first() {
+        let context = forge_domain::Context::default()
+            .add_message(forge_domain::ContextMessage::user(
+                "Fix the login bug",
+                None,
+            ))
+            .add_message(forge_domain::ContextMessage::assistant(
+                "Done", None, None, None,
+            ))
+            .add_message(forge_domain::ContextMessage::user(
+                "Now add a test\nfor the fix",
+                None,
+            ));
+        let mut fixture =
+            create_test_conversation("550e8400-e29b-41d4-a716-446655440004", Some("Login bug"));
+        fixture.context = Some(context);
+
+        let details = ConversationSelector::conversation_details(&fixture);
+
+        let actual: Vec<String> = console::strip_ansi_codes(&details)
+            .lines()
+            .skip_while(|line| *line != "Recent Prompts")
+            .skip(1)
+            .map(|line| line.trim().to_string())
+            .collect();
+        let expected = vec![
+            "⦿ Now add a test".to_string(),
+            "⦿ Fix the login bug".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
 }
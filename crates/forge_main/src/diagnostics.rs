@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use forge_domain::redact;
+
+/// Directory under the forge base path where panic reports are written
+const CRASH_DIR: &str = "crashes";
+
+/// Directory every file of a diagnostic bundle is placed in
+const BUNDLE_ROOT: &str = "forge-diagnostics";
+
+/// Directory under the forge base path where diagnostic bundles are written
+const BUNDLE_DIR: &str = "diagnostics";
+
+/// Returns the directory diagnostic bundles are written to unless the user
+/// picks another path
+pub fn bundle_path(base_path: &Path) -> PathBuf {
+    base_path.join(BUNDLE_DIR)
+}
+
+/// Returns the directory panic reports are written to
+pub fn crash_path(base_path: &Path) -> PathBuf {
+    base_path.join(CRASH_DIR)
+}
+
+/// Writes a panic report so it can be included in a later diagnostic bundle
+pub fn write_panic_report(
+    base_path: &Path,
+    message: &str,
+    location: Option<String>,
+) -> std::io::Result<PathBuf> {
+    let dir = crash_path(base_path);
+    std::fs::create_dir_all(&dir)?;
+
+    let now = Utc::now();
+    let path = dir.join(format!("panic-{}.txt", now.format("%Y%m%d-%H%M%S%.3f")));
+    let report = format!(
+        "time: {}\nversion: {}\nos: {} {}\nlocation: {}\nmessage: {message}\n\n{}\n",
+        now.to_rfc3339(),
+        forge_tracker::VERSION,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        location.as_deref().unwrap_or("unknown"),
+        std::backtrace::Backtrace::force_capture(),
+    );
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Files collected for a bug report, written as a single `.tar.gz` archive
+#[derive(Default)]
+pub struct DiagnosticBundle {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl DiagnosticBundle {
+    /// Adds a file at `name` relative to the bundle root
+    pub fn add(&mut self, name: impl Into<String>, content: impl Into<Vec<u8>>) {
+        self.files.push((name.into(), content.into()));
+    }
+
+    /// Adds the redacted tail of the `limit` most recently modified files in
+    /// `dir` under `prefix`, keeping at most `max_bytes` of each file
+    pub fn add_recent_files(
+        &mut self,
+        prefix: &str,
+        dir: &Path,
+        limit: usize,
+        max_bytes: usize,
+    ) -> std::io::Result<()> {
+        if !dir.is_dir() {
+            return Ok(());
+        }
+
+        let mut files = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                metadata
+                    .is_file()
+                    .then(|| (metadata.modified().ok(), entry.path()))
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| b.0.cmp(&a.0));
+
+        for (_, path) in files.into_iter().take(limit) {
+            let content = std::fs::read(&path)?;
+            let tail = &content[content.len().saturating_sub(max_bytes)..];
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            self.add(
+                format!("{prefix}/{name}"),
+                redact(&String::from_utf8_lossy(tail)),
+            );
+        }
+        Ok(())
+    }
+
+    /// Writes the bundle as a gzip-compressed tar archive
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(path)?;
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        let mtime = Utc::now().timestamp().max(0) as u64;
+
+        for (name, content) in &self.files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(mtime);
+            builder.append_data(
+                &mut header,
+                format!("{BUNDLE_ROOT}/{name}"),
+                content.as_slice(),
+            )?;
+        }
+
+        builder.into_inner()?.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_bundle_writes_tar_gz_archive() {
+        let fixture = tempfile::tempdir().unwrap();
+        let path = fixture.path().join("bundle.tar.gz");
+        let mut bundle = DiagnosticBundle::default();
+        bundle.add("system.txt", "version: 1.0.0\n");
+        bundle.add(format!("logs/{}.log", "a".repeat(120)), "log line\n");
+
+        bundle.write(&path).unwrap();
+
+        let file = std::fs::File::open(&path).unwrap();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let actual = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let name = entry.path().unwrap().to_string_lossy().to_string();
+                let mut content = String::new();
+                entry.read_to_string(&mut content).unwrap();
+                (name, content)
+            })
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            (
+                "forge-diagnostics/system.txt".to_string(),
+                "version: 1.0.0\n".to_string(),
+            ),
+            (
+                format!("forge-diagnostics/logs/{}.log", "a".repeat(120)),
+                "log line\n".to_string(),
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use colored::Colorize;
+use forge_api::FileDiff;
+
+use crate::info::Info;
+
+/// Diffs of the files changed in the current conversation, kept so diffs
+/// too large to print can be expanded on request with `/diff <file>`
+#[derive(Debug, Default, Clone)]
+pub struct DiffHistory {
+    diffs: Vec<FileDiff>,
+}
+
+impl DiffHistory {
+    pub fn push(&mut self, diff: FileDiff) {
+        self.diffs.push(diff);
+    }
+
+    /// Whether the diff changes more lines than `max_lines`, 0 meaning no
+    /// limit
+    pub fn is_large(diff: &FileDiff, max_lines: usize) -> bool {
+        max_lines > 0 && diff.lines_added + diff.lines_removed > max_lines as u64
+    }
+
+    /// One line summary printed in place of a diff too large to show
+    pub fn collapsed(diff: &FileDiff, cwd: &Path) -> String {
+        let path = display_path(&diff.path, cwd);
+        format!(
+            "{} {} {} {}",
+            path.bold(),
+            format!("+{}", diff.lines_added).green(),
+            format!("-{}", diff.lines_removed).red(),
+            format!("(run /diff {path} to expand)").dimmed()
+        )
+    }
+
+    /// Diffs of the files whose path is or ends with `query`, in the order
+    /// they were made
+    pub fn find(&self, query: &str, cwd: &Path) -> Vec<&FileDiff> {
+        let query = Path::new(query);
+        let absolute = cwd.join(query);
+        self.diffs
+            .iter()
+            .filter(|diff| {
+                let path = Path::new(&diff.path);
+                path == absolute || path.ends_with(query)
+            })
+            .collect()
+    }
+
+    /// Files changed in the conversation with the lines added and removed in
+    /// each, or `None` when no file was changed
+    pub fn summary(&self, cwd: &Path) -> Option<Info> {
+        let mut files: Vec<(&str, u64, u64)> = Vec::new();
+        for diff in &self.diffs {
+            match files.iter_mut().find(|(path, ..)| *path == diff.path) {
+                Some((_, added, removed)) => {
+                    *added += diff.lines_added;
+                    *removed += diff.lines_removed;
+                }
+                None => files.push((&diff.path, diff.lines_added, diff.lines_removed)),
+            }
+        }
+        if files.is_empty() {
+            return None;
+        }
+
+        let added: u64 = files.iter().map(|(_, added, _)| added).sum();
+        let removed: u64 = files.iter().map(|(_, _, removed)| removed).sum();
+        let mut info = Info::new().add_title(format!(
+            "{} FILES CHANGED (+{added} -{removed})",
+            files.len()
+        ));
+        for (path, added, removed) in files {
+            info = info.add_key_value(display_path(path, cwd), format!("+{added} -{removed}"));
+        }
+        Some(info)
+    }
+}
+
+/// Path relative to the working directory when it is inside it
+fn display_path(path: &str, cwd: &Path) -> String {
+    Path::new(path)
+        .strip_prefix(cwd)
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use console::strip_ansi_codes;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn diff_fixture(path: &str, lines_added: u64, lines_removed: u64) -> FileDiff {
+        FileDiff {
+            path: path.to_string(),
+            diff: format!("diff of {path}"),
+            lines_added,
+            lines_removed,
+        }
+    }
+
+    fn history_fixture() -> DiffHistory {
+        let mut history = DiffHistory::default();
+        history.push(diff_fixture("/repo/src/lib.rs", 400, 12));
+        history.push(diff_fixture("/repo/src/main.rs", 3, 1));
+        history.push(diff_fixture("/repo/src/lib.rs", 2, 2));
+        history
+    }
+
+    #[test]
+    fn test_find_matches_relative_and_suffix_paths() {
+        let fixture = history_fixture();
+        let cwd = Path::new("/repo");
+
+        let actual = (
+            fixture.find("src/lib.rs", cwd).len(),
+            fixture.find("main.rs", cwd).len(),
+            fixture.find("ib.rs", cwd).len(),
+        );
+
+        let expected = (2, 1, 0);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_is_large() {
+        let fixture = diff_fixture("/repo/src/lib.rs", 150, 60);
+
+        assert!(DiffHistory::is_large(&fixture, 200));
+        assert!(!DiffHistory::is_large(&fixture, 210));
+        assert!(!DiffHistory::is_large(&fixture, 0));
+    }
+
+    #[test]
+    fn test_collapsed_summary() {
+        let fixture = diff_fixture("/repo/src/lib.rs", 400, 12);
+
+        let actual = DiffHistory::collapsed(&fixture, Path::new("/repo"));
+
+        let expected = "src/lib.rs +400 -12 (run /diff src/lib.rs to expand)";
+        assert_eq!(strip_ansi_codes(&actual), expected);
+    }
+
+    #[test]
+    fn test_summary_totals_per_file() {
+        let fixture = history_fixture();
+
+        let actual =
+            strip_ansi_codes(&fixture.summary(Path::new("/repo")).unwrap().to_string()).to_string();
+
+        assert!(actual.contains("2 FILES CHANGED (+405 -15)"));
+        assert!(actual.contains("+402 -14"));
+        assert!(actual.contains("+3 -1"));
+        assert!(DiffHistory::default().summary(Path::new("/repo")).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+/// Minimum time between two writes of the draft while the input keeps changing
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A draft left unsent by a previous session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedDraft {
+    pub content: String,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// Unsent content of the input editor, autosaved so a prompt being written
+/// survives a crash or an accidental Ctrl+C
+pub struct Draft {
+    path: PathBuf,
+    content: String,
+    dirty: bool,
+    last_save: Option<Instant>,
+}
+
+impl Draft {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, content: String::new(), dirty: false, last_save: None }
+    }
+
+    /// Records the current input, writing it to disk once
+    /// [`AUTOSAVE_INTERVAL`] has passed since the last write. An empty input
+    /// keeps the last one so that clearing the input by accident can still
+    /// be recovered.
+    pub fn update(&mut self, content: &str) {
+        if content.trim().is_empty() {
+            // Save what was typed before the input got cleared
+            self.flush();
+            return;
+        }
+        if self.content == content {
+            return;
+        }
+        self.content = content.to_string();
+        self.dirty = true;
+
+        let due = self
+            .last_save
+            .is_none_or(|last_save| last_save.elapsed() >= AUTOSAVE_INTERVAL);
+        if due {
+            self.flush();
+        }
+    }
+
+    /// Writes changes not yet saved to disk
+    pub fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        if let Err(error) = self.write() {
+            tracing::warn!(error = ?error, "Failed to autosave the input draft");
+        }
+        self.dirty = false;
+        self.last_save = Some(Instant::now());
+    }
+
+    /// Saves and hands back the last input, leaving the draft on disk to be
+    /// recovered by the next session
+    pub fn take(&mut self) -> String {
+        self.flush();
+        std::mem::take(&mut self.content)
+    }
+
+    /// Forgets the draft once it has been submitted or discarded
+    pub fn clear(&mut self) {
+        self.content.clear();
+        self.dirty = false;
+        if let Err(error) = std::fs::remove_file(&self.path)
+            && error.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(error = ?error, "Failed to remove the input draft");
+        }
+    }
+
+    /// The draft saved on disk, if any
+    pub fn saved(&self) -> Option<SavedDraft> {
+        let content = std::fs::read_to_string(&self.path).ok()?;
+        if content.trim().is_empty() {
+            return None;
+        }
+        let saved_at = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        Some(SavedDraft { content, saved_at })
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write through a temporary file so a crash mid-write can't leave a
+        // truncated draft behind
+        let temp = self.path.with_extension("tmp");
+        std::fs::write(&temp, &self.content)?;
+        std::fs::rename(temp, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn saved_content(draft: &Draft) -> Option<String> {
+        draft.saved().map(|saved| saved.content)
+    }
+
+    #[test]
+    fn test_update_saves_within_interval_only_on_flush() {
+        let dir = TempDir::new().unwrap();
+        let mut fixture = Draft::new(dir.path().join("draft.md"));
+
+        fixture.update("fix the");
+        fixture.update("fix the tests");
+        let before_flush = saved_content(&fixture);
+        fixture.flush();
+
+        let actual = (before_flush, saved_content(&fixture));
+        let expected = (
+            Some("fix the".to_string()),
+            Some("fix the tests".to_string()),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_empty_input_keeps_saved_draft() {
+        let dir = TempDir::new().unwrap();
+        let mut fixture = Draft::new(dir.path().join("draft.md"));
+
+        fixture.update("fix the tests");
+        fixture.update("fix the tests again");
+        fixture.update("");
+
+        let actual = (fixture.take(), saved_content(&fixture));
+        let expected = (
+            "fix the tests again".to_string(),
+            Some("fix the tests again".to_string()),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_clear_removes_saved_draft() {
+        let dir = TempDir::new().unwrap();
+        let mut fixture = Draft::new(dir.path().join("draft.md"));
+
+        fixture.update("fix the tests");
+        fixture.clear();
+
+        let actual = saved_content(&fixture);
+        let expected = None;
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,16 +1,19 @@
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent, KeyEventKind};
 use forge_api::Environment;
 use nu_ansi_term::{Color, Style};
 use reedline::{
-    ColumnarMenu, DefaultHinter, EditCommand, EditMode, Emacs, FileBackedHistory, KeyCode,
-    KeyModifiers, MenuBuilder, Prompt, PromptEditMode, Reedline, ReedlineEvent, ReedlineMenu,
-    ReedlineRawEvent, Signal, default_emacs_keybindings,
+    ColumnarMenu, DefaultHinter, EditCommand, EditMode, Emacs, ExampleHighlighter,
+    FileBackedHistory, Highlighter, HistoryItem, KeyCode, KeyModifiers, MenuBuilder, Prompt,
+    PromptEditMode, Reedline, ReedlineEvent, ReedlineMenu, ReedlineRawEvent, Signal, StyledText,
+    default_emacs_keybindings,
 };
 
+use super::clipboard::save_clipboard_image;
 use super::completer::InputCompleter;
+use super::draft::{Draft, SavedDraft};
 use super::zsh::paste::wrap_pasted_text;
 use crate::model::ForgeCommandManager;
 
@@ -20,6 +23,7 @@ const COMPLETION_MENU: &str = "completion_menu";
 
 pub struct ForgeEditor {
     editor: Reedline,
+    draft: Arc<Mutex<Draft>>,
 }
 
 pub enum ReadResult {
@@ -86,7 +90,11 @@ impl ForgeEditor {
                 .with_selected_text_style(Style::new().on(Color::White).fg(Color::Black)),
         );
 
-        let edit_mode = Box::new(ForgeEditMode::new(Self::init()));
+        let edit_mode = Box::new(ForgeEditMode::new(
+            Self::init(),
+            std::env::temp_dir().join("forge-clipboard"),
+        ));
+        let draft = Arc::new(Mutex::new(Draft::new(env.draft_path())));
 
         let editor = Reedline::create()
             .with_completer(Box::new(InputCompleter::new(env.cwd, manager)))
@@ -94,21 +102,56 @@ impl ForgeEditor {
             .with_hinter(Box::new(
                 DefaultHinter::default().with_style(Style::new().fg(Color::DarkGray)),
             ))
+            .with_highlighter(Box::new(DraftHighlighter::new(draft.clone())))
             .with_menu(ReedlineMenu::EngineCompleter(completion_menu))
             .with_edit_mode(edit_mode)
             .with_quick_completions(true)
             .with_ansi_colors(true)
             .use_bracketed_paste(true);
-        Self { editor }
+        Self { editor, draft }
     }
 
     pub fn prompt(&mut self, prompt: &dyn Prompt) -> anyhow::Result<ReadResult> {
         let signal = self.editor.read_line(prompt);
+        match &signal {
+            Ok(Signal::Success(_)) => self.draft.lock().unwrap().clear(),
+            Ok(Signal::CtrlC) => {
+                // Keep the discarded input on disk and in the history so an
+                // accidental Ctrl+C is undone by pressing Up
+                let discarded = self.draft.lock().unwrap().take();
+                if !discarded.is_empty()
+                    && let Err(error) = self
+                        .editor
+                        .history_mut()
+                        .save(HistoryItem::from_command_line(discarded.trim()))
+                {
+                    tracing::warn!(error = ?error, "Failed to keep the discarded input");
+                }
+            }
+            _ => {}
+        }
+        if matches!(signal, Ok(Signal::Success(_) | Signal::CtrlC)) {
+            // Flush the entry right away so it survives the terminal closing and
+            // is visible to other running sessions
+            if let Err(error) = self.editor.sync_history() {
+                tracing::warn!(error = ?error, "Failed to save input history");
+            }
+        }
         signal
             .map(Into::into)
             .map_err(|e| anyhow::anyhow!(ReadLineError(e)))
     }
 
+    /// Input left unsent by a previous session
+    pub fn saved_draft(&self) -> Option<SavedDraft> {
+        self.draft.lock().unwrap().saved()
+    }
+
+    /// Drops the input left unsent by a previous session
+    pub fn discard_draft(&mut self) {
+        self.draft.lock().unwrap().clear();
+    }
+
     /// Sets the buffer content to be pre-filled on the next prompt
     pub fn set_buffer(&mut self, content: String) {
         self.editor
@@ -120,21 +163,63 @@ impl ForgeEditor {
 #[error(transparent)]
 pub struct ReadLineError(std::io::Error);
 
+/// Highlighter that autosaves the input as a draft on every repaint, the
+/// only hook reedline offers into the buffer while a line is being edited
+struct DraftHighlighter {
+    inner: ExampleHighlighter,
+    draft: Arc<Mutex<Draft>>,
+}
+
+impl DraftHighlighter {
+    fn new(draft: Arc<Mutex<Draft>>) -> Self {
+        Self { inner: ExampleHighlighter::default(), draft }
+    }
+}
+
+impl Highlighter for DraftHighlighter {
+    fn highlight(&self, line: &str, cursor: usize) -> StyledText {
+        self.draft.lock().unwrap().update(line);
+        self.inner.highlight(line, cursor)
+    }
+}
+
 /// Custom edit mode that wraps Emacs and intercepts paste events.
 ///
 /// When the terminal sends a bracketed-paste (e.g. from a drag-and-drop),
 /// this mode checks whether the pasted text is an existing file path and,
 /// if so, wraps it in `@[...]` before it reaches the reedline buffer. This
 /// gives the user immediate visual feedback in the input field.
+///
+/// Images can't be pasted as text: Ctrl+V, or a paste the terminal leaves
+/// empty because the clipboard holds an image, saves the clipboard image to
+/// `clipboard_dir` and inserts it as an `@[...]` attachment.
 struct ForgeEditMode {
     inner: Emacs,
+    clipboard_dir: PathBuf,
 }
 
 impl ForgeEditMode {
     /// Creates a new `ForgeEditMode` wrapping an Emacs mode with the given
     /// keybindings.
-    fn new(keybindings: reedline::Keybindings) -> Self {
-        Self { inner: Emacs::new(keybindings) }
+    fn new(keybindings: reedline::Keybindings, clipboard_dir: PathBuf) -> Self {
+        Self { inner: Emacs::new(keybindings), clipboard_dir }
+    }
+
+    /// Inserts the clipboard image as an attachment, or `None` when the
+    /// clipboard holds no image
+    fn paste_image(&self) -> Option<ReedlineEvent> {
+        match save_clipboard_image(&self.clipboard_dir) {
+            Ok(path) => path.map(|path| {
+                ReedlineEvent::Edit(vec![EditCommand::InsertString(format!(
+                    "@[{}] ",
+                    path.display()
+                ))])
+            }),
+            Err(error) => {
+                tracing::warn!(error = ?error, "Failed to read an image from the clipboard");
+                None
+            }
+        }
     }
 }
 
@@ -144,10 +229,26 @@ impl EditMode for ForgeEditMode {
         let raw: Event = event.into();
 
         if let Event::Paste(ref body) = raw {
+            if body.trim().is_empty()
+                && let Some(event) = self.paste_image()
+            {
+                return event;
+            }
             let wrapped = wrap_pasted_text(body);
             return ReedlineEvent::Edit(vec![EditCommand::InsertString(wrapped)]);
         }
 
+        if let Event::Key(KeyEvent {
+            code: KeyCode::Char('v'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            ..
+        }) = raw
+            && let Some(event) = self.paste_image()
+        {
+            return event;
+        }
+
         // For every other event, delegate to the inner Emacs mode.
         // We need to reconstruct a ReedlineRawEvent from the crossterm Event.
         // ReedlineRawEvent implements TryFrom<Event>.
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::humanize_time;
+
+/// Longest preview of a prompt shown when picking one from the history
+const PREVIEW_CHARS: usize = 80;
+
+/// Number of prompts kept in the log, older ones are dropped
+const MAX_ENTRIES: usize = 1000;
+
+/// A prompt submitted from the input editor along with the workspace it was
+/// submitted from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub prompt: String,
+    pub cwd: PathBuf,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let first_line = self.prompt.lines().next().unwrap_or_default();
+        let mut preview = first_line.chars().take(PREVIEW_CHARS).collect::<String>();
+        if preview.len() < self.prompt.trim_end().len() {
+            preview.push('…');
+        }
+        write!(f, "{preview} ({})", humanize_time(self.timestamp))
+    }
+}
+
+/// Prompts from every session, appended as JSON lines so they survive the
+/// terminal closing and can be recalled per workspace or across all of them.
+/// Only the latest [`MAX_ENTRIES`] prompts are kept.
+pub struct PromptHistory {
+    path: PathBuf,
+    capacity: usize,
+}
+
+impl PromptHistory {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, capacity: MAX_ENTRIES }
+    }
+
+    /// Appends `prompt` as submitted from `cwd`, dropping the oldest prompts
+    /// once the log holds more than its capacity
+    pub fn record(&self, prompt: &str, cwd: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let entry = HistoryEntry {
+            prompt: prompt.to_string(),
+            cwd: cwd.to_path_buf(),
+            timestamp: Utc::now(),
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        drop(file);
+
+        self.truncate()
+    }
+
+    /// Rewrites the log with only its latest `capacity` lines
+    fn truncate(&self) -> anyhow::Result<()> {
+        let content = std::fs::read_to_string(&self.path)?;
+        let lines = content.lines().collect::<Vec<_>>();
+        if lines.len() <= self.capacity {
+            return Ok(());
+        }
+
+        let mut kept = lines[lines.len() - self.capacity..].join("\n");
+        kept.push('\n');
+        // Write through a temporary file so a crash mid-write can't lose the
+        // whole history
+        let temp = self.path.with_extension("tmp");
+        std::fs::write(&temp, kept)?;
+        std::fs::rename(temp, &self.path)?;
+        Ok(())
+    }
+
+    /// Prompts newest first without repeats, limited to those submitted from
+    /// `cwd` when given and to those containing `query` (case-insensitive)
+    pub fn search(
+        &self,
+        cwd: Option<&Path>,
+        query: Option<&str>,
+    ) -> anyhow::Result<Vec<HistoryEntry>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error.into()),
+        };
+        let query = query.map(str::to_lowercase);
+
+        let mut seen = HashSet::new();
+        Ok(content
+            .lines()
+            .rev()
+            // Skip lines torn by a crash mid-write instead of failing the lookup
+            .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+            .filter(|entry| cwd.is_none_or(|cwd| entry.cwd == cwd))
+            .filter(|entry| {
+                query
+                    .as_ref()
+                    .is_none_or(|query| entry.prompt.to_lowercase().contains(query))
+            })
+            .filter(|entry| seen.insert(entry.prompt.clone()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn prompts(entries: Vec<HistoryEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.prompt).collect()
+    }
+
+    #[test]
+    fn test_search_returns_newest_first_without_repeats() {
+        let dir = TempDir::new().unwrap();
+        let fixture = PromptHistory::new(dir.path().join("prompt_history.jsonl"));
+        let cwd = Path::new("/project");
+        fixture.record("fix the tests", cwd).unwrap();
+        fixture.record("explain the parser", cwd).unwrap();
+        fixture.record("fix the tests", cwd).unwrap();
+
+        let actual = prompts(fixture.search(None, None).unwrap());
+        let expected = vec![
+            "fix the tests".to_string(),
+            "explain the parser".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_search_filters_by_workspace_and_query() {
+        let dir = TempDir::new().unwrap();
+        let fixture = PromptHistory::new(dir.path().join("prompt_history.jsonl"));
+        fixture.record("Fix the tests", Path::new("/a")).unwrap();
+        fixture.record("fix the build", Path::new("/b")).unwrap();
+        fixture
+            .record("explain the parser", Path::new("/a"))
+            .unwrap();
+
+        let actual = (
+            prompts(fixture.search(Some(Path::new("/a")), None).unwrap()),
+            prompts(fixture.search(None, Some("FIX")).unwrap()),
+        );
+        let expected = (
+            vec![
+                "explain the parser".to_string(),
+                "Fix the tests".to_string(),
+            ],
+            vec!["fix the build".to_string(), "Fix the tests".to_string()],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_search_skips_invalid_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("prompt_history.jsonl");
+        let fixture = PromptHistory::new(path.clone());
+        fixture.record("first", Path::new("/a")).unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b"{\"prompt\":\"torn\n").unwrap();
+        fixture.record("second", Path::new("/a")).unwrap();
+
+        let actual = prompts(fixture.search(None, None).unwrap());
+        let expected = vec!["second".to_string(), "first".to_string()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_record_keeps_latest_entries() {
+        let dir = TempDir::new().unwrap();
+        let fixture = PromptHistory { path: dir.path().join("prompt_history.jsonl"), capacity: 2 };
+        let cwd = Path::new("/project");
+        fixture.record("first", cwd).unwrap();
+        fixture.record("second", cwd).unwrap();
+        fixture.record("third", cwd).unwrap();
+
+        let actual = (
+            prompts(fixture.search(None, None).unwrap()),
+            std::fs::read_to_string(&fixture.path)
+                .unwrap()
+                .lines()
+                .count(),
+        );
+        let expected = (vec!["third".to_string(), "second".to_string()], 2);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_search_without_file_is_empty() {
+        let dir = TempDir::new().unwrap();
+        let fixture = PromptHistory::new(dir.path().join("missing.jsonl"));
+
+        let actual = fixture.search(None, None).unwrap();
+        let expected: Vec<HistoryEntry> = vec![];
+        assert_eq!(actual, expected);
+    }
+}
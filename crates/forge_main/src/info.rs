@@ -3,7 +3,9 @@ use std::path::Path;
 use std::time::Duration;
 
 use colored::Colorize;
-use forge_api::{Conversation, Environment, ForgeConfig, Metrics, Role, Usage, UserUsage};
+use forge_api::{
+    Conversation, Environment, ForgeConfig, Metrics, Role, StorageScope, Usage, UserUsage,
+};
 use forge_tracker::VERSION;
 use num_format::{Locale, ToFormattedString};
 
@@ -269,8 +271,10 @@ impl<T: IntoInfoValue> IntoInfoValue for Option<T> {
     }
 }
 
-impl From<&Environment> for Info {
-    fn from(env: &Environment) -> Self {
+/// Describes the environment, with checkpoints under the configured storage
+/// scope
+impl From<(&Environment, StorageScope)> for Info {
+    fn from((env, storage): (&Environment, StorageScope)) -> Self {
         // Get the current git branch
         let branch_info = match get_git_branch() {
             Some(branch) => branch,
@@ -282,8 +286,12 @@ impl From<&Environment> for Info {
             .add_key_value("Version", VERSION)
             .add_key_value("Working Directory", format_path_for_display(env, &env.cwd))
             .add_key_value("Shell", env.shell.as_str())
-            .add_key_value("Git Branch", branch_info)
-            .add_title("PATHS");
+            .add_key_value("Git Branch", branch_info);
+        let editor = crate::vscode::detect_editor();
+        if let Some(editor) = editor {
+            info = info.add_key_value("Editor", editor.to_string());
+        }
+        info = info.add_title("PATHS");
 
         // Only show logs path if the directory exists
         let log_path = env.log_path();
@@ -300,12 +308,15 @@ impl From<&Environment> for Info {
             )
             .add_key_value(
                 "Checkpoints",
-                format_path_for_display(env, &env.snapshot_path()),
+                format_path_for_display(env, &env.snapshot_path(storage)),
             )
             .add_key_value(
                 "Policies",
                 format_path_for_display(env, &env.permissions_path()),
             );
+        if let Some(storage_path) = editor.and_then(|editor| editor.storage_path()) {
+            info = info.add_key_value("Editor Data", format_path_for_display(env, &storage_path));
+        }
 
         info
     }
@@ -399,6 +410,13 @@ impl From<&ForgeConfig> for Info {
                     .as_ref()
                     .map(|p| p.display().to_string()),
             )
+            .add_key_value(
+                "Replay Responses",
+                config
+                    .replay_responses
+                    .as_ref()
+                    .map(|p| p.display().to_string()),
+            )
             .add_key_value(
                 "Stdout Max Line Length",
                 config.max_stdout_line_chars.to_string(),
@@ -3,7 +3,9 @@ use std::sync::{Arc, Mutex};
 
 use forge_api::Environment;
 
+use crate::draft::SavedDraft;
 use crate::editor::{ForgeEditor, ReadResult};
+use crate::history::{HistoryEntry, PromptHistory};
 use crate::model::{ForgeCommandManager, SlashCommand};
 use crate::prompt::ForgePrompt;
 use crate::tracker;
@@ -12,6 +14,8 @@ use crate::tracker;
 pub struct Console {
     command: Arc<ForgeCommandManager>,
     editor: Mutex<ForgeEditor>,
+    history: PromptHistory,
+    cwd: PathBuf,
 }
 
 impl Console {
@@ -21,8 +25,10 @@ impl Console {
         custom_history_path: Option<PathBuf>,
         command: Arc<ForgeCommandManager>,
     ) -> Self {
+        let history = PromptHistory::new(env.prompt_history_path());
+        let cwd = env.cwd.clone();
         let editor = Mutex::new(ForgeEditor::new(env, custom_history_path, command.clone()));
-        Self { command, editor }
+        Self { command, editor, history, cwd }
     }
 }
 
@@ -38,12 +44,35 @@ impl Console {
                 ReadResult::Empty => continue,
                 ReadResult::Success(text) => {
                     tracker::prompt(text.clone());
+                    // Slash commands stay in the editor history only, the log keeps prompts
+                    if !text.starts_with('/')
+                        && let Err(error) = self.history.record(&text, &self.cwd)
+                    {
+                        tracing::warn!(error = ?error, "Failed to record prompt history");
+                    }
                     return self.command.parse(&text);
                 }
             }
         }
     }
 
+    /// Previously submitted prompts, newest first, from the current workspace
+    /// unless `all` is set and containing `query` when given
+    pub fn history(&self, all: bool, query: Option<&str>) -> anyhow::Result<Vec<HistoryEntry>> {
+        let cwd = (!all).then_some(self.cwd.as_path());
+        self.history.search(cwd, query)
+    }
+
+    /// Input left unsent by a previous session in this workspace
+    pub fn saved_draft(&self) -> Option<SavedDraft> {
+        self.editor.lock().unwrap().saved_draft()
+    }
+
+    /// Drops the input left unsent by a previous session
+    pub fn discard_draft(&self) {
+        self.editor.lock().unwrap().discard_draft();
+    }
+
     /// Sets the buffer content for the next prompt
     pub fn set_buffer(&self, content: String) {
         let mut editor = self.editor.lock().unwrap();
@@ -0,0 +1,12 @@
+/// Spinner hint while a pause requested with Ctrl+C waits for the tool call in
+/// progress to finish
+pub const PAUSING_HINT: &str = "· Pausing after the current tool call, Ctrl+C again to abort";
+
+/// What to do with a turn paused with Ctrl+C
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumIter)]
+pub enum PauseAction {
+    Continue,
+    #[strum(to_string = "Add guidance")]
+    Guide,
+    Abort,
+}
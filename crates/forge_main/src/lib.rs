@@ -1,15 +1,26 @@
+mod agents;
 pub mod banner;
+mod ci;
 mod cli;
+mod clipboard;
+mod commit;
 mod completer;
+mod context_preset;
 mod conversation_selector;
+pub mod diagnostics;
+mod diffs;
 mod display_constants;
+mod draft;
 mod editor;
+mod history;
 mod info;
 mod input;
+mod interrupt;
 mod model;
 mod oauth_callback;
 mod porcelain;
 mod prompt;
+mod queue;
 mod sandbox;
 mod state;
 mod stream_renderer;
@@ -20,6 +31,7 @@ pub mod tracker;
 mod ui;
 mod utils;
 mod vscode;
+mod workspaces;
 mod zsh;
 
 mod update;
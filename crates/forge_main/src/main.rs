@@ -1,13 +1,13 @@
 use std::io::Read;
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use forge_api::ForgeAPI;
+use forge_api::{API, ForgeAPI};
 use forge_config::ForgeConfig;
 use forge_domain::TitleFormat;
-use forge_main::{Cli, Sandbox, TitleDisplayExt, UI, tracker};
+use forge_main::{Cli, Sandbox, TitleDisplayExt, UI, diagnostics, tracker};
 
 /// Enables ENABLE_VIRTUAL_TERMINAL_PROCESSING on the stdout console handle.
 ///
@@ -83,6 +83,11 @@ async fn run() -> Result<()> {
         };
 
         println!("{}", TitleFormat::error(message.to_string()).display());
+        let _ = diagnostics::write_panic_report(
+            &forge_config::ConfigReader::base_path(),
+            &message,
+            panic_info.location().map(|location| location.to_string()),
+        );
         tracker::error_blocking(message);
         std::process::exit(1);
     }));
@@ -100,10 +105,11 @@ async fn run() -> Result<()> {
         }
     }
 
-    // Read and validate configuration at startup so any errors are surfaced
-    // immediately rather than silently falling back to defaults at runtime.
-    let config =
-        ForgeConfig::read().context("Failed to read Forge configuration from .forge.toml")?;
+    // Files in the config directory replace the embedded prompt templates of the
+    // same name
+    forge_app::TemplateEngine::set_override_dir(
+        forge_config::ConfigReader::base_path().join("templates"),
+    );
 
     // Handle worktree creation if specified
     let cwd: PathBuf = match (&cli.sandbox, &cli.directory) {
@@ -120,9 +126,46 @@ async fn run() -> Result<()> {
         (_, _) => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
     };
 
-    let mut ui = UI::init(cli, config, move |config| {
-        ForgeAPI::init(cwd.clone(), config)
-    })?;
+    // Read and validate configuration at startup so any errors are surfaced
+    // immediately rather than silently falling back to defaults at runtime. The
+    // workspace config is the one of the directory the session runs in.
+    // A profile given with --profile applies to this session only and is not
+    // saved.
+    let config = ForgeConfig::read(&cwd, cli.profile.as_deref())
+        .context("Failed to read Forge configuration from .forge.toml")?;
+
+    // Scope the session to a subproject of a monorepo
+    let (cwd, config) = match &cli.project {
+        Some(project) => {
+            let projects = ForgeAPI::init(cwd.clone(), config.clone())
+                .get_projects()
+                .await?;
+            let Some(found) = projects
+                .iter()
+                .find(|found| found.name == *project || found.path == Path::new(project))
+            else {
+                let names = projects
+                    .iter()
+                    .map(|project| project.name.as_str())
+                    .collect::<Vec<_>>();
+                anyhow::bail!(
+                    "Project '{project}' not found. Available projects: {}",
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                );
+            };
+            let cwd = cwd.join(&found.path);
+            let config = ForgeConfig::read(&cwd, cli.profile.as_deref())
+                .context("Failed to read Forge configuration from .forge.toml")?;
+            (cwd, config)
+        }
+        None => (cwd, config),
+    };
+
+    let mut ui = UI::init(cli, config, cwd, ForgeAPI::init)?;
     ui.run().await;
 
     Ok(())
@@ -1,7 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use forge_api::{AgentInfo, Model, Template};
-use forge_domain::UserCommand;
+use forge_domain::{CommandArgument, Effort, UserCommand};
 use strum::{EnumProperty, IntoEnumIterator};
 use strum_macros::{EnumIter, EnumProperty};
 
@@ -45,6 +45,8 @@ pub struct ForgeCommand {
     pub name: String,
     pub description: String,
     pub value: Option<String>,
+    /// Arguments declared by a custom command
+    pub arguments: Vec<CommandArgument>,
 }
 
 #[derive(Debug)]
@@ -101,6 +103,13 @@ impl ForgeCommandManager {
                 | "commit"
                 | "rename"
                 | "rn"
+                | "diff"
+                | "profile"
+                | "history"
+                | "agents"
+                | "instructions"
+                | "title"
+                | "queue"
         )
     }
 
@@ -115,6 +124,7 @@ impl ForgeCommandManager {
                 name: command.name().to_string(),
                 description: command.usage().to_string(),
                 value: None,
+                arguments: vec![],
             })
             .collect::<Vec<_>>()
     }
@@ -132,7 +142,7 @@ impl ForgeCommandManager {
             let description = format!("⚙ {}", cmd.description);
             let value = cmd.prompt.clone();
 
-            ForgeCommand { name, description, value }
+            ForgeCommand { name, description, value, arguments: cmd.arguments }
         });
 
         guard.extend(new_commands);
@@ -174,6 +184,7 @@ impl ForgeCommandManager {
                 name: command_name,
                 description,
                 value: Some(agent_id_str.to_string()),
+                arguments: vec![],
             });
 
             result.registered_count += 1;
@@ -276,6 +287,59 @@ impl ForgeCommandManager {
             "/provider" | "/login" => Ok(SlashCommand::Login),
             "/tools" => Ok(SlashCommand::Tools),
             "/agent" => Ok(SlashCommand::Agent),
+            "/agents" => {
+                let id = parameters.get(1).map(|id| id.to_string());
+                match parameters.first().copied() {
+                    None | Some("list") => Ok(SlashCommand::Agents(AgentsCommand::List)),
+                    Some("show") => Ok(SlashCommand::Agents(AgentsCommand::Show(id))),
+                    Some("new") => Ok(SlashCommand::Agents(AgentsCommand::New)),
+                    Some("edit") => Ok(SlashCommand::Agents(AgentsCommand::Edit(id))),
+                    Some(action) => Err(anyhow::anyhow!(
+                        "Unknown action '{action}'. Usage: /agents [list|show <id>|new|edit <id>]"
+                    )),
+                }
+            }
+            "/queue" => {
+                const USAGE: &str = "Usage: /queue [list|edit <n>|cancel <n>|clear|send]";
+                let position = || {
+                    parameters
+                        .get(1)
+                        .and_then(|position| position.parse::<usize>().ok())
+                        .ok_or_else(|| anyhow::anyhow!("Missing queued message number. {USAGE}"))
+                };
+                match parameters.first().copied() {
+                    None | Some("list") => Ok(SlashCommand::Queue(QueueCommand::List)),
+                    Some("edit") => Ok(SlashCommand::Queue(QueueCommand::Edit(position()?))),
+                    Some("cancel") => Ok(SlashCommand::Queue(QueueCommand::Cancel(position()?))),
+                    Some("clear") => Ok(SlashCommand::Queue(QueueCommand::Clear)),
+                    Some("send") => Ok(SlashCommand::Queue(QueueCommand::Send)),
+                    Some(action) => Err(anyhow::anyhow!("Unknown action '{action}'. {USAGE}")),
+                }
+            }
+            "/context" => match parameters.first().copied() {
+                None | Some("list") => Ok(SlashCommand::Context(ContextCommand::List)),
+                Some("use") => {
+                    let name = parameters.get(1).ok_or_else(|| {
+                        anyhow::anyhow!("Missing preset name. Usage: /context [list|use <name>]")
+                    })?;
+                    Ok(SlashCommand::Context(ContextCommand::Use(name.to_string())))
+                }
+                Some(action) => Err(anyhow::anyhow!(
+                    "Unknown action '{action}'. Usage: /context [list|use <name>]"
+                )),
+            },
+            "/reasoning" => match parameters.first().copied() {
+                None => Ok(SlashCommand::Reasoning(ReasoningCommand::Show)),
+                Some("reset") => Ok(SlashCommand::Reasoning(ReasoningCommand::Reset)),
+                Some(effort) => {
+                    let effort = effort.parse::<Effort>().map_err(|_| {
+                        anyhow::anyhow!(
+                            "Unknown effort '{effort}'. Usage: /reasoning [none|minimal|low|medium|high|xhigh|max|reset]"
+                        )
+                    })?;
+                    Ok(SlashCommand::Reasoning(ReasoningCommand::Set(effort)))
+                }
+            },
             "/logout" => Ok(SlashCommand::Logout),
             "/retry" => Ok(SlashCommand::Retry),
             "/conversation" | "/conversations" => Ok(SlashCommand::Conversations),
@@ -283,10 +347,60 @@ impl ForgeCommandManager {
                 // Support flexible syntax:
                 // /commit              -> commit with AI message
                 // /commit 5000         -> commit with max-diff of 5000 bytes
+                // /commit split        -> split changes into several commits
                 let max_diff_size = parameters.iter().find_map(|&p| p.parse::<usize>().ok());
-                Ok(SlashCommand::Commit { max_diff_size })
+                let split = parameters.contains(&"split");
+                Ok(SlashCommand::Commit { max_diff_size, split })
             }
             "/index" => Ok(SlashCommand::Index),
+            "/instructions" => Ok(SlashCommand::Instructions),
+            "/diff" => {
+                let path = parameters.join(" ");
+                Ok(SlashCommand::Diff(
+                    Some(path).filter(|path| !path.is_empty()),
+                ))
+            }
+            "/snapshot" => {
+                const USAGE: &str = "Usage: /snapshot diff <file> <from> <to>";
+                match parameters.as_slice() {
+                    ["diff", path, from, to] => {
+                        let index = |value: &str| {
+                            value.parse::<usize>().map_err(|_| {
+                                anyhow::anyhow!("Invalid snapshot number '{value}'. {USAGE}")
+                            })
+                        };
+                        Ok(SlashCommand::Snapshot(SnapshotDiff {
+                            path: path.to_string(),
+                            from: index(from)?,
+                            to: index(to)?,
+                        }))
+                    }
+                    _ => Err(anyhow::anyhow!(USAGE)),
+                }
+            }
+            "/profile" => {
+                let name = parameters.join(" ");
+                Ok(SlashCommand::Profile(
+                    Some(name).filter(|name| !name.is_empty()),
+                ))
+            }
+            "/workspace" => {
+                let path = parameters.join(" ");
+                Ok(SlashCommand::Workspace(
+                    Some(path).filter(|path| !path.is_empty()),
+                ))
+            }
+            "/history" => {
+                // /history             -> prompts from this workspace
+                // /history all         -> prompts from every workspace
+                // /history [all] text  -> prompts containing text
+                let all = parameters.first() == Some(&"all");
+                let query = parameters[usize::from(all)..].join(" ");
+                Ok(SlashCommand::History {
+                    all,
+                    query: Some(query).filter(|query| !query.is_empty()),
+                })
+            }
             "/rename" | "/rn" => {
                 let name = parameters.join(" ");
                 let name = name.trim().to_string();
@@ -297,6 +411,12 @@ impl ForgeCommandManager {
                 }
                 Ok(SlashCommand::Rename(name))
             }
+            "/title" => {
+                let title = parameters.join(" ");
+                Ok(SlashCommand::Title(
+                    Some(title).filter(|title| !title.is_empty()),
+                ))
+            }
             text => {
                 let parts = text.split_ascii_whitespace().collect::<Vec<&str>>();
 
@@ -316,15 +436,30 @@ impl ForgeCommandManager {
                     // Handle custom workflow commands
                     let command_name = command.strip_prefix('/').unwrap_or(command);
                     if let Some(command) = self.find(command_name) {
-                        let template = Template::new(
-                            self.extract_command_value(&command, &parts[1..])
-                                .unwrap_or_default(),
-                        );
-                        Ok(SlashCommand::Custom(UserCommand::new(
-                            command.name.clone(),
-                            template,
-                            parameters.into_iter().map(|s| s.to_owned()).collect(),
-                        )))
+                        let arguments = CommandArgument::bind(&command.arguments, &parameters)
+                            .map_err(|error| anyhow::anyhow!("/{}: {error}", command.name))?;
+                        let parameters = parameters.into_iter().map(|s| s.to_owned()).collect();
+                        if command.arguments.is_empty() {
+                            let template = Template::new(
+                                self.extract_command_value(&command, &parts[1..])
+                                    .unwrap_or_default(),
+                            );
+                            return Ok(SlashCommand::Custom(UserCommand::new(
+                                command.name.clone(),
+                                template,
+                                parameters,
+                            )));
+                        }
+
+                        // Commands declaring arguments always render their own prompt
+                        Ok(SlashCommand::Custom(
+                            UserCommand::new(
+                                command.name.clone(),
+                                command.value.clone().unwrap_or_default(),
+                                parameters,
+                            )
+                            .arguments(arguments),
+                        ))
                     } else {
                         Err(anyhow::anyhow!("{command} is not valid"))
                     }
@@ -432,6 +567,10 @@ pub enum SlashCommand {
     #[strum(props(usage = "Rename the current conversation. Usage: /rename <name>"))]
     Rename(String),
 
+    /// Show or change the title of the current conversation
+    #[strum(props(usage = "Show the conversation title, or change it. Usage: /title [new title]"))]
+    Title(Option<String>),
+
     /// Switch directly to a specific agent by ID
     #[strum(props(usage = "Switch directly to a specific agent"))]
     AgentSwitch(String),
@@ -441,17 +580,150 @@ pub enum SlashCommand {
     /// Examples:
     /// - `/commit` - Generate message and commit
     /// - `/commit 5000` - Commit with max diff of 5000 bytes
+    /// - `/commit split` - Split changes into several reviewed commits
     #[strum(props(
-        usage = "Generate AI commit message and commit changes. Format: /commit <max-diff|preview>"
+        usage = "Generate AI commit message and commit changes. Format: /commit <max-diff|preview|split>"
     ))]
-    Commit { max_diff_size: Option<usize> },
+    Commit {
+        max_diff_size: Option<usize>,
+        split: bool,
+    },
 
     /// Index the current workspace for semantic code search
     #[strum(props(usage = "Index the current workspace for semantic search"))]
     Index,
+
+    /// Show the files changed in the conversation, or the full diffs of one
+    /// of them
+    #[strum(props(
+        usage = "List files changed in the conversation or expand their diffs. Usage: /diff [file]"
+    ))]
+    Diff(Option<String>),
+
+    /// Show what changed between two snapshots taken of a file before agent
+    /// edits, without restoring anything
+    #[strum(props(
+        usage = "Compare two snapshots of a file, 0 being the oldest. Usage: /snapshot diff <file> <from> <to>"
+    ))]
+    Snapshot(SnapshotDiff),
+
+    /// List the profiles, or switch to one and save it as the active profile
+    #[strum(props(usage = "List profiles or switch to one. Usage: /profile [name]"))]
+    Profile(Option<String>),
+
+    /// Switch the session to another workspace, picking one of the workspaces
+    /// opened before when no path is given
+    #[strum(props(
+        usage = "Switch to another workspace without restarting. Usage: /workspace [path]"
+    ))]
+    Workspace(Option<String>),
+
+    /// Inspect the configured agents and create or edit custom ones
+    #[strum(props(
+        usage = "List, inspect, create or edit agents. Usage: /agents [list|show <id>|new|edit <id>]"
+    ))]
+    Agents(AgentsCommand),
+
+    /// List the project instruction files loaded into the system prompt
+    #[strum(props(usage = "List the instruction files (AGENTS.md, FORGE.md, CLAUDE.md) in use"))]
+    Instructions,
+
+    /// Pick a previously submitted prompt to edit and send again
+    #[strum(props(usage = "Search previous prompts and reuse one. Usage: /history [all] [query]"))]
+    History { all: bool, query: Option<String> },
+
+    /// Review, edit or cancel the messages typed while the agent was busy
+    #[strum(props(
+        usage = "Manage messages queued while the agent is busy. Usage: /queue [list|edit <n>|cancel <n>|clear|send]"
+    ))]
+    Queue(QueueCommand),
+
+    /// List the context presets of the workspace or place one in the prompt
+    #[strum(props(
+        usage = "Load a named set of files and notes into the prompt. Usage: /context [list|use <name>]"
+    ))]
+    Context(ContextCommand),
+
+    /// Show or change the reasoning effort of every agent for this session
+    #[strum(props(
+        usage = "Set the reasoning effort for this session. Usage: /reasoning [none|minimal|low|medium|high|xhigh|max|reset]"
+    ))]
+    Reasoning(ReasoningCommand),
+}
+
+/// Actions of the `/agents` command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AgentsCommand {
+    /// List every agent with its model and location
+    #[default]
+    List,
+    /// Show an agent's configuration, tools and prompt, picking one when no id
+    /// is given
+    Show(Option<String>),
+    /// Scaffold a new agent definition file with guided prompts
+    New,
+    /// Open a custom agent's definition file in the editor, picking one when
+    /// no id is given
+    Edit(Option<String>),
+}
+
+/// Actions of the `/queue` command. Messages are numbered from 1 in the order
+/// they will be sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum QueueCommand {
+    /// List the queued messages
+    #[default]
+    List,
+    /// Change the text of a queued message
+    Edit(usize),
+    /// Remove a queued message
+    Cancel(usize),
+    /// Remove every queued message
+    Clear,
+    /// Resume sending queued messages after an interrupted turn
+    Send,
+}
+
+/// Arguments of `/snapshot diff`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub path: String,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Actions of the `/context` command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ContextCommand {
+    /// List the context presets defined in the configuration
+    #[default]
+    List,
+    /// Place the files and notes of the named preset in the prompt
+    Use(String),
+}
+
+/// Actions of the `/reasoning` command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ReasoningCommand {
+    /// Show the reasoning effort in use
+    #[default]
+    Show,
+    /// Use this effort for every agent until Forge exits
+    Set(Effort),
+    /// Go back to the effort of the agents and the configuration
+    Reset,
 }
 
 impl SlashCommand {
+    /// Whether the command runs a turn of the agent, during which further
+    /// input is queued
+    pub fn runs_turn(&self) -> bool {
+        matches!(
+            self,
+            SlashCommand::Message(_) | SlashCommand::Custom(_) | SlashCommand::Retry
+        )
+    }
+
     pub fn name(&self) -> &str {
         match self {
             SlashCommand::Compact => "compact",
@@ -478,8 +750,19 @@ impl SlashCommand {
             SlashCommand::Conversations => "conversation",
             SlashCommand::Delete => "delete",
             SlashCommand::Rename(_) => "rename",
+            SlashCommand::Title(_) => "title",
+            SlashCommand::Queue(_) => "queue",
+            SlashCommand::Context(_) => "context",
+            SlashCommand::Reasoning(_) => "reasoning",
             SlashCommand::AgentSwitch(agent_id) => agent_id,
             SlashCommand::Index => "index",
+            SlashCommand::Diff(_) => "diff",
+            SlashCommand::Snapshot(_) => "snapshot",
+            SlashCommand::Profile(_) => "profile",
+            SlashCommand::Workspace(_) => "workspace",
+            SlashCommand::History { .. } => "history",
+            SlashCommand::Agents(_) => "agents",
+            SlashCommand::Instructions => "instructions",
         }
     }
 
@@ -491,6 +774,7 @@ impl SlashCommand {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::fmt::Display;
 
     use colored::Colorize;
@@ -577,6 +861,7 @@ mod tests {
             name: String::from("/test"),
             description: String::from("Test command"),
             value: None,
+            arguments: vec![],
         };
         let parts = vec!["arg1", "arg2"];
 
@@ -595,12 +880,14 @@ mod tests {
                 name: String::from("/test"),
                 description: String::from("Test command"),
                 value: Some(String::from("default_value")),
+                arguments: vec![],
             }])),
         };
         let command = ForgeCommand {
             name: String::from("/test"),
             description: String::from("Test command"),
             value: None,
+            arguments: vec![],
         };
         let parts: Vec<&str> = vec![];
 
@@ -619,12 +906,14 @@ mod tests {
                 name: String::from("/test"),
                 description: String::from("Test command"),
                 value: Some(String::from("default_value")),
+                arguments: vec![],
             }])),
         };
         let command = ForgeCommand {
             name: String::from("/test"),
             description: String::from("Test command"),
             value: None,
+            arguments: vec![],
         };
         let parts = vec![""];
 
@@ -643,12 +932,14 @@ mod tests {
                 name: String::from("/test"),
                 description: String::from("Test command"),
                 value: Some(String::from("default_value")),
+                arguments: vec![],
             }])),
         };
         let command = ForgeCommand {
             name: String::from("/test"),
             description: String::from("Test command"),
             value: None,
+            arguments: vec![],
         };
         let parts = vec!["  "];
 
@@ -667,12 +958,14 @@ mod tests {
                 name: String::from("/test"),
                 description: String::from("Test command"),
                 value: None,
+                arguments: vec![],
             }])),
         };
         let command = ForgeCommand {
             name: String::from("/test"),
             description: String::from("Test command"),
             value: None,
+            arguments: vec![],
         };
         let parts: Vec<&str> = vec![];
 
@@ -691,12 +984,14 @@ mod tests {
                 name: String::from("/test"),
                 description: String::from("Test command"),
                 value: Some(String::from("default_value")),
+                arguments: vec![],
             }])),
         };
         let command = ForgeCommand {
             name: String::from("/test"),
             description: String::from("Test command"),
             value: None,
+            arguments: vec![],
         };
         let parts = vec!["provided_value"];
 
@@ -1124,8 +1419,8 @@ mod tests {
         let fixture = ForgeCommandManager::default();
         let actual = fixture.parse("/commit").unwrap();
         match actual {
-            SlashCommand::Commit { max_diff_size } => {
-                assert_eq!(max_diff_size, None);
+            SlashCommand::Commit { max_diff_size, split } => {
+                assert_eq!((max_diff_size, split), (None, false));
             }
             _ => panic!("Expected Commit command, got {actual:?}"),
         }
@@ -1136,7 +1431,7 @@ mod tests {
         let fixture = ForgeCommandManager::default();
         let actual = fixture.parse("/commit preview").unwrap();
         match actual {
-            SlashCommand::Commit { max_diff_size } => {
+            SlashCommand::Commit { max_diff_size, .. } => {
                 assert_eq!(max_diff_size, None);
             }
             _ => panic!("Expected Commit command with preview, got {actual:?}"),
@@ -1148,7 +1443,7 @@ mod tests {
         let fixture = ForgeCommandManager::default();
         let actual = fixture.parse("/commit 5000").unwrap();
         match actual {
-            SlashCommand::Commit { max_diff_size } => {
+            SlashCommand::Commit { max_diff_size, .. } => {
                 assert_eq!(max_diff_size, Some(5000));
             }
             _ => panic!("Expected Commit command with max_diff_size, got {actual:?}"),
@@ -1160,13 +1455,25 @@ mod tests {
         let fixture = ForgeCommandManager::default();
         let actual = fixture.parse("/commit preview 10000").unwrap();
         match actual {
-            SlashCommand::Commit { max_diff_size } => {
+            SlashCommand::Commit { max_diff_size, .. } => {
                 assert_eq!(max_diff_size, Some(10000));
             }
             _ => panic!("Expected Commit command with all flags, got {actual:?}"),
         }
     }
 
+    #[test]
+    fn test_parse_commit_command_with_split() {
+        let fixture = ForgeCommandManager::default();
+        let actual = fixture.parse("/commit split 8000").unwrap();
+        match actual {
+            SlashCommand::Commit { max_diff_size, split } => {
+                assert_eq!((max_diff_size, split), (Some(8000), true));
+            }
+            _ => panic!("Expected Commit command with split, got {actual:?}"),
+        }
+    }
+
     #[test]
     fn test_commit_command_in_default_commands() {
         let manager = ForgeCommandManager::default();
@@ -1239,6 +1546,233 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_parse_diff_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/diff").unwrap(),
+            fixture.parse("/diff src/lib.rs").unwrap(),
+        );
+
+        let expected = (
+            SlashCommand::Diff(None),
+            SlashCommand::Diff(Some("src/lib.rs".to_string())),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_snapshot_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/snapshot diff src/lib.rs 0 2").unwrap(),
+            fixture
+                .parse("/snapshot diff src/lib.rs 0")
+                .unwrap_err()
+                .to_string(),
+            fixture
+                .parse("/snapshot diff src/lib.rs a 1")
+                .unwrap_err()
+                .to_string(),
+        );
+
+        let expected = (
+            SlashCommand::Snapshot(SnapshotDiff { path: "src/lib.rs".to_string(), from: 0, to: 2 }),
+            "Usage: /snapshot diff <file> <from> <to>".to_string(),
+            "Invalid snapshot number 'a'. Usage: /snapshot diff <file> <from> <to>".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_profile_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/profile").unwrap(),
+            fixture.parse("/profile work").unwrap(),
+        );
+
+        let expected = (
+            SlashCommand::Profile(None),
+            SlashCommand::Profile(Some("work".to_string())),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_workspace_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/workspace").unwrap(),
+            fixture.parse("/workspace ../web app").unwrap(),
+        );
+
+        let expected = (
+            SlashCommand::Workspace(None),
+            SlashCommand::Workspace(Some("../web app".to_string())),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_history_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/history").unwrap(),
+            fixture.parse("/history all").unwrap(),
+            fixture.parse("/history all fix tests").unwrap(),
+            fixture.parse("/history parser").unwrap(),
+        );
+
+        let expected = (
+            SlashCommand::History { all: false, query: None },
+            SlashCommand::History { all: true, query: None },
+            SlashCommand::History { all: true, query: Some("fix tests".to_string()) },
+            SlashCommand::History { all: false, query: Some("parser".to_string()) },
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_agents_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/agents").unwrap(),
+            fixture.parse("/agents list").unwrap(),
+            fixture.parse("/agents show reviewer").unwrap(),
+            fixture.parse("/agents new").unwrap(),
+            fixture.parse("/agents edit").unwrap(),
+        );
+
+        let expected = (
+            SlashCommand::Agents(AgentsCommand::List),
+            SlashCommand::Agents(AgentsCommand::List),
+            SlashCommand::Agents(AgentsCommand::Show(Some("reviewer".to_string()))),
+            SlashCommand::Agents(AgentsCommand::New),
+            SlashCommand::Agents(AgentsCommand::Edit(None)),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_agents_command_unknown_action() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = fixture.parse("/agents remove").unwrap_err().to_string();
+
+        let expected = "Unknown action 'remove'. Usage: /agents [list|show <id>|new|edit <id>]";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_custom_command_with_arguments() {
+        let fixture = ForgeCommandManager::default();
+        fixture.register_all(vec![
+            forge_domain::Command::default()
+                .name("review")
+                .description("Review code")
+                .prompt("Review {{target}}")
+                .arguments(vec![
+                    CommandArgument::default().name("target").required(true),
+                    CommandArgument::default()
+                        .name("strict")
+                        .kind(forge_domain::ArgumentType::Boolean)
+                        .default_value(serde_json::json!(false)),
+                ]),
+        ]);
+
+        let actual = (
+            fixture.parse("/review src strict=yes").unwrap(),
+            fixture.parse("/review strict=no").unwrap_err().to_string(),
+        );
+
+        let expected = (
+            SlashCommand::Custom(
+                UserCommand::new(
+                    "review",
+                    "Review {{target}}",
+                    vec!["src".to_string(), "strict=yes".to_string()],
+                )
+                .arguments(BTreeMap::from([
+                    ("strict".to_string(), serde_json::json!(true)),
+                    ("target".to_string(), serde_json::json!("src")),
+                ])),
+            ),
+            "/review: Missing required argument 'target'. Usage: <target> [strict=false]"
+                .to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_queue_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/queue").unwrap(),
+            fixture.parse("/queue edit 2").unwrap(),
+            fixture.parse("/queue cancel 1").unwrap(),
+            fixture.parse("/queue clear").unwrap(),
+            fixture.parse("/queue send").unwrap(),
+            fixture.parse("/queue cancel").unwrap_err().to_string(),
+        );
+
+        let expected = (
+            SlashCommand::Queue(QueueCommand::List),
+            SlashCommand::Queue(QueueCommand::Edit(2)),
+            SlashCommand::Queue(QueueCommand::Cancel(1)),
+            SlashCommand::Queue(QueueCommand::Clear),
+            SlashCommand::Queue(QueueCommand::Send),
+            "Missing queued message number. Usage: /queue [list|edit <n>|cancel <n>|clear|send]"
+                .to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_context_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/context").unwrap(),
+            fixture.parse("/context use frontend").unwrap(),
+            fixture.parse("/context use").unwrap_err().to_string(),
+        );
+
+        let expected = (
+            SlashCommand::Context(ContextCommand::List),
+            SlashCommand::Context(ContextCommand::Use("frontend".to_string())),
+            "Missing preset name. Usage: /context [list|use <name>]".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_reasoning_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/reasoning").unwrap(),
+            fixture.parse("/reasoning High").unwrap(),
+            fixture.parse("/reasoning reset").unwrap(),
+            fixture.parse("/reasoning loud").is_err(),
+        );
+
+        let expected = (
+            SlashCommand::Reasoning(ReasoningCommand::Show),
+            SlashCommand::Reasoning(ReasoningCommand::Set(Effort::High)),
+            SlashCommand::Reasoning(ReasoningCommand::Reset),
+            true,
+        );
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_parse_rename_command() {
         let fixture = ForgeCommandManager::default();
@@ -1278,6 +1812,22 @@ mod tests {
         assert_eq!(actual, SlashCommand::Rename("my title".to_string()));
     }
 
+    #[test]
+    fn test_parse_title_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = (
+            fixture.parse("/title").unwrap(),
+            fixture.parse("/title  Auth refactor ").unwrap(),
+        );
+
+        let expected = (
+            SlashCommand::Title(None),
+            SlashCommand::Title(Some("Auth refactor".to_string())),
+        );
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_rename_is_reserved_command() {
         assert!(ForgeCommandManager::is_reserved_command("rename"));
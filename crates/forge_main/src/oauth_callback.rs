@@ -113,6 +113,42 @@ impl Drop for LocalhostOAuthCallbackServer {
     }
 }
 
+/// Whether forge runs where a browser cannot reach it, e.g. over SSH or on a
+/// Linux server without a display, so sign-in should not rely on opening a
+/// browser or on a localhost callback.
+pub(crate) fn is_headless_session() -> bool {
+    is_headless(
+        |name| std::env::var_os(name).is_some_and(|value| !value.is_empty()),
+        cfg!(target_os = "linux"),
+    )
+}
+
+fn is_headless(has_var: impl Fn(&str) -> bool, needs_display: bool) -> bool {
+    let remote = ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+        .iter()
+        .any(|name| has_var(name));
+    // WSL opens the Windows browser without a display
+    let no_display = needs_display
+        && !has_var("DISPLAY")
+        && !has_var("WAYLAND_DISPLAY")
+        && !has_var("WSL_DISTRO_NAME");
+    remote || no_display
+}
+
+/// The authorization code pasted by the user, who may paste either the code
+/// itself or the whole URL the browser was redirected to.
+pub(crate) fn parse_authorization_code(input: &str) -> String {
+    let input = input.trim();
+    Url::parse(input)
+        .ok()
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "code")
+                .map(|(_, code)| code.into_owned())
+        })
+        .unwrap_or_else(|| input.to_string())
+}
+
 fn escape_html(input: &str) -> String {
     input
         .replace('&', "&amp;")
@@ -363,6 +399,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_headless_over_ssh() {
+        let fixture = |name: &str| ["SSH_CONNECTION", "DISPLAY"].contains(&name);
+        let actual = is_headless(fixture, true);
+        assert!(actual);
+    }
+
+    #[test]
+    fn test_is_headless_without_display() {
+        let actual = (
+            is_headless(|_| false, true),
+            is_headless(|name| name == "WAYLAND_DISPLAY", true),
+            is_headless(|name| name == "WSL_DISTRO_NAME", true),
+            is_headless(|_| false, false),
+        );
+        let expected = (true, false, false, false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_authorization_code() {
+        let actual = (
+            parse_authorization_code(
+                " http://localhost:1455/auth/callback?code=abc%2F123&state=expected-state\n",
+            ),
+            parse_authorization_code("abc123#expected-state"),
+            parse_authorization_code("http://localhost:1455/auth/callback?error=denied"),
+        );
+        let expected = (
+            "abc/123".to_string(),
+            "abc123#expected-state".to_string(),
+            "http://localhost:1455/auth/callback?error=denied".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
     fn sample_callback_server() -> (Arc<Server>, SocketAddr, Arc<AtomicBool>) {
         let fixture = TcpListener::bind("127.0.0.1:0").unwrap();
         let addr = fixture.local_addr().unwrap();
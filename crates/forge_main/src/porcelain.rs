@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use std::fmt;
 
+use forge_domain::{ChatResponse, ChatResponseContent};
+use forge_server::ConversationUsage;
 use indexmap::IndexSet;
+use serde::Serialize;
 
 use crate::display_constants::headers;
 use crate::info::{Info, Section};
@@ -464,6 +467,118 @@ impl From<&Info> for Porcelain {
     }
 }
 
+/// Version of the NDJSON event stream, bumped on incompatible changes to the
+/// events so that wrapper tools can detect them
+pub const EVENT_STREAM_VERSION: u32 = 1;
+
+/// Why a turn ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoneStatus {
+    Complete,
+    Interrupted,
+    Error,
+}
+
+/// Event of the NDJSON stream printed on stdout with `--output-format ndjson`.
+///
+/// Unlike the server's stream events, these carry the full tool calls and
+/// results so that wrapper tools can follow a turn without parsing the human
+/// rendering, which goes to stderr instead.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PorcelainEvent {
+    TurnStart {
+        conversation_id: String,
+    },
+    /// Text produced by the agent, to be appended to the previous deltas
+    ContentDelta {
+        text: String,
+        /// Whether the text is reasoning rather than part of the answer
+        reasoning: bool,
+    },
+    ToolCall {
+        call_id: Option<String>,
+        name: String,
+        arguments: serde_json::Value,
+    },
+    ToolResult {
+        call_id: Option<String>,
+        name: String,
+        is_error: bool,
+        output: String,
+    },
+    /// A tool changed a file
+    FileEdit {
+        path: String,
+        lines_added: u64,
+        lines_removed: u64,
+        /// Unified diff of the change, without colors
+        diff: String,
+    },
+    /// Token usage accumulated over the conversation
+    Usage(ConversationUsage),
+    Done {
+        status: DoneStatus,
+        reason: Option<String>,
+    },
+}
+
+/// A [`PorcelainEvent`] tagged with the version of the stream
+#[derive(Serialize)]
+struct EventLine<'a> {
+    version: u32,
+    #[serde(flatten)]
+    event: &'a PorcelainEvent,
+}
+
+impl PorcelainEvent {
+    /// Converts a chat response into its event, `None` for responses that
+    /// are only rendered for humans or are summarised by the `done` event
+    pub fn from_response(response: &ChatResponse) -> Option<Self> {
+        match response {
+            ChatResponse::TaskMessage { content } => match content {
+                ChatResponseContent::Markdown { text, .. } => {
+                    Some(Self::ContentDelta { text: text.clone(), reasoning: false })
+                }
+                ChatResponseContent::FileDiff(diff) => Some(Self::FileEdit {
+                    path: diff.path.clone(),
+                    lines_added: diff.lines_added,
+                    lines_removed: diff.lines_removed,
+                    diff: String::from_utf8_lossy(&strip_ansi_escapes::strip(&diff.diff))
+                        .into_owned(),
+                }),
+                ChatResponseContent::ToolInput(_) | ChatResponseContent::ToolOutput(_) => None,
+            },
+            ChatResponse::TaskReasoning { content } => {
+                Some(Self::ContentDelta { text: content.clone(), reasoning: true })
+            }
+            ChatResponse::ToolCallStart { tool_call, .. } => Some(Self::ToolCall {
+                call_id: tool_call.call_id.as_ref().map(|id| id.as_str().to_string()),
+                name: tool_call.name.to_string(),
+                arguments: serde_json::to_value(&tool_call.arguments)
+                    .unwrap_or(serde_json::Value::Null),
+            }),
+            ChatResponse::ToolCallEnd(result) => Some(Self::ToolResult {
+                call_id: result.call_id.as_ref().map(|id| id.as_str().to_string()),
+                name: result.name.to_string(),
+                is_error: result.is_error(),
+                output: result.output.as_str().unwrap_or_default().to_string(),
+            }),
+            ChatResponse::TaskComplete
+            | ChatResponse::RetryAttempt { .. }
+            | ChatResponse::Interrupt { .. }
+            | ChatResponse::PermissionRequest { .. } => None,
+        }
+    }
+
+    /// Serializes the event as a single JSON line tagged with the stream
+    /// version
+    pub fn to_line(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&EventLine { version: EVENT_STREAM_VERSION, event: self })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -1238,4 +1353,71 @@ mod tests {
 
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn test_event_line_is_versioned() {
+        let fixture = PorcelainEvent::ContentDelta { text: "Hello".to_string(), reasoning: false };
+
+        let actual = fixture.to_line().unwrap();
+
+        let expected = r#"{"version":1,"type":"content_delta","text":"Hello","reasoning":false}"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_usage_event_flattens_usage() {
+        let fixture = PorcelainEvent::Usage(ConversationUsage {
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+            cached_tokens: 0,
+            cost: None,
+        });
+
+        let actual = fixture.to_line().unwrap();
+
+        let expected = r#"{"version":1,"type":"usage","prompt_tokens":10,"completion_tokens":5,"total_tokens":15,"cached_tokens":0,"cost":null}"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_tool_call_end_becomes_tool_result() {
+        let fixture = ChatResponse::ToolCallEnd(
+            forge_domain::ToolResult::new("shell")
+                .call_id(forge_domain::ToolCallId::new("call_1"))
+                .output(Ok(forge_domain::ToolOutput::text("done"))),
+        );
+
+        let actual = PorcelainEvent::from_response(&fixture);
+
+        let expected = Some(PorcelainEvent::ToolResult {
+            call_id: Some("call_1".to_string()),
+            name: "shell".to_string(),
+            is_error: false,
+            output: "done".to_string(),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_file_diff_becomes_uncolored_file_edit() {
+        let fixture = ChatResponse::TaskMessage {
+            content: ChatResponseContent::FileDiff(forge_domain::FileDiff {
+                path: "src/lib.rs".to_string(),
+                diff: "\u{1b}[32m+fn a() {}\u{1b}[0m".to_string(),
+                lines_added: 1,
+                lines_removed: 0,
+            }),
+        };
+
+        let actual = PorcelainEvent::from_response(&fixture);
+
+        let expected = Some(PorcelainEvent::FileEdit {
+            path: "src/lib.rs".to_string(),
+            lines_added: 1,
+            lines_removed: 0,
+            diff: "+fn a() {}".to_string(),
+        });
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::fmt::Write;
+use std::fmt::{self, Display, Write};
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -15,6 +15,47 @@ use crate::display_constants::markers;
 // Constants
 const MULTILINE_INDICATOR: &str = "::: ";
 const RIGHT_CHEVRON: &str = "❯";
+/// Share of the compaction threshold from which the context indicator warns
+/// that compaction is about to trigger
+const COMPACTION_WARNING_RATIO: f64 = 0.8;
+
+/// Share of the model's context window taken up by the conversation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContextUsage {
+    /// Tokens in the conversation's context
+    pub tokens: usize,
+    /// Size of the model's context window in tokens
+    pub window: u64,
+    /// Token count at which the context gets compacted, if compaction is
+    /// enabled
+    pub compact_at: Option<usize>,
+}
+
+impl ContextUsage {
+    fn percent(&self, tokens: usize) -> u64 {
+        (tokens as f64 * 100.0 / self.window.max(1) as f64).round() as u64
+    }
+
+    /// Whether the context is close enough to the compaction threshold to
+    /// warn that the next turns will compact it
+    pub fn is_near_compaction(&self) -> bool {
+        self.compact_at.is_some_and(|compact_at| {
+            self.tokens as f64 >= compact_at as f64 * COMPACTION_WARNING_RATIO
+        })
+    }
+}
+
+impl Display for ContextUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}% ctx", self.percent(self.tokens))?;
+        if let Some(compact_at) = self.compact_at
+            && self.is_near_compaction()
+        {
+            write!(f, ", compacts at {}%", self.percent(compact_at))?;
+        }
+        write!(f, "]")
+    }
+}
 
 /// Very Specialized Prompt for the Agent Chat
 #[derive(Clone, Setters)]
@@ -24,6 +65,7 @@ pub struct ForgePrompt {
     pub usage: Option<Usage>,
     pub agent_id: AgentId,
     pub model: Option<ModelId>,
+    pub context: Option<ContextUsage>,
 }
 
 impl Prompt for ForgePrompt {
@@ -92,13 +134,23 @@ impl Prompt for ForgePrompt {
         write!(result, "]").unwrap();
 
         // Apply styling once at the end
-        Cow::Owned(
-            Style::new()
-                .bold()
-                .fg(Color::DarkGray)
-                .paint(&result)
-                .to_string(),
-        )
+        let mut result = Style::new()
+            .bold()
+            .fg(Color::DarkGray)
+            .paint(&result)
+            .to_string();
+
+        if let Some(context) = self.context.as_ref() {
+            let color = if context.is_near_compaction() {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+            let context = Style::new().bold().fg(color).paint(context.to_string());
+            write!(result, "{context}").unwrap();
+        }
+
+        Cow::Owned(result)
     }
 
     fn render_prompt_indicator(&self, _prompt_mode: reedline::PromptEditMode) -> Cow<'_, str> {
@@ -180,6 +232,7 @@ mod tests {
                 usage: None,
                 agent_id: AgentId::default(),
                 model: None,
+                context: None,
             }
         }
     }
@@ -239,6 +292,46 @@ mod tests {
         assert!(actual.contains("0"));
     }
 
+    #[test]
+    fn test_context_usage_display() {
+        let fixture = [
+            ContextUsage { tokens: 63_000, window: 100_000, compact_at: Some(100_000) },
+            ContextUsage { tokens: 63_000, window: 100_000, compact_at: Some(70_000) },
+            ContextUsage { tokens: 63_000, window: 100_000, compact_at: None },
+        ];
+
+        let actual = fixture
+            .iter()
+            .map(|usage| usage.to_string())
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            "[63% ctx]".to_string(),
+            "[63% ctx, compacts at 70%]".to_string(),
+            "[63% ctx]".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_prompt_right_with_context_usage() {
+        let mut prompt = ForgePrompt::default();
+        let _ = prompt.context(ContextUsage {
+            tokens: 63_000,
+            window: 100_000,
+            compact_at: Some(70_000),
+        });
+
+        let actual = prompt.render_prompt_right();
+
+        let expected = Style::new()
+            .bold()
+            .fg(Color::Yellow)
+            .paint("[63% ctx, compacts at 70%]")
+            .to_string();
+        assert!(actual.ends_with(&expected));
+    }
+
     #[test]
     fn test_render_prompt_multiline_indicator() {
         let prompt = ForgePrompt::default();
@@ -0,0 +1,290 @@
+use std::collections::VecDeque;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+/// How long the type-ahead reader waits for input before checking whether it
+/// should stop
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Prefix of the commands managing the queue, applied as soon as the agent is
+/// done rather than in turn
+const QUEUE_COMMAND: &str = "/queue";
+
+#[derive(Debug, Default)]
+struct State {
+    messages: VecDeque<String>,
+    held: bool,
+}
+
+/// Messages typed while the agent is busy, sent one per turn once it is done
+#[derive(Debug, Clone, Default)]
+pub struct MessageQueue(Arc<Mutex<State>>);
+
+impl MessageQueue {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Adds `message` to the end of the queue and returns its position,
+    /// starting from 1
+    pub fn push(&self, message: String) -> usize {
+        let mut state = self.state();
+        state.messages.push_back(message);
+        state.messages.len()
+    }
+
+    /// Takes the next message to send, unless the queue is held. `/queue`
+    /// commands jump ahead so that they apply to the messages queued before
+    /// them.
+    pub fn pop(&self) -> Option<String> {
+        let mut state = self.state();
+        if state.held {
+            return None;
+        }
+        let index = state
+            .messages
+            .iter()
+            .position(|message| is_queue_command(message))
+            .unwrap_or(0);
+        state.messages.remove(index)
+    }
+
+    /// Queued messages in the order they will be sent
+    pub fn messages(&self) -> Vec<String> {
+        self.state().messages.iter().cloned().collect()
+    }
+
+    /// The message at `position`, starting from 1
+    pub fn get(&self, position: usize) -> anyhow::Result<String> {
+        let state = self.state();
+        position
+            .checked_sub(1)
+            .and_then(|index| state.messages.get(index))
+            .cloned()
+            .ok_or_else(|| out_of_range(position, state.messages.len()))
+    }
+
+    /// Replaces the message at `position`, starting from 1
+    pub fn edit(&self, position: usize, message: String) -> anyhow::Result<()> {
+        let mut state = self.state();
+        let len = state.messages.len();
+        let slot = position
+            .checked_sub(1)
+            .and_then(|index| state.messages.get_mut(index))
+            .ok_or_else(|| out_of_range(position, len))?;
+        *slot = message;
+        Ok(())
+    }
+
+    /// Removes and returns the message at `position`, starting from 1
+    pub fn cancel(&self, position: usize) -> anyhow::Result<String> {
+        let mut state = self.state();
+        let len = state.messages.len();
+        position
+            .checked_sub(1)
+            .and_then(|index| state.messages.remove(index))
+            .ok_or_else(|| out_of_range(position, len))
+    }
+
+    /// Removes every message and returns how many there were
+    pub fn clear(&self) -> usize {
+        let mut state = self.state();
+        let count = state.messages.len();
+        state.messages.clear();
+        count
+    }
+
+    /// Stops sending queued messages, e.g. after the turn they follow was
+    /// interrupted, until [`MessageQueue::resume`] is called
+    pub fn hold(&self) {
+        self.state().held = true;
+    }
+
+    pub fn resume(&self) {
+        self.state().held = false;
+    }
+
+    pub fn len(&self) -> usize {
+        self.state().messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state().messages.is_empty()
+    }
+}
+
+fn is_queue_command(message: &str) -> bool {
+    message == QUEUE_COMMAND || message.starts_with(&format!("{QUEUE_COMMAND} "))
+}
+
+fn out_of_range(position: usize, len: usize) -> anyhow::Error {
+    match len {
+        0 => anyhow::anyhow!("The queue is empty"),
+        len => anyhow::anyhow!("No queued message #{position}, pick one from 1 to {len}"),
+    }
+}
+
+/// Reads lines typed while the agent is busy and adds them to a
+/// [`MessageQueue`]. The terminal stays in line mode, so input is echoed and
+/// editable as usual and only complete lines are received.
+pub struct TypeAhead {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl TypeAhead {
+    /// Starts reading the terminal in the background, calling `on_queued`
+    /// with the position and text of every message added to `queue`. Does
+    /// nothing when stdin is not a terminal.
+    pub fn start(queue: MessageQueue, on_queued: impl Fn(usize, &str) + Send + 'static) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        if !std::io::stdin().is_terminal() {
+            return Self { stop, handle: None };
+        }
+
+        let handle = std::thread::spawn({
+            let stop = stop.clone();
+            move || {
+                let mut line = String::new();
+                while !stop.load(Ordering::SeqCst) {
+                    // Polling would take the keys meant for a question the agent is
+                    // asking, so the terminal is claimed across the poll and the read
+                    let Some(terminal) = forge_select::claim_terminal() else {
+                        std::thread::sleep(POLL_INTERVAL);
+                        continue;
+                    };
+                    match event::poll(POLL_INTERVAL) {
+                        Ok(true) => {}
+                        Ok(false) => continue,
+                        Err(error) => {
+                            tracing::warn!(error = ?error, "Stopped reading input typed ahead");
+                            break;
+                        }
+                    }
+                    let Ok(event) = event::read() else {
+                        break;
+                    };
+                    drop(terminal);
+                    match event {
+                        Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                            KeyCode::Enter => {
+                                let message = std::mem::take(&mut line).trim().to_string();
+                                if !message.is_empty() {
+                                    let position = queue.push(message.clone());
+                                    on_queued(position, &message);
+                                }
+                            }
+                            KeyCode::Backspace => {
+                                line.pop();
+                            }
+                            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                line.push(c);
+                            }
+                            _ => {}
+                        },
+                        Event::Paste(text) => line.push_str(&text),
+                        _ => {}
+                    }
+                }
+            }
+        });
+
+        Self { stop, handle: Some(handle) }
+    }
+
+    /// Stops reading and waits for the reader to let go of the terminal
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for TypeAhead {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn fixture(messages: &[&str]) -> MessageQueue {
+        let queue = MessageQueue::default();
+        for message in messages {
+            queue.push(message.to_string());
+        }
+        queue
+    }
+
+    #[test]
+    fn test_pop_sends_queue_commands_first() {
+        let fixture = fixture(&["fix the tests", "update the docs", "/queue cancel 1"]);
+
+        let actual = std::iter::from_fn(|| fixture.pop()).collect::<Vec<_>>();
+        let expected = vec![
+            "/queue cancel 1".to_string(),
+            "fix the tests".to_string(),
+            "update the docs".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_held_queue_keeps_messages_until_resumed() {
+        let fixture = fixture(&["fix the tests"]);
+
+        fixture.hold();
+        let held = fixture.pop();
+        fixture.resume();
+
+        let actual = (held, fixture.pop());
+        let expected = (None, Some("fix the tests".to_string()));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_edit_and_cancel_by_position() {
+        let fixture = fixture(&["fix the tests", "update the docs", "release"]);
+
+        fixture.edit(2, "update the README".to_string()).unwrap();
+        let cancelled = fixture.cancel(1).unwrap();
+
+        let actual = (cancelled, fixture.messages());
+        let expected = (
+            "fix the tests".to_string(),
+            vec!["update the README".to_string(), "release".to_string()],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_positions_out_of_range() {
+        let actual = (
+            fixture(&["fix the tests"])
+                .cancel(2)
+                .unwrap_err()
+                .to_string(),
+            fixture(&[]).edit(1, String::new()).unwrap_err().to_string(),
+            fixture(&["fix the tests"])
+                .cancel(0)
+                .unwrap_err()
+                .to_string(),
+        );
+        let expected = (
+            "No queued message #2, pick one from 1 to 1".to_string(),
+            "The queue is empty".to_string(),
+            "No queued message #0, pick one from 1 to 1".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result, bail};
@@ -137,3 +137,243 @@ impl<'a> Sandbox<'a> {
             .context("Failed to canonicalize worktree path")
     }
 }
+
+/// A worktree created with `--sandbox`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SandboxEntry {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Sandbox<'_> {
+    /// Summary of the changes of the sandbox, like `git diff --shortstat`, or
+    /// `None` when it has none
+    pub fn changes(&self, cwd: &Path) -> Result<Option<String>> {
+        Ok(summarize_numstat(&self.diff(cwd, &["--numstat"])?))
+    }
+
+    /// Root of the main working tree of the repository holding `cwd`, also
+    /// when `cwd` is inside a sandbox
+    fn repository_root(cwd: &Path) -> Result<PathBuf> {
+        let common_dir = git(
+            cwd,
+            &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+        )?;
+        Path::new(&common_dir)
+            .parent()
+            .map(Path::to_path_buf)
+            .context("Failed to determine git repository root")
+    }
+
+    /// Lists the sandboxes of the repository holding `cwd`, i.e. the worktrees
+    /// next to it named after their branch
+    pub fn list(cwd: &Path) -> Result<Vec<SandboxEntry>> {
+        let root = Self::repository_root(cwd)?;
+        let output = git(&root, &["worktree", "list", "--porcelain"])?;
+        Ok(parse_worktrees(&output, root.parent().unwrap_or(&root)))
+    }
+
+    fn entry(&self, cwd: &Path) -> Result<(PathBuf, SandboxEntry)> {
+        let root = Self::repository_root(cwd)?;
+        let output = git(&root, &["worktree", "list", "--porcelain"])?;
+        let entry = parse_worktrees(&output, root.parent().unwrap_or(&root))
+            .into_iter()
+            .find(|entry| entry.name == self.dir)
+            .with_context(|| format!("No sandbox named '{}'", self.dir))?;
+        Ok((root, entry))
+    }
+
+    /// Changes of the sandbox since it branched off the current branch of the
+    /// repository holding `cwd`, including changes and new files the agent did
+    /// not commit. `args` are passed on to `git diff`, e.g. `--numstat`. The
+    /// sandbox is left untouched.
+    pub fn diff(&self, cwd: &Path, args: &[&str]) -> Result<String> {
+        let (root, entry) = self.entry(cwd)?;
+        let head = git(&root, &["rev-parse", "HEAD"])?;
+        let base = git(&entry.path, &["merge-base", &head, "HEAD"])?;
+        let mut diff_args = vec!["diff", base.as_str()];
+        diff_args.extend_from_slice(args);
+        let mut diffs = vec![git(&entry.path, &diff_args)?];
+
+        // Untracked files are compared with an empty file, as they are not in
+        // the index the diff above reads
+        let untracked = git(&entry.path, &["ls-files", "--others", "--exclude-standard"])?;
+        for file in untracked.lines() {
+            let mut diff_args = vec!["diff", "--no-index"];
+            diff_args.extend_from_slice(args);
+            diff_args.extend_from_slice(&["--", "/dev/null", file]);
+            diffs.push(git_diff_no_index(&entry.path, &diff_args)?);
+        }
+
+        diffs.retain(|diff| !diff.is_empty());
+        Ok(diffs.join("\n"))
+    }
+
+    /// Commits what the agent left uncommitted in the sandbox, merges its
+    /// branch into the current branch of the repository holding `cwd` and
+    /// removes the sandbox
+    pub fn merge(&self, cwd: &Path) -> Result<()> {
+        let (root, entry) = self.entry(cwd)?;
+        git(&entry.path, &["add", "--all"])?;
+        if !git(&entry.path, &["status", "--porcelain"])?.is_empty() {
+            let message = format!("Changes from sandbox {}", entry.name);
+            git(&entry.path, &["commit", "--message", &message])?;
+        }
+        git(&root, &["merge", "--no-ff", "--no-edit", &entry.name])?;
+        Self::remove(&root, &entry, false)
+    }
+
+    /// Removes the sandbox and its branch without merging them
+    pub fn discard(&self, cwd: &Path) -> Result<()> {
+        let (root, entry) = self.entry(cwd)?;
+        Self::remove(&root, &entry, true)
+    }
+
+    fn remove(root: &Path, entry: &SandboxEntry, force: bool) -> Result<()> {
+        let path = entry.path.to_string_lossy().to_string();
+        let (worktree_args, branch_flag) = if force {
+            (vec!["worktree", "remove", "--force", path.as_str()], "-D")
+        } else {
+            (vec!["worktree", "remove", path.as_str()], "-d")
+        };
+        git(root, &worktree_args)?;
+        git(root, &["branch", branch_flag, &entry.name])?;
+        Ok(())
+    }
+}
+
+/// Runs git in `dir` and returns its output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+/// Runs `git diff --no-index` in `dir`, which exits with 1 when the files
+/// differ, and returns its output
+fn git_diff_no_index(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+/// Summarizes `git diff --numstat` output like `git diff --shortstat` does,
+/// or returns `None` when nothing changed
+fn summarize_numstat(numstat: &str) -> Option<String> {
+    let (mut files, mut insertions, mut deletions) = (0, 0, 0);
+    for line in numstat.lines() {
+        let mut counts = line.split('\t');
+        // Binary files have `-` for both counts
+        insertions += counts
+            .next()
+            .and_then(|count| count.parse::<u64>().ok())
+            .unwrap_or(0);
+        deletions += counts
+            .next()
+            .and_then(|count| count.parse::<u64>().ok())
+            .unwrap_or(0);
+        files += 1;
+    }
+    (files > 0).then(|| {
+        let plural = |count: u64| if count == 1 { "" } else { "s" };
+        format!(
+            "{files} file{} changed, {insertions} insertion{}(+), {deletions} deletion{}(-)",
+            plural(files),
+            plural(insertions),
+            plural(deletions)
+        )
+    })
+}
+
+/// Picks the sandboxes out of `git worktree list --porcelain`: worktrees in
+/// `parent` named after their branch, as `Sandbox::create` lays them out
+fn parse_worktrees(output: &str, parent: &Path) -> Vec<SandboxEntry> {
+    output
+        .split("\n\n")
+        .filter_map(|block| {
+            let mut path = None;
+            let mut branch = None;
+            for line in block.lines() {
+                if let Some(value) = line.strip_prefix("worktree ") {
+                    path = Some(PathBuf::from(value));
+                } else if let Some(value) = line.strip_prefix("branch refs/heads/") {
+                    branch = Some(value.to_string());
+                }
+            }
+            let (path, name) = (path?, branch?);
+            (path == parent.join(&name)).then_some(SandboxEntry { name, path })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_summarize_numstat() {
+        let fixture = "10\t2\tsrc/lib.rs\n-\t-\tlogo.png\n1\t0\t/dev/null => src/new.rs";
+
+        let actual = (summarize_numstat(fixture), summarize_numstat(""));
+
+        let expected = (
+            Some("3 files changed, 11 insertions(+), 2 deletions(-)".to_string()),
+            None,
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_worktrees_keeps_sandboxes() {
+        let fixture = "worktree /work/forge
+HEAD 1111111111111111111111111111111111111111
+branch refs/heads/main
+
+worktree /work/fix-parser
+HEAD 2222222222222222222222222222222222222222
+branch refs/heads/fix-parser
+
+worktree /tmp/review
+HEAD 3333333333333333333333333333333333333333
+branch refs/heads/review
+
+worktree /work/detached
+HEAD 4444444444444444444444444444444444444444
+detached
+";
+
+        let actual = parse_worktrees(fixture, Path::new("/work"));
+
+        let expected = vec![SandboxEntry {
+            name: "fix-parser".to_string(),
+            path: PathBuf::from("/work/fix-parser"),
+        }];
+        assert_eq!(actual, expected);
+    }
+}
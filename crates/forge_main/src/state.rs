@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use derive_setters::Setters;
 use forge_api::{ConversationId, Environment};
 
+use crate::diffs::DiffHistory;
+
 //TODO: UIState and ForgePrompt seem like the same thing and can be merged
 /// State information for the UI
 #[derive(Debug, Default, Clone, Setters)]
@@ -10,10 +12,15 @@ use forge_api::{ConversationId, Environment};
 pub struct UIState {
     pub cwd: PathBuf,
     pub conversation_id: Option<ConversationId>,
+    pub diffs: DiffHistory,
 }
 
 impl UIState {
     pub fn new(env: Environment) -> Self {
-        Self { cwd: env.cwd, conversation_id: Default::default() }
+        Self {
+            cwd: env.cwd,
+            conversation_id: Default::default(),
+            diffs: Default::default(),
+        }
     }
 }
@@ -1,4 +1,5 @@
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
@@ -42,6 +43,15 @@ impl<P: ConsoleWriter> SharedSpinner<P> {
             .stop(message)
     }
 
+    /// Replaces the hint shown after the elapsed time, or restores the
+    /// default one with `None`.
+    pub fn set_hint(&self, hint: Option<&str>) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .set_hint(hint)
+    }
+
     /// Resets the stopwatch to zero.
     pub fn reset(&self) {
         self.0.lock().unwrap_or_else(|e| e.into_inner()).reset()
@@ -88,22 +98,77 @@ fn term_width() -> usize {
         .unwrap_or(80)
 }
 
+/// Records terminal resizes reported with `SIGWINCH` until they are taken.
+///
+/// Listening stops when the signal is dropped. Outside a tokio runtime, or on
+/// platforms without `SIGWINCH`, no resize is ever reported.
+struct ResizeSignal {
+    resized: Arc<AtomicBool>,
+    listener: Option<tokio::task::AbortHandle>,
+}
+
+impl ResizeSignal {
+    fn listen() -> Self {
+        let resized = Arc::new(AtomicBool::new(false));
+        #[cfg(unix)]
+        let listener = tokio::runtime::Handle::try_current().ok().map(|handle| {
+            use tokio::signal::unix::{SignalKind, signal};
+
+            let resized = resized.clone();
+            handle
+                .spawn(async move {
+                    let Ok(mut window_change) = signal(SignalKind::window_change()) else {
+                        return;
+                    };
+                    while window_change.recv().await.is_some() {
+                        resized.store(true, Ordering::Relaxed);
+                    }
+                })
+                .abort_handle()
+        });
+        #[cfg(not(unix))]
+        let listener = None;
+        Self { resized, listener }
+    }
+
+    /// Whether the terminal was resized since the last call
+    fn take(&self) -> bool {
+        self.resized.swap(false, Ordering::Relaxed)
+    }
+}
+
+impl Drop for ResizeSignal {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
+        }
+    }
+}
+
 /// Streaming markdown writer with automatic spinner management.
 ///
 /// Coordinates between markdown rendering and spinner visibility:
 /// - Stops spinner when content is being written
 /// - Restarts spinner when idle
+/// - Lays out content still being streamed for the new width when the terminal
+///   is resized
 pub struct StreamingWriter<P: ConsoleWriter> {
     active: Option<ActiveRenderer<P>>,
     spinner: SharedSpinner<P>,
     printer: Arc<P>,
+    resize: ResizeSignal,
 }
 
 impl<P: ConsoleWriter + 'static> StreamingWriter<P> {
     /// Creates a new stream writer with the given shared spinner and output
     /// printer.
     pub fn new(spinner: SharedSpinner<P>, printer: Arc<P>) -> Self {
-        Self { active: None, spinner, printer }
+        Self {
+            active: None,
+            spinner,
+            printer,
+            resize: ResizeSignal::listen(),
+        }
     }
 
     /// Writes markdown content with normal styling.
@@ -125,6 +190,7 @@ impl<P: ConsoleWriter + 'static> StreamingWriter<P> {
     }
 
     fn write_styled(&mut self, text: &str, style: Style) -> Result<()> {
+        self.apply_resize();
         self.ensure_renderer(style)?;
         if let Some(ref mut active) = self.active {
             active.push(text)?;
@@ -132,6 +198,20 @@ impl<P: ConsoleWriter + 'static> StreamingWriter<P> {
         Ok(())
     }
 
+    /// Applies the width of a resized terminal to the active renderer. The
+    /// renderer only writes whole lines, so between two pushes no line is
+    /// half-written at the old width. The spinner line, drawn for the old
+    /// width, is cleared and drawn again with the next line written.
+    fn apply_resize(&mut self) {
+        if !self.resize.take() {
+            return;
+        }
+        if let Some(ref mut active) = self.active {
+            let _ = self.spinner.stop(None);
+            active.renderer.set_width(term_width());
+        }
+    }
+
     fn ensure_renderer(&mut self, new_style: Style) -> Result<()> {
         let needs_switch = self.active.as_ref().is_some_and(|a| a.style != new_style);
 
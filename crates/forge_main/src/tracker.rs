@@ -1,7 +1,37 @@
-use forge_tracker::{EventKind, ToolCallPayload};
+use std::path::PathBuf;
+
+use forge_tracker::{EventKind, OtlpExporter, TelemetryMode, ToolCallPayload};
 
 use crate::TRACKER;
 
+/// Applies the configured telemetry mode to the global tracker
+pub fn configure(mode: forge_config::TelemetryMode, events_path: PathBuf) {
+    let mode = match mode {
+        forge_config::TelemetryMode::Off => TelemetryMode::Off,
+        forge_config::TelemetryMode::Local => TelemetryMode::Local,
+        forge_config::TelemetryMode::Remote => TelemetryMode::Remote,
+    };
+    TRACKER.configure(mode, events_path);
+}
+
+/// Standard OpenTelemetry variable used when no collector is configured
+const OTLP_ENDPOINT_ENV_VAR: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// Builds the OpenTelemetry exporter from the configured collector, falling
+/// back to `OTEL_EXPORTER_OTLP_ENDPOINT`
+pub fn otlp_exporter(config: Option<&forge_config::OtlpConfig>) -> Option<OtlpExporter> {
+    match config {
+        Some(config) => Some(OtlpExporter::new(
+            config.endpoint.clone(),
+            config.headers.clone().into_iter().collect(),
+        )),
+        None => std::env::var(OTLP_ENDPOINT_ENV_VAR)
+            .ok()
+            .filter(|endpoint| !endpoint.is_empty())
+            .map(|endpoint| OtlpExporter::new(endpoint, Default::default())),
+    }
+}
+
 /// Helper functions to eliminate duplication of tokio::spawn + TRACKER patterns
 /// Generic dispatcher for any event
 fn dispatch(event: EventKind) {
@@ -42,6 +72,11 @@ pub fn prompt(text: String) {
     dispatch(EventKind::Prompt(text));
 }
 
+/// For task completion events, used to measure turn latency
+pub fn task_complete() {
+    dispatch(EventKind::TaskComplete);
+}
+
 /// For model setting
 pub fn set_model(model: String) {
     tokio::spawn(TRACKER.set_model(model));
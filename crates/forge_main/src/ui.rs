@@ -12,46 +12,73 @@ use convert_case::{Case, Casing};
 use forge_api::{
     API, AgentId, AnyProvider, ApiKeyRequest, AuthContextRequest, AuthContextResponse, ChatRequest,
     ChatResponse, CodeRequest, ConfigOperation, Conversation, ConversationId, DeviceCodeRequest,
-    Event, InterruptionReason, ModelId, Provider, ProviderId, TextMessage, UserPrompt,
+    Event, InterruptionReason, ModelId, PauseSignal, Provider, ProviderId, TextMessage, UserPrompt,
 };
 use forge_app::utils::{format_display_path, truncate_key};
-use forge_app::{CommitResult, ToolResolver};
+use forge_app::{AgentExt, CommitPlan, CommitResult, TemplateEngine, ToolResolver};
 use forge_config::ForgeConfig;
 use forge_display::MarkdownFormat;
 use forge_domain::{
-    AuthMethod, ChatResponseContent, ConsoleWriter, ContextMessage, Role, TitleFormat, UserCommand,
+    Agent, AuthMethod, ChatResponseContent, ConsoleWriter, ContextMessage, Cron, PackKind,
+    PullRequestDraft, Role, Schedule, TitleFormat, UserCommand,
 };
 use forge_fs::ForgeFS;
 use forge_select::ForgeWidget;
+use forge_server::{ConversationUsage, StreamEvent};
 use forge_spinner::SpinnerManager;
 use forge_tracker::ToolCallPayload;
 use futures::future;
+use strum::IntoEnumIterator;
 use tokio_stream::StreamExt;
 use url::Url;
 
+use crate::agents::{AgentScaffold, open_in_editor};
+use crate::ci::{CiCriteria, CiReport, CiRun};
 use crate::cli::{
-    Cli, CommitCommandGroup, ConversationCommand, ListCommand, McpCommand, TopLevelCommand,
+    CiArgs, Cli, CommitCommandGroup, ConversationCommand, ListCommand, McpCommand, OutputFormat,
+    PackCommand, PrCommandGroup, SandboxCommand, ScheduleCommand, SnapshotCommand, TopLevelCommand,
 };
-use crate::conversation_selector::ConversationSelector;
+use crate::commit::{GroupAction, SplitProgress, format_commit_group};
+use crate::context_preset::PresetContext;
+use crate::conversation_selector::{ConversationSelector, LockedConversationAction};
+use crate::diagnostics::{self, DiagnosticBundle};
+use crate::diffs::DiffHistory;
 use crate::display_constants::{CommandType, headers, markers, status};
 use crate::editor::ReadLineError;
 use crate::info::Info;
 use crate::input::Console;
-use crate::model::{ForgeCommandManager, SlashCommand};
-use crate::porcelain::Porcelain;
-use crate::prompt::ForgePrompt;
+use crate::interrupt::{PAUSING_HINT, PauseAction};
+use crate::model::{
+    AgentsCommand, ContextCommand, ForgeCommandManager, QueueCommand, ReasoningCommand,
+    SlashCommand, SnapshotDiff,
+};
+use crate::porcelain::{DoneStatus, Porcelain, PorcelainEvent};
+use crate::prompt::{ContextUsage, ForgePrompt};
+use crate::queue::{MessageQueue, TypeAhead};
+use crate::sandbox::Sandbox;
 use crate::state::UIState;
 use crate::stream_renderer::{SharedSpinner, StreamingWriter};
 use crate::sync_display::SyncProgressDisplay;
 use crate::title_display::TitleDisplayExt;
 use crate::tools_display::format_tools;
-use crate::update::on_update;
-use crate::utils::humanize_time;
+use crate::update::{on_rollback, on_update};
+use crate::utils::{humanize_time, slugify};
+use crate::workspaces::WorkspaceSessions;
 use crate::zsh::ZshRPrompt;
 use crate::{TRACKER, banner, tracker};
 
 // File-specific constants
 const MISSING_AGENT_TITLE: &str = "<missing agent.title>";
+/// Number of projects listed by `forge stats`
+const MAX_STATS_PROJECTS: usize = 5;
+/// Number of log files and panic reports included in a diagnostic bundle
+const MAX_BUNDLE_FILES: usize = 3;
+/// Maximum size of each log file included in a diagnostic bundle
+const MAX_BUNDLE_LOG_BYTES: usize = 512 * 1024;
+/// Pause between the words of a reply replayed in real time
+const REPLAY_WORD_DELAY: Duration = Duration::from_millis(20);
+/// Pause after a tool call or diff replayed in real time
+const REPLAY_STEP_DELAY: Duration = Duration::from_millis(400);
 
 /// Conversation dump format used by the /dump command
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -99,7 +126,54 @@ fn format_mcp_headers(server: &forge_domain::McpServerConfig) -> Option<String>
     }
 }
 
-pub struct UI<A: ConsoleWriter, F: Fn(ForgeConfig) -> A> {
+/// Formats the metadata of a model shown beside the model picker.
+fn format_model_details(model: &forge_domain::Model, provider_id: &ProviderId) -> String {
+    let capability = |supported: Option<bool>| match supported {
+        Some(true) => status::YES,
+        Some(false) => status::NO,
+        None => markers::EMPTY,
+    };
+    let input = model
+        .input_modalities
+        .iter()
+        .map(|modality| format!("{modality:?}").to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Info::new()
+        .add_title(model.name.as_deref().unwrap_or(model.id.as_str()))
+        .add_key_value("ID", model.id.as_str())
+        .add_key_value("Provider", provider_id.to_string())
+        .add_key_value(
+            "Context Window",
+            model.context_length.map(|limit| limit.to_string()),
+        )
+        .add_key_value("Tools", capability(model.tools_supported))
+        .add_key_value(
+            "Parallel Tools",
+            capability(model.supports_parallel_tool_calls),
+        )
+        .add_key_value("Reasoning", capability(model.supports_reasoning))
+        .add_key_value("Input", input)
+        .add_key_value("Description", model.description.as_deref())
+        .to_string()
+}
+
+/// Describes when a schedule runs next, in local time
+fn format_next_run(cron: &Cron) -> String {
+    match cron.next_after(chrono::Local::now().naive_local()) {
+        Some(time) => time.format("%Y-%m-%d %H:%M").to_string(),
+        None => "never".to_string(),
+    }
+}
+
+/// Prints an event of the NDJSON stream as a line on stdout
+fn print_event(event: &PorcelainEvent) -> Result<()> {
+    println!("{}", event.to_line()?);
+    Ok(())
+}
+
+pub struct UI<A: ConsoleWriter, F: Fn(PathBuf, ForgeConfig) -> A> {
     markdown: MarkdownFormat,
     state: UIState,
     api: Arc<F::Output>,
@@ -108,12 +182,19 @@ pub struct UI<A: ConsoleWriter, F: Fn(ForgeConfig) -> A> {
     command: Arc<ForgeCommandManager>,
     cli: Cli,
     spinner: SharedSpinner<A>,
+    queue: MessageQueue,
+    pause: PauseSignal,
+    /// Conversation this process holds the lock of
+    locked_conversation: Option<ConversationId>,
+    /// Conversation attached read-only because another process holds it
+    read_only_conversation: Option<ConversationId>,
+    workspaces: WorkspaceSessions,
     config: ForgeConfig,
     #[allow(dead_code)] // The guard is kept alive by being held in the struct
     _guard: forge_tracker::Guard,
 }
 
-impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI<A, F> {
+impl<A: API + ConsoleWriter + 'static, F: Fn(PathBuf, ForgeConfig) -> A + Send + Sync> UI<A, F> {
     /// Writes a line to the console output
     /// Takes anything that implements ToString trait
     fn writeln<T: ToString>(&mut self, content: T) -> anyhow::Result<()> {
@@ -157,9 +238,22 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
 
     // Handle creating a new conversation
     async fn on_new(&mut self) -> Result<()> {
-        let config = forge_config::ForgeConfig::read().unwrap_or_default();
+        // The lock belongs to the API about to be replaced
+        if let Some(id) = self.locked_conversation.take() {
+            self.api.unlock_conversation(&id).await?;
+        }
+        self.read_only_conversation = None;
+
+        let config = forge_config::ForgeConfig::read(
+            self.workspaces.current(),
+            self.config.profile.as_deref(),
+        )
+        .unwrap_or_default();
         self.config = config.clone();
-        self.api = Arc::new((self.new_api)(config));
+        self.api = Arc::new((self.new_api)(
+            self.workspaces.current().to_path_buf(),
+            config,
+        ));
         self.init_state(false).await?;
 
         // Set agent if provided via CLI
@@ -209,15 +303,23 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
     /// * `cli` - Parsed command-line arguments
     /// * `config` - Pre-read application configuration for the initial API
     ///   instance
+    /// * `cwd` - Workspace the session starts in
     /// * `f` - Factory closure invoked once at startup and again on each `/new`
-    ///   command; receives the latest [`ForgeConfig`] so that config changes
-    ///   from `forge config set` are reflected in new conversations
-    pub fn init(cli: Cli, config: ForgeConfig, f: F) -> Result<Self> {
+    ///   and `/workspace` command; receives the workspace and the latest
+    ///   [`ForgeConfig`] so that config changes from `forge config set` are
+    ///   reflected in new conversations
+    pub fn init(cli: Cli, config: ForgeConfig, cwd: PathBuf, f: F) -> Result<Self> {
         // Parse CLI arguments first to get flags
-        let api = Arc::new(f(config.clone()));
+        let api = Arc::new(f(cwd.clone(), config.clone()));
         let env = api.environment();
         let command = Arc::new(ForgeCommandManager::default());
         let spinner = SharedSpinner::new(SpinnerManager::new(api.clone()));
+        tracker::configure(config.telemetry, env.telemetry_path());
+        let guard = forge_tracker::init_tracing(
+            env.log_path(),
+            TRACKER.clone(),
+            tracker::otlp_exporter(config.otlp.as_ref()),
+        )?;
         Ok(Self {
             state: Default::default(),
             api,
@@ -230,34 +332,78 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             cli,
             command,
             spinner,
+            queue: MessageQueue::default(),
+            pause: PauseSignal::default(),
+            locked_conversation: None,
+            read_only_conversation: None,
+            workspaces: WorkspaceSessions::new(cwd),
             markdown: MarkdownFormat::new(),
             config,
-            _guard: forge_tracker::init_tracing(env.log_path(), TRACKER.clone())?,
+            _guard: guard,
         })
     }
 
     async fn prompt(&self) -> Result<SlashCommand> {
         // Get usage from current conversation if available
-        let usage = if let Some(conversation_id) = &self.state.conversation_id {
-            self.api
-                .conversation(conversation_id)
-                .await
-                .ok()
-                .flatten()
-                .and_then(|conv| conv.accumulated_usage())
+        let conversation = if let Some(conversation_id) = &self.state.conversation_id {
+            self.api.conversation(conversation_id).await.ok().flatten()
         } else {
             None
         };
+        let usage = conversation
+            .as_ref()
+            .and_then(|conv| conv.accumulated_usage());
 
         // Prompt the user for input
         let agent_id = self.api.get_active_agent().await.unwrap_or_default();
         let model = self
             .get_agent_model(self.api.get_active_agent().await)
             .await;
-        let forge_prompt = ForgePrompt { cwd: self.state.cwd.clone(), usage, model, agent_id };
+        let context = match (conversation.as_ref(), model.as_ref()) {
+            (Some(conversation), Some(model)) => {
+                self.context_usage(conversation, &agent_id, model).await
+            }
+            _ => None,
+        };
+        let forge_prompt =
+            ForgePrompt { cwd: self.state.cwd.clone(), usage, model, agent_id, context };
         self.console.prompt(forge_prompt).await
     }
 
+    /// How much of the model's context window the conversation takes up, and
+    /// the token count at which the agent compacts it
+    async fn context_usage(
+        &self,
+        conversation: &Conversation,
+        agent_id: &AgentId,
+        model: &ModelId,
+    ) -> Option<ContextUsage> {
+        let window = self
+            .api
+            .get_models()
+            .await
+            .ok()?
+            .into_iter()
+            .find(|candidate| &candidate.id == model)?
+            .context_length?;
+        let tokens = *conversation.token_count_with(&self.api.token_counter(model))?;
+        let compact_at = self
+            .api
+            .get_agents()
+            .await
+            .ok()?
+            .into_iter()
+            .find(|agent| &agent.id == agent_id)
+            .and_then(|agent| {
+                agent
+                    .apply_config(&self.config)
+                    .compact
+                    .with_context_length(Some(window))
+                    .token_threshold
+            });
+        Some(ContextUsage { tokens, window, compact_at })
+    }
+
     pub async fn run(&mut self) {
         match self.run_inner().await {
             Ok(_) => {}
@@ -290,6 +436,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         self.trace_user();
         self.hydrate_caches();
         self.init_conversation().await?;
+        self.auto_prune_conversations();
 
         // Check for dispatch flag first
         if let Some(dispatch_json) = self.cli.event.clone() {
@@ -300,7 +447,10 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         let input = self.cli.prompt.clone().or(self.cli.piped_input.clone());
         if let Some(input) = input {
             tracker::prompt(input.clone());
-            self.spinner.start(None)?;
+            // The spinner would garble the event stream
+            if self.cli.output_format == OutputFormat::Text {
+                self.spinner.start(None)?;
+            }
             tokio::select! {
                 _ = tokio::signal::ctrl_c() => {
                     tracing::info!("User interrupted operation with Ctrl+C");
@@ -314,6 +464,8 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             return Ok(());
         }
 
+        self.recover_draft()?;
+
         // Get initial input from prompt
         // Prompt can fail if it doesn't have access to TTY. If it fails the first time,
         // we will stop everything and bubble up the error.
@@ -322,28 +474,69 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         loop {
             match command {
                 Ok(command) => {
-                    tokio::select! {
-                        _ = tokio::signal::ctrl_c() => {
+                    // Input typed while the agent works is queued for the following turns
+                    let runs_turn = command.runs_turn();
+                    let type_ahead = runs_turn.then(|| self.start_type_ahead());
+                    let pause = self.pause.clone();
+                    let spinner = self.spinner.clone();
+                    let outcome = {
+                        let turn = self.on_command(command);
+                        tokio::pin!(turn);
+                        loop {
+                            tokio::select! {
+                                _ = tokio::signal::ctrl_c() => {
+                                    // A first Ctrl+C pauses the turn once the tool call in
+                                    // progress is done, a second one aborts it right away
+                                    if !runs_turn || pause.is_requested() {
+                                        break None;
+                                    }
+                                    tracing::info!("User paused operation with Ctrl+C");
+                                    pause.request();
+                                    spinner.set_hint(Some(PAUSING_HINT));
+                                }
+                                result = &mut turn => break Some(result),
+                            }
+                        }
+                    };
+
+                    match outcome {
+                        // Dropping the turn aborts the agent and kills the commands it started
+                        None => {
+                            self.spinner.stop(None)?;
                             self.spinner.reset();
                             tracing::info!("User interrupted operation with Ctrl+C");
+                            self.writeln_title(TitleFormat::error("Aborted"))?;
+                            self.hold_queue()?;
                         }
-                        result = self.on_command(command) => {
-                            match result {
-                                Ok(exit) => if exit {return Ok(())},
-                                Err(error) => {
-                                    if let Some(conversation_id) = self.state.conversation_id.as_ref()
-                                        && let Some(conversation) = self.api.conversation(conversation_id).await.ok().flatten() {
-                                            TRACKER.set_conversation(conversation).await;
-                                        }
-                                    tracker::error(&error);
-                                    tracing::error!(error = ?error);
-                                    self.spinner.stop(None)?;
-                                    self.writeln_to_stderr(TitleFormat::error(format!("{error:?}")).display().to_string())?;
-                                },
+                        Some(Ok(exit)) => {
+                            if exit {
+                                return Ok(());
                             }
                         }
+                        Some(Err(error)) => {
+                            if let Some(conversation_id) = self.state.conversation_id.as_ref()
+                                && let Some(conversation) =
+                                    self.api.conversation(conversation_id).await.ok().flatten()
+                            {
+                                TRACKER.set_conversation(conversation).await;
+                            }
+                            tracker::error(&error);
+                            tracing::error!(error = ?error);
+                            self.spinner.stop(None)?;
+                            self.writeln_to_stderr(
+                                TitleFormat::error(format!("{error:?}"))
+                                    .display()
+                                    .to_string(),
+                            )?;
+                            self.hold_queue()?;
+                        }
                     }
 
+                    // A pause requested as the turn was finishing has nothing left to pause
+                    self.pause.clear();
+                    if let Some(type_ahead) = type_ahead {
+                        type_ahead.stop();
+                    }
                     self.spinner.stop(None)?;
                 }
                 Err(error) => {
@@ -361,9 +554,208 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     }
                 }
             }
-            // Centralized prompt call at the end of the loop
-            command = self.prompt().await;
+            // Send the messages queued during the turn before asking for more input
+            command = match self.next_queued() {
+                Some(queued) => queued,
+                None => self.prompt().await,
+            };
+        }
+    }
+
+    /// Reads the lines typed while a turn runs into the message queue
+    fn start_type_ahead(&self) -> TypeAhead {
+        let spinner = self.spinner.clone();
+        TypeAhead::start(self.queue.clone(), move |position, message| {
+            let title = TitleFormat::info(format!("Queued #{position}")).sub_title(message);
+            let _ = spinner.write_ln(title.display());
+        })
+    }
+
+    /// Takes the next queued message, announcing it before it is sent
+    fn next_queued(&mut self) -> Option<Result<SlashCommand>> {
+        let message = self.queue.pop()?;
+        let sub_title = match self.queue.len() {
+            0 => message.clone(),
+            remaining => format!("{message} ({remaining} more queued)"),
+        };
+        let announced =
+            self.writeln_title(TitleFormat::action("Sending queued message").sub_title(sub_title));
+        Some(announced.and_then(|_| self.command.parse(&message)))
+    }
+
+    /// Stops sending queued messages after a turn was interrupted or failed,
+    /// so that they can be reviewed first
+    fn hold_queue(&mut self) -> Result<()> {
+        self.queue.hold();
+        let count = self.queue.len();
+        if count > 0 {
+            self.writeln_title(
+                TitleFormat::info(format!("Kept {count} queued message(s)"))
+                    .sub_title("/queue to review them, /queue send to send them"),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Lists, edits or cancels the messages queued while the agent was busy
+    fn on_queue(&mut self, action: QueueCommand) -> Result<()> {
+        match action {
+            QueueCommand::List => {
+                let messages = self.queue.messages();
+                if messages.is_empty() {
+                    self.writeln_title(TitleFormat::info("No queued messages"))?;
+                    return Ok(());
+                }
+                let mut info =
+                    Info::new().add_title(format!("QUEUED MESSAGES ({})", messages.len()));
+                for (index, message) in messages.iter().enumerate() {
+                    info = info.add_key_value(format!("#{}", index + 1), message);
+                }
+                self.writeln(info)?;
+            }
+            QueueCommand::Edit(position) => {
+                let current = self.queue.get(position)?;
+                let edited = ForgeWidget::input(format!("Queued message #{position}"))
+                    .with_default(&current)
+                    .prompt()?;
+                if let Some(edited) = edited.filter(|edited| !edited.trim().is_empty()) {
+                    self.queue.edit(position, edited.trim().to_string())?;
+                    self.writeln_title(TitleFormat::action(format!(
+                        "Updated queued message #{position}"
+                    )))?;
+                }
+            }
+            QueueCommand::Cancel(position) => {
+                let message = self.queue.cancel(position)?;
+                self.writeln_title(
+                    TitleFormat::action(format!("Cancelled queued message #{position}"))
+                        .sub_title(message),
+                )?;
+            }
+            QueueCommand::Clear => {
+                let count = self.queue.clear();
+                self.writeln_title(TitleFormat::action(format!(
+                    "Cleared {count} queued message(s)"
+                )))?;
+            }
+            QueueCommand::Send => {
+                // The main loop picks up the next message once this command returns
+                self.queue.resume();
+                if self.queue.is_empty() {
+                    self.writeln_title(TitleFormat::info("No queued messages"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists the context presets of the workspace or places the files and notes
+    /// of one in the prompt
+    fn on_context(&mut self, action: ContextCommand) -> Result<()> {
+        match action {
+            ContextCommand::List => {
+                if self.config.context_presets.is_empty() {
+                    self.writeln_title(TitleFormat::info(
+                        "No context presets defined, add them under [context_presets.<name>] in .forge.toml",
+                    ))?;
+                    return Ok(());
+                }
+                let mut info = Info::new();
+                for (name, preset) in &self.config.context_presets {
+                    info = info
+                        .add_title(name.to_uppercase())
+                        .add_key_value("files", preset.files.len().to_string())
+                        .add_key_value("globs", preset.globs.join(", "))
+                        .add_key_value("memory", preset.memory.len().to_string());
+                }
+                self.writeln(info)?;
+            }
+            ContextCommand::Use(name) => {
+                let preset = self.config.context_presets.get(&name).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unknown context preset '{name}'; define it under [context_presets.{name}] in .forge.toml"
+                    )
+                })?;
+                let context = PresetContext::resolve(preset, &self.api.environment().cwd)?;
+                for file in &context.missing {
+                    self.writeln_title(TitleFormat::error(format!("File not found: {file}")))?;
+                }
+                self.writeln_title(
+                    TitleFormat::action(format!("Loaded context preset '{name}'")).sub_title(
+                        format!(
+                            "{} file(s), {} note(s)",
+                            context.files.len(),
+                            context.memory.len()
+                        ),
+                    ),
+                )?;
+                self.console.set_buffer(context.to_prompt());
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows the reasoning effort in use, or sets it for every agent until
+    /// Forge exits
+    async fn on_reasoning(&mut self, action: ReasoningCommand) -> Result<()> {
+        match action {
+            ReasoningCommand::Show => {
+                let title = match self.api.get_session_reasoning_effort().await? {
+                    Some(effort) => {
+                        TitleFormat::info(effort.to_string()).sub_title("for this session")
+                    }
+                    None => match self.api.get_reasoning_effort().await? {
+                        Some(effort) => TitleFormat::info(effort.to_string())
+                            .sub_title("from the configuration; agents may set their own"),
+                        None => TitleFormat::info("Reasoning effort not set")
+                            .sub_title("each agent uses its own or the model's default"),
+                    },
+                };
+                self.writeln_title(title)?;
+            }
+            ReasoningCommand::Set(effort) => {
+                self.api
+                    .update_config(vec![ConfigOperation::SetSessionReasoningEffort(Some(
+                        effort.clone(),
+                    ))])
+                    .await?;
+                self.writeln_title(
+                    TitleFormat::action(effort.to_string())
+                        .sub_title("is now the reasoning effort for this session"),
+                )?;
+            }
+            ReasoningCommand::Reset => {
+                self.api
+                    .update_config(vec![ConfigOperation::SetSessionReasoningEffort(None)])
+                    .await?;
+                self.writeln_title(TitleFormat::action(
+                    "Reasoning effort follows the agents and the configuration again",
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Offers to restore input left unsent when the previous session crashed
+    /// or was closed after an accidental Ctrl+C
+    fn recover_draft(&mut self) -> Result<()> {
+        let Some(draft) = self.console.saved_draft() else {
+            return Ok(());
+        };
+
+        let lines = draft.content.lines().count();
+        let restore = ForgeWidget::confirm(format!(
+            "Restore the unsent prompt from {} ({lines} lines)?",
+            humanize_time(draft.saved_at)
+        ))
+        .with_default(true)
+        .prompt()?;
+
+        match restore {
+            Some(true) => self.console.set_buffer(draft.content),
+            _ => self.console.discard_draft(),
         }
+        Ok(())
     }
 
     // Improve startup time by hydrating caches
@@ -380,6 +772,16 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         });
     }
 
+    // Applies the retention policy in the background when auto-pruning is
+    // enabled, sparing the conversation in use
+    fn auto_prune_conversations(&self) {
+        if self.config.retention.as_ref().is_some_and(|r| r.auto_prune) {
+            let api = self.api.clone();
+            let active = self.state.conversation_id;
+            tokio::spawn(async move { api.prune_conversations(active.as_ref(), false).await });
+        }
+    }
+
     async fn handle_generate_conversation_id(&mut self) -> Result<()> {
         let conversation_id = forge_domain::ConversationId::generate();
         println!("{}", conversation_id.into_string());
@@ -393,6 +795,38 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     crate::cli::AgentCommand::List => {
                         self.on_show_agents(agent_group.porcelain, false).await?;
                     }
+                    crate::cli::AgentCommand::History { id } => {
+                        self.on_agent_history(&id, agent_group.porcelain).await?;
+                    }
+                    crate::cli::AgentCommand::Diff { id, from, to } => {
+                        self.on_agent_diff(&id, from, to).await?;
+                    }
+                    crate::cli::AgentCommand::Rollback { id, version } => {
+                        let restored = self.api.rollback_agent(&id, version).await?;
+                        self.writeln_title(
+                            TitleFormat::info(format!(
+                                "Restored agent {} to version {version}",
+                                id.as_str().bold()
+                            ))
+                            .sub_title(restored.path.display().to_string()),
+                        )?;
+                    }
+                    crate::cli::AgentCommand::Pack(command) => {
+                        self.on_pack_command(PackKind::Agent, command, agent_group.porcelain)
+                            .await?;
+                    }
+                }
+                return Ok(());
+            }
+            TopLevelCommand::Skill(skill_group) => {
+                match skill_group.command {
+                    crate::cli::SkillCommand::List { custom } => {
+                        self.on_show_skills(skill_group.porcelain, custom).await?;
+                    }
+                    crate::cli::SkillCommand::Pack(command) => {
+                        self.on_pack_command(PackKind::Skill, command, skill_group.porcelain)
+                            .await?;
+                    }
                 }
                 return Ok(());
             }
@@ -433,6 +867,15 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     ListCommand::Skill { custom } => {
                         self.on_show_skills(porcelain, custom).await?;
                     }
+                    ListCommand::Template => {
+                        self.on_show_templates(porcelain)?;
+                    }
+                    ListCommand::Profile => {
+                        self.on_show_profiles(porcelain)?;
+                    }
+                    ListCommand::Project => {
+                        self.on_show_projects(porcelain).await?;
+                    }
                 }
                 return Ok(());
             }
@@ -558,6 +1001,43 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 self.on_info(porcelain, conversation_id).await?;
                 return Ok(());
             }
+            TopLevelCommand::Stats { porcelain } => {
+                self.on_stats(porcelain).await?;
+                return Ok(());
+            }
+            TopLevelCommand::Serve { addr } => {
+                self.writeln_title(TitleFormat::info(format!(
+                    "Serving REST API on http://{addr} (spec at /openapi.json)"
+                )))?;
+                // Confirmations are sent to the clients instead of blocking on this
+                // terminal
+                let config = self.config.clone().non_interactive(true);
+                self.api = Arc::new((self.new_api)(
+                    self.workspaces.current().to_path_buf(),
+                    config.clone(),
+                ));
+                self.config = config;
+                let server = self.config.server.clone().unwrap_or_default();
+                if server.users.is_empty() && !addr.ip().is_loopback() {
+                    self.writeln_title(TitleFormat::warning(
+                        "No server users are configured; anyone who can reach this address has full access",
+                    ))?;
+                }
+                forge_server::serve(self.api.clone(), server, addr).await?;
+                return Ok(());
+            }
+            TopLevelCommand::Ci(args) => {
+                let exit_code = self.on_ci(args).await;
+                std::process::exit(exit_code);
+            }
+            TopLevelCommand::Schedule(schedule_group) => {
+                self.on_schedule(schedule_group.command).await?;
+                return Ok(());
+            }
+            TopLevelCommand::Sandbox(sandbox_group) => {
+                self.on_sandbox(sandbox_group.command)?;
+                return Ok(());
+            }
             TopLevelCommand::Banner => {
                 banner::display(true)?;
                 return Ok(());
@@ -575,6 +1055,14 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 self.handle_conversation_command(conversation_group).await?;
                 return Ok(());
             }
+            TopLevelCommand::Replay { conversation_id, realtime } => {
+                self.on_replay(conversation_id, realtime).await?;
+                return Ok(());
+            }
+            TopLevelCommand::Snapshot(group) => {
+                self.on_snapshot_command(group.command).await?;
+                return Ok(());
+            }
             TopLevelCommand::Suggest { prompt } => {
                 self.on_cmd(UserPrompt::from(prompt)).await?;
                 return Ok(());
@@ -664,6 +1152,13 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             }
             TopLevelCommand::Commit(commit_group) => {
                 self.init_state(false).await?;
+                if commit_group.split {
+                    let additional_context =
+                        Some(commit_group.text.join(" ")).filter(|text| !text.is_empty());
+                    self.on_commit_split(commit_group.max_diff_size, additional_context)
+                        .await?;
+                    return Ok(());
+                }
                 let preview = commit_group.preview;
                 let result = self.handle_commit_command(commit_group).await?;
                 if preview {
@@ -675,12 +1170,25 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 }
                 return Ok(());
             }
+            TopLevelCommand::Pr(pr_group) => {
+                self.init_state(false).await?;
+                self.on_pull_request(pr_group).await?;
+                return Ok(());
+            }
             TopLevelCommand::Data(data_command_group) => {
                 let mut stream = self.api.generate_data(data_command_group.into()).await?;
                 while let Some(data) = stream.next().await {
                     self.writeln(data?)?;
                 }
             }
+            TopLevelCommand::Templates(templates_command) => {
+                match templates_command {
+                    crate::cli::TemplatesCommand::Check => {
+                        self.on_templates_check().await?;
+                    }
+                }
+                return Ok(());
+            }
             TopLevelCommand::Vscode(vscode_command) => {
                 match vscode_command {
                     crate::cli::VscodeCommand::InstallExtension => {
@@ -690,7 +1198,21 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 return Ok(());
             }
             TopLevelCommand::Update(args) => {
-                let update = forge_config::Update::default().auto_update(args.no_confirm);
+                if args.rollback {
+                    let version = on_rollback(self.api.clone()).await?;
+                    self.writeln_title(TitleFormat::info(format!(
+                        "Rolled back to forge {version}"
+                    )))?;
+                    return Ok(());
+                }
+                let channel = args.channel.map(Into::into).or_else(|| {
+                    self.config
+                        .updates
+                        .as_ref()
+                        .and_then(|update| update.channel)
+                });
+                let mut update = forge_config::Update::default().auto_update(args.no_confirm);
+                update.channel = channel;
                 on_update(self.api.clone(), Some(&update)).await;
                 return Ok(());
             }
@@ -698,8 +1220,12 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 self.on_zsh_setup().await?;
                 return Ok(());
             }
-            TopLevelCommand::Doctor => {
-                self.on_zsh_doctor().await?;
+            TopLevelCommand::Doctor { bundle, output } => {
+                if bundle {
+                    self.on_diagnostic_bundle(output).await?;
+                } else {
+                    self.on_zsh_doctor().await?;
+                }
                 return Ok(());
             }
         }
@@ -802,6 +1328,21 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     name.bold()
                 )))?;
             }
+            ConversationCommand::Pin { id } => {
+                self.validate_conversation_exists(&id).await?;
+
+                self.api.pin_conversation(&id, true).await?;
+                self.writeln_title(TitleFormat::info(format!("Pinned conversation: {id}")))?;
+            }
+            ConversationCommand::Unpin { id } => {
+                self.validate_conversation_exists(&id).await?;
+
+                self.api.pin_conversation(&id, false).await?;
+                self.writeln_title(TitleFormat::info(format!("Unpinned conversation: {id}")))?;
+            }
+            ConversationCommand::Prune { dry_run, porcelain } => {
+                self.on_prune_conversations(dry_run, porcelain).await?;
+            }
         }
 
         Ok(())
@@ -830,6 +1371,55 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Applies the configured retention policy and prints the affected
+    /// conversations
+    ///
+    /// # Arguments
+    /// * `dry_run` - If true, report what would be deleted without deleting
+    /// * `porcelain` - If true, output in machine-readable format
+    async fn on_prune_conversations(&mut self, dry_run: bool, porcelain: bool) -> Result<()> {
+        self.spinner.start(Some("Pruning conversations"))?;
+        let report = self
+            .api
+            .prune_conversations(self.state.conversation_id.as_ref(), dry_run)
+            .await?;
+        self.spinner.stop(None)?;
+
+        let mut info = Info::new();
+        for conversation in &report.pruned {
+            info = info
+                .add_title(conversation.id)
+                .add_key_value(
+                    "Title",
+                    conversation
+                        .title
+                        .clone()
+                        .unwrap_or_else(|| markers::EMPTY.to_string()),
+                )
+                .add_key_value("Updated", humanize_time(conversation.updated_at))
+                .add_key_value("Size", format!("{} bytes", conversation.size_bytes))
+                .add_key_value("Reason", conversation.reason.to_string());
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).truncate(1, 60).uppercase_headers();
+            self.writeln(porcelain)?;
+            return Ok(());
+        }
+
+        if !report.pruned.is_empty() {
+            self.writeln(info)?;
+        }
+
+        let action = if dry_run { "Would prune" } else { "Pruned" };
+        self.writeln_title(TitleFormat::info(format!(
+            "{action} {} conversation(s), reclaiming {} bytes",
+            report.pruned.len(),
+            report.reclaimed_bytes()
+        )))?;
+        Ok(())
+    }
+
     /// Handle `mcp login <name>` command.
     ///
     /// Triggers the OAuth authentication flow for the specified MCP server.
@@ -950,7 +1540,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         &mut self,
         provider_group: crate::cli::ProviderCommandGroup,
     ) -> anyhow::Result<()> {
-        use crate::cli::ProviderCommand;
+        use crate::cli::{ProviderCacheCommand, ProviderCommand};
 
         match provider_group.command {
             ProviderCommand::Login { provider } => {
@@ -963,11 +1553,40 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 self.on_show_providers(provider_group.porcelain, types)
                     .await?;
             }
+            ProviderCommand::Cache(ProviderCacheCommand::Info) => {
+                self.on_response_cache_info(provider_group.porcelain)
+                    .await?;
+            }
+            ProviderCommand::Cache(ProviderCacheCommand::Clear) => {
+                let count = self.api.clear_response_cache().await?;
+                self.writeln_title(TitleFormat::action(format!(
+                    "Cleared {count} cached response(s)"
+                )))?;
+            }
         }
 
         Ok(())
     }
 
+    async fn on_response_cache_info(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        let cache = self.api.response_cache_info().await?;
+        let info = Info::new()
+            .add_title("RESPONSE CACHE")
+            .add_key_value("Enabled", cache.enabled.to_string())
+            .add_key_value("Entries", cache.entries.to_string())
+            .add_key_value(
+                "Size",
+                format!("{:.1} MB", cache.size_bytes as f64 / (1024.0 * 1024.0)),
+            );
+
+        if porcelain {
+            self.writeln(Porcelain::from(&info).uppercase_headers())?;
+        } else {
+            self.writeln(info)?;
+        }
+        Ok(())
+    }
+
     async fn handle_provider_login(
         &mut self,
         provider_id: Option<&ProviderId>,
@@ -1089,35 +1708,158 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         }
     }
 
-    /// Builds an Info structure for agents with their details
-    async fn build_agents_info(&self, custom: bool) -> anyhow::Result<Info> {
-        let mut agents = self.api.get_agents().await?;
-        // Sort agents alphabetically by ID
-        agents.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+    /// Splits the pending changes into logical commits and commits each
+    /// group the user approves, restoring the index to the remaining changes
+    /// afterwards
+    async fn on_commit_split(
+        &mut self,
+        max_diff_size: Option<usize>,
+        additional_context: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.spinner.start(Some("Planning commits"))?;
+        let plan = self
+            .api
+            .plan_commits(max_diff_size, additional_context)
+            .await;
+        self.spinner.stop(None)?;
+        let mut plan = plan?;
 
-        // Filter agents based on custom flag
-        if custom {
-            agents.retain(|agent| agent.path.is_some());
-        }
+        let mut progress = SplitProgress::default();
+        let result = self.review_commit_groups(&mut plan, &mut progress).await;
 
-        let mut info = Info::new();
+        // Restore the index even when a commit failed halfway through
+        let staged = progress.staged_after(&plan);
+        self.api.stage_commit_groups(&plan, &staged).await?;
+        result?;
 
-        for agent in agents.iter() {
-            let id = agent.id.as_str().to_string();
-            let title = agent
-                .title
-                .as_deref()
-                .map(|title| title.lines().collect::<Vec<_>>().join(" "));
+        self.writeln_title(
+            TitleFormat::info(format!(
+                "Created {} of {} commits",
+                progress.committed.len(),
+                plan.groups.len()
+            ))
+            .sub_title(format!("{} skipped", progress.skipped.len())),
+        )?;
+        Ok(())
+    }
 
-            // Get provider and model for this agent
-            let provider_name = match self.get_provider(Some(agent.id.clone())).await {
-                Ok(p) => p.id.to_string(),
-                Err(e) => format!("Error: [{}]", e),
-            };
+    /// Generates a pull request for the current branch, lets the user adjust
+    /// it in their editor and opens it
+    async fn on_pull_request(&mut self, args: PrCommandGroup) -> anyhow::Result<()> {
+        let base = match args.base {
+            Some(base) => base,
+            None => self.api.default_branch(&args.remote).await?,
+        };
+        let additional_context = Some(args.text.join(" ")).filter(|text| !text.is_empty());
 
-            let model_name = agent.model.as_str().to_string();
+        self.spinner.start(Some("Writing pull request"))?;
+        let draft = self
+            .api
+            .pull_request_draft(&args.remote, &base, args.max_diff_size, additional_context)
+            .await;
+        self.spinner.stop(None)?;
+        let draft = draft?;
 
-            let reasoning = if agent
+        if args.preview {
+            self.writeln(draft.to_string())?;
+            return Ok(());
+        }
+
+        let file = tempfile::Builder::new()
+            .prefix("forge-pr-")
+            .suffix(".md")
+            .tempfile()
+            .context("Failed to create pull request file")?;
+        std::fs::write(file.path(), draft.to_string())?;
+        open_in_editor(file.path())?;
+        let Some(draft) = PullRequestDraft::parse(&std::fs::read_to_string(file.path())?) else {
+            self.writeln_title(TitleFormat::info(
+                "Pull request not opened: the title is empty",
+            ))?;
+            return Ok(());
+        };
+
+        self.spinner.start(Some("Opening pull request"))?;
+        let url = self
+            .api
+            .create_pull_request(&args.remote, &base, &draft)
+            .await;
+        self.spinner.stop(None)?;
+        self.writeln_title(TitleFormat::action("Opened pull request").sub_title(url?.to_string()))?;
+        Ok(())
+    }
+
+    /// Asks the user to commit, edit or skip each group of `plan` in order
+    async fn review_commit_groups(
+        &mut self,
+        plan: &mut CommitPlan,
+        progress: &mut SplitProgress,
+    ) -> anyhow::Result<()> {
+        let total = plan.groups.len();
+        let mut index = 0;
+        while index < total {
+            self.writeln(format_commit_group(index, total, &plan.groups[index]))?;
+            let actions = GroupAction::iter().collect::<Vec<_>>();
+            match ForgeWidget::select("What should be done with this commit?", actions).prompt()? {
+                Some(GroupAction::Commit) => {
+                    self.api
+                        .stage_commit_groups(plan, &progress.staged_for(index))
+                        .await?;
+                    let result = self
+                        .api
+                        .commit_staged(plan.groups[index].message.clone())
+                        .await?;
+                    self.writeln_title(TitleFormat::action("Committed").sub_title(result.message))?;
+                    progress.committed.push(index);
+                    index += 1;
+                }
+                Some(GroupAction::Edit) => {
+                    if let Some(message) = ForgeWidget::input("Commit message")
+                        .with_default(&plan.groups[index].message)
+                        .prompt()?
+                    {
+                        plan.groups[index].message = message;
+                    }
+                }
+                Some(GroupAction::Skip) => {
+                    progress.skipped.push(index);
+                    index += 1;
+                }
+                Some(GroupAction::Abort) | None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds an Info structure for agents with their details
+    async fn build_agents_info(&self, custom: bool) -> anyhow::Result<Info> {
+        let mut agents = self.api.get_agents().await?;
+        // Sort agents alphabetically by ID
+        agents.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+
+        // Filter agents based on custom flag
+        if custom {
+            agents.retain(|agent| agent.path.is_some());
+        }
+
+        let mut info = Info::new();
+
+        for agent in agents.iter() {
+            let id = agent.id.as_str().to_string();
+            let title = agent
+                .title
+                .as_deref()
+                .map(|title| title.lines().collect::<Vec<_>>().join(" "));
+
+            // Get provider and model for this agent
+            let provider_name = match self.get_provider(Some(agent.id.clone())).await {
+                Ok(p) => p.id.to_string(),
+                Err(e) => format!("Error: [{}]", e),
+            };
+
+            let model_name = agent.model.as_str().to_string();
+
+            let reasoning = if agent
                 .reasoning
                 .as_ref()
                 .and_then(|a| a.enabled)
@@ -1403,6 +2145,35 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Lists the projects of the monorepo in the working directory
+    async fn on_show_projects(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        let projects = self.api.get_projects().await?;
+        if projects.is_empty() {
+            self.writeln_title(TitleFormat::info(
+                "No workspace projects found in the current directory",
+            ))?;
+            return Ok(());
+        }
+
+        let mut info = Info::new();
+        for project in projects {
+            info = info
+                .add_title(project.name.clone())
+                .add_key_value("name", project.name)
+                .add_key_value("path", project.path.display().to_string())
+                .add_key_value("kind", project.kind.to_string());
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).drop_col(0).uppercase_headers();
+            self.writeln(porcelain)?;
+        } else {
+            self.writeln(info)?;
+        }
+
+        Ok(())
+    }
+
     /// Lists available skills
     async fn on_show_skills(&mut self, porcelain: bool, custom: bool) -> anyhow::Result<()> {
         let skills = self.api.get_skills().await?;
@@ -1445,6 +2216,421 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Lists the embedded prompt templates replaced by the user's files
+    fn on_show_templates(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        let overrides = TemplateEngine::overrides();
+        if overrides.is_empty() {
+            let dir = forge_config::ConfigReader::base_path().join("templates");
+            self.writeln_title(TitleFormat::info(format!(
+                "No templates overridden, add files to {} to replace the built-in ones",
+                dir.display()
+            )))?;
+            return Ok(());
+        }
+
+        let mut info = Info::new();
+        let env = self.api.environment();
+        for template in overrides {
+            info = info
+                .add_title(template.name.to_uppercase())
+                .add_key_value("name", template.name)
+                .add_key_value("path", format_display_path(&template.path, &env.cwd));
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).drop_col(0).uppercase_headers();
+            self.writeln(porcelain)?;
+        } else {
+            self.writeln(info)?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists the profiles defined in the configuration, marking the active one
+    fn on_show_profiles(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        if self.config.profiles.is_empty() {
+            self.writeln_title(TitleFormat::info(
+                "No profiles defined, add them under [profiles.<name>] in .forge.toml",
+            ))?;
+            return Ok(());
+        }
+
+        let mut info = Info::new();
+        for (name, profile) in &self.config.profiles {
+            let active = self.config.profile.as_ref() == Some(name);
+            let session = profile.session.as_ref();
+            let effort = profile
+                .reasoning
+                .as_ref()
+                .and_then(|reasoning| reasoning.effort.as_ref());
+            info = info
+                .add_title(name.to_uppercase())
+                .add_key_value("name", name)
+                .add_key_value("active", active.to_string())
+                .add_key_value("provider", session.map(|s| &s.provider_id))
+                .add_key_value("model", session.map(|s| &s.model_id))
+                .add_key_value("api key var", profile.api_key_var.as_ref())
+                .add_key_value("restricted", profile.restricted.map(|r| r.to_string()))
+                .add_key_value("reasoning effort", effort.map(|e| e.to_string()));
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).drop_col(0).uppercase_headers();
+            self.writeln(porcelain)?;
+        } else {
+            self.writeln(info)?;
+        }
+
+        Ok(())
+    }
+
+    /// Switches to the profile `name` and saves it as the active profile
+    async fn on_set_profile(&mut self, name: String) -> anyhow::Result<()> {
+        if !self.config.profiles.contains_key(&name) {
+            let available = self
+                .config
+                .profiles
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow::bail!("Unknown profile '{name}'. Available profiles: {available}");
+        }
+
+        self.api
+            .update_config(vec![ConfigOperation::SetProfile(Some(name.clone()))])
+            .await?;
+        self.config = ForgeConfig::read(self.workspaces.current(), Some(&name))?;
+        self.writeln_title(TitleFormat::action(name).sub_title("is now the active profile"))?;
+        Ok(())
+    }
+
+    /// Switches the session to another workspace: the workspace's
+    /// configuration, policies, instructions and commands apply from now on.
+    /// The conversation left is kept for when the session switches back, and
+    /// the one last used in the new workspace is resumed.
+    async fn on_workspace(&mut self, path: Option<String>) -> anyhow::Result<()> {
+        let path = match path {
+            Some(path) => {
+                let path = self.workspaces.current().join(path);
+                path.canonicalize()
+                    .with_context(|| format!("Workspace {} not found", path.display()))?
+            }
+            None => {
+                let current = self.workspaces.current().to_path_buf();
+                let others = self
+                    .workspaces
+                    .paths()
+                    .filter(|path| *path != current)
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>();
+                if others.is_empty() {
+                    self.writeln_title(
+                        TitleFormat::info(current.display().to_string())
+                            .sub_title("switch to another one with /workspace <path>"),
+                    )?;
+                    return Ok(());
+                }
+                match ForgeWidget::select("Switch to workspace", others).prompt()? {
+                    Some(path) => PathBuf::from(path),
+                    None => return Ok(()),
+                }
+            }
+        };
+        if !path.is_dir() {
+            anyhow::bail!("{} is not a directory", path.display());
+        }
+        if path == self.workspaces.current() {
+            self.writeln_title(
+                TitleFormat::info(path.display().to_string()).sub_title("is already in use"),
+            )?;
+            return Ok(());
+        }
+
+        // The configuration of the workspace is read and validated before any
+        // state changes, so a failure leaves the session in the current one.
+        // Only the keys a repository may safely set are read from it.
+        let config = ForgeConfig::read(&path, self.config.profile.as_deref())
+            .with_context(|| format!("Failed to read the configuration of {}", path.display()))?;
+        std::env::set_current_dir(&path)
+            .with_context(|| format!("Failed to switch to {}", path.display()))?;
+
+        // The lock belongs to the API about to be replaced
+        if let Some(id) = self.locked_conversation.take() {
+            self.api.unlock_conversation(&id).await?;
+        }
+        self.read_only_conversation = None;
+        let conversation_id = self
+            .workspaces
+            .switch(path.clone(), self.state.conversation_id);
+
+        self.config = config;
+        self.api = Arc::new((self.new_api)(path.clone(), self.config.clone()));
+        self.init_state(false).await?;
+        self.console = Console::new(
+            self.api.environment(),
+            self.config.custom_history_path.clone(),
+            self.command.clone(),
+        );
+        if let Some(agent_id) = self.cli.agent.clone() {
+            self.api.set_active_agent(agent_id).await?;
+        }
+        self.state.conversation_id = conversation_id;
+        self.cli.conversation = None;
+        self.cli.conversation_id = None;
+        self.spinner.reset();
+        self.hydrate_caches();
+
+        let title = TitleFormat::action(format!("Switched to {}", path.display()));
+        self.writeln_title(match conversation_id {
+            Some(id) => title.sub_title(format!("resumed conversation {id}")),
+            None => title,
+        })?;
+        Ok(())
+    }
+
+    /// Renders all templates and agent prompts with sample data, failing when
+    /// any of them does not render
+    async fn on_templates_check(&mut self) -> anyhow::Result<()> {
+        let agents = self.api.get_agents().await?;
+        let prompts = agents
+            .into_iter()
+            .flat_map(|agent| {
+                let system = agent
+                    .system_prompt
+                    .map(|prompt| (format!("{} system prompt", agent.id), prompt.template));
+                let user = agent
+                    .user_prompt
+                    .map(|prompt| (format!("{} user prompt", agent.id), prompt.template));
+                system.into_iter().chain(user)
+            })
+            .collect();
+
+        let checks = TemplateEngine::default().check(prompts);
+        let total = checks.len();
+        let mut failed = 0;
+        for check in checks {
+            match check.error {
+                Some(error) => {
+                    failed += 1;
+                    self.writeln_title(TitleFormat::error(check.name).sub_title(error))?;
+                }
+                None => self.writeln_title(TitleFormat::debug(check.name))?,
+            }
+        }
+
+        if failed > 0 {
+            anyhow::bail!("{failed} of {total} templates failed to render");
+        }
+        self.writeln_title(TitleFormat::info(format!("All {total} templates rendered")))?;
+        Ok(())
+    }
+
+    /// Lists the recorded versions of a custom agent
+    async fn on_agent_history(
+        &mut self,
+        agent_id: &AgentId,
+        porcelain: bool,
+    ) -> anyhow::Result<()> {
+        let versions = self.api.agent_versions(agent_id).await?;
+        if versions.is_empty() {
+            self.writeln_title(TitleFormat::info(format!(
+                "No recorded versions for agent {}",
+                agent_id.as_str().bold()
+            )))?;
+            return Ok(());
+        }
+
+        let env = self.api.environment();
+        let mut info = Info::new();
+        for version in versions.iter().rev() {
+            info = info
+                .add_title(format!("v{}", version.version))
+                .add_key_value("Path", format_display_path(&version.path, &env.cwd))
+                .add_key_value("Hash", version.hash.chars().take(7).collect::<String>())
+                .add_key_value("Recorded", humanize_time(version.recorded_at));
+        }
+
+        if porcelain {
+            self.writeln(Porcelain::from(&info).uppercase_headers())?;
+        } else {
+            self.writeln(info)?;
+        }
+        Ok(())
+    }
+
+    /// Shows usage statistics aggregated from local telemetry events
+    async fn on_stats(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        let path = self.api.environment().telemetry_path();
+        if !path.exists() {
+            self.writeln_title(TitleFormat::info(
+                "No local telemetry recorded. Set `telemetry = \"local\"` in .forge.toml or FORGE_TRACKER=local to collect usage statistics",
+            ))?;
+            return Ok(());
+        }
+
+        let stats = forge_tracker::UsageStats::read(&path)
+            .await
+            .map_err(|error| anyhow::anyhow!("Failed to read {}: {error:?}", path.display()))?;
+
+        let mut info = Info::new()
+            .add_title("USAGE")
+            .add_key_value("Events", stats.events.to_string())
+            .add_key_value("Prompts", stats.prompts.to_string())
+            .add_key_value("Errors", stats.errors.to_string())
+            .add_key_value(
+                "Average Turn",
+                stats
+                    .average_turn_latency
+                    .map_or("-".to_string(), |latency| {
+                        humantime::format_duration(Duration::from_secs(latency.as_secs()))
+                            .to_string()
+                    }),
+            );
+
+        if !stats.tools.is_empty() {
+            info = info.add_title("TOOLS");
+            for tool in &stats.tools {
+                info = info.add_key_value(
+                    &tool.name,
+                    format!(
+                        "{} calls, {:.1}% failed",
+                        tool.calls,
+                        tool.failure_rate() * 100.0
+                    ),
+                );
+            }
+        }
+
+        if !stats.projects.is_empty() {
+            info = info.add_title("PROJECTS");
+            for project in stats.projects.iter().take(MAX_STATS_PROJECTS) {
+                info = info.add_key_value(&project.path, format!("{} prompts", project.prompts));
+            }
+        }
+
+        if porcelain {
+            self.writeln(Porcelain::from(&info).uppercase_headers())?;
+        } else {
+            self.writeln(info)?;
+        }
+        Ok(())
+    }
+
+    /// Shows the changes between two versions of a custom agent
+    async fn on_agent_diff(
+        &mut self,
+        agent_id: &AgentId,
+        from: u32,
+        to: Option<u32>,
+    ) -> anyhow::Result<()> {
+        let to = match to {
+            Some(to) => to,
+            None => self
+                .api
+                .agent_versions(agent_id)
+                .await?
+                .last()
+                .map(|version| version.version)
+                .with_context(|| format!("No recorded versions for agent '{agent_id}'"))?,
+        };
+
+        let old = self.api.agent_version_content(agent_id, from).await?;
+        let new = self.api.agent_version_content(agent_id, to).await?;
+        let result = forge_display::DiffFormat::format(&old, &new);
+
+        self.writeln_title(TitleFormat::info(format!(
+            "{} v{from} → v{to}",
+            agent_id.as_str().bold()
+        )))?;
+        if result.lines_added() == 0 && result.lines_removed() == 0 {
+            self.writeln("No changes")?;
+        } else {
+            self.writeln(result.diff())?;
+        }
+        Ok(())
+    }
+
+    /// Installs, removes, lists or checks skill and agent packs
+    async fn on_pack_command(
+        &mut self,
+        kind: PackKind,
+        command: PackCommand,
+        porcelain: bool,
+    ) -> anyhow::Result<()> {
+        // Abbreviated commit hash, matching `git log --oneline`
+        let short = |commit: &str| commit.chars().take(7).collect::<String>();
+
+        let info = match command {
+            PackCommand::Add { source } => {
+                self.spinner.start(Some("Installing"))?;
+                let pack = self.api.install_pack(kind, &source).await?;
+                self.spinner.stop(None)?;
+                self.writeln_title(
+                    TitleFormat::info(format!("Installed {kind} pack {}", pack.name.bold()))
+                        .sub_title(short(&pack.commit)),
+                )?;
+                return Ok(());
+            }
+            PackCommand::Remove { name } => {
+                self.api.remove_pack(kind, &name).await?;
+                self.writeln_title(TitleFormat::info(format!(
+                    "Removed {kind} pack {}",
+                    name.bold()
+                )))?;
+                return Ok(());
+            }
+            PackCommand::Installed => {
+                let mut info = Info::new();
+                for pack in self.api.list_packs(kind).await? {
+                    info = info
+                        .add_title(pack.name.clone())
+                        .add_key_value("Source", pack.source().to_string())
+                        .add_key_value("Commit", short(&pack.commit))
+                        .add_key_value("Installed", humanize_time(pack.installed_at));
+                }
+                info
+            }
+            PackCommand::Check => {
+                self.spinner.start(Some("Checking"))?;
+                let statuses = self.api.check_packs(kind).await?;
+                self.spinner.stop(None)?;
+
+                let mut info = Info::new();
+                for status in statuses {
+                    let state = if !status.intact {
+                        "modified"
+                    } else if status.has_update() {
+                        "update available"
+                    } else {
+                        "up to date"
+                    };
+                    info = info
+                        .add_title(status.pack.name.clone())
+                        .add_key_value("Commit", short(&status.pack.commit))
+                        .add_key_value(
+                            "Latest",
+                            status
+                                .latest_commit
+                                .as_deref()
+                                .map(short)
+                                .unwrap_or_else(|| markers::EMPTY.to_string()),
+                        )
+                        .add_key_value("Status", state);
+                }
+                info
+            }
+        };
+
+        if porcelain {
+            self.writeln(Porcelain::from(&info).uppercase_headers())?;
+        } else {
+            self.writeln(info)?;
+        }
+        Ok(())
+    }
+
     /// Lists current configuration values
     async fn on_show_config(&mut self, porcelain: bool) -> anyhow::Result<()> {
         // Get the effective resolved config
@@ -1468,6 +2654,38 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Lists every effective configuration value with the source that set it
+    fn on_show_config_origins(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        let (_, entries) = ForgeConfig::read_with_origins(
+            self.workspaces.current(),
+            self.config.profile.as_deref(),
+        )?;
+        let display = |value: serde_json::Value| match value {
+            serde_json::Value::String(value) => value,
+            value => value.to_string(),
+        };
+
+        if porcelain {
+            let mut info = Info::new();
+            for entry in entries {
+                info = info
+                    .add_title(entry.key)
+                    .add_key_value("value", display(entry.value))
+                    .add_key_value("origin", entry.origin.to_string());
+            }
+            self.writeln(Porcelain::from(&info).uppercase_headers())?;
+        } else {
+            let mut info = Info::new().add_title("CONFIGURATION");
+            for entry in entries {
+                let origin = format!("({})", entry.origin).dimmed();
+                info = info.add_key_value(entry.key, format!("{} {origin}", display(entry.value)));
+            }
+            self.writeln(info)?;
+        }
+
+        Ok(())
+    }
+
     /// Displays available tools for the current agent
     async fn on_show_tools(&mut self, agent_id: AgentId, porcelain: bool) -> anyhow::Result<()> {
         self.spinner.start(Some("Loading"))?;
@@ -1649,6 +2867,71 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Writes an archive with version info, redacted config, recent logs,
+    /// panic reports and the last conversation for attaching to bug reports,
+    /// to `output` or else into the diagnostics directory of the data directory
+    async fn on_diagnostic_bundle(&mut self, output: Option<PathBuf>) -> anyhow::Result<()> {
+        self.spinner.start(Some("Collecting diagnostics"))?;
+        let env = self.api.environment();
+        let mut bundle = DiagnosticBundle::default();
+
+        bundle.add(
+            "system.txt",
+            format!(
+                "version: {}\nos: {} ({})\nshell: {}\n",
+                forge_tracker::VERSION,
+                env.os,
+                std::env::consts::ARCH,
+                env.shell
+            ),
+        );
+
+        let mut config = serde_json::to_value(&self.config)?;
+        forge_domain::redact_json(&mut config);
+        bundle.add("config.json", serde_json::to_string_pretty(&config)?);
+
+        bundle.add_recent_files(
+            "logs",
+            &env.log_path(),
+            MAX_BUNDLE_FILES,
+            MAX_BUNDLE_LOG_BYTES,
+        )?;
+        bundle.add_recent_files(
+            "crashes",
+            &diagnostics::crash_path(&env.base_path),
+            MAX_BUNDLE_FILES,
+            MAX_BUNDLE_LOG_BYTES,
+        )?;
+
+        if let Some(conversation) = self.api.last_conversation().await? {
+            let mut conversation = serde_json::to_value(&conversation)?;
+            forge_domain::redact_json(&mut conversation);
+            bundle.add(
+                "conversation.json",
+                serde_json::to_string_pretty(&conversation)?,
+            );
+        }
+
+        let path = match output {
+            Some(output) => env.cwd.join(output),
+            None => diagnostics::bundle_path(&env.base_path).join(format!(
+                "forge-diagnostics-{}.tar.gz",
+                chrono::Utc::now().format("%Y%m%d-%H%M%S")
+            )),
+        };
+        bundle.write(&path)?;
+        self.spinner.stop(None)?;
+
+        self.writeln_title(
+            TitleFormat::action(format!(
+                "Diagnostic bundle written to {}",
+                format_display_path(&path, &env.cwd)
+            ))
+            .sub_title("Review it for sensitive data before attaching it to an issue"),
+        )?;
+        Ok(())
+    }
+
     /// Run ZSH environment diagnostics
     async fn on_zsh_doctor(&mut self) -> anyhow::Result<()> {
         // Stop spinner before streaming output to avoid interference
@@ -1671,28 +2954,30 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
-    /// Install the Forge VS Code extension
+    /// Install the Forge extension in VS Code or the fork whose terminal
+    /// Forge runs in
     async fn on_vscode_extension_install(&mut self) -> anyhow::Result<()> {
+        let editor = crate::vscode::detect_editor().unwrap_or_default();
         self.spinner
-            .start(Some("Installing Forge VS Code extension"))?;
+            .start(Some(&format!("Installing Forge {editor} extension")))?;
 
-        match crate::vscode::install_extension() {
+        match crate::vscode::install_extension(editor) {
             Ok(true) => {
                 self.spinner.stop(None)?;
-                self.writeln_title(TitleFormat::info(
-                    "Forge VS Code extension installed successfully",
-                ))?;
+                self.writeln_title(TitleFormat::info(format!(
+                    "Forge {editor} extension installed successfully"
+                )))?;
             }
             Ok(false) => {
                 self.spinner.stop(None)?;
-                self.writeln_title(TitleFormat::error(
-                    "Failed to install Forge VS Code extension.",
-                ))?;
+                self.writeln_title(TitleFormat::error(format!(
+                    "Failed to install Forge {editor} extension."
+                )))?;
             }
             Err(e) => {
                 self.spinner.stop(None)?;
                 self.writeln_title(TitleFormat::error(format!(
-                    "Failed to install Forge VS Code extension: {e}"
+                    "Failed to install Forge {editor} extension: {e}"
                 )))?;
             }
         }
@@ -1927,6 +3212,19 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             SlashCommand::Rename(ref name) => {
                 self.handle_rename_conversation(name.clone()).await?;
             }
+            SlashCommand::Queue(ref action) => {
+                self.on_queue(action.clone())?;
+            }
+            SlashCommand::Context(ref action) => {
+                self.on_context(action.clone())?;
+            }
+            SlashCommand::Reasoning(ref action) => {
+                self.on_reasoning(action.clone()).await?;
+            }
+            SlashCommand::Title(ref title) => match title {
+                Some(title) => self.handle_rename_conversation(title.clone()).await?,
+                None => self.on_show_title().await?,
+            },
             SlashCommand::Dump { html } => {
                 self.spinner.start(Some("Dumping"))?;
                 self.on_dump(html).await?;
@@ -1978,9 +3276,14 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             SlashCommand::Shell(ref command) => {
                 self.api.execute_shell_command_raw(command).await?;
             }
-            SlashCommand::Commit { max_diff_size } => {
+            SlashCommand::Commit { max_diff_size, split: true } => {
+                self.on_commit_split(max_diff_size.or(Some(100_000)), None)
+                    .await?;
+            }
+            SlashCommand::Commit { max_diff_size, split: false } => {
                 let args = CommitCommandGroup {
                     preview: true,
+                    split: false,
                     max_diff_size: max_diff_size.or(Some(100_000)),
                     diff: None,
                     text: Vec::new(),
@@ -2079,6 +3382,31 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 let working_dir = self.state.cwd.clone();
                 self.on_index(working_dir, false).await?;
             }
+            SlashCommand::Diff(path) => {
+                self.on_diff(path)?;
+            }
+            SlashCommand::Snapshot(diff) => {
+                self.on_snapshot_diff(diff).await?;
+            }
+            SlashCommand::Profile(name) => match name {
+                Some(name) => self.on_set_profile(name).await?,
+                None => self.on_show_profiles(false)?,
+            },
+            SlashCommand::Workspace(path) => {
+                self.on_workspace(path).await?;
+            }
+            SlashCommand::History { all, query } => {
+                self.on_history(all, query)?;
+            }
+            SlashCommand::Instructions => {
+                self.on_instructions().await?;
+            }
+            SlashCommand::Agents(command) => match command {
+                AgentsCommand::List => self.on_show_agents(false, false).await?,
+                AgentsCommand::Show(id) => self.on_agent_show(id).await?,
+                AgentsCommand::New => self.on_agent_new().await?,
+                AgentsCommand::Edit(id) => self.on_agent_edit(id).await?,
+            },
             SlashCommand::AgentSwitch(agent_id) => {
                 // Validate that the agent exists by checking against loaded agents
                 let agents = self.api.get_agent_infos().await?;
@@ -2126,6 +3454,27 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Shows the title of the current conversation
+    async fn on_show_title(&mut self) -> anyhow::Result<()> {
+        let conversation = match self.state.conversation_id {
+            Some(conversation_id) => self.api.conversation(&conversation_id).await?,
+            None => None,
+        };
+
+        match conversation.and_then(|conversation| conversation.title) {
+            Some(title) => self.writeln_title(TitleFormat::info(format!(
+                "Conversation title: '{}'",
+                title.bold()
+            )))?,
+            None => {
+                self.writeln_title(TitleFormat::info("No conversation title yet").sub_title(
+                    "It is generated after the first message, or set it with /title <new title>",
+                ))?
+            }
+        }
+        Ok(())
+    }
+
     /// Select a model from all configured providers using porcelain-style
     /// tabular display matching the shell plugin's `:model` UI.
     ///
@@ -2248,9 +3597,11 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         // The first line is the header; data rows follow in the same order as
         // the Info entries (sorted by provider, then model within provider).
         let mut model_entries: Vec<(ModelId, ProviderId)> = Vec::new();
+        let mut model_details: Vec<String> = Vec::new();
         for pm in &all_provider_models {
             for model in &pm.models {
                 model_entries.push((model.id.clone(), pm.provider_id.clone()));
+                model_details.push(format_model_details(model, &pm.provider_id));
             }
         }
 
@@ -2261,6 +3612,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             model_id: Option<ModelId>,
             provider_id: Option<ProviderId>,
             display: String,
+            details: String,
         }
         impl std::fmt::Display for ModelRow {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -2274,6 +3626,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             model_id: None,
             provider_id: None,
             display: all_lines[0].to_string(),
+            details: String::new(),
         });
         // Data rows
         for (i, line) in all_lines.iter().skip(1).enumerate() {
@@ -2282,6 +3635,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 model_id: entry.map(|(m, _)| m.clone()),
                 provider_id: entry.map(|(_, p)| p.clone()),
                 display: line.to_string(),
+                details: model_details.get(i).cloned().unwrap_or_default(),
             });
         }
 
@@ -2299,6 +3653,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         match ForgeWidget::select("Model", rows)
             .with_starting_cursor(starting_cursor)
             .with_header_lines(1)
+            .with_preview(|row| row.details.clone())
             .prompt()?
         {
             Some(row) => Ok(row.model_id.zip(row.provider_id)),
@@ -2408,6 +3763,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         use colored::Colorize;
 
         let display_uri = verification_uri_complete.unwrap_or(verification_uri);
+        let headless = crate::oauth_callback::is_headless_session();
 
         self.writeln("")?;
         self.writeln(format!(
@@ -2415,11 +3771,13 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             "→".blue(),
             display_uri.blue().underline()
         ))?;
-        // Try to copy code to clipboard automatically (not available on Android)
+        // Try to copy code to clipboard automatically (not available on Android,
+        // and not the user's clipboard in a headless session)
         #[cfg(not(target_os = "android"))]
-        let clipboard_copied = arboard::Clipboard::new()
-            .and_then(|mut clipboard| clipboard.set_text(user_code))
-            .is_ok();
+        let clipboard_copied = !headless
+            && arboard::Clipboard::new()
+                .and_then(|mut clipboard| clipboard.set_text(user_code))
+                .is_ok();
 
         #[cfg(target_os = "android")]
         let clipboard_copied = false;
@@ -2439,8 +3797,10 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         }
         self.writeln("")?;
 
-        // Try to open browser automatically
-        if let Err(e) = open::that(display_uri) {
+        // Try to open browser automatically, unless there is none to open
+        if headless {
+            self.writeln("Open the link on any device with a browser to sign in".dimmed())?;
+        } else if let Err(e) = open::that(display_uri) {
             self.writeln_title(TitleFormat::error(format!(
                 "Failed to open browser automatically: {e}"
             )))?;
@@ -2504,7 +3864,12 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             format!("Authenticate using your {provider_id} account").dimmed()
         ))?;
 
-        let callback_server =
+        // A browser elsewhere cannot reach a localhost callback, so a headless
+        // session always pastes the code
+        let headless = crate::oauth_callback::is_headless_session();
+        let callback_server = if headless {
+            None
+        } else {
             match crate::oauth_callback::LocalhostOAuthCallbackServer::start(request) {
                 Ok(Some(server)) => {
                     self.writeln(format!(
@@ -2519,7 +3884,8 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     // started — fall back to manual code paste.
                     None
                 }
-            };
+            }
+        };
 
         // Display authorization URL
         self.writeln(format!(
@@ -2529,7 +3895,12 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         ))?;
 
         // Try to open browser automatically
-        if let Err(e) = open::that(request.authorization_url.as_str()) {
+        if headless {
+            self.writeln(
+                "Open the link on any device with a browser, then paste the code or the URL it redirects to"
+                    .dimmed(),
+            )?;
+        } else if let Err(e) = open::that(request.authorization_url.as_str()) {
             self.writeln_title(TitleFormat::error(format!(
                 "Failed to open browser automatically: {e}"
             )))?;
@@ -2538,9 +3909,11 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         let code = if let Some(server) = callback_server {
             server.wait_for_code().await?
         } else {
-            // Prompt user to paste authorization code
+            // Prompt user to paste authorization code, or the redirect URL
+            // carrying it
             let code = ForgeWidget::input("Paste the authorization code")
                 .prompt()?
+                .map(|input| crate::oauth_callback::parse_authorization_code(&input))
                 .ok_or_else(|| anyhow::anyhow!("Authorization code input cancelled"))?;
 
             if code.trim().is_empty() {
@@ -2603,8 +3976,23 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             })
             .collect();
 
+        // Device flows only need a browser on some device, so they are the
+        // default where forge cannot open one
+        let starting_cursor = crate::oauth_callback::is_headless_session()
+            .then(|| {
+                auth_methods.iter().position(|method| {
+                    matches!(
+                        method,
+                        AuthMethod::OAuthDevice(_) | AuthMethod::CodexDevice(_)
+                    )
+                })
+            })
+            .flatten()
+            .unwrap_or(0);
+
         match ForgeWidget::select("Select authentication method:", method_names.clone())
             .with_help_message("Use arrow keys to navigate and Enter to select")
+            .with_starting_cursor(starting_cursor)
             .prompt()?
         {
             Some(selected_name) => {
@@ -2963,27 +4351,356 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                 )])
                 .await?;
 
-            self.writeln_title(
-                TitleFormat::action(format!("{}", provider.id))
-                    .sub_title("is now the default provider"),
-            )?;
+            self.writeln_title(
+                TitleFormat::action(format!("{}", provider.id))
+                    .sub_title("is now the default provider"),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Handle dispatching events from the CLI
+    async fn handle_dispatch(&mut self, json: String) -> Result<()> {
+        // Initialize the conversation
+        let conversation_id = self.init_conversation().await?;
+
+        // Parse the JSON to determine the event name and value
+        let event: UserCommand = serde_json::from_str(&json)?;
+
+        // Create the chat request with the event
+        let chat = ChatRequest::new(event.into(), conversation_id);
+
+        self.on_chat(chat).await
+    }
+
+    /// Runs a prompt or workflow event without user interaction for CI jobs.
+    ///
+    /// Prints every event as a JSON line followed by the report of the run
+    /// and returns the exit code reflecting its outcome.
+    async fn on_ci(&mut self, args: CiArgs) -> i32 {
+        let mut run = CiRun::default();
+        let mut events = Vec::new();
+        let result = async {
+            let criteria = CiCriteria::new(args.fail_on_tool_error, args.expect.as_deref())?;
+            self.run_ci(&args, &mut run, &mut events).await?;
+            anyhow::Ok(criteria)
+        }
+        .await;
+        let (criteria, error) = match result {
+            Ok(criteria) => (criteria, None),
+            Err(error) => {
+                tracing::error!(error = ?error);
+                (CiCriteria::default(), Some(format!("{error:#}")))
+            }
+        };
+
+        let conversation_id = self.state.conversation_id;
+        let usage = self.conversation_usage(conversation_id).await;
+        let report = run.finish(&criteria, conversation_id, usage, error);
+        if let Ok(line) = serde_json::to_string(&report) {
+            println!("{line}");
+        }
+
+        if let Some(dir) = &args.output_dir
+            && let Err(error) = self.write_ci_artifacts(dir, &report, &events).await
+        {
+            eprintln!(
+                "{}",
+                TitleFormat::error(format!("Failed to write CI artifacts: {error:#}")).display()
+            );
+        }
+        report.exit_code
+    }
+
+    /// Lists, shows, merges or discards the worktrees created with `--sandbox`
+    fn on_sandbox(&mut self, command: SandboxCommand) -> Result<()> {
+        match command {
+            SandboxCommand::List => {
+                let cwd = self.api.environment().cwd;
+                let sandboxes = Sandbox::list(&cwd)?;
+                if sandboxes.is_empty() {
+                    self.writeln_title(TitleFormat::info(
+                        "No sandboxes, start one with `forge --sandbox <name>`",
+                    ))?;
+                    return Ok(());
+                }
+                let mut info = Info::new();
+                for entry in sandboxes {
+                    let changes = Sandbox::new(&entry.name).changes(&cwd)?;
+                    info = info
+                        .add_title(entry.name.to_uppercase())
+                        .add_key_value("path", entry.path.display().to_string())
+                        .add_key_value("changes", changes.unwrap_or_else(|| "none".to_string()));
+                }
+                self.writeln(info)?;
+            }
+            SandboxCommand::Diff { name } => {
+                let diff = Sandbox::new(&name).diff(&self.api.environment().cwd, &[])?;
+                if diff.is_empty() {
+                    self.writeln_title(TitleFormat::info(format!(
+                        "Sandbox '{name}' has no changes"
+                    )))?;
+                } else {
+                    self.writeln(diff)?;
+                }
+            }
+            SandboxCommand::Merge { name } => {
+                Sandbox::new(&name).merge(&self.api.environment().cwd)?;
+                self.writeln_title(TitleFormat::action(format!(
+                    "Merged sandbox '{name}' into the current branch"
+                )))?;
+            }
+            SandboxCommand::Discard { name } => {
+                Sandbox::new(&name).discard(&self.api.environment().cwd)?;
+                self.writeln_title(TitleFormat::action(format!("Discarded sandbox '{name}'")))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds, lists, removes or runs the schedules of the configuration
+    async fn on_schedule(&mut self, command: ScheduleCommand) -> Result<()> {
+        match command {
+            ScheduleCommand::Add { name, cron, prompt, agent } => {
+                let cron = cron.parse::<Cron>()?;
+                let next_run = format_next_run(&cron);
+                let mut schedule = Schedule::new(cron, prompt);
+                schedule.agent = agent;
+                self.api
+                    .update_config(vec![ConfigOperation::SetSchedule(
+                        name.clone(),
+                        Some(schedule),
+                    )])
+                    .await?;
+                self.writeln_title(
+                    TitleFormat::action(format!("Added schedule '{name}'"))
+                        .sub_title(format!("next run {next_run}")),
+                )?;
+            }
+            ScheduleCommand::List => {
+                if self.config.schedules.is_empty() {
+                    self.writeln_title(TitleFormat::info(
+                        "No schedules defined, add one with `forge schedule add`",
+                    ))?;
+                    return Ok(());
+                }
+                let mut info = Info::new();
+                for (name, schedule) in &self.config.schedules {
+                    let next_run = match schedule.cron.parse::<Cron>() {
+                        Ok(cron) => format_next_run(&cron),
+                        Err(error) => format!("{error:#}"),
+                    };
+                    info = info
+                        .add_title(name.to_uppercase())
+                        .add_key_value("cron", &schedule.cron)
+                        .add_key_value("agent", schedule.agent.as_deref().unwrap_or("active"))
+                        .add_key_value("prompt", &schedule.prompt)
+                        .add_key_value("next run", next_run);
+                }
+                self.writeln(info)?;
+            }
+            ScheduleCommand::Remove { name } => {
+                if !self.config.schedules.contains_key(&name) {
+                    anyhow::bail!("Unknown schedule '{name}'");
+                }
+                self.api
+                    .update_config(vec![ConfigOperation::SetSchedule(name.clone(), None)])
+                    .await?;
+                self.writeln_title(TitleFormat::action(format!("Removed schedule '{name}'")))?;
+            }
+            ScheduleCommand::Run => self.run_schedules().await?,
+        }
+        Ok(())
+    }
+
+    /// Runs each schedule at its times until interrupted. Runs go through the
+    /// `forge ci` runner, so nobody is asked for confirmations and each run is
+    /// stored as a conversation.
+    async fn run_schedules(&mut self) -> Result<()> {
+        let schedules = self
+            .config
+            .schedules
+            .iter()
+            .map(|(name, schedule)| {
+                let cron = schedule
+                    .cron
+                    .parse::<Cron>()
+                    .with_context(|| format!("Invalid schedule '{name}'"))?;
+                Ok((name.clone(), cron, schedule.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if schedules.is_empty() {
+            anyhow::bail!("No schedules defined, add one with `forge schedule add`");
+        }
+        self.writeln_title(TitleFormat::info(format!(
+            "Running {} schedule(s), press Ctrl+C to stop",
+            schedules.len()
+        )))?;
+
+        let mut last_run = chrono::Local::now().naive_local();
+        loop {
+            let Some(next_run) = schedules
+                .iter()
+                .filter_map(|(_, cron, _)| cron.next_after(last_run))
+                .min()
+            else {
+                anyhow::bail!("None of the schedules runs again");
+            };
+            let wait = next_run - chrono::Local::now().naive_local();
+            tokio::time::sleep(wait.to_std().unwrap_or_default()).await;
+
+            for (name, cron, schedule) in &schedules {
+                if cron.next_after(last_run) != Some(next_run) {
+                    continue;
+                }
+                self.writeln_title(TitleFormat::action(format!("Running schedule '{name}'")))?;
+                let args = CiArgs {
+                    prompt: Some(schedule.prompt.clone()),
+                    event: None,
+                    agent: schedule.agent.as_deref().map(AgentId::new),
+                    fail_on_tool_error: false,
+                    expect: None,
+                    output_dir: None,
+                };
+                let exit_code = self.on_ci(args).await;
+                let title = match exit_code {
+                    0 => TitleFormat::action(format!("Schedule '{name}' finished")),
+                    _ => TitleFormat::error(format!("Schedule '{name}' failed")),
+                };
+                let conversation = self
+                    .state
+                    .conversation_id
+                    .map(|id| format!("conversation {}", id.into_string()))
+                    .unwrap_or_default();
+                self.writeln_title(title.sub_title(conversation))?;
+            }
+            last_run = next_run;
+        }
+    }
+
+    /// Token usage accumulated over a conversation, zero when it is unknown
+    async fn conversation_usage(
+        &self,
+        conversation_id: Option<ConversationId>,
+    ) -> ConversationUsage {
+        match conversation_id {
+            Some(id) => self
+                .api
+                .conversation(&id)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|conversation| conversation.accumulated_usage())
+                .map(ConversationUsage::from)
+                .unwrap_or_default(),
+            None => ConversationUsage::default(),
+        }
+    }
+
+    async fn run_ci(
+        &mut self,
+        args: &CiArgs,
+        run: &mut CiRun,
+        events: &mut Vec<String>,
+    ) -> Result<()> {
+        // Policies are enforced and confirmations denied as nobody can answer them
+        let config = self.config.clone().restricted(true).non_interactive(true);
+        self.api = Arc::new((self.new_api)(
+            self.workspaces.current().to_path_buf(),
+            config.clone(),
+        ));
+        self.config = config;
+
+        // Selecting a provider or model interactively would block the job
+        self.api
+            .get_default_provider()
+            .await
+            .context("No provider is configured; run `forge provider login` first")?;
+        if let Some(agent_id) = args.agent.clone().or(self.cli.agent.clone()) {
+            self.api.set_active_agent(agent_id).await?;
+        }
+        if self
+            .get_agent_model(self.api.get_active_agent().await)
+            .await
+            .is_none()
+        {
+            anyhow::bail!("No model is configured; run `forge config set model` first");
         }
 
-        Ok(())
-    }
+        let piped_input = self.cli.piped_input.clone();
+        let event: Event = match (&args.event, &args.prompt) {
+            (Some(json), _) => serde_json::from_str::<UserCommand>(json)
+                .context("Failed to parse --event")?
+                .into(),
+            (None, Some(prompt)) => {
+                // Piped input is context for an explicit prompt, as with `forge -p`
+                let event = Event::new(prompt.clone());
+                match piped_input {
+                    Some(piped) => event.additional_context(piped),
+                    None => event,
+                }
+            }
+            (None, None) => Event::new(piped_input.context(
+                "Provide a prompt with --prompt, an event with --event or pipe a prompt to stdin",
+            )?),
+        };
 
-    // Handle dispatching events from the CLI
-    async fn handle_dispatch(&mut self, json: String) -> Result<()> {
-        // Initialize the conversation
-        let conversation_id = self.init_conversation().await?;
+        let conversation = Conversation::generate();
+        let conversation_id = conversation.id;
+        self.api.upsert_conversation(conversation).await?;
+        self.state.conversation_id = Some(conversation_id);
 
-        // Parse the JSON to determine the event name and value
-        let event: UserCommand = serde_json::from_str(&json)?;
+        let mut stream = self
+            .api
+            .chat(ChatRequest::new(event, conversation_id))
+            .await?;
+        while let Some(response) = stream.next().await {
+            let event = StreamEvent::from(response?);
+            run.observe(&event);
+            let line = serde_json::to_string(&event)?;
+            println!("{line}");
+            events.push(line);
+        }
+        Ok(())
+    }
 
-        // Create the chat request with the event
-        let chat = ChatRequest::new(event.into(), conversation_id);
+    /// Writes the report, event log and conversation of a CI run to `dir`
+    async fn write_ci_artifacts(
+        &self,
+        dir: &std::path::Path,
+        report: &CiReport,
+        events: &[String],
+    ) -> Result<()> {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(
+            dir.join("report.json"),
+            serde_json::to_string_pretty(report)?,
+        )
+        .await?;
+        let log = events
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect::<String>();
+        tokio::fs::write(dir.join("events.jsonl"), log).await?;
 
-        self.on_chat(chat).await
+        if let Some(conversation_id) = self.state.conversation_id
+            && let Some(conversation) = self.api.conversation(&conversation_id).await?
+        {
+            let related_conversations = self.fetch_related_conversations(&conversation).await;
+            tokio::fs::write(
+                dir.join("conversation.html"),
+                conversation.to_html_with_related(&related_conversations),
+            )
+            .await?;
+            let dump = ConversationDump { conversation, related_conversations };
+            tokio::fs::write(
+                dir.join("conversation.json"),
+                serde_json::to_string_pretty(&dump)?,
+            )
+            .await?;
+        }
+        Ok(())
     }
 
     /// Initializes and returns a conversation ID for the current session.
@@ -3172,7 +4889,16 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
     }
 
     async fn on_chat(&mut self, chat: ChatRequest) -> Result<()> {
-        let mut stream = self.api.chat(chat).await?;
+        let Some(conversation_id) = self.claim_conversation(chat.conversation_id).await? else {
+            return Ok(());
+        };
+        let chat = chat.conversation_id(conversation_id);
+
+        if self.cli.output_format == OutputFormat::Ndjson {
+            return self.on_chat_ndjson(chat).await;
+        }
+
+        let mut stream = self.api.chat(chat.pause(self.pause.clone())).await?;
 
         // Always use streaming content writer
         let mut writer = StreamingWriter::new(self.spinner.clone(), self.api.clone());
@@ -3196,6 +4922,117 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Locks the conversation of a turn about to start so that other Forge
+    /// processes can only read it. When another process holds it, asks
+    /// whether to attach read-only, returning `None`, or to start a new
+    /// conversation, whose id is returned instead.
+    async fn claim_conversation(&mut self, id: ConversationId) -> Result<Option<ConversationId>> {
+        if self.locked_conversation == Some(id) {
+            return Ok(Some(id));
+        }
+
+        let mut id = id;
+        if let Some(holder) = self.api.lock_conversation(&id).await? {
+            self.spinner.stop(None)?;
+            let message = format!("Conversation {id} is open in {holder}");
+            if !self.cli.is_interactive() {
+                anyhow::bail!("{message}");
+            }
+            if self.read_only_conversation == Some(id) {
+                self.writeln_title(
+                    TitleFormat::error(message).sub_title("use /new to start a new conversation"),
+                )?;
+                return Ok(None);
+            }
+
+            self.writeln_title(TitleFormat::error(message))?;
+            let actions = LockedConversationAction::iter().collect::<Vec<_>>();
+            match ForgeWidget::select("How should Forge go on?", actions).prompt()? {
+                Some(LockedConversationAction::New) => {
+                    let conversation = Conversation::generate();
+                    id = conversation.id;
+                    self.api.upsert_conversation(conversation).await?;
+                    self.api.lock_conversation(&id).await?;
+                    self.state.conversation_id = Some(id);
+                    self.print_conversation_status(true, id)?;
+                    self.spinner.start(None)?;
+                }
+                Some(LockedConversationAction::ReadOnly) => {
+                    self.read_only_conversation = Some(id);
+                    if let Some(conversation) = self.api.conversation(&id).await? {
+                        self.on_show_last_message(conversation, false).await?;
+                    }
+                    self.writeln_title(TitleFormat::info("Attached read-only").sub_title(
+                        "prompts are sent once the other process closes the conversation",
+                    ))?;
+                    return Ok(None);
+                }
+                None => return Ok(None),
+            }
+        }
+
+        self.read_only_conversation = None;
+        if let Some(previous) = self.locked_conversation.replace(id) {
+            self.api.unlock_conversation(&previous).await?;
+        }
+        Ok(Some(id))
+    }
+
+    /// Streams the events of a chat as NDJSON on stdout, ending with the
+    /// usage of the conversation and how the turn ended. Tool titles and
+    /// outputs are rendered for humans on stderr.
+    async fn on_chat_ndjson(&mut self, chat: ChatRequest) -> Result<()> {
+        let conversation_id = chat.conversation_id;
+        print_event(&PorcelainEvent::TurnStart { conversation_id: conversation_id.into_string() })?;
+
+        let mut interrupted = None;
+        let result = async {
+            let mut stream = self.api.chat(chat).await?;
+            while let Some(response) = stream.next().await {
+                let response = response?;
+                if let Some(event) = PorcelainEvent::from_response(&response) {
+                    print_event(&event)?;
+                }
+                match response {
+                    ChatResponse::TaskMessage {
+                        content: ChatResponseContent::ToolInput(title),
+                    } => {
+                        self.writeln_to_stderr(title.display().to_string())?;
+                    }
+                    ChatResponse::TaskMessage {
+                        content: ChatResponseContent::ToolOutput(text),
+                    } => {
+                        self.writeln_to_stderr(text)?;
+                    }
+                    ChatResponse::ToolCallStart { notifier, .. } => notifier.notify_one(),
+                    ChatResponse::RetryAttempt { cause, .. } => {
+                        self.writeln_to_stderr(
+                            TitleFormat::error(cause.as_str()).display().to_string(),
+                        )?;
+                    }
+                    response @ ChatResponse::Interrupt { .. } => {
+                        if let StreamEvent::Interrupt { reason } = StreamEvent::from(response) {
+                            interrupted = Some(reason);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            anyhow::Ok(())
+        }
+        .await;
+
+        let (status, reason) = match (&result, interrupted) {
+            (Err(error), _) => (DoneStatus::Error, Some(format!("{error:#}"))),
+            (Ok(()), Some(reason)) => (DoneStatus::Interrupted, Some(reason)),
+            (Ok(()), None) => (DoneStatus::Complete, None),
+        };
+        let usage = self.conversation_usage(Some(conversation_id)).await;
+        print_event(&PorcelainEvent::Usage(usage))?;
+        print_event(&PorcelainEvent::Done { status, reason })?;
+        result
+    }
+
     /// Fetches related conversations for a given conversation in parallel.
     ///
     /// Returns a vector of related conversations that could be successfully
@@ -3226,6 +5063,11 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             let conversation = self.api.conversation(&conversation_id).await?;
             if let Some(conversation) = conversation {
                 let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+                // Name the dump after the conversation so exports are easy to tell apart
+                let prefix = match conversation.title.as_deref().map(slugify) {
+                    Some(slug) if !slug.is_empty() => format!("{timestamp}-{slug}"),
+                    _ => timestamp.to_string(),
+                };
 
                 // Collect related conversations from agent tool calls
                 let related_conversations = self.fetch_related_conversations(&conversation).await;
@@ -3240,7 +5082,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                         conversation.to_html_with_related(&related_conversations)
                     };
 
-                    let path = format!("{timestamp}-dump.html");
+                    let path = format!("{prefix}-dump.html");
                     tokio::fs::write(path.as_str(), &html_content).await?;
 
                     let subtitle = if related_conversations.is_empty() {
@@ -3263,7 +5105,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                         related_conversations: related_conversations.clone(),
                     };
 
-                    let path = format!("{timestamp}-dump.json");
+                    let path = format!("{prefix}-dump.json");
                     let content = serde_json::to_string_pretty(&dump_data)?;
                     tokio::fs::write(path.as_str(), content).await?;
 
@@ -3311,6 +5153,15 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     writer.finish()?;
                     self.writeln(text)?;
                 }
+                ChatResponseContent::FileDiff(diff) => {
+                    writer.finish()?;
+                    if DiffHistory::is_large(&diff, self.config.max_diff_lines) {
+                        self.writeln(DiffHistory::collapsed(&diff, &self.state.cwd))?;
+                    } else {
+                        self.writeln(&diff.diff)?;
+                    }
+                    self.state.diffs.push(diff);
+                }
                 ChatResponseContent::Markdown { text, partial: _ } => {
                     writer.write(&text)?;
                 }
@@ -3357,7 +5208,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     return Ok(());
                 }
             }
-            ChatResponse::RetryAttempt { cause, duration: _ } => {
+            ChatResponse::RetryAttempt { cause, duration: _, stalled } => {
                 if !self
                     .config
                     .retry
@@ -3365,7 +5216,12 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     .is_some_and(|r| r.suppress_errors)
                 {
                     writer.finish()?;
-                    self.spinner.start(Some("Retrying"))?;
+                    let message = if stalled {
+                        "Provider stalled, retrying"
+                    } else {
+                        "Retrying"
+                    };
+                    self.spinner.start(Some(message))?;
                     self.writeln_title(TitleFormat::error(cause.as_str()))?;
                 }
             }
@@ -3380,6 +5236,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     InterruptionReason::MaxToolFailurePerTurnLimitReached { limit, .. } => {
                         format!("Maximum tool failure limit ({limit}) reached for this turn")
                     }
+                    InterruptionReason::Paused => return self.on_paused().await,
                 };
 
                 self.writeln_title(TitleFormat::action(title))?;
@@ -3393,8 +5250,14 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             ChatResponse::TaskReasoning { content } => {
                 writer.write_dimmed(&content)?;
             }
+            ChatResponse::PermissionRequest { .. } => {
+                // The confirmation prompt is rendered by the policy service
+                writer.finish()?;
+                self.spinner.stop(None)?;
+            }
             ChatResponse::TaskComplete => {
                 writer.finish()?;
+                tracker::task_complete();
                 if let Some(conversation_id) = self.state.conversation_id {
                     self.writeln_title(
                         TitleFormat::debug("Finished").sub_title(conversation_id.into_string()),
@@ -3409,6 +5272,37 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Asks whether to continue, guide or abort a turn paused with Ctrl+C
+    async fn on_paused(&mut self) -> anyhow::Result<()> {
+        self.pause.clear();
+        self.spinner.set_hint(None);
+        self.writeln_title(TitleFormat::action("Paused"))?;
+
+        let actions = PauseAction::iter().collect::<Vec<_>>();
+        let message = match ForgeWidget::select("How should the agent go on?", actions).prompt()? {
+            Some(PauseAction::Continue) => None,
+            Some(PauseAction::Guide) => {
+                match ForgeWidget::input("Guidance for the agent")
+                    .prompt()?
+                    .filter(|guidance| !guidance.trim().is_empty())
+                {
+                    Some(guidance) => Some(guidance),
+                    None => return self.on_pause_abort(),
+                }
+            }
+            Some(PauseAction::Abort) | None => return self.on_pause_abort(),
+        };
+
+        self.spinner.start(None)?;
+        Box::pin(self.on_message(message)).await
+    }
+
+    /// Ends a paused turn, keeping the queued messages for review
+    fn on_pause_abort(&mut self) -> anyhow::Result<()> {
+        self.writeln_title(TitleFormat::error("Aborted"))?;
+        self.hold_queue()
+    }
+
     async fn should_continue(&mut self) -> anyhow::Result<bool> {
         let should_continue = ForgeWidget::confirm("Do you want to continue anyway?")
             .with_default(true)
@@ -3546,6 +5440,277 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         self.on_chat(chat).await
     }
 
+    /// Lists the files changed in the conversation, or prints every diff of
+    /// the files matching `path`
+    fn on_diff(&mut self, path: Option<String>) -> anyhow::Result<()> {
+        let Some(path) = path else {
+            match self.state.diffs.summary(&self.state.cwd) {
+                Some(summary) => self.writeln(summary)?,
+                None => {
+                    self.writeln_title(TitleFormat::info("No files changed in this conversation"))?
+                }
+            }
+            return Ok(());
+        };
+
+        let diffs = self
+            .state
+            .diffs
+            .find(&path, &self.state.cwd)
+            .into_iter()
+            .map(|diff| diff.diff.clone())
+            .collect::<Vec<_>>();
+        if diffs.is_empty() {
+            self.writeln_title(TitleFormat::error(format!(
+                "No changes to {path} in this conversation"
+            )))?;
+        }
+        for diff in diffs {
+            self.writeln(diff)?;
+        }
+        Ok(())
+    }
+
+    /// Shows what changed between two snapshots of a file
+    async fn on_snapshot_diff(&mut self, diff: SnapshotDiff) -> anyhow::Result<()> {
+        let path = self.state.cwd.join(&diff.path);
+        let result = self.api.diff_snapshots(path, diff.from, diff.to).await?;
+        self.writeln_title(
+            TitleFormat::info(format!(
+                "{} snapshots {} → {}",
+                diff.path, diff.from, diff.to
+            ))
+            .sub_title(format!("+{} -{}", result.lines_added, result.lines_removed)),
+        )?;
+        if result.diff.trim().is_empty() {
+            self.writeln_title(TitleFormat::info("The snapshots are identical"))?;
+        } else {
+            self.writeln(result.diff)?;
+        }
+        Ok(())
+    }
+
+    /// Lets the user pick a previously submitted prompt and places it in the
+    /// input buffer to be edited and sent again
+    fn on_history(&mut self, all: bool, query: Option<String>) -> anyhow::Result<()> {
+        let entries = self.console.history(all, query.as_deref())?;
+        if entries.is_empty() {
+            let scope = if all { "" } else { " in this workspace" };
+            self.writeln_title(TitleFormat::info(format!("No matching prompts{scope}")))?;
+            return Ok(());
+        }
+
+        if let Some(entry) = ForgeWidget::select("Prompt", entries).prompt()? {
+            self.console.set_buffer(entry.prompt);
+        }
+        Ok(())
+    }
+
+    /// Lists the instruction files loaded into the system prompt, in the order
+    /// they are applied
+    async fn on_instructions(&mut self) -> anyhow::Result<()> {
+        let files = self.api.get_instruction_files().await?;
+        if files.is_empty() {
+            self.writeln_title(TitleFormat::info(
+                "No instruction files found. Add an AGENTS.md to the project to give agents instructions",
+            ))?;
+            return Ok(());
+        }
+
+        let mut info = Info::new().add_title("INSTRUCTIONS");
+        for file in files {
+            let lines = file.content.lines().count();
+            info = info.add_key_value(
+                format_display_path(&file.path, &self.state.cwd),
+                format!("{lines} lines"),
+            );
+        }
+        self.writeln(info)?;
+        Ok(())
+    }
+
+    /// Finds the agent with the given id, letting the user pick one when no id
+    /// is given. Returns `None` when the selection is cancelled.
+    async fn find_agent(&mut self, id: Option<String>) -> anyhow::Result<Option<Agent>> {
+        let mut agents = self.api.get_agents().await?;
+        let id = match id {
+            Some(id) => id,
+            None => {
+                agents.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+                let ids = agents
+                    .iter()
+                    .map(|agent| agent.id.to_string())
+                    .collect::<Vec<_>>();
+                match ForgeWidget::select("Agent", ids).prompt()? {
+                    Some(id) => id,
+                    None => return Ok(None),
+                }
+            }
+        };
+
+        agents
+            .into_iter()
+            .find(|agent| agent.id.as_str() == id)
+            .map(Some)
+            .with_context(|| format!("Agent '{id}' not found"))
+    }
+
+    /// Shows an agent's configuration, the tools it resolves to and its
+    /// system prompt
+    async fn on_agent_show(&mut self, id: Option<String>) -> anyhow::Result<()> {
+        let Some(agent) = self.find_agent(id).await? else {
+            return Ok(());
+        };
+
+        let all_tools = self.api.get_tools().await?;
+        let resolver = ToolResolver::new(all_tools.into());
+        let tools = resolver
+            .resolve(&agent)
+            .into_iter()
+            .map(|def| def.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let provider = match self.get_provider(Some(agent.id.clone())).await {
+            Ok(provider) => provider.id.to_string(),
+            Err(e) => format!("Error: [{}]", e),
+        };
+        let location = agent
+            .path
+            .clone()
+            .unwrap_or_else(|| markers::BUILT_IN.to_string());
+
+        let info = Info::new()
+            .add_title(agent.id.as_str().to_case(Case::UpperSnake))
+            .add_key_value("Id", agent.id.as_str())
+            .add_key_value("Title", agent.title.as_ref())
+            .add_key_value("Description", agent.description.as_ref())
+            .add_key_value("Location", location)
+            .add_key_value("Provider", provider)
+            .add_key_value("Model", agent.model.as_str())
+            .add_key_value("Tools", Some(tools).filter(|tools| !tools.is_empty()))
+            .add_key_value(
+                "Max Turns",
+                agent.max_turns.map(|max_turns| max_turns.to_string()),
+            );
+        self.writeln(info)?;
+
+        if let Some(prompt) = &agent.system_prompt {
+            self.writeln_title(TitleFormat::info("System prompt"))?;
+            self.writeln(prompt.template.trim())?;
+        }
+        Ok(())
+    }
+
+    /// Asks for the details of a new agent and writes its definition file to
+    /// the project or global agents directory
+    async fn on_agent_new(&mut self) -> anyhow::Result<()> {
+        let Some(id) = ForgeWidget::input("Agent id (e.g. reviewer)").prompt()? else {
+            return Ok(());
+        };
+        let id = id.trim().to_string();
+        anyhow::ensure!(
+            AgentScaffold::is_valid_id(&id),
+            "Invalid agent id '{id}': use lowercase letters, digits, '-' and '_', starting with a letter"
+        );
+
+        let Some(title) = ForgeWidget::input("Title").prompt()? else {
+            return Ok(());
+        };
+        let Some(description) =
+            ForgeWidget::input("Description (tells other agents when to delegate to it)")
+                .prompt()?
+        else {
+            return Ok(());
+        };
+        let Some(model) = ForgeWidget::input("Model (leave empty for the default model)")
+            .allow_empty(true)
+            .prompt()?
+        else {
+            return Ok(());
+        };
+
+        let overview = self.api.get_tools().await?;
+        let tool_names = overview
+            .system
+            .iter()
+            .map(|tool| tool.name.to_string())
+            .collect::<Vec<_>>();
+        let Some(tools) = ForgeWidget::multi_select("Tools", tool_names).prompt()? else {
+            return Ok(());
+        };
+
+        let env = self.api.environment();
+        let project = format!("This project ({})", env.agent_cwd_path().display());
+        let global = format!("All projects ({})", env.agent_path().display());
+        let Some(scope) =
+            ForgeWidget::select("Save for", vec![project.clone(), global]).prompt()?
+        else {
+            return Ok(());
+        };
+        let dir = if scope == project {
+            env.agent_cwd_path()
+        } else {
+            env.agent_path()
+        };
+
+        let path = dir.join(format!("{id}.md"));
+        anyhow::ensure!(!path.exists(), "{} already exists", path.display());
+
+        let scaffold = AgentScaffold {
+            id: id.clone(),
+            title: title.trim().to_string(),
+            description: description.trim().to_string(),
+            model: Some(model.trim().to_string()).filter(|model| !model.is_empty()),
+            tools,
+        };
+        ForgeFS::create_dir_all(&dir).await?;
+        ForgeFS::write(&path, scaffold.render()).await?;
+        self.api
+            .record_agent_version(&AgentId::new(&id), &path)
+            .await?;
+        self.api.reload_agents().await?;
+        self.writeln_title(TitleFormat::action(format!(
+            "Created agent {id} at {}",
+            path.display()
+        )))?;
+
+        let write_prompt = ForgeWidget::confirm("Open it in your editor to write its prompt?")
+            .with_default(true)
+            .prompt()?;
+        if write_prompt == Some(true) {
+            open_in_editor(&path)?;
+            self.api
+                .record_agent_version(&AgentId::new(&id), &path)
+                .await?;
+            self.api.reload_agents().await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a custom agent's definition file in the editor, recording its
+    /// content as a version before and after editing, and reloads the agents
+    /// once it is closed
+    async fn on_agent_edit(&mut self, id: Option<String>) -> anyhow::Result<()> {
+        let Some(agent) = self.find_agent(id).await? else {
+            return Ok(());
+        };
+        let path = agent.path.with_context(|| {
+            format!(
+                "'{}' is a built-in agent. Create an agent with the same id using /agents new to override it",
+                agent.id
+            )
+        })?;
+
+        // Changes made outside of Forge since the latest version are kept too
+        let path = std::path::Path::new(&path);
+        self.api.record_agent_version(&agent.id, path).await?;
+        open_in_editor(path)?;
+        self.api.record_agent_version(&agent.id, path).await?;
+        self.api.reload_agents().await?;
+        self.writeln_title(TitleFormat::action(format!("Reloaded agent {}", agent.id)))?;
+        Ok(())
+    }
+
     async fn on_usage(&mut self) -> anyhow::Result<()> {
         self.spinner.start(Some("Loading Usage"))?;
 
@@ -3599,6 +5764,12 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             crate::cli::ConfigCommand::List => {
                 self.on_show_config(porcelain).await?;
             }
+            crate::cli::ConfigCommand::Show { origin: false } => {
+                self.on_show_config(porcelain).await?;
+            }
+            crate::cli::ConfigCommand::Show { origin: true } => {
+                self.on_show_config_origins(porcelain)?;
+            }
             crate::cli::ConfigCommand::Path => {
                 let path = forge_config::ConfigReader::config_path();
                 self.writeln(path.display().to_string())?;
@@ -3696,6 +5867,27 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                         .sub_title("is now the reasoning effort"),
                 )?;
             }
+            ConfigSetField::Storage { scope, migrate } => {
+                if migrate {
+                    self.spinner.start(Some("Migrating storage"))?;
+                    let migration = self.api.migrate_storage(scope).await?;
+                    self.spinner.stop(None)?;
+                    self.writeln_title(TitleFormat::info(format!(
+                        "Moved {} conversations and {} snapshots",
+                        migration.conversations, migration.snapshots
+                    )))?;
+                } else {
+                    self.api
+                        .update_config(vec![ConfigOperation::SetStorageScope(scope)])
+                        .await?;
+                }
+                self.writeln_title(
+                    TitleFormat::action(scope.to_string()).sub_title("is now the storage scope"),
+                )?;
+            }
+            ConfigSetField::Profile { name } => {
+                self.on_set_profile(name).await?;
+            }
         }
 
         Ok(())
@@ -3756,6 +5948,14 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     None => self.writeln("ReasoningEffort: Not set")?,
                 }
             }
+            ConfigGetField::Storage => {
+                let scope = self.api.get_storage_scope().await?;
+                self.writeln(scope.to_string())?;
+            }
+            ConfigGetField::Profile => match self.config.profile.clone() {
+                Some(name) => self.writeln(name)?,
+                None => self.writeln("Profile: Not set")?,
+            },
         }
 
         Ok(())
@@ -3796,6 +5996,11 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
             .map(|val| val == "1")
             .unwrap_or(true); // Default to true
 
+        let token_count = conversation.and_then(|conversation| match &model_id {
+            Some(model) => conversation.token_count_with(&self.api.token_counter(model)),
+            None => conversation.token_count(),
+        });
+
         let rprompt = ZshRPrompt::from_config(&self.config)
             .agent(
                 std::env::var("_FORGE_ACTIVE_AGENT")
@@ -3804,7 +6009,7 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
                     .map(AgentId::new),
             )
             .model(model_id)
-            .token_count(conversation.and_then(|conversation| conversation.token_count()))
+            .token_count(token_count)
             .cost(cost)
             .use_nerd_font(use_nerd_font);
 
@@ -3888,6 +6093,80 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
         Ok(())
     }
 
+    /// Re-renders a stored conversation turn by turn. In real time, replies
+    /// are streamed word by word and every tool call is followed by a pause.
+    async fn on_snapshot_command(&mut self, command: SnapshotCommand) -> Result<()> {
+        match command {
+            SnapshotCommand::Export { paths, output } => {
+                let files = paths.len();
+                let snapshots = self
+                    .api
+                    .export_snapshot_bundle(paths, output.clone())
+                    .await?;
+                self.writeln_title(
+                    TitleFormat::action(format!("Exported {}", output.display()))
+                        .sub_title(format!("{files} file(s), {snapshots} snapshot(s)")),
+                )?;
+            }
+            SnapshotCommand::Import { bundle } => {
+                let changed = self.api.import_snapshot_bundle(bundle.clone()).await?;
+                let cwd = self.api.environment().cwd;
+                self.writeln_title(
+                    TitleFormat::action(format!("Imported {}", bundle.display()))
+                        .sub_title(format!("{} file(s) changed", changed.len())),
+                )?;
+                for path in changed {
+                    let path = path
+                        .strip_prefix(&cwd)
+                        .unwrap_or(&path)
+                        .display()
+                        .to_string();
+                    self.writeln(format!("  {path}"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn on_replay(&mut self, conversation_id: ConversationId, realtime: bool) -> Result<()> {
+        self.validate_conversation_exists(&conversation_id).await?;
+        let turns = self.api.replay_conversation(&conversation_id).await?;
+        if turns.is_empty() {
+            self.writeln_title(TitleFormat::info("Conversation has no turns to replay"))?;
+            return Ok(());
+        }
+
+        let mut writer = StreamingWriter::new(self.spinner.clone(), self.api.clone());
+        for (index, turn) in turns.into_iter().enumerate() {
+            writer.finish()?;
+            self.writeln_title(TitleFormat::action(format!("Turn {}", index + 1)))?;
+            for content in turn {
+                match content {
+                    ChatResponseContent::Markdown { text, .. } if realtime => {
+                        for word in text.split_inclusive(char::is_whitespace) {
+                            let content = ChatResponseContent::Markdown {
+                                text: word.to_string(),
+                                partial: true,
+                            };
+                            self.handle_chat_response(content.into(), &mut writer)
+                                .await?;
+                            tokio::time::sleep(REPLAY_WORD_DELAY).await;
+                        }
+                    }
+                    content => {
+                        self.handle_chat_response(content.into(), &mut writer)
+                            .await?;
+                        if realtime {
+                            tokio::time::sleep(REPLAY_STEP_DELAY).await;
+                        }
+                    }
+                }
+            }
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
     async fn on_index(&mut self, path: std::path::PathBuf, init: bool) -> anyhow::Result<()> {
         use forge_domain::SyncProgress;
         use forge_spinner::ProgressBarManager;
@@ -4339,8 +6618,8 @@ impl<A: API + ConsoleWriter + 'static, F: Fn(ForgeConfig) -> A + Send + Sync> UI
     /// at least a few seconds.
     fn install_vscode_extension(&self) {
         tokio::task::spawn_blocking(|| {
-            if crate::vscode::should_install_extension() {
-                let _ = crate::vscode::install_extension();
+            if let Some(editor) = crate::vscode::editor_missing_extension() {
+                let _ = crate::vscode::install_extension(editor);
             }
         });
     }
@@ -1,57 +1,320 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
+use anyhow::Context;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use colored::Colorize;
 use forge_api::API;
-use forge_config::Update;
+use forge_config::{Update, UpdateChannel};
 use forge_select::ForgeWidget;
 use forge_tracker::VERSION;
-use update_informer::{Check, Version, registry};
-
-/// Runs the official installation script to update Forge, failing silently.
-/// When `auto_update` is true, exits immediately after a successful update
-/// without prompting the user.
-async fn execute_update_command(api: Arc<impl API>, auto_update: bool) {
-    // Spawn a new task that won't block the main application
-    let output = api
-        .execute_shell_command_raw("curl -fsSL https://forgecode.dev/cli | sh")
-        .await;
-
-    match output {
-        Err(err) => {
-            // Send an event to the tracker on failure
-            // We don't need to handle this result since we're failing silently
-            let _ = send_update_failure_event(&format!("Auto update failed {err}")).await;
-        }
-        Ok(output) => {
-            if output.success() {
-                let should_exit = if auto_update {
-                    true
-                } else {
-                    let answer = forge_select::ForgeWidget::confirm(
-                        "You need to close forge to complete update. Do you want to close it now?",
-                    )
-                    .with_default(true)
-                    .prompt();
-                    answer.unwrap_or_default().unwrap_or_default()
-                };
-                if should_exit {
-                    std::process::exit(0);
-                }
+use semver::Version;
+use serde::Deserialize;
+
+/// GitHub repository forge releases are published to
+const REPOSITORY: &str = "tailcallhq/forgecode";
+
+/// Base64 encoded Ed25519 public key the release binaries are signed with,
+/// burned in by the release build. Builds without the repository variable set
+/// get an empty value, which counts as no key.
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("UPDATE_PUBLIC_KEY");
+
+#[derive(Debug, Clone, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn version(&self) -> Option<Version> {
+        Version::parse(self.tag_name.trim_start_matches('v')).ok()
+    }
+
+    fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// The channel a version is released on: stable without a pre-release,
+/// nightly for `-nightly` pre-releases and beta for any other pre-release
+fn channel_of(version: &Version) -> UpdateChannel {
+    if version.pre.is_empty() {
+        UpdateChannel::Stable
+    } else if version.pre.as_str().starts_with("nightly") {
+        UpdateChannel::Nightly
+    } else {
+        UpdateChannel::Beta
+    }
+}
+
+/// The newest release newer than `current` on `channel` or a more stable one
+fn latest_release<'a>(
+    releases: &'a [Release],
+    channel: UpdateChannel,
+    current: &Version,
+) -> Option<(Version, &'a Release)> {
+    releases
+        .iter()
+        .filter_map(|release| Some((release.version()?, release)))
+        .filter(|(version, _)| channel_of(version) <= channel && version > current)
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+}
+
+/// Name of the release binary built for this platform
+fn asset_name() -> Option<String> {
+    let arch = std::env::consts::ARCH;
+    let target = match std::env::consts::OS {
+        "linux" if cfg!(target_env = "musl") => format!("{arch}-unknown-linux-musl"),
+        "linux" => format!("{arch}-unknown-linux-gnu"),
+        "macos" => format!("{arch}-apple-darwin"),
+        "windows" => format!("{arch}-pc-windows-msvc.exe"),
+        "android" => format!("{arch}-linux-android"),
+        _ => return None,
+    };
+    Some(format!("forge-{target}"))
+}
+
+/// Verifies the base64 encoded Ed25519 `signature` of `binary`
+fn verify_signature(binary: &[u8], signature: &str, public_key: &str) -> anyhow::Result<()> {
+    let public_key = STANDARD
+        .decode(public_key.trim())
+        .context("Invalid update public key")?;
+    let signature = STANDARD
+        .decode(signature.trim())
+        .context("Invalid update signature")?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+        .verify(binary, &signature)
+        .map_err(|_| anyhow::anyhow!("The downloaded binary does not match its signature"))
+}
+
+/// Replaces the executable at `path` with `binary`.
+///
+/// The binary is staged next to the executable so the final rename stays on
+/// one filesystem. Windows cannot overwrite a running executable, but it can
+/// rename it out of the way first.
+fn replace_executable(path: &Path, binary: &[u8]) -> anyhow::Result<()> {
+    let staged = path.with_extension("new");
+    std::fs::write(&staged, binary)
+        .with_context(|| format!("Failed to write {}", staged.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    #[cfg(windows)]
+    {
+        let old = path.with_extension("old");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(path, &old)
+            .with_context(|| format!("Failed to move {} aside", path.display()))?;
+    }
+
+    std::fs::rename(&staged, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Package manager that installed the running executable and owns its file,
+/// with the name of the package it installed
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PackageManager {
+    Npm(String),
+    Homebrew(String),
+}
+
+impl PackageManager {
+    /// Detects a package manager from the path of the executable: npm packages
+    /// live under `node_modules/<package>` and Homebrew formulae under
+    /// `Cellar/<formula>`. Symlinks are resolved first, since both link the
+    /// executable from a `bin` directory.
+    fn detect(executable: &Path) -> Option<Self> {
+        let executable = executable
+            .canonicalize()
+            .unwrap_or_else(|_| executable.to_path_buf());
+        let components = executable
+            .components()
+            .filter_map(|component| component.as_os_str().to_str())
+            .collect::<Vec<_>>();
+        let after = |name: &str| {
+            let position = components.iter().position(|component| *component == name)?;
+            components.get(position + 1).copied()
+        };
+
+        if let Some(package) = after("node_modules") {
+            let package = if package.starts_with('@') {
+                format!("{package}/{}", after(package)?)
             } else {
-                let exit_output = match output.code() {
-                    Some(code) => format!("Process exited with code: {code}"),
-                    None => "Process exited without code".to_string(),
-                };
-                let _ =
-                    send_update_failure_event(&format!("Auto update failed, {exit_output}",)).await;
-            }
+                package.to_string()
+            };
+            return Some(Self::Npm(package));
         }
+        after("Cellar").map(|formula| Self::Homebrew(formula.to_string()))
+    }
+
+    /// Shell command installing `version` with the package manager. Homebrew
+    /// only installs the version of its formula.
+    fn upgrade_command(&self, version: &Version) -> String {
+        match self {
+            Self::Npm(package) => format!("npm install -g {package}@{version}"),
+            Self::Homebrew(formula) => format!("brew upgrade {formula}"),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Npm(_) => "npm",
+            Self::Homebrew(_) => "Homebrew",
+        }
+    }
+}
+
+/// Update bookkeeping kept under `<base_path>/updates`: when releases were
+/// last checked, and the binary the last update replaced, for
+/// `forge update --rollback`.
+struct UpdateState {
+    dir: PathBuf,
+}
+
+impl UpdateState {
+    fn new(base_path: &Path) -> Self {
+        Self { dir: base_path.join("updates") }
+    }
+
+    fn previous_binary(&self) -> PathBuf {
+        self.dir.join(if cfg!(windows) {
+            "forge-previous.exe"
+        } else {
+            "forge-previous"
+        })
+    }
+
+    fn previous_version(&self) -> PathBuf {
+        self.dir.join("previous-version")
+    }
+
+    fn last_check(&self) -> PathBuf {
+        self.dir.join("last-check")
+    }
+
+    /// Whether `interval` has passed since releases were last checked
+    fn is_check_due(&self, interval: Duration) -> bool {
+        interval.is_zero()
+            || std::fs::metadata(self.last_check())
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_none_or(|elapsed| elapsed >= interval)
+    }
+
+    fn mark_checked(&self) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.last_check(), "")?;
+        Ok(())
+    }
+
+    /// Keeps a copy of the executable at `current`, of `version`
+    fn save(&self, current: &Path, version: &str) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::copy(current, self.previous_binary())
+            .with_context(|| format!("Failed to back up {}", current.display()))?;
+        std::fs::write(self.previous_version(), version)?;
+        Ok(())
+    }
+
+    /// Swaps the executable at `current`, of `version`, with the saved one and
+    /// returns the version restored. The replaced executable is saved in turn,
+    /// so a rollback can itself be undone.
+    fn restore(&self, current: &Path, version: &str) -> anyhow::Result<String> {
+        let previous = std::fs::read(self.previous_binary())
+            .context("No previous version of forge to roll back to")?;
+        let previous_version = std::fs::read_to_string(self.previous_version())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        self.save(current, version)?;
+        replace_executable(current, &previous)?;
+        Ok(previous_version)
     }
 }
 
-async fn confirm_update(version: Version) -> bool {
+fn http_client() -> anyhow::Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .user_agent(format!("forge/{VERSION}"))
+        .build()?)
+}
+
+async fn fetch_releases(client: &reqwest::Client) -> anyhow::Result<Vec<Release>> {
+    Ok(client
+        .get(format!(
+            "https://api.github.com/repos/{REPOSITORY}/releases?per_page=30"
+        ))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+async fn download(client: &reqwest::Client, asset: &Asset) -> anyhow::Result<Vec<u8>> {
+    let bytes = client
+        .get(&asset.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to download {}", asset.name))?
+        .bytes()
+        .await?;
+    Ok(bytes.to_vec())
+}
+
+/// Downloads the binary of `release` for this platform, verifies its
+/// signature and installs it in place of the running executable, keeping the
+/// latter for a rollback.
+async fn install(
+    client: &reqwest::Client,
+    release: &Release,
+    state: &UpdateState,
+) -> anyhow::Result<()> {
+    let public_key = UPDATE_PUBLIC_KEY
+        .filter(|key| !key.trim().is_empty())
+        .context(
+            "This build of forge cannot verify updates, reinstall it from https://forgecode.dev",
+        )?;
+    let name = asset_name().context("No forge release is built for this platform")?;
+    let binary = release
+        .asset(&name)
+        .with_context(|| format!("Release {} has no {name}", release.tag_name))?;
+    let signature = release
+        .asset(&format!("{name}.sig"))
+        .with_context(|| format!("Release {} is not signed", release.tag_name))?;
+
+    let binary = download(client, binary).await?;
+    let signature = String::from_utf8(download(client, signature).await?)?;
+    verify_signature(&binary, &signature, public_key)?;
+
+    let current = std::env::current_exe()?;
+    state.save(&current, VERSION)?;
+    replace_executable(&current, &binary)
+}
+
+async fn confirm_update(version: &Version, channel: UpdateChannel) -> bool {
+    let label = match channel {
+        UpdateChannel::Stable => "latest",
+        UpdateChannel::Beta => "latest beta",
+        UpdateChannel::Nightly => "latest nightly",
+    };
     let answer = ForgeWidget::confirm(format!(
-        "Confirm upgrade from {} -> {} (latest)?",
+        "Confirm upgrade from {} -> {} ({label})?",
         VERSION.to_string().bold().white(),
         version.to_string().bold().white()
     ))
@@ -65,11 +328,31 @@ async fn confirm_update(version: Version) -> bool {
     }
 }
 
-/// Checks if there is an update available
+/// Asks to close forge once the update is installed, or exits right away when
+/// `auto_update` is true.
+fn exit_after_update(auto_update: bool) {
+    let should_exit = if auto_update {
+        true
+    } else {
+        let answer = ForgeWidget::confirm(
+            "You need to close forge to complete update. Do you want to close it now?",
+        )
+        .with_default(true)
+        .prompt();
+        answer.unwrap_or_default().unwrap_or_default()
+    };
+    if should_exit {
+        std::process::exit(0);
+    }
+}
+
+/// Checks if there is an update available on the configured channel and
+/// installs it once confirmed
 pub async fn on_update(api: Arc<impl API>, update: Option<&Update>) {
     let update = update.cloned().unwrap_or_default();
     let frequency = update.frequency.unwrap_or_default();
     let auto_update = update.auto_update.unwrap_or_default();
+    let channel = update.channel.unwrap_or_default();
 
     // Check if version is development version, in which case we skip the update
     // check
@@ -77,20 +360,203 @@ pub async fn on_update(api: Arc<impl API>, update: Option<&Update>) {
         // Skip update for development version 0.1.0
         return;
     }
+    let Ok(current) = Version::parse(VERSION.trim_start_matches('v')) else {
+        return;
+    };
 
-    let informer = update_informer::new(registry::GitHub, "tailcallhq/forgecode", VERSION)
-        .interval(frequency.into());
+    let state = UpdateState::new(&api.environment().base_path);
+    if !state.is_check_due(frequency.into()) {
+        return;
+    }
 
-    if let Some(version) = informer.check_version().ok().flatten()
-        && (auto_update || confirm_update(version).await)
-    {
-        execute_update_command(api, auto_update).await;
+    let result = async {
+        let client = http_client()?;
+        let releases = fetch_releases(&client).await?;
+        state.mark_checked()?;
+
+        if let Some((version, release)) = latest_release(&releases, channel, &current)
+            && (auto_update || confirm_update(&version, channel).await)
+        {
+            // Replacing a binary a package manager owns would leave the package
+            // manager unaware of the version installed, so it updates it instead
+            match PackageManager::detect(&std::env::current_exe()?) {
+                Some(manager) => {
+                    let command = manager.upgrade_command(&version);
+                    let output = api.execute_shell_command_raw(&command).await?;
+                    anyhow::ensure!(output.success(), "`{command}` failed");
+                }
+                None => install(&client, release, &state).await?,
+            }
+            exit_after_update(auto_update);
+        }
+        anyhow::Ok(())
+    }
+    .await;
+
+    if let Err(err) = result {
+        let _ = send_update_failure_event(&format!("Auto update failed {err:#}")).await;
     }
 }
 
+/// Restores the version of forge the last update replaced and returns it
+///
+/// # Errors
+///
+/// Returns an error if no update was installed yet or the executable cannot be
+/// replaced.
+pub async fn on_rollback(api: Arc<impl API>) -> anyhow::Result<String> {
+    let current = std::env::current_exe()?;
+    if let Some(manager) = PackageManager::detect(&current) {
+        anyhow::bail!(
+            "forge was installed with {}, use it to install another version",
+            manager.name()
+        );
+    }
+    let state = UpdateState::new(&api.environment().base_path);
+    state.restore(&current, VERSION)
+}
+
 /// Sends an event to the tracker when an update fails
 async fn send_update_failure_event(error_msg: &str) -> anyhow::Result<()> {
     tracing::error!(error = error_msg, "Update failed");
     // Always return Ok since we want to fail silently
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn release_fixture(tag: &str) -> Release {
+        Release { tag_name: tag.to_string(), assets: vec![] }
+    }
+
+    fn latest_tag(channel: UpdateChannel, current: &str) -> Option<String> {
+        let releases = [
+            release_fixture("v1.2.0"),
+            release_fixture("v1.3.0-beta.1"),
+            release_fixture("v1.3.0-nightly.20261015"),
+            release_fixture("v1.1.0"),
+            release_fixture("not-a-version"),
+        ];
+        latest_release(&releases, channel, &Version::parse(current).unwrap())
+            .map(|(_, release)| release.tag_name.clone())
+    }
+
+    #[test]
+    fn test_latest_release_per_channel() {
+        let actual = (
+            latest_tag(UpdateChannel::Stable, "1.1.0"),
+            latest_tag(UpdateChannel::Beta, "1.1.0"),
+            latest_tag(UpdateChannel::Nightly, "1.1.0"),
+            latest_tag(UpdateChannel::Stable, "1.2.0"),
+        );
+        let expected = (
+            Some("v1.2.0".to_string()),
+            Some("v1.3.0-beta.1".to_string()),
+            Some("v1.3.0-nightly.20261015".to_string()),
+            None,
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_detect_package_manager() {
+        let actual = [
+            "/usr/lib/node_modules/forgecode/bin/forge",
+            "/home/me/.npm-global/lib/node_modules/@antinomyhq/forge/bin/forge",
+            "/opt/homebrew/Cellar/code-forge/1.2.0/bin/forge",
+            "/home/me/.local/bin/forge",
+        ]
+        .map(|path| PackageManager::detect(Path::new(path)));
+
+        let expected = [
+            Some(PackageManager::Npm("forgecode".to_string())),
+            Some(PackageManager::Npm("@antinomyhq/forge".to_string())),
+            Some(PackageManager::Homebrew("code-forge".to_string())),
+            None,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_package_manager_upgrade_command() {
+        let version = Version::parse("1.3.0-beta.1").unwrap();
+
+        let actual = [
+            PackageManager::Npm("@antinomyhq/forge".to_string()).upgrade_command(&version),
+            PackageManager::Homebrew("code-forge".to_string()).upgrade_command(&version),
+        ];
+
+        let expected = [
+            "npm install -g @antinomyhq/forge@1.3.0-beta.1".to_string(),
+            "brew upgrade code-forge".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        use ring::signature::{Ed25519KeyPair, KeyPair};
+
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public_key = STANDARD.encode(key_pair.public_key().as_ref());
+        let signature = STANDARD.encode(key_pair.sign(b"forge binary").as_ref());
+
+        let actual = (
+            verify_signature(b"forge binary", &signature, &public_key).is_ok(),
+            verify_signature(b"tampered binary", &signature, &public_key).is_ok(),
+        );
+        let expected = (true, false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_restore_swaps_with_previous_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let current = dir.path().join("forge");
+        std::fs::write(&current, "v1").unwrap();
+        let fixture = UpdateState::new(dir.path());
+        fixture.save(&current, "1.0.0").unwrap();
+        replace_executable(&current, b"v2").unwrap();
+
+        let restored = fixture.restore(&current, "2.0.0").unwrap();
+        let undone = fixture.restore(&current, "1.0.0").unwrap();
+
+        let actual = (restored, undone, std::fs::read_to_string(&current).unwrap());
+        let expected = ("1.0.0".to_string(), "2.0.0".to_string(), "v2".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_restore_without_previous_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = UpdateState::new(dir.path());
+
+        let actual = fixture.restore(&dir.path().join("forge"), "1.0.0");
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_check_is_due_until_marked() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = UpdateState::new(dir.path());
+        let day = Duration::from_secs(60 * 60 * 24);
+
+        let before = fixture.is_check_due(day);
+        fixture.mark_checked().unwrap();
+        let actual = (
+            before,
+            fixture.is_check_due(day),
+            fixture.is_check_due(Duration::ZERO),
+        );
+
+        let expected = (true, false, true);
+        assert_eq!(actual, expected);
+    }
+}
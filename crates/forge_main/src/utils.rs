@@ -33,6 +33,24 @@ pub fn humanize_number(n: usize) -> String {
     }
 }
 
+/// Longest slug derived from a title, in characters
+const MAX_SLUG_CHARS: usize = 50;
+
+/// Turns a title into a lowercase, dash-separated name safe to use in file
+/// names (e.g., "Fix the Auth flow!" becomes "fix-the-auth-flow").
+pub fn slugify(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    match slug.char_indices().nth(MAX_SLUG_CHARS) {
+        Some((end, _)) => slug[..end].trim_end_matches('-').to_string(),
+        None => slug,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -73,4 +91,24 @@ mod tests {
         let expected = "0";
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_slugify() {
+        let long = "a".repeat(60);
+        let fixture = [
+            "Fix the Auth flow!",
+            "  Résumé -- parser ",
+            "",
+            long.as_str(),
+        ];
+
+        let actual = fixture.map(slugify);
+        let expected = [
+            "fix-the-auth-flow".to_string(),
+            "résumé-parser".to_string(),
+            String::new(),
+            "a".repeat(50),
+        ];
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,64 +1,138 @@
 //! VS Code terminal detection and automatic extension installation
+//!
+//! Forks of VS Code (Cursor, Windsurf, VSCodium) run the same extension host
+//! and set the same terminal variables, so they are detected as variants of
+//! one editor that differ in their CLI, storage directory and process name.
 
+use std::path::PathBuf;
 use std::process::Command;
 
-/// Checks if running in VS Code terminal
-///
-/// Detects VS Code by checking for environment variables that are set
-/// when running in the VS Code integrated terminal.
-pub fn is_vscode_terminal() -> bool {
-    std::env::var("TERM_PROGRAM")
-        .map(|val| val == "vscode")
-        .unwrap_or(false)
-        || std::env::var("VSCODE_PID").is_ok()
-        || std::env::var("VSCODE_GIT_ASKPASS_NODE").is_ok()
-        || std::env::var("VSCODE_GIT_IPC_HANDLE").is_ok()
+/// Identifier of the Forge extension on the VS Code Marketplace and Open VSX
+const EXTENSION_ID: &str = "ForgeCode.forge-vscode";
+
+/// Editor built on VS Code whose integrated terminal Forge runs in
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, strum_macros::Display, strum_macros::EnumIter,
+)]
+pub enum EditorVariant {
+    #[default]
+    #[strum(to_string = "VS Code")]
+    VsCode,
+    Cursor,
+    Windsurf,
+    #[strum(to_string = "VSCodium")]
+    VsCodium,
+}
+
+impl EditorVariant {
+    /// Command line tool of the editor used to manage extensions
+    pub fn cli(self) -> &'static str {
+        match self {
+            EditorVariant::VsCode => "code",
+            EditorVariant::Cursor => "cursor",
+            EditorVariant::Windsurf => "windsurf",
+            EditorVariant::VsCodium => "codium",
+        }
+    }
+
+    /// Name of the editor's directory under the user configuration directory
+    fn storage_dir(self) -> &'static str {
+        match self {
+            EditorVariant::VsCode => "Code",
+            EditorVariant::Cursor => "Cursor",
+            EditorVariant::Windsurf => "Windsurf",
+            EditorVariant::VsCodium => "VSCodium",
+        }
+    }
+
+    /// Directory the editor stores its settings and workspace state in, e.g.
+    /// `~/.config/Cursor` on Linux or `~/Library/Application Support/Cursor`
+    /// on macOS
+    pub fn storage_path(self) -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(self.storage_dir()))
+    }
+}
+
+/// Detects the VS Code variant whose integrated terminal Forge runs in
+pub fn detect_editor() -> Option<EditorVariant> {
+    detect_editor_from(|key| std::env::var(key).ok())
+}
+
+fn detect_editor_from(env: impl Fn(&str) -> Option<String>) -> Option<EditorVariant> {
+    let in_terminal = env("TERM_PROGRAM").is_some_and(|value| value == "vscode")
+        || env("VSCODE_PID").is_some()
+        || env("VSCODE_GIT_ASKPASS_NODE").is_some()
+        || env("VSCODE_GIT_IPC_HANDLE").is_some();
+    if !in_terminal {
+        return None;
+    }
+
+    // Cursor sets a variable of its own in every terminal
+    if env("CURSOR_TRACE_ID").is_some() {
+        return Some(EditorVariant::Cursor);
+    }
+
+    // Otherwise the askpass helper points into the editor's installation,
+    // whose executable is named after its command line tool
+    let executable = env("VSCODE_GIT_ASKPASS_NODE")
+        .or_else(|| env("VSCODE_GIT_ASKPASS_MAIN"))
+        .unwrap_or_default()
+        .to_lowercase();
+    [
+        EditorVariant::Cursor,
+        EditorVariant::Windsurf,
+        EditorVariant::VsCodium,
+    ]
+    .into_iter()
+    .find(|variant| executable.contains(variant.cli()))
+    .or(Some(EditorVariant::VsCode))
 }
 
-/// Checks if the Forge VS Code extension is installed
+/// Checks if the Forge extension is installed in the given editor
 ///
-/// Checks VS Code's extension list to see if ForgeCode.forge-vscode is
+/// Checks the editor's extension list to see if ForgeCode.forge-vscode is
 /// installed.
-pub fn is_extension_installed() -> bool {
+pub fn is_extension_installed(editor: EditorVariant) -> bool {
     // Try to list installed extensions
-    if let Ok(output) = Command::new("code").arg("--list-extensions").output()
+    if let Ok(output) = Command::new(editor.cli()).arg("--list-extensions").output()
         && output.status.success()
         && let Ok(extensions) = String::from_utf8(output.stdout)
     {
-        return extensions
-            .lines()
-            .any(|line| line.trim() == "ForgeCode.forge-vscode");
+        return extensions.lines().any(|line| line.trim() == EXTENSION_ID);
     }
     false
 }
 
-/// Attempts to install the Forge VS Code extension silently
+/// Attempts to install the Forge extension silently into the given editor
 ///
 /// Returns Ok(true) if installation was successful, Ok(false) if it failed,
 /// or Err if the command couldn't be executed.
-pub fn install_extension() -> Result<bool, std::io::Error> {
-    let output = Command::new("code")
+pub fn install_extension(editor: EditorVariant) -> Result<bool, std::io::Error> {
+    let output = Command::new(editor.cli())
         .arg("--install-extension")
-        .arg("ForgeCode.forge-vscode")
+        .arg(EXTENSION_ID)
         .arg("--force")
         .output()?;
 
     Ok(output.status.success())
 }
 
-/// Returns true if we should install the extension
+/// Returns the editor the extension should be installed in, if any
 ///
-/// This will return true only when:
-/// - Running in VS Code terminal
-/// - Extension is not installed
-pub fn should_install_extension() -> bool {
-    is_vscode_terminal() && !is_extension_installed()
+/// This will return an editor only when:
+/// - Running in the terminal of VS Code or one of its forks
+/// - Extension is not installed in that editor
+pub fn editor_missing_extension() -> Option<EditorVariant> {
+    detect_editor().filter(|editor| !is_extension_installed(*editor))
 }
 
 #[cfg(test)]
 mod tests {
     use std::env;
 
+    use pretty_assertions::assert_eq;
+    use strum::IntoEnumIterator;
+
     use super::*;
 
     fn with_env_var<F>(key: &str, value: &str, test: F)
@@ -85,38 +159,114 @@ mod tests {
     #[test]
     fn test_is_vscode_terminal_with_term_program() {
         with_env_var("TERM_PROGRAM", "vscode", || {
-            assert!(is_vscode_terminal());
+            assert!(detect_editor().is_some());
         });
     }
 
     #[test]
     fn test_is_vscode_terminal_with_vscode_pid() {
         with_env_var("VSCODE_PID", "12345", || {
-            assert!(is_vscode_terminal());
+            assert!(detect_editor().is_some());
         });
     }
 
     #[test]
     fn test_is_vscode_terminal_with_git_askpass() {
         with_env_var("VSCODE_GIT_ASKPASS_NODE", "/path/to/node", || {
-            assert!(is_vscode_terminal());
+            assert!(detect_editor().is_some());
         });
     }
 
     #[test]
     fn test_is_vscode_terminal_with_git_ipc() {
         with_env_var("VSCODE_GIT_IPC_HANDLE", "handle", || {
-            assert!(is_vscode_terminal());
+            assert!(detect_editor().is_some());
         });
     }
 
+    fn env_fixture(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        move |key| {
+            vars.iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn test_detect_editor_outside_vscode() {
+        let actual = detect_editor_from(env_fixture(&[("TERM_PROGRAM", "iTerm.app")]));
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn test_detect_editor_defaults_to_vscode() {
+        let actual = detect_editor_from(env_fixture(&[
+            ("TERM_PROGRAM", "vscode"),
+            (
+                "VSCODE_GIT_ASKPASS_NODE",
+                "/Applications/Visual Studio Code.app/Contents/Frameworks/Code Helper.app",
+            ),
+        ]));
+        assert_eq!(actual, Some(EditorVariant::VsCode));
+    }
+
+    #[test]
+    fn test_detect_editor_recognizes_forks() {
+        let fixtures = [
+            (
+                env_fixture(&[("TERM_PROGRAM", "vscode"), ("CURSOR_TRACE_ID", "abc")]),
+                EditorVariant::Cursor,
+            ),
+            (
+                env_fixture(&[(
+                    "VSCODE_GIT_ASKPASS_NODE",
+                    "/Applications/Windsurf.app/Contents/Frameworks/Windsurf Helper.app",
+                )]),
+                EditorVariant::Windsurf,
+            ),
+            (
+                env_fixture(&[
+                    ("TERM_PROGRAM", "vscode"),
+                    (
+                        "VSCODE_GIT_ASKPASS_MAIN",
+                        "/usr/share/codium/resources/app/askpass.js",
+                    ),
+                ]),
+                EditorVariant::VsCodium,
+            ),
+        ];
+
+        for (fixture, expected) in fixtures {
+            let actual = detect_editor_from(fixture);
+            assert_eq!(actual, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_editor_variant_display_and_cli() {
+        let actual = EditorVariant::iter()
+            .map(|variant| (variant.to_string(), variant.cli()))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            ("VS Code".to_string(), "code"),
+            ("Cursor".to_string(), "cursor"),
+            ("Windsurf".to_string(), "windsurf"),
+            ("VSCodium".to_string(), "codium"),
+        ];
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_should_install_when_in_vscode() {
         with_env_var("TERM_PROGRAM", "vscode", || {
             // We can't reliably test the actual installation check since it depends
             // on the actual VS Code installation, but we can verify the logic
             // when in VS Code terminal
-            assert!(is_vscode_terminal());
+            assert!(detect_editor().is_some());
         });
     }
 }
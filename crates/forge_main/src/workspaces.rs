@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+
+use forge_api::ConversationId;
+
+/// Workspaces opened during this session, each with the conversation last
+/// used in it so that switching back resumes it
+#[derive(Debug, Clone)]
+pub struct WorkspaceSessions {
+    /// Workspaces in the order they were opened, the current one included
+    visited: Vec<(PathBuf, Option<ConversationId>)>,
+    current: usize,
+}
+
+impl WorkspaceSessions {
+    pub fn new(workspace: PathBuf) -> Self {
+        Self { visited: vec![(workspace, None)], current: 0 }
+    }
+
+    /// The workspace in use
+    pub fn current(&self) -> &Path {
+        &self.visited[self.current].0
+    }
+
+    /// Every workspace opened during this session, in the order they were
+    /// opened
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.visited.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// Makes `workspace` the current one, keeping `conversation_id` as the
+    /// conversation of the workspace left. Returns the conversation last used
+    /// in `workspace`, if it was opened before.
+    pub fn switch(
+        &mut self,
+        workspace: PathBuf,
+        conversation_id: Option<ConversationId>,
+    ) -> Option<ConversationId> {
+        self.visited[self.current].1 = conversation_id;
+        match self.visited.iter().position(|(path, _)| *path == workspace) {
+            Some(index) => self.current = index,
+            None => {
+                self.visited.push((workspace, None));
+                self.current = self.visited.len() - 1;
+            }
+        }
+        self.visited[self.current].1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_switch_back_resumes_conversation() {
+        let mut fixture = WorkspaceSessions::new(PathBuf::from("/work/api"));
+        let conversation_id = ConversationId::generate();
+
+        let opened = fixture.switch(PathBuf::from("/work/web"), Some(conversation_id));
+        let resumed = fixture.switch(PathBuf::from("/work/api"), None);
+
+        let actual = (
+            opened,
+            resumed,
+            fixture.current().to_path_buf(),
+            fixture.paths().map(Path::to_path_buf).collect::<Vec<_>>(),
+        );
+        let expected = (
+            None,
+            Some(conversation_id),
+            PathBuf::from("/work/api"),
+            vec![PathBuf::from("/work/api"), PathBuf::from("/work/web")],
+        );
+        assert_eq!(actual, expected);
+    }
+}
@@ -73,6 +73,15 @@ impl<W: Write> StreamdownRenderer<W> {
         }
     }
 
+    /// Set a new terminal width, e.g. after the terminal was resized.
+    ///
+    /// Lines already written keep their layout, while the content still
+    /// buffered, such as an incomplete line or table, is laid out for the new
+    /// width.
+    pub fn set_width(&mut self, width: usize) {
+        self.renderer.set_width(width);
+    }
+
     /// Push a token to the renderer.
     ///
     /// Tokens are buffered until a complete line is received, then rendered.
@@ -109,3 +118,36 @@ impl<W: Write> StreamdownRenderer<W> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(width: usize, chunks: &[&str], resized_width: Option<usize>) -> String {
+        let mut output = Vec::new();
+        let mut renderer = StreamdownRenderer::new(&mut output, width);
+        for (index, chunk) in chunks.iter().enumerate() {
+            if index == 1
+                && let Some(width) = resized_width
+            {
+                renderer.set_width(width);
+            }
+            renderer.push(chunk).unwrap();
+        }
+        renderer.finish().unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_set_width_lays_out_buffered_table() {
+        let fixture = [
+            "| Name | Description |\n|---|---|\n",
+            "| forge | A terminal agent that writes and edits code with you |\n",
+        ];
+
+        let actual = render(120, &fixture, Some(30));
+
+        let expected = render(30, &fixture, None);
+        assert_eq!(actual, expected);
+    }
+}
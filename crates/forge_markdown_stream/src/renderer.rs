@@ -54,6 +54,12 @@ impl<W: Write> Renderer<W> {
         }
     }
 
+    /// Set a new terminal width, applied to everything rendered from now on,
+    /// including table rows buffered so far.
+    pub fn set_width(&mut self, width: usize) {
+        self.width = width;
+    }
+
     /// Set a new theme.
     #[allow(dead_code)]
     pub fn set_theme(&mut self, theme: Theme) {
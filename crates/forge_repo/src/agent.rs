@@ -1,30 +1,36 @@
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use forge_app::{AgentRepository, DirectoryReaderInfra, EnvironmentInfra, FileInfoInfra};
-use forge_config::ForgeConfig;
-use forge_domain::{ModelId, ProviderId, Template};
+use forge_app::{
+    AgentRepository, DirectoryReaderInfra, EnvironmentInfra, FileInfoInfra, FileReaderInfra,
+};
+use forge_config::{ForgeConfig, ModelConfig, ModelRouting};
+use forge_domain::{AgentId, ModelId, PackKind, ProviderId, Template};
 use gray_matter::Matter;
 use gray_matter::engine::YAML;
 
 use crate::agent_definition::AgentDefinition;
+use crate::pack::installed_pack_dirs;
 
 /// Infrastructure implementation for loading agent definitions from multiple
 /// sources:
 /// 1. Built-in agents (embedded in the application)
-/// 2. Global custom agents (from ~/.forge/agents/ directory)
-/// 3. Project-local agents (from .forge/agents/ directory in current working
+/// 2. Installed agent packs (from ~/.forge/packs/agents/ directory)
+/// 3. Global custom agents (from ~/.forge/agents/ directory)
+/// 4. Project-local agents (from .forge/agents/ directory in current working
 ///    directory)
 ///
 /// ## Agent Precedence
 /// When agents have duplicate IDs across different sources, the precedence
-/// order is: **CWD (project-local) > Global custom > Built-in**
+/// order is: **CWD (project-local) > Global custom > Installed packs >
+/// Built-in**
 ///
-/// This means project-local agents can override global agents, and both can
-/// override built-in agents.
+/// This means project-local agents can override global agents, which can
+/// override agents from packs, and all of them can override built-in agents.
 ///
 /// ## Directory Resolution
 /// - **Built-in agents**: Embedded in application binary
+/// - **Pack agents**: `~/forge/packs/agents/<pack>/[agents/]*.md`
 /// - **Global agents**: `~/forge/agents/*.md`
 /// - **CWD agents**: `./.forge/agents/*.md` (relative to current working
 ///   directory)
@@ -41,7 +47,9 @@ impl<I> ForgeAgentRepository<I> {
     }
 }
 
-impl<I: FileInfoInfra + EnvironmentInfra + DirectoryReaderInfra> ForgeAgentRepository<I> {
+impl<I: FileInfoInfra + EnvironmentInfra + DirectoryReaderInfra + FileReaderInfra>
+    ForgeAgentRepository<I>
+{
     /// Load all agent definitions from all available sources with conflict
     /// resolution.
     async fn load_agents(&self) -> anyhow::Result<Vec<AgentDefinition>> {
@@ -53,6 +61,11 @@ impl<I: FileInfoInfra + EnvironmentInfra + DirectoryReaderInfra> ForgeAgentRepos
         // Load built-in agents (no path - will display as "BUILT IN")
         let mut agents = self.init_default().await?;
 
+        // Load agents from installed packs
+        for dir in installed_pack_dirs(self.infra.as_ref(), PackKind::Agent).await? {
+            agents.extend(self.init_pack_dir(&dir).await?);
+        }
+
         // Load custom agents from global directory
         let dir = self.infra.get_environment().agent_path();
         let custom_agents = self.init_agent_dir(&dir).await?;
@@ -64,7 +77,7 @@ impl<I: FileInfoInfra + EnvironmentInfra + DirectoryReaderInfra> ForgeAgentRepos
         agents.extend(cwd_agents);
 
         // Handle agent ID conflicts by keeping the last occurrence
-        // This gives precedence order: CWD > Global Custom > Built-in
+        // This gives precedence order: CWD > Global Custom > Packs > Built-in
         Ok(resolve_agent_conflicts(agents))
     }
 
@@ -104,6 +117,30 @@ impl<I: FileInfoInfra + EnvironmentInfra + DirectoryReaderInfra> ForgeAgentRepos
 
         Ok(agents)
     }
+
+    /// Loads agents from an installed pack, skipping markdown files that are
+    /// not agent definitions (such as a README) instead of failing
+    async fn init_pack_dir(&self, dir: &std::path::Path) -> anyhow::Result<Vec<AgentDefinition>> {
+        let files = self
+            .infra
+            .read_directory_files(dir, Some("*.md"))
+            .await
+            .with_context(|| format!("Failed to read agents from: {}", dir.display()))?;
+
+        Ok(files
+            .into_iter()
+            .filter_map(|(path, content)| match parse_agent_file(&content) {
+                Ok(mut agent) => {
+                    agent.path = Some(path.display().to_string());
+                    Some(agent)
+                }
+                Err(error) => {
+                    tracing::warn!(path = %path.display(), error = %error, "Skipping invalid agent in pack");
+                    None
+                }
+            })
+            .collect())
+    }
 }
 
 /// Implementation function for resolving agent ID conflicts by keeping the last
@@ -142,6 +179,17 @@ where
     Ok(agents)
 }
 
+/// Model the routing rules pick for an agent by the task it performs
+fn routed_model<'a>(agent_id: &AgentId, routing: &'a ModelRouting) -> Option<&'a ModelConfig> {
+    if *agent_id == AgentId::MUSE {
+        routing.plan.as_ref()
+    } else if *agent_id == AgentId::FORGE {
+        routing.edit.as_ref()
+    } else {
+        None
+    }
+}
+
 /// Parse raw content into an AgentDefinition with YAML frontmatter
 fn parse_agent_file(content: &str) -> Result<AgentDefinition> {
     // Parse the frontmatter using gray_matter with type-safe deserialization
@@ -149,33 +197,44 @@ fn parse_agent_file(content: &str) -> Result<AgentDefinition> {
     let result = gray_matter.parse::<AgentDefinition>(content)?;
 
     // Extract the frontmatter
-    let agent = result
-        .data
-        .context("Empty system prompt content")?
-        .system_prompt(Template::new(result.content));
+    let agent = result.data.context("Empty system prompt content")?;
+
+    // A named template replaces the body, so having both is ambiguous
+    let system_prompt = match &agent.system_prompt_template {
+        Some(name) if !result.content.trim().is_empty() => {
+            anyhow::bail!(
+                "Agent '{}' sets system_prompt_template '{name}' and a system prompt body, remove one of them",
+                agent.id
+            )
+        }
+        Some(name) => Template::new(format!("{{{{> {name} }}}}")),
+        None => Template::new(result.content),
+    };
 
-    Ok(agent)
+    Ok(agent.system_prompt(system_prompt))
 }
 
 #[async_trait::async_trait]
-impl<F: FileInfoInfra + EnvironmentInfra<Config = ForgeConfig> + DirectoryReaderInfra>
-    AgentRepository for ForgeAgentRepository<F>
+impl<
+    F: FileInfoInfra + EnvironmentInfra<Config = ForgeConfig> + DirectoryReaderInfra + FileReaderInfra,
+> AgentRepository for ForgeAgentRepository<F>
 {
     async fn get_agents(&self) -> anyhow::Result<Vec<forge_domain::Agent>> {
         let agent_defs = self.load_agents().await?;
 
-        let session = self
-            .infra
-            .get_config()?
+        let config = self.infra.get_config()?;
+        let session = config
             .session
             .ok_or(forge_domain::Error::NoDefaultSession)?;
+        let routing = config.routing.unwrap_or_default();
 
         Ok(agent_defs
             .into_iter()
             .map(|def| {
+                let model = routed_model(&def.id, &routing).unwrap_or(&session);
                 def.into_agent(
-                    ProviderId::from(session.provider_id.clone()),
-                    ModelId::from(session.model_id.clone()),
+                    ProviderId::from(model.provider_id.clone()),
+                    ModelId::from(model.model_id.clone()),
                 )
             })
             .collect())
@@ -218,6 +277,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_routed_model_by_task() {
+        let plan = ModelConfig::new("openai", "gpt-4.1-mini");
+        let fixture = ModelRouting::default().plan(plan.clone());
+
+        let actual = (
+            routed_model(&AgentId::MUSE, &fixture),
+            routed_model(&AgentId::FORGE, &fixture),
+            routed_model(&AgentId::SAGE, &fixture),
+        );
+
+        let expected = (Some(&plan), None, None);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_agent_with_system_prompt_template() {
+        let content = "---\nid: templated\nsystem_prompt_template: my-prompt.md\n---\n";
+
+        let actual = parse_agent_file(content).unwrap();
+
+        let expected = "{{> my-prompt.md }}";
+        assert_eq!(actual.system_prompt.unwrap().template, expected);
+    }
+
+    #[test]
+    fn test_parse_agent_with_template_and_body_fails() {
+        let content = "---\nid: templated\nsystem_prompt_template: my-prompt.md\n---\nBody";
+
+        let actual = parse_agent_file(content);
+
+        assert!(actual.is_err());
+    }
+
     #[tokio::test]
     async fn test_parse_advanced_agent() {
         let content = forge_test_kit::fixture!("/src/fixtures/agents/advanced.md").await;
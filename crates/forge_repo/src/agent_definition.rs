@@ -1,7 +1,7 @@
 use derive_setters::Setters;
 use forge_domain::{
     Agent, AgentId, Compact, EventContext, MaxTokens, ModelId, ProviderId, ReasoningConfig,
-    SystemContext, Temperature, Template, ToolName, TopK, TopP,
+    SystemContext, Temperature, Template, ToolName, TopK, TopP, Verbosity,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -45,6 +45,12 @@ pub(crate) struct AgentDefinition {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub system_prompt: Option<Template<SystemContext>>,
 
+    /// Name of a registered template, built-in (e.g.
+    /// `forge-custom-agent-template.md`) or a user partial, used as the system
+    /// prompt instead of the body of the agent file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_template: Option<String>,
+
     // Template for the user prompt provided to the agent
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user_prompt: Option<Template<EventContext>>,
@@ -102,6 +108,32 @@ pub(crate) struct AgentDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_k: Option<TopK>,
 
+    /// Frequency penalty used for agent
+    ///
+    /// Penalizes tokens by how often they already appeared, from -2.0 to 2.0.
+    /// Positive values make the model repeat itself less. Supported by OpenAI
+    /// compatible and Google providers
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+
+    /// Presence penalty used for agent
+    ///
+    /// Penalizes tokens that already appeared, from -2.0 to 2.0. Positive
+    /// values push the model towards new topics. Supported by OpenAI
+    /// compatible and Google providers
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+
+    /// Stop sequences used for agent
+    ///
+    /// The response ends when the model generates one of them. Supported by
+    /// every provider except the OpenAI Responses API
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+
     /// Maximum number of tokens the model can generate
     ///
     /// Controls the maximum length of the model's response.
@@ -119,6 +151,12 @@ pub(crate) struct AgentDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<ReasoningConfig>,
 
+    /// Detail of the agent's answers: low, medium or high.
+    /// Sent to OpenAI models as `verbosity`; other providers ignore it
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+
     /// Maximum number of times a tool can fail before sending the response back
     /// to the LLM forces the completion.
     #[serde(default)]
@@ -154,8 +192,12 @@ impl AgentDefinition {
             max_tokens: self.max_tokens,
             top_p: self.top_p,
             top_k: self.top_k,
+            frequency_penalty: self.frequency_penalty,
+            presence_penalty: self.presence_penalty,
+            stop: self.stop,
             tools: self.tools,
             reasoning: self.reasoning,
+            verbosity: self.verbosity,
             compact: self.compact.unwrap_or_default(),
             max_turns: self.max_turns,
             custom_rules: self.custom_rules,
@@ -0,0 +1,341 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use bytes::Bytes;
+use forge_app::{EnvironmentInfra, FileInfoInfra, FileReaderInfra, FileWriterInfra};
+use forge_domain::{AgentId, AgentVersion, AgentVersionRepository};
+
+/// Name of the manifest listing the recorded versions of an agent
+const MANIFEST_FILE: &str = "versions.json";
+
+/// Repository implementation for the revision history of custom agent
+/// definitions.
+///
+/// Each agent has a directory `<base_path>/agent_versions/<agent_id>` holding a
+/// `versions.json` manifest and the content of every version as `<n>.md`.
+pub struct ForgeAgentVersionRepository<I> {
+    infra: Arc<I>,
+}
+
+impl<I> ForgeAgentVersionRepository<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+}
+
+/// Records `content` as a new version of the agent when it differs from the
+/// latest recorded version of the same file
+async fn record_agent_version<
+    I: EnvironmentInfra + FileInfoInfra + FileReaderInfra + FileWriterInfra,
+>(
+    infra: &I,
+    agent_id: &AgentId,
+    path: &Path,
+    content: &str,
+) -> anyhow::Result<Option<AgentVersion>> {
+    let dir = agent_dir(infra, agent_id);
+    let mut versions = read_manifest(infra, &dir).await?;
+    let Some(version) = AgentVersion::next(
+        &versions,
+        path.to_path_buf(),
+        forge_app::compute_hash(content),
+    ) else {
+        return Ok(None);
+    };
+
+    infra
+        .write(
+            &dir.join(format!("{}.md", version.version)),
+            Bytes::from(content.to_string()),
+        )
+        .await?;
+    versions.push(version.clone());
+    infra
+        .write(
+            &dir.join(MANIFEST_FILE),
+            Bytes::from(serde_json::to_string_pretty(&versions)?),
+        )
+        .await?;
+
+    Ok(Some(version))
+}
+
+fn agent_dir<I: EnvironmentInfra>(infra: &I, agent_id: &AgentId) -> PathBuf {
+    infra
+        .get_environment()
+        .agent_versions_path()
+        .join(agent_id.as_str())
+}
+
+async fn read_manifest<I: FileInfoInfra + FileReaderInfra>(
+    infra: &I,
+    dir: &Path,
+) -> anyhow::Result<Vec<AgentVersion>> {
+    let path = dir.join(MANIFEST_FILE);
+    if !infra.exists(&path).await? {
+        return Ok(vec![]);
+    }
+
+    let content = infra.read_utf8(&path).await?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse agent versions: {}", path.display()))
+}
+
+#[async_trait::async_trait]
+impl<I: EnvironmentInfra + FileInfoInfra + FileReaderInfra + FileWriterInfra> AgentVersionRepository
+    for ForgeAgentVersionRepository<I>
+{
+    async fn record_agent_version(
+        &self,
+        agent_id: &AgentId,
+        path: &Path,
+    ) -> anyhow::Result<Option<AgentVersion>> {
+        let content = self
+            .infra
+            .read_utf8(path)
+            .await
+            .with_context(|| format!("Failed to read agent: {}", path.display()))?;
+        record_agent_version(self.infra.as_ref(), agent_id, path, &content).await
+    }
+
+    async fn list_agent_versions(&self, agent_id: &AgentId) -> anyhow::Result<Vec<AgentVersion>> {
+        read_manifest(
+            self.infra.as_ref(),
+            &agent_dir(self.infra.as_ref(), agent_id),
+        )
+        .await
+    }
+
+    async fn read_agent_version(&self, agent_id: &AgentId, version: u32) -> anyhow::Result<String> {
+        let path = agent_dir(self.infra.as_ref(), agent_id).join(format!("{version}.md"));
+        if !self.infra.exists(&path).await? {
+            anyhow::bail!("Version {version} of agent '{agent_id}' not found");
+        }
+        self.infra.read_utf8(&path).await
+    }
+
+    async fn rollback_agent(
+        &self,
+        agent_id: &AgentId,
+        version: u32,
+    ) -> anyhow::Result<AgentVersion> {
+        let versions = self.list_agent_versions(agent_id).await?;
+        let target = versions
+            .iter()
+            .find(|v| v.version == version)
+            .with_context(|| format!("Version {version} of agent '{agent_id}' not found"))?;
+
+        let content = self.read_agent_version(agent_id, version).await?;
+        self.infra
+            .write(&target.path, Bytes::from(content.clone()))
+            .await
+            .with_context(|| format!("Failed to restore {}", target.path.display()))?;
+
+        // Restoring content identical to the latest version records nothing
+        let restored =
+            record_agent_version(self.infra.as_ref(), agent_id, &target.path, &content).await?;
+        match restored {
+            Some(restored) => Ok(restored),
+            None => self
+                .list_agent_versions(agent_id)
+                .await?
+                .into_iter()
+                .rev()
+                .find(|v| v.path == target.path)
+                .context("Agent version history is empty"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use forge_domain::{ConfigOperation, Environment, FileInfo};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Infrastructure backed by the file system, with the data directory in a
+    /// temporary directory
+    struct MockInfra {
+        base_path: PathBuf,
+    }
+
+    impl EnvironmentInfra for MockInfra {
+        type Config = forge_config::ForgeConfig;
+
+        fn get_environment(&self) -> Environment {
+            use fake::{Fake, Faker};
+            let mut env: Environment = Faker.fake();
+            env.base_path = self.base_path.clone();
+            env
+        }
+
+        async fn update_environment(&self, _ops: Vec<ConfigOperation>) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn get_config(&self) -> anyhow::Result<forge_config::ForgeConfig> {
+            Ok(forge_config::ForgeConfig::default())
+        }
+
+        fn get_env_var(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_env_vars(&self) -> BTreeMap<String, String> {
+            BTreeMap::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileInfoInfra for MockInfra {
+        async fn is_binary(&self, _path: &Path) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn is_file(&self, path: &Path) -> anyhow::Result<bool> {
+            Ok(path.is_file())
+        }
+
+        async fn exists(&self, path: &Path) -> anyhow::Result<bool> {
+            Ok(path.exists())
+        }
+
+        async fn file_size(&self, path: &Path) -> anyhow::Result<u64> {
+            Ok(std::fs::metadata(path)?.len())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileReaderInfra for MockInfra {
+        async fn read_utf8(&self, path: &Path) -> anyhow::Result<String> {
+            Ok(std::fs::read_to_string(path)?)
+        }
+
+        fn read_batch_utf8(
+            &self,
+            _batch_size: usize,
+            _paths: Vec<PathBuf>,
+        ) -> impl futures::Stream<Item = (PathBuf, anyhow::Result<String>)> + Send {
+            futures::stream::empty()
+        }
+
+        async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+            Ok(std::fs::read(path)?)
+        }
+
+        async fn range_read_utf8(
+            &self,
+            _path: &Path,
+            _start_line: u64,
+            _end_line: u64,
+        ) -> anyhow::Result<(String, FileInfo)> {
+            anyhow::bail!("Not supported")
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileWriterInfra for MockInfra {
+        async fn write(&self, path: &Path, content: Bytes) -> anyhow::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            Ok(std::fs::write(path, content)?)
+        }
+
+        async fn append(&self, _path: &Path, _content: Bytes) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn write_temp(
+            &self,
+            _prefix: &str,
+            _ext: &str,
+            _content: &str,
+        ) -> anyhow::Result<PathBuf> {
+            anyhow::bail!("Not supported")
+        }
+    }
+
+    fn repository_fixture(dir: &tempfile::TempDir) -> ForgeAgentVersionRepository<MockInfra> {
+        ForgeAgentVersionRepository::new(Arc::new(MockInfra {
+            base_path: dir.path().join("forge"),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_record_agent_version_skips_unchanged_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = repository_fixture(&dir);
+        let agent_id = AgentId::new("reviewer");
+        let path = dir.path().join("reviewer.md");
+
+        std::fs::write(&path, "v1").unwrap();
+        let first = fixture
+            .record_agent_version(&agent_id, &path)
+            .await
+            .unwrap();
+        let unchanged = fixture
+            .record_agent_version(&agent_id, &path)
+            .await
+            .unwrap();
+        std::fs::write(&path, "v2").unwrap();
+        let changed = fixture
+            .record_agent_version(&agent_id, &path)
+            .await
+            .unwrap();
+
+        let actual = (
+            first.map(|version| version.version),
+            unchanged,
+            changed.map(|version| version.version),
+            fixture.read_agent_version(&agent_id, 2).await.unwrap(),
+        );
+        let expected = (Some(1), None, Some(2), "v2".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_agent_restores_content_as_new_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = repository_fixture(&dir);
+        let agent_id = AgentId::new("reviewer");
+        let path = dir.path().join("reviewer.md");
+        std::fs::write(&path, "v1").unwrap();
+        fixture
+            .record_agent_version(&agent_id, &path)
+            .await
+            .unwrap();
+        std::fs::write(&path, "v2").unwrap();
+        fixture
+            .record_agent_version(&agent_id, &path)
+            .await
+            .unwrap();
+
+        let restored = fixture.rollback_agent(&agent_id, 1).await.unwrap();
+
+        let actual = (
+            restored.version,
+            std::fs::read_to_string(&path).unwrap(),
+            fixture.list_agent_versions(&agent_id).await.unwrap().len(),
+        );
+        let expected = (3, "v1".to_string(), 3);
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_read_agent_version_fails_for_unknown_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = repository_fixture(&dir);
+
+        let actual = fixture
+            .read_agent_version(&AgentId::new("reviewer"), 1)
+            .await
+            .is_err();
+
+        assert_eq!(actual, true);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::Context;
+use forge_domain::{ConversationId, ConversationLockHolder};
+
+/// A lock taken by this process, released once every user unlocked it
+struct HeldLock {
+    /// Open handle keeping the operating system lock
+    _file: File,
+    count: usize,
+}
+
+/// Advisory locks keeping two Forge processes, e.g. the CLI and the server,
+/// from writing the same conversation. Each conversation has a lock file
+/// recording its holder. The operating system releases the locks of a process
+/// when it exits, so a crash never leaves a conversation locked.
+pub struct ConversationLocks {
+    dir: PathBuf,
+    held: Mutex<HashMap<ConversationId, HeldLock>>,
+}
+
+impl ConversationLocks {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, held: Default::default() }
+    }
+
+    fn held(&self) -> MutexGuard<'_, HashMap<ConversationId, HeldLock>> {
+        self.held.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn path(&self, conversation_id: &ConversationId) -> PathBuf {
+        self.dir
+            .join(format!("{}.lock", conversation_id.into_string()))
+    }
+
+    /// Takes the lock of the conversation, or returns the process holding it
+    pub fn lock(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> anyhow::Result<Option<ConversationLockHolder>> {
+        let mut held = self.held();
+        if let Some(lock) = held.get_mut(conversation_id) {
+            lock.count += 1;
+            return Ok(None);
+        }
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(conversation_id);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(Some(read_holder(&mut file))),
+            Err(TryLockError::Error(error)) => {
+                return Err(error).with_context(|| format!("Failed to lock {}", path.display()));
+            }
+        }
+
+        let holder = ConversationLockHolder {
+            pid: Some(std::process::id()),
+            acquired_at: Some(chrono::Utc::now()),
+        };
+        file.set_len(0)?;
+        file.write_all(serde_json::to_string(&holder)?.as_bytes())?;
+        held.insert(*conversation_id, HeldLock { _file: file, count: 1 });
+        Ok(None)
+    }
+
+    /// Releases one use of a lock held by this process
+    pub fn unlock(&self, conversation_id: &ConversationId) {
+        let mut held = self.held();
+        if let Some(lock) = held.get_mut(conversation_id) {
+            lock.count -= 1;
+            if lock.count == 0 {
+                // Closing the file releases the lock
+                held.remove(conversation_id);
+            }
+        }
+    }
+
+    /// Fails when another process holds the lock of the conversation.
+    /// Conversations nobody locked stay writable.
+    pub fn ensure_writable(&self, conversation_id: &ConversationId) -> anyhow::Result<()> {
+        if self.held().contains_key(conversation_id) {
+            return Ok(());
+        }
+        let Ok(mut file) = File::open(self.path(conversation_id)) else {
+            return Ok(());
+        };
+        match file.try_lock_shared() {
+            Ok(()) => Ok(()),
+            Err(TryLockError::WouldBlock) => Err(anyhow::anyhow!(
+                "Conversation {} is open in {}, so it can only be read from here",
+                conversation_id.into_string(),
+                read_holder(&mut file)
+            )),
+            Err(TryLockError::Error(error)) => Err(error.into()),
+        }
+    }
+}
+
+/// Reads the holder recorded in a lock file, falling back to an unknown holder
+/// when the file can't be read or is still being written
+fn read_holder(file: &mut File) -> ConversationLockHolder {
+    let mut content = String::new();
+    match file.read_to_string(&mut content) {
+        Ok(_) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => ConversationLockHolder::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Two lock sets on the same directory, standing for two processes
+    fn fixture() -> (tempfile::TempDir, ConversationLocks, ConversationLocks) {
+        let dir = tempfile::tempdir().unwrap();
+        let first = ConversationLocks::new(dir.path().to_path_buf());
+        let second = ConversationLocks::new(dir.path().to_path_buf());
+        (dir, first, second)
+    }
+
+    #[test]
+    fn test_lock_held_elsewhere_is_read_only() {
+        let (_dir, first, second) = fixture();
+        let id = ConversationId::generate();
+
+        first.lock(&id).unwrap();
+
+        let actual = (
+            second.lock(&id).unwrap().and_then(|holder| holder.pid),
+            second.ensure_writable(&id).is_err(),
+            first.ensure_writable(&id).is_ok(),
+        );
+        let expected = (Some(std::process::id()), true, true);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_lock_is_released_after_last_unlock() {
+        let (_dir, first, second) = fixture();
+        let id = ConversationId::generate();
+
+        first.lock(&id).unwrap();
+        first.lock(&id).unwrap();
+        first.unlock(&id);
+        let still_held = second.lock(&id).unwrap().is_some();
+        first.unlock(&id);
+
+        let actual = (still_held, second.lock(&id).unwrap());
+        let expected = (true, None);
+        assert_eq!(actual, expected);
+    }
+}
@@ -4,10 +4,45 @@
 //! `forge_domain` counterparts for compile-time safety while keeping the
 //! storage layer independent from domain model changes.
 
+use std::collections::HashMap;
+
 use anyhow::Context as _;
 use forge_domain::{Context, ConversationId};
 use serde::{Deserialize, Serialize};
 
+/// Payloads larger than this many bytes are moved out of the context JSON and
+/// into the content-addressed blob store
+pub(super) const BLOB_THRESHOLD_BYTES: usize = 16 * 1024;
+
+/// Database model for the blobs table
+///
+/// Blobs are keyed by the SHA-256 hash of their content so identical payloads
+/// are stored only once regardless of how many conversations reference them.
+#[derive(Debug, Clone, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable)]
+#[diesel(table_name = crate::database::schema::blobs)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub(super) struct BlobRecord {
+    pub hash: String,
+    pub content: String,
+    pub size: i64,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl BlobRecord {
+    fn new(content: String) -> Self {
+        Self {
+            hash: forge_app::compute_hash(&content),
+            size: content.len() as i64,
+            content,
+            created_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+fn missing_blob(hash: &str) -> anyhow::Error {
+    anyhow::anyhow!("Blob '{hash}' referenced by the conversation is missing")
+}
+
 /// Repository-specific representation of ModelId
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
@@ -30,6 +65,30 @@ impl From<ModelIdRecord> for forge_domain::ModelId {
 pub(super) struct ImageRecord {
     url: String,
     mime_type: String,
+    /// Hash of the blob holding the image URL when it was too large to inline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    blob: Option<String>,
+}
+
+impl ImageRecord {
+    fn externalize(&mut self, threshold: usize) -> Option<BlobRecord> {
+        if self.blob.is_some() || self.url.len() <= threshold {
+            return None;
+        }
+        let blob = BlobRecord::new(std::mem::take(&mut self.url));
+        self.blob = Some(blob.hash.clone());
+        Some(blob)
+    }
+
+    fn resolve(&mut self, blobs: &HashMap<String, String>) -> anyhow::Result<()> {
+        if let Some(hash) = self.blob.take() {
+            self.url = blobs
+                .get(&hash)
+                .cloned()
+                .ok_or_else(|| missing_blob(&hash))?;
+        }
+        Ok(())
+    }
 }
 
 impl From<&forge_domain::Image> for ImageRecord {
@@ -37,6 +96,7 @@ impl From<&forge_domain::Image> for ImageRecord {
         Self {
             url: image.url().to_string(),
             mime_type: image.mime_type().to_string(),
+            blob: None,
         }
     }
 }
@@ -378,6 +438,8 @@ pub(super) enum ToolValueRecord {
     },
     Image(ImageRecord),
     Empty,
+    /// Hash of the blob holding text that was too large to inline
+    Blob(String),
     // Legacy variants for backward compatibility with old conversations
     // These were removed from the domain model but may exist in stored data
     /// Legacy: Markdown-formatted text (now converted to Text)
@@ -396,6 +458,32 @@ pub(super) struct FileDiffRecord {
     pub new_text: String,
 }
 
+impl ToolValueRecord {
+    fn externalize(&mut self, threshold: usize) -> Option<BlobRecord> {
+        match self {
+            Self::Text(text) if text.len() > threshold => {
+                let blob = BlobRecord::new(std::mem::take(text));
+                *self = Self::Blob(blob.hash.clone());
+                Some(blob)
+            }
+            Self::Image(image) => image.externalize(threshold),
+            _ => None,
+        }
+    }
+
+    fn resolve(&mut self, blobs: &HashMap<String, String>) -> anyhow::Result<()> {
+        match self {
+            Self::Blob(hash) => {
+                let content = blobs.get(hash).cloned().ok_or_else(|| missing_blob(hash))?;
+                *self = Self::Text(content);
+            }
+            Self::Image(image) => image.resolve(blobs)?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
 impl From<&forge_domain::ToolValue> for ToolValueRecord {
     fn from(value: &forge_domain::ToolValue) -> Self {
         match value {
@@ -422,6 +510,7 @@ impl TryFrom<ToolValueRecord> for forge_domain::ToolValue {
             },
             ToolValueRecord::Image(img) => Self::Image(img.into()),
             ToolValueRecord::Empty => Self::Empty,
+            ToolValueRecord::Blob(hash) => return Err(missing_blob(&hash)),
             // Legacy variant migrations
             ToolValueRecord::Markdown(md) => Self::Text(md),
             ToolValueRecord::FileDiff(diff) => {
@@ -746,6 +835,46 @@ pub(super) struct ContextRecord {
     stream: Option<bool>,
 }
 
+impl ContextRecord {
+    /// Moves tool outputs and images larger than `threshold` bytes into blobs,
+    /// leaving references to their hashes in the context
+    pub(super) fn externalize_blobs(&mut self, threshold: usize) -> Vec<BlobRecord> {
+        let mut blobs = Vec::new();
+        for entry in &mut self.messages {
+            match &mut entry.message {
+                ContextMessageValueRecord::Tool(result) => blobs.extend(
+                    result
+                        .output
+                        .values
+                        .iter_mut()
+                        .filter_map(|value| value.externalize(threshold)),
+                ),
+                ContextMessageValueRecord::Image(image) => {
+                    blobs.extend(image.externalize(threshold))
+                }
+                ContextMessageValueRecord::Text(_) => {}
+            }
+        }
+        blobs
+    }
+
+    /// Replaces blob references in the context with the blob content
+    pub(super) fn resolve_blobs(&mut self, blobs: &HashMap<String, String>) -> anyhow::Result<()> {
+        for entry in &mut self.messages {
+            match &mut entry.message {
+                ContextMessageValueRecord::Tool(result) => {
+                    for value in &mut result.output.values {
+                        value.resolve(blobs)?;
+                    }
+                }
+                ContextMessageValueRecord::Image(image) => image.resolve(blobs)?,
+                ContextMessageValueRecord::Text(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
 impl From<&Context> for ContextRecord {
     fn from(context: &Context) -> Self {
         Self {
@@ -819,6 +948,10 @@ impl TryFrom<ContextRecord> for Context {
             reasoning: record.reasoning.map(Into::into),
             stream: record.stream,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
         })
     }
 }
@@ -949,25 +1082,43 @@ pub(super) struct ConversationRecord {
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: Option<chrono::NaiveDateTime>,
     pub metrics: Option<String>,
+    pub pinned: bool,
+    pub owner: Option<String>,
 }
 
 impl ConversationRecord {
     /// Creates a new ConversationRecord from a Conversation domain object
+    #[cfg(test)]
     pub fn new(
         conversation: forge_domain::Conversation,
         workspace_id: forge_domain::WorkspaceHash,
     ) -> Self {
+        Self::with_blobs(conversation, workspace_id, usize::MAX).0
+    }
+
+    /// Creates a new ConversationRecord, moving context payloads larger than
+    /// `blob_threshold` bytes into the returned blobs
+    pub fn with_blobs(
+        conversation: forge_domain::Conversation,
+        workspace_id: forge_domain::WorkspaceHash,
+        blob_threshold: usize,
+    ) -> (Self, Vec<BlobRecord>) {
+        let mut blobs = Vec::new();
         let context = conversation
             .context
             .as_ref()
             .filter(|ctx| !ctx.messages.is_empty() || ctx.initiator.is_some())
             .map(ContextRecord::from)
+            .map(|mut ctx_record| {
+                blobs = ctx_record.externalize_blobs(blob_threshold);
+                ctx_record
+            })
             .and_then(|ctx_record| serde_json::to_string(&ctx_record).ok());
         let updated_at = context.as_ref().map(|_| chrono::Utc::now().naive_utc());
         let metrics_record = MetricsRecord::from(&conversation.metrics);
         let metrics = serde_json::to_string(&metrics_record).ok();
 
-        Self {
+        let record = Self {
             conversation_id: conversation.id.into_string(),
             title: conversation.title.clone(),
             context,
@@ -975,55 +1126,72 @@ impl ConversationRecord {
             updated_at,
             workspace_id: workspace_id.id() as i64,
             metrics,
-        }
+            pinned: conversation.metadata.pinned,
+            owner: conversation.metadata.owner.clone(),
+        };
+        (record, blobs)
     }
-}
-
-impl TryFrom<ConversationRecord> for forge_domain::Conversation {
-    type Error = anyhow::Error;
 
-    fn try_from(record: ConversationRecord) -> anyhow::Result<Self> {
-        let conversation_id = record.conversation_id.clone();
+    /// Converts the record into a Conversation, resolving blob references in
+    /// the context from `blobs`, a map of blob hash to content
+    pub fn into_conversation(
+        self,
+        blobs: &HashMap<String, String>,
+    ) -> anyhow::Result<forge_domain::Conversation> {
+        let conversation_id = self.conversation_id.clone();
         let id = ConversationId::parse(conversation_id.clone())
             .with_context(|| format!("Failed to parse conversation ID: {}", conversation_id))?;
 
-        let context = if let Some(context_str) = record.context {
-            Some(
-                serde_json::from_str::<ContextRecord>(&context_str)
-                    .with_context(|| {
-                        format!(
-                            "Failed to deserialize context for conversation {}",
-                            conversation_id
-                        )
-                    })?
-                    .try_into()
-                    .with_context(|| {
-                        format!(
-                            "Failed to convert context record to domain type for conversation {}",
-                            conversation_id
-                        )
-                    })?,
-            )
+        let context = if let Some(context_str) = self.context {
+            let mut context_record = serde_json::from_str::<ContextRecord>(&context_str)
+                .with_context(|| {
+                    format!(
+                        "Failed to deserialize context for conversation {}",
+                        conversation_id
+                    )
+                })?;
+            context_record.resolve_blobs(blobs).with_context(|| {
+                format!(
+                    "Failed to resolve blobs for conversation {}",
+                    conversation_id
+                )
+            })?;
+            Some(context_record.try_into().with_context(|| {
+                format!(
+                    "Failed to convert context record to domain type for conversation {}",
+                    conversation_id
+                )
+            })?)
         } else {
             None
         };
 
         // Deserialize metrics using MetricsRecord for compile-time safety
-        let metrics = record
+        let metrics = self
             .metrics
             .and_then(|m| serde_json::from_str::<MetricsRecord>(&m).ok())
             .map(forge_domain::Metrics::from)
             .unwrap_or_else(|| {
-                forge_domain::Metrics::default().started_at(record.created_at.and_utc())
+                forge_domain::Metrics::default().started_at(self.created_at.and_utc())
             });
 
         Ok(forge_domain::Conversation::new(id)
             .context(context)
-            .title(record.title)
+            .title(self.title)
             .metrics(metrics)
             .metadata(
-                forge_domain::MetaData::new(record.created_at.and_utc())
-                    .updated_at(record.updated_at.map(|updated_at| updated_at.and_utc())),
+                forge_domain::MetaData::new(self.created_at.and_utc())
+                    .updated_at(self.updated_at.map(|updated_at| updated_at.and_utc()))
+                    .pinned(self.pinned)
+                    .owner(self.owner),
             ))
     }
 }
+
+impl TryFrom<ConversationRecord> for forge_domain::Conversation {
+    type Error = anyhow::Error;
+
+    fn try_from(record: ConversationRecord) -> anyhow::Result<Self> {
+        record.into_conversation(&HashMap::new())
+    }
+}
@@ -1,11 +1,16 @@
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
+use diesel::dsl::{exists, not, sql};
 use diesel::prelude::*;
-use forge_domain::{Conversation, ConversationId, ConversationRepository, WorkspaceHash};
+use diesel::sql_types::BigInt;
+use forge_domain::{
+    Conversation, ConversationId, ConversationRepository, ConversationSummary, WorkspaceHash,
+};
 
-use crate::conversation::conversation_record::ConversationRecord;
+use crate::conversation::conversation_record::{BLOB_THRESHOLD_BYTES, ConversationRecord};
 use crate::database::DatabasePool;
-use crate::database::schema::conversations;
+use crate::database::schema::{blobs, conversation_blobs, conversations};
 
 pub struct ConversationRepositoryImpl {
     pool: Arc<DatabasePool>,
@@ -16,6 +21,49 @@ impl ConversationRepositoryImpl {
     pub fn new(pool: Arc<DatabasePool>, workspace_id: WorkspaceHash) -> Self {
         Self { pool, wid: workspace_id }
     }
+
+    /// Loads the content of every blob referenced by the given conversations,
+    /// keyed by blob hash
+    fn load_blobs(
+        connection: &mut SqliteConnection,
+        conversation_ids: &[&str],
+    ) -> anyhow::Result<HashMap<String, String>> {
+        let rows: Vec<(String, String)> = conversation_blobs::table
+            .inner_join(blobs::table.on(blobs::hash.eq(conversation_blobs::hash)))
+            .filter(conversation_blobs::conversation_id.eq_any(conversation_ids.iter().copied()))
+            .select((blobs::hash, blobs::content))
+            .distinct()
+            .load(connection)?;
+        Ok(rows.into_iter().collect())
+    }
+
+    fn into_conversations(
+        connection: &mut SqliteConnection,
+        records: Vec<ConversationRecord>,
+    ) -> anyhow::Result<Vec<Conversation>> {
+        let ids: Vec<&str> = records
+            .iter()
+            .map(|record| record.conversation_id.as_str())
+            .collect();
+        let contents = Self::load_blobs(connection, &ids)?;
+        records
+            .into_iter()
+            .map(|record| record.into_conversation(&contents))
+            .collect()
+    }
+
+    /// Deletes blobs that are no longer referenced by any conversation,
+    /// returning how many were removed. Upserts and deletes only drop
+    /// references, so this runs once at startup rather than on every write.
+    pub fn collect_garbage(&self) -> anyhow::Result<usize> {
+        let mut connection = self.pool.get_connection()?;
+        let deleted = diesel::delete(blobs::table)
+            .filter(not(exists(
+                conversation_blobs::table.filter(conversation_blobs::hash.eq(blobs::hash)),
+            )))
+            .execute(&mut connection)?;
+        Ok(deleted)
+    }
 }
 
 #[async_trait::async_trait]
@@ -24,19 +72,46 @@ impl ConversationRepository for ConversationRepositoryImpl {
         let mut connection = self.pool.get_connection()?;
 
         let wid = self.wid;
-        let record = ConversationRecord::new(conversation, wid);
-        diesel::insert_into(conversations::table)
-            .values(&record)
-            .on_conflict(conversations::conversation_id)
-            .do_update()
-            .set((
-                conversations::title.eq(&record.title),
-                conversations::context.eq(&record.context),
-                conversations::updated_at.eq(record.updated_at),
-                conversations::metrics.eq(&record.metrics),
-            ))
-            .execute(&mut connection)?;
-        Ok(())
+        let (record, blob_records) =
+            ConversationRecord::with_blobs(conversation, wid, BLOB_THRESHOLD_BYTES);
+        connection.transaction::<_, anyhow::Error, _>(|connection| {
+            diesel::insert_into(conversations::table)
+                .values(&record)
+                .on_conflict(conversations::conversation_id)
+                .do_update()
+                .set((
+                    conversations::title.eq(&record.title),
+                    conversations::context.eq(&record.context),
+                    conversations::updated_at.eq(record.updated_at),
+                    conversations::metrics.eq(&record.metrics),
+                    conversations::pinned.eq(record.pinned),
+                ))
+                .execute(connection)?;
+
+            // Identical payloads share a single blob row
+            for blob in &blob_records {
+                diesel::insert_or_ignore_into(blobs::table)
+                    .values(blob)
+                    .execute(connection)?;
+            }
+
+            // Replace the references so blobs dropped from the context become collectable
+            diesel::delete(conversation_blobs::table)
+                .filter(conversation_blobs::conversation_id.eq(&record.conversation_id))
+                .execute(connection)?;
+            let hashes: BTreeSet<&str> =
+                blob_records.iter().map(|blob| blob.hash.as_str()).collect();
+            for hash in hashes {
+                diesel::insert_into(conversation_blobs::table)
+                    .values((
+                        conversation_blobs::conversation_id.eq(&record.conversation_id),
+                        conversation_blobs::hash.eq(hash),
+                    ))
+                    .execute(connection)?;
+            }
+
+            Ok(())
+        })
     }
 
     async fn get_conversation(
@@ -51,7 +126,7 @@ impl ConversationRepository for ConversationRepositoryImpl {
             .optional()?;
 
         match record {
-            Some(record) => Ok(Some(Conversation::try_from(record)?)),
+            Some(record) => Ok(Self::into_conversations(&mut connection, vec![record])?.pop()),
             None => Ok(None),
         }
     }
@@ -79,9 +154,7 @@ impl ConversationRepository for ConversationRepositoryImpl {
             return Ok(None);
         }
 
-        let conversations: Result<Vec<Conversation>, _> =
-            records.into_iter().map(Conversation::try_from).collect();
-        Ok(Some(conversations?))
+        Ok(Some(Self::into_conversations(&mut connection, records)?))
     }
 
     async fn get_last_conversation(&self) -> anyhow::Result<Option<Conversation>> {
@@ -93,24 +166,74 @@ impl ConversationRepository for ConversationRepositoryImpl {
             .order(conversations::updated_at.desc())
             .first(&mut connection)
             .optional()?;
-        let conversation = match record {
-            Some(record) => Some(Conversation::try_from(record)?),
-            None => None,
-        };
-        Ok(conversation)
+        match record {
+            Some(record) => Ok(Self::into_conversations(&mut connection, vec![record])?.pop()),
+            None => Ok(None),
+        }
     }
 
     async fn delete_conversation(&self, conversation_id: &ConversationId) -> anyhow::Result<()> {
         let mut connection = self.pool.get_connection()?;
         let workspace_id = self.wid.id() as i64;
 
-        // Security: Ensure users can only delete conversations within their workspace
-        diesel::delete(conversations::table)
-            .filter(conversations::workspace_id.eq(&workspace_id))
-            .filter(conversations::conversation_id.eq(conversation_id.into_string()))
-            .execute(&mut connection)?;
+        let conversation_id = conversation_id.into_string();
 
-        Ok(())
+        connection.transaction::<_, anyhow::Error, _>(|connection| {
+            // Security: Ensure users can only delete conversations within their workspace
+            let deleted = diesel::delete(conversations::table)
+                .filter(conversations::workspace_id.eq(&workspace_id))
+                .filter(conversations::conversation_id.eq(&conversation_id))
+                .execute(connection)?;
+
+            if deleted > 0 {
+                diesel::delete(conversation_blobs::table)
+                    .filter(conversation_blobs::conversation_id.eq(&conversation_id))
+                    .execute(connection)?;
+            }
+            Ok(())
+        })
+    }
+
+    async fn get_conversation_summaries(&self) -> anyhow::Result<Vec<ConversationSummary>> {
+        let mut connection = self.pool.get_connection()?;
+        let workspace_id = self.wid.id() as i64;
+
+        // Measure the stored payload and its blobs in bytes without loading them
+        let rows: Vec<(
+            String,
+            Option<String>,
+            chrono::NaiveDateTime,
+            Option<chrono::NaiveDateTime>,
+            bool,
+            i64,
+        )> = conversations::table
+            .filter(conversations::workspace_id.eq(&workspace_id))
+            .select((
+                conversations::conversation_id,
+                conversations::title,
+                conversations::created_at,
+                conversations::updated_at,
+                conversations::pinned,
+                sql::<BigInt>(
+                    "COALESCE(LENGTH(CAST(context AS BLOB)), 0) + \
+                     COALESCE(LENGTH(CAST(metrics AS BLOB)), 0) + \
+                     (SELECT COALESCE(SUM(blobs.size), 0) FROM conversation_blobs \
+                      JOIN blobs ON blobs.hash = conversation_blobs.hash \
+                      WHERE conversation_blobs.conversation_id = conversations.conversation_id)",
+                ),
+            ))
+            .load(&mut connection)?;
+
+        rows.into_iter()
+            .map(|(id, title, created_at, updated_at, pinned, size)| {
+                let id = ConversationId::parse(&id)?;
+                let updated_at = updated_at.unwrap_or(created_at).and_utc();
+                Ok(ConversationSummary::new(id, updated_at)
+                    .title(title)
+                    .size_bytes(size.max(0) as u64)
+                    .pinned(pinned))
+            })
+            .collect()
     }
 }
 
@@ -349,6 +472,8 @@ mod tests {
             updated_at: None,
             workspace_id: 0,
             metrics: None,
+            pinned: false,
+            owner: None,
         };
 
         let actual = Conversation::try_from(fixture)?;
@@ -793,6 +918,8 @@ mod tests {
             updated_at: None,
             workspace_id: 0,
             metrics: None,
+            pinned: false,
+            owner: None,
         };
 
         let result = Conversation::try_from(fixture);
@@ -999,4 +1126,142 @@ mod tests {
             forge_domain::ToolValue::Text("[File diff: /src/main.rs]".to_string())
         );
     }
+
+    #[tokio::test]
+    async fn test_get_conversation_summaries() -> anyhow::Result<()> {
+        let context = Context::default().messages(vec![ContextMessage::user("Hello", None).into()]);
+        let mut pinned = Conversation::generate().context(Some(context));
+        pinned.metadata.pinned = true;
+        let empty = Conversation::generate().title(Some("Empty".to_string()));
+        let repo = repository()?;
+
+        repo.upsert_conversation(pinned.clone()).await?;
+        repo.upsert_conversation(empty.clone()).await?;
+
+        let mut actual = repo
+            .get_conversation_summaries()
+            .await?
+            .into_iter()
+            .map(|summary| {
+                (
+                    summary.id,
+                    summary.title,
+                    summary.pinned,
+                    summary.size_bytes > 0,
+                )
+            })
+            .collect::<Vec<_>>();
+        actual.sort_by_key(|(_, _, pinned, _)| *pinned);
+
+        let expected = vec![
+            (empty.id, Some("Empty".to_string()), false, true),
+            (pinned.id, None, true, true),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upsert_updates_pinned_flag() -> anyhow::Result<()> {
+        let mut fixture = Conversation::generate();
+        let repo = repository()?;
+        repo.upsert_conversation(fixture.clone()).await?;
+
+        fixture.metadata.pinned = true;
+        repo.upsert_conversation(fixture.clone()).await?;
+
+        let actual = repo
+            .get_conversation(&fixture.id)
+            .await?
+            .unwrap()
+            .metadata
+            .pinned;
+
+        assert!(actual);
+        Ok(())
+    }
+
+    fn large_output_fixture(content: &str) -> Conversation {
+        let context = Context::default().messages(vec![
+            ContextMessage::Tool(ToolResult::new("read").success(content)).into(),
+        ]);
+        Conversation::generate().context(Some(context))
+    }
+
+    fn blob_count(repo: &ConversationRepositoryImpl) -> anyhow::Result<i64> {
+        let mut connection = repo.pool.get_connection()?;
+        Ok(blobs::table.count().get_result(&mut connection)?)
+    }
+
+    #[tokio::test]
+    async fn test_upsert_moves_large_tool_output_into_blob() -> anyhow::Result<()> {
+        let content = "x".repeat(BLOB_THRESHOLD_BYTES + 1);
+        let fixture = large_output_fixture(&content);
+        let repo = repository()?;
+
+        repo.upsert_conversation(fixture.clone()).await?;
+
+        let stored: Option<String> = conversations::table
+            .filter(conversations::conversation_id.eq(fixture.id.into_string()))
+            .select(conversations::context)
+            .first(&mut repo.pool.get_connection()?)?;
+        assert!(!stored.unwrap().contains(&content));
+        assert_eq!(blob_count(&repo)?, 1);
+
+        let actual = repo.get_conversation(&fixture.id).await?.unwrap().context;
+        let expected = fixture.context;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upsert_keeps_small_tool_output_inline() -> anyhow::Result<()> {
+        let fixture = large_output_fixture("small output");
+        let repo = repository()?;
+
+        repo.upsert_conversation(fixture).await?;
+
+        assert_eq!(blob_count(&repo)?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_identical_payloads_share_blob_until_unreferenced() -> anyhow::Result<()> {
+        let content = "y".repeat(BLOB_THRESHOLD_BYTES + 1);
+        let first = large_output_fixture(&content);
+        let second = large_output_fixture(&content);
+        let repo = repository()?;
+        repo.upsert_conversation(first.clone()).await?;
+        repo.upsert_conversation(second.clone()).await?;
+        assert_eq!(blob_count(&repo)?, 1);
+
+        repo.delete_conversation(&first.id).await?;
+        repo.collect_garbage()?;
+        assert_eq!(blob_count(&repo)?, 1);
+
+        repo.delete_conversation(&second.id).await?;
+        assert_eq!(blob_count(&repo)?, 1);
+        repo.collect_garbage()?;
+        assert_eq!(blob_count(&repo)?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upsert_collects_blob_dropped_from_context() -> anyhow::Result<()> {
+        let content = "z".repeat(BLOB_THRESHOLD_BYTES + 1);
+        let fixture = large_output_fixture(&content);
+        let repo = repository()?;
+        repo.upsert_conversation(fixture.clone()).await?;
+
+        let compacted = fixture.context(Some(
+            Context::default().messages(vec![ContextMessage::user("Summary", None).into()]),
+        ));
+        repo.upsert_conversation(compacted).await?;
+        assert_eq!(blob_count(&repo)?, 1);
+
+        let actual = repo.collect_garbage()?;
+        assert_eq!(actual, 1);
+        assert_eq!(blob_count(&repo)?, 0);
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
+mod conversation_lock;
 mod conversation_record;
 mod conversation_repo;
 
+pub use conversation_lock::*;
 pub use conversation_repo::*;
@@ -0,0 +1,2 @@
+-- Remove pinned column from conversations table
+ALTER TABLE conversations DROP COLUMN pinned;
@@ -0,0 +1,2 @@
+-- Add pinned flag so retention pruning can skip selected conversations
+ALTER TABLE conversations ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT 0;
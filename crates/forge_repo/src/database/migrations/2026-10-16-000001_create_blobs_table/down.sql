@@ -0,0 +1,4 @@
+-- Drop content-addressed blob storage
+DROP INDEX IF EXISTS idx_conversation_blobs_hash;
+DROP TABLE IF EXISTS conversation_blobs;
+DROP TABLE IF EXISTS blobs;
@@ -0,0 +1,16 @@
+-- Content-addressed storage for large conversation payloads
+CREATE TABLE IF NOT EXISTS blobs (
+    hash TEXT PRIMARY KEY NOT NULL,
+    content TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tracks which conversations reference which blobs so unreferenced blobs can be collected
+CREATE TABLE IF NOT EXISTS conversation_blobs (
+    conversation_id TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    PRIMARY KEY (conversation_id, hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversation_blobs_hash ON conversation_blobs(hash);
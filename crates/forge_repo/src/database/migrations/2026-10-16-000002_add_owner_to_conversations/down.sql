@@ -0,0 +1,2 @@
+-- Remove owner column from conversations table
+ALTER TABLE conversations DROP COLUMN owner;
@@ -0,0 +1,2 @@
+-- Add owner so conversations created through the server are isolated per user
+ALTER TABLE conversations ADD COLUMN owner TEXT;
@@ -1,5 +1,21 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    blobs (hash) {
+        hash -> Text,
+        content -> Text,
+        size -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    conversation_blobs (conversation_id, hash) {
+        conversation_id -> Text,
+        hash -> Text,
+    }
+}
+
 diesel::table! {
     conversations (conversation_id) {
         conversation_id -> Text,
@@ -9,5 +25,9 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Nullable<Timestamp>,
         metrics -> Nullable<Text>,
+        pinned -> Bool,
+        owner -> Nullable<Text>,
     }
 }
+
+diesel::allow_tables_to_appear_in_same_query!(blobs, conversation_blobs, conversations,);
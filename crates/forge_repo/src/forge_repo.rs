@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::Bytes;
 use forge_app::{
@@ -10,11 +11,13 @@ use forge_app::{
 };
 use forge_config::ForgeConfig;
 use forge_domain::{
-    AnyProvider, AuthCredential, ChatCompletionMessage, ChatRepository, CommandOutput, Context,
-    Conversation, ConversationId, ConversationRepository, Environment, FileInfo,
-    FuzzySearchRepository, McpServerConfig, MigrationResult, Model, ModelId, Provider, ProviderId,
-    ProviderRepository, ResultStream, SearchMatch, Skill, SkillRepository, Snapshot,
-    SnapshotRepository,
+    AgentId, AgentVersion, AgentVersionRepository, AnyProvider, AuthCredential,
+    ChatCompletionMessage, ChatRepository, CommandOutput, Context, Conversation, ConversationId,
+    ConversationLockHolder, ConversationRepository, ConversationSummary, Environment, FileInfo,
+    FuzzySearchRepository, InstalledPack, McpServerConfig, MigrationResult, Model, ModelId,
+    PackKind, PackRepository, PackSource, PackStatus, Provider, ProviderId, ProviderRepository,
+    ResponseCacheInfo, ResultStream, SearchMatch, Skill, SkillRepository, Snapshot,
+    SnapshotRepository, SnapshotTag, StorageMigration, StorageRepository, StorageScope,
 };
 // Re-export CacacheStorage from forge_infra
 pub use forge_infra::CacacheStorage;
@@ -24,13 +27,16 @@ use reqwest_eventsource::EventSource;
 use url::Url;
 
 use crate::agent::ForgeAgentRepository;
+use crate::agent_version::ForgeAgentVersionRepository;
 use crate::context_engine::ForgeContextEngineRepository;
-use crate::conversation::ConversationRepositoryImpl;
+use crate::conversation::{ConversationLocks, ConversationRepositoryImpl};
 use crate::database::{DatabasePool, PoolConfig};
 use crate::fs_snap::ForgeFileSnapshotService;
 use crate::fuzzy_search::ForgeFuzzySearchRepository;
+use crate::pack::ForgePackRepository;
 use crate::provider::{ForgeChatRepository, ForgeProviderRepository};
 use crate::skill::ForgeSkillRepository;
+use crate::storage::ForgeStorageRepository;
 use crate::validation::ForgeValidationRepository;
 
 /// Repository layer that implements all domain repository traits
@@ -42,12 +48,16 @@ pub struct ForgeRepo<F> {
     infra: Arc<F>,
     file_snapshot_service: Arc<ForgeFileSnapshotService>,
     conversation_repository: Arc<ConversationRepositoryImpl>,
+    conversation_locks: Arc<ConversationLocks>,
     mcp_cache_repository: Arc<CacacheStorage>,
     provider_repository: Arc<ForgeProviderRepository<F>>,
     chat_repository: Arc<ForgeChatRepository<F>>,
     codebase_repo: Arc<ForgeContextEngineRepository<F>>,
     agent_repository: Arc<ForgeAgentRepository<F>>,
+    agent_version_repository: Arc<ForgeAgentVersionRepository<F>>,
     skill_repository: Arc<ForgeSkillRepository<F>>,
+    pack_repository: Arc<ForgePackRepository<F>>,
+    storage_repository: Arc<ForgeStorageRepository<F>>,
     validation_repository: Arc<ForgeValidationRepository<F>>,
     fuzzy_search_repository: Arc<ForgeFuzzySearchRepository<F>>,
 }
@@ -62,37 +72,58 @@ impl<
 {
     pub fn new(infra: Arc<F>) -> Self {
         let env = infra.get_environment();
-        let file_snapshot_service = Arc::new(ForgeFileSnapshotService::new(env.clone()));
+        let config = infra.get_config().ok();
+        let scope = config
+            .as_ref()
+            .map(|config| forge_app::utils::storage_scope(config.storage))
+            .unwrap_or_default();
+        let compress_snapshots = config
+            .as_ref()
+            .is_some_and(|config| config.compress_snapshots);
+        let file_snapshot_service = Arc::new(ForgeFileSnapshotService::new(
+            env.snapshot_path(scope),
+            compress_snapshots,
+        ));
         let db_pool =
-            Arc::new(DatabasePool::try_from(PoolConfig::new(env.database_path())).unwrap());
+            Arc::new(DatabasePool::try_from(PoolConfig::new(env.database_path(scope))).unwrap());
         let conversation_repository = Arc::new(ConversationRepositoryImpl::new(
             db_pool.clone(),
-            env.workspace_hash(),
+            env.storage_workspace_hash(scope),
         ));
+        if let Err(error) = conversation_repository.collect_garbage() {
+            tracing::warn!(error = %error, "Failed to collect unreferenced blobs");
+        }
+        let conversation_locks = Arc::new(ConversationLocks::new(env.conversation_locks_path()));
 
-        let mcp_cache_repository = Arc::new(CacacheStorage::new(
-            env.cache_dir().join("mcp_cache"),
-            Some(3600),
-        )); // 1 hour TTL
+        // 1 hour TTL
+        let mcp_cache_repository =
+            Arc::new(CacacheStorage::new(env.kv_cache_dir(), Some(3600)).with_namespace("mcp"));
 
         let provider_repository = Arc::new(ForgeProviderRepository::new(infra.clone()));
         let chat_repository = Arc::new(ForgeChatRepository::new(infra.clone()));
 
         let codebase_repo = Arc::new(ForgeContextEngineRepository::new(infra.clone()));
         let agent_repository = Arc::new(ForgeAgentRepository::new(infra.clone()));
+        let agent_version_repository = Arc::new(ForgeAgentVersionRepository::new(infra.clone()));
         let skill_repository = Arc::new(ForgeSkillRepository::new(infra.clone()));
+        let pack_repository = Arc::new(ForgePackRepository::new(infra.clone()));
+        let storage_repository = Arc::new(ForgeStorageRepository::new(infra.clone()));
         let validation_repository = Arc::new(ForgeValidationRepository::new(infra.clone()));
         let fuzzy_search_repository = Arc::new(ForgeFuzzySearchRepository::new(infra.clone()));
         Self {
             infra,
             file_snapshot_service,
             conversation_repository,
+            conversation_locks,
             mcp_cache_repository,
             provider_repository,
             chat_repository,
             codebase_repo,
             agent_repository,
+            agent_version_repository,
             skill_repository,
+            pack_repository,
+            storage_repository,
             validation_repository,
             fuzzy_search_repository,
         }
@@ -108,11 +139,57 @@ impl<F: Send + Sync> SnapshotRepository for ForgeRepo<F> {
     async fn undo_snapshot(&self, file_path: &Path) -> anyhow::Result<()> {
         self.file_snapshot_service.undo_snapshot(file_path).await
     }
+
+    async fn read_snapshot(&self, file_path: &Path, index: usize) -> anyhow::Result<Vec<u8>> {
+        self.file_snapshot_service
+            .read_snapshot(file_path, index)
+            .await
+    }
+
+    async fn restore_all_at(&self, timestamp: Duration) -> anyhow::Result<Vec<PathBuf>> {
+        self.file_snapshot_service.restore_all_at(timestamp).await
+    }
+
+    async fn tag_snapshots(
+        &self,
+        file_path: &Path,
+        since: Duration,
+        tag: &SnapshotTag,
+    ) -> anyhow::Result<()> {
+        self.file_snapshot_service
+            .tag_snapshots(file_path, since, tag)
+            .await
+    }
+
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> anyhow::Result<Vec<Snapshot>> {
+        self.file_snapshot_service
+            .list_snapshots_for_conversation(conversation_id)
+            .await
+    }
+
+    async fn export_bundle(
+        &self,
+        paths: &[PathBuf],
+        root: &Path,
+        output: &Path,
+    ) -> anyhow::Result<usize> {
+        self.file_snapshot_service
+            .export_bundle(paths, root, output)
+            .await
+    }
+
+    async fn import_bundle(&self, bundle: &Path, root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        self.file_snapshot_service.import_bundle(bundle, root).await
+    }
 }
 
 #[async_trait::async_trait]
 impl<F: Send + Sync> ConversationRepository for ForgeRepo<F> {
     async fn upsert_conversation(&self, conversation: Conversation) -> anyhow::Result<()> {
+        self.conversation_locks.ensure_writable(&conversation.id)?;
         self.conversation_repository
             .upsert_conversation(conversation)
             .await
@@ -141,10 +218,29 @@ impl<F: Send + Sync> ConversationRepository for ForgeRepo<F> {
     }
 
     async fn delete_conversation(&self, conversation_id: &ConversationId) -> anyhow::Result<()> {
+        self.conversation_locks.ensure_writable(conversation_id)?;
         self.conversation_repository
             .delete_conversation(conversation_id)
             .await
     }
+
+    async fn get_conversation_summaries(&self) -> anyhow::Result<Vec<ConversationSummary>> {
+        self.conversation_repository
+            .get_conversation_summaries()
+            .await
+    }
+
+    async fn lock_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> anyhow::Result<Option<ConversationLockHolder>> {
+        self.conversation_locks.lock(conversation_id)
+    }
+
+    async fn unlock_conversation(&self, conversation_id: &ConversationId) -> anyhow::Result<()> {
+        self.conversation_locks.unlock(conversation_id);
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -169,6 +265,25 @@ impl<
     async fn models(&self, provider: Provider<Url>) -> anyhow::Result<Vec<Model>> {
         self.chat_repository.models(provider).await
     }
+
+    async fn count_tokens(
+        &self,
+        model_id: &ModelId,
+        context: &Context,
+        provider: Provider<Url>,
+    ) -> anyhow::Result<Option<u64>> {
+        self.chat_repository
+            .count_tokens(model_id, context, provider)
+            .await
+    }
+
+    async fn response_cache_info(&self) -> anyhow::Result<ResponseCacheInfo> {
+        self.chat_repository.response_cache_info().await
+    }
+
+    async fn clear_response_cache(&self) -> anyhow::Result<usize> {
+        self.chat_repository.clear_response_cache().await
+    }
 }
 
 #[async_trait::async_trait]
@@ -256,6 +371,21 @@ impl<F: Send + Sync> KVStore for ForgeRepo<F> {
         self.mcp_cache_repository.cache_set(key, value).await
     }
 
+    async fn cache_set_with_ttl<K, V>(
+        &self,
+        key: &K,
+        value: &V,
+        ttl: std::time::Duration,
+    ) -> anyhow::Result<()>
+    where
+        K: std::hash::Hash + Sync,
+        V: serde::Serialize + Sync,
+    {
+        self.mcp_cache_repository
+            .cache_set_with_ttl(key, value, ttl)
+            .await
+    }
+
     async fn cache_clear(&self) -> anyhow::Result<()> {
         self.mcp_cache_repository.cache_clear().await
     }
@@ -488,8 +618,14 @@ where
 }
 
 #[async_trait::async_trait]
-impl<F: FileInfoInfra + EnvironmentInfra<Config = ForgeConfig> + DirectoryReaderInfra + Send + Sync>
-    AgentRepository for ForgeRepo<F>
+impl<
+    F: FileInfoInfra
+        + EnvironmentInfra<Config = ForgeConfig>
+        + DirectoryReaderInfra
+        + FileReaderInfra
+        + Send
+        + Sync,
+> AgentRepository for ForgeRepo<F>
 {
     async fn get_agents(&self) -> anyhow::Result<Vec<forge_domain::Agent>> {
         self.agent_repository.get_agents().await
@@ -500,6 +636,43 @@ impl<F: FileInfoInfra + EnvironmentInfra<Config = ForgeConfig> + DirectoryReader
     }
 }
 
+#[async_trait::async_trait]
+impl<F: EnvironmentInfra + FileInfoInfra + FileReaderInfra + FileWriterInfra + Send + Sync>
+    AgentVersionRepository for ForgeRepo<F>
+{
+    async fn record_agent_version(
+        &self,
+        agent_id: &AgentId,
+        path: &Path,
+    ) -> anyhow::Result<Option<AgentVersion>> {
+        self.agent_version_repository
+            .record_agent_version(agent_id, path)
+            .await
+    }
+
+    async fn list_agent_versions(&self, agent_id: &AgentId) -> anyhow::Result<Vec<AgentVersion>> {
+        self.agent_version_repository
+            .list_agent_versions(agent_id)
+            .await
+    }
+
+    async fn read_agent_version(&self, agent_id: &AgentId, version: u32) -> anyhow::Result<String> {
+        self.agent_version_repository
+            .read_agent_version(agent_id, version)
+            .await
+    }
+
+    async fn rollback_agent(
+        &self,
+        agent_id: &AgentId,
+        version: u32,
+    ) -> anyhow::Result<AgentVersion> {
+        self.agent_version_repository
+            .rollback_agent(agent_id, version)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl<F: FileInfoInfra + EnvironmentInfra + FileReaderInfra + WalkerInfra + Send + Sync>
     SkillRepository for ForgeRepo<F>
@@ -509,6 +682,52 @@ impl<F: FileInfoInfra + EnvironmentInfra + FileReaderInfra + WalkerInfra + Send
     }
 }
 
+#[async_trait::async_trait]
+impl<
+    F: EnvironmentInfra
+        + CommandInfra
+        + FileInfoInfra
+        + FileReaderInfra
+        + FileWriterInfra
+        + FileDirectoryInfra
+        + Send
+        + Sync,
+> PackRepository for ForgeRepo<F>
+{
+    async fn install_pack(
+        &self,
+        kind: PackKind,
+        source: &PackSource,
+    ) -> anyhow::Result<InstalledPack> {
+        self.pack_repository.install_pack(kind, source).await
+    }
+
+    async fn remove_pack(&self, kind: PackKind, name: &str) -> anyhow::Result<()> {
+        self.pack_repository.remove_pack(kind, name).await
+    }
+
+    async fn list_packs(&self, kind: PackKind) -> anyhow::Result<Vec<InstalledPack>> {
+        self.pack_repository.list_packs(kind).await
+    }
+
+    async fn check_packs(&self, kind: PackKind) -> anyhow::Result<Vec<PackStatus>> {
+        self.pack_repository.check_packs(kind).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<F: EnvironmentInfra<Config = forge_config::ForgeConfig> + WalkerInfra + Send + Sync>
+    StorageRepository for ForgeRepo<F>
+{
+    async fn migrate_storage(
+        &self,
+        from: StorageScope,
+        to: StorageScope,
+    ) -> anyhow::Result<StorageMigration> {
+        self.storage_repository.migrate_storage(from, to).await
+    }
+}
+
 impl<F: StrategyFactory> StrategyFactory for ForgeRepo<F> {
     type Strategy = F::Strategy;
 
@@ -1,17 +1,20 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
-use forge_domain::{Environment, Snapshot, SnapshotRepository};
+use forge_domain::{ConversationId, Snapshot, SnapshotRepository, SnapshotTag};
 
 pub struct ForgeFileSnapshotService {
     inner: Arc<forge_snaps::SnapshotService>,
 }
 
 impl ForgeFileSnapshotService {
-    pub fn new(env: Environment) -> Self {
+    pub fn new(snapshot_path: PathBuf, compress: bool) -> Self {
         Self {
-            inner: Arc::new(forge_snaps::SnapshotService::new(env.snapshot_path())),
+            inner: Arc::new(
+                forge_snaps::SnapshotService::new(snapshot_path).with_compression(compress),
+            ),
         }
     }
 }
@@ -27,4 +30,43 @@ impl SnapshotRepository for ForgeFileSnapshotService {
     async fn undo_snapshot(&self, file_path: &Path) -> Result<()> {
         self.inner.undo_snapshot(file_path.to_path_buf()).await
     }
+
+    async fn read_snapshot(&self, file_path: &Path, index: usize) -> Result<Vec<u8>> {
+        self.inner
+            .read_snapshot(file_path.to_path_buf(), index)
+            .await
+    }
+
+    async fn restore_all_at(&self, timestamp: Duration) -> Result<Vec<PathBuf>> {
+        self.inner.restore_all_at(timestamp).await
+    }
+
+    async fn tag_snapshots(
+        &self,
+        file_path: &Path,
+        since: Duration,
+        tag: &SnapshotTag,
+    ) -> Result<()> {
+        self.inner
+            .tag_snapshots_since(file_path.to_path_buf(), since, tag)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Vec<Snapshot>> {
+        self.inner
+            .list_snapshots_for_conversation(conversation_id)
+            .await
+    }
+
+    async fn export_bundle(&self, paths: &[PathBuf], root: &Path, output: &Path) -> Result<usize> {
+        self.inner.export_bundle(paths, root, output).await
+    }
+
+    async fn import_bundle(&self, bundle: &Path, root: &Path) -> Result<Vec<PathBuf>> {
+        self.inner.import_bundle(bundle, root).await
+    }
 }
@@ -1,13 +1,16 @@
 mod agent;
 mod agent_definition;
+mod agent_version;
 mod context_engine;
 mod conversation;
 mod database;
 mod forge_repo;
 mod fs_snap;
 mod fuzzy_search;
+mod pack;
 mod provider;
 mod skill;
+mod storage;
 mod validation;
 
 mod proto_generated {
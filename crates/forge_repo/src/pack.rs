@@ -0,0 +1,399 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, bail};
+use bytes::Bytes;
+use forge_app::{
+    CommandInfra, EnvironmentInfra, FileDirectoryInfra, FileInfoInfra, FileReaderInfra,
+    FileWriterInfra,
+};
+use forge_domain::{InstalledPack, PackKind, PackRepository, PackSource, PackStatus};
+
+/// Name of the manifest recording every installed pack
+const MANIFEST_FILE: &str = "packs.json";
+
+/// Repository implementation for installing skill and agent packs from git.
+///
+/// Packs are cloned into `<base_path>/packs/<kind>s/<name>` and recorded in
+/// `<base_path>/packs/packs.json` together with the commit and git tree hash
+/// that were installed. The tree hash is compared against the checkout to
+/// detect packs that were modified after installation.
+///
+/// Definitions are read from the `skills/` or `agents/` subdirectory of a pack
+/// when present, otherwise from the pack root.
+pub struct ForgePackRepository<I> {
+    infra: Arc<I>,
+}
+
+impl<I> ForgePackRepository<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+}
+
+impl<I: CommandInfra + FileWriterInfra> ForgePackRepository<I> {
+    /// Runs a git command in `cwd` and returns its trimmed stdout
+    async fn git(&self, args: String, cwd: &Path) -> anyhow::Result<String> {
+        let command = format!("git {args}");
+        let output = self
+            .infra
+            .execute_command(command.clone(), cwd.to_path_buf(), true, None)
+            .await?;
+
+        if output.exit_code != Some(0) {
+            return Err(anyhow::anyhow!(output.stderr.trim().to_string())
+                .context(format!("'{command}' failed")));
+        }
+
+        Ok(output.stdout.trim().to_string())
+    }
+
+    /// Clones `source` into `dir` and returns the installed commit and tree
+    /// hash
+    async fn clone_pack(
+        &self,
+        kind: PackKind,
+        source: &PackSource,
+        parent: &Path,
+        dir: &Path,
+    ) -> anyhow::Result<(String, String)> {
+        self.git(
+            format!(
+                "clone --quiet -- {} {}",
+                quote(&source.url),
+                quote(&dir.display().to_string())
+            ),
+            parent,
+        )
+        .await
+        .with_context(|| format!("Failed to clone {}", source.url))?;
+
+        if let Some(reference) = &source.reference {
+            self.git(format!("checkout --quiet {} --", quote(reference)), dir)
+                .await
+                .with_context(|| format!("Version '{reference}' not found in {}", source.url))?;
+        }
+
+        let files = self.git("ls-files".to_string(), dir).await?;
+        if !has_definitions(&files, kind) {
+            bail!("{source} does not contain any {kind} definitions");
+        }
+
+        let commit = self.git("rev-parse HEAD".to_string(), dir).await?;
+        let tree = self.git("rev-parse HEAD^{tree}".to_string(), dir).await?;
+        Ok((commit, tree))
+    }
+
+    /// Returns true when the checkout still matches the recorded tree and has
+    /// no local modifications
+    async fn verify(&self, pack: &InstalledPack, dir: &Path) -> anyhow::Result<bool> {
+        let tree = self.git("rev-parse HEAD^{tree}".to_string(), dir).await?;
+        let changes = self.git("status --porcelain".to_string(), dir).await?;
+        Ok(tree == pack.tree && changes.is_empty())
+    }
+
+    /// Returns the commit the pack's reference currently points to upstream.
+    /// Packs pinned to a commit never have updates.
+    async fn latest_commit(
+        &self,
+        pack: &InstalledPack,
+        dir: &Path,
+    ) -> anyhow::Result<Option<String>> {
+        let reference = pack.reference.as_deref().unwrap_or("HEAD");
+        if is_commit_hash(reference) {
+            return Ok(None);
+        }
+
+        let output = self
+            .git(
+                format!(
+                    "ls-remote -- {} {} {}",
+                    quote(&pack.url),
+                    quote(reference),
+                    quote(&format!("{reference}^{{}}"))
+                ),
+                dir,
+            )
+            .await?;
+        Ok(parse_ls_remote(&output))
+    }
+
+    async fn write_manifest(
+        &self,
+        packs_path: &Path,
+        packs: &[InstalledPack],
+    ) -> anyhow::Result<()> {
+        let content = serde_json::to_string_pretty(packs)?;
+        self.infra
+            .write(&packs_path.join(MANIFEST_FILE), Bytes::from(content))
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<
+    I: EnvironmentInfra
+        + CommandInfra
+        + FileInfoInfra
+        + FileReaderInfra
+        + FileWriterInfra
+        + FileDirectoryInfra,
+> PackRepository for ForgePackRepository<I>
+{
+    async fn install_pack(
+        &self,
+        kind: PackKind,
+        source: &PackSource,
+    ) -> anyhow::Result<InstalledPack> {
+        if source
+            .reference
+            .as_deref()
+            .is_some_and(|r| r.starts_with('-'))
+        {
+            bail!("Invalid pack version: {source}");
+        }
+
+        let packs_path = self.infra.get_environment().packs_path();
+        let parent = kind_dir(&packs_path, kind);
+        self.infra.create_dirs(&parent).await?;
+
+        // Clone into a staging directory so a failed install leaves any
+        // previously installed version untouched
+        let name = source.name();
+        let target = pack_dir(&packs_path, kind, &name)?;
+        let staging = parent.join(format!(".{name}.tmp"));
+        remove_dir(&staging).await?;
+        let (commit, tree) = match self.clone_pack(kind, source, &parent, &staging).await {
+            Ok(installed) => installed,
+            Err(error) => {
+                remove_dir(&staging).await?;
+                return Err(error);
+            }
+        };
+
+        remove_dir(&target).await?;
+        tokio::fs::rename(&staging, &target)
+            .await
+            .with_context(|| format!("Failed to install pack to {}", target.display()))?;
+
+        let pack = InstalledPack {
+            name,
+            kind,
+            url: source.url.clone(),
+            reference: source.reference.clone(),
+            commit,
+            tree,
+            installed_at: chrono::Utc::now(),
+        };
+
+        let mut packs = read_manifest(self.infra.as_ref(), &packs_path).await?;
+        packs.retain(|p| !(p.kind == kind && p.name == pack.name));
+        packs.push(pack.clone());
+        self.write_manifest(&packs_path, &packs).await?;
+
+        Ok(pack)
+    }
+
+    async fn remove_pack(&self, kind: PackKind, name: &str) -> anyhow::Result<()> {
+        let packs_path = self.infra.get_environment().packs_path();
+        let dir = pack_dir(&packs_path, kind, name)?;
+        let mut packs = read_manifest(self.infra.as_ref(), &packs_path).await?;
+
+        let count = packs.len();
+        packs.retain(|p| !(p.kind == kind && p.name == name));
+        if packs.len() == count {
+            bail!("No {kind} pack named '{name}' is installed");
+        }
+
+        remove_dir(&dir).await?;
+        self.write_manifest(&packs_path, &packs).await
+    }
+
+    async fn list_packs(&self, kind: PackKind) -> anyhow::Result<Vec<InstalledPack>> {
+        let packs_path = self.infra.get_environment().packs_path();
+        let packs = read_manifest(self.infra.as_ref(), &packs_path).await?;
+        Ok(packs.into_iter().filter(|p| p.kind == kind).collect())
+    }
+
+    async fn check_packs(&self, kind: PackKind) -> anyhow::Result<Vec<PackStatus>> {
+        let packs_path = self.infra.get_environment().packs_path();
+        let mut statuses = Vec::new();
+
+        for pack in self.list_packs(kind).await? {
+            let dir = pack_dir(&packs_path, kind, &pack.name)?;
+            let intact = self.verify(&pack, &dir).await.unwrap_or(false);
+            let latest_commit = self
+                .latest_commit(&pack, &dir)
+                .await
+                .unwrap_or_else(|error| {
+                    tracing::warn!(pack = %pack.name, error = %error, "Failed to check pack for updates");
+                    None
+                });
+            statuses.push(PackStatus { pack, latest_commit, intact });
+        }
+
+        Ok(statuses)
+    }
+}
+
+/// Returns the directories holding the definitions of every installed pack of
+/// the given kind
+pub(crate) async fn installed_pack_dirs<I: EnvironmentInfra + FileInfoInfra + FileReaderInfra>(
+    infra: &I,
+    kind: PackKind,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let packs_path = infra.get_environment().packs_path();
+    let mut dirs = Vec::new();
+
+    for pack in read_manifest(infra, &packs_path).await? {
+        if pack.kind != kind {
+            continue;
+        }
+        let dir = match pack_dir(&packs_path, kind, &pack.name) {
+            Ok(dir) => dir,
+            Err(error) => {
+                tracing::warn!(pack = %pack.name, error = %error, "Skipping pack");
+                continue;
+            }
+        };
+        let nested = dir.join(format!("{kind}s"));
+        if infra.exists(&nested).await? {
+            dirs.push(nested);
+        } else {
+            dirs.push(dir);
+        }
+    }
+
+    Ok(dirs)
+}
+
+async fn read_manifest<I: FileInfoInfra + FileReaderInfra>(
+    infra: &I,
+    packs_path: &Path,
+) -> anyhow::Result<Vec<InstalledPack>> {
+    let path = packs_path.join(MANIFEST_FILE);
+    if !infra.exists(&path).await? {
+        return Ok(vec![]);
+    }
+
+    let content = infra.read_utf8(&path).await?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse pack manifest: {}", path.display()))
+}
+
+fn kind_dir(packs_path: &Path, kind: PackKind) -> PathBuf {
+    packs_path.join(format!("{kind}s"))
+}
+
+/// Directory of the pack named `name`, refusing names that would resolve
+/// outside the directory of its kind
+fn pack_dir(packs_path: &Path, kind: PackKind, name: &str) -> anyhow::Result<PathBuf> {
+    if matches!(name, "" | ".") || name.contains(['/', '\\']) || name.contains("..") {
+        bail!("Invalid pack name: '{name}'");
+    }
+    Ok(kind_dir(packs_path, kind).join(name))
+}
+
+async fn remove_dir(path: &Path) -> anyhow::Result<()> {
+    match tokio::fs::remove_dir_all(path).await {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+            Err(error).with_context(|| format!("Failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Quotes an argument for safe interpolation into a shell command
+fn quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+fn is_commit_hash(reference: &str) -> bool {
+    reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Returns true when the `git ls-files` output contains definitions of the
+/// given kind
+fn has_definitions(files: &str, kind: PackKind) -> bool {
+    files.lines().any(|file| match kind {
+        PackKind::Skill => file == "SKILL.md" || file.ends_with("/SKILL.md"),
+        PackKind::Agent => file.ends_with(".md") && !file.eq_ignore_ascii_case("README.md"),
+    })
+}
+
+/// Extracts the commit from `git ls-remote` output, preferring the peeled
+/// commit of an annotated tag over the tag object itself
+fn parse_ls_remote(output: &str) -> Option<String> {
+    let refs: Vec<(&str, &str)> = output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+
+    refs.iter()
+        .find(|(_, name)| name.ends_with("^{}"))
+        .or_else(|| refs.first())
+        .map(|(commit, _)| commit.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_quote_escapes_single_quotes() {
+        let actual = quote("it's");
+        let expected = r"'it'\''s'";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_pack_dir_rejects_names_leaving_the_kind_dir() {
+        let fixture = Path::new("/forge/packs");
+
+        let actual = ["../../etc", "org/repo", r"..\repo", "..", ".", ""]
+            .map(|name| pack_dir(fixture, PackKind::Skill, name).is_err());
+
+        assert_eq!(actual, [true; 6]);
+        assert_eq!(
+            pack_dir(fixture, PackKind::Skill, "skills-rust").unwrap(),
+            PathBuf::from("/forge/packs/skills/skills-rust")
+        );
+    }
+
+    #[test]
+    fn test_has_definitions_for_skills() {
+        let fixture = "README.md\nrust/SKILL.md\nrust/scripts/check.sh";
+        assert!(has_definitions(fixture, PackKind::Skill));
+        assert!(!has_definitions("README.md\nsrc/lib.rs", PackKind::Skill));
+    }
+
+    #[test]
+    fn test_has_definitions_for_agents_ignores_readme() {
+        assert!(has_definitions("README.md\nreviewer.md", PackKind::Agent));
+        assert!(!has_definitions("README.md", PackKind::Agent));
+    }
+
+    #[test]
+    fn test_parse_ls_remote_prefers_peeled_tag() {
+        let fixture = "aaaa\trefs/tags/v1.0.0\nbbbb\trefs/tags/v1.0.0^{}";
+        let actual = parse_ls_remote(fixture);
+        let expected = Some("bbbb".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_ls_remote_branch() {
+        let fixture = "cccc\tHEAD";
+        let actual = parse_ls_remote(fixture);
+        let expected = Some("cccc".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_ls_remote_missing_reference() {
+        let actual = parse_ls_remote("");
+        assert_eq!(actual, None);
+    }
+}
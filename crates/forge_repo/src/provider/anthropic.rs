@@ -3,12 +3,12 @@ use std::sync::Arc;
 use anyhow::Context as _;
 use eventsource_stream::Eventsource;
 use forge_app::domain::{
-    ChatCompletionMessage, Context, Model, ModelId, ResultStream, Transformer,
+    ChatCompletionMessage, Content, Context, Model, ModelId, ResultStream, Transformer,
 };
 use forge_app::dto::anthropic::{
-    AuthSystemMessage, CapitalizeToolNames, DropInvalidToolUse, EnforceStrictObjectSchema,
-    EventData, ListModelResponse, ReasoningTransform, RemoveOutputFormat, Request, SanitizeToolIds,
-    SetCache,
+    AuthSystemMessage, CapitalizeToolNames, CountTokensRequest, CountTokensResponse,
+    DropInvalidToolUse, EnforceStrictObjectSchema, EventData, ListModelResponse,
+    ReasoningTransform, RemoveOutputFormat, Request, SanitizeToolIds, SetCache,
 };
 use forge_app::{EnvironmentInfra, HttpInfra};
 use forge_domain::{ChatRepository, Provider, ProviderId};
@@ -195,7 +195,11 @@ impl<T: HttpInfra> Anthropic<T> {
                 let request_url = request_url.clone();
                 async move {
                     match event_result {
-                        Ok(event) if ["[DONE]", ""].contains(&event.data.as_str()) => None,
+                        Ok(event) if event.data == "[DONE]" => None,
+                        // Keep-alive, passed on so it counts as activity
+                        Ok(event) if event.data.is_empty() => {
+                            Some(Ok(ChatCompletionMessage::assistant(Content::part(""))))
+                        }
                         Ok(event) => Some(
                             serde_json::from_str::<EventData>(&event.data)
                                 .with_context(|| {
@@ -267,6 +271,57 @@ impl<T: HttpInfra> Anthropic<T> {
             }
         }
     }
+
+    /// Counts the input tokens of `context` with the `messages/count_tokens`
+    /// endpoint, which sits next to the messages endpoint
+    pub async fn count_tokens(&self, model: &ModelId, context: Context) -> anyhow::Result<u64> {
+        let context = ReasoningTransform.transform(context);
+        let request = Request::try_from(context)?.model(model.as_str().to_string());
+        let request = AuthSystemMessage::default()
+            .when(|_| self.use_oauth)
+            .pipe(CapitalizeToolNames)
+            .pipe(DropInvalidToolUse)
+            .pipe(SanitizeToolIds)
+            .transform(request);
+
+        let url = format!(
+            "{}/count_tokens",
+            self.provider.url.as_str().trim_end_matches('/')
+        );
+        let parsed_url = Url::parse(&url).with_context(|| format!("Invalid URL: {}", url))?;
+        debug!(url = %url, model = %model, "Counting tokens");
+
+        let json_bytes = serde_json::to_vec(&CountTokensRequest::from(request))
+            .with_context(|| "Failed to serialize request")?;
+        let response = self
+            .http
+            .http_post(
+                &parsed_url,
+                Some(create_headers(self.get_headers())),
+                json_bytes.into(),
+            )
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let status = response.status();
+        let ctx_msg = format_http_context(Some(status), "POST", &url);
+        let text = response
+            .text()
+            .await
+            .with_context(|| ctx_msg.clone())
+            .with_context(|| "Failed to decode response into text")?;
+
+        if status.is_success() {
+            let response: CountTokensResponse = serde_json::from_str(&text)
+                .with_context(|| ctx_msg)
+                .with_context(|| "Failed to deserialize token count response")?;
+            Ok(response.input_tokens)
+        } else {
+            Err(anyhow::anyhow!(text))
+                .with_context(|| ctx_msg)
+                .with_context(|| "Failed to count tokens")
+        }
+    }
 }
 
 fn into_sse_parse_error<E>(error: eventsource_stream::EventStreamError<E>) -> anyhow::Error
@@ -359,6 +414,20 @@ impl<F: HttpInfra + EnvironmentInfra<Config = forge_config::ForgeConfig> + 'stat
             .map_err(|e| into_retry(e, &retry_config))
             .context("Failed to fetch models from Anthropic provider")
     }
+
+    async fn count_tokens(
+        &self,
+        model_id: &ModelId,
+        context: &Context,
+        provider: Provider<Url>,
+    ) -> anyhow::Result<Option<u64>> {
+        let provider_client = self.create_client(provider)?;
+
+        provider_client
+            .count_tokens(model_id, context.clone())
+            .await
+            .map(Some)
+    }
 }
 
 #[cfg(test)]
@@ -404,11 +473,15 @@ mod tests {
 
         async fn http_post(
             &self,
-            _url: &Url,
-            _headers: Option<HeaderMap>,
-            _body: Bytes,
+            url: &Url,
+            headers: Option<HeaderMap>,
+            body: Bytes,
         ) -> anyhow::Result<reqwest::Response> {
-            unimplemented!()
+            let mut request = self.client.post(url.clone()).body(body);
+            if let Some(headers) = headers {
+                request = request.headers(headers);
+            }
+            Ok(request.send().await?)
         }
 
         async fn http_delete(&self, _url: &Url) -> anyhow::Result<reqwest::Response> {
@@ -583,6 +656,25 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_count_tokens() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let mock = fixture
+            .mock_count_tokens(serde_json::json!({"input_tokens": 42}), 200)
+            .await;
+        let anthropic = create_anthropic(&fixture.url())?;
+        let context = Context::default().add_message(ContextMessage::user("Hello", None));
+
+        let actual = anthropic
+            .count_tokens(&ModelId::new("claude-sonnet-4"), context)
+            .await?;
+
+        mock.assert_async().await;
+        let expected = 42;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_fetch_models_http_error_status() -> anyhow::Result<()> {
         let mut fixture = MockServer::new().await;
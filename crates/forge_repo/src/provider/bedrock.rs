@@ -546,12 +546,14 @@ impl FromDomain<forge_domain::Context>
             || adjusted_top_p.is_some()
             || context.top_k.is_some()
             || context.max_tokens.is_some()
+            || context.stop.is_some()
         {
             Some(
                 InferenceConfiguration::builder()
                     .set_temperature(context.temperature.map(|t| t.value()))
                     .set_top_p(adjusted_top_p.map(|t| t.value()))
                     .set_max_tokens(context.max_tokens.map(|t| t as i32))
+                    .set_stop_sequences(context.stop.clone())
                     .build(),
             )
         } else {
@@ -1685,6 +1687,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
         };
 
         let actual = ConverseStreamInput::from_domain(fixture).unwrap();
@@ -1715,6 +1721,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
         };
 
         let actual = ConverseStreamInput::from_domain(fixture).unwrap();
@@ -1746,6 +1756,10 @@ mod tests {
             }),
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
         };
 
         let actual = ConverseStreamInput::from_domain(fixture).unwrap();
@@ -1780,6 +1794,10 @@ mod tests {
             }),
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
         };
 
         let actual = ConverseStreamInput::from_domain(fixture).unwrap();
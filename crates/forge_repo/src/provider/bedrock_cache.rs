@@ -103,6 +103,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -131,6 +135,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -161,6 +169,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -118,6 +118,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -162,6 +166,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -212,6 +220,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -249,6 +261,10 @@ mod tests {
             reasoning: None,
             stream: None,
             response_format: None,
+            verbosity: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
             initiator: None,
         };
 
@@ -1,7 +1,9 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use forge_app::domain::{
-    ChatCompletionMessage, Context, Model, ModelId, ProviderResponse, ResultStream,
+    ChatCompletionMessage, Context, Model, ModelId, ProviderResponse, ResponseCacheInfo,
+    ResultStream,
 };
 use forge_app::{EnvironmentInfra, HttpInfra};
 use forge_domain::{ChatRepository, Provider, ProviderId};
@@ -15,13 +17,18 @@ use crate::provider::google::GoogleResponseRepository;
 use crate::provider::openai::OpenAIResponseRepository;
 use crate::provider::openai_responses::OpenAIResponsesResponseRepository;
 use crate::provider::opencode::OpenCodeZenResponseRepository;
+use crate::provider::response_cache::ResponseCache;
+use crate::provider::stall::detect_stalls;
 
 /// Repository responsible for routing chat requests to the appropriate provider
 /// implementation based on the provider's response type.
 pub struct ForgeChatRepository<F> {
     router: Arc<ProviderRouter<F>>,
     model_cache: Arc<CacacheStorage>,
+    response_cache: ResponseCache,
     bg_refresh: BgRefresh,
+    /// Time without data after which a response stream counts as stalled
+    stall_timeout: Option<Duration>,
 }
 
 impl<F: EnvironmentInfra<Config = forge_config::ForgeConfig> + HttpInfra> ForgeChatRepository<F> {
@@ -43,10 +50,13 @@ impl<F: EnvironmentInfra<Config = forge_config::ForgeConfig> + HttpInfra> ForgeC
         let google_repo = GoogleResponseRepository::new(infra.clone());
         let opencode_zen_repo = OpenCodeZenResponseRepository::new(infra.clone());
 
-        let model_cache = Arc::new(CacacheStorage::new(
-            env.cache_dir().join("model_cache"),
-            Some(model_cache_ttl_secs as u128),
-        ));
+        let model_cache = Arc::new(
+            CacacheStorage::new(env.kv_cache_dir(), Some(model_cache_ttl_secs as u128))
+                .with_namespace("models"),
+        );
+        let response_cache = ResponseCache::new(env.kv_cache_dir(), config.response_cache.as_ref());
+        let stall_timeout = (config.stream_stall_timeout_secs > 0)
+            .then(|| Duration::from_secs(config.stream_stall_timeout_secs));
 
         Self {
             router: Arc::new(ProviderRouter {
@@ -58,7 +68,9 @@ impl<F: EnvironmentInfra<Config = forge_config::ForgeConfig> + HttpInfra> ForgeC
                 opencode_zen_repo,
             }),
             model_cache,
+            response_cache,
             bg_refresh: BgRefresh::default(),
+            stall_timeout,
         }
     }
 }
@@ -73,7 +85,22 @@ impl<F: EnvironmentInfra<Config = forge_config::ForgeConfig> + HttpInfra + Sync>
         context: Context,
         provider: Provider<Url>,
     ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
-        self.router.chat(model_id, context, provider).await
+        let key = self.response_cache.key(&provider.id, model_id, &context);
+        if let Some(key) = &key
+            && let Some(message) = self.response_cache.get(key).await
+        {
+            tracing::debug!(provider_id = %provider.id, model = %model_id, "answering from the response cache");
+            return Ok(Box::pin(tokio_stream::once(Ok(message))));
+        }
+
+        let mut stream = self.router.chat(model_id, context, provider).await?;
+        if let Some(timeout) = self.stall_timeout {
+            stream = detect_stalls(stream, timeout);
+        }
+        Ok(match key {
+            Some(key) => self.response_cache.record(key, stream),
+            None => stream,
+        })
     }
 
     async fn models(&self, provider: Provider<Url>) -> anyhow::Result<Vec<Model>> {
@@ -118,6 +145,23 @@ impl<F: EnvironmentInfra<Config = forge_config::ForgeConfig> + HttpInfra + Sync>
 
         Ok(models)
     }
+
+    async fn count_tokens(
+        &self,
+        model_id: &ModelId,
+        context: &Context,
+        provider: Provider<Url>,
+    ) -> anyhow::Result<Option<u64>> {
+        self.router.count_tokens(model_id, context, provider).await
+    }
+
+    async fn response_cache_info(&self) -> anyhow::Result<ResponseCacheInfo> {
+        self.response_cache.info().await
+    }
+
+    async fn clear_response_cache(&self) -> anyhow::Result<usize> {
+        self.response_cache.clear().await
+    }
 }
 
 /// Routes chat and model requests to the correct provider backend.
@@ -191,6 +235,23 @@ impl<F: HttpInfra + EnvironmentInfra<Config = forge_config::ForgeConfig> + Sync>
             )),
         }
     }
+
+    /// Only Anthropic's own API exposes token counting; other providers
+    /// speaking the Anthropic protocol do not.
+    async fn count_tokens(
+        &self,
+        model_id: &ModelId,
+        context: &Context,
+        provider: Provider<Url>,
+    ) -> anyhow::Result<Option<u64>> {
+        if provider.id == ProviderId::ANTHROPIC || provider.id == ProviderId::CLAUDE_CODE {
+            self.anthropic_repo
+                .count_tokens(model_id, context, provider)
+                .await
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 /// Tracks abort handles for background tasks and cancels them on drop.
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use forge_app::domain::ChatCompletionMessage;
+use forge_app::domain::{ChatCompletionMessage, Content};
 use forge_app::dto::openai::Error;
 use reqwest::Url;
 use reqwest_eventsource::{Event, EventSource};
@@ -23,11 +23,16 @@ where
                 match event {
                     Ok(event) => match event {
                         Event::Open => None,
-                        Event::Message(event) if ["[DONE]", ""].contains(&event.data.as_str()) => {
+                        Event::Message(event) if event.data == "[DONE]" => {
 
                             debug!("Received completion from Upstream");
                             None
                         }
+                        // Keep-alive, passed on as an empty message so it counts as
+                        // activity of the provider
+                        Event::Message(event) if event.data.is_empty() => {
+                            Some(Ok(ChatCompletionMessage::assistant(Content::part(""))))
+                        }
                         Event::Message(message) => Some(
                             serde_json::from_str::<Response>(&message.data)
                                 .with_context(|| {
@@ -11,7 +11,9 @@ mod openai;
 mod openai_responses;
 mod opencode;
 mod provider_repo;
+mod response_cache;
 mod retry;
+mod stall;
 mod utils;
 
 pub use chat::*;
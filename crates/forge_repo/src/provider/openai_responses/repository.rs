@@ -198,7 +198,8 @@ impl<T: HttpInfra> OpenAIResponsesProvider<T> {
             .filter_map(|event_result| async move {
                 match event_result {
                     Ok(Event::Open) => None,
-                    Ok(Event::Message(msg)) if ["[DONE]", ""].contains(&msg.data.as_str()) => None,
+                    Ok(Event::Message(msg)) if msg.data == "[DONE]" => None,
+                    Ok(Event::Message(msg)) if msg.data.is_empty() => Some(Ok(keepalive())),
                     Ok(Event::Message(msg)) => {
                         let result = serde_json::from_str::<super::response::ResponsesStreamEvent>(
                             &msg.data,
@@ -206,7 +207,9 @@ impl<T: HttpInfra> OpenAIResponsesProvider<T> {
                         .with_context(|| format!("Failed to parse SSE event: {}", msg.data));
 
                         match result {
-                            Ok(super::response::ResponsesStreamEvent::Keepalive { .. }) => None,
+                            Ok(super::response::ResponsesStreamEvent::Keepalive { .. }) => {
+                                Some(Ok(keepalive()))
+                            }
                             Ok(super::response::ResponsesStreamEvent::Ping { cost }) => {
                                 let usage =
                                     forge_domain::Usage { cost: Some(cost), ..Default::default() };
@@ -268,14 +271,17 @@ impl<T: HttpInfra> OpenAIResponsesProvider<T> {
             .eventsource()
             .filter_map(|event_result| async move {
                 match event_result {
-                    Ok(event) if ["[DONE]", ""].contains(&event.data.as_str()) => None,
+                    Ok(event) if event.data == "[DONE]" => None,
+                    Ok(event) if event.data.is_empty() => Some(Ok(keepalive())),
                     Ok(event) => {
                         let result = serde_json::from_str::<super::response::ResponsesStreamEvent>(
                             &event.data,
                         )
                         .with_context(|| format!("Failed to parse SSE event: {}", event.data));
                         match result {
-                            Ok(super::response::ResponsesStreamEvent::Keepalive { .. }) => None,
+                            Ok(super::response::ResponsesStreamEvent::Keepalive { .. }) => {
+                                Some(Ok(keepalive()))
+                            }
                             Ok(super::response::ResponsesStreamEvent::Ping { cost }) => {
                                 let usage =
                                     forge_domain::Usage { cost: Some(cost), ..Default::default() };
@@ -303,6 +309,14 @@ impl<T: HttpInfra> OpenAIResponsesProvider<T> {
     }
 }
 
+/// Empty message standing in for a keep-alive of the provider, so that it
+/// counts as activity when detecting stalled streams
+fn keepalive() -> super::response::StreamItem {
+    super::response::StreamItem::Message(Box::new(ChatCompletionMessage::assistant(
+        forge_domain::Content::part(""),
+    )))
+}
+
 fn into_sse_parse_error<E>(error: eventsource_stream::EventStreamError<E>) -> anyhow::Error
 where
     E: std::fmt::Debug + std::fmt::Display + Send + Sync + 'static,
@@ -1401,10 +1415,10 @@ mod tests {
         Ok(())
     }
 
-    /// Tests that the Codex stream silently skips keepalive events that
-    /// cannot be deserialized as `ResponseStreamEvent`.
+    /// Tests that the Codex stream passes keepalive events on as empty
+    /// messages rather than failing to parse them.
     #[tokio::test]
-    async fn test_codex_provider_skips_keepalive_events() -> anyhow::Result<()> {
+    async fn test_codex_provider_passes_keepalive_events_as_empty_messages() -> anyhow::Result<()> {
         let mut fixture = MockServer::new().await;
 
         let events = vec![
@@ -1420,7 +1434,7 @@ mod tests {
                     "delta": "hello"
                 })
             ),
-            // Keepalive event that should be silently skipped
+            // Keepalive event that should become an empty message
             "event: keepalive".to_string(),
             format!(
                 "data: {}",
@@ -1469,17 +1483,24 @@ mod tests {
             .chat(&ModelId::from("gpt-5.1-codex-mini"), context)
             .await?;
 
-        // First message should be the text delta (keepalive was skipped)
+        // First message should be the text delta
         let first = stream.next().await.expect("stream should yield")?;
         mock.assert_async().await;
         assert_eq!(first.content, Some(Content::part("hello")));
 
-        // Second message should be the completion event
+        // Second message should be the empty keepalive
         let second = stream
             .next()
             .await
             .expect("stream should yield second message")?;
-        assert_eq!(second.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(second.content, Some(Content::part("")));
+
+        // Third message should be the completion event
+        let third = stream
+            .next()
+            .await
+            .expect("stream should yield third message")?;
+        assert_eq!(third.finish_reason, Some(FinishReason::Stop));
 
         Ok(())
     }
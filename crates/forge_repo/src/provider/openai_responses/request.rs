@@ -4,7 +4,7 @@ use anyhow::Context as _;
 use async_openai::types::responses as oai;
 use forge_app::domain::{Context as ChatContext, ContextMessage, MessagePhase, Role, ToolChoice};
 use forge_app::utils::enforce_strict_schema;
-use forge_domain::{Effort, ReasoningConfig, ReasoningFull};
+use forge_domain::{Effort, ReasoningConfig, ReasoningFull, Verbosity};
 
 use crate::provider::FromDomain;
 
@@ -105,6 +105,21 @@ impl FromDomain<ToolChoice> for oai::ToolChoiceParam {
     }
 }
 
+/// Converts domain Verbosity to an OpenAI plain text configuration
+impl FromDomain<Verbosity> for oai::ResponseTextParam {
+    fn from_domain(verbosity: Verbosity) -> anyhow::Result<Self> {
+        let verbosity = match verbosity {
+            Verbosity::Low => oai::Verbosity::Low,
+            Verbosity::Medium => oai::Verbosity::Medium,
+            Verbosity::High => oai::Verbosity::High,
+        };
+        Ok(oai::ResponseTextParam {
+            format: oai::TextResponseFormatConfiguration::Text,
+            verbosity: Some(verbosity),
+        })
+    }
+}
+
 /// Converts domain ReasoningConfig to OpenAI Reasoning configuration
 impl FromDomain<ReasoningConfig> for oai::Reasoning {
     fn from_domain(config: ReasoningConfig) -> anyhow::Result<Self> {
@@ -351,6 +366,10 @@ impl FromDomain<ChatContext> for oai::CreateResponse {
             builder.reasoning(reasoning_config);
         }
 
+        if let Some(verbosity) = context.verbosity {
+            builder.text(oai::ResponseTextParam::from_domain(verbosity)?);
+        }
+
         if let Some(prompt_cache_key) = prompt_cache_key {
             builder.prompt_cache_key(prompt_cache_key);
         }
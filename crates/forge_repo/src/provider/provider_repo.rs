@@ -366,17 +366,33 @@ impl<
         })
     }
 
+    /// Creates a credential from the API key variable of the active profile,
+    /// when the profile's session uses this provider and the variable is set
+    fn profile_credential(&self, config: &ProviderConfig) -> Option<AuthCredential> {
+        let forge_config = self.infra.get_config().ok()?;
+        let profile = forge_config.active_profile()?;
+        let api_key_var = profile.api_key_var.clone()?;
+        if ProviderId::from(profile.session.as_ref()?.provider_id.clone()) != config.id {
+            return None;
+        }
+        let config = ProviderConfig { api_key_vars: Some(api_key_var), ..config.clone() };
+        self.create_credential_from_env(&config).ok()
+    }
+
     /// Creates a provider with template URLs (not rendered).
     /// The service layer is responsible for rendering templates.
     async fn create_provider(
         &self,
         config: &ProviderConfig,
     ) -> anyhow::Result<forge_domain::ProviderTemplate> {
-        // Get credential from file
-        let mut credential = self
-            .get_credential(&config.id)
-            .await?
-            .ok_or_else(|| Error::provider_not_available(config.id.clone()))?;
+        // Get credential from the active profile, falling back to the file
+        let mut credential = match self.profile_credential(config) {
+            Some(credential) => credential,
+            None => self
+                .get_credential(&config.id)
+                .await?
+                .ok_or_else(|| Error::provider_not_available(config.id.clone()))?,
+        };
 
         // Check if this is a Google ADC credential and refresh it
         // Google ADC tokens expire quickly, so we refresh them on every load
@@ -0,0 +1,322 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use forge_app::KVStore;
+use forge_domain::{
+    BoxStream, ChatCompletionMessage, ChatCompletionMessageFull, Content, Context, FinishReason,
+    MessagePhase, ModelId, ProviderId, Reasoning, ReasoningFull, ResponseCacheInfo,
+    ResultStreamExt, ToolCall, ToolCallFull, Usage,
+};
+use forge_infra::CacacheStorage;
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+/// Identifies a request whose response can be reused: same provider, same
+/// model and same request once stripped of what doesn't reach the model
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseCacheKey {
+    provider: ProviderId,
+    model: ModelId,
+    request: String,
+}
+
+impl ResponseCacheKey {
+    /// Key of the request, or `None` when it isn't sent with a temperature of
+    /// 0 and its response may vary from one call to the next
+    pub fn new(provider: &ProviderId, model: &ModelId, context: &Context) -> Option<Self> {
+        if context
+            .temperature
+            .is_none_or(|temperature| temperature.value() != 0.0)
+        {
+            return None;
+        }
+
+        let mut context = context.clone();
+        // Bookkeeping that differs between runs without changing the request
+        context.conversation_id = None;
+        context.initiator = None;
+        context.stream = None;
+        for entry in &mut context.messages {
+            entry.usage = None;
+        }
+        // Going through a JSON value sorts object keys, so that maps in tool
+        // schemas hash the same whatever their order
+        let request = serde_json::to_value(&context).ok()?.to_string();
+
+        Some(Self { provider: provider.clone(), model: model.clone(), request })
+    }
+}
+
+/// A complete response as stored in the cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedResponse {
+    content: String,
+    thought_signature: Option<String>,
+    reasoning: Option<String>,
+    reasoning_details: Option<Vec<ReasoningFull>>,
+    tool_calls: Vec<ToolCallFull>,
+    usage: Usage,
+    finish_reason: Option<FinishReason>,
+    phase: Option<MessagePhase>,
+}
+
+impl From<ChatCompletionMessageFull> for CachedResponse {
+    fn from(message: ChatCompletionMessageFull) -> Self {
+        Self {
+            content: message.content,
+            thought_signature: message.thought_signature,
+            reasoning: message.reasoning,
+            reasoning_details: message.reasoning_details,
+            tool_calls: message.tool_calls,
+            usage: message.usage,
+            finish_reason: message.finish_reason,
+            phase: message.phase,
+        }
+    }
+}
+
+impl From<CachedResponse> for ChatCompletionMessage {
+    fn from(response: CachedResponse) -> Self {
+        ChatCompletionMessage {
+            content: (!response.content.is_empty()).then(|| Content::full(response.content)),
+            thought_signature: response.thought_signature,
+            reasoning: response.reasoning.map(Content::full),
+            reasoning_details: response
+                .reasoning_details
+                .map(|details| vec![Reasoning::Full(details)]),
+            tool_calls: response
+                .tool_calls
+                .into_iter()
+                .map(ToolCall::Full)
+                .collect(),
+            // Replaying a response costs nothing
+            usage: Some(Usage { cost: None, ..response.usage }),
+            finish_reason: response.finish_reason,
+            phase: response.phase,
+        }
+    }
+}
+
+/// Responses of the providers to deterministic requests, stored on disk so
+/// that identical requests are answered without calling the provider again
+pub struct ResponseCache {
+    storage: Arc<CacacheStorage>,
+    enabled: bool,
+}
+
+impl ResponseCache {
+    /// Cache configured by `config`. Without a configuration nothing is
+    /// cached, but responses cached earlier can still be inspected and
+    /// cleared.
+    pub fn new(cache_dir: PathBuf, config: Option<&forge_config::ResponseCache>) -> Self {
+        let ttl_seconds = config
+            .and_then(|config| config.ttl_hours)
+            .map(|hours| hours as u128 * 3600);
+        let mut storage = CacacheStorage::new(cache_dir, ttl_seconds).with_namespace("responses");
+        if let Some(max_size_mb) = config.and_then(|config| config.max_size_mb) {
+            storage = storage.with_max_size(max_size_mb.saturating_mul(1024 * 1024));
+        }
+        Self { storage: Arc::new(storage), enabled: config.is_some() }
+    }
+
+    /// Key to look the request up with, or `None` when the cache is disabled
+    /// or the response may vary from one call to the next
+    pub fn key(
+        &self,
+        provider: &ProviderId,
+        model: &ModelId,
+        context: &Context,
+    ) -> Option<ResponseCacheKey> {
+        self.enabled
+            .then(|| ResponseCacheKey::new(provider, model, context))
+            .flatten()
+    }
+
+    /// The cached response to the request, replayed as a single message
+    pub async fn get(&self, key: &ResponseCacheKey) -> Option<ChatCompletionMessage> {
+        match self.storage.cache_get::<_, CachedResponse>(key).await {
+            Ok(response) => response.map(ChatCompletionMessage::from),
+            Err(error) => {
+                tracing::warn!(error = ?error, "Failed to read the response cache");
+                None
+            }
+        }
+    }
+
+    /// Passes `stream` through, storing the response once it has been
+    /// received in full. Responses cut short by an error or by the consumer
+    /// are not stored.
+    pub fn record(
+        &self,
+        key: ResponseCacheKey,
+        stream: BoxStream<ChatCompletionMessage, anyhow::Error>,
+    ) -> BoxStream<ChatCompletionMessage, anyhow::Error> {
+        let storage = self.storage.clone();
+        Box::pin(futures::stream::unfold(
+            (stream, Some(Vec::new()), Some((storage, key))),
+            |(mut stream, mut received, mut store)| async move {
+                match stream.next().await {
+                    Some(message) => {
+                        match &message {
+                            Ok(message) => {
+                                if let Some(received) = received.as_mut() {
+                                    received.push(message.clone());
+                                }
+                            }
+                            Err(_) => received = None,
+                        }
+                        Some((message, (stream, received, store)))
+                    }
+                    None => {
+                        if let (Some(received), Some((storage, key))) = (received, store.take()) {
+                            store_response(&storage, &key, received).await;
+                        }
+                        None
+                    }
+                }
+            },
+        ))
+    }
+
+    pub async fn info(&self) -> anyhow::Result<ResponseCacheInfo> {
+        let (entries, size_bytes) = self.storage.usage().await?;
+        Ok(ResponseCacheInfo { enabled: self.enabled, entries, size_bytes })
+    }
+
+    pub async fn clear(&self) -> anyhow::Result<usize> {
+        let (entries, _) = self.storage.usage().await?;
+        self.storage.cache_clear().await?;
+        Ok(entries)
+    }
+}
+
+/// Collapses the received messages into one response and stores it, unless
+/// the response is incomplete
+async fn store_response(
+    storage: &CacacheStorage,
+    key: &ResponseCacheKey,
+    received: Vec<ChatCompletionMessage>,
+) {
+    let stream: BoxStream<ChatCompletionMessage, anyhow::Error> =
+        Box::pin(tokio_stream::iter(received.into_iter().map(Ok)));
+    let Ok(response) = stream.into_full(false).await else {
+        return;
+    };
+    if response.finish_reason.is_none() {
+        return;
+    }
+    if let Err(error) = storage
+        .cache_set(key, &CachedResponse::from(response))
+        .await
+    {
+        tracing::warn!(error = ?error, "Failed to store the response in the cache");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{ContextMessage, Temperature, ToolCallArguments, ToolCallId, ToolName};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn fixture_context() -> Context {
+        Context::default()
+            .add_message(ContextMessage::user("Summarize the README", None))
+            .temperature(Temperature::new(0.0).unwrap())
+    }
+
+    fn fixture_cache() -> ResponseCache {
+        ResponseCache::new(
+            tempfile::tempdir().unwrap().keep(),
+            Some(&forge_config::ResponseCache::default()),
+        )
+    }
+
+    fn key(context: &Context) -> Option<ResponseCacheKey> {
+        ResponseCacheKey::new(&ProviderId::OPENAI, &ModelId::new("gpt-4o"), context)
+    }
+
+    #[test]
+    fn test_key_only_for_zero_temperature() {
+        let fixture = fixture_context();
+
+        let actual = (
+            key(&fixture).is_some(),
+            key(&fixture.clone().temperature(Temperature::new(0.7).unwrap())).is_some(),
+            key(&Context { temperature: None, ..fixture }).is_some(),
+        );
+        let expected = (true, false, false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_key_ignores_bookkeeping() {
+        let fixture = fixture_context();
+
+        let actual = key(&fixture
+            .clone()
+            .conversation_id(forge_domain::ConversationId::generate())
+            .initiator("agent")
+            .stream(true));
+        let expected = key(&fixture);
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_recorded_response_is_replayed_as_one_message() {
+        let fixture = fixture_cache();
+        let key = key(&fixture_context()).unwrap();
+        let tool_call = ToolCallFull::new(ToolName::new("read"))
+            .call_id(ToolCallId::new("call_1"))
+            .arguments(ToolCallArguments::from_json(r#"{"path":"README.md"}"#));
+        let stream: BoxStream<ChatCompletionMessage, anyhow::Error> =
+            Box::pin(tokio_stream::iter(vec![
+                Ok(ChatCompletionMessage::default().content(Content::part("Reading "))),
+                Ok(ChatCompletionMessage::default()
+                    .content(Content::part("the file"))
+                    .tool_calls(vec![ToolCall::Full(tool_call.clone())])
+                    .finish_reason(FinishReason::ToolCalls)),
+            ]));
+
+        let passed = fixture
+            .record(key.clone(), stream)
+            .collect::<Vec<_>>()
+            .await;
+        let actual = (passed.len(), fixture.get(&key).await);
+        let expected = (
+            2,
+            Some(ChatCompletionMessage {
+                content: Some(Content::full("Reading the file")),
+                // Arguments are stored as JSON, so they are replayed parsed
+                tool_calls: vec![ToolCall::Full(ToolCallFull {
+                    arguments: tool_call.arguments.normalize(),
+                    ..tool_call
+                })],
+                usage: Some(Usage::default()),
+                finish_reason: Some(FinishReason::ToolCalls),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_failed_response_is_not_stored() {
+        let fixture = fixture_cache();
+        let key = key(&fixture_context()).unwrap();
+        let stream: BoxStream<ChatCompletionMessage, anyhow::Error> =
+            Box::pin(tokio_stream::iter(vec![
+                Ok(ChatCompletionMessage::default().content(Content::part("Reading"))),
+                Err(anyhow::anyhow!("connection reset")),
+            ]));
+
+        let _ = fixture
+            .record(key.clone(), stream)
+            .collect::<Vec<_>>()
+            .await;
+        let actual = fixture.get(&key).await;
+        let expected = None;
+        assert_eq!(actual, expected);
+    }
+}
@@ -5,7 +5,22 @@ use forge_config::RetryConfig;
 
 const TRANSPORT_ERROR_CODES: [&str; 3] = ["ERR_STREAM_PREMATURE_CLOSE", "ECONNRESET", "ETIMEDOUT"];
 
+/// Phrases providers use to reject a request that exceeds the context window
+const CONTEXT_OVERFLOW_MESSAGES: [&str; 6] = [
+    "context_length_exceeded",
+    "maximum context length",
+    "prompt is too long",
+    "input is too long",
+    "exceeds the context window",
+    "input token count exceeds",
+];
+
 pub fn into_retry(error: anyhow::Error, retry_config: &RetryConfig) -> anyhow::Error {
+    // Retrying the same request can't help, the caller has to shrink the context
+    if is_context_overflow_error(&error) {
+        return DomainError::ContextOverflow(error).into();
+    }
+
     if let Some(code) = get_req_status_code(&error)
         .or(get_event_req_status_code(&error))
         .or(get_api_status_code(&error))
@@ -26,6 +41,16 @@ pub fn into_retry(error: anyhow::Error, retry_config: &RetryConfig) -> anyhow::E
     error
 }
 
+/// Checks if the provider rejected the request for exceeding the context
+/// window. Providers report this as a plain 400 response, so the body is
+/// matched against the phrases they use.
+fn is_context_overflow_error(error: &anyhow::Error) -> bool {
+    let message = format!("{error:#}").to_lowercase();
+    CONTEXT_OVERFLOW_MESSAGES
+        .into_iter()
+        .any(|phrase| message.contains(phrase))
+}
+
 /// Checks if the error is an Anthropic `overloaded_error`, which arrives as an
 /// SSE event payload rather than an HTTP status code and must be retried.
 fn is_anthropic_overloaded_error(error: &anyhow::Error) -> bool {
@@ -119,6 +144,7 @@ fn is_event_transport_error(error: &anyhow::Error) -> bool {
 mod tests {
     use anyhow::anyhow;
     use forge_app::dto::openai::{Error, ErrorCode, ErrorResponse};
+    use pretty_assertions::assert_eq;
 
     use super::*;
 
@@ -339,4 +365,25 @@ mod tests {
         let retry_config = fixture_retry_config(vec![]);
         assert!(is_retryable(into_retry(req_err.into(), &retry_config)));
     }
+
+    #[test]
+    fn test_into_retry_classifies_context_overflow() {
+        let retry_config = fixture_retry_config(vec![400]);
+        let is_overflow = |error: anyhow::Error| {
+            into_retry(error, &retry_config)
+                .downcast_ref::<DomainError>()
+                .is_some_and(|error| matches!(error, DomainError::ContextOverflow(_)))
+        };
+
+        let actual = [
+            anyhow::Error::from(Error::InvalidStatusCode(400))
+                .context(r#"400 Reason: {"error":{"code":"context_length_exceeded"}}"#),
+            anyhow!("prompt is too long: 210000 tokens > 200000 maximum"),
+            anyhow!("ValidationException: Input is too long for requested model."),
+            anyhow::Error::from(Error::InvalidStatusCode(400)),
+        ]
+        .map(is_overflow);
+        let expected = [true, true, true, false];
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use forge_app::domain::{BoxStream, ChatCompletionMessage, Error as DomainError};
+use tokio_stream::StreamExt;
+
+/// Ends `stream` with a retryable [`DomainError::ProviderStalled`] error once
+/// the provider sent nothing for `timeout`, so that a connection the provider
+/// silently abandoned is retried instead of waited on forever. Providers pass
+/// keep-alive and ping events on as empty messages, so a provider that is
+/// still working on a response without streaming content is not stalled.
+pub fn detect_stalls(
+    stream: BoxStream<ChatCompletionMessage, anyhow::Error>,
+    timeout: Duration,
+) -> BoxStream<ChatCompletionMessage, anyhow::Error> {
+    Box::pin(futures::stream::unfold(
+        Some(stream),
+        move |stream| async move {
+            let mut stream = stream?;
+            match tokio::time::timeout(timeout, stream.next()).await {
+                Ok(Some(message)) => Some((message, Some(stream))),
+                Ok(None) => None,
+                Err(_) => {
+                    tracing::warn!(timeout = ?timeout, "Provider stream stalled");
+                    let error = DomainError::ProviderStalled(timeout.as_secs());
+                    Some((Err(DomainError::Retryable(error.into()).into()), None))
+                }
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_detect_stalls_ends_silent_stream() {
+        let fixture = Box::pin(
+            futures::stream::iter([Ok(ChatCompletionMessage::assistant("Hello"))])
+                .chain(futures::stream::pending()),
+        );
+
+        let actual = detect_stalls(fixture, Duration::from_secs(30))
+            .map(|message| match message {
+                Ok(message) => message.content.map(|content| content.as_str().to_string()),
+                Err(error) => Some(error.to_string()),
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        let expected = vec![
+            Some("Hello".to_string()),
+            Some("Provider stalled, no data received for 30s".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_detect_stalls_keeps_stream_alive_on_pings() {
+        let ping = || {
+            ChatCompletionMessage::try_from(forge_app::dto::anthropic::Event::Ping { cost: None })
+        };
+        let fixture = Box::pin(
+            futures::stream::iter([ping(), ping(), ping()])
+                .chain(futures::stream::iter([Ok(
+                    ChatCompletionMessage::assistant("Done"),
+                )]))
+                .throttle(Duration::from_secs(20)),
+        );
+
+        let actual = detect_stalls(fixture, Duration::from_secs(30))
+            .map(|message| match message {
+                Ok(message) => message.content.map(|content| content.as_str().to_string()),
+                Err(error) => Some(error.to_string()),
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        let expected = vec![
+            Some(String::new()),
+            Some(String::new()),
+            Some(String::new()),
+            Some("Done".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -3,29 +3,35 @@ use std::sync::Arc;
 use anyhow::Context;
 use forge_app::domain::Skill;
 use forge_app::{EnvironmentInfra, FileInfoInfra, FileReaderInfra, Walker, WalkerInfra};
-use forge_domain::SkillRepository;
+use forge_domain::{PackKind, SkillRepository};
 use futures::future::join_all;
 use gray_matter::Matter;
 use gray_matter::engine::YAML;
 use serde::Deserialize;
 
+use crate::pack::installed_pack_dirs;
+
 /// Repository implementation for loading skills from multiple sources:
 /// 1. Built-in skills (embedded in the application)
-/// 2. Global custom skills (from ~/forge/skills/ directory)
-/// 3. Agents skills (from ~/.agents/skills/ directory)
-/// 4. Project-local skills (from .forge/skills/ directory in current working
+/// 2. Installed skill packs (from ~/forge/packs/skills/ directory)
+/// 3. Global custom skills (from ~/forge/skills/ directory)
+/// 4. Agents skills (from ~/.agents/skills/ directory)
+/// 5. Project-local skills (from .forge/skills/ directory in current working
 ///    directory)
 ///
 /// ## Skill Precedence
 /// When skills have duplicate names across different sources, the precedence
 /// order is: **CWD (project-local) > Agents (~/.agents/skills) > Global
-/// custom > Built-in**
+/// custom > Installed packs > Built-in**
 ///
 /// This means project-local skills can override agents skills, which can
-/// override global skills, which can override built-in skills.
+/// override global skills, which can override skills from packs, which can
+/// override built-in skills.
 ///
 /// ## Directory Resolution
 /// - **Built-in skills**: Embedded in application binary
+/// - **Pack skills**: `~/forge/packs/skills/<pack>/SKILL.md` or
+///   `~/forge/packs/skills/<pack>/[skills/]<skill-name>/SKILL.md`
 /// - **Global skills**: `~/forge/skills/<skill-name>/SKILL.md`
 /// - **Agents skills**: `~/.agents/skills/<skill-name>/SKILL.md`
 /// - **CWD skills**: `./.forge/skills/<skill-name>/SKILL.md` (relative to
@@ -82,6 +88,14 @@ impl<I: FileInfoInfra + EnvironmentInfra + FileReaderInfra + WalkerInfra> SkillR
         let builtin_skills = self.load_builtin_skills();
         skills.extend(builtin_skills);
 
+        // Load skills from installed packs. A pack with a SKILL.md at its root is
+        // a single skill, otherwise each of its subdirectories is one.
+        let packs_dir = env.packs_path().join("skills");
+        skills.extend(self.load_skills_from_dir(&packs_dir).await?);
+        for dir in installed_pack_dirs(self.infra.as_ref(), PackKind::Skill).await? {
+            skills.extend(self.load_skills_from_dir(&dir).await?);
+        }
+
         // Load global skills
         let global_dir = env.global_skills_path();
         let global_skills = self.load_skills_from_dir(&global_dir).await?;
@@ -99,7 +113,7 @@ impl<I: FileInfoInfra + EnvironmentInfra + FileReaderInfra + WalkerInfra> SkillR
         skills.extend(cwd_skills);
 
         // Resolve conflicts by keeping the last occurrence (CWD > Agents > Global >
-        // Built-in)
+        // Packs > Built-in)
         let skills = resolve_skill_conflicts(skills);
 
         // Render all skills with environment context
@@ -312,8 +326,9 @@ mod tests {
     fn fixture_skill_repo() -> (ForgeSkillRepository<ForgeInfra>, std::path::PathBuf) {
         let skill_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("src/fixtures/skills_with_resources");
-        let config = ForgeConfig::read().unwrap_or_default();
-        let infra = Arc::new(ForgeInfra::new(std::env::current_dir().unwrap(), config));
+        let cwd = std::env::current_dir().unwrap();
+        let config = ForgeConfig::read(&cwd, None).unwrap_or_default();
+        let infra = Arc::new(ForgeInfra::new(cwd, config));
         let repo = ForgeSkillRepository::new(infra);
         (repo, skill_dir)
     }
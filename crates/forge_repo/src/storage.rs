@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::bail;
+use forge_app::{EnvironmentInfra, Walker, WalkerInfra};
+use forge_config::ForgeConfig;
+use forge_domain::{
+    ConversationRepository, Environment, Snapshot, StorageMigration, StorageRepository,
+    StorageScope,
+};
+use forge_snaps::{BLOBS_DIRECTORY, SnapshotService};
+
+use crate::conversation::ConversationRepositoryImpl;
+use crate::database::{DatabasePool, PoolConfig};
+
+/// Repository implementation for moving a workspace's conversations and
+/// snapshots between the global data directory and the workspace's `.forge/`
+/// directory.
+pub struct ForgeStorageRepository<I> {
+    infra: Arc<I>,
+}
+
+impl<I> ForgeStorageRepository<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+}
+
+impl<I: WalkerInfra> ForgeStorageRepository<I> {
+    /// Copies every conversation of the workspace into the target database and
+    /// deletes it from the source database
+    async fn migrate_conversations(
+        &self,
+        env: &Environment,
+        from: StorageScope,
+        to: StorageScope,
+    ) -> anyhow::Result<usize> {
+        let source_path = env.database_path(from);
+        if !source_path.exists() {
+            return Ok(0);
+        }
+
+        let source = ConversationRepositoryImpl::new(
+            Arc::new(DatabasePool::try_from(PoolConfig::new(source_path))?),
+            env.storage_workspace_hash(from),
+        );
+        let target = ConversationRepositoryImpl::new(
+            Arc::new(DatabasePool::try_from(PoolConfig::new(
+                env.database_path(to),
+            ))?),
+            env.storage_workspace_hash(to),
+        );
+
+        let mut count = 0;
+        for summary in source.get_conversation_summaries().await? {
+            if let Some(conversation) = source.get_conversation(&summary.id).await? {
+                target.upsert_conversation(conversation).await?;
+                source.delete_conversation(&summary.id).await?;
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Moves the snapshots of the workspace's files into the target snapshot
+    /// store, linking them to its blobs
+    async fn migrate_snapshots(
+        &self,
+        env: &Environment,
+        from: StorageScope,
+        to: StorageScope,
+        compress: bool,
+    ) -> anyhow::Result<usize> {
+        let source = env.snapshot_path(from);
+        if !source.exists() {
+            return Ok(0);
+        }
+
+        // Workspace storage only holds snapshots of the workspace itself, while
+        // the global directory is shared and has to be filtered by the hashes
+        // of the workspace's files
+        let hashes = match from {
+            StorageScope::Workspace => None,
+            StorageScope::Global => Some(self.workspace_path_hashes(&env.cwd).await?),
+        };
+
+        let target = SnapshotService::new(env.snapshot_path(to)).with_compression(compress);
+        let mut count = 0;
+        let mut entries = tokio::fs::read_dir(&source).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let hash = entry.file_name().to_string_lossy().to_string();
+            if !entry.file_type().await?.is_dir()
+                || hash == BLOBS_DIRECTORY
+                || hashes
+                    .as_ref()
+                    .is_some_and(|hashes| !hashes.contains(&hash))
+            {
+                continue;
+            }
+            count += target.adopt(&entry.path()).await?;
+        }
+
+        Ok(count)
+    }
+
+    /// Returns the snapshot directory names of every file in the workspace
+    async fn workspace_path_hashes(&self, cwd: &Path) -> anyhow::Result<HashSet<String>> {
+        let files = self
+            .infra
+            .walk(Walker::unlimited().cwd(cwd.to_path_buf()))
+            .await?;
+
+        Ok(files
+            .into_iter()
+            .filter(|file| !file.is_dir())
+            .filter_map(|file| Snapshot::create(cwd.join(&file.path)).ok())
+            .map(|snapshot| snapshot.path_hash())
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: EnvironmentInfra<Config = ForgeConfig> + WalkerInfra> StorageRepository
+    for ForgeStorageRepository<I>
+{
+    async fn migrate_storage(
+        &self,
+        from: StorageScope,
+        to: StorageScope,
+    ) -> anyhow::Result<StorageMigration> {
+        if from == to {
+            bail!("Storage is already {to}-scoped");
+        }
+
+        let env = self.infra.get_environment();
+        let compress = self.infra.get_config()?.compress_snapshots;
+        let conversations = self.migrate_conversations(&env, from, to).await?;
+        let snapshots = self.migrate_snapshots(&env, from, to, compress).await?;
+
+        Ok(StorageMigration { conversations, snapshots })
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Number of prompts currently waiting for the user
+static ACTIVE_PROMPTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Held by whatever reads the terminal: prompts for as long as they are alive,
+/// background readers around each poll and the read following it
+static TERMINAL: Mutex<()> = Mutex::new(());
+
+fn lock_terminal() -> MutexGuard<'static, ()> {
+    TERMINAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Whether a prompt is waiting for the user's answer or about to
+fn is_prompting() -> bool {
+    ACTIVE_PROMPTS.load(Ordering::SeqCst) > 0
+}
+
+/// Claim of the terminal by a background reader, see [`claim_terminal`]
+pub struct TerminalClaim {
+    _terminal: MutexGuard<'static, ()>,
+}
+
+/// Claims the terminal for a background reader, unless a prompt is waiting for
+/// the user. Prompts opening meanwhile wait for the claim to be dropped, so a
+/// reader holding it across a poll and the following read can't take their
+/// input.
+pub fn claim_terminal() -> Option<TerminalClaim> {
+    if is_prompting() {
+        return None;
+    }
+    let terminal = TERMINAL.try_lock().ok()?;
+    Some(TerminalClaim { _terminal: terminal })
+}
+
+/// Marks a prompt as active and holds the terminal for as long as it is alive
+pub(crate) struct ActivePrompt {
+    _terminal: MutexGuard<'static, ()>,
+}
+
+impl ActivePrompt {
+    pub(crate) fn new() -> Self {
+        // Announced before locking so that background readers stop claiming
+        // the terminal while the prompt waits for it
+        ACTIVE_PROMPTS.fetch_add(1, Ordering::SeqCst);
+        Self { _terminal: lock_terminal() }
+    }
+}
+
+impl Drop for ActivePrompt {
+    fn drop(&mut self) {
+        ACTIVE_PROMPTS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    // A single test since the terminal is shared by the whole process
+    #[test]
+    fn test_claim_terminal_excludes_prompts() {
+        let before = claim_terminal().is_some();
+        let prompt = ActivePrompt::new();
+        let during_prompt = claim_terminal().is_some();
+        drop(prompt);
+
+        let claim = claim_terminal().unwrap();
+        let prompt = std::thread::spawn(|| drop(ActivePrompt::new()));
+        while !is_prompting() {
+            std::thread::yield_now();
+        }
+        let while_waiting = claim_terminal().is_some();
+        drop(claim);
+        prompt.join().unwrap();
+
+        let actual = (
+            before,
+            during_prompt,
+            while_waiting,
+            claim_terminal().is_some(),
+        );
+        let expected = (true, false, false, true);
+        assert_eq!(actual, expected);
+    }
+}
@@ -5,6 +5,8 @@ use colored::Colorize;
 use rustyline::DefaultEditor;
 use tracing::debug;
 
+use crate::active::ActivePrompt;
+
 /// Strips bracketed-paste escape sequences from a string.
 ///
 /// When bracketed paste mode is active in the terminal, pasted text is wrapped
@@ -63,6 +65,7 @@ impl InputBuilder {
             return Ok(None);
         }
 
+        let _active = ActivePrompt::new();
         let mut rl = DefaultEditor::new()?;
 
         // On Windows, rustyline miscounts ANSI escape bytes as visible characters,
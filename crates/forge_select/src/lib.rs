@@ -1,9 +1,12 @@
+mod active;
 mod confirm;
 mod input;
 mod multi;
+mod preview;
 mod select;
 mod widget;
 
+pub use active::{TerminalClaim, claim_terminal};
 pub use input::InputBuilder;
 pub use multi::MultiSelectBuilder;
 pub use select::SelectBuilder;
@@ -4,15 +4,32 @@ use anyhow::Result;
 use console::strip_ansi_codes;
 use fzf_wrapped::{Fzf, Layout};
 
-use crate::select::{indexed_items, parse_fzf_index};
+use crate::active::ActivePrompt;
+use crate::preview::{PreviewFn, Previews};
+use crate::select::{indexed_items, match_args, parse_fzf_index};
 
 /// Builder for multi-select prompts.
 pub struct MultiSelectBuilder<T> {
     pub(crate) message: String,
     pub(crate) options: Vec<T>,
+    pub(crate) exact: bool,
+    pub(crate) preview: Option<PreviewFn<T>>,
 }
 
 impl<T> MultiSelectBuilder<T> {
+    /// Match the query as a contiguous substring instead of fuzzily.
+    pub fn with_exact(mut self, exact: bool) -> Self {
+        self.exact = exact;
+        self
+    }
+
+    /// Show details of the highlighted item, rendered by `preview`, in a pane
+    /// beside the list.
+    pub fn with_preview(mut self, preview: impl Fn(&T) -> String + Send + 'static) -> Self {
+        self.preview = Some(Box::new(preview));
+        self
+    }
+
     /// Execute multi-select prompt.
     ///
     /// # Returns
@@ -43,7 +60,14 @@ impl<T> MultiSelectBuilder<T> {
             .map(|item| strip_ansi_codes(&item.to_string()).trim().to_string())
             .collect();
 
-        let fzf = build_multi_fzf(&self.message);
+        let previews = self
+            .preview
+            .as_ref()
+            .map(|preview| Previews::write(&self.options, preview))
+            .transpose()?;
+
+        let _active = ActivePrompt::new();
+        let fzf = build_multi_fzf(&self.message, self.exact, previews.as_ref());
 
         let mut fzf = fzf;
         fzf.run()
@@ -74,22 +98,26 @@ impl<T> MultiSelectBuilder<T> {
     }
 }
 
-/// Builds an `Fzf` instance for multi-select prompts.
-fn build_multi_fzf(message: &str) -> Fzf {
+/// Builds an `Fzf` instance for multi-select prompts, matching and previewing
+/// like `build_fzf`.
+fn build_multi_fzf(message: &str, exact: bool, previews: Option<&Previews>) -> Fzf {
     let mut builder = Fzf::builder();
     builder.layout(Layout::Reverse);
     builder.no_scrollbar(true);
     builder.prompt(format!("{} ❯ ", message));
-    builder.custom_args(vec![
+    let mut args = vec![
         "--height=80%".to_string(),
-        "--exact".to_string(),
         "--cycle".to_string(),
-        "--color=dark,header:bold".to_string(),
         "--pointer=▌".to_string(),
         "--delimiter=\t".to_string(),
         "--with-nth=2..".to_string(),
         "--multi".to_string(),
-    ]);
+    ];
+    args.extend(match_args(exact));
+    if let Some(previews) = previews {
+        args.extend(previews.args());
+    }
+    builder.custom_args(args);
     builder
         .build()
         .expect("fzf builder should always succeed with default options")
@@ -106,5 +134,6 @@ mod tests {
         let builder = ForgeWidget::multi_select("Select options:", vec!["a", "b", "c"]);
         assert_eq!(builder.message, "Select options:");
         assert_eq!(builder.options, vec!["a", "b", "c"]);
+        assert!(!builder.exact);
     }
 }
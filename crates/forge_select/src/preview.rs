@@ -0,0 +1,72 @@
+use anyhow::{Context, Result};
+use tempfile::TempDir;
+
+/// Renders the details of an item shown in the preview pane.
+pub(crate) type PreviewFn<T> = Box<dyn Fn(&T) -> String + Send>;
+
+/// Previews of the items of a prompt, rendered up front and written to a
+/// temporary directory as one file per item index.
+///
+/// fzf can only preview by running a command, so it is pointed at the file of
+/// the highlighted item through the index field of the `"{idx}\t{display}"`
+/// lines built by `indexed_items`. The directory is removed on drop.
+pub(crate) struct Previews {
+    dir: TempDir,
+}
+
+impl Previews {
+    /// Renders a preview for each of `options` with `preview`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the temporary directory or a file cannot be written.
+    pub(crate) fn write<T>(options: &[T], preview: &PreviewFn<T>) -> Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix("forge-preview-")
+            .tempdir()
+            .context("Failed to create preview directory")?;
+        for (index, option) in options.iter().enumerate() {
+            std::fs::write(dir.path().join(index.to_string()), preview(option))
+                .context("Failed to write preview")?;
+        }
+        Ok(Self { dir })
+    }
+
+    /// fzf arguments showing the preview of the highlighted item beside the
+    /// list; fzf substitutes `{1}` with the item's quoted index.
+    pub(crate) fn args(&self) -> Vec<String> {
+        let command = if cfg!(windows) {
+            format!("cd /d \"{}\" && type {{1}}", self.dir.path().display())
+        } else {
+            format!("cat '{}'/{{1}}", self.dir.path().display())
+        };
+        vec![
+            format!("--preview={command}"),
+            "--preview-window=right,50%,wrap".to_string(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_write_renders_preview_per_index() {
+        let fixture: PreviewFn<&str> = Box::new(|item| format!("Details of {item}"));
+
+        let previews = Previews::write(&["apple", "banana"], &fixture).unwrap();
+
+        let actual = (
+            std::fs::read_to_string(previews.dir.path().join("0")).unwrap(),
+            std::fs::read_to_string(previews.dir.path().join("1")).unwrap(),
+        );
+        let expected = (
+            "Details of apple".to_string(),
+            "Details of banana".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+}
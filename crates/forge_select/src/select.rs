@@ -4,6 +4,9 @@ use anyhow::Result;
 use console::strip_ansi_codes;
 use fzf_wrapped::{Fzf, Layout, run_with_output};
 
+use crate::active::ActivePrompt;
+use crate::preview::{PreviewFn, Previews};
+
 /// Builder for select prompts with fuzzy search.
 pub struct SelectBuilder<T> {
     pub(crate) message: String,
@@ -13,6 +16,8 @@ pub struct SelectBuilder<T> {
     pub(crate) help_message: Option<&'static str>,
     pub(crate) initial_text: Option<String>,
     pub(crate) header_lines: usize,
+    pub(crate) exact: bool,
+    pub(crate) preview: Option<PreviewFn<T>>,
 }
 
 /// Builds an `Fzf` instance with standard layout and an optional header.
@@ -34,9 +39,13 @@ pub struct SelectBuilder<T> {
 /// The `load` event is used instead of `start` because items are written to
 /// fzf's stdin after the process starts.
 ///
-/// The flags `--exact`, `--cycle`, `--select-1`, `--no-scrollbar`, and
+/// The flags `--cycle`, `--select-1`, `--no-scrollbar`, and
 /// `--color=dark,header:bold` mirror the shell plugin's `_forge_fzf` wrapper
-/// for a consistent user experience across both entry points.
+/// for a consistent user experience across both entry points. Matching is
+/// fuzzy unless `exact` is set, see [`match_args`].
+///
+/// When `previews` are given, the preview of the highlighted item is shown
+/// in a pane beside the list, see [`Previews::args`].
 ///
 /// The `message` is used as the fzf `--prompt` so the prompt line reads
 /// `"Select a model: "` instead of the default `"> "`, placing the question
@@ -48,6 +57,8 @@ fn build_fzf(
     initial_text: Option<&str>,
     starting_cursor: Option<usize>,
     header_lines: usize,
+    exact: bool,
+    previews: Option<&Previews>,
 ) -> Fzf {
     let mut builder = Fzf::builder();
     builder.layout(Layout::Reverse);
@@ -60,14 +71,13 @@ fn build_fzf(
 
     let mut args = vec![
         "--height=80%".to_string(),
-        "--exact".to_string(),
         "--cycle".to_string(),
         "--select-1".to_string(),
-        "--color=dark,header:bold".to_string(),
         "--pointer=▌".to_string(),
         "--delimiter=\t".to_string(),
         "--with-nth=2..".to_string(),
     ];
+    args.extend(match_args(exact));
     if let Some(query) = initial_text {
         args.push(format!("--query={}", query));
     }
@@ -77,6 +87,9 @@ fn build_fzf(
     if header_lines > 0 {
         args.push(format!("--header-lines={}", header_lines));
     }
+    if let Some(previews) = previews {
+        args.extend(previews.args());
+    }
     builder.custom_args(args);
 
     builder
@@ -84,6 +97,27 @@ fn build_fzf(
         .expect("fzf builder should always succeed with default options")
 }
 
+/// Matching flags shared by the select and multi-select prompts.
+///
+/// Fuzzy matching scores every item against the query as it is typed, the
+/// way skim and fzf do by default: characters may be spread across the item,
+/// consecutive characters and matches at word boundaries score higher, and
+/// ties go to the shorter item, then the one matching earlier, then the one
+/// listed first. The matched characters are underlined in bold so it is clear
+/// why an item ranks where it does. With `exact` the query must appear as a
+/// contiguous substring instead, which suits short lists of similar items.
+pub(crate) fn match_args(exact: bool) -> Vec<String> {
+    let mode = if exact {
+        "--exact"
+    } else {
+        "--tiebreak=length,begin,index"
+    };
+    vec![
+        mode.to_string(),
+        "--color=dark,header:bold,hl:bold:underline,hl+:bold:underline".to_string(),
+    ]
+}
+
 /// Formats items as `"{idx}\t{display}"` for passing to fzf.
 ///
 /// The index prefix lets us recover the original position from fzf's output
@@ -122,6 +156,22 @@ impl<T: 'static> SelectBuilder<T> {
         self
     }
 
+    /// Match the query as a contiguous substring instead of fuzzily.
+    pub fn with_exact(mut self, exact: bool) -> Self {
+        self.exact = exact;
+        self
+    }
+
+    /// Show details of the highlighted item, rendered by `preview`, in a pane
+    /// beside the list.
+    ///
+    /// Previews of all options are rendered before the prompt opens, so
+    /// `preview` should be cheap for long lists.
+    pub fn with_preview(mut self, preview: impl Fn(&T) -> String + Send + 'static) -> Self {
+        self.preview = Some(Box::new(preview));
+        self
+    }
+
     /// Set initial search text for fuzzy search.
     pub fn with_initial_text(mut self, text: impl Into<String>) -> Self {
         self.initial_text = Some(text.into());
@@ -159,6 +209,8 @@ impl<T: 'static> SelectBuilder<T> {
             return Ok(None);
         }
 
+        let _active = ActivePrompt::new();
+
         if std::any::TypeId::of::<T>() == std::any::TypeId::of::<bool>() {
             return prompt_confirm_as(&self.message, self.default);
         }
@@ -173,12 +225,20 @@ impl<T: 'static> SelectBuilder<T> {
             .map(|item| strip_ansi_codes(&item.to_string()).trim().to_string())
             .collect();
 
+        let previews = self
+            .preview
+            .as_ref()
+            .map(|preview| Previews::write(&self.options, preview))
+            .transpose()?;
+
         let fzf = build_fzf(
             &self.message,
             self.help_message,
             self.initial_text.as_deref(),
             self.starting_cursor,
             self.header_lines,
+            self.exact,
+            previews.as_ref(),
         );
 
         let selected = run_with_output(fzf, indexed_items(&display_options));
@@ -205,7 +265,7 @@ fn prompt_confirm(message: &str, default: Option<bool>) -> Result<Option<bool>>
         Some(0)
     };
 
-    let fzf = build_fzf(message, None, None, starting_cursor, 0);
+    let fzf = build_fzf(message, None, None, starting_cursor, 0, true, None);
     let selected = run_with_output(fzf, items.iter().copied());
 
     let result: Option<bool> = match selected.as_deref().map(str::trim) {
@@ -322,4 +382,40 @@ mod tests {
         let builder = ForgeWidget::select("Test", vec!["a", "b", "c"]).with_starting_cursor(2);
         assert_eq!(builder.starting_cursor, Some(2));
     }
+
+    #[test]
+    fn test_select_builder_matches_fuzzily_by_default() {
+        let builder = ForgeWidget::select("Test", vec!["a", "b", "c"]);
+        assert!(!builder.exact);
+    }
+
+    #[test]
+    fn test_with_exact() {
+        let builder = ForgeWidget::select("Test", vec!["a", "b", "c"]).with_exact(true);
+        assert!(builder.exact);
+    }
+
+    #[test]
+    fn test_with_preview() {
+        let builder = ForgeWidget::select("Test", vec!["a", "b"])
+            .with_preview(|item: &&str| format!("Details of {item}"));
+        let actual = builder.preview.map(|preview| preview(&"a"));
+        assert_eq!(actual, Some("Details of a".to_string()));
+    }
+
+    #[test]
+    fn test_match_args_fuzzy() {
+        let actual = match_args(false);
+        let expected = vec![
+            "--tiebreak=length,begin,index".to_string(),
+            "--color=dark,header:bold,hl:bold:underline,hl+:bold:underline".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_match_args_exact() {
+        let actual = match_args(true);
+        assert_eq!(actual[0], "--exact");
+    }
 }
@@ -20,6 +20,8 @@ impl ForgeWidget {
             help_message: None,
             initial_text: None,
             header_lines: 0,
+            exact: false,
+            preview: None,
         }
     }
 
@@ -40,6 +42,11 @@ impl ForgeWidget {
 
     /// Multi-select prompt.
     pub fn multi_select<T>(message: impl Into<String>, options: Vec<T>) -> MultiSelectBuilder<T> {
-        MultiSelectBuilder { message: message.into(), options }
+        MultiSelectBuilder {
+            message: message.into(),
+            options,
+            exact: false,
+            preview: None,
+        }
     }
 }
@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::HeaderMap;
+use axum::http::header::AUTHORIZATION;
+use axum::middleware::Next;
+use axum::response::Response;
+use forge_api::Conversation;
+use forge_config::{ServerConfig, ServerQuota, ServerRole, ServerUser};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+
+use crate::error::{Error, Result};
+
+/// Name of the security scheme documented in the OpenAPI specification
+pub(crate) const SECURITY_SCHEME: &str = "api_key";
+
+/// Query parameter carrying the API key for clients that cannot set headers,
+/// such as browser WebSockets
+const ACCESS_TOKEN_PARAM: &str = "access_token";
+
+/// Prefix of the principal names of webhooks, keeping the conversations and
+/// quota of a webhook apart from those of a user with the same name
+const WEBHOOK_PREFIX: &str = "webhook:";
+
+/// User a request is made on behalf of
+#[derive(Debug, Clone, PartialEq)]
+pub struct Principal {
+    /// Name of the authenticated user, absent when the server has no users
+    pub name: Option<String>,
+    pub role: ServerRole,
+    /// Usage allowed to the principal's API key, unlimited when absent
+    pub quota: Option<ServerQuota>,
+}
+
+impl Principal {
+    /// Principal of every request when no users are configured
+    fn anonymous(quota: Option<ServerQuota>) -> Self {
+        Self { name: None, role: ServerRole::Operator, quota }
+    }
+
+    /// Principal of the runs a webhook starts, owning their conversations
+    pub fn webhook(name: &str, quota: Option<ServerQuota>) -> Self {
+        Self {
+            name: Some(format!("{WEBHOOK_PREFIX}{name}")),
+            role: ServerRole::Operator,
+            quota,
+        }
+    }
+
+    /// Key the usage of the principal is tracked under
+    pub fn key(&self) -> String {
+        self.name.clone().unwrap_or_default()
+    }
+
+    /// Fails unless the principal may modify conversations and send prompts
+    pub fn require_operator(&self) -> Result<()> {
+        match self.role {
+            ServerRole::Operator => Ok(()),
+            ServerRole::Viewer => Err(Error::Forbidden(
+                "This action requires the operator role".to_string(),
+            )),
+        }
+    }
+
+    /// Users only see the conversations they created
+    pub fn can_access(&self, conversation: &Conversation) -> bool {
+        self.name.is_none() || conversation.metadata.owner == self.name
+    }
+}
+
+/// Resolves the API key of a request to a configured user
+#[derive(Clone, Default)]
+pub(crate) struct Authenticator {
+    users: Arc<Vec<ServerUser>>,
+    quota: Option<ServerQuota>,
+}
+
+impl Authenticator {
+    pub fn new(config: ServerConfig) -> Self {
+        Self { users: Arc::new(config.users), quota: config.quota }
+    }
+
+    fn authenticate(&self, api_key: Option<&str>) -> Result<Principal> {
+        if self.users.is_empty() {
+            return Ok(Principal::anonymous(self.quota.clone()));
+        }
+
+        let api_key = api_key.ok_or_else(|| Error::Unauthorized("Missing API key".to_string()))?;
+        self.users
+            .iter()
+            .find(|user| constant_time_eq(user.api_key.as_bytes(), api_key.as_bytes()))
+            .map(|user| Principal {
+                name: Some(user.name.clone()),
+                role: user.role,
+                quota: user.quota.clone().or_else(|| self.quota.clone()),
+            })
+            .ok_or_else(|| Error::Unauthorized("Invalid API key".to_string()))
+    }
+}
+
+/// Fails if two users or two webhooks share a name, a user and a webhook share
+/// one, a user name starts with the prefix reserved for webhooks, a user has no
+/// API key or shares it with another user, or a webhook has no secret
+pub(crate) fn validate_config(config: &ServerConfig) -> anyhow::Result<()> {
+    let mut names = HashSet::new();
+    let users = config.users.iter().map(|user| &user.name);
+    let webhooks = config.webhooks.iter().map(|webhook| &webhook.name);
+    for name in users.clone().chain(webhooks) {
+        anyhow::ensure!(
+            names.insert(name),
+            "Server users and webhooks must have unique names, '{name}' is used twice"
+        );
+    }
+    if let Some(user) = config
+        .users
+        .iter()
+        .find(|user| user.name.starts_with(WEBHOOK_PREFIX))
+    {
+        anyhow::bail!(
+            "Server user '{}' must not start with '{WEBHOOK_PREFIX}'",
+            user.name
+        );
+    }
+    let mut api_keys = HashSet::new();
+    for user in &config.users {
+        anyhow::ensure!(
+            !user.api_key.trim().is_empty(),
+            "Server user '{}' must have an API key",
+            user.name
+        );
+        anyhow::ensure!(
+            api_keys.insert(&user.api_key),
+            "Server user '{}' must have an API key of its own, another user has the same one",
+            user.name
+        );
+    }
+    if let Some(webhook) = config
+        .webhooks
+        .iter()
+        .find(|webhook| webhook.secret.trim().is_empty())
+    {
+        anyhow::bail!(
+            "Server webhook '{}' must have a secret, deliveries signed with an empty one can be forged by anyone",
+            webhook.name
+        );
+    }
+    Ok(())
+}
+
+/// Middleware attaching the [`Principal`] of the request to its extensions
+pub(crate) async fn authenticate(
+    State(authenticator): State<Authenticator>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response> {
+    let api_key = bearer_token(request.headers())
+        .or_else(|| access_token(request.uri().query().unwrap_or_default()));
+    let principal = authenticator.authenticate(api_key.as_deref())?;
+    request.extensions_mut().insert(principal);
+    Ok(next.run(request).await)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
+}
+
+fn access_token(query: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        pair.split_once('=')
+            .filter(|(name, _)| *name == ACCESS_TOKEN_PARAM)
+            .map(|(_, value)| value.to_string())
+    })
+}
+
+/// Compares secrets without leaking the position of the first difference
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Documents bearer authentication in the OpenAPI specification
+pub(crate) struct SecurityAddon;
+
+impl utoipa::Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                SECURITY_SCHEME,
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_config::ServerWebhook;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn authenticator_fixture() -> Authenticator {
+        Authenticator::new(ServerConfig::default().users(vec![
+            ServerUser::default()
+                .name("alice")
+                .api_key("alice-key")
+                .role(ServerRole::Operator),
+            ServerUser::default().name("bob").api_key("bob-key"),
+        ]))
+    }
+
+    #[test]
+    fn test_authenticate_without_users_is_anonymous() {
+        let actual = Authenticator::default().authenticate(None).unwrap();
+        let expected = Principal::anonymous(None);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_authenticate_resolves_user_by_api_key() {
+        let actual = authenticator_fixture()
+            .authenticate(Some("bob-key"))
+            .unwrap();
+        let expected = Principal {
+            name: Some("bob".to_string()),
+            role: ServerRole::Viewer,
+            quota: None,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_authenticate_rejects_missing_and_unknown_keys() {
+        let fixture = authenticator_fixture();
+        assert!(matches!(
+            fixture.authenticate(None),
+            Err(Error::Unauthorized(_))
+        ));
+        assert!(matches!(
+            fixture.authenticate(Some("alice-key2")),
+            Err(Error::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_viewer_cannot_operate_and_sees_only_own_conversations() {
+        let fixture = Principal {
+            name: Some("bob".to_string()),
+            role: ServerRole::Viewer,
+            quota: None,
+        };
+        let mut owned = Conversation::generate();
+        owned.metadata.owner = Some("bob".to_string());
+        let other = Conversation::generate();
+
+        assert!(matches!(
+            fixture.require_operator(),
+            Err(Error::Forbidden(_))
+        ));
+        assert!(fixture.can_access(&owned));
+        assert!(!fixture.can_access(&other));
+    }
+
+    #[test]
+    fn test_webhook_does_not_share_conversations_with_user_of_same_name() {
+        let fixture = Principal::webhook("bob", None);
+        let mut owned = Conversation::generate();
+        owned.metadata.owner = Some("bob".to_string());
+
+        assert!(!fixture.can_access(&owned));
+        assert_eq!(fixture.key(), "webhook:bob");
+    }
+
+    #[test]
+    fn test_validate_config_rejects_shared_names() {
+        let user = ServerUser::default().name("ci").api_key("ci-key");
+        let webhook = ServerWebhook::default().name("ci").secret("secret");
+        let reserved = ServerUser::default().name("webhook:ci").api_key("key");
+
+        assert!(validate_config(&ServerConfig::default().users(vec![user.clone()])).is_ok());
+        assert!(
+            validate_config(
+                &ServerConfig::default()
+                    .users(vec![user.clone()])
+                    .webhooks(vec![webhook])
+            )
+            .is_err()
+        );
+        assert!(validate_config(&ServerConfig::default().users(vec![user.clone(), user])).is_err());
+        assert!(validate_config(&ServerConfig::default().users(vec![reserved])).is_err());
+    }
+
+    #[test]
+    fn test_validate_config_rejects_user_without_api_key() {
+        let fixture =
+            ServerConfig::default().users(vec![ServerUser::default().name("alice").api_key("")]);
+
+        let actual = validate_config(&fixture).unwrap_err().to_string();
+
+        let expected = "Server user 'alice' must have an API key";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_validate_config_rejects_shared_api_keys() {
+        let fixture = ServerConfig::default().users(vec![
+            ServerUser::default().name("alice").api_key("shared-key"),
+            ServerUser::default().name("bob").api_key("shared-key"),
+        ]);
+
+        let actual = validate_config(&fixture).unwrap_err().to_string();
+
+        let expected =
+            "Server user 'bob' must have an API key of its own, another user has the same one";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_validate_config_rejects_webhook_without_secret() {
+        let fixture = ServerConfig::default()
+            .webhooks(vec![ServerWebhook::default().name("github").secret("  ")]);
+
+        let actual = validate_config(&fixture).unwrap_err().to_string();
+
+        let expected = "Server webhook 'github' must have a secret, deliveries signed with an empty one can be forged by anyone";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_access_token_from_query() {
+        let actual = access_token("limit=5&access_token=secret");
+        let expected = Some("secret".to_string());
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,402 @@
+use chrono::{DateTime, Utc};
+use forge_api::{
+    ChatResponse, ChatResponseContent, Conversation, InterruptionReason, Model, Usage, UserUsage,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Conversation metadata without its message history
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ConversationSummary {
+    pub id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub pinned: bool,
+}
+
+impl From<&Conversation> for ConversationSummary {
+    fn from(conversation: &Conversation) -> Self {
+        Self {
+            id: conversation.id.into_string(),
+            title: conversation.title.clone(),
+            created_at: conversation.metadata.created_at,
+            updated_at: conversation.metadata.updated_at,
+            pinned: conversation.metadata.pinned,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListConversationsQuery {
+    /// Maximum number of conversations to return, most recent first
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct CreateConversationRequest {
+    pub title: Option<String>,
+}
+
+/// Fields to change on a conversation; absent fields are left unchanged
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateConversationRequest {
+    pub title: Option<String>,
+    pub pinned: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SendMessageRequest {
+    /// Prompt sent to the active agent
+    pub prompt: String,
+    /// Extra context appended after the prompt
+    pub additional_context: Option<String>,
+}
+
+/// Message sent by a WebSocket client
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Sends a prompt to the active agent
+    Prompt(SendMessageRequest),
+    /// Answers the `permission_request` event with the same `id`
+    PermissionResponse { id: String, allowed: bool },
+}
+
+/// Event streamed while the agent works on a prompt, sent as the data of a
+/// server-sent event named after `type` or as a WebSocket text message
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// Markdown produced by the agent; partial messages are deltas to append
+    /// to the previous ones
+    Message {
+        text: String,
+        partial: bool,
+    },
+    /// Reasoning produced by the agent
+    Reasoning {
+        text: String,
+    },
+    /// Title describing the input of a tool call
+    ToolInput {
+        title: String,
+    },
+    /// Output of a tool call
+    ToolOutput {
+        text: String,
+    },
+    ToolCallStart {
+        name: String,
+    },
+    ToolCallEnd {
+        name: String,
+        is_error: bool,
+    },
+    /// The provider request failed and is being retried
+    Retry {
+        cause: String,
+    },
+    /// The agent stopped because a per-turn limit was reached
+    Interrupt {
+        reason: String,
+    },
+    /// A tool call waits for the user to allow a restricted operation or
+    /// access to a file that may hold secrets. WebSocket clients answer with a
+    /// `permission_response` naming `id`; requests without an `id`, such as
+    /// those streamed as server-sent events, are denied.
+    PermissionRequest {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        tool_name: String,
+        message: String,
+    },
+    /// The agent finished working on the prompt
+    Complete,
+    Error {
+        message: String,
+    },
+}
+
+impl StreamEvent {
+    /// Name of the server-sent event carrying this payload
+    pub fn name(&self) -> &'static str {
+        match self {
+            StreamEvent::Message { .. } => "message",
+            StreamEvent::Reasoning { .. } => "reasoning",
+            StreamEvent::ToolInput { .. } => "tool_input",
+            StreamEvent::ToolOutput { .. } => "tool_output",
+            StreamEvent::ToolCallStart { .. } => "tool_call_start",
+            StreamEvent::ToolCallEnd { .. } => "tool_call_end",
+            StreamEvent::Retry { .. } => "retry",
+            StreamEvent::Interrupt { .. } => "interrupt",
+            StreamEvent::PermissionRequest { .. } => "permission_request",
+            StreamEvent::Complete => "complete",
+            StreamEvent::Error { .. } => "error",
+        }
+    }
+}
+
+/// Lets the orchestrator execute a tool call it announced. It waits for the
+/// frontend to acknowledge the start of every tool call before running it, so
+/// each response of a turn must pass through here, whether or not it reaches a
+/// client.
+pub fn ack_tool_call_start(response: &ChatResponse) {
+    if let ChatResponse::ToolCallStart { notifier, .. } = response {
+        notifier.notify_one();
+    }
+}
+
+impl From<ChatResponse> for StreamEvent {
+    fn from(response: ChatResponse) -> Self {
+        match response {
+            ChatResponse::TaskMessage { content } => match content {
+                ChatResponseContent::ToolInput(title) => StreamEvent::ToolInput {
+                    title: match title.sub_title {
+                        Some(sub_title) => format!("{} {sub_title}", title.title),
+                        None => title.title,
+                    },
+                },
+                ChatResponseContent::ToolOutput(text) => StreamEvent::ToolOutput { text },
+                ChatResponseContent::FileDiff(diff) => StreamEvent::ToolOutput { text: diff.diff },
+                ChatResponseContent::Markdown { text, partial } => {
+                    StreamEvent::Message { text, partial }
+                }
+            },
+            ChatResponse::TaskReasoning { content } => StreamEvent::Reasoning { text: content },
+            ChatResponse::TaskComplete => StreamEvent::Complete,
+            ChatResponse::ToolCallStart { tool_call, .. } => {
+                StreamEvent::ToolCallStart { name: tool_call.name.to_string() }
+            }
+            ChatResponse::ToolCallEnd(result) => StreamEvent::ToolCallEnd {
+                is_error: result.is_error(),
+                name: result.name.to_string(),
+            },
+            ChatResponse::RetryAttempt { cause, .. } => {
+                StreamEvent::Retry { cause: cause.as_str().to_string() }
+            }
+            ChatResponse::Interrupt { reason } => StreamEvent::Interrupt {
+                reason: match reason {
+                    InterruptionReason::MaxRequestPerTurnLimitReached { limit } => {
+                        format!("Maximum request ({limit}) per turn achieved")
+                    }
+                    InterruptionReason::MaxToolFailurePerTurnLimitReached { limit, .. } => {
+                        format!("Maximum tool failure limit ({limit}) reached for this turn")
+                    }
+                    InterruptionReason::Paused => "Paused by the user".to_string(),
+                },
+            },
+            ChatResponse::PermissionRequest { tool_name, message, .. } => {
+                StreamEvent::PermissionRequest {
+                    id: None,
+                    tool_name: tool_name.to_string(),
+                    message,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ModelInfo {
+    pub id: String,
+    pub name: Option<String>,
+    pub context_length: Option<u64>,
+    pub tools_supported: Option<bool>,
+    pub supports_reasoning: Option<bool>,
+}
+
+impl From<Model> for ModelInfo {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id.to_string(),
+            name: model.name,
+            context_length: model.context_length,
+            tools_supported: model.tools_supported,
+            supports_reasoning: model.supports_reasoning,
+        }
+    }
+}
+
+/// Token usage accumulated over every request of a conversation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct ConversationUsage {
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub total_tokens: usize,
+    pub cached_tokens: usize,
+    pub cost: Option<f64>,
+}
+
+impl From<Usage> for ConversationUsage {
+    fn from(usage: Usage) -> Self {
+        Self {
+            prompt_tokens: *usage.prompt_tokens,
+            completion_tokens: *usage.completion_tokens,
+            total_tokens: *usage.total_tokens,
+            cached_tokens: *usage.cached_tokens,
+            cost: usage.cost,
+        }
+    }
+}
+
+/// Request quota of the authenticated Forge account
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct AccountUsage {
+    pub plan: String,
+    pub current: u32,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Seconds until the quota resets
+    pub reset_in: Option<u64>,
+}
+
+impl From<UserUsage> for AccountUsage {
+    fn from(usage: UserUsage) -> Self {
+        Self {
+            plan: usage.plan.r#type,
+            current: usage.usage.current,
+            limit: usage.usage.limit,
+            remaining: usage.usage.remaining,
+            reset_in: usage.usage.reset_in,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+    /// When an exhausted quota resets, only set on 429 responses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_at: Option<DateTime<Utc>>,
+}
+
+/// Usage of the caller's API key against its quota
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct QuotaStatus {
+    /// API requests made today
+    pub requests: QuotaUsage,
+    /// Tokens consumed by prompts today
+    pub tokens: QuotaUsage,
+    /// Provider cost of prompts this month
+    pub cost: CostQuota,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct QuotaUsage {
+    pub used: u64,
+    /// Absent when unlimited
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    pub reset_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CostQuota {
+    pub used: f64,
+    /// Absent when unlimited
+    pub limit: Option<f64>,
+    pub remaining: Option<f64>,
+    pub reset_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct CreateShareRequest {
+    /// Hours until the link expires, 24 by default and at most 720
+    pub expires_in_hours: Option<u64>,
+}
+
+/// Read-only link to a conversation with secrets redacted
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ShareLink {
+    pub token: String,
+    /// Path of the shared page, relative to the server
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Agent run started by a webhook delivery
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct WebhookRun {
+    /// Conversation the agent works in
+    pub conversation_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use forge_api::{ToolCallFull, ToolName};
+    use pretty_assertions::assert_eq;
+    use tokio::sync::Notify;
+
+    use super::*;
+
+    #[test]
+    fn test_conversation_summary_from_conversation() {
+        let fixture = Conversation::generate().title(Some("Refactor".to_string()));
+
+        let actual = ConversationSummary::from(&fixture);
+
+        let expected = ConversationSummary {
+            id: fixture.id.into_string(),
+            title: Some("Refactor".to_string()),
+            created_at: fixture.metadata.created_at,
+            updated_at: None,
+            pinned: false,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_ack_tool_call_start_notifies() {
+        let notifier = Arc::new(Notify::new());
+        let fixture = ChatResponse::ToolCallStart {
+            tool_call: ToolCallFull::new(ToolName::new("read")),
+            notifier: notifier.clone(),
+        };
+
+        ack_tool_call_start(&fixture);
+        let actual = StreamEvent::from(fixture);
+
+        let expected = StreamEvent::ToolCallStart { name: "read".to_string() };
+        assert_eq!(actual, expected);
+        // Completes immediately because the permit was stored by notify_one
+        notifier.notified().await;
+    }
+
+    #[test]
+    fn test_client_message_deserializes_prompt() {
+        let fixture = r#"{"type": "prompt", "prompt": "fix the build"}"#;
+
+        let ClientMessage::Prompt(actual) = serde_json::from_str(fixture).unwrap() else {
+            panic!("expected a prompt");
+        };
+
+        assert_eq!(actual.prompt, "fix the build");
+        assert_eq!(actual.additional_context, None);
+    }
+
+    #[test]
+    fn test_client_message_deserializes_permission_response() {
+        let fixture = r#"{"type": "permission_response", "id": "1", "allowed": true}"#;
+
+        let ClientMessage::PermissionResponse { id, allowed } =
+            serde_json::from_str(fixture).unwrap()
+        else {
+            panic!("expected a permission response");
+        };
+
+        assert_eq!((id.as_str(), allowed), ("1", true));
+    }
+
+    #[test]
+    fn test_stream_event_serializes_with_type_tag() {
+        let fixture = StreamEvent::ToolCallEnd { name: "shell".to_string(), is_error: true };
+        let actual = serde_json::to_value(&fixture).unwrap();
+        let expected =
+            serde_json::json!({ "type": "tool_call_end", "name": "shell", "is_error": true });
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,71 @@
+use axum::Json;
+use axum::http::StatusCode;
+use axum::http::header::RETRY_AFTER;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+
+use crate::dto::ErrorResponse;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{message}")]
+    TooManyRequests {
+        message: String,
+        reset_at: DateTime<Utc>,
+    },
+
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl Error {
+    fn status(&self) -> StatusCode {
+        match self {
+            Error::NotFound(_) => StatusCode::NOT_FOUND,
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::Forbidden(_) => StatusCode::FORBIDDEN,
+            Error::Conflict(_) => StatusCode::CONFLICT,
+            Error::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Error::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if let Error::Internal(error) = &self {
+            tracing::error!(error = ?error, "Request failed");
+        }
+        let reset_at = match &self {
+            Error::TooManyRequests { reset_at, .. } => Some(*reset_at),
+            _ => None,
+        };
+        let body = Json(ErrorResponse { error: format!("{self:#}"), reset_at });
+        match reset_at {
+            Some(reset_at) => {
+                let retry_after = (reset_at - Utc::now()).num_seconds().max(0);
+                (status, [(RETRY_AFTER, retry_after.to_string())], body).into_response()
+            }
+            None => (status, body).into_response(),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use forge_api::{API, ConversationId};
+
+use crate::error::{Error, Result};
+
+/// Lock of a conversation held while the server writes to it, released when
+/// dropped so that the CLI can pick the conversation up again
+pub(crate) struct ConversationLease {
+    api: Arc<dyn API>,
+    conversation_id: ConversationId,
+}
+
+impl ConversationLease {
+    /// Locks the conversation, failing with a conflict when another Forge
+    /// process, e.g. the CLI, has it open
+    pub async fn acquire(api: Arc<dyn API>, conversation_id: ConversationId) -> Result<Self> {
+        if let Some(holder) = api.lock_conversation(&conversation_id).await? {
+            return Err(Error::Conflict(format!(
+                "Conversation {conversation_id} is open in {holder}; it can still be read, or send the prompt to a new conversation"
+            )));
+        }
+        Ok(Self { api, conversation_id })
+    }
+}
+
+impl Drop for ConversationLease {
+    fn drop(&mut self) {
+        let api = self.api.clone();
+        let conversation_id = self.conversation_id;
+        tokio::spawn(async move {
+            if let Err(error) = api.unlock_conversation(&conversation_id).await {
+                tracing::warn!(error = ?error, %conversation_id, "Failed to unlock conversation");
+            }
+        });
+    }
+}
@@ -0,0 +1,21 @@
+//! HTTP server exposing Forge conversations over a REST API.
+//!
+//! Agent responses are streamed as server-sent events or over a WebSocket per
+//! conversation. The OpenAPI specification of the API is served at
+//! `/openapi.json`. When users are configured, requests authenticate with an
+//! API key and only see the conversations their user created.
+
+mod auth;
+mod dto;
+mod error;
+mod lease;
+mod quota;
+mod routes;
+mod share;
+mod webhook;
+mod ws;
+
+pub use auth::Principal;
+pub use dto::*;
+pub use error::{Error, Result};
+pub use routes::{ApiDoc, router, serve};
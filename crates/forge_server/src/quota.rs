@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use forge_api::{API, ConversationId, Usage};
+use forge_config::ServerQuota;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::auth::Principal;
+use crate::dto::{CostQuota, QuotaStatus, QuotaUsage};
+use crate::error::{Error, Result};
+
+/// File under the forge base path where per-key usage is persisted
+const USAGE_FILE: &str = "server_usage.json";
+
+/// Usage of a single API key in the current day and month
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct KeyUsage {
+    day: Option<NaiveDate>,
+    requests: u64,
+    tokens: u64,
+    /// First day of the month the cost was accumulated in
+    month: Option<NaiveDate>,
+    cost: f64,
+}
+
+impl KeyUsage {
+    /// Resets the counters whose window ended before `now`
+    fn roll(&mut self, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.requests = 0;
+            self.tokens = 0;
+        }
+        let month = first_day_of_month(today);
+        if self.month != Some(month) {
+            self.month = Some(month);
+            self.cost = 0.0;
+        }
+    }
+
+    fn check_request(&self, quota: &ServerQuota, now: DateTime<Utc>) -> Result<()> {
+        match quota.requests_per_day {
+            Some(limit) if self.requests >= limit => Err(Error::TooManyRequests {
+                message: format!("Daily request quota of {limit} exhausted"),
+                reset_at: next_day(now),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_usage(&self, quota: &ServerQuota, now: DateTime<Utc>) -> Result<()> {
+        if let Some(limit) = quota.tokens_per_day
+            && self.tokens >= limit
+        {
+            return Err(Error::TooManyRequests {
+                message: format!("Daily token quota of {limit} exhausted"),
+                reset_at: next_day(now),
+            });
+        }
+        if let Some(limit) = quota.cost_per_month
+            && self.cost >= limit
+        {
+            return Err(Error::TooManyRequests {
+                message: format!("Monthly cost quota of {limit:.2} exhausted"),
+                reset_at: next_month(now),
+            });
+        }
+        Ok(())
+    }
+
+    fn status(&self, quota: &ServerQuota, now: DateTime<Utc>) -> QuotaStatus {
+        QuotaStatus {
+            requests: QuotaUsage {
+                used: self.requests,
+                limit: quota.requests_per_day,
+                remaining: quota
+                    .requests_per_day
+                    .map(|limit| limit.saturating_sub(self.requests)),
+                reset_at: next_day(now),
+            },
+            tokens: QuotaUsage {
+                used: self.tokens,
+                limit: quota.tokens_per_day,
+                remaining: quota
+                    .tokens_per_day
+                    .map(|limit| limit.saturating_sub(self.tokens)),
+                reset_at: next_day(now),
+            },
+            cost: CostQuota {
+                used: self.cost,
+                limit: quota.cost_per_month,
+                remaining: quota
+                    .cost_per_month
+                    .map(|limit| (limit - self.cost).max(0.0)),
+                reset_at: next_month(now),
+            },
+        }
+    }
+}
+
+fn first_day_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn next_day(now: DateTime<Utc>) -> DateTime<Utc> {
+    let tomorrow = now.date_naive().succ_opt().unwrap_or(NaiveDate::MAX);
+    tomorrow.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn next_month(now: DateTime<Utc>) -> DateTime<Utc> {
+    let first = first_day_of_month(now.date_naive());
+    let next = first
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(NaiveDate::MAX);
+    next.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// Tracks the usage of every API key against its quota, persisting it so
+/// quotas survive restarts
+pub(crate) struct QuotaTracker {
+    path: PathBuf,
+    usage: Mutex<HashMap<String, KeyUsage>>,
+}
+
+impl QuotaTracker {
+    /// Loads the usage recorded under `base_path`, starting from scratch when
+    /// it is missing or unreadable
+    pub fn new(base_path: PathBuf) -> Self {
+        let path = base_path.join(USAGE_FILE);
+        let usage = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, usage: Mutex::new(usage) }
+    }
+
+    async fn persist(&self, usage: &HashMap<String, KeyUsage>) {
+        let result = async {
+            if let Some(parent) = self.path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&self.path, serde_json::to_vec(usage)?).await?;
+            anyhow::Ok(())
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(error = ?error, "Failed to persist server usage");
+        }
+    }
+
+    /// Counts a request against the daily request quota of the principal
+    pub async fn check_request(&self, principal: &Principal) -> Result<()> {
+        let Some(quota) = &principal.quota else {
+            return Ok(());
+        };
+        let now = Utc::now();
+        let mut usage = self.usage.lock().await;
+        let key_usage = usage.entry(principal.key()).or_default();
+        key_usage.roll(now);
+        key_usage.check_request(quota, now)?;
+        key_usage.requests += 1;
+        self.persist(&usage).await;
+        Ok(())
+    }
+
+    /// Fails when the principal has exhausted its token or cost quota
+    pub async fn check_usage(&self, principal: &Principal) -> Result<()> {
+        let Some(quota) = &principal.quota else {
+            return Ok(());
+        };
+        let now = Utc::now();
+        let mut usage = self.usage.lock().await;
+        let key_usage = usage.entry(principal.key()).or_default();
+        key_usage.roll(now);
+        key_usage.check_usage(quota, now)
+    }
+
+    async fn record_usage(&self, key: String, tokens: u64, cost: f64) {
+        let mut usage = self.usage.lock().await;
+        let key_usage = usage.entry(key).or_default();
+        key_usage.roll(Utc::now());
+        key_usage.tokens += tokens;
+        key_usage.cost += cost;
+        self.persist(&usage).await;
+    }
+
+    pub async fn status(&self, principal: &Principal) -> QuotaStatus {
+        let now = Utc::now();
+        let mut key_usage = self
+            .usage
+            .lock()
+            .await
+            .get(&principal.key())
+            .cloned()
+            .unwrap_or_default();
+        key_usage.roll(now);
+        key_usage.status(&principal.quota.clone().unwrap_or_default(), now)
+    }
+}
+
+/// Middleware counting every request against the request quota of its
+/// principal
+pub(crate) async fn enforce_request_quota(
+    Extension(quotas): Extension<Arc<QuotaTracker>>,
+    Extension(principal): Extension<Principal>,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    quotas.check_request(&principal).await?;
+    Ok(next.run(request).await)
+}
+
+/// Records the tokens and cost consumed by a prompt once its response stream
+/// is dropped, whether it completed or the client disconnected
+pub(crate) struct UsageRecorder {
+    api: Arc<dyn API>,
+    quotas: Arc<QuotaTracker>,
+    key: String,
+    conversation_id: ConversationId,
+    before: Usage,
+}
+
+impl UsageRecorder {
+    pub fn new(
+        api: Arc<dyn API>,
+        quotas: Arc<QuotaTracker>,
+        principal: &Principal,
+        conversation_id: ConversationId,
+        before: Option<Usage>,
+    ) -> Self {
+        Self {
+            api,
+            quotas,
+            key: principal.key(),
+            conversation_id,
+            before: before.unwrap_or_default(),
+        }
+    }
+}
+
+impl Drop for UsageRecorder {
+    fn drop(&mut self) {
+        let api = self.api.clone();
+        let quotas = self.quotas.clone();
+        let key = std::mem::take(&mut self.key);
+        let conversation_id = self.conversation_id;
+        let before = self.before;
+        tokio::spawn(async move {
+            let after = match api.conversation(&conversation_id).await {
+                Ok(conversation) => conversation
+                    .and_then(|conversation| conversation.accumulated_usage())
+                    .unwrap_or_default(),
+                Err(error) => {
+                    tracing::warn!(error = ?error, "Failed to read conversation usage");
+                    return;
+                }
+            };
+            let tokens = (*after.total_tokens).saturating_sub(*before.total_tokens) as u64;
+            let cost = (after.cost.unwrap_or_default() - before.cost.unwrap_or_default()).max(0.0);
+            quotas.record_usage(key, tokens, cost).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn time(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().to_utc()
+    }
+
+    #[test]
+    fn test_roll_resets_daily_and_monthly_counters() {
+        let mut actual = KeyUsage {
+            day: Some(NaiveDate::from_ymd_opt(2026, 1, 31).unwrap()),
+            requests: 10,
+            tokens: 500,
+            month: Some(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+            cost: 3.5,
+        };
+
+        actual.roll(time("2026-02-01T08:00:00Z"));
+
+        let expected = KeyUsage {
+            day: Some(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()),
+            requests: 0,
+            tokens: 0,
+            month: Some(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()),
+            cost: 0.0,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_exhausted_quotas_report_reset_time() {
+        let now = time("2026-02-14T08:00:00Z");
+        let quota = ServerQuota::default()
+            .requests_per_day(2)
+            .tokens_per_day(1000)
+            .cost_per_month(5.0);
+        let mut fixture = KeyUsage::default();
+        fixture.roll(now);
+        fixture.requests = 2;
+        fixture.cost = 5.0;
+
+        let actual = (
+            fixture.check_request(&quota, now).unwrap_err(),
+            fixture.check_usage(&quota, now).unwrap_err(),
+        );
+
+        assert!(matches!(
+            actual.0,
+            Error::TooManyRequests { reset_at, .. } if reset_at == time("2026-02-15T00:00:00Z")
+        ));
+        assert!(matches!(
+            actual.1,
+            Error::TooManyRequests { reset_at, .. } if reset_at == time("2026-03-01T00:00:00Z")
+        ));
+    }
+
+    #[test]
+    fn test_status_reports_remaining_quota() {
+        let now = time("2026-02-14T08:00:00Z");
+        let quota = ServerQuota::default().tokens_per_day(1000);
+        let mut fixture = KeyUsage::default();
+        fixture.roll(now);
+        fixture.requests = 3;
+        fixture.tokens = 1200;
+
+        let actual = fixture.status(&quota, now);
+
+        let expected = QuotaStatus {
+            requests: QuotaUsage {
+                used: 3,
+                limit: None,
+                remaining: None,
+                reset_at: time("2026-02-15T00:00:00Z"),
+            },
+            tokens: QuotaUsage {
+                used: 1200,
+                limit: Some(1000),
+                remaining: Some(0),
+                reset_at: time("2026-02-15T00:00:00Z"),
+            },
+            cost: CostQuota {
+                used: 0.0,
+                limit: None,
+                remaining: None,
+                reset_at: time("2026-03-01T00:00:00Z"),
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+}
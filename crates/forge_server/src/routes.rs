@@ -0,0 +1,445 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use forge_api::{API, ChatRequest, Conversation, ConversationId, Event};
+use forge_config::ServerConfig;
+use futures::{Stream, StreamExt};
+use utoipa::OpenApi;
+
+use crate::auth::{Authenticator, Principal, SecurityAddon, authenticate, validate_config};
+use crate::dto::*;
+use crate::error::{Error, Result};
+use crate::lease::ConversationLease;
+use crate::quota::{QuotaTracker, UsageRecorder, enforce_request_quota};
+use crate::share::{ShareStore, create_share, revoke_shares, shared_conversation};
+use crate::webhook::{Webhooks, receive_webhook};
+
+pub(crate) type ApiState = State<Arc<dyn API>>;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Forge API",
+        description = "Manage Forge conversations and stream agent responses"
+    ),
+    paths(
+        list_conversations,
+        create_conversation,
+        get_conversation,
+        update_conversation,
+        delete_conversation,
+        send_message,
+        crate::ws::conversation_socket,
+        conversation_usage,
+        list_models,
+        account_usage,
+        quota_status,
+        crate::webhook::receive_webhook,
+        crate::share::create_share,
+        crate::share::revoke_shares,
+        crate::share::shared_conversation,
+    ),
+    components(schemas(
+        ConversationSummary,
+        CreateConversationRequest,
+        UpdateConversationRequest,
+        SendMessageRequest,
+        StreamEvent,
+        ClientMessage,
+        ModelInfo,
+        ConversationUsage,
+        AccountUsage,
+        QuotaStatus,
+        WebhookRun,
+        CreateShareRequest,
+        ShareLink,
+        ErrorResponse,
+    )),
+    modifiers(&SecurityAddon),
+    security(("api_key" = []))
+)]
+pub struct ApiDoc;
+
+/// Builds the router serving the REST API on top of `api`, authenticating
+/// requests against the users of `config`
+pub fn router<A: API + 'static>(api: Arc<A>, config: ServerConfig) -> Router {
+    let api: Arc<dyn API> = api;
+    let webhooks = Webhooks::new(&config);
+    let authenticator = Authenticator::new(config);
+    let quotas = Arc::new(QuotaTracker::new(api.environment().base_path));
+    let shares = Arc::new(ShareStore::new(api.environment().base_path));
+    Router::new()
+        .route(
+            "/v1/conversations",
+            get(list_conversations).post(create_conversation),
+        )
+        .route(
+            "/v1/conversations/{id}",
+            get(get_conversation)
+                .patch(update_conversation)
+                .delete(delete_conversation),
+        )
+        .route(
+            "/v1/conversations/{id}/messages",
+            axum::routing::post(send_message),
+        )
+        .route(
+            "/v1/conversations/{id}/ws",
+            get(crate::ws::conversation_socket),
+        )
+        .route(
+            "/v1/conversations/{id}/share",
+            axum::routing::post(create_share).delete(revoke_shares),
+        )
+        .route("/v1/conversations/{id}/usage", get(conversation_usage))
+        .route("/v1/models", get(list_models))
+        .route("/v1/usage", get(account_usage))
+        .layer(axum::middleware::from_fn(enforce_request_quota))
+        // Checking the remaining quota must work once it is exhausted
+        .route("/v1/quota", get(quota_status))
+        .layer(axum::middleware::from_fn_with_state(
+            authenticator,
+            authenticate,
+        ))
+        // Webhook deliveries authenticate with their signature and check the
+        // quota themselves once it is verified
+        .route("/v1/webhooks/{name}", axum::routing::post(receive_webhook))
+        .layer(Extension(quotas))
+        // Share links grant read access to a single conversation
+        .route("/share/{token}", get(shared_conversation))
+        .layer(Extension(webhooks))
+        .layer(Extension(shares))
+        // The specification is public so clients can discover how to authenticate
+        .route("/openapi.json", get(openapi))
+        .with_state(api)
+}
+
+/// Serves the REST API on `addr` until the process is terminated
+pub async fn serve<A: API + 'static>(
+    api: Arc<A>,
+    config: ServerConfig,
+    addr: SocketAddr,
+) -> anyhow::Result<()> {
+    validate_config(&config)?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(
+        addr = %listener.local_addr()?,
+        users = config.users.len(),
+        webhooks = config.webhooks.len(),
+        "Serving REST API"
+    );
+    axum::serve(listener, router(api, config)).await?;
+    Ok(())
+}
+
+async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+fn parse_id(id: &str) -> Result<ConversationId> {
+    ConversationId::parse(id)
+        .map_err(|_| Error::BadRequest(format!("Invalid conversation id: {id}")))
+}
+
+/// Builds the chat request sending `request` to the conversation
+pub(crate) fn chat_request(
+    conversation_id: ConversationId,
+    request: SendMessageRequest,
+) -> Result<ChatRequest> {
+    if request.prompt.trim().is_empty() {
+        return Err(Error::BadRequest("Prompt must not be empty".to_string()));
+    }
+
+    let mut event = Event::new(request.prompt);
+    if let Some(additional_context) = request.additional_context {
+        event = event.additional_context(additional_context);
+    }
+    Ok(ChatRequest::new(event, conversation_id))
+}
+
+/// Loads a conversation the principal has access to. Conversations of other
+/// users are reported as missing so their ids are not disclosed.
+pub(crate) async fn find_conversation(
+    api: &dyn API,
+    principal: &Principal,
+    id: &str,
+) -> Result<Conversation> {
+    let id = parse_id(id)?;
+    api.conversation(&id)
+        .await?
+        .filter(|conversation| principal.can_access(conversation))
+        .ok_or_else(|| Error::NotFound(format!("Conversation {id} not found")))
+}
+
+/// Lists conversations, most recent first
+#[utoipa::path(
+    get,
+    path = "/v1/conversations",
+    params(ListConversationsQuery),
+    responses((status = 200, body = [ConversationSummary]))
+)]
+async fn list_conversations(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Query(query): Query<ListConversationsQuery>,
+) -> Result<Json<Vec<ConversationSummary>>> {
+    // The limit applies after filtering out conversations of other users
+    let conversations = api.get_conversations(None).await?;
+    Ok(Json(
+        conversations
+            .iter()
+            .filter(|conversation| principal.can_access(conversation))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .map(ConversationSummary::from)
+            .collect(),
+    ))
+}
+
+/// Creates an empty conversation
+#[utoipa::path(
+    post,
+    path = "/v1/conversations",
+    request_body = CreateConversationRequest,
+    responses(
+        (status = 201, body = ConversationSummary),
+        (status = 403, body = ErrorResponse)
+    )
+)]
+async fn create_conversation(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Json(request): Json<CreateConversationRequest>,
+) -> Result<(StatusCode, Json<ConversationSummary>)> {
+    principal.require_operator()?;
+    let mut conversation = Conversation::generate().title(request.title);
+    conversation.metadata.owner = principal.name;
+    api.upsert_conversation(conversation.clone()).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ConversationSummary::from(&conversation)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/conversations/{id}",
+    params(("id" = String, Path, description = "Conversation id")),
+    responses(
+        (status = 200, body = ConversationSummary),
+        (status = 404, body = ErrorResponse)
+    )
+)]
+async fn get_conversation(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<ConversationSummary>> {
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    Ok(Json(ConversationSummary::from(&conversation)))
+}
+
+/// Renames or pins a conversation
+#[utoipa::path(
+    patch,
+    path = "/v1/conversations/{id}",
+    params(("id" = String, Path, description = "Conversation id")),
+    request_body = UpdateConversationRequest,
+    responses(
+        (status = 200, body = ConversationSummary),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+        (status = 409, body = ErrorResponse)
+    )
+)]
+async fn update_conversation(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateConversationRequest>,
+) -> Result<Json<ConversationSummary>> {
+    principal.require_operator()?;
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    let _lease = ConversationLease::acquire(api.clone(), conversation.id).await?;
+    if let Some(title) = request.title {
+        api.rename_conversation(&conversation.id, title).await?;
+    }
+    if let Some(pinned) = request.pinned {
+        api.pin_conversation(&conversation.id, pinned).await?;
+    }
+    get_conversation(State(api), Extension(principal), Path(id)).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/conversations/{id}",
+    params(("id" = String, Path, description = "Conversation id")),
+    responses(
+        (status = 204),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+        (status = 409, body = ErrorResponse)
+    )
+)]
+async fn delete_conversation(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<StatusCode> {
+    principal.require_operator()?;
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    let _lease = ConversationLease::acquire(api.clone(), conversation.id).await?;
+    api.delete_conversation(&conversation.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Sends a prompt to the active agent and streams its response as
+/// server-sent events
+#[utoipa::path(
+    post,
+    path = "/v1/conversations/{id}/messages",
+    params(("id" = String, Path, description = "Conversation id")),
+    request_body = SendMessageRequest,
+    responses(
+        (status = 200, content_type = "text/event-stream", body = StreamEvent),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+        (status = 409, body = ErrorResponse),
+        (status = 429, body = ErrorResponse)
+    )
+)]
+async fn send_message(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Extension(quotas): Extension<Arc<QuotaTracker>>,
+    Path(id): Path<String>,
+    Json(request): Json<SendMessageRequest>,
+) -> Result<Sse<impl Stream<Item = std::result::Result<SseEvent, Infallible>>>> {
+    principal.require_operator()?;
+    quotas.check_usage(&principal).await?;
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    let lease = ConversationLease::acquire(api.clone(), conversation.id).await?;
+    let stream = api.chat(chat_request(conversation.id, request)?).await?;
+    let recorder = UsageRecorder::new(
+        api.clone(),
+        quotas,
+        &principal,
+        conversation.id,
+        conversation.accumulated_usage(),
+    );
+    let events = stream.map(move |response| {
+        // Usage is recorded and the conversation unlocked when the stream, and
+        // with it this closure, is dropped
+        let _ = (&recorder, &lease);
+        let event = match response {
+            Ok(response) => {
+                ack_tool_call_start(&response);
+                StreamEvent::from(response)
+            }
+            Err(error) => StreamEvent::Error { message: format!("{error:#}") },
+        };
+        let data = serde_json::to_string(&event).unwrap_or_default();
+        Ok(SseEvent::default().event(event.name()).data(data))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Returns the token usage accumulated by a conversation
+#[utoipa::path(
+    get,
+    path = "/v1/conversations/{id}/usage",
+    params(("id" = String, Path, description = "Conversation id")),
+    responses(
+        (status = 200, body = ConversationUsage),
+        (status = 404, body = ErrorResponse)
+    )
+)]
+async fn conversation_usage(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<ConversationUsage>> {
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    Ok(Json(
+        conversation
+            .accumulated_usage()
+            .map(ConversationUsage::from)
+            .unwrap_or_default(),
+    ))
+}
+
+/// Lists the models of the configured provider
+#[utoipa::path(
+    get,
+    path = "/v1/models",
+    responses((status = 200, body = [ModelInfo]))
+)]
+async fn list_models(State(api): ApiState) -> Result<Json<Vec<ModelInfo>>> {
+    let models = api.get_models().await?;
+    Ok(Json(models.into_iter().map(ModelInfo::from).collect()))
+}
+
+/// Returns the request quota of the authenticated Forge account
+#[utoipa::path(
+    get,
+    path = "/v1/usage",
+    responses(
+        (status = 200, body = AccountUsage),
+        (status = 404, body = ErrorResponse)
+    )
+)]
+async fn account_usage(State(api): ApiState) -> Result<Json<AccountUsage>> {
+    let usage = api
+        .user_usage()
+        .await?
+        .ok_or_else(|| Error::NotFound("Not logged in to a Forge account".to_string()))?;
+    Ok(Json(AccountUsage::from(usage)))
+}
+
+/// Returns the usage of the caller's API key against its quota
+#[utoipa::path(
+    get,
+    path = "/v1/quota",
+    responses((status = 200, body = QuotaStatus))
+)]
+async fn quota_status(
+    Extension(principal): Extension<Principal>,
+    Extension(quotas): Extension<Arc<QuotaTracker>>,
+) -> Json<QuotaStatus> {
+    Json(quotas.status(&principal).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_openapi_documents_every_route() {
+        let spec = ApiDoc::openapi();
+
+        let actual = spec.paths.paths.keys().cloned().collect::<Vec<_>>();
+
+        let expected = vec![
+            "/share/{token}".to_string(),
+            "/v1/conversations".to_string(),
+            "/v1/conversations/{id}".to_string(),
+            "/v1/conversations/{id}/messages".to_string(),
+            "/v1/conversations/{id}/share".to_string(),
+            "/v1/conversations/{id}/usage".to_string(),
+            "/v1/conversations/{id}/ws".to_string(),
+            "/v1/models".to_string(),
+            "/v1/quota".to_string(),
+            "/v1/usage".to_string(),
+            "/v1/webhooks/{name}".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use chrono::{DateTime, Duration, Utc};
+use forge_api::{API, Conversation, ConversationId};
+use futures::future;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::auth::Principal;
+use crate::dto::{CreateShareRequest, ErrorResponse, ShareLink};
+use crate::error::{Error, Result};
+use crate::routes::{ApiState, find_conversation};
+
+/// File under the forge base path where share links are persisted
+const SHARES_FILE: &str = "server_shares.json";
+
+/// Lifetime of a share link when the request does not set one
+const DEFAULT_EXPIRY_HOURS: u64 = 24;
+
+/// Longest lifetime a share link may have
+const MAX_EXPIRY_HOURS: u64 = 30 * 24;
+
+/// Read-only link to a conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Share {
+    conversation_id: ConversationId,
+    expires_at: DateTime<Utc>,
+}
+
+/// Share links of every conversation, persisted so links survive restarts
+pub(crate) struct ShareStore {
+    path: PathBuf,
+    shares: Mutex<HashMap<String, Share>>,
+}
+
+impl ShareStore {
+    /// Loads the links recorded under `base_path`, starting from scratch when
+    /// they are missing or unreadable
+    pub fn new(base_path: PathBuf) -> Self {
+        let path = base_path.join(SHARES_FILE);
+        let shares = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, shares: Mutex::new(shares) }
+    }
+
+    async fn persist(&self, shares: &HashMap<String, Share>) {
+        let result = async {
+            if let Some(parent) = self.path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&self.path, serde_json::to_vec(shares)?).await?;
+            anyhow::Ok(())
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(error = ?error, "Failed to persist share links");
+        }
+    }
+
+    /// Creates a link to the conversation expiring at `expires_at` and returns
+    /// its token
+    async fn create(&self, conversation_id: ConversationId, expires_at: DateTime<Utc>) -> String {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let mut shares = self.shares.lock().await;
+        prune_expired(&mut shares, Utc::now());
+        shares.insert(token.clone(), Share { conversation_id, expires_at });
+        self.persist(&shares).await;
+        token
+    }
+
+    /// Revokes every link to the conversation
+    async fn revoke(&self, conversation_id: ConversationId) {
+        let mut shares = self.shares.lock().await;
+        shares.retain(|_, share| share.conversation_id != conversation_id);
+        self.persist(&shares).await;
+    }
+
+    /// Conversation a link points to, unless it expired
+    async fn resolve(&self, token: &str) -> Option<ConversationId> {
+        self.shares
+            .lock()
+            .await
+            .get(token)
+            .filter(|share| share.expires_at > Utc::now())
+            .map(|share| share.conversation_id)
+    }
+}
+
+fn prune_expired(shares: &mut HashMap<String, Share>, now: DateTime<Utc>) {
+    shares.retain(|_, share| share.expires_at > now);
+}
+
+fn expires_at(request: &CreateShareRequest, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let hours = request.expires_in_hours.unwrap_or(DEFAULT_EXPIRY_HOURS);
+    if hours == 0 || hours > MAX_EXPIRY_HOURS {
+        return Err(Error::BadRequest(format!(
+            "Expiry must be between 1 and {MAX_EXPIRY_HOURS} hours"
+        )));
+    }
+    Ok(now + Duration::hours(hours as i64))
+}
+
+/// Publishes a conversation as a read-only page with secrets redacted
+#[utoipa::path(
+    post,
+    path = "/v1/conversations/{id}/share",
+    params(("id" = String, Path, description = "Conversation id")),
+    request_body = CreateShareRequest,
+    responses(
+        (status = 201, body = ShareLink),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse)
+    )
+)]
+pub(crate) async fn create_share(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Extension(shares): Extension<Arc<ShareStore>>,
+    Path(id): Path<String>,
+    Json(request): Json<CreateShareRequest>,
+) -> Result<(StatusCode, Json<ShareLink>)> {
+    principal.require_operator()?;
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    let expires_at = expires_at(&request, Utc::now())?;
+    let token = shares.create(conversation.id, expires_at).await;
+    Ok((
+        StatusCode::CREATED,
+        Json(ShareLink { url: format!("/share/{token}"), token, expires_at }),
+    ))
+}
+
+/// Revokes every share link of a conversation
+#[utoipa::path(
+    delete,
+    path = "/v1/conversations/{id}/share",
+    params(("id" = String, Path, description = "Conversation id")),
+    responses(
+        (status = 204),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse)
+    )
+)]
+pub(crate) async fn revoke_shares(
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Extension(shares): Extension<Arc<ShareStore>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode> {
+    principal.require_operator()?;
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    shares.revoke(conversation.id).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Renders a shared conversation as a read-only HTML page
+#[utoipa::path(
+    get,
+    path = "/share/{token}",
+    params(("token" = String, Path, description = "Token of the share link")),
+    responses(
+        (status = 200, content_type = "text/html", body = String),
+        (status = 404, body = ErrorResponse)
+    ),
+    // The token grants access to the single conversation it was created for
+    security(())
+)]
+pub(crate) async fn shared_conversation(
+    State(api): ApiState,
+    Extension(shares): Extension<Arc<ShareStore>>,
+    Path(token): Path<String>,
+) -> Result<Response> {
+    // Expired and unknown links are indistinguishable to avoid probing
+    let not_found = || Error::NotFound("Share link not found or expired".to_string());
+    let conversation_id = shares.resolve(&token).await.ok_or_else(not_found)?;
+    let conversation = api
+        .conversation(&conversation_id)
+        .await?
+        .ok_or_else(not_found)?
+        .redacted()?;
+    let related = related_conversations(api.as_ref(), &conversation).await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, "text/html; charset=utf-8"),
+            (CACHE_CONTROL, "no-store"),
+        ],
+        conversation.to_html_with_related(&related),
+    )
+        .into_response())
+}
+
+/// Redacted conversations of the agents the conversation delegated to
+async fn related_conversations(
+    api: &dyn API,
+    conversation: &Conversation,
+) -> anyhow::Result<Vec<Conversation>> {
+    let related = future::join_all(
+        conversation
+            .related_conversation_ids()
+            .iter()
+            .map(|id| api.conversation(id)),
+    )
+    .await;
+    related
+        .into_iter()
+        .filter_map(|conversation| conversation.ok().flatten())
+        .map(|conversation| conversation.redacted())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn time(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().to_utc()
+    }
+
+    #[test]
+    fn test_expires_at_defaults_and_bounds() {
+        let now = time("2026-02-14T08:00:00Z");
+
+        let actual = expires_at(&CreateShareRequest::default(), now).unwrap();
+
+        assert_eq!(actual, time("2026-02-15T08:00:00Z"));
+        for hours in [0, MAX_EXPIRY_HOURS + 1] {
+            let fixture = CreateShareRequest { expires_in_hours: Some(hours) };
+            assert!(matches!(
+                expires_at(&fixture, now),
+                Err(Error::BadRequest(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_links_do_not_resolve() {
+        let fixture = tempfile::tempdir().unwrap();
+        let store = ShareStore::new(fixture.path().to_path_buf());
+        let conversation_id = ConversationId::generate();
+        let active = store
+            .create(conversation_id, Utc::now() + Duration::hours(1))
+            .await;
+        let expired = store
+            .create(conversation_id, Utc::now() - Duration::hours(1))
+            .await;
+
+        let actual = (
+            store.resolve(&active).await,
+            store.resolve(&expired).await,
+            ShareStore::new(fixture.path().to_path_buf())
+                .resolve(&active)
+                .await,
+        );
+
+        let expected = (Some(conversation_id), None, Some(conversation_id));
+        assert_eq!(actual, expected);
+    }
+}
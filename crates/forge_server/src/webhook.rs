@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use forge_api::{API, ChatRequest, Conversation, ConversationId, Event, Template, UserCommand};
+use forge_config::{ServerConfig, ServerQuota, ServerWebhook, ServerWebhookTrigger};
+use futures::StreamExt;
+use handlebars::Handlebars;
+use hmac::{Hmac, KeyInit, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::auth::Principal;
+use crate::dto::{WebhookRun, ack_tool_call_start};
+use crate::error::{Error, Result};
+use crate::lease::ConversationLease;
+use crate::quota::{QuotaTracker, UsageRecorder};
+use crate::routes::ApiState;
+
+/// Header carrying the HMAC-SHA256 signature of a delivery
+const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
+/// Header carrying the name of the event a delivery reports
+const EVENT_HEADER: &str = "x-github-event";
+
+/// Webhooks configured on the server and the quota their runs count against
+#[derive(Clone)]
+pub(crate) struct Webhooks {
+    webhooks: Arc<Vec<ServerWebhook>>,
+    quota: Option<ServerQuota>,
+}
+
+impl Webhooks {
+    pub fn new(config: &ServerConfig) -> Self {
+        Self {
+            webhooks: Arc::new(config.webhooks.clone()),
+            quota: config.quota.clone(),
+        }
+    }
+
+    /// Principal of the runs started by the webhook named `name`, tracking
+    /// their usage against the server-wide quota
+    fn principal(&self, name: &str) -> Principal {
+        Principal::webhook(name, self.quota.clone())
+    }
+}
+
+/// Starts an agent run for a webhook delivery.
+///
+/// The run continues in the background after the response is sent; its
+/// progress is available through the returned conversation. Deliveries
+/// matching no trigger of the webhook are acknowledged and ignored.
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks/{name}",
+    params(("name" = String, Path, description = "Webhook name")),
+    request_body(content = Object, description = "Event payload"),
+    responses(
+        (status = 202, body = WebhookRun),
+        (status = 204, description = "No trigger matched the delivery"),
+        (status = 400, body = crate::dto::ErrorResponse),
+        (status = 401, body = crate::dto::ErrorResponse),
+        (status = 404, body = crate::dto::ErrorResponse),
+        (status = 429, body = crate::dto::ErrorResponse)
+    ),
+    // Deliveries are authenticated by their signature instead of an API key
+    security(())
+)]
+pub(crate) async fn receive_webhook(
+    State(api): ApiState,
+    Extension(webhooks): Extension<Webhooks>,
+    Extension(quotas): Extension<Arc<QuotaTracker>>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response> {
+    let webhook = webhooks
+        .webhooks
+        .iter()
+        .find(|webhook| webhook.name == name)
+        .ok_or_else(|| Error::NotFound(format!("Webhook {name} not found")))?;
+
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok());
+    if !verify_signature(&webhook.secret, &body, signature) {
+        return Err(Error::Unauthorized("Invalid webhook signature".to_string()));
+    }
+
+    // Deliveries count against the quota like requests authenticated by a key
+    let principal = webhooks.principal(&webhook.name);
+    quotas.check_request(&principal).await?;
+
+    let payload: Value = serde_json::from_slice(&body)
+        .map_err(|error| Error::BadRequest(format!("Invalid payload: {error}")))?;
+    let event_name = headers
+        .get(EVENT_HEADER)
+        .and_then(|value| value.to_str().ok());
+    let Some(trigger) = webhook
+        .triggers
+        .iter()
+        .find(|trigger| matches(trigger, event_name, &payload))
+    else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+
+    let prompt = render_prompt(&trigger.prompt, &payload)?;
+    let event = match &trigger.command {
+        Some(command_name) => {
+            let command = api
+                .get_commands()
+                .await?
+                .into_iter()
+                .find(|command| &command.name == command_name)
+                .ok_or_else(|| anyhow::anyhow!("Workflow command {command_name} not found"))?;
+            // The prompt is passed as parameters so payload content is never
+            // rendered as part of the command's template
+            UserCommand::new(
+                command.name,
+                Template::new(command.prompt.unwrap_or_default()),
+                vec![prompt],
+            )
+            .into()
+        }
+        None => Event::new(prompt),
+    };
+
+    quotas.check_usage(&principal).await?;
+    let mut conversation = Conversation::generate();
+    conversation.metadata.owner = principal.name.clone();
+    api.upsert_conversation(conversation.clone()).await?;
+    let recorder = UsageRecorder::new(api.clone(), quotas, &principal, conversation.id, None);
+    tokio::spawn(run(api, event, conversation.id, recorder));
+
+    let run = WebhookRun { conversation_id: conversation.id.into_string() };
+    Ok((StatusCode::ACCEPTED, Json(run)).into_response())
+}
+
+/// Drives the agent's response to completion since no client consumes it,
+/// recording its usage once it ends
+async fn run(
+    api: Arc<dyn API>,
+    event: Event,
+    conversation_id: ConversationId,
+    _recorder: UsageRecorder,
+) {
+    let _lease = match ConversationLease::acquire(api.clone(), conversation_id).await {
+        Ok(lease) => lease,
+        Err(error) => {
+            tracing::warn!(error = ?error, %conversation_id, "Failed to lock webhook conversation");
+            return;
+        }
+    };
+    let mut responses = match api.chat(ChatRequest::new(event, conversation_id)).await {
+        Ok(responses) => responses,
+        Err(error) => {
+            tracing::warn!(error = ?error, %conversation_id, "Failed to start webhook run");
+            return;
+        }
+    };
+    while let Some(response) = responses.next().await {
+        match response {
+            Ok(response) => ack_tool_call_start(&response),
+            Err(error) => {
+                tracing::warn!(error = ?error, %conversation_id, "Webhook run failed");
+            }
+        }
+    }
+}
+
+/// Whether the trigger applies to a delivery of `event_name` with `payload`
+fn matches(trigger: &ServerWebhookTrigger, event_name: Option<&str>, payload: &Value) -> bool {
+    let event_matches = trigger
+        .event
+        .as_deref()
+        .is_none_or(|expected| event_name == Some(expected));
+    event_matches
+        && trigger
+            .when
+            .iter()
+            .all(|(pointer, expected)| match payload.pointer(pointer) {
+                Some(Value::String(actual)) => actual == expected,
+                Some(actual) => serde_json::from_str::<Value>(expected)
+                    .is_ok_and(|expected| expected == *actual),
+                None => false,
+            })
+}
+
+/// Renders the prompt template of a trigger with the delivery's payload
+fn render_prompt(template: &str, payload: &Value) -> anyhow::Result<String> {
+    let mut handlebars = Handlebars::new();
+    // Prompts are plain text, HTML escaping would mangle quotes in the payload
+    handlebars.register_escape_fn(handlebars::no_escape);
+    Ok(handlebars.render_template(template, payload)?)
+}
+
+/// Checks a `sha256=<hex>` signature of `body` made with `secret`, comparing
+/// it in constant time
+fn verify_signature(secret: &str, body: &[u8], signature: Option<&str>) -> bool {
+    let Some(signature) = signature
+        .and_then(|signature| signature.strip_prefix("sha256="))
+        .and_then(|signature| hex::decode(signature).ok())
+    else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn trigger_fixture() -> ServerWebhookTrigger {
+        ServerWebhookTrigger::default()
+            .event("issues".to_string())
+            .when([("/action".to_string(), "opened".to_string())])
+            .prompt("Fix issue #{{issue.number}}: {{issue.title}}")
+    }
+
+    #[test]
+    fn test_trigger_matches_event_and_payload_fields() {
+        let fixture = trigger_fixture();
+        let opened = json!({ "action": "opened" });
+        let closed = json!({ "action": "closed" });
+
+        assert!(matches(&fixture, Some("issues"), &opened));
+        assert!(!matches(&fixture, Some("issues"), &closed));
+        assert!(!matches(&fixture, Some("push"), &opened));
+        assert!(!matches(&fixture, None, &opened));
+    }
+
+    #[test]
+    fn test_trigger_matches_non_string_fields() {
+        let fixture = ServerWebhookTrigger::default()
+            .when([("/workflow_run/run_attempt".to_string(), "1".to_string())]);
+        let payload = json!({ "workflow_run": { "run_attempt": 1 } });
+
+        assert!(matches(&fixture, Some("workflow_run"), &payload));
+    }
+
+    #[test]
+    fn test_render_prompt_extracts_payload_fields() {
+        let fixture = json!({ "issue": { "number": 42, "title": "Crash on \"empty\" input" } });
+
+        let actual = render_prompt(&trigger_fixture().prompt, &fixture).unwrap();
+
+        let expected = "Fix issue #42: Crash on \"empty\" input";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        // Example from GitHub's webhook documentation
+        let secret = "It's a Secret to Everybody";
+        let body = b"Hello, World!";
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        assert!(verify_signature(secret, body, Some(signature)));
+        assert!(!verify_signature("wrong", body, Some(signature)));
+        assert!(!verify_signature(secret, body, None));
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::response::Response;
+use forge_api::{API, ChatResponse, ConversationId, PermissionReply};
+use futures::StreamExt;
+use futures::stream::BoxStream;
+
+use crate::auth::Principal;
+use crate::dto::{ClientMessage, SendMessageRequest, StreamEvent, ack_tool_call_start};
+use crate::error::Result;
+use crate::lease::ConversationLease;
+use crate::quota::{QuotaTracker, UsageRecorder};
+use crate::routes::{ApiState, chat_request, find_conversation};
+
+/// Opens a WebSocket streaming live updates of a conversation.
+///
+/// The client sends `ClientMessage` JSON text messages and receives every
+/// `StreamEvent` of the agent's response as a JSON text message. Prompts sent
+/// while the agent is still working are handled once it completes. Permission
+/// requests wait for the client's `permission_response` and are denied when
+/// the client disconnects without answering.
+#[utoipa::path(
+    get,
+    path = "/v1/conversations/{id}/ws",
+    params(("id" = String, Path, description = "Conversation id")),
+    responses(
+        (status = 101, description = "Switches to the WebSocket protocol"),
+        (status = 403, body = crate::dto::ErrorResponse),
+        (status = 404, body = crate::dto::ErrorResponse),
+        (status = 429, body = crate::dto::ErrorResponse)
+    )
+)]
+pub(crate) async fn conversation_socket(
+    upgrade: WebSocketUpgrade,
+    State(api): ApiState,
+    Extension(principal): Extension<Principal>,
+    Extension(quotas): Extension<Arc<QuotaTracker>>,
+    Path(id): Path<String>,
+) -> Result<Response> {
+    // Viewers could only watch their own prompts, which they cannot send
+    principal.require_operator()?;
+    let conversation = find_conversation(api.as_ref(), &principal, &id).await?;
+    Ok(upgrade.on_upgrade(move |socket| async move {
+        if let Err(error) = handle_socket(api, quotas, principal, conversation.id, socket).await {
+            tracing::debug!(error = ?error, "WebSocket connection closed");
+        }
+    }))
+}
+
+/// A prompt the agent is working on, holding the conversation lock and the
+/// usage recorder until its responses are exhausted
+struct Turn {
+    responses: BoxStream<'static, anyhow::Result<ChatResponse>>,
+    _recorder: UsageRecorder,
+    _lease: ConversationLease,
+}
+
+impl Turn {
+    async fn start(
+        api: &Arc<dyn API>,
+        quotas: &Arc<QuotaTracker>,
+        principal: &Principal,
+        conversation_id: ConversationId,
+        request: SendMessageRequest,
+    ) -> Result<Self> {
+        // Every prompt counts as a request, like prompts sent over HTTP
+        quotas.check_request(principal).await?;
+        quotas.check_usage(principal).await?;
+        let chat = chat_request(conversation_id, request)?;
+        let lease = ConversationLease::acquire(api.clone(), conversation_id).await?;
+        let before = api
+            .conversation(&conversation_id)
+            .await?
+            .and_then(|conversation| conversation.accumulated_usage());
+        let responses = api.chat(chat).await?.boxed();
+        let recorder = UsageRecorder::new(
+            api.clone(),
+            quotas.clone(),
+            principal,
+            conversation_id,
+            before,
+        );
+        Ok(Self { responses, _recorder: recorder, _lease: lease })
+    }
+
+    /// Next response of the agent, or never when there is no turn so that
+    /// `select!` keeps waiting on the other branches
+    async fn next(turn: &mut Option<Turn>) -> Option<anyhow::Result<ChatResponse>> {
+        match turn {
+            Some(turn) => turn.responses.next().await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+async fn handle_socket(
+    api: Arc<dyn API>,
+    quotas: Arc<QuotaTracker>,
+    principal: Principal,
+    conversation_id: ConversationId,
+    mut socket: WebSocket,
+) -> anyhow::Result<()> {
+    let mut prompts = VecDeque::new();
+    let mut turn: Option<Turn> = None;
+    // Permission requests of the agent waiting for the client, by id
+    let mut permissions: HashMap<String, PermissionReply> = HashMap::new();
+    let mut permission_ids = 0u64;
+
+    loop {
+        if turn.is_none()
+            && let Some(request) = prompts.pop_front()
+        {
+            match Turn::start(&api, &quotas, &principal, conversation_id, request).await {
+                Ok(started) => turn = Some(started),
+                Err(error) => {
+                    let message = format!("{error:#}");
+                    send_event(&mut socket, StreamEvent::Error { message }).await?;
+                    continue;
+                }
+            }
+        }
+
+        tokio::select! {
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Err(error)) => return Err(error.into()),
+                    // Pings are answered by axum and binary messages are not supported
+                    Some(Ok(_)) => continue,
+                };
+                let error = match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Prompt(request)) => {
+                        prompts.push_back(request);
+                        continue;
+                    }
+                    Ok(ClientMessage::PermissionResponse { id, allowed }) => {
+                        match permissions.remove(&id) {
+                            Some(reply) => {
+                                reply.send(allowed);
+                                continue;
+                            }
+                            None => format!("No permission request is waiting with id {id}"),
+                        }
+                    }
+                    Err(error) => format!("Invalid message: {error}"),
+                };
+                send_event(&mut socket, StreamEvent::Error { message: error }).await?;
+            }
+            response = Turn::next(&mut turn) => {
+                let event = match response {
+                    Some(Ok(ChatResponse::PermissionRequest { tool_name, message, reply: Some(reply) })) => {
+                        permission_ids += 1;
+                        let id = permission_ids.to_string();
+                        permissions.insert(id.clone(), reply);
+                        StreamEvent::PermissionRequest { id: Some(id), tool_name: tool_name.to_string(), message }
+                    }
+                    Some(Ok(response)) => {
+                        ack_tool_call_start(&response);
+                        StreamEvent::from(response)
+                    }
+                    Some(Err(error)) => StreamEvent::Error { message: format!("{error:#}") },
+                    None => {
+                        turn = None;
+                        permissions.clear();
+                        continue;
+                    }
+                };
+                send_event(&mut socket, event).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn send_event(socket: &mut WebSocket, event: StreamEvent) -> anyhow::Result<()> {
+    let text = serde_json::to_string(&event)?;
+    socket.send(Message::Text(text.into())).await?;
+    Ok(())
+}
@@ -79,15 +79,12 @@ impl<F: ProviderRepository + EnvironmentInfra<Config = forge_config::ForgeConfig
             .reasoning
             .clone()
             .and_then(|r| r.effort)
-            .map(|e| match e {
-                forge_config::Effort::None => Effort::None,
-                forge_config::Effort::Minimal => Effort::Minimal,
-                forge_config::Effort::Low => Effort::Low,
-                forge_config::Effort::Medium => Effort::Medium,
-                forge_config::Effort::High => Effort::High,
-                forge_config::Effort::XHigh => Effort::XHigh,
-                forge_config::Effort::Max => Effort::Max,
-            }))
+            .map(to_domain_effort))
+    }
+
+    async fn get_session_reasoning_effort(&self) -> anyhow::Result<Option<Effort>> {
+        let config = self.infra.get_config()?;
+        Ok(config.session_reasoning_effort.map(to_domain_effort))
     }
 
     async fn update_config(&self, ops: Vec<ConfigOperation>) -> anyhow::Result<()> {
@@ -96,6 +93,19 @@ impl<F: ProviderRepository + EnvironmentInfra<Config = forge_config::ForgeConfig
     }
 }
 
+/// Converts a configured effort level to its domain counterpart.
+fn to_domain_effort(effort: forge_config::Effort) -> Effort {
+    match effort {
+        forge_config::Effort::None => Effort::None,
+        forge_config::Effort::Minimal => Effort::Minimal,
+        forge_config::Effort::Low => Effort::Low,
+        forge_config::Effort::Medium => Effort::Medium,
+        forge_config::Effort::High => Effort::High,
+        forge_config::Effort::XHigh => Effort::XHigh,
+        forge_config::Effort::Max => Effort::Max,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -223,7 +233,11 @@ mod tests {
                                 mc.model.to_string(),
                             ));
                         }
-                        ConfigOperation::SetReasoningEffort(_) => {
+                        ConfigOperation::SetReasoningEffort(_)
+                        | ConfigOperation::SetSessionReasoningEffort(_)
+                        | ConfigOperation::SetStorageScope(_)
+                        | ConfigOperation::SetProfile(_)
+                        | ConfigOperation::SetSchedule(..) => {
                             // No-op in tests
                         }
                     }
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use forge_app::domain::{
-    Attachment, AttachmentContent, DirectoryEntry, FileTag, Image, LineNumbers,
+    Attachment, AttachmentContent, DirectoryEntry, FileTag, Image, LineNumbers, SecretFiles,
 };
 use forge_app::utils::format_display_path;
 use forge_app::{
@@ -40,6 +40,13 @@ impl<
         if !path.is_absolute() {
             path = self.infra.get_environment().cwd.join(path);
         }
+        let secret_files = SecretFiles::new(&self.infra.get_config()?.secret_files)?;
+        if secret_files.is_secret(&path) {
+            anyhow::bail!(
+                "{} may contain secrets and was not attached. Ask the agent to read it to share it",
+                path.display()
+            );
+        }
 
         // Check if path is a directory (exists but is not a file)
         if self.infra.exists(&path).await? && !self.infra.is_file(&path).await? {
@@ -50,6 +57,7 @@ impl<
             // Create DirectoryEntry for each entry
             let mut entries: Vec<DirectoryEntry> = dir_entries
                 .into_iter()
+                .filter(|(entry_path, is_dir)| *is_dir || !secret_files.is_secret(entry_path))
                 .map(|(entry_path, is_dir)| {
                     let normalized_path = format_display_path(&entry_path, &path);
                     DirectoryEntry { path: normalized_path, is_dir }
@@ -169,7 +177,11 @@ pub mod tests {
         }
 
         fn get_config(&self) -> anyhow::Result<forge_config::ForgeConfig> {
-            Ok(forge_config::ForgeConfig { max_read_lines: 2000, ..Default::default() })
+            Ok(forge_config::ForgeConfig {
+                max_read_lines: 2000,
+                secret_files: vec![".env".to_string()],
+                ..Default::default()
+            })
         }
 
         async fn update_environment(&self, _ops: Vec<ConfigOperation>) -> anyhow::Result<()> {
@@ -1088,6 +1100,37 @@ pub mod tests {
         assert_eq!(attachment.path, "/test/mydir");
     }
 
+    #[tokio::test]
+    async fn test_add_url_leaves_out_secret_files() {
+        let infra = Arc::new(MockCompositeService::new());
+        infra.file_service.add_dir(PathBuf::from("/test/app"));
+        infra.add_file(
+            PathBuf::from("/test/app/.env"),
+            "API_KEY=secret".to_string(),
+        );
+        infra.add_file(
+            PathBuf::from("/test/app/main.rs"),
+            "fn main() {}".to_string(),
+        );
+        let chat_request = ForgeChatRequest::new(infra.clone());
+
+        let listing = chat_request.attachments("@[/test/app]").await.unwrap();
+        let secret = chat_request.attachments("@[/test/app/.env]").await;
+
+        let actual = (
+            match &listing[0].content {
+                AttachmentContent::DirectoryListing { entries } => entries
+                    .iter()
+                    .map(|entry| entry.path.clone())
+                    .collect::<Vec<_>>(),
+                _ => panic!("Expected DirectoryListing attachment"),
+            },
+            secret.is_err(),
+        );
+        let expected = (vec!["main.rs".to_string()], true);
+        assert_eq!(actual, expected);
+    }
+
     #[tokio::test]
     async fn test_add_url_with_empty_directory() {
         let infra = Arc::new(MockCompositeService::new());
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use forge_app::domain::Command;
+use forge_app::domain::{Command, CommandArgument};
 use forge_app::{
     DirectoryReaderInfra, EnvironmentInfra, FileInfoInfra, FileReaderInfra, FileWriterInfra,
 };
@@ -143,11 +143,34 @@ fn parse_command_file(content: &str) -> Result<Command> {
         .context("Empty command frontmatter")?
         .prompt(result.content);
 
+    validate_arguments(&command.arguments)?;
+
     Ok(command)
 }
 
+/// Ensures argument names can be referenced from the prompt template and
+/// don't shadow each other or the raw `parameters` variable
+fn validate_arguments(arguments: &[CommandArgument]) -> Result<()> {
+    let mut names = HashSet::new();
+    for argument in arguments {
+        let name = argument.name.as_str();
+        anyhow::ensure!(
+            name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+            "Invalid argument name '{name}': use letters, digits and underscores"
+        );
+        anyhow::ensure!(
+            name != "parameters",
+            "Argument name 'parameters' is reserved for the raw command input"
+        );
+        anyhow::ensure!(names.insert(name), "Duplicate argument '{name}'");
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
+    use forge_app::domain::ArgumentType;
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -178,6 +201,36 @@ mod tests {
         assert!(actual.prompt.as_ref().unwrap().contains("Step 2"));
     }
 
+    #[tokio::test]
+    async fn test_parse_command_with_arguments() {
+        let content = forge_test_kit::fixture!("src/fixtures/commands/arguments.md").await;
+
+        let actual = parse_command_file(&content).unwrap().arguments;
+        let expected = vec![
+            CommandArgument::default()
+                .name("target")
+                .description("File or directory to review")
+                .required(true),
+            CommandArgument::default()
+                .name("depth")
+                .kind(ArgumentType::Number)
+                .default_value(serde_json::json!(2)),
+            CommandArgument::default()
+                .name("tone")
+                .values(vec!["friendly".to_string(), "strict".to_string()]),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_command_with_reserved_argument_name() {
+        let content = "---\nname: broken\narguments:\n  - name: parameters\n---\n\n{{parameters}}";
+
+        let actual = parse_command_file(content).unwrap_err().to_string();
+        let expected = "Argument name 'parameters' is reserved for the raw command input";
+        assert_eq!(actual, expected);
+    }
+
     #[tokio::test]
     async fn test_parse_invalid_frontmatter() {
         let content = forge_test_kit::fixture!("src/fixtures/commands/invalid.md").await;
@@ -6,8 +6,8 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use forge_app::{CommandInfra, EnvironmentInfra, FileReaderInfra, WalkerInfra, WorkspaceService};
 use forge_domain::{
-    AuthCredential, AuthDetails, ProviderId, ProviderRepository, SyncProgress, UserId, WorkspaceId,
-    WorkspaceIndexRepository,
+    AuthCredential, AuthDetails, ProviderId, ProviderRepository, SecretFiles, SyncProgress, UserId,
+    WorkspaceId, WorkspaceIndexRepository,
 };
 use forge_stream::MpscStream;
 use futures::future::join_all;
@@ -64,7 +64,9 @@ impl<
         emit(SyncProgress::Starting).await;
 
         let (token, user_id) = self.get_workspace_credentials().await?;
-        let batch_size = self.infra.get_config()?.max_file_read_batch_size;
+        let config = self.infra.get_config()?;
+        let batch_size = config.max_file_read_batch_size;
+        let secret_files = SecretFiles::new(&config.secret_files)?;
         let path = canonicalize_path(path)?;
 
         // Find existing workspace - do NOT auto-create
@@ -85,6 +87,7 @@ impl<
             token,
             batch_size,
         )
+        .with_secret_files(secret_files)
         .run(emit)
         .await
     }
@@ -359,7 +362,9 @@ impl<
         // sync), avoiding a redundant canonicalize() IO call.
         let canonical_path = PathBuf::from(&workspace.working_dir);
 
-        let batch_size = self.infra.get_config()?.max_file_read_batch_size;
+        let config = self.infra.get_config()?;
+        let batch_size = config.max_file_read_batch_size;
+        let secret_files = SecretFiles::new(&config.secret_files)?;
 
         WorkspaceSyncEngine::new(
             Arc::clone(&self.infra),
@@ -370,6 +375,7 @@ impl<
             token,
             batch_size,
         )
+        .with_secret_files(secret_files)
         .compute_status()
         .await
     }
@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use forge_app::ConversationService;
-use forge_app::domain::{Conversation, ConversationId};
+use forge_app::domain::{
+    Conversation, ConversationId, ConversationLockHolder, PruneReport, RetentionPolicy,
+};
 use forge_domain::ConversationRepository;
 
 /// Service for managing conversations, including creation, retrieval, and
@@ -66,4 +68,39 @@ impl<S: ConversationRepository> ConversationService for ForgeConversationService
             .delete_conversation(conversation_id)
             .await
     }
+
+    async fn prune_conversations(
+        &self,
+        policy: &RetentionPolicy,
+        keep: Option<&ConversationId>,
+        dry_run: bool,
+    ) -> Result<PruneReport> {
+        let mut summaries = self
+            .conversation_repository
+            .get_conversation_summaries()
+            .await?;
+        summaries.retain(|summary| Some(&summary.id) != keep);
+        let pruned = policy.select(summaries, chrono::Utc::now());
+
+        if !dry_run {
+            for conversation in &pruned {
+                self.conversation_repository
+                    .delete_conversation(&conversation.id)
+                    .await?;
+            }
+        }
+
+        Ok(PruneReport { pruned, dry_run })
+    }
+
+    async fn lock_conversation(
+        &self,
+        id: &ConversationId,
+    ) -> Result<Option<ConversationLockHolder>> {
+        self.conversation_repository.lock_conversation(id).await
+    }
+
+    async fn unlock_conversation(&self, id: &ConversationId) -> Result<()> {
+        self.conversation_repository.unlock_conversation(id).await
+    }
 }
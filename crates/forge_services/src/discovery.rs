@@ -1,18 +1,35 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use anyhow::Result;
-use forge_app::domain::File;
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use forge_app::domain::{File, Project, ProjectKind, RepoMap, TokenCounter};
 use forge_app::{
-    DirectoryReaderInfra, EnvironmentInfra, FileDiscoveryService, Walker, WalkerInfra,
+    DirectoryReaderInfra, EnvironmentInfra, FileDiscoveryService, FileReaderInfra, Walker,
+    WalkerInfra,
 };
+use futures::StreamExt;
+use serde::Deserialize;
 
+use crate::repo_map::{build_repo_map, fit_repo_map};
+
+/// Most files read to build a repository map
+const MAX_REPO_MAP_FILES: usize = 1000;
+
+/// Number of files read concurrently while building a repository map
+const REPO_MAP_BATCH_SIZE: usize = 50;
+
+/// Discovers the files and projects of the workspace. Repository maps are
+/// built once per working directory and cached, then fitted to the token
+/// budget of each request.
 pub struct ForgeDiscoveryService<F> {
     service: Arc<F>,
+    repo_maps: DashMap<PathBuf, Arc<RepoMap>>,
 }
 
 impl<F> ForgeDiscoveryService<F> {
     pub fn new(service: Arc<F>) -> Self {
-        Self { service }
+        Self { service, repo_maps: Default::default() }
     }
 }
 
@@ -26,9 +43,45 @@ impl<F: EnvironmentInfra + WalkerInfra> ForgeDiscoveryService<F> {
     }
 }
 
+impl<F: WalkerInfra + FileReaderInfra> ForgeDiscoveryService<F> {
+    /// Builds the full repository map of the workspace at `cwd`
+    async fn build_repo_map(&self, cwd: &Path) -> Result<RepoMap> {
+        let walker = Walker {
+            max_breadth: None,
+            max_files: Some(MAX_REPO_MAP_FILES),
+            ..Walker::conservative()
+        }
+        .cwd(cwd.to_path_buf());
+        let paths = self
+            .service
+            .walk(walker)
+            .await?
+            .into_iter()
+            .filter(|file| !file.is_dir())
+            .map(|file| cwd.join(file.path))
+            .collect();
+
+        // Files that aren't valid UTF-8 are left out of the map
+        let files = self
+            .service
+            .read_batch_utf8(REPO_MAP_BATCH_SIZE, paths)
+            .filter_map(|(path, content)| {
+                let path = path
+                    .strip_prefix(cwd)
+                    .map(|path| path.display().to_string())
+                    .ok();
+                async move { path.zip(content.ok()) }
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(build_repo_map(&files))
+    }
+}
+
 #[async_trait::async_trait]
-impl<F: EnvironmentInfra + WalkerInfra + DirectoryReaderInfra + Send + Sync> FileDiscoveryService
-    for ForgeDiscoveryService<F>
+impl<F: EnvironmentInfra + WalkerInfra + DirectoryReaderInfra + FileReaderInfra + Send + Sync>
+    FileDiscoveryService for ForgeDiscoveryService<F>
 {
     async fn collect_files(&self, config: Walker) -> Result<Vec<File>> {
         self.discover_with_config(config).await
@@ -56,6 +109,187 @@ impl<F: EnvironmentInfra + WalkerInfra + DirectoryReaderInfra + Send + Sync> Fil
 
         Ok(files)
     }
+
+    async fn list_projects(&self) -> Result<Vec<Project>> {
+        let cwd = self.service.get_environment().cwd;
+        tokio::task::spawn_blocking(move || detect_projects(&cwd)).await?
+    }
+
+    async fn repo_map(&self, max_tokens: usize, counter: &dyn TokenCounter) -> Result<RepoMap> {
+        let cwd = self.service.get_environment().cwd;
+        let cached = self.repo_maps.get(&cwd).map(|entry| entry.value().clone());
+        let repo_map = match cached {
+            Some(repo_map) => repo_map,
+            None => {
+                let repo_map = Arc::new(self.build_repo_map(&cwd).await?);
+                self.repo_maps.insert(cwd, repo_map.clone());
+                repo_map
+            }
+        };
+        Ok(fit_repo_map(&repo_map, max_tokens, counter))
+    }
+}
+
+#[derive(Deserialize)]
+struct CargoManifest {
+    package: Option<NamedPackage>,
+    workspace: Option<CargoWorkspace>,
+}
+
+#[derive(Deserialize)]
+struct CargoWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct NamedPackage {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    name: Option<String>,
+    workspaces: Option<NpmWorkspaces>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NpmWorkspaces {
+    Packages(Vec<String>),
+    Config {
+        #[serde(default)]
+        packages: Vec<String>,
+    },
+}
+
+#[derive(Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+/// Detects the subprojects declared by the cargo, npm, pnpm and bazel
+/// workspaces at `root`. Bazel has no member list, so its projects are the
+/// top-level directories holding a `BUILD` file.
+fn detect_projects(root: &Path) -> Result<Vec<Project>> {
+    let mut projects = Vec::new();
+
+    if let Some(manifest) = read_manifest(&root.join("Cargo.toml"), |content| {
+        toml_edit::de::from_str::<CargoManifest>(content).map_err(Into::into)
+    })? && let Some(workspace) = manifest.workspace
+    {
+        let patterns = workspace
+            .members
+            .into_iter()
+            .chain(workspace.exclude.into_iter().map(|path| format!("!{path}")))
+            .collect::<Vec<_>>();
+        for path in expand_members(root, &patterns, "Cargo.toml")? {
+            let name = read_manifest(&root.join(&path).join("Cargo.toml"), |content| {
+                toml_edit::de::from_str::<CargoManifest>(content).map_err(Into::into)
+            })?
+            .and_then(|manifest| manifest.package?.name);
+            projects.push(project(name, path, ProjectKind::Cargo));
+        }
+    }
+
+    let npm = read_manifest(&root.join("package.json"), |content| {
+        serde_json::from_str::<PackageJson>(content).map_err(Into::into)
+    })?
+    .and_then(|package| package.workspaces)
+    .map(|workspaces| match workspaces {
+        NpmWorkspaces::Packages(packages) | NpmWorkspaces::Config { packages } => packages,
+    })
+    .map(|packages| (ProjectKind::Npm, packages));
+    let pnpm = read_manifest(&root.join("pnpm-workspace.yaml"), |content| {
+        serde_yml::from_str::<PnpmWorkspace>(content).map_err(Into::into)
+    })?
+    .map(|workspace| (ProjectKind::Pnpm, workspace.packages));
+    for (kind, patterns) in npm.into_iter().chain(pnpm) {
+        for path in expand_members(root, &patterns, "package.json")? {
+            let name = read_manifest(&root.join(&path).join("package.json"), |content| {
+                serde_json::from_str::<PackageJson>(content).map_err(Into::into)
+            })?
+            .and_then(|package| package.name);
+            projects.push(project(name, path, kind));
+        }
+    }
+
+    let is_bazel = ["MODULE.bazel", "WORKSPACE", "WORKSPACE.bazel"]
+        .iter()
+        .any(|marker| root.join(marker).is_file());
+    if is_bazel {
+        for entry in std::fs::read_dir(root)? {
+            let path = entry?.path();
+            let is_package = ["BUILD", "BUILD.bazel"]
+                .iter()
+                .any(|build| path.join(build).is_file());
+            if let Some(dir) = path.file_name().filter(|_| is_package) {
+                let dir = PathBuf::from(dir);
+                projects.push(project(
+                    Some(format!("//{}", dir.display())),
+                    dir,
+                    ProjectKind::Bazel,
+                ));
+            }
+        }
+    }
+
+    projects.sort_by(|a, b| (&a.path, a.kind).cmp(&(&b.path, b.kind)));
+    projects.dedup_by(|a, b| a.path == b.path && a.kind == b.kind);
+    Ok(projects)
+}
+
+/// Names a project after its directory when its manifest has no name
+fn project(name: Option<String>, path: PathBuf, kind: ProjectKind) -> Project {
+    let name = name.unwrap_or_else(|| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string())
+    });
+    Project::new(name, path, kind)
+}
+
+/// Parses the manifest at `path`, or returns `None` when there is none
+fn read_manifest<T>(path: &Path, parse: impl Fn(&str) -> Result<T>) -> Result<Option<T>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse(&content)
+        .map(Some)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Expands the member globs of a workspace into the directories, relative to
+/// `root`, holding a `manifest`. Globs starting with `!` exclude directories.
+fn expand_members(root: &Path, patterns: &[String], manifest: &str) -> Result<Vec<PathBuf>> {
+    let exclude = patterns
+        .iter()
+        .filter_map(|pattern| pattern.strip_prefix('!'))
+        .map(|pattern| glob::Pattern::new(pattern.trim_end_matches('/')))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut members = Vec::new();
+    for pattern in patterns.iter().filter(|pattern| !pattern.starts_with('!')) {
+        let pattern = root.join(pattern.trim_end_matches('/'));
+        for path in glob::glob(&pattern.to_string_lossy())?.flatten() {
+            let Ok(path) = path.strip_prefix(root).map(Path::to_path_buf) else {
+                continue;
+            };
+            let skipped = path
+                .components()
+                .any(|part| part.as_os_str() == "node_modules")
+                || exclude.iter().any(|exclude| exclude.matches_path(&path));
+            if !skipped && root.join(&path).join(manifest).is_file() {
+                members.push(path);
+            }
+        }
+    }
+    Ok(members)
 }
 
 #[cfg(test)]
@@ -121,6 +355,34 @@ mod tests {
         }
     }
 
+    #[async_trait::async_trait]
+    impl FileReaderInfra for MockInfra {
+        async fn read_utf8(&self, _path: &std::path::Path) -> Result<String> {
+            unimplemented!()
+        }
+
+        fn read_batch_utf8(
+            &self,
+            _batch_size: usize,
+            _paths: Vec<PathBuf>,
+        ) -> impl futures::Stream<Item = (PathBuf, Result<String>)> + Send {
+            futures::stream::empty()
+        }
+
+        async fn read(&self, _path: &std::path::Path) -> Result<Vec<u8>> {
+            unimplemented!()
+        }
+
+        async fn range_read_utf8(
+            &self,
+            _path: &std::path::Path,
+            _start_line: u64,
+            _end_line: u64,
+        ) -> Result<(String, forge_app::domain::FileInfo)> {
+            unimplemented!()
+        }
+    }
+
     #[async_trait::async_trait]
     impl DirectoryReaderInfra for MockInfra {
         async fn list_directory_entries(
@@ -268,4 +530,40 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_detect_projects() {
+        let fixture = tempfile::tempdir().unwrap();
+        let files = [
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/legacy\"]\n",
+            ),
+            ("crates/core/Cargo.toml", "[package]\nname = \"app_core\"\n"),
+            ("crates/legacy/Cargo.toml", "[package]\nname = \"legacy\"\n"),
+            ("crates/notes/README.md", "Not a crate"),
+            ("package.json", r#"{"workspaces": {"packages": ["web/*"]}}"#),
+            ("web/site/package.json", r#"{"name": "@acme/site"}"#),
+            ("web/node_modules/package.json", "{}"),
+            ("pnpm-workspace.yaml", "packages:\n  - tools/*\n"),
+            ("tools/lint/package.json", "{}"),
+            ("MODULE.bazel", ""),
+            ("services/BUILD.bazel", ""),
+        ];
+        for (path, content) in files {
+            let path = fixture.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let actual = detect_projects(fixture.path()).unwrap();
+
+        let expected = vec![
+            Project::new("app_core", "crates/core", ProjectKind::Cargo),
+            Project::new("//services", "services", ProjectKind::Bazel),
+            Project::new("lint", "tools/lint", ProjectKind::Pnpm),
+            Project::new("@acme/site", "web/site", ProjectKind::Npm),
+        ];
+        assert_eq!(actual, expected);
+    }
 }
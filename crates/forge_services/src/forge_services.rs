@@ -19,11 +19,15 @@ use crate::command::CommandLoaderService as ForgeCommandLoaderService;
 use crate::conversation::ForgeConversationService;
 use crate::discovery::ForgeDiscoveryService;
 use crate::fd::FdDefault;
+use crate::git_state::ForgeGitStateService;
 use crate::instructions::ForgeCustomInstructionsService;
 use crate::mcp::{ForgeMcpManager, ForgeMcpService};
 use crate::policy::ForgePolicyService;
 use crate::provider_service::ForgeProviderService;
+use crate::pull_request::ForgePullRequestService;
+use crate::snapshot::ForgeSnapshotService;
 use crate::template::ForgeTemplateService;
+use crate::token_counter::ForgeTokenCounter;
 use crate::tool_services::{
     ForgeFetch, ForgeFollowup, ForgeFsPatch, ForgeFsRead, ForgeFsRemove, ForgeFsSearch,
     ForgeFsUndo, ForgeFsWrite, ForgeImageRead, ForgePlanCreate, ForgeShell, ForgeSkillFetch,
@@ -70,18 +74,22 @@ pub struct ForgeServices<
     file_remove_service: Arc<ForgeFsRemove<F>>,
     file_patch_service: Arc<ForgeFsPatch<F>>,
     file_undo_service: Arc<ForgeFsUndo<F>>,
+    file_snapshot_service: Arc<ForgeSnapshotService<F>>,
     shell_service: Arc<ForgeShell<F>>,
     fetch_service: Arc<ForgeFetch>,
     followup_service: Arc<ForgeFollowup<F>>,
     mcp_service: Arc<McpService<F>>,
     custom_instructions_service: Arc<ForgeCustomInstructionsService<F>>,
     auth_service: Arc<AuthService<F>>,
+    pull_request_service: Arc<ForgePullRequestService<F>>,
+    git_state_service: Arc<ForgeGitStateService<F>>,
     agent_registry_service: Arc<ForgeAgentRegistryService<F>>,
     command_loader_service: Arc<ForgeCommandLoaderService<F>>,
     policy_service: ForgePolicyService<F>,
     provider_auth_service: ForgeProviderAuthService<F>,
     workspace_service: Arc<crate::context_engine::ForgeWorkspaceService<F, FdDefault<F>>>,
     skill_service: Arc<ForgeSkillFetch<F>>,
+    token_counter: Arc<ForgeTokenCounter<F>>,
     infra: Arc<F>,
 }
 
@@ -115,6 +123,8 @@ impl<
         let suggestion_service = Arc::new(ForgeDiscoveryService::new(infra.clone()));
         let conversation_service = Arc::new(ForgeConversationService::new(infra.clone()));
         let auth_service = Arc::new(ForgeAuthService::new(infra.clone()));
+        let pull_request_service = Arc::new(ForgePullRequestService::new(infra.clone()));
+        let git_state_service = Arc::new(ForgeGitStateService::new(infra.clone()));
         let chat_service = Arc::new(ForgeProviderService::new(infra.clone()));
         let config_service = Arc::new(ForgeAppConfigService::new(infra.clone()));
         let file_create_service = Arc::new(ForgeFsWrite::new(infra.clone()));
@@ -125,6 +135,7 @@ impl<
         let file_remove_service = Arc::new(ForgeFsRemove::new(infra.clone()));
         let file_patch_service = Arc::new(ForgeFsPatch::new(infra.clone()));
         let file_undo_service = Arc::new(ForgeFsUndo::new(infra.clone()));
+        let file_snapshot_service = Arc::new(ForgeSnapshotService::new(infra.clone()));
         let shell_service = Arc::new(ForgeShell::new(infra.clone()));
         let fetch_service = Arc::new(ForgeFetch::new());
         let followup_service = Arc::new(ForgeFollowup::new(infra.clone()));
@@ -140,6 +151,7 @@ impl<
             discovery,
         ));
         let skill_service = Arc::new(ForgeSkillFetch::new(infra.clone()));
+        let token_counter = Arc::new(ForgeTokenCounter::new(infra.clone()));
 
         Self {
             conversation_service,
@@ -155,12 +167,15 @@ impl<
             file_remove_service,
             file_patch_service,
             file_undo_service,
+            file_snapshot_service,
             shell_service,
             fetch_service,
             followup_service,
             mcp_service,
             custom_instructions_service,
             auth_service,
+            pull_request_service,
+            git_state_service,
             config_service,
             agent_registry_service,
             command_loader_service,
@@ -168,6 +183,7 @@ impl<
             provider_auth_service,
             workspace_service,
             skill_service,
+            token_counter,
             chat_service,
             infra,
         }
@@ -224,16 +240,20 @@ impl<
     type FsSearchService = ForgeFsSearch<F>;
     type FollowUpService = ForgeFollowup<F>;
     type FsUndoService = ForgeFsUndo<F>;
+    type FileSnapshotService = ForgeSnapshotService<F>;
     type NetFetchService = ForgeFetch;
     type ShellService = ForgeShell<F>;
     type McpService = McpService<F>;
     type AuthService = AuthService<F>;
+    type PullRequestService = ForgePullRequestService<F>;
+    type GitStateService = ForgeGitStateService<F>;
     type AgentRegistry = ForgeAgentRegistryService<F>;
     type CommandLoaderService = ForgeCommandLoaderService<F>;
     type PolicyService = ForgePolicyService<F>;
     type ProviderService = ForgeProviderService<F>;
     type WorkspaceService = crate::context_engine::ForgeWorkspaceService<F, FdDefault<F>>;
     type SkillFetchService = ForgeSkillFetch<F>;
+    type TokenCounterService = ForgeTokenCounter<F>;
 
     fn config_service(&self) -> &Self::AppConfigService {
         &self.config_service
@@ -295,6 +315,10 @@ impl<
         &self.file_undo_service
     }
 
+    fn file_snapshot_service(&self) -> &Self::FileSnapshotService {
+        &self.file_snapshot_service
+    }
+
     fn net_fetch_service(&self) -> &Self::NetFetchService {
         &self.fetch_service
     }
@@ -311,6 +335,14 @@ impl<
         self.auth_service.as_ref()
     }
 
+    fn pull_request_service(&self) -> &Self::PullRequestService {
+        &self.pull_request_service
+    }
+
+    fn git_state_service(&self) -> &Self::GitStateService {
+        &self.git_state_service
+    }
+
     fn agent_registry(&self) -> &Self::AgentRegistry {
         &self.agent_registry_service
     }
@@ -334,6 +366,10 @@ impl<
         &self.skill_service
     }
 
+    fn token_counter_service(&self) -> &Self::TokenCounterService {
+        &self.token_counter
+    }
+
     fn provider_service(&self) -> &Self::ProviderService {
         &self.chat_service
     }
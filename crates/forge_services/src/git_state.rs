@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use forge_app::{CommandInfra, EnvironmentInfra, GitStateService};
+use forge_domain::GitState;
+
+/// Reads the git state of the workspace for prompt templates
+pub struct ForgeGitStateService<I> {
+    infra: Arc<I>,
+}
+
+impl<I> ForgeGitStateService<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: CommandInfra + EnvironmentInfra> GitStateService for ForgeGitStateService<I> {
+    async fn git_state(&self) -> anyhow::Result<Option<GitState>> {
+        let cwd = self.infra.get_environment().cwd;
+        let status = self
+            .infra
+            .execute_command(
+                "git status --porcelain=v2 --branch".to_string(),
+                cwd.clone(),
+                true,
+                None,
+            )
+            .await?;
+        // Not a repository, or git isn't installed
+        if status.exit_code != Some(0) {
+            return Ok(None);
+        }
+
+        let mut state = GitState::from_status(&status.stdout);
+        // Fails on a branch without commits, which leaves the message out
+        let log = self
+            .infra
+            .execute_command("git log -1 --format=%B".to_string(), cwd, true, None)
+            .await?;
+        if log.exit_code == Some(0) {
+            state.last_commit_message =
+                Some(log.stdout.trim().to_string()).filter(|message| !message.is_empty());
+        }
+        Ok(Some(state))
+    }
+}
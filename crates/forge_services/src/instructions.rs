@@ -1,17 +1,27 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use dashmap::DashMap;
 use forge_app::{CommandInfra, CustomInstructionsService, EnvironmentInfra, FileReaderInfra};
+use forge_domain::InstructionFile;
 
-/// This service looks for AGENTS.md files in three locations in order of
-/// priority:
-/// 1. Base path (environment.base_path)
-/// 2. Git root directory (if available)
-/// 3. Current working directory (environment.cwd)
+/// Names of the instruction files looked up in every project directory, in
+/// the order they are applied
+const INSTRUCTION_FILE_NAMES: [&str; 3] = ["CLAUDE.md", "AGENTS.md", "FORGE.md"];
+
+/// This service loads instruction files in order of increasing precedence:
+/// 1. `AGENTS.md` in the base path (environment.base_path)
+/// 2. `CLAUDE.md`, `AGENTS.md` and `FORGE.md` in the git root, then in every
+///    directory below it down to the current working directory
+///
+/// Outside a git repository only the current working directory is searched.
+/// Files nested deeper are applied later so that they can refine the
+/// instructions of their parents. The files found are cached per working
+/// directory.
 #[derive(Clone)]
 pub struct ForgeCustomInstructionsService<F> {
     infra: Arc<F>,
-    cache: tokio::sync::OnceCell<Vec<String>>,
+    cache: Arc<DashMap<PathBuf, Vec<InstructionFile>>>,
 }
 
 impl<F: EnvironmentInfra + FileReaderInfra + CommandInfra> ForgeCustomInstructionsService<F> {
@@ -19,39 +29,32 @@ impl<F: EnvironmentInfra + FileReaderInfra + CommandInfra> ForgeCustomInstructio
         Self { infra, cache: Default::default() }
     }
 
-    async fn discover_agents_files(&self) -> Vec<PathBuf> {
-        let mut paths = Vec::new();
+    async fn discover_agents_files(&self, cwd: &Path) -> Vec<PathBuf> {
         let environment = self.infra.get_environment();
+        let git_root = self.get_git_root(cwd).await;
 
         // Base custom instructions
-        let base_agent_md = environment.global_agentsmd_path();
-        if !paths.contains(&base_agent_md) {
-            paths.push(base_agent_md);
-        }
+        let mut paths = vec![environment.global_agentsmd_path()];
 
-        // Repo custom instructions
-        if let Some(git_root_path) = self.get_git_root().await {
-            let git_agent_md = git_root_path.join("AGENTS.md");
-            if !paths.contains(&git_agent_md) {
-                paths.push(git_agent_md);
+        // Repo and working dir custom instructions
+        for dir in instruction_dirs(git_root.as_deref(), cwd) {
+            for name in INSTRUCTION_FILE_NAMES {
+                let path = dir.join(name);
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
             }
         }
 
-        // Working dir custom instructions
-        let cwd_agent_md = environment.local_agentsmd_path();
-        if !paths.contains(&cwd_agent_md) {
-            paths.push(cwd_agent_md);
-        }
-
         paths
     }
 
-    async fn get_git_root(&self) -> Option<PathBuf> {
+    async fn get_git_root(&self, cwd: &Path) -> Option<PathBuf> {
         let output = self
             .infra
             .execute_command(
                 "git rev-parse --show-toplevel".to_owned(),
-                self.infra.get_environment().cwd,
+                cwd.to_path_buf(),
                 true, // silent mode - don't print git output
                 None, // no environment variables needed for git command
             )
@@ -65,18 +68,41 @@ impl<F: EnvironmentInfra + FileReaderInfra + CommandInfra> ForgeCustomInstructio
         }
     }
 
-    async fn init(&self) -> Vec<String> {
-        let paths = self.discover_agents_files().await;
+    async fn init(&self, cwd: &Path) -> Vec<InstructionFile> {
+        let paths = self.discover_agents_files(cwd).await;
 
-        let mut custom_instructions = Vec::new();
+        let mut files: Vec<InstructionFile> = Vec::new();
 
         for path in paths {
-            if let Ok(content) = self.infra.read_utf8(&path).await {
-                custom_instructions.push(content);
+            let Ok(content) = self.infra.read_utf8(&path).await else {
+                continue;
+            };
+            // Skip empty files and copies such as a CLAUDE.md symlinked to AGENTS.md
+            if content.trim().is_empty() || files.iter().any(|file| file.content == content) {
+                continue;
             }
+            files.push(InstructionFile { path, content });
         }
 
-        custom_instructions
+        files
+    }
+}
+
+/// Directories searched for project instructions, outermost first: the git
+/// root and every directory from it down to `cwd`, or only `cwd` when it is
+/// not inside a git repository
+fn instruction_dirs(git_root: Option<&Path>, cwd: &Path) -> Vec<PathBuf> {
+    match git_root.filter(|root| cwd.starts_with(root)) {
+        Some(root) => {
+            let mut dirs = cwd
+                .ancestors()
+                .take_while(|dir| dir.starts_with(root))
+                .map(Path::to_path_buf)
+                .collect::<Vec<_>>();
+            dirs.reverse();
+            dirs
+        }
+        None => vec![cwd.to_path_buf()],
     }
 }
 
@@ -85,6 +111,179 @@ impl<F: EnvironmentInfra + FileReaderInfra + CommandInfra> CustomInstructionsSer
     for ForgeCustomInstructionsService<F>
 {
     async fn get_custom_instructions(&self) -> Vec<String> {
-        self.cache.get_or_init(|| self.init()).await.clone()
+        self.get_instruction_files()
+            .await
+            .iter()
+            .map(InstructionFile::to_rule)
+            .collect()
+    }
+
+    async fn get_instruction_files(&self) -> Vec<InstructionFile> {
+        let cwd = self.infra.get_environment().cwd;
+        if let Some(files) = self.cache.get(&cwd) {
+            return files.clone();
+        }
+        let files = self.init(&cwd).await;
+        self.cache.insert(cwd, files.clone());
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::Mutex;
+
+    use fake::{Fake, Faker};
+    use forge_domain::{CommandOutput, ConfigOperation, Environment};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Infra outside any git repository whose working directory can change
+    struct MockInfra {
+        cwd: Mutex<PathBuf>,
+        files: HashMap<PathBuf, String>,
+    }
+
+    impl EnvironmentInfra for MockInfra {
+        type Config = forge_config::ForgeConfig;
+
+        fn get_environment(&self) -> Environment {
+            let mut env: Environment = Faker.fake();
+            env.base_path = PathBuf::from("/home/user/.forge");
+            env.cwd = self.cwd.lock().unwrap().clone();
+            env
+        }
+
+        fn get_config(&self) -> anyhow::Result<forge_config::ForgeConfig> {
+            Ok(forge_config::ForgeConfig::default())
+        }
+
+        async fn update_environment(&self, _ops: Vec<ConfigOperation>) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+
+        fn get_env_var(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_env_vars(&self) -> BTreeMap<String, String> {
+            BTreeMap::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileReaderInfra for MockInfra {
+        async fn read_utf8(&self, path: &Path) -> anyhow::Result<String> {
+            self.files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("File not found: {}", path.display()))
+        }
+
+        fn read_batch_utf8(
+            &self,
+            _batch_size: usize,
+            _paths: Vec<PathBuf>,
+        ) -> impl futures::Stream<Item = (PathBuf, anyhow::Result<String>)> + Send {
+            futures::stream::empty()
+        }
+
+        async fn read(&self, _path: &Path) -> anyhow::Result<Vec<u8>> {
+            unimplemented!()
+        }
+
+        async fn range_read_utf8(
+            &self,
+            _path: &Path,
+            _start_line: u64,
+            _end_line: u64,
+        ) -> anyhow::Result<(String, forge_domain::FileInfo)> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CommandInfra for MockInfra {
+        async fn execute_command(
+            &self,
+            command: String,
+            _working_dir: PathBuf,
+            _silent: bool,
+            _env_vars: Option<Vec<String>>,
+        ) -> anyhow::Result<CommandOutput> {
+            Ok(CommandOutput {
+                stdout: String::new(),
+                stderr: "fatal: not a git repository".to_string(),
+                command,
+                exit_code: Some(128),
+            })
+        }
+
+        async fn execute_command_raw(
+            &self,
+            _command: &str,
+            _working_dir: PathBuf,
+            _env_vars: Option<Vec<String>>,
+        ) -> anyhow::Result<std::process::ExitStatus> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_instruction_files_follow_cwd() {
+        let infra = Arc::new(MockInfra {
+            cwd: Mutex::new(PathBuf::from("/first")),
+            files: HashMap::from([
+                (PathBuf::from("/first/AGENTS.md"), "first".to_string()),
+                (PathBuf::from("/second/AGENTS.md"), "second".to_string()),
+            ]),
+        });
+        let fixture = ForgeCustomInstructionsService::new(infra.clone());
+
+        let first = fixture.get_instruction_files().await;
+        *infra.cwd.lock().unwrap() = PathBuf::from("/second");
+        let second = fixture.get_instruction_files().await;
+
+        let actual = (first, second);
+        let expected = (
+            vec![InstructionFile {
+                path: PathBuf::from("/first/AGENTS.md"),
+                content: "first".to_string(),
+            }],
+            vec![InstructionFile {
+                path: PathBuf::from("/second/AGENTS.md"),
+                content: "second".to_string(),
+            }],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_instruction_dirs_walks_from_git_root_to_cwd() {
+        let actual = instruction_dirs(
+            Some(Path::new("/repo")),
+            Path::new("/repo/crates/forge_main"),
+        );
+        let expected = vec![
+            PathBuf::from("/repo"),
+            PathBuf::from("/repo/crates"),
+            PathBuf::from("/repo/crates/forge_main"),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_instruction_dirs_without_git_root() {
+        let actual = (
+            instruction_dirs(None, Path::new("/tmp/project")),
+            instruction_dirs(Some(Path::new("/repo")), Path::new("/tmp/project")),
+        );
+        let expected = (
+            vec![PathBuf::from("/tmp/project")],
+            vec![PathBuf::from("/tmp/project")],
+        );
+        assert_eq!(actual, expected);
     }
 }
@@ -2,7 +2,6 @@ mod agent_registry;
 mod app_config;
 mod attachment;
 mod auth;
-mod clipper;
 mod command;
 mod context_engine;
 mod conversation;
@@ -12,20 +11,25 @@ mod fd;
 mod fd_git;
 mod fd_walker;
 mod forge_services;
+mod git_state;
 mod instructions;
 mod mcp;
+mod outline;
 mod policy;
 
 mod provider_auth;
 mod provider_service;
+mod pull_request;
 mod range;
+mod repo_map;
+mod snapshot;
 mod sync;
 mod template;
+mod token_counter;
 mod tool_services;
 mod utils;
 
 pub use app_config::*;
-pub use clipper::*;
 pub use command::*;
 pub use context_engine::*;
 pub use discovery::*;
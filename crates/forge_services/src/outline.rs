@@ -0,0 +1,101 @@
+/// Keywords starting a declaration in common languages
+const DECLARATION_KEYWORDS: [&str; 14] = [
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "impl",
+    "mod",
+    "type",
+    "macro_rules",
+    "class",
+    "interface",
+    "def",
+    "function",
+    "func",
+    "module",
+];
+
+/// Keywords that can precede the declaration keyword
+const MODIFIERS: [&str; 14] = [
+    "pub",
+    "pub(crate)",
+    "pub(super)",
+    "async",
+    "unsafe",
+    "const",
+    "extern",
+    "export",
+    "default",
+    "abstract",
+    "public",
+    "private",
+    "protected",
+    "static",
+];
+
+/// Returns the lines of `content` declaring functions, types and other
+/// symbols, each with its line number starting at 1. Indentation is kept so
+/// that methods show under their type.
+pub fn outline(content: &str) -> Vec<(usize, &str)> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| is_declaration(line))
+        .map(|(index, line)| (index + 1, line.trim_end()))
+        .collect()
+}
+
+fn is_declaration(line: &str) -> bool {
+    let keyword = line
+        .split_whitespace()
+        .find(|word| !MODIFIERS.contains(word));
+    keyword.is_some_and(|word| {
+        // Declarations may be followed directly by generics or a name
+        let word = word
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or_default();
+        DECLARATION_KEYWORDS.contains(&word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_outline_keeps_declarations() {
+        let fixture = r#"use std::fmt;
+
+/// A point
+pub struct Point {
+    x: i32,
+}
+
+impl<T> Display for Wrapper<T> {
+    pub(crate) async fn render(&self) -> String {
+        let fn_name = "render";
+        format!("{fn_name}")
+    }
+}
+
+class Shape:
+    def area(self):
+        return 0
+"#;
+
+        let actual = outline(fixture);
+
+        let expected = vec![
+            (4, "pub struct Point {"),
+            (8, "impl<T> Display for Wrapper<T> {"),
+            (9, "    pub(crate) async fn render(&self) -> String {"),
+            (15, "class Shape:"),
+            (16, "    def area(self):"),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -12,6 +12,7 @@ use forge_app::{
     PolicyDecision, PolicyService, UserInfra,
 };
 use strum_macros::{Display, EnumIter};
+use tokio::sync::RwLock;
 
 /// User response for permission confirmation requests
 #[derive(Debug, Clone, PartialEq, Eq, Display, EnumIter, strum_macros::EnumString)]
@@ -30,6 +31,9 @@ pub enum PolicyPermission {
 #[derive(Clone)]
 pub struct ForgePolicyService<I> {
     infra: Arc<I>,
+    /// Policies read from the policies file, kept up to date with the changes
+    /// this service writes
+    policies: Arc<RwLock<Option<PolicyConfig>>>,
 }
 /// Default policies loaded once at startup from the embedded YAML file
 static DEFAULT_POLICIES: LazyLock<PolicyConfig> = LazyLock::new(|| {
@@ -44,7 +48,7 @@ where
     I: FileReaderInfra + FileWriterInfra + FileInfoInfra + EnvironmentInfra + DirectoryReaderInfra,
 {
     pub fn new(infra: Arc<I>) -> Self {
-        Self { infra }
+        Self { infra, policies: Arc::new(RwLock::new(None)) }
     }
 
     fn permissions_path(&self) -> PathBuf {
@@ -74,17 +78,23 @@ where
         }
     }
 
-    /// Load all policy definitions from the forge/policies directory
+    /// Load all policy definitions from the forge/policies directory. The file
+    /// is read once and then served from memory.
     async fn read_policies(&self) -> anyhow::Result<Option<PolicyConfig>> {
+        if let Some(policies) = self.policies.read().await.as_ref() {
+            return Ok(Some(policies.clone()));
+        }
+
         let policies_path = self.permissions_path();
         if !self.infra.exists(&policies_path).await? {
             return Ok(None);
         }
 
         let content = self.infra.read_utf8(&policies_path).await?;
-        let policies = serde_yml::from_str(&content)
+        let policies: PolicyConfig = serde_yml::from_str(&content)
             .with_context(|| format!("Failed to parse policy {}", policies_path.display()))?;
 
+        *self.policies.write().await = Some(policies.clone());
         Ok(Some(policies))
     }
 
@@ -104,6 +114,7 @@ where
         self.infra
             .write(&policies_path, Bytes::from(new_content.to_owned()))
             .await?;
+        *self.policies.write().await = Some(policies);
 
         Ok(())
     }
@@ -126,6 +137,7 @@ where
         self.infra
             .write(&policies_path, Bytes::from(content))
             .await?;
+        *self.policies.write().await = Some(default_policies);
 
         Ok(())
     }
@@ -204,6 +216,17 @@ where
             }
         }
     }
+
+    async fn requires_confirmation(&self, operation: &PermissionOperation) -> anyhow::Result<bool> {
+        // Falls back to the defaults without creating the policies file, which is
+        // created and reported by `check_operation_permission`
+        let policies = self
+            .read_policies()
+            .await?
+            .unwrap_or_else(Self::load_default_policies);
+        let engine = PolicyEngine::new(&policies);
+        Ok(matches!(engine.can_perform(operation), Permission::Confirm))
+    }
 }
 
 /// Create a policy for an operation based on its type
@@ -8,7 +8,7 @@ use forge_app::domain::{
 };
 use forge_domain::{
     AuthCredential, ChatRepository, Context, MigrationResult, ModelSource, Provider,
-    ProviderRepository, ProviderTemplate,
+    ProviderRepository, ProviderTemplate, ResponseCacheInfo,
 };
 use url::Url;
 
@@ -132,6 +132,14 @@ impl<R: ChatRepository + ProviderRepository> ProviderService for ForgeProviderSe
     async fn migrate_env_credentials(&self) -> Result<Option<MigrationResult>> {
         self.repository.migrate_env_credentials().await
     }
+
+    async fn response_cache_info(&self) -> Result<ResponseCacheInfo> {
+        self.repository.response_cache_info().await
+    }
+
+    async fn clear_response_cache(&self) -> Result<usize> {
+        self.repository.clear_response_cache().await
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use forge_app::{CommandInfra, EnvironmentInfra, HttpInfra, PullRequestService};
+use forge_domain::{CodeHost, PullRequestDraft, RemoteRepository};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue, USER_AGENT};
+use serde::Deserialize;
+use url::Url;
+
+/// Pull request as returned by the GitHub and GitLab APIs
+#[derive(Deserialize)]
+struct CreatedPullRequest {
+    #[serde(alias = "web_url")]
+    html_url: Url,
+}
+
+/// Opens pull requests through the GitHub and GitLab REST APIs
+pub struct ForgePullRequestService<I> {
+    infra: Arc<I>,
+}
+
+impl<I: HttpInfra + EnvironmentInfra + CommandInfra> ForgePullRequestService<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+
+    /// Finds the access token for `repository`: the token environment
+    /// variable of the public host first, then the credentials git stores for
+    /// its domain
+    async fn token(&self, repository: &RemoteRepository) -> anyhow::Result<String> {
+        let variables = token_variables(repository);
+        if let Some(token) = variables
+            .iter()
+            .filter_map(|name| self.infra.get_env_var(name))
+            .find(|token| !token.trim().is_empty())
+        {
+            return Ok(token);
+        }
+
+        let domain = repository.domain.replace('\'', r"'\''");
+        let output = self
+            .infra
+            .execute_command(
+                format!(
+                    "printf '%s\\n' protocol=https 'host={domain}' '' | GIT_TERMINAL_PROMPT=0 git credential fill"
+                ),
+                self.infra.get_environment().cwd,
+                true,
+                None,
+            )
+            .await?;
+        output
+            .stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("password="))
+            .filter(|_| output.success())
+            .map(str::to_string)
+            .with_context(|| match variables {
+                [] => format!(
+                    "No {} credentials found for {}. Store them with a git credential helper",
+                    repository.host, repository.domain
+                ),
+                variables => format!(
+                    "No {} credentials found for {}. Set {} or store them with a git credential helper",
+                    repository.host,
+                    repository.domain,
+                    variables.join(" or ")
+                ),
+            })
+    }
+}
+
+/// Environment variables holding a token for `repository`. Only the public
+/// GitHub and GitLab domains read them, so that a token issued for those is
+/// never sent to a self-hosted instance or a look-alike domain.
+fn token_variables(repository: &RemoteRepository) -> &'static [&'static str] {
+    match (repository.host, repository.domain.as_str()) {
+        (CodeHost::GitHub, "github.com") => &["GITHUB_TOKEN", "GH_TOKEN"],
+        (CodeHost::GitLab, "gitlab.com") => &["GITLAB_TOKEN"],
+        _ => &[],
+    }
+}
+
+/// Builds the API endpoint and JSON body creating a pull request
+fn api_request(
+    repository: &RemoteRepository,
+    head: &str,
+    base: &str,
+    draft: &PullRequestDraft,
+) -> anyhow::Result<(Url, serde_json::Value)> {
+    let (url, body) = match repository.host {
+        CodeHost::GitHub => {
+            let api = match repository.domain.as_str() {
+                "github.com" => "https://api.github.com".to_string(),
+                domain => format!("https://{domain}/api/v3"),
+            };
+            (
+                format!("{api}/repos/{}/pulls", repository.path),
+                serde_json::json!({
+                    "title": draft.title,
+                    "body": draft.description,
+                    "head": head,
+                    "base": base,
+                }),
+            )
+        }
+        CodeHost::GitLab => (
+            format!(
+                "https://{}/api/v4/projects/{}/merge_requests",
+                repository.domain,
+                repository.path.replace('/', "%2F")
+            ),
+            serde_json::json!({
+                "title": draft.title,
+                "description": draft.description,
+                "source_branch": head,
+                "target_branch": base,
+            }),
+        ),
+    };
+    Ok((Url::parse(&url)?, body))
+}
+
+#[async_trait::async_trait]
+impl<I: HttpInfra + EnvironmentInfra + CommandInfra> PullRequestService
+    for ForgePullRequestService<I>
+{
+    async fn create_pull_request(
+        &self,
+        repository: &RemoteRepository,
+        head: &str,
+        base: &str,
+        draft: &PullRequestDraft,
+    ) -> anyhow::Result<Url> {
+        let token = self.token(repository).await?;
+        let (url, body) = api_request(repository, head, base, draft)?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}"))?,
+        );
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(USER_AGENT, HeaderValue::from_static("forge"));
+
+        let response = self
+            .infra
+            .http_post(&url, Some(headers), serde_json::to_vec(&body)?.into())
+            .await?;
+        let status = response.status();
+        let content = response.bytes().await?;
+        if !status.is_success() {
+            anyhow::bail!(
+                "{} refused the pull request ({status}): {}",
+                repository.host,
+                String::from_utf8_lossy(&content).trim()
+            );
+        }
+
+        let created: CreatedPullRequest = serde_json::from_slice(&content)
+            .with_context(|| format!("Unexpected response from {}", repository.host))?;
+        Ok(created.html_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_token_variables() {
+        let repository = |host, domain: &str| RemoteRepository {
+            host,
+            domain: domain.to_string(),
+            path: "owner/name".to_string(),
+        };
+        let fixture = [
+            repository(CodeHost::GitHub, "github.com"),
+            repository(CodeHost::GitLab, "gitlab.com"),
+            repository(CodeHost::GitHub, "github.example.com"),
+            repository(CodeHost::GitHub, "github.com.attacker.io"),
+            repository(CodeHost::GitLab, "gitlab.example.com"),
+        ];
+
+        let actual = fixture.each_ref().map(token_variables);
+
+        let expected: [&[&str]; 5] = [
+            &["GITHUB_TOKEN", "GH_TOKEN"],
+            &["GITLAB_TOKEN"],
+            &[],
+            &[],
+            &[],
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_api_request() {
+        let draft = PullRequestDraft::new("Add pull requests", "Opens one");
+        let fixture = [
+            RemoteRepository {
+                host: CodeHost::GitHub,
+                domain: "github.com".to_string(),
+                path: "antinomyhq/forge".to_string(),
+            },
+            RemoteRepository {
+                host: CodeHost::GitLab,
+                domain: "gitlab.example.com".to_string(),
+                path: "group/sub/project".to_string(),
+            },
+        ];
+
+        let actual =
+            fixture.map(|repository| api_request(&repository, "feature", "main", &draft).unwrap());
+
+        let expected = [
+            (
+                Url::parse("https://api.github.com/repos/antinomyhq/forge/pulls").unwrap(),
+                serde_json::json!({
+                    "title": "Add pull requests",
+                    "body": "Opens one",
+                    "head": "feature",
+                    "base": "main",
+                }),
+            ),
+            (
+                Url::parse(
+                    "https://gitlab.example.com/api/v4/projects/group%2Fsub%2Fproject/merge_requests",
+                )
+                .unwrap(),
+                serde_json::json!({
+                    "title": "Add pull requests",
+                    "description": "Opens one",
+                    "source_branch": "feature",
+                    "target_branch": "main",
+                }),
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use forge_domain::{RepoMap, RepoMapEntry, TokenCounter};
+
+use crate::outline::outline;
+
+/// Most declarations listed per file, so large files don't crowd out the rest
+const MAX_SYMBOLS: usize = 20;
+
+/// Keywords starting an import line in common languages
+const IMPORT_KEYWORDS: [&str; 6] = ["use", "mod", "import", "from", "require", "#include"];
+
+/// Keywords that can precede the import keyword
+const MODIFIERS: [&str; 4] = ["pub", "pub(crate)", "pub(super)", "export"];
+
+/// File stems standing for their directory, e.g. `mod.rs` and `index.ts`
+const DIRECTORY_MODULES: [&str; 3] = ["mod", "index", "__init__"];
+
+/// Builds the repository map of `files`, given as paths relative to the
+/// workspace root with their content. Files are ranked by the number of files
+/// importing them, then by the number of symbols they declare.
+pub fn build_repo_map(files: &[(String, String)]) -> RepoMap {
+    let modules = files
+        .iter()
+        .map(|(path, _)| module_name(path))
+        .collect::<Vec<_>>();
+
+    let mut entries = files
+        .iter()
+        .enumerate()
+        .map(|(index, (path, content))| {
+            let imported = imported_names(content);
+            let dependencies = files
+                .iter()
+                .zip(&modules)
+                .enumerate()
+                .filter(|(other, (_, module))| {
+                    *other != index && imported.contains(module.as_str())
+                })
+                .map(|(_, ((path, _), _))| path.clone())
+                .collect();
+            let symbols = outline(content)
+                .into_iter()
+                .take(MAX_SYMBOLS)
+                .map(|(_, line)| line.trim().trim_end_matches('{').trim_end().to_string())
+                .collect();
+            RepoMapEntry { path: path.clone(), symbols, dependencies, dependents: 0 }
+        })
+        .collect::<Vec<_>>();
+
+    let mut dependents = HashMap::<String, usize>::new();
+    for dependency in entries.iter().flat_map(|entry| &entry.dependencies) {
+        *dependents.entry(dependency.clone()).or_default() += 1;
+    }
+    for entry in &mut entries {
+        entry.dependents = dependents.get(&entry.path).copied().unwrap_or_default();
+    }
+
+    entries.retain(|entry| !entry.symbols.is_empty() || entry.dependents > 0);
+    entries.sort_by(|a, b| {
+        b.dependents
+            .cmp(&a.dependents)
+            .then_with(|| b.symbols.len().cmp(&a.symbols.len()))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    RepoMap { entries }
+}
+
+/// Keeps the highest ranked files of `repo_map` that fit in `max_tokens` as
+/// counted by `counter`
+pub fn fit_repo_map(repo_map: &RepoMap, max_tokens: usize, counter: &dyn TokenCounter) -> RepoMap {
+    let mut tokens = 0;
+    let mut entries = repo_map
+        .entries
+        .iter()
+        .take_while(|entry| {
+            tokens += counter.count(&entry.to_string());
+            tokens <= max_tokens
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // Imports of files left out of the map point nowhere
+    let included = entries
+        .iter()
+        .map(|entry| entry.path.clone())
+        .collect::<HashSet<_>>();
+    for entry in &mut entries {
+        entry
+            .dependencies
+            .retain(|dependency| included.contains(dependency));
+    }
+
+    RepoMap { entries }
+}
+
+/// Name other files import a file by: its stem, or the name of its directory
+/// for files standing for it
+fn module_name(path: &str) -> String {
+    let path = Path::new(path);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if DIRECTORY_MODULES.contains(&stem.as_str())
+        && let Some(dir) = path.parent().and_then(Path::file_name)
+    {
+        return dir.to_string_lossy().to_string();
+    }
+    stem
+}
+
+/// Identifiers on the import lines of `content`
+fn imported_names(content: &str) -> BTreeSet<&str> {
+    content
+        .lines()
+        .filter(|line| {
+            let keyword = line
+                .split_whitespace()
+                .find(|word| !MODIFIERS.contains(word));
+            keyword.is_some_and(|word| IMPORT_KEYWORDS.contains(&word))
+                || line.contains("require(")
+                || line.contains(" from ")
+        })
+        .flat_map(|line| line.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-')))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::ApproxTokenCounter;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_build_repo_map_ranks_imported_files_first() {
+        let fixture = [
+            (
+                "src/main.rs",
+                "mod config;\nuse crate::config::Config;\n\nfn main() {}\n",
+            ),
+            (
+                "src/server/mod.rs",
+                "use crate::config::Config;\n\npub struct Server;\n",
+            ),
+            (
+                "src/config.rs",
+                "pub struct Config {\n    port: u16,\n}\n\nimpl Config {\n    pub fn load() -> Self {\n",
+            ),
+            ("web/app.ts", "import { start } from './server';\n"),
+            ("README.md", "# Project\n"),
+        ]
+        .map(|(path, content)| (path.to_string(), content.to_string()));
+
+        let actual = fit_repo_map(&build_repo_map(&fixture), 1000, &ApproxTokenCounter);
+
+        let expected = RepoMap {
+            entries: vec![
+                RepoMapEntry {
+                    path: "src/config.rs".to_string(),
+                    symbols: vec![
+                        "pub struct Config".to_string(),
+                        "impl Config".to_string(),
+                        "pub fn load() -> Self".to_string(),
+                    ],
+                    dependencies: vec![],
+                    dependents: 2,
+                },
+                RepoMapEntry {
+                    path: "src/server/mod.rs".to_string(),
+                    symbols: vec!["pub struct Server;".to_string()],
+                    dependencies: vec!["src/config.rs".to_string()],
+                    dependents: 1,
+                },
+                RepoMapEntry {
+                    path: "src/main.rs".to_string(),
+                    symbols: vec!["mod config;".to_string(), "fn main() {}".to_string()],
+                    dependencies: vec!["src/config.rs".to_string()],
+                    dependents: 0,
+                },
+            ],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_build_repo_map_respects_token_budget() {
+        let fixture = [
+            ("src/a.rs", "fn a() {}\n"),
+            ("src/b.rs", "fn b() {}\nfn c() {}\n"),
+        ]
+        .map(|(path, content)| (path.to_string(), content.to_string()));
+
+        let actual = fit_repo_map(&build_repo_map(&fixture), 10, &ApproxTokenCounter)
+            .entries
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect::<Vec<_>>();
+
+        let expected = vec!["src/b.rs".to_string()];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,190 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use forge_app::FileSnapshotService;
+use forge_display::DiffFormat;
+use forge_domain::{ConversationId, Snapshot, SnapshotRepository, SnapshotTag, UnifiedDiff};
+
+use crate::utils::assert_absolute_path;
+
+/// Compares the snapshots taken of a file before each agent edit
+pub struct ForgeSnapshotService<F> {
+    infra: Arc<F>,
+}
+
+impl<F> ForgeSnapshotService<F> {
+    pub fn new(infra: Arc<F>) -> Self {
+        Self { infra }
+    }
+}
+
+impl<F: SnapshotRepository> ForgeSnapshotService<F> {
+    async fn read(&self, file_path: &Path, index: usize) -> anyhow::Result<String> {
+        let content = self.infra.read_snapshot(file_path, index).await?;
+        String::from_utf8(content).with_context(|| {
+            format!(
+                "Snapshot {index} of {} is not valid UTF-8",
+                file_path.display()
+            )
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<F: SnapshotRepository> FileSnapshotService for ForgeSnapshotService<F> {
+    async fn diff_snapshots(
+        &self,
+        file_path: &Path,
+        from_index: usize,
+        to_index: usize,
+    ) -> anyhow::Result<UnifiedDiff> {
+        assert_absolute_path(file_path)?;
+        let from = self.read(file_path, from_index).await?;
+        let to = self.read(file_path, to_index).await?;
+        let result = DiffFormat::format_highlighted(&from, &to, file_path);
+
+        Ok(UnifiedDiff {
+            path: file_path.to_path_buf(),
+            from_index,
+            to_index,
+            diff: result.diff().to_string(),
+            lines_added: result.lines_added(),
+            lines_removed: result.lines_removed(),
+        })
+    }
+
+    async fn restore_all_at(&self, timestamp: Duration) -> anyhow::Result<Vec<PathBuf>> {
+        self.infra.restore_all_at(timestamp).await
+    }
+
+    async fn tag_snapshots(
+        &self,
+        file_path: &Path,
+        since: Duration,
+        tag: &SnapshotTag,
+    ) -> anyhow::Result<()> {
+        self.infra.tag_snapshots(file_path, since, tag).await
+    }
+
+    async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> anyhow::Result<Vec<Snapshot>> {
+        self.infra
+            .list_snapshots_for_conversation(conversation_id)
+            .await
+    }
+
+    async fn export_bundle(
+        &self,
+        paths: &[PathBuf],
+        root: &Path,
+        output: &Path,
+    ) -> anyhow::Result<usize> {
+        for path in paths {
+            assert_absolute_path(path)?;
+        }
+        assert_absolute_path(root)?;
+        assert_absolute_path(output)?;
+        self.infra.export_bundle(paths, root, output).await
+    }
+
+    async fn import_bundle(&self, bundle: &Path, root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        assert_absolute_path(bundle)?;
+        assert_absolute_path(root)?;
+        self.infra.import_bundle(bundle, root).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    struct MockSnapshots(HashMap<usize, &'static str>);
+
+    #[async_trait::async_trait]
+    impl SnapshotRepository for MockSnapshots {
+        async fn insert_snapshot(&self, _file_path: &Path) -> anyhow::Result<Snapshot> {
+            unimplemented!()
+        }
+
+        async fn undo_snapshot(&self, _file_path: &Path) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+
+        async fn read_snapshot(&self, _file_path: &Path, index: usize) -> anyhow::Result<Vec<u8>> {
+            self.0
+                .get(&index)
+                .map(|content| content.as_bytes().to_vec())
+                .context("No such snapshot")
+        }
+
+        async fn restore_all_at(&self, _timestamp: Duration) -> anyhow::Result<Vec<PathBuf>> {
+            unimplemented!()
+        }
+
+        async fn tag_snapshots(
+            &self,
+            _file_path: &Path,
+            _since: Duration,
+            _tag: &SnapshotTag,
+        ) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+
+        async fn list_snapshots_for_conversation(
+            &self,
+            _conversation_id: &ConversationId,
+        ) -> anyhow::Result<Vec<Snapshot>> {
+            unimplemented!()
+        }
+
+        async fn export_bundle(
+            &self,
+            _paths: &[PathBuf],
+            _root: &Path,
+            _output: &Path,
+        ) -> anyhow::Result<usize> {
+            unimplemented!()
+        }
+
+        async fn import_bundle(
+            &self,
+            _bundle: &Path,
+            _root: &Path,
+        ) -> anyhow::Result<Vec<PathBuf>> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_snapshots() {
+        let fixture = ForgeSnapshotService::new(Arc::new(MockSnapshots(HashMap::from([
+            (0, "first\nsecond\n"),
+            (1, "first\nchanged\nthird\n"),
+        ]))));
+
+        let actual = fixture
+            .diff_snapshots(&PathBuf::from("/project/notes.txt"), 0, 1)
+            .await
+            .unwrap();
+
+        let expected = (PathBuf::from("/project/notes.txt"), 2, 1);
+        assert_eq!(
+            (actual.path, actual.lines_added, actual.lines_removed),
+            expected
+        );
+        assert!(
+            fixture
+                .diff_snapshots(Path::new("/project/notes.txt"), 0, 2)
+                .await
+                .is_err()
+        );
+    }
+}
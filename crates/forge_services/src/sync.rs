@@ -3,7 +3,9 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use forge_app::{FileReaderInfra, SyncProgressCounter, WorkspaceStatus, compute_hash};
-use forge_domain::{ApiKey, FileHash, SyncProgress, UserId, WorkspaceId, WorkspaceIndexRepository};
+use forge_domain::{
+    ApiKey, FileHash, SecretFiles, SyncProgress, UserId, WorkspaceId, WorkspaceIndexRepository,
+};
 use futures::stream::{Stream, StreamExt};
 use tracing::{info, warn};
 
@@ -55,6 +57,7 @@ pub struct WorkspaceSyncEngine<F, D> {
     user_id: UserId,
     token: ApiKey,
     batch_size: usize,
+    secret_files: SecretFiles,
 }
 
 impl<F, D> WorkspaceSyncEngine<F, D> {
@@ -77,8 +80,16 @@ impl<F, D> WorkspaceSyncEngine<F, D> {
             user_id,
             token,
             batch_size,
+            secret_files: SecretFiles::default(),
         }
     }
+
+    /// Keeps files matching `secret_files` out of the workspace, so they are
+    /// never uploaded and are deleted remotely if they were before
+    pub fn with_secret_files(mut self, secret_files: SecretFiles) -> Self {
+        self.secret_files = secret_files;
+        self
+    }
 }
 
 impl<F: 'static + WorkspaceIndexRepository + FileReaderInfra, D: FileDiscovery + 'static>
@@ -324,6 +335,7 @@ impl<F: 'static + WorkspaceIndexRepository + FileReaderInfra, D: FileDiscovery +
         let discovery = self.discovery.clone();
         let workspace_id = self.workspace_id.clone();
         let batch_size = self.batch_size;
+        let secret_files = self.secret_files.clone();
 
         async_stream::stream! {
             let file_paths: Vec<PathBuf> = match discover_sync_file_paths(
@@ -331,7 +343,10 @@ impl<F: 'static + WorkspaceIndexRepository + FileReaderInfra, D: FileDiscovery +
                 &dir_path,
                 &workspace_id,
             ).await {
-                Ok(file_paths) => file_paths,
+                Ok(file_paths) => file_paths
+                    .into_iter()
+                    .filter(|path| !secret_files.is_secret(path))
+                    .collect(),
                 Err(err) => {
                     yield Err(err);
                     return;
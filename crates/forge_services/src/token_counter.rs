@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use forge_app::TokenCounterService;
+use forge_domain::{ChatRepository, Context, ModelId, Provider, TokenCount, Tokenizer};
+use url::Url;
+
+/// Counts tokens with tiktoken, asking the provider for exact counts when it
+/// exposes a token counting API
+pub struct ForgeTokenCounter<F> {
+    infra: Arc<F>,
+}
+
+impl<F> ForgeTokenCounter<F> {
+    pub fn new(infra: Arc<F>) -> Self {
+        Self { infra }
+    }
+}
+
+#[async_trait::async_trait]
+impl<F: ChatRepository> TokenCounterService for ForgeTokenCounter<F> {
+    fn token_counter(&self, model: &ModelId) -> Tokenizer {
+        Tokenizer::for_model(model)
+    }
+
+    async fn count_tokens(
+        &self,
+        context: &Context,
+        model: &ModelId,
+        provider: Option<Provider<Url>>,
+    ) -> TokenCount {
+        if let Some(provider) = provider {
+            match self.infra.count_tokens(model, context, provider).await {
+                Ok(Some(count)) => return TokenCount::Actual(count as usize),
+                Ok(None) => {}
+                Err(error) => {
+                    tracing::warn!(error = ?error, "Failed to count tokens with the provider, using the local tokenizer")
+                }
+            }
+        }
+
+        let tokenizer = Tokenizer::for_model(model);
+        TokenCount::Approx(
+            context
+                .messages
+                .iter()
+                .map(|message| message.token_count_with(&tokenizer))
+                .sum(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{ContextMessage, Model};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    struct MockChatRepository(Option<u64>);
+
+    #[async_trait::async_trait]
+    impl ChatRepository for MockChatRepository {
+        async fn chat(
+            &self,
+            _model_id: &ModelId,
+            _context: Context,
+            _provider: Provider<Url>,
+        ) -> forge_domain::ResultStream<forge_domain::ChatCompletionMessage, anyhow::Error>
+        {
+            unimplemented!()
+        }
+
+        async fn models(&self, _provider: Provider<Url>) -> anyhow::Result<Vec<Model>> {
+            unimplemented!()
+        }
+
+        async fn count_tokens(
+            &self,
+            _model_id: &ModelId,
+            _context: &Context,
+            _provider: Provider<Url>,
+        ) -> anyhow::Result<Option<u64>> {
+            Ok(self.0)
+        }
+    }
+
+    fn provider() -> Provider<Url> {
+        Provider {
+            id: forge_domain::ProviderId::ANTHROPIC,
+            provider_type: Default::default(),
+            response: Some(forge_domain::ProviderResponse::Anthropic),
+            url: Url::parse("https://api.anthropic.com/v1/messages").unwrap(),
+            models: None,
+            auth_methods: vec![],
+            url_params: vec![],
+            credential: None,
+            custom_headers: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_count_tokens_prefers_provider_count() {
+        let fixture = ForgeTokenCounter::new(Arc::new(MockChatRepository(Some(42))));
+        let context = Context::default().add_message(ContextMessage::user("hello world", None));
+
+        let actual = fixture
+            .count_tokens(&context, &ModelId::new("claude-sonnet-4"), Some(provider()))
+            .await;
+        let expected = TokenCount::Actual(42);
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_count_tokens_falls_back_to_tokenizer() {
+        let fixture = ForgeTokenCounter::new(Arc::new(MockChatRepository(None)));
+        let context = Context::default().add_message(ContextMessage::user("hello world", None));
+
+        let actual = fixture
+            .count_tokens(&context, &ModelId::new("gpt-4"), Some(provider()))
+            .await;
+        let expected = TokenCount::Approx(2);
+        assert_eq!(actual, expected);
+    }
+}
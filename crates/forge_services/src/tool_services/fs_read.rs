@@ -8,6 +8,7 @@ use forge_app::{
 };
 use forge_domain::{FileInfo, Image};
 
+use crate::outline::outline;
 use crate::range::resolve_range;
 use crate::utils::assert_absolute_path;
 
@@ -202,6 +203,39 @@ impl<F: FileInfoInfra + EnvironmentInfra<Config = forge_config::ForgeConfig> + I
 
         Ok(ReadOutput { content: Content::file(content), info: file_info })
     }
+
+    async fn outline(&self, path: String) -> anyhow::Result<ReadOutput> {
+        let path = Path::new(&path);
+        assert_absolute_path(path)?;
+
+        let config = self.infra.get_config()?;
+        assert_file_size(&*self.infra, path, config.max_file_size_bytes).await?;
+
+        let raw_content = self
+            .infra
+            .read(path)
+            .await
+            .with_context(|| format!("Failed to read file from {}", path.display()))?;
+        let full_content = String::from_utf8(raw_content)
+            .with_context(|| format!("Failed to read file as UTF-8 from {}", path.display()))?;
+
+        let hash = compute_hash(&full_content);
+        let total_lines = full_content.lines().count() as u64;
+
+        // The outline skips lines, so each line carries its own number
+        let content = outline(&full_content)
+            .into_iter()
+            .map(|(number, line)| {
+                format!("{number}:{}", truncate_line(line, config.max_line_chars))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Ok(ReadOutput {
+            content: Content::file(content),
+            info: FileInfo::new(1, total_lines, total_lines, hash),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -7,6 +7,7 @@ use forge_app::{
     WalkerInfra,
 };
 use forge_domain::{FSSearch, OutputMode};
+use grep_matcher::Matcher;
 use grep_regex::RegexMatcherBuilder;
 use grep_searcher::sinks::UTF8;
 use grep_searcher::{Searcher, SearcherBuilder, Sink, SinkContext, SinkContextKind, SinkMatch};
@@ -63,6 +64,7 @@ impl<W: WalkerInfra + FileReaderInfra + FileInfoInfra> FsSearchService for Forge
             }
             OutputMode::Content => self.search_content(&file_paths, &matcher, &params).await?,
             OutputMode::Count => self.search_count(&file_paths, &matcher).await?,
+            OutputMode::Json => self.search_json(&file_paths, &matcher, &params).await?,
         };
 
         if matches.is_empty() {
@@ -148,15 +150,11 @@ impl<W: WalkerInfra + FileReaderInfra + FileInfoInfra> ForgeFsSearch<W> {
             .await
             .with_context(|| format!("Failed to walk directory '{}'", dir.display()))?;
 
-        let mut paths = Vec::new();
-        for file in walked_files {
-            let path = dir.join(file.path);
-            if self.infra.is_file(&path).await? {
-                paths.push(path);
-            }
-        }
-
-        Ok(paths)
+        Ok(walked_files
+            .into_iter()
+            .filter(|file| !file.is_dir())
+            .map(|file| dir.join(file.path))
+            .collect())
     }
 
     /// Checks if a file matches the glob and type filters
@@ -489,6 +487,57 @@ impl<W: WalkerInfra + FileReaderInfra + FileInfoInfra> ForgeFsSearch<W> {
 
         Ok(all_matches)
     }
+
+    /// Searches files and returns each match with its line, column and
+    /// context
+    async fn search_json(
+        &self,
+        paths: &[PathBuf],
+        matcher: &grep_regex::RegexMatcher,
+        params: &FSSearch,
+    ) -> anyhow::Result<Vec<Match>> {
+        // Line numbers are part of every located match
+        let params = FSSearch { show_line_numbers: Some(true), ..params.clone() };
+        let matches = self.search_content(paths, matcher, &params).await?;
+
+        matches
+            .into_iter()
+            .map(|matched| {
+                let (line_number, line, before_context, after_context) = match matched.result {
+                    Some(MatchResult::Found { line_number, line }) => {
+                        (line_number, line, Vec::new(), Vec::new())
+                    }
+                    Some(MatchResult::ContextMatch {
+                        line_number,
+                        line,
+                        before_context,
+                        after_context,
+                    }) => (line_number, line, before_context, after_context),
+                    result => return Ok(Match { path: matched.path, result }),
+                };
+
+                // Multiline matches may not match the first line alone
+                let (column, text) = match matcher.find(line.as_bytes())? {
+                    Some(found) => (
+                        found.start() + 1,
+                        line[found.start()..found.end()].to_string(),
+                    ),
+                    None => (1, line.clone()),
+                };
+                Ok(Match {
+                    path: matched.path,
+                    result: Some(MatchResult::Located {
+                        line_number: line_number.unwrap_or_default(),
+                        column,
+                        matched: text,
+                        line,
+                        before_context,
+                        after_context,
+                    }),
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -590,9 +639,15 @@ mod test {
                             .to_string_lossy()
                             .to_string();
                         let file_name = path.file_name().map(|n| n.to_string_lossy().to_string());
-                        let size = entry.metadata().await?.len();
-
-                        files.push(WalkedFile { path: relative_path, file_name, size });
+                        let metadata = entry.metadata().await?;
+
+                        files.push(WalkedFile {
+                            path: relative_path,
+                            file_name,
+                            size: metadata.len(),
+                            modified: metadata.modified().ok(),
+                            is_binary: false,
+                        });
                     }
                 }
             }
@@ -943,6 +998,52 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_json_mode_locates_matches() {
+        let fixture = TempDir::new().unwrap();
+        fs::write(
+            fixture.path().join("test.txt"),
+            "line 1\nlet value = MATCH;\nline 3",
+        )
+        .await
+        .unwrap();
+
+        let params = FSSearch {
+            pattern: "MATCH".to_string(),
+            path: Some(fixture.path().to_string_lossy().to_string()),
+            output_mode: Some(OutputMode::Json),
+            after_context: Some(1),
+            show_line_numbers: Some(false),
+            ..Default::default()
+        };
+
+        let actual = ForgeFsSearch::new(Arc::new(MockInfra::default()))
+            .search(params)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(actual.matches.len(), 1);
+        match &actual.matches[0].result {
+            Some(MatchResult::Located {
+                line_number,
+                column,
+                matched,
+                line,
+                before_context,
+                after_context,
+            }) => {
+                assert_eq!(line_number, &2);
+                assert_eq!(column, &13);
+                assert_eq!(matched, "MATCH");
+                assert_eq!(line, "let value = MATCH;");
+                assert!(before_context.is_empty());
+                assert_eq!(after_context, &vec!["line 3".to_string()]);
+            }
+            _ => panic!("Expected Located, got {:?}", actual.matches[0].result),
+        }
+    }
+
     #[tokio::test]
     async fn test_before_context_only() {
         let fixture = TempDir::new().unwrap();
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use forge_domain::SnapshotTag;
+use serde::{Deserialize, Serialize};
+
+/// Entry of a bundle listing the files it holds
+pub(crate) const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// Index of the entries of a snapshot bundle
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct BundleManifest {
+    pub files: Vec<BundledFile>,
+}
+
+/// A file of a snapshot bundle, with its states before each agent edit and
+/// at export
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BundledFile {
+    /// Path relative to the directory the bundle was exported from
+    pub path: PathBuf,
+    /// Snapshots taken before each agent edit, from the oldest
+    pub snapshots: Vec<BundledSnapshot>,
+    /// Entry holding the content at export, absent when the file was removed
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BundledSnapshot {
+    /// Time the snapshot was taken, as a duration since the Unix epoch
+    pub timestamp: Duration,
+    /// Entry holding the content of the snapshot
+    pub entry: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<SnapshotTag>,
+}
+
+/// Packs named entries into a zstd compressed tar archive
+pub(crate) fn pack(entries: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, content) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, name, content.as_slice())?;
+    }
+    let archive = builder.into_inner()?;
+    Ok(zstd::encode_all(archive.as_slice(), 0)?)
+}
+
+/// Reads the entries of an archive written by [`pack`], by name
+pub(crate) fn unpack(bundle: &[u8]) -> Result<HashMap<String, Vec<u8>>> {
+    let archive = zstd::decode_all(bundle).context("Not a zstd compressed archive")?;
+    let mut entries = HashMap::new();
+    for entry in tar::Archive::new(archive.as_slice()).entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        entries.insert(name, content);
+    }
+    Ok(entries)
+}
@@ -1,4 +1,5 @@
 // Export the modules
+mod bundle;
 mod service;
 
 // Re-export the SnapshotInfo struct and SnapshotId
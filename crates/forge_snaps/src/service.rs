@@ -1,37 +1,547 @@
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
-use forge_domain::Snapshot;
+use forge_domain::{ConversationId, Snapshot, SnapshotId, SnapshotTag};
 use forge_fs::ForgeFS;
 
+use crate::bundle::{self, BundleManifest, BundledFile, BundledSnapshot, MANIFEST_ENTRY};
+
+/// Directory of the snapshot store holding the content-addressed blobs
+pub const BLOBS_DIRECTORY: &str = "blobs";
+
+/// File in each snapshot directory recording the path of the file its
+/// snapshots were taken of. Together these files index the snapshots of every
+/// file without a shared index that concurrent processes would contend over.
+const PATH_FILE: &str = "path";
+
+/// Magic number starting every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
 /// Implementation of the SnapshotService
+///
+/// Each snapshot file is a hard link to a blob named after the blake3 hash of
+/// its content, so identical file states share one copy on disk. The link
+/// count of a blob counts its snapshots, and a blob is removed with its last
+/// snapshot. Platforms without link counts store full copies instead.
+///
+/// Blobs can be stored compressed with zstd. Whether a blob is compressed is
+/// read from its first bytes, so snapshots written before compression was
+/// enabled, or while it was disabled, stay readable.
 #[derive(Debug)]
 pub struct SnapshotService {
     /// Base directory for storing snapshots
     snapshots_directory: PathBuf,
+    /// Whether new blobs are compressed
+    compress: bool,
 }
 
 impl SnapshotService {
     /// Create a new FileSystemSnapshotService with a specific home path
     pub fn new(snapshot_base_dir: PathBuf) -> Self {
-        Self { snapshots_directory: snapshot_base_dir }
+        Self { snapshots_directory: snapshot_base_dir, compress: false }
+    }
+
+    /// Compresses the blobs of new snapshots with zstd
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
     }
 }
 
 impl SnapshotService {
     pub async fn create_snapshot(&self, path: PathBuf) -> Result<Snapshot> {
         let snapshot = Snapshot::create(path)?;
+        let snapshot_path = self.prepare(&snapshot).await?;
+        let content = ForgeFS::read(&snapshot.path).await?;
+        self.store(&content, &snapshot_path).await?;
+        Ok(snapshot)
+    }
 
-        // Create intermediary directories if they don't exist
+    /// Returns where `snapshot` is stored, creating its directory along with
+    /// the file recording the path it was taken of
+    async fn prepare(&self, snapshot: &Snapshot) -> Result<PathBuf> {
         let snapshot_path = snapshot.snapshot_path(Some(self.snapshots_directory.clone()));
-        if let Some(parent) = PathBuf::from(&snapshot_path).parent() {
+        if let Some(parent) = snapshot_path.parent() {
             ForgeFS::create_dir_all(parent).await?;
+            let path_file = parent.join(PATH_FILE);
+            if !ForgeFS::exists(&path_file) {
+                ForgeFS::write(&path_file, snapshot.path.as_bytes()).await?;
+            }
         }
+        Ok(snapshot_path)
+    }
 
-        let content = ForgeFS::read(&snapshot.path).await?;
-        let path = snapshot.snapshot_path(Some(self.snapshots_directory.clone()));
-        ForgeFS::write(path, content).await?;
-        Ok(snapshot)
+    fn blob_path(&self, content: &[u8]) -> PathBuf {
+        blob_path_in(&self.snapshots_directory, content)
+    }
+
+    /// Encodes `content` the way blobs are stored. Content that happens to
+    /// start like a zstd frame is compressed even when compression is
+    /// disabled, so that it can't be mistaken for a compressed blob.
+    fn encode(&self, content: &[u8]) -> Result<Vec<u8>> {
+        if self.compress || content.starts_with(&ZSTD_MAGIC) {
+            Ok(zstd::encode_all(content, 0)?)
+        } else {
+            Ok(content.to_vec())
+        }
+    }
+
+    /// Reads the content of a snapshot, decompressing it if it was stored
+    /// compressed
+    async fn read_stored(snapshot_path: &Path) -> Result<Vec<u8>> {
+        let stored = ForgeFS::read(snapshot_path).await?;
+        if !stored.starts_with(&ZSTD_MAGIC) {
+            return Ok(stored);
+        }
+        zstd::decode_all(stored.as_slice())
+            .with_context(|| format!("Failed to decompress {}", snapshot_path.display()))
+    }
+
+    /// Stores `content` at `snapshot_path` as a hard link to its blob, falling
+    /// back to a full copy when the link can't be created
+    async fn store(&self, content: &[u8], snapshot_path: &Path) -> Result<()> {
+        let stored = self.encode(content)?;
+        if cfg!(unix) {
+            let blob = self.blob_path(content);
+            if !ForgeFS::exists(&blob) {
+                ForgeFS::create_dir_all(self.snapshots_directory.join(BLOBS_DIRECTORY)).await?;
+                // Renaming a complete file into place keeps concurrent snapshots
+                // from linking to a partially written blob
+                let partial = blob.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+                ForgeFS::write(&partial, &stored).await?;
+                tokio::fs::rename(&partial, &blob).await?;
+            }
+            if tokio::fs::hard_link(&blob, snapshot_path).await.is_ok() {
+                return Ok(());
+            }
+        }
+        ForgeFS::write(snapshot_path, stored).await
+    }
+
+    /// Removes the blob of `content` once no snapshot links to it anymore
+    async fn release(&self, content: &[u8]) -> Result<()> {
+        release_blob(&self.blob_path(content)).await
+    }
+
+    /// Moves the snapshot directory `source` of another snapshot store into
+    /// this store, merging it with the directory of the same file if there is
+    /// one. Its snapshots are linked to this store's blobs, and the blobs of
+    /// the other store that no snapshot links to anymore are removed. Returns
+    /// the number of moved snapshots.
+    pub async fn adopt(&self, source: &Path) -> Result<usize> {
+        let name = source
+            .file_name()
+            .with_context(|| format!("Invalid snapshot directory {}", source.display()))?;
+        let source_store = source.parent().unwrap_or(Path::new(""));
+        let target = self.snapshots_directory.join(name);
+        ForgeFS::create_dir_all(&target).await?;
+
+        let mut count = 0;
+        let mut entries = ForgeFS::read_dir(source).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let destination = target.join(entry.file_name());
+            if entry.file_name().to_string_lossy().ends_with(".snap") {
+                let content = Self::read_stored(&entry.path()).await?;
+                let source_blob = blob_path_in(source_store, &content);
+                // A snapshot taken at the same time in both stores is kept once
+                if !ForgeFS::exists(&destination) {
+                    self.take_blob(&source_blob, &content).await?;
+                    self.store(&content, &destination).await?;
+                }
+                ForgeFS::remove_file(entry.path()).await?;
+                release_blob(&source_blob).await?;
+                count += 1;
+            } else if entry.file_name() == PATH_FILE && ForgeFS::exists(&destination) {
+                ForgeFS::remove_file(entry.path()).await?;
+            } else {
+                move_file(&entry.path(), &destination).await?;
+            }
+        }
+
+        tokio::fs::remove_dir(source).await?;
+        Ok(count)
+    }
+
+    /// Renames the blob of another store into this store when the snapshot
+    /// being moved is its last one and this store doesn't have it yet, so
+    /// that its content isn't written again. Blobs that other snapshots still
+    /// link to are left in place, and the snapshot is then stored as new
+    /// content, since sharing a blob between stores would keep either from
+    /// telling when the blob is unused.
+    async fn take_blob(&self, source_blob: &Path, content: &[u8]) -> Result<()> {
+        let blob = self.blob_path(content);
+        let Ok(metadata) = tokio::fs::metadata(source_blob).await else {
+            return Ok(());
+        };
+        // The blob itself and the snapshot being moved
+        if link_count(&metadata) != Some(2) || ForgeFS::exists(&blob) {
+            return Ok(());
+        }
+        ForgeFS::create_dir_all(self.snapshots_directory.join(BLOBS_DIRECTORY)).await?;
+        // Renaming fails across filesystems, where the content is written anew
+        let _ = tokio::fs::rename(source_blob, &blob).await;
+        Ok(())
+    }
+
+    /// Removes the snapshots created more than `age` ago along with the blobs
+    /// no remaining snapshot links to. Returns the number of removed
+    /// snapshots.
+    pub async fn purge_older_than(&self, age: Duration) -> Result<usize> {
+        if !ForgeFS::exists(&self.snapshots_directory) {
+            return Ok(0);
+        }
+        let cutoff = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .saturating_sub(age);
+
+        let mut removed = 0;
+        let mut dirs = ForgeFS::read_dir(&self.snapshots_directory).await?;
+        while let Some(dir) = dirs.next_entry().await? {
+            if dir.file_name() == BLOBS_DIRECTORY || !dir.file_type().await?.is_dir() {
+                continue;
+            }
+            let mut files = ForgeFS::read_dir(dir.path()).await?;
+            let mut remaining = 0;
+            while let Some(file) = files.next_entry().await? {
+                // Tags are removed along with their snapshot
+                if file.file_name() == PATH_FILE || file.path().extension() == Some("tag".as_ref())
+                {
+                    continue;
+                }
+                match Snapshot::timestamp_of(&file.path()) {
+                    Some(timestamp) if timestamp < cutoff => {
+                        ForgeFS::remove_file(file.path()).await?;
+                        Self::remove_tag(&file.path()).await?;
+                        removed += 1;
+                    }
+                    _ => remaining += 1,
+                }
+            }
+            if remaining == 0 {
+                let path_file = dir.path().join(PATH_FILE);
+                if ForgeFS::exists(&path_file) {
+                    ForgeFS::remove_file(&path_file).await?;
+                }
+                tokio::fs::remove_dir(dir.path()).await?;
+            }
+        }
+
+        let blobs = self.snapshots_directory.join(BLOBS_DIRECTORY);
+        if ForgeFS::exists(&blobs) {
+            let mut entries = ForgeFS::read_dir(&blobs).await?;
+            while let Some(blob) = entries.next_entry().await? {
+                if link_count(&blob.metadata().await?) == Some(1) {
+                    ForgeFS::remove_file(blob.path()).await?;
+                }
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Lists the snapshots stored for `path`, from the oldest to the most
+    /// recent
+    async fn list_snapshots(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let snapshot = Snapshot::create(path.to_path_buf())?;
+        let snapshot_dir = self.snapshots_directory.join(snapshot.path_hash());
+        if !ForgeFS::exists(&snapshot_dir) {
+            return Ok(Vec::new());
+        }
+        Self::snapshots_in(&snapshot_dir).await
+    }
+
+    /// Lists the snapshots in a snapshot directory, from the oldest to the
+    /// most recent
+    async fn snapshots_in(snapshot_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut snapshots = Vec::new();
+        let mut dir = ForgeFS::read_dir(snapshot_dir).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            if entry.file_name().to_string_lossy().ends_with(".snap") {
+                snapshots.push(entry.path());
+            }
+        }
+        // File names start with the timestamp, so they sort chronologically
+        snapshots.sort();
+        Ok(snapshots)
+    }
+
+    /// Reads the content of a snapshot of `path`, numbered from 0 for the
+    /// oldest
+    pub async fn read_snapshot(&self, path: PathBuf, index: usize) -> Result<Vec<u8>> {
+        let snapshots = self.list_snapshots(&path).await?;
+        let snapshot = snapshots.get(index).with_context(|| {
+            format!(
+                "Snapshot {index} of {path:?} not found, it has {} snapshot(s)",
+                snapshots.len()
+            )
+        })?;
+        Self::read_stored(snapshot).await
+    }
+
+    /// Reads the tag of a snapshot, if it has one
+    async fn read_tag(snapshot_path: &Path) -> Result<Option<SnapshotTag>> {
+        let tag_path = Snapshot::tag_path(snapshot_path);
+        if !ForgeFS::exists(&tag_path) {
+            return Ok(None);
+        }
+        let tag = serde_json::from_slice(&ForgeFS::read(&tag_path).await?)
+            .with_context(|| format!("Invalid snapshot tag {}", tag_path.display()))?;
+        Ok(Some(tag))
+    }
+
+    /// Removes the tag of a snapshot, if it has one
+    async fn remove_tag(snapshot_path: &Path) -> Result<()> {
+        let tag_path = Snapshot::tag_path(snapshot_path);
+        if ForgeFS::exists(&tag_path) {
+            ForgeFS::remove_file(&tag_path).await?;
+        }
+        Ok(())
+    }
+
+    /// Tags the snapshots of `path` taken at or after `since`, a duration
+    /// since the Unix epoch, with the agent turn that triggered them.
+    /// Snapshots that already carry a tag keep it. Returns the number of
+    /// tagged snapshots.
+    pub async fn tag_snapshots_since(
+        &self,
+        path: PathBuf,
+        since: Duration,
+        tag: &SnapshotTag,
+    ) -> Result<usize> {
+        let content = serde_json::to_vec(tag)?;
+        let mut tagged = 0;
+        for snapshot in self.list_snapshots(&path).await? {
+            let tag_path = Snapshot::tag_path(&snapshot);
+            if Snapshot::timestamp_of(&snapshot).is_some_and(|taken| taken >= since)
+                && !ForgeFS::exists(&tag_path)
+            {
+                ForgeFS::write(&tag_path, &content).await?;
+                tagged += 1;
+            }
+        }
+        Ok(tagged)
+    }
+
+    /// Lists the snapshots taken before the edits of a conversation, from the
+    /// oldest to the most recent
+    pub async fn list_snapshots_for_conversation(
+        &self,
+        conversation_id: &ConversationId,
+    ) -> Result<Vec<Snapshot>> {
+        let mut snapshots = Vec::new();
+        for (path, dir) in self.snapshotted_files().await? {
+            for snapshot_path in Self::snapshots_in(&dir).await? {
+                let Some(tag) = Self::read_tag(&snapshot_path).await? else {
+                    continue;
+                };
+                let Some(timestamp) = Snapshot::timestamp_of(&snapshot_path) else {
+                    continue;
+                };
+                if tag.conversation_id == *conversation_id {
+                    snapshots.push(Snapshot {
+                        id: SnapshotId::new(),
+                        timestamp,
+                        path: path.display().to_string(),
+                        conversation_id: Some(tag.conversation_id),
+                        tool_call_id: tag.tool_call_id,
+                    });
+                }
+            }
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.timestamp);
+        Ok(snapshots)
+    }
+
+    /// Lists the files with snapshots along with their snapshot directories.
+    /// Directories from before files recorded their path are left out.
+    async fn snapshotted_files(&self) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let mut files = Vec::new();
+        if !ForgeFS::exists(&self.snapshots_directory) {
+            return Ok(files);
+        }
+        let mut dirs = ForgeFS::read_dir(&self.snapshots_directory).await?;
+        while let Some(dir) = dirs.next_entry().await? {
+            let path_file = dir.path().join(PATH_FILE);
+            if dir.file_name() == BLOBS_DIRECTORY || !ForgeFS::exists(&path_file) {
+                continue;
+            }
+            let path = String::from_utf8(ForgeFS::read(&path_file).await?)
+                .with_context(|| format!("Invalid path in {}", path_file.display()))?;
+            files.push((PathBuf::from(path), dir.path()));
+        }
+        Ok(files)
+    }
+
+    /// Restores every file the agent changed since `timestamp`, a duration
+    /// since the Unix epoch, to its content at that time.
+    ///
+    /// A snapshot holds the content of a file from before an edit, so each
+    /// file gets the content of its first snapshot taken at or after
+    /// `timestamp`. Files without such a snapshot weren't changed since and
+    /// are left alone, as are files created since. The current content of a
+    /// restored file is snapshotted first, so that the restore can be undone.
+    /// Returns the restored files.
+    pub async fn restore_all_at(&self, timestamp: Duration) -> Result<Vec<PathBuf>> {
+        let mut restored = Vec::new();
+        for (path, dir) in self.snapshotted_files().await? {
+            let snapshot = Self::snapshots_in(&dir)
+                .await?
+                .into_iter()
+                .find(|snapshot| {
+                    Snapshot::timestamp_of(snapshot).is_some_and(|taken| taken >= timestamp)
+                });
+            let Some(snapshot) = snapshot else {
+                continue;
+            };
+
+            let content = Self::read_stored(&snapshot).await?;
+            if ForgeFS::exists(&path) {
+                if ForgeFS::read(&path).await? == content {
+                    continue;
+                }
+                self.create_snapshot(path.clone()).await?;
+            } else if let Some(parent) = path.parent() {
+                ForgeFS::create_dir_all(parent).await?;
+            }
+            ForgeFS::write(&path, content).await?;
+            restored.push(path);
+        }
+        restored.sort();
+        Ok(restored)
+    }
+
+    /// Writes a bundle of the files at `paths` to `output`, a zstd compressed
+    /// tar archive holding their snapshots and their current content, so that
+    /// their edits can be reviewed and applied on another machine with
+    /// [`Self::import_bundle`]. Paths are recorded relative to `root`.
+    /// Returns the number of bundled snapshots.
+    pub async fn export_bundle(
+        &self,
+        paths: &[PathBuf],
+        root: &Path,
+        output: &Path,
+    ) -> Result<usize> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let mut manifest = BundleManifest::default();
+        let mut entries = Vec::new();
+        for (index, path) in paths.iter().enumerate() {
+            let path = PathBuf::from(Snapshot::create(path.clone())?.path);
+            let relative = path
+                .strip_prefix(&root)
+                .with_context(|| format!("{} is outside {}", path.display(), root.display()))?
+                .to_path_buf();
+            let snapshot_paths = self.list_snapshots(&path).await?;
+            if snapshot_paths.is_empty() {
+                anyhow::bail!("No snapshots found for {}", path.display());
+            }
+
+            let mut snapshots = Vec::new();
+            for (number, snapshot_path) in snapshot_paths.iter().enumerate() {
+                let Some(timestamp) = Snapshot::timestamp_of(snapshot_path) else {
+                    continue;
+                };
+                let entry = format!("files/{index}/{number}.snap");
+                entries.push((entry.clone(), Self::read_stored(snapshot_path).await?));
+                let tag = Self::read_tag(snapshot_path).await?;
+                snapshots.push(BundledSnapshot { timestamp, entry, tag });
+            }
+            let current = if ForgeFS::exists(&path) {
+                let entry = format!("files/{index}/current");
+                entries.push((entry.clone(), ForgeFS::read(&path).await?));
+                Some(entry)
+            } else {
+                None
+            };
+            manifest
+                .files
+                .push(BundledFile { path: relative, snapshots, current });
+        }
+
+        let bundled = manifest.files.iter().map(|file| file.snapshots.len()).sum();
+        entries.push((
+            MANIFEST_ENTRY.to_string(),
+            serde_json::to_vec_pretty(&manifest)?,
+        ));
+        ForgeFS::write(output, bundle::pack(&entries)?).await?;
+        Ok(bundled)
+    }
+
+    /// Applies a bundle written by [`Self::export_bundle`] to the files under
+    /// `root`. The snapshots of the bundle join the ones of each file, so they
+    /// can be diffed and restored as usual, and each file gets its content at
+    /// export. The current content of a changed file is snapshotted first, so
+    /// that the import can be undone. Returns the changed files.
+    pub async fn import_bundle(&self, bundle: &Path, root: &Path) -> Result<Vec<PathBuf>> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let mut entries = bundle::unpack(&ForgeFS::read(bundle).await?)
+            .with_context(|| format!("Failed to read the bundle {}", bundle.display()))?;
+        let mut entry = |name: &str| {
+            entries
+                .remove(name)
+                .with_context(|| format!("Entry {name} is missing from the bundle"))
+        };
+        let manifest: BundleManifest =
+            serde_json::from_slice(&entry(MANIFEST_ENTRY)?).context("Invalid bundle manifest")?;
+
+        let mut changed = Vec::new();
+        for file in manifest.files {
+            // Keeps a crafted bundle from writing outside the root
+            if !file
+                .path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                anyhow::bail!("Invalid path {} in the bundle", file.path.display());
+            }
+            let path = root.join(&file.path);
+
+            for bundled in file.snapshots {
+                let content = entry(&bundled.entry)?;
+                let snapshot = Snapshot {
+                    timestamp: bundled.timestamp,
+                    ..Snapshot::create(path.clone())?
+                };
+                let snapshot_path = self.prepare(&snapshot).await?;
+                // Imported before
+                if ForgeFS::exists(&snapshot_path) {
+                    continue;
+                }
+                self.store(&content, &snapshot_path).await?;
+                if let Some(tag) = bundled.tag {
+                    ForgeFS::write(
+                        Snapshot::tag_path(&snapshot_path),
+                        serde_json::to_vec(&tag)?,
+                    )
+                    .await?;
+                }
+            }
+
+            let current = file.current.map(|name| entry(&name)).transpose()?;
+            match current {
+                Some(content) => {
+                    if ForgeFS::exists(&path) {
+                        if ForgeFS::read(&path).await? == content {
+                            continue;
+                        }
+                        self.create_snapshot(path.clone()).await?;
+                    } else if let Some(parent) = path.parent() {
+                        ForgeFS::create_dir_all(parent).await?;
+                    }
+                    ForgeFS::write(&path, content).await?;
+                }
+                None => {
+                    if !ForgeFS::exists(&path) {
+                        continue;
+                    }
+                    self.create_snapshot(path.clone()).await?;
+                    ForgeFS::remove_file(&path).await?;
+                }
+            }
+            changed.push(path);
+        }
+        changed.sort();
+        Ok(changed)
     }
 
     /// Find the most recent snapshot for a given path based on filename
@@ -71,16 +581,62 @@ impl SnapshotService {
             .context(format!("No valid snapshots found for {path:?}"))?;
 
         // Restore the content
-        let content = ForgeFS::read(&snapshot_path).await?;
-        ForgeFS::write(&path, content).await?;
+        let content = Self::read_stored(&snapshot_path).await?;
+        ForgeFS::write(&path, &content).await?;
 
         // Remove the used snapshot
         ForgeFS::remove_file(&snapshot_path).await?;
+        Self::remove_tag(&snapshot_path).await?;
+        self.release(&content).await?;
 
         Ok(())
     }
 }
 
+/// Path of the blob of `content` in the snapshot store at `snapshots_directory`
+fn blob_path_in(snapshots_directory: &Path, content: &[u8]) -> PathBuf {
+    snapshots_directory
+        .join(BLOBS_DIRECTORY)
+        .join(blake3::hash(content).to_hex().as_str())
+}
+
+/// Removes `blob` once no snapshot links to it anymore
+async fn release_blob(blob: &Path) -> Result<()> {
+    if let Ok(metadata) = tokio::fs::metadata(blob).await
+        && link_count(&metadata) == Some(1)
+    {
+        ForgeFS::remove_file(blob).await?;
+    }
+    Ok(())
+}
+
+/// Moves `source` to `target`, copying it when they are on different
+/// filesystems
+async fn move_file(source: &Path, target: &Path) -> Result<()> {
+    if tokio::fs::rename(source, target).await.is_ok() {
+        return Ok(());
+    }
+    tokio::fs::copy(source, target)
+        .await
+        .with_context(|| format!("Failed to copy {}", source.display()))?;
+    ForgeFS::remove_file(source).await?;
+    Ok(())
+}
+
+/// Number of hard links to a file, `None` where the platform doesn't expose it
+fn link_count(metadata: &std::fs::Metadata) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(metadata.nlink())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
@@ -90,7 +646,7 @@ mod tests {
     // Test helpers
     struct TestContext {
         _temp_dir: TempDir,
-        _snapshots_dir: PathBuf,
+        snapshots_dir: PathBuf,
         test_file: PathBuf,
         service: SnapshotService,
     }
@@ -107,12 +663,7 @@ mod tests {
             let test_file = temp_path.join("test.txt");
             let service = SnapshotService::new(snapshots_dir.clone());
 
-            Ok(Self {
-                _temp_dir: temp_dir,
-                _snapshots_dir: snapshots_dir,
-                test_file,
-                service,
-            })
+            Ok(Self { _temp_dir: temp_dir, snapshots_dir, test_file, service })
         }
 
         async fn write_content(&self, content: &str) -> Result<()> {
@@ -131,6 +682,12 @@ mod tests {
         async fn undo_snapshot(&self) -> Result<()> {
             self.service.undo_snapshot(self.test_file.clone()).await
         }
+
+        fn blob_count(&self) -> usize {
+            std::fs::read_dir(self.snapshots_dir.join(BLOBS_DIRECTORY))
+                .map(|entries| entries.count())
+                .unwrap_or_default()
+        }
     }
 
     #[tokio::test]
@@ -250,4 +807,331 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_identical_snapshots_share_a_blob() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+
+        // Act
+        ctx.write_content("Same content").await?;
+        ctx.create_snapshot().await?;
+        ctx.create_snapshot().await?;
+        ctx.write_content("Other content").await?;
+        ctx.create_snapshot().await?;
+        let shared = ctx.blob_count();
+        ctx.undo_snapshot().await?;
+        ctx.undo_snapshot().await?;
+        let after_undo = ctx.blob_count();
+        ctx.undo_snapshot().await?;
+
+        // Assert
+        assert_eq!((shared, after_undo, ctx.blob_count()), (2, 1, 0));
+        assert_eq!(ctx.read_content().await?, "Same content");
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_adopt_relinks_snapshots_to_target_blobs() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        let other_file = ctx.test_file.with_file_name("other.txt");
+        ForgeFS::write(&other_file, b"Shared").await?;
+        ctx.service.create_snapshot(other_file).await?;
+        ctx.write_content("Shared").await?;
+        ctx.create_snapshot().await?;
+        ctx.create_snapshot().await?;
+        ctx.write_content("Unique").await?;
+        ctx.create_snapshot().await?;
+        let target_dir = ctx.snapshots_dir.with_file_name("target");
+        let target = SnapshotService::new(target_dir.clone());
+        let links = |dir: &Path, content: &str| {
+            std::fs::metadata(blob_path_in(dir, content.as_bytes()))
+                .ok()
+                .and_then(|metadata| link_count(&metadata))
+        };
+
+        // Act
+        let snapshot_dir = ctx
+            .snapshots_dir
+            .join(Snapshot::create(ctx.test_file.clone())?.path_hash());
+        let actual = target.adopt(&snapshot_dir).await?;
+
+        // Assert
+        assert_eq!(actual, 3);
+        assert!(!snapshot_dir.exists());
+        assert_eq!(
+            (
+                links(&target_dir, "Shared"),
+                links(&target_dir, "Unique"),
+                links(&ctx.snapshots_dir, "Shared"),
+                links(&ctx.snapshots_dir, "Unique"),
+            ),
+            (Some(3), Some(2), Some(2), None)
+        );
+        assert_eq!(
+            target.read_snapshot(ctx.test_file.clone(), 0).await?,
+            b"Shared"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_snapshot_by_index() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        ctx.write_content("Initial content").await?;
+        ctx.create_snapshot().await?;
+        ctx.write_content("Second content").await?;
+        ctx.create_snapshot().await?;
+
+        // Act
+        let first = ctx.service.read_snapshot(ctx.test_file.clone(), 0).await?;
+        let second = ctx.service.read_snapshot(ctx.test_file.clone(), 1).await?;
+        let missing = ctx.service.read_snapshot(ctx.test_file.clone(), 2).await;
+
+        // Assert
+        assert_eq!(
+            (String::from_utf8(first)?, String::from_utf8(second)?),
+            ("Initial content".to_string(), "Second content".to_string())
+        );
+        assert!(missing.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_restore_all_at() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        let other_file = ctx.test_file.with_file_name("other.txt");
+        ForgeFS::write(&other_file, b"Other before").await?;
+        ctx.service.create_snapshot(other_file.clone()).await?;
+        ForgeFS::write(&other_file, b"Other after").await?;
+        ctx.write_content("Initial content").await?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
+        ctx.create_snapshot().await?;
+        ctx.write_content("Second content").await?;
+        ctx.create_snapshot().await?;
+        ctx.write_content("Final content").await?;
+
+        // Act
+        let restored = ctx.service.restore_all_at(timestamp).await?;
+        let restored_content = ctx.read_content().await?;
+        ctx.undo_snapshot().await?;
+
+        // Assert
+        assert_eq!(restored, vec![ctx.test_file.clone()]);
+        assert_eq!(restored_content, "Initial content");
+        assert_eq!(ctx.read_content().await?, "Final content");
+        assert_eq!(ForgeFS::read(&other_file).await?, b"Other after");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_compressed_snapshots() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        let compressed = SnapshotService::new(ctx.snapshots_dir.clone()).with_compression(true);
+        ctx.write_content("Legacy content").await?;
+        ctx.create_snapshot().await?;
+        let content = "Compressed content ".repeat(100);
+        ctx.write_content(&content).await?;
+        let snapshot = compressed.create_snapshot(ctx.test_file.clone()).await?;
+
+        // Act
+        let legacy = compressed.read_snapshot(ctx.test_file.clone(), 0).await?;
+        let current = compressed.read_snapshot(ctx.test_file.clone(), 1).await?;
+        let stored = ForgeFS::read(snapshot.snapshot_path(Some(ctx.snapshots_dir.clone()))).await?;
+
+        // Assert
+        assert_eq!(
+            (String::from_utf8(legacy)?, String::from_utf8(current)?),
+            ("Legacy content".to_string(), content.clone())
+        );
+        assert!(stored.starts_with(&ZSTD_MAGIC));
+        assert!(stored.len() < content.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_snapshots_for_conversation() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        let conversation_id = ConversationId::generate();
+        let tag = |conversation_id, call_id| SnapshotTag {
+            conversation_id,
+            tool_call_id: Some(forge_domain::ToolCallId::new(call_id)),
+        };
+        ctx.write_content("Initial content").await?;
+        ctx.create_snapshot().await?;
+        let since = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        ctx.create_snapshot().await?;
+        ctx.service
+            .tag_snapshots_since(
+                ctx.test_file.clone(),
+                since,
+                &tag(conversation_id, "call_1"),
+            )
+            .await?;
+        let since = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        ctx.create_snapshot().await?;
+        ctx.service
+            .tag_snapshots_since(
+                ctx.test_file.clone(),
+                since,
+                &tag(ConversationId::generate(), "call_2"),
+            )
+            .await?;
+
+        // Act
+        let actual = ctx
+            .service
+            .list_snapshots_for_conversation(&conversation_id)
+            .await?
+            .into_iter()
+            .map(|snapshot| (snapshot.path, snapshot.tool_call_id))
+            .collect::<Vec<_>>();
+        ctx.service.purge_older_than(Duration::ZERO).await?;
+        let purged = ctx
+            .service
+            .list_snapshots_for_conversation(&conversation_id)
+            .await?;
+
+        // Assert
+        let expected = vec![(
+            ctx.test_file.display().to_string(),
+            Some(forge_domain::ToolCallId::new("call_1")),
+        )];
+        assert_eq!(actual, expected);
+        assert!(purged.is_empty());
+        assert!(!ForgeFS::exists(
+            ctx.snapshots_dir
+                .join(Snapshot::create(ctx.test_file.clone())?.path_hash())
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_import_bundle() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        let root = ctx.test_file.parent().unwrap().to_path_buf();
+        let conversation_id = ConversationId::generate();
+        ctx.write_content("Before the agent").await?;
+        let since = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        ctx.create_snapshot().await?;
+        ctx.service
+            .tag_snapshots_since(
+                ctx.test_file.clone(),
+                since,
+                &SnapshotTag { conversation_id, tool_call_id: None },
+            )
+            .await?;
+        ctx.write_content("After the agent").await?;
+        let bundle = root.join("review.tar.zst");
+
+        let other = TestContext::new().await?;
+        let other_root = other.test_file.parent().unwrap().to_path_buf();
+        other.write_content("Reviewer's copy").await?;
+
+        // Act
+        let bundled = ctx
+            .service
+            .export_bundle(std::slice::from_ref(&ctx.test_file), &root, &bundle)
+            .await?;
+        let changed = other.service.import_bundle(&bundle, &other_root).await?;
+        let reimported = other.service.import_bundle(&bundle, &other_root).await?;
+
+        // Assert
+        assert_eq!(bundled, 1);
+        assert_eq!(changed, vec![other.test_file.clone()]);
+        assert!(reimported.is_empty());
+        assert_eq!(other.read_content().await?, "After the agent");
+        let snapshots = [
+            other
+                .service
+                .read_snapshot(other.test_file.clone(), 0)
+                .await?,
+            other
+                .service
+                .read_snapshot(other.test_file.clone(), 1)
+                .await?,
+        ];
+        assert_eq!(
+            snapshots,
+            [b"Before the agent".to_vec(), b"Reviewer's copy".to_vec()]
+        );
+        let tagged = other
+            .service
+            .list_snapshots_for_conversation(&conversation_id)
+            .await?;
+        assert_eq!(tagged.len(), 1);
+        other.undo_snapshot().await?;
+        assert_eq!(other.read_content().await?, "Reviewer's copy");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_bundle_rejects_paths_outside_root() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        let root = ctx.test_file.parent().unwrap().to_path_buf();
+        let manifest = BundleManifest {
+            files: vec![BundledFile {
+                path: PathBuf::from("../escaped.txt"),
+                snapshots: vec![],
+                current: Some("files/0/current".to_string()),
+            }],
+        };
+        let bundle = root.join("crafted.tar.zst");
+        ForgeFS::write(
+            &bundle,
+            bundle::pack(&[
+                (MANIFEST_ENTRY.to_string(), serde_json::to_vec(&manifest)?),
+                ("files/0/current".to_string(), b"Escaped".to_vec()),
+            ])?,
+        )
+        .await?;
+
+        // Act
+        let actual = ctx.service.import_bundle(&bundle, &root).await;
+
+        // Assert
+        assert!(actual.is_err());
+        assert!(!ForgeFS::exists(root.parent().unwrap().join("escaped.txt")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_purge_older_than() -> Result<()> {
+        // Arrange
+        let ctx = TestContext::new().await?;
+        ctx.write_content("Initial content").await?;
+        ctx.create_snapshot().await?;
+        ctx.create_snapshot().await?;
+
+        // Act
+        let kept = ctx
+            .service
+            .purge_older_than(Duration::from_secs(3600))
+            .await?;
+        let purged = ctx.service.purge_older_than(Duration::ZERO).await?;
+
+        // Assert
+        assert_eq!((kept, purged, ctx.blob_count()), (0, 2, 0));
+        assert!(ctx.undo_snapshot().await.is_err());
+
+        Ok(())
+    }
 }
@@ -14,6 +14,10 @@ pub use progress_bar::*;
 const TICK_DURATION_MS: u64 = 60;
 const TICKS: &[&str; 10] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
+/// Hint shown after the elapsed time unless replaced with
+/// [`SpinnerManager::set_hint`]
+const DEFAULT_HINT: &str = "· Ctrl+C to pause, twice to abort";
+
 /// Formats elapsed time into a compact string representation.
 ///
 /// # Arguments
@@ -53,6 +57,7 @@ pub struct SpinnerManager<P: ConsoleWriter> {
     accumulated_elapsed: Duration,
     word_index: Option<usize>,
     message: Option<String>,
+    hint: Option<String>,
     printer: Arc<P>,
 }
 
@@ -64,6 +69,7 @@ impl<P: ConsoleWriter> SpinnerManager<P> {
             accumulated_elapsed: Duration::ZERO,
             word_index: None,
             message: None,
+            hint: None,
             printer,
         }
     }
@@ -113,7 +119,7 @@ impl<P: ConsoleWriter> SpinnerManager<P> {
                     ),
             )
             .with_message(word.green().bold().to_string())
-            .with_prefix("· Ctrl+C to interrupt");
+            .with_prefix(self.hint().to_string());
 
         // Preserve spinner tick position for visual continuity
         // The spinner has 10 tick positions cycling every 600ms (60ms per tick)
@@ -159,12 +165,26 @@ impl<P: ConsoleWriter> SpinnerManager<P> {
         Ok(())
     }
 
-    /// Resets the elapsed time to zero.
+    /// Replaces the hint shown after the elapsed time, or restores the
+    /// default one with `None`. The hint is kept across start/stop cycles.
+    pub fn set_hint(&mut self, hint: Option<&str>) {
+        self.hint = hint.map(str::to_owned);
+        if let Some(spinner) = &self.spinner {
+            spinner.set_prefix(self.hint().to_string());
+        }
+    }
+
+    fn hint(&self) -> &str {
+        self.hint.as_deref().unwrap_or(DEFAULT_HINT)
+    }
+
+    /// Resets the elapsed time to zero and restores the default hint.
     /// Call this when starting a completely new task/conversation.
     pub fn reset(&mut self) {
         self.accumulated_elapsed = Duration::ZERO;
         self.word_index = None;
         self.message = None;
+        self.hint = None;
     }
 
     /// Writes a line to stdout, suspending the spinner if active.
@@ -226,7 +246,7 @@ mod tests {
     use forge_domain::ConsoleWriter;
     use pretty_assertions::assert_eq;
 
-    use super::{SpinnerManager, format_elapsed_time};
+    use super::{DEFAULT_HINT, SpinnerManager, format_elapsed_time};
 
     /// A simple printer that writes directly to stdout/stderr.
     /// Used for testing when synchronized output is not needed.
@@ -300,6 +320,25 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[tokio::test]
+    async fn test_hint_is_kept_across_restarts_until_reset() {
+        let mut fixture_spinner = fixture_spinner();
+
+        fixture_spinner.start(None).unwrap();
+        fixture_spinner.set_hint(Some("· Ctrl+C again to abort"));
+        fixture_spinner.stop(None).unwrap();
+        fixture_spinner.start(None).unwrap();
+        let restarted = fixture_spinner.spinner.as_ref().unwrap().prefix();
+        fixture_spinner.reset();
+
+        let actual = (restarted, fixture_spinner.hint().to_string());
+        let expected = (
+            "· Ctrl+C again to abort".to_string(),
+            DEFAULT_HINT.to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
     #[tokio::test]
     async fn test_word_index_caching_behavior() {
         let mut fixture_spinner = fixture_spinner();
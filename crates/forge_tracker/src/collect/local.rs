@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use tokio::io::AsyncWriteExt;
+
+use super::super::Result;
+use super::Collect;
+use crate::Event;
+
+/// Collector that appends every event as a JSON line to a local file so it can
+/// be inspected without anything leaving the machine
+pub struct Tracker {
+    path: PathBuf,
+}
+
+impl Tracker {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait::async_trait]
+impl Collect for Tracker {
+    async fn collect(&self, event: Event) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut line = serde_json::to_string(&event)?;
+        line.push('\n');
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        // Tokio writes in the background, so the line is only on disk once
+        // flushed
+        file.flush().await?;
+        Ok(())
+    }
+}
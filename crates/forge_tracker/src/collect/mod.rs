@@ -1,5 +1,6 @@
 use crate::Event;
 
+pub mod local;
 pub mod posthog;
 
 ///
@@ -1,7 +1,8 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::process::Output;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, LazyLock};
+use std::sync::{Arc, LazyLock, RwLock};
 
 use chrono::{DateTime, Utc};
 use forge_domain::Conversation;
@@ -10,11 +11,10 @@ use tokio::process::Command;
 use tokio::sync::Mutex;
 
 use super::Result;
-use crate::can_track::can_track;
-use crate::collect::{Collect, posthog};
+use crate::collect::{Collect, local, posthog};
 use crate::event::Identity;
 use crate::rate_limit::RateLimiter;
-use crate::{Event, EventKind, client_id};
+use crate::{Event, EventKind, TelemetryMode, client_id};
 
 const POSTHOG_API_SECRET: &str = match option_env!("POSTHOG_API_SECRET") {
     Some(val) => val,
@@ -26,8 +26,6 @@ const VERSION: &str = match option_env!("APP_VERSION") {
     None => env!("CARGO_PKG_VERSION"),
 };
 
-const TRACKING_ENV_VAR_NAME: &str = "FORGE_TRACKER";
-
 // Cached system information that doesn't change during application lifetime
 static CACHED_CORES: LazyLock<usize> = LazyLock::new(|| System::physical_core_count().unwrap_or(0));
 static CACHED_CLIENT_ID: LazyLock<String> = LazyLock::new(|| {
@@ -60,7 +58,8 @@ const MAX_EVENTS_PER_MINUTE: usize = 1_000;
 #[derive(Clone)]
 pub struct Tracker {
     collectors: Arc<Vec<Box<dyn Collect>>>,
-    can_track: bool,
+    mode: Arc<RwLock<TelemetryMode>>,
+    events_path: Arc<RwLock<Option<PathBuf>>>,
     start_time: DateTime<Utc>,
     email: Arc<Mutex<Option<Vec<String>>>>,
     model: Arc<Mutex<Option<String>>>,
@@ -73,10 +72,12 @@ impl Default for Tracker {
     fn default() -> Self {
         let posthog_tracker = Box::new(posthog::Tracker::new(POSTHOG_API_SECRET));
         let start_time = Utc::now();
-        let can_track = can_track();
         Self {
             collectors: Arc::new(vec![posthog_tracker]),
-            can_track,
+            mode: Arc::new(RwLock::new(
+                TelemetryMode::resolve(TelemetryMode::default()),
+            )),
+            events_path: Arc::new(RwLock::new(None)),
             start_time,
             email: Arc::new(Mutex::new(None)),
             model: Arc::new(Mutex::new(None)),
@@ -88,6 +89,26 @@ impl Default for Tracker {
 }
 
 impl Tracker {
+    /// Applies the configured telemetry mode. The `FORGE_TRACKER` environment
+    /// variable takes precedence over `mode`. In local mode events are
+    /// appended as JSON lines to `events_path`.
+    pub fn configure(&self, mode: TelemetryMode, events_path: PathBuf) {
+        if let Ok(mut guard) = self.mode.write() {
+            *guard = TelemetryMode::resolve(mode);
+        }
+        if let Ok(mut guard) = self.events_path.write() {
+            *guard = Some(events_path);
+        }
+    }
+
+    /// Returns the effective telemetry mode
+    pub fn mode(&self) -> TelemetryMode {
+        self.mode
+            .read()
+            .map(|mode| *mode)
+            .unwrap_or(TelemetryMode::Off)
+    }
+
     pub async fn set_model<S: Into<String>>(&'static self, model: S) {
         let mut guard = self.model.lock().await;
         *guard = Some(model.into());
@@ -105,7 +126,8 @@ impl Tracker {
     }
 
     pub async fn dispatch(&self, event_kind: EventKind) -> Result<()> {
-        if !self.can_track {
+        let mode = self.mode();
+        if mode == TelemetryMode::Off {
             return Ok(());
         }
 
@@ -113,12 +135,18 @@ impl Tracker {
             return Ok(()); // Drop event if rate limit exceeded
         }
 
+        // Email addresses are only collected for remote telemetry
+        let email = match mode {
+            TelemetryMode::Remote => self.system_info().await,
+            TelemetryMode::Off | TelemetryMode::Local => vec![],
+        };
+
         // Create a new event
-        let email = self.system_info().await;
         let event = Event {
             event_name: event_kind.name(),
             event_value: event_kind.value(),
             start_time: self.start_time,
+            timestamp: Some(Utc::now()),
             cores: cores(),
             client_id: client_id(),
             os_name: os_name(),
@@ -137,9 +165,20 @@ impl Tracker {
             },
         };
 
-        // Dispatch the event to all collectors
-        for collector in self.collectors.as_ref() {
-            collector.collect(event.clone()).await?;
+        match mode {
+            TelemetryMode::Off => {}
+            TelemetryMode::Local => {
+                let path = self.events_path.read().ok().and_then(|path| path.clone());
+                if let Some(path) = path {
+                    local::Tracker::new(path).collect(event).await?;
+                }
+            }
+            TelemetryMode::Remote => {
+                // Dispatch the event to all collectors
+                for collector in self.collectors.as_ref() {
+                    collector.collect(event.clone()).await?;
+                }
+            }
         }
         Ok(())
     }
@@ -163,18 +202,8 @@ impl Tracker {
     }
 }
 
-fn tracking_enabled() -> bool {
-    std::env::var(TRACKING_ENV_VAR_NAME)
-        .map(|value| !value.eq_ignore_ascii_case("false"))
-        .unwrap_or(true)
-}
-
 // Get the email address
 async fn system_info() -> HashSet<String> {
-    if !tracking_enabled() {
-        return HashSet::new();
-    }
-
     fn parse(output: Output) -> Option<String> {
         if output.status.success() {
             let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -282,39 +311,51 @@ mod tests {
 
     static TRACKER: LazyLock<Tracker> = LazyLock::new(Tracker::default);
 
-    #[test]
-    fn test_tracking_fixture() {
-        unsafe {
-            std::env::remove_var(TRACKING_ENV_VAR_NAME);
-        }
-        let actual = tracking_enabled();
-        let expected = true;
-        assert_eq!(actual, expected);
-
-        unsafe {
-            std::env::set_var(TRACKING_ENV_VAR_NAME, "false");
-        }
-        let actual = tracking_enabled();
-        let expected = false;
-        assert_eq!(actual, expected);
-
-        unsafe {
-            std::env::set_var(TRACKING_ENV_VAR_NAME, "FALSE");
-        }
-        let actual = tracking_enabled();
-        let expected = false;
+    #[tokio::test]
+    async fn test_local_mode_appends_events_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = dir.path().join("telemetry.jsonl");
+        let tracker = Tracker::default();
+        *tracker.mode.write().unwrap() = TelemetryMode::Local;
+        *tracker.events_path.write().unwrap() = Some(fixture.clone());
+
+        tracker
+            .dispatch(EventKind::Prompt("ping".to_string()))
+            .await
+            .unwrap();
+        tracker
+            .dispatch(EventKind::Error("boom".to_string()))
+            .await
+            .unwrap();
+
+        let content = std::fs::read_to_string(&fixture).unwrap();
+        let actual: Vec<String> = content
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<Event>(line)
+                    .unwrap()
+                    .event_name
+                    .to_string()
+            })
+            .collect();
+        let expected = vec!["prompt".to_string(), "error".to_string()];
         assert_eq!(actual, expected);
+    }
 
-        unsafe {
-            std::env::set_var(TRACKING_ENV_VAR_NAME, "true");
-        }
-        let actual = tracking_enabled();
-        let expected = true;
-        assert_eq!(actual, expected);
+    #[tokio::test]
+    async fn test_off_mode_drops_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = dir.path().join("telemetry.jsonl");
+        let tracker = Tracker::default();
+        *tracker.mode.write().unwrap() = TelemetryMode::Off;
+        *tracker.events_path.write().unwrap() = Some(fixture.clone());
+
+        tracker
+            .dispatch(EventKind::Prompt("ping".to_string()))
+            .await
+            .unwrap();
 
-        unsafe {
-            std::env::remove_var(TRACKING_ENV_VAR_NAME);
-        }
+        assert!(!fixture.exists());
     }
 
     #[tokio::test]
@@ -23,6 +23,9 @@ pub enum Error {
 
     #[debug("IO Error: {}", _0)]
     IO(std::io::Error),
+
+    #[debug("OTLP Exporter Error: {}", _0)]
+    OtlpExporter(opentelemetry_otlp::ExporterBuildError),
 }
 
 pub type Result<A> = std::result::Result<A, Error>;
@@ -10,6 +10,10 @@ pub struct Event {
     pub event_name: Name,
     pub event_value: String,
     pub start_time: DateTime<Utc>,
+    /// Time the event was dispatched; absent in events recorded by older
+    /// versions
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
     pub cores: usize,
     pub client_id: String,
     pub os_name: String,
@@ -77,6 +81,7 @@ pub enum EventKind {
     Error(String),
     Trace(Vec<u8>),
     Login(Identity),
+    TaskComplete,
 }
 
 impl EventKind {
@@ -88,6 +93,7 @@ impl EventKind {
             Self::ToolCall(_) => Name::from("tool_call".to_string()),
             Self::Trace(_) => Name::from("trace".to_string()),
             Self::Login(_) => Name::from("login".to_string()),
+            Self::TaskComplete => Name::from("task_complete".to_string()),
         }
     }
     pub fn value(&self) -> String {
@@ -98,6 +104,7 @@ impl EventKind {
             Self::ToolCall(payload) => serde_json::to_string(&payload).unwrap_or_default(),
             Self::Trace(trace) => String::from_utf8_lossy(trace).to_string(),
             Self::Login(id) => id.login.to_owned(),
+            Self::TaskComplete => "".to_string(),
         }
     }
 }
@@ -5,9 +5,15 @@ mod dispatch;
 mod error;
 mod event;
 mod log;
+mod mode;
+mod otlp;
 mod rate_limit;
+mod stats;
 pub use can_track::VERSION;
 pub use dispatch::Tracker;
 use error::Result;
 pub use event::{Event, EventKind, ToolCallPayload};
 pub use log::{Guard, init_tracing};
+pub use mode::{TELEMETRY_ENV_VAR_NAME, TelemetryMode};
+pub use otlp::{OtlpExporter, OtlpGuard};
+pub use stats::{ProjectUsage, ToolUsage, UsageStats};
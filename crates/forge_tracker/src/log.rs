@@ -5,13 +5,18 @@ use tracing_appender::non_blocking::{self, WorkerGuard};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{self, Layer, filter};
 
-use crate::Tracker;
-use crate::can_track::can_track;
+use crate::{OtlpExporter, OtlpGuard, TelemetryMode, Tracker};
 
-pub fn init_tracing(log_path: PathBuf, tracker: Tracker) -> anyhow::Result<Guard> {
+/// Initializes logging and, when `otlp` is set, exports spans and token usage
+/// metrics to an OpenTelemetry collector.
+pub fn init_tracing(
+    log_path: PathBuf,
+    tracker: Tracker,
+    otlp: Option<OtlpExporter>,
+) -> anyhow::Result<Guard> {
     debug!(path = %log_path.display(), "Initializing logging system in JSON format");
 
-    // If tracking is enabled, use PostHog for logging; otherwise, use a rolling
+    // With remote telemetry, use PostHog for logging; otherwise, use a rolling
     // file appender.
     let (writer, guard, level) = prepare_writer(log_path, tracker);
 
@@ -28,12 +33,26 @@ pub fn init_tracing(log_path: PathBuf, tracker: Tracker) -> anyhow::Result<Guard
         .with_writer(writer)
         .with_filter(filter);
 
+    let (otlp_layer, otlp_guard) = otlp
+        .and_then(|otlp| {
+            otlp.start()
+                .inspect_err(|error| debug!(error = ?error, "Failed to start OTLP export"))
+                .ok()
+        })
+        .unzip();
+    let otlp_layer = otlp_layer.map(|layer| {
+        layer.with_filter(filter::filter_fn(|metadata| {
+            metadata.target().starts_with("forge_")
+        }))
+    });
+
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::try_from_env("FORGE_LOG").unwrap_or(level))
         .with(fmt_layer)
+        .with(otlp_layer)
         .init();
 
-    Ok(Guard(guard))
+    Ok(Guard { _writer: guard, _otlp: otlp_guard })
 }
 
 fn prepare_writer(
@@ -44,7 +63,7 @@ fn prepare_writer(
     WorkerGuard,
     tracing_subscriber::EnvFilter,
 ) {
-    let ((non_blocking, guard), env) = if can_track() {
+    let ((non_blocking, guard), env) = if tracker.mode() == TelemetryMode::Remote {
         let append = PostHogWriter::new(tracker);
         (
             tracing_appender::non_blocking(append),
@@ -60,7 +79,10 @@ fn prepare_writer(
     (non_blocking, guard, env)
 }
 
-pub struct Guard(#[allow(dead_code)] WorkerGuard);
+pub struct Guard {
+    _writer: WorkerGuard,
+    _otlp: Option<OtlpGuard>,
+}
 
 struct PostHogWriter {
     tracker: Tracker,
@@ -0,0 +1,89 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::can_track::can_track;
+
+/// Environment variable that overrides the configured telemetry mode
+pub const TELEMETRY_ENV_VAR_NAME: &str = "FORGE_TRACKER";
+
+/// Where telemetry events are sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TelemetryMode {
+    /// No events are recorded
+    Off,
+    /// Events are appended to a local file and never leave the machine
+    Local,
+    /// Events are sent to the telemetry service
+    #[default]
+    Remote,
+}
+
+impl TelemetryMode {
+    /// Resolves the effective mode, giving the `FORGE_TRACKER` environment
+    /// variable precedence over the configured mode. Remote telemetry is never
+    /// sent from development builds.
+    pub fn resolve(configured: TelemetryMode) -> TelemetryMode {
+        let mode = std::env::var(TELEMETRY_ENV_VAR_NAME)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(configured);
+
+        match mode {
+            TelemetryMode::Remote if !can_track() => TelemetryMode::Off,
+            mode => mode,
+        }
+    }
+}
+
+impl FromStr for TelemetryMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" | "false" => Ok(TelemetryMode::Off),
+            "local" => Ok(TelemetryMode::Local),
+            "remote" | "true" => Ok(TelemetryMode::Remote),
+            other => Err(format!(
+                "Invalid telemetry mode '{other}', expected one of: off, local, remote"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TelemetryMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelemetryMode::Off => write!(f, "off"),
+            TelemetryMode::Local => write!(f, "local"),
+            TelemetryMode::Remote => write!(f, "remote"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_accepts_legacy_boolean_values() {
+        let actual: Vec<TelemetryMode> = ["FALSE", "true", "Local", "off"]
+            .iter()
+            .map(|value| value.parse().unwrap())
+            .collect();
+        let expected = vec![
+            TelemetryMode::Off,
+            TelemetryMode::Remote,
+            TelemetryMode::Local,
+            TelemetryMode::Off,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_mode() {
+        let actual = "verbose".parse::<TelemetryMode>();
+        assert!(actual.is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{
+    MetricExporter, Protocol, SpanExporter, WithExportConfig, WithHttpConfig,
+};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider, Temporality};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::Subscriber;
+use tracing_opentelemetry::MetricsLayer;
+use tracing_subscriber::Layer;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::Result;
+use crate::can_track::VERSION;
+
+const SERVICE_NAME: &str = "forge";
+const SCOPE_NAME: &str = "forge_tracker";
+
+/// Interval at which token usage metrics are exported
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Exports tracing spans and token usage metrics to an OpenTelemetry collector
+/// using OTLP over HTTP with JSON encoding.
+///
+/// Spans are exported to `<endpoint>/v1/traces`. Events carrying a
+/// `monotonic_counter.gen_ai.client.token.usage` field are exported as
+/// `gen_ai.client.token.usage` data points to `<endpoint>/v1/metrics`.
+#[derive(Clone)]
+pub struct OtlpExporter {
+    endpoint: String,
+    headers: HashMap<String, String>,
+}
+
+impl OtlpExporter {
+    /// Creates an exporter for the collector at `endpoint`, e.g.
+    /// `http://localhost:4318`. `headers` are sent with every request, which
+    /// is typically used for authentication.
+    pub fn new(endpoint: impl Into<String>, headers: HashMap<String, String>) -> Self {
+        Self { endpoint: endpoint.into(), headers }
+    }
+
+    /// Builds the tracing layer feeding the OpenTelemetry pipelines together
+    /// with a guard that flushes pending spans and metrics on drop
+    pub(crate) fn start<S>(self) -> Result<(impl Layer<S>, OtlpGuard)>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        let resource = Resource::builder()
+            .with_service_name(SERVICE_NAME)
+            .with_attribute(KeyValue::new("service.version", VERSION))
+            .build();
+
+        let spans = SpanExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpJson)
+            .with_endpoint(self.url("v1/traces"))
+            .with_headers(self.headers.clone())
+            .with_timeout(EXPORT_TIMEOUT)
+            .build()?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(spans)
+            .with_resource(resource.clone())
+            .build();
+
+        let metrics = MetricExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpJson)
+            .with_endpoint(self.url("v1/metrics"))
+            .with_headers(self.headers.clone())
+            .with_timeout(EXPORT_TIMEOUT)
+            .with_temporality(Temporality::Delta)
+            .build()?;
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(
+                PeriodicReader::builder(metrics)
+                    .with_interval(FLUSH_INTERVAL)
+                    .build(),
+            )
+            .with_resource(resource)
+            .build();
+
+        let layer = tracing_opentelemetry::layer()
+            .with_tracer(tracer_provider.tracer(SCOPE_NAME))
+            .and_then(MetricsLayer::new(meter_provider.clone()));
+        Ok((layer, OtlpGuard { tracer_provider, meter_provider }))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{path}", self.endpoint.trim_end_matches('/'))
+    }
+}
+
+/// Flushes buffered spans and metrics and stops their export when dropped
+pub struct OtlpGuard {
+    tracer_provider: SdkTracerProvider,
+    meter_provider: SdkMeterProvider,
+}
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        if let Err(error) = self.tracer_provider.shutdown() {
+            tracing::debug!(error = ?error, "Failed to export spans");
+        }
+        if let Err(error) = self.meter_provider.shutdown() {
+            tracing::debug!(error = ?error, "Failed to export metrics");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use pretty_assertions::assert_eq;
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+
+    /// Serves a collector on a free port, returning its address and the
+    /// paths and bodies of the requests it received
+    fn collector_fixture() -> (String, mpsc::Receiver<(String, String)>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                let _ = request.as_reader().read_to_string(&mut body);
+                let _ = sender.send((request.url().to_string(), body));
+                let _ = request.respond(tiny_http::Response::empty(200));
+            }
+        });
+        (address, receiver)
+    }
+
+    #[test]
+    fn test_exports_spans_and_token_usage() {
+        let (endpoint, requests) = collector_fixture();
+        let (layer, guard) = OtlpExporter::new(endpoint, HashMap::new()).start().unwrap();
+
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("provider_request", gen_ai.request.model = "gpt-5");
+            let _entered = span.enter();
+            tracing::info!(
+                monotonic_counter.gen_ai.client.token.usage = 100u64,
+                gen_ai.token.type = "input",
+            );
+        });
+        drop(guard);
+
+        let mut actual = requests
+            .try_iter()
+            .map(|(path, body)| {
+                let name = if path == "/v1/traces" {
+                    "provider_request"
+                } else {
+                    "gen_ai.client.token.usage"
+                };
+                (path, body.contains(name))
+            })
+            .collect::<Vec<_>>();
+        actual.sort();
+
+        let expected = vec![
+            ("/v1/metrics".to_string(), true),
+            ("/v1/traces".to_string(), true),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::{Event, Result};
+
+/// Number of calls and failures of a single tool
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolUsage {
+    pub name: String,
+    pub calls: usize,
+    pub failures: usize,
+}
+
+impl ToolUsage {
+    /// Share of calls that failed, between 0 and 1
+    pub fn failure_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.calls as f64
+        }
+    }
+}
+
+/// Number of prompts sent from a single working directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectUsage {
+    pub path: String,
+    pub prompts: usize,
+}
+
+/// Usage statistics aggregated from locally recorded telemetry events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageStats {
+    pub events: usize,
+    pub prompts: usize,
+    pub errors: usize,
+    /// Tools ordered by number of calls, most used first
+    pub tools: Vec<ToolUsage>,
+    /// Working directories ordered by number of prompts, busiest first
+    pub projects: Vec<ProjectUsage>,
+    /// Average time between a prompt and the completion of its task
+    pub average_turn_latency: Option<Duration>,
+}
+
+impl UsageStats {
+    /// Reads the JSON lines written by local telemetry and aggregates them,
+    /// skipping lines that cannot be parsed
+    pub async fn read(path: &Path) -> Result<Self> {
+        let content = tokio::fs::read_to_string(path).await?;
+        Ok(Self::from_events(content.lines().filter_map(|line| {
+            serde_json::from_str::<Event>(line).ok()
+        })))
+    }
+
+    pub fn from_events(events: impl IntoIterator<Item = Event>) -> Self {
+        let mut stats = UsageStats::default();
+        let mut tools: HashMap<String, ToolUsage> = HashMap::new();
+        let mut projects: HashMap<String, usize> = HashMap::new();
+        let mut pending_prompts: HashMap<(String, DateTime<Utc>), DateTime<Utc>> = HashMap::new();
+        let mut latencies = Vec::new();
+
+        for event in events {
+            stats.events += 1;
+            // Events of one process share the client id and start time
+            let session = (event.client_id.clone(), event.start_time);
+
+            match &*event.event_name {
+                "prompt" => {
+                    stats.prompts += 1;
+                    if let Some(cwd) = &event.cwd {
+                        *projects.entry(cwd.clone()).or_default() += 1;
+                    }
+                    if let Some(timestamp) = event.timestamp {
+                        pending_prompts.insert(session, timestamp);
+                    }
+                }
+                "task_complete" => {
+                    if let Some(prompted_at) = pending_prompts.remove(&session)
+                        && let Some(timestamp) = event.timestamp
+                        && let Ok(latency) = (timestamp - prompted_at).to_std()
+                    {
+                        latencies.push(latency);
+                    }
+                }
+                "tool_call" => {
+                    let payload: serde_json::Value =
+                        serde_json::from_str(&event.event_value).unwrap_or_default();
+                    let Some(name) = payload["tool_name"].as_str() else {
+                        continue;
+                    };
+                    let usage = tools.entry(name.to_string()).or_insert_with(|| ToolUsage {
+                        name: name.to_string(),
+                        calls: 0,
+                        failures: 0,
+                    });
+                    usage.calls += 1;
+                    if !payload["cause"].is_null() {
+                        usage.failures += 1;
+                    }
+                }
+                "error" => stats.errors += 1,
+                _ => {}
+            }
+        }
+
+        stats.tools = tools.into_values().collect();
+        stats
+            .tools
+            .sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.name.cmp(&b.name)));
+
+        stats.projects = projects
+            .into_iter()
+            .map(|(path, prompts)| ProjectUsage { path, prompts })
+            .collect();
+        stats
+            .projects
+            .sort_by(|a, b| b.prompts.cmp(&a.prompts).then_with(|| a.path.cmp(&b.path)));
+
+        if !latencies.is_empty() {
+            stats.average_turn_latency =
+                Some(latencies.iter().sum::<Duration>() / latencies.len() as u32);
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{EventKind, ToolCallPayload};
+
+    fn event_fixture(kind: EventKind, cwd: &str, seconds: i64) -> Event {
+        let start_time = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+        Event {
+            event_name: kind.name(),
+            event_value: kind.value(),
+            start_time,
+            timestamp: Some(start_time + chrono::Duration::seconds(seconds)),
+            cores: 1,
+            client_id: "client".to_string(),
+            os_name: "linux".to_string(),
+            up_time: 0,
+            path: None,
+            cwd: Some(cwd.to_string()),
+            user: "user".to_string(),
+            args: vec![],
+            version: "1.0.0".to_string(),
+            email: vec![],
+            model: None,
+            conversation: None,
+            identity: None,
+        }
+    }
+
+    #[test]
+    fn test_from_events_aggregates_usage() {
+        let fixture = vec![
+            event_fixture(EventKind::Prompt("a".to_string()), "/app", 0),
+            event_fixture(
+                EventKind::ToolCall(ToolCallPayload::new("read".to_string())),
+                "/app",
+                1,
+            ),
+            event_fixture(
+                EventKind::ToolCall(
+                    ToolCallPayload::new("shell".to_string()).with_cause("exit 1".to_string()),
+                ),
+                "/app",
+                2,
+            ),
+            event_fixture(
+                EventKind::ToolCall(ToolCallPayload::new("read".to_string())),
+                "/app",
+                3,
+            ),
+            event_fixture(EventKind::TaskComplete, "/app", 4),
+            event_fixture(EventKind::Prompt("b".to_string()), "/lib", 10),
+            event_fixture(EventKind::Error("boom".to_string()), "/lib", 11),
+            event_fixture(EventKind::TaskComplete, "/lib", 12),
+            event_fixture(EventKind::Prompt("c".to_string()), "/app", 20),
+        ];
+
+        let actual = UsageStats::from_events(fixture);
+
+        let expected = UsageStats {
+            events: 9,
+            prompts: 3,
+            errors: 1,
+            tools: vec![
+                ToolUsage { name: "read".to_string(), calls: 2, failures: 0 },
+                ToolUsage { name: "shell".to_string(), calls: 1, failures: 1 },
+            ],
+            projects: vec![
+                ProjectUsage { path: "/app".to_string(), prompts: 2 },
+                ProjectUsage { path: "/lib".to_string(), prompts: 1 },
+            ],
+            average_turn_latency: Some(Duration::from_secs(3)),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_from_events_without_events() {
+        let actual = UsageStats::from_events(vec![]);
+        let expected = UsageStats::default();
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use derive_setters::Setters;
@@ -11,6 +12,10 @@ pub struct File {
     pub path: String,
     pub file_name: Option<String>,
     pub size: u64,
+    /// Last modification time, when the platform reports one
+    pub modified: Option<SystemTime>,
+    /// Whether the file's extension marks it as binary
+    pub is_binary: bool,
 }
 
 impl File {
@@ -19,6 +24,45 @@ impl File {
     }
 }
 
+/// Order of the entries returned by a walk
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WalkOrder {
+    /// Order in which entries were visited, directories included
+    #[default]
+    Traversal,
+    /// Largest files first, directories excluded
+    LargestFirst,
+    /// Most recently modified files first, directories excluded
+    RecentlyModified,
+}
+
+/// Why an entry was left out of the walk
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Symlinks are only walked when `follow_symlinks` is enabled
+    Symlink,
+    /// The symlink's target does not exist
+    BrokenSymlink,
+    /// The symlink points to one of its own ancestors
+    SymlinkLoop,
+    /// The entry could not be read
+    Unreadable,
+}
+
+/// Entry left out of the walk, with its path relative to the base directory
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedEntry {
+    pub path: String,
+    pub reason: SkipReason,
+}
+
+/// Files found by a walk along with the entries it skipped
+#[derive(Clone, Debug, Default)]
+pub struct WalkReport {
+    pub files: Vec<File>,
+    pub skipped: Vec<SkippedEntry>,
+}
+
 #[derive(Debug, Clone, Setters)]
 pub struct Walker {
     /// Base directory to start walking from
@@ -41,6 +85,17 @@ pub struct Walker {
 
     /// Whether to skip binary files
     skip_binary: bool,
+
+    /// Whether to walk into symlinked files and directories instead of
+    /// skipping them. Symlinks pointing to one of their ancestors are always
+    /// skipped.
+    follow_symlinks: bool,
+
+    /// Order of the returned entries
+    order: WalkOrder,
+
+    /// Maximum number of entries to return, applied after ordering
+    limit: usize,
 }
 
 const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024; // 1MB
@@ -61,6 +116,9 @@ impl Walker {
             max_files: DEFAULT_MAX_FILES,
             max_total_size: DEFAULT_MAX_TOTAL_SIZE,
             skip_binary: true,
+            follow_symlinks: false,
+            order: WalkOrder::Traversal,
+            limit: usize::MAX,
         }
     }
 
@@ -76,14 +134,22 @@ impl Walker {
             max_files: usize::MAX,
             max_total_size: u64::MAX,
             skip_binary: false,
+            follow_symlinks: false,
+            order: WalkOrder::Traversal,
+            limit: usize::MAX,
         }
     }
 }
 
 impl Walker {
     pub async fn get(&self) -> Result<Vec<File>> {
+        Ok(self.report().await?.files)
+    }
+
+    /// Walks the filesystem, also reporting the entries that were skipped
+    pub async fn report(&self) -> Result<WalkReport> {
         let walker = self.clone();
-        spawn_blocking(move || walker.get_blocking())
+        spawn_blocking(move || walker.report_blocking())
             .await
             .context("Failed to spawn blocking task")?
     }
@@ -107,10 +173,50 @@ impl Walker {
     /// Blocking function to scan filesystem. Use this when you already have
     /// a runtime or want to avoid spawning a new one.
     pub fn get_blocking(&self) -> Result<Vec<File>> {
+        Ok(self.report_blocking()?.files)
+    }
+
+    /// Path of an entry relative to the base directory
+    fn relative_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.cwd)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Entry a walk error prevented from being visited, if any
+    fn skipped_by(&self, error: &ignore::Error) -> Option<SkippedEntry> {
+        match error {
+            ignore::Error::Loop { child, .. } => Some(SkippedEntry {
+                path: self.relative_path(child),
+                reason: SkipReason::SymlinkLoop,
+            }),
+            ignore::Error::WithDepth { err, .. } => self.skipped_by(err),
+            ignore::Error::WithPath { path, err } => self.skipped_by(err).or_else(|| {
+                let is_symlink = path
+                    .symlink_metadata()
+                    .is_ok_and(|metadata| metadata.file_type().is_symlink());
+                let reason = if is_symlink && !path.exists() {
+                    SkipReason::BrokenSymlink
+                } else {
+                    SkipReason::Unreadable
+                };
+                Some(SkippedEntry { path: self.relative_path(path), reason })
+            }),
+            _ => None,
+        }
+    }
+
+    /// Blocking variant of [`Walker::report`]
+    pub fn report_blocking(&self) -> Result<WalkReport> {
         let mut files = Vec::new();
+        let mut skipped = Vec::new();
         let mut total_size = 0u64;
         let mut dir_entries: HashMap<String, usize> = HashMap::new();
         let mut file_count = 0;
+        // Ordered walks visit every entry and apply the limits once ranked, so
+        // that the top-ranked entries are never cut by traversal order
+        let ordered = self.order != WalkOrder::Traversal;
 
         // TODO: Convert to async and return a stream
         let walk = WalkBuilder::new(&self.cwd)
@@ -119,14 +225,26 @@ impl Walker {
             .max_depth(Some(self.max_depth))
             // Skip files that exceed size limit
             .max_filesize(Some(self.max_file_size))
+            .follow_links(self.follow_symlinks)
             // TODO: use build_parallel() for better performance
             .build();
 
-        'walk_loop: for entry in walk.flatten() {
+        'walk_loop: for entry in walk {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(error) => {
+                    skipped.extend(self.skipped_by(&error));
+                    continue;
+                }
+            };
             let path = entry.path();
 
-            // Skip symlinks — we only process real files and directories.
-            if entry.path_is_symlink() {
+            // Symlinks are only walked into when explicitly enabled
+            if entry.path_is_symlink() && !self.follow_symlinks {
+                skipped.push(SkippedEntry {
+                    path: self.relative_path(path),
+                    reason: SkipReason::Symlink,
+                });
                 continue;
             }
 
@@ -141,7 +259,7 @@ impl Walker {
             }
 
             // Handle breadth limit
-            if let Some(parent) = path.parent() {
+            if let Some(parent) = path.parent().filter(|_| !ordered) {
                 let parent_path = parent.to_string_lossy().to_string();
                 let entry_count = dir_entries.entry(parent_path).or_insert(0);
                 *entry_count += 1;
@@ -154,24 +272,32 @@ impl Walker {
             let is_dir = path.is_dir();
 
             // Skip binary files if configured
-            if self.skip_binary && !is_dir && Self::is_likely_binary(path) {
+            let is_binary = !is_dir && Self::is_likely_binary(path);
+            if self.skip_binary && is_binary {
                 continue;
             }
 
             let metadata = match path.metadata() {
                 Ok(meta) => meta,
-                Err(_) => continue, // Skip files we can't read metadata for
+                Err(_) => {
+                    skipped.push(SkippedEntry {
+                        path: self.relative_path(path),
+                        reason: SkipReason::Unreadable,
+                    });
+                    continue;
+                }
             };
 
             let file_size = metadata.len();
+            let modified = metadata.modified().ok();
 
             // Check total size limit
-            if total_size + file_size > self.max_total_size {
+            if !ordered && total_size + file_size > self.max_total_size {
                 break 'walk_loop;
             }
 
             // Check if we've hit the file count limit (only count non-directories)
-            if !is_dir {
+            if !ordered && !is_dir {
                 file_count += 1;
                 if file_count > self.max_files {
                     break 'walk_loop;
@@ -194,14 +320,71 @@ impl Walker {
                 path_string
             };
 
-            files.push(File { path: path_string, file_name, size: file_size });
+            files.push(File {
+                path: path_string,
+                file_name,
+                size: file_size,
+                modified,
+                is_binary,
+            });
 
             if !is_dir {
                 total_size += file_size;
             }
         }
 
-        Ok(files)
+        Ok(WalkReport { files: self.arrange(files), skipped })
+    }
+
+    /// Applies the configured order and limit to the walked entries. Ordered
+    /// entries are sorted before the breadth, file count and total size limits
+    /// are applied.
+    fn arrange(&self, mut files: Vec<File>) -> Vec<File> {
+        match self.order {
+            WalkOrder::Traversal => {}
+            WalkOrder::LargestFirst => {
+                files.retain(|file| !file.is_dir());
+                files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+                files = self.within_limits(files);
+            }
+            WalkOrder::RecentlyModified => {
+                files.retain(|file| !file.is_dir());
+                files.sort_by(|a, b| {
+                    b.modified
+                        .cmp(&a.modified)
+                        .then_with(|| a.path.cmp(&b.path))
+                });
+                files = self.within_limits(files);
+            }
+        }
+        files.truncate(self.limit);
+        files
+    }
+
+    /// Keeps the highest ranked files that fit the breadth, file count and
+    /// total size limits
+    fn within_limits(&self, files: Vec<File>) -> Vec<File> {
+        let mut dir_entries: HashMap<String, usize> = HashMap::new();
+        let mut total_size = 0u64;
+        let mut kept = Vec::new();
+        for file in files {
+            let parent = Path::new(&file.path)
+                .parent()
+                .map(|parent| parent.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let entry_count = dir_entries.entry(parent).or_insert(0);
+            *entry_count += 1;
+            if *entry_count > self.max_breadth {
+                continue;
+            }
+
+            if kept.len() >= self.max_files || total_size + file.size > self.max_total_size {
+                break;
+            }
+            total_size += file.size;
+            kept.push(file);
+        }
+        kept
     }
 }
 
@@ -681,4 +864,180 @@ mod tests {
             "dangling symlinks should be excluded from walker results"
         );
     }
+
+    #[tokio::test]
+    async fn test_walker_reports_skipped_symlinks() {
+        let fixture = fixtures::Fixture::default();
+        fixture.add_file("real.txt", "content").unwrap();
+        std::os::unix::fs::symlink(
+            fixture.as_path().join("real.txt"),
+            fixture.as_path().join("link.txt"),
+        )
+        .unwrap();
+
+        let actual = Walker::max_all()
+            .cwd(fixture.as_path().to_path_buf())
+            .report()
+            .await
+            .unwrap()
+            .skipped;
+
+        let expected =
+            vec![SkippedEntry { path: "link.txt".to_string(), reason: SkipReason::Symlink }];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_walker_follows_symlinks_when_enabled() {
+        let fixture = fixtures::Fixture::default();
+        fixture.add_file("vendor/lib/index.js", "").unwrap();
+        std::os::unix::fs::symlink(
+            fixture.as_path().join("vendor/lib"),
+            fixture.as_path().join("lib"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(
+            fixture.as_path().join("ghost.txt"),
+            fixture.as_path().join("dangling.txt"),
+        )
+        .unwrap();
+
+        let actual = Walker::max_all()
+            .cwd(fixture.as_path().to_path_buf())
+            .follow_symlinks(true)
+            .report()
+            .await
+            .unwrap();
+
+        let mut actual_files: Vec<_> = actual
+            .files
+            .iter()
+            .filter(|f| !f.is_dir())
+            .map(|f| f.path.as_str())
+            .collect();
+        actual_files.sort();
+        assert_eq!(actual_files, vec!["lib/index.js", "vendor/lib/index.js"]);
+        assert_eq!(
+            actual.skipped,
+            vec![SkippedEntry {
+                path: "dangling.txt".to_string(),
+                reason: SkipReason::BrokenSymlink
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_walker_skips_symlink_loops() {
+        let fixture = fixtures::Fixture::default();
+        fixture.add_file("src/main.rs", "").unwrap();
+        std::os::unix::fs::symlink(fixture.as_path(), fixture.as_path().join("src/root")).unwrap();
+
+        let actual = Walker::max_all()
+            .cwd(fixture.as_path().to_path_buf())
+            .follow_symlinks(true)
+            .report()
+            .await
+            .unwrap();
+
+        let actual_files: Vec<_> = actual
+            .files
+            .iter()
+            .filter(|f| !f.is_dir())
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(actual_files, vec!["src/main.rs"]);
+        assert_eq!(
+            actual.skipped,
+            vec![SkippedEntry {
+                path: "src/root".to_string(),
+                reason: SkipReason::SymlinkLoop
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_walker_reports_file_metadata() {
+        let fixture =
+            fixtures::create_sized_files(&[("text.txt".into(), 10), ("binary.exe".into(), 20)])
+                .unwrap();
+
+        let actual = Walker::max_all()
+            .cwd(fixture.path().to_path_buf())
+            .order(WalkOrder::LargestFirst)
+            .get()
+            .await
+            .unwrap();
+
+        let actual: Vec<_> = actual
+            .iter()
+            .map(|f| (f.path.as_str(), f.size, f.is_binary, f.modified.is_some()))
+            .collect();
+        let expected = vec![
+            ("binary.exe", 20, true, true),
+            ("text.txt", 10, false, true),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_ordered_walk_ranks_before_applying_limits() {
+        let fixture = fixtures::create_sized_files(&[
+            ("a.txt".into(), 10),
+            ("b.txt".into(), 20),
+            ("z.txt".into(), 30),
+        ])
+        .unwrap();
+
+        let actual = Walker::max_all()
+            .cwd(fixture.path().to_path_buf())
+            .order(WalkOrder::LargestFirst)
+            .max_files(1)
+            .get()
+            .await
+            .unwrap();
+
+        let actual: Vec<_> = actual.iter().map(|f| f.path.as_str()).collect();
+        let expected = vec!["z.txt"];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_arrange_orders_and_limits_files() {
+        let file = |path: &str, size: u64, modified: u64| File {
+            path: path.to_string(),
+            file_name: None,
+            size,
+            modified: Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(modified)),
+            is_binary: false,
+        };
+        let fixture = vec![
+            file("src/", 4096, 30),
+            file("src/old.rs", 300, 10),
+            file("src/new.rs", 100, 20),
+            file("src/mid.rs", 200, 15),
+        ];
+
+        let largest = Walker::max_all()
+            .order(WalkOrder::LargestFirst)
+            .limit(2)
+            .arrange(fixture.clone());
+        let recent = Walker::max_all()
+            .order(WalkOrder::RecentlyModified)
+            .arrange(fixture.clone());
+        let traversal = Walker::max_all().limit(1).arrange(fixture.clone());
+        let bounded = Walker::max_all()
+            .order(WalkOrder::LargestFirst)
+            .max_breadth(2)
+            .max_total_size(450)
+            .arrange(fixture);
+
+        let paths = |files: Vec<File>| files.into_iter().map(|f| f.path).collect::<Vec<_>>();
+        assert_eq!(paths(largest), vec!["src/old.rs", "src/mid.rs"]);
+        assert_eq!(paths(bounded), vec!["src/old.rs"]);
+        assert_eq!(
+            paths(recent),
+            vec!["src/new.rs", "src/mid.rs", "src/old.rs"]
+        );
+        assert_eq!(paths(traversal), vec!["src/"]);
+    }
 }
@@ -98,6 +98,20 @@ start=$(date +%s%N)
 $FORGE_BIN list skill --porcelain 2>&1 | head -20 || echo "No skills found"
 print_runtime "$start"
 
+# Template overrides list
+print_section "TEMPLATES"
+print_command "$FORGE_BIN list template --porcelain"
+start=$(date +%s%N)
+$FORGE_BIN list template --porcelain 2>&1 | head -20 || echo "No templates overridden"
+print_runtime "$start"
+
+# Profiles list
+print_section "PROFILES"
+print_command "$FORGE_BIN list profile --porcelain"
+start=$(date +%s%N)
+$FORGE_BIN list profile --porcelain 2>&1 | head -20 || echo "No profiles defined"
+print_runtime "$start"
+
 # Summary
 print_section "SUMMARY"
 echo "List types WITH \$ID column:"